@@ -15,6 +15,10 @@ use crate::passkey::{decrypt_chunks, encrypt_chunks};
 /// ensure some amount of security.
 // 180 days
 const REFRESH_TOKEN_EXPIRATION: u64 = 15552000;
+/// The amount of time that a successful game ownership check can be reused for when
+/// the entitlement API fails
+// 14 days
+const OWNERSHIP_CHECK_EXPIRATION: u64 = 1209600;
 
 /// A handle to the authentication database where things like credentials are stored
 pub struct AuthDatabase {
@@ -156,6 +160,9 @@ pub struct DatabaseAccount {
 	pub sensitive: SensitiveAccountInfoSerialized,
 	/// Passkey information for the account
 	pub passkey: Option<PasskeyInfo>,
+	/// When the account was last confirmed to own the game, as a UTC timestamp in seconds
+	#[serde(default)]
+	pub ownership_checked: Option<u64>,
 }
 
 impl DatabaseAccount {
@@ -174,6 +181,7 @@ impl DatabaseAccount {
 			expires,
 			sensitive: SensitiveAccountInfoSerialized::Encrypted(Vec::new()),
 			passkey: None,
+			ownership_checked: None,
 		};
 		out.set_sensitive_info(sensitive)
 			.context("Failed to set sensitive information for account in database")?;
@@ -185,6 +193,19 @@ impl DatabaseAccount {
 		self.passkey.is_some()
 	}
 
+	/// Checks if the account has successfully passed a game ownership check recently enough
+	/// that the result can still be used
+	pub fn has_recent_ownership_check(&self) -> bool {
+		let Some(checked) = self.ownership_checked else {
+			return false;
+		};
+		let Ok(now) = utc_timestamp() else {
+			return false;
+		};
+
+		now < checked + OWNERSHIP_CHECK_EXPIRATION
+	}
+
 	/// Checks if the account is logged in, where their sensitive info is present
 	pub fn is_logged_in(&self) -> bool {
 		!matches!(self.sensitive, SensitiveAccountInfoSerialized::None)
@@ -347,6 +368,9 @@ pub struct SensitiveAccountInfo {
 	pub access_token: Option<String>,
 	/// When the access token expires
 	pub access_token_expires: Option<u64>,
+	/// The client token that the access token is bound to, for accounts on Yggdrasil servers
+	#[serde(default)]
+	pub client_token: Option<String>,
 }
 
 /// Passkey information in the database
@@ -373,3 +397,36 @@ pub fn calculate_expiration_date() -> u64 {
 	let now = utc_timestamp().unwrap_or_default();
 	now + REFRESH_TOKEN_EXPIRATION
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_recent_ownership_check() {
+		let sensitive = SensitiveAccountInfo {
+			refresh_token: None,
+			xbox_uid: None,
+			keypair: None,
+			access_token: None,
+			access_token_expires: None,
+			client_token: None,
+		};
+		let mut account = DatabaseAccount::new(
+			"test".into(),
+			"Steve".into(),
+			"uuid".into(),
+			calculate_expiration_date(),
+			sensitive,
+		)
+		.unwrap();
+		assert!(!account.has_recent_ownership_check());
+
+		let now = utc_timestamp().unwrap();
+		account.ownership_checked = Some(now);
+		assert!(account.has_recent_ownership_check());
+
+		account.ownership_checked = Some(now - OWNERSHIP_CHECK_EXPIRATION - 1);
+		assert!(!account.has_recent_ownership_check());
+	}
+}
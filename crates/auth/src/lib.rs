@@ -16,5 +16,7 @@ pub mod mc;
 mod mc_msa;
 /// Usage of passkeys for encoding and decoding sensitive info
 pub mod passkey;
+/// Authentication with Yggdrasil servers that are compatible with authlib-injector
+pub mod yggdrasil;
 
 pub use rsa::{RsaPrivateKey, RsaPublicKey};
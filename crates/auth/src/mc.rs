@@ -20,10 +20,22 @@ const DEVICE_CODE_URL: &str = "https://login.microsoftonline.com/consumers/oauth
 const MSA_AUTHORIZE_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/authorize";
 const MSA_TOKEN_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/token";
 
+/// How the user signs in to their Microsoft account
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MicrosoftLoginMethod {
+	/// The frontend shows the login page in a browser on this device
+	#[default]
+	Browser,
+	/// A link and code are shown for the user to enter on another device, for when there
+	/// is no browser available, such as on headless servers or over SSH
+	DeviceCode,
+}
+
 /// Authenticate a Microsoft account using Microsoft OAuth.
 /// Will authenticate every time and will not use the database.
 pub async fn authenticate_microsoft_account(
 	client_id: ClientId,
+	method: MicrosoftLoginMethod,
 	client: &reqwest::Client,
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<MicrosoftAuthResult> {
@@ -32,7 +44,12 @@ pub async fn authenticate_microsoft_account(
 		.await
 		.context("Failed to execute authorization and generate login page")?;
 
-	o.display_special_ms_auth(response.verification_uri(), response.user_code().secret());
+	match method {
+		MicrosoftLoginMethod::Browser => {
+			o.display_special_ms_auth(response.verification_uri(), response.user_code().secret())
+		}
+		MicrosoftLoginMethod::DeviceCode => display_device_code(&response, o),
+	}
 
 	let token = get_microsoft_token(&oauth_client, response)
 		.await
@@ -113,6 +130,26 @@ pub async fn generate_login_page(
 	out.map_err(decorate_request_token_error)
 }
 
+/// Shows the link and code for logging in from another device, without trying to open a browser
+fn display_device_code(response: &StandardDeviceAuthorizationResponse, o: &mut impl NitroOutput) {
+	o.display(MessageContents::Property(
+		"On another device, open".into(),
+		Box::new(MessageContents::Hyperlink(
+			response.verification_uri().to_string(),
+		)),
+	));
+	o.display(MessageContents::Property(
+		"and enter the code".into(),
+		Box::new(MessageContents::Copyable(
+			response.user_code().secret().clone(),
+		)),
+	));
+	o.display(MessageContents::Simple(format!(
+		"The code expires in {} minutes",
+		response.expires_in().as_secs() / 60
+	)));
+}
+
 /// A TokenResponse from Microsoft OAuth
 pub type MicrosoftToken = StandardTokenResponse<EmptyExtraTokenFields, BasicTokenType>;
 
@@ -0,0 +1,219 @@
+use anyhow::{Context, bail};
+use rand::RngCore;
+use reqwest::{Client, StatusCode, header::HeaderValue};
+use serde::Deserialize;
+
+use crate::mc::AccessToken;
+
+/// The header that authentication servers use to point to their authlib-injector API
+const API_LOCATION_HEADER: &str = "X-Authlib-Injector-API-Location";
+
+/// Finds the root of the authlib-injector API for an authentication server. Servers can be given
+/// as just their website, in which case they point to the API themselves with a header
+pub async fn resolve_api_root(server: &str, client: &Client) -> anyhow::Result<String> {
+	let server = if server.starts_with("https://") || server.starts_with("http://") {
+		server.to_string()
+	} else {
+		format!("https://{server}")
+	};
+
+	let response = client
+		.get(&server)
+		.send()
+		.await
+		.context("Failed to contact authentication server")?;
+
+	let root = match response
+		.headers()
+		.get(API_LOCATION_HEADER)
+		.map(HeaderValue::to_str)
+	{
+		Some(Ok(location)) => response
+			.url()
+			.join(location)
+			.context("Authentication server gave an invalid API location")?
+			.to_string(),
+		_ => server,
+	};
+
+	Ok(root.trim_end_matches('/').to_string())
+}
+
+/// Logs in to an authentication server with a username or email and a password
+pub async fn authenticate(
+	api_root: &str,
+	username: &str,
+	password: &str,
+	client_token: &str,
+	client: &Client,
+) -> anyhow::Result<YggdrasilAuthResult> {
+	let body = serde_json::json!({
+		"agent": {
+			"name": "Minecraft",
+			"version": 1
+		},
+		"username": username,
+		"password": password,
+		"clientToken": client_token,
+		"requestUser": false
+	});
+
+	let response: AuthResponse = post(api_root, "authenticate", &body, client).await?;
+	response.into_result()
+}
+
+/// Gets a new access token for an account using its old one
+pub async fn refresh(
+	api_root: &str,
+	access_token: &AccessToken,
+	client_token: &str,
+	client: &Client,
+) -> anyhow::Result<YggdrasilAuthResult> {
+	let body = serde_json::json!({
+		"accessToken": access_token.0,
+		"clientToken": client_token,
+		"requestUser": false
+	});
+
+	let response: AuthResponse = post(api_root, "refresh", &body, client).await?;
+	response.into_result()
+}
+
+/// Checks whether an access token can still be used to join servers
+pub async fn validate(
+	api_root: &str,
+	access_token: &AccessToken,
+	client_token: &str,
+	client: &Client,
+) -> anyhow::Result<bool> {
+	let body = serde_json::json!({
+		"accessToken": access_token.0,
+		"clientToken": client_token
+	});
+
+	let response = client
+		.post(format!("{api_root}/authserver/validate"))
+		.json(&body)
+		.send()
+		.await
+		.context("Failed to contact authentication server")?;
+
+	Ok(response.status() == StatusCode::NO_CONTENT)
+}
+
+/// Generates a new client token, which identifies this launcher to the authentication server
+pub fn generate_client_token() -> String {
+	let mut bytes = [0u8; 16];
+	rand::thread_rng().fill_bytes(&mut bytes);
+	hex::encode(bytes)
+}
+
+/// Sends a request to an endpoint of the authentication server
+async fn post<T: for<'de> Deserialize<'de>>(
+	api_root: &str,
+	endpoint: &str,
+	body: &serde_json::Value,
+	client: &Client,
+) -> anyhow::Result<T> {
+	let response = client
+		.post(format!("{api_root}/authserver/{endpoint}"))
+		.json(body)
+		.send()
+		.await
+		.context("Failed to contact authentication server")?;
+
+	if !response.status().is_success() {
+		let error: ErrorResponse = response
+			.json()
+			.await
+			.context("Failed to read error from authentication server")?;
+		bail!(
+			"Authentication server returned an error: {}",
+			error.error_message.unwrap_or(error.error)
+		);
+	}
+
+	response
+		.json()
+		.await
+		.context("Failed to read response from authentication server")
+}
+
+/// Result from logging in or refreshing with an authentication server
+#[derive(Debug, Clone)]
+pub struct YggdrasilAuthResult {
+	/// The access token for logging into the game
+	pub access_token: AccessToken,
+	/// The client token that the access token is bound to
+	pub client_token: String,
+	/// The UUID of the selected profile, without hyphens
+	pub uuid: String,
+	/// The name of the selected profile
+	pub name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthResponse {
+	access_token: String,
+	client_token: String,
+	selected_profile: Option<YggdrasilProfile>,
+	#[serde(default)]
+	available_profiles: Vec<YggdrasilProfile>,
+}
+
+impl AuthResponse {
+	fn into_result(self) -> anyhow::Result<YggdrasilAuthResult> {
+		// Pick the only profile if the server didn't select one
+		let profile = match self.selected_profile {
+			Some(profile) => profile,
+			None if self.available_profiles.len() == 1 => self
+				.available_profiles
+				.into_iter()
+				.next()
+				.expect("Length was checked"),
+			None => bail!("The account does not have a single profile to play with"),
+		};
+
+		Ok(YggdrasilAuthResult {
+			access_token: AccessToken(self.access_token),
+			client_token: self.client_token,
+			uuid: profile.id,
+			name: profile.name,
+		})
+	}
+}
+
+#[derive(Deserialize)]
+struct YggdrasilProfile {
+	id: String,
+	name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorResponse {
+	error: String,
+	error_message: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_profile_selection() {
+		let response: AuthResponse = serde_json::from_str(
+			r#"{"accessToken":"a","clientToken":"c","availableProfiles":[{"id":"123","name":"Steve"}]}"#,
+		)
+		.unwrap();
+		let result = response.into_result().unwrap();
+		assert_eq!(result.name, "Steve");
+		assert_eq!(result.uuid, "123");
+
+		let response: AuthResponse =
+			serde_json::from_str(r#"{"accessToken":"a","clientToken":"c","availableProfiles":[]}"#)
+				.unwrap();
+		assert!(response.into_result().is_err());
+	}
+}
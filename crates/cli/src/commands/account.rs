@@ -7,6 +7,7 @@ use itertools::Itertools;
 use nitrolaunch::config::modifications::{ConfigModification, apply_modifications_and_write};
 use nitrolaunch::config_crate::account::{AccountConfig, AccountVariant};
 use nitrolaunch::core::account::AccountKind;
+use nitrolaunch::core::auth_crate::mc::MicrosoftLoginMethod;
 
 use clap::Subcommand;
 use color_print::{cformat, cprint, cprintln};
@@ -16,7 +17,6 @@ use nitrolaunch::plugin_crate::hook::hooks::{
 };
 use nitrolaunch::shared::minecraft::{CosmeticState, SkinVariant};
 use nitrolaunch::shared::output::{MessageContents, NitroOutput};
-use reqwest::Client;
 
 #[derive(Debug, Subcommand)]
 pub enum AccountSubcommand {
@@ -40,6 +40,10 @@ pub enum AccountSubcommand {
 	Login {
 		/// The account to authenticate. If not specified, uses the default account
 		account: Option<String>,
+		/// Log in by entering a code on another device instead of opening a browser on this one.
+		/// Useful on headless servers. This is the default over SSH
+		#[arg(long)]
+		device_code: bool,
 	},
 	#[command(about = "Log out an account")]
 	Logout {
@@ -91,7 +95,10 @@ pub async fn run(subcommand: AccountSubcommand, data: &mut CmdData<'_>) -> anyho
 		AccountSubcommand::Switch { account } => switch(data, account).await,
 		AccountSubcommand::Status => status(data).await,
 		AccountSubcommand::Passkey { account } => passkey(data, account).await,
-		AccountSubcommand::Login { account } => login(data, account).await,
+		AccountSubcommand::Login {
+			account,
+			device_code,
+		} => login(data, account, device_code).await,
 		AccountSubcommand::Logout { account } => logout(data, account).await,
 		AccountSubcommand::Add {} => add(data).await,
 		AccountSubcommand::Cosmetic { subcommand } => match subcommand {
@@ -131,6 +138,10 @@ async fn list(data: &mut CmdData<'_>, raw: bool) -> anyhow::Result<()> {
 					cprint!("<s><g>{}</g>", id)
 				}
 				AccountKind::Demo => cprint!("<s><c!>{}</c!>", id),
+				AccountKind::Offline { username } => cprint!("<s><y!>{}</y!> ({username})", id),
+				AccountKind::AuthlibInjector { server, .. } => {
+					cprint!("<s><m>{}</m> ({server})", id)
+				}
 				AccountKind::Unknown(other) => cprint!("<s><k!>({other}) {}</k!>", id),
 			}
 			if let Some(chosen) = config.accounts.get_chosen_account()
@@ -183,6 +194,8 @@ async fn status(data: &mut CmdData<'_>) -> anyhow::Result<()> {
 			match account.get_kind() {
 				AccountKind::Microsoft { .. } => cprint!("<s,g!>{}", account.get_id()),
 				AccountKind::Demo => cprint!("<s,c!>{}", account.get_id()),
+				AccountKind::Offline { .. } => cprint!("<s,y!>{}", account.get_id()),
+				AccountKind::AuthlibInjector { .. } => cprint!("<s,m>{}", account.get_id()),
 				AccountKind::Unknown(other) => cprint!("<s,k!>({other}) {}", account.get_id()),
 			}
 
@@ -217,19 +230,28 @@ async fn passkey(data: &mut CmdData<'_>, account: Option<String>) -> anyhow::Res
 	Ok(())
 }
 
-async fn login(data: &mut CmdData<'_>, account: Option<String>) -> anyhow::Result<()> {
+async fn login(
+	data: &mut CmdData<'_>,
+	account: Option<String>,
+	device_code: bool,
+) -> anyhow::Result<()> {
 	data.ensure_config(true).await?;
 	let config = data.config.get_mut();
 	if let Some(account) = account {
 		config.accounts.choose_account(&account)?;
 	}
+	if device_code {
+		config
+			.accounts
+			.set_ms_login_method(MicrosoftLoginMethod::DeviceCode);
+	}
 
-	let client = Client::new();
+	let client = data.client.clone();
 	config
 		.accounts
 		.authenticate(false, &data.paths.core, &client, data.output)
 		.await
-		.context("Failed to authenticate")?;
+		.map_err(|e| e.context("Failed to authenticate"))?;
 
 	Ok(())
 }
@@ -260,10 +282,37 @@ async fn add(data: &mut CmdData<'_>) -> anyhow::Result<()> {
 	// Build the account
 	let id = inquire::Text::new("What is the ID for the account?").prompt()?;
 
-	let options = vec![AccountVariant::Microsoft {}, AccountVariant::Demo {}];
+	let options = vec![
+		AccountVariant::Microsoft {},
+		AccountVariant::Demo {},
+		AccountVariant::Offline {},
+		AccountVariant::AuthlibInjector {},
+	];
 	let kind = inquire::Select::new("What kind of account is this?", options).prompt()?;
 
-	let account = AccountConfig::Simple(kind);
+	let account = if let AccountVariant::Offline = kind {
+		let username = inquire::Text::new("What username should the account play with?")
+			.with_default(&id)
+			.prompt()?;
+		AccountConfig::Advanced {
+			variant: kind,
+			auto_lock: None,
+			username: Some(username),
+			server: None,
+		}
+	} else if let AccountVariant::AuthlibInjector = kind {
+		let server =
+			inquire::Text::new("What is the URL of the authentication server?").prompt()?;
+		let username = inquire::Text::new("What username or email do you log in with?").prompt()?;
+		AccountConfig::Advanced {
+			variant: kind,
+			auto_lock: None,
+			username: Some(username),
+			server: Some(server),
+		}
+	} else {
+		AccountConfig::Simple(kind)
+	};
 
 	apply_modifications_and_write(
 		&mut config,
@@ -288,7 +337,7 @@ async fn cosmetic_list(data: &mut CmdData<'_>, account: Option<String>) -> anyho
 		config.accounts.choose_account(&account)?;
 	}
 
-	let client = Client::new();
+	let client = data.client.clone();
 	let (skins, capes) = config
 		.accounts
 		.get_cosmetics(&data.paths.core, &client, data.output)
@@ -330,7 +379,7 @@ async fn cosmetic_upload(
 	data.ensure_config(true).await?;
 	let config = data.config.get_mut();
 
-	let client = Client::new();
+	let client = data.client.clone();
 
 	let skin = load_from_uri(&skin, &client)
 		.await
@@ -5,6 +5,9 @@ use super::CmdData;
 use anyhow::Context;
 use clap::Subcommand;
 use color_print::cprintln;
+use nitrolaunch::core::io::prune::PruneOptions;
+use nitrolaunch::io::prune::prune_game_files;
+use nitrolaunch::shared::util::format_bytes;
 
 use std::fs;
 
@@ -21,6 +24,25 @@ files in your instances or any other user data."
 		#[arg(short, long)]
 		data: bool,
 	},
+	#[command(
+		about = "Remove unused game files",
+		long_about = "Remove game assets, libraries, and jars that aren't used by any of your instances.
+If no kinds of files are selected, all of them will be pruned."
+	)]
+	Prune {
+		/// Only show what would be removed and how much space it would free
+		#[arg(short, long)]
+		dry_run: bool,
+		/// Prune game assets
+		#[arg(long)]
+		assets: bool,
+		/// Prune game libraries
+		#[arg(long)]
+		libraries: bool,
+		/// Prune game jars
+		#[arg(long)]
+		jars: bool,
+	},
 	#[clap(external_subcommand)]
 	External(Vec<String>),
 }
@@ -28,6 +50,12 @@ files in your instances or any other user data."
 pub async fn run(subcommand: FilesSubcommand, data: &mut CmdData<'_>) -> anyhow::Result<()> {
 	match subcommand {
 		FilesSubcommand::Remove { data: remove_data } => remove(data, remove_data).await,
+		FilesSubcommand::Prune {
+			dry_run,
+			assets,
+			libraries,
+			jars,
+		} => prune(data, dry_run, assets, libraries, jars).await,
 		FilesSubcommand::External(args) => call_plugin_subcommand(args, Some("files"), data).await,
 	}
 }
@@ -45,3 +73,39 @@ pub async fn remove(data: &mut CmdData<'_>, remove_data: bool) -> anyhow::Result
 
 	Ok(())
 }
+
+pub async fn prune(
+	data: &mut CmdData<'_>,
+	dry_run: bool,
+	assets: bool,
+	libraries: bool,
+	jars: bool,
+) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get();
+
+	let all = !assets && !libraries && !jars;
+	let options = PruneOptions {
+		assets: all || assets,
+		libraries: all || libraries,
+		jars: all || jars,
+		dry_run,
+	};
+
+	let report = prune_game_files(config, &options, &data.paths, data.output)?;
+	if dry_run {
+		for (name, files) in [
+			("Assets", &report.assets),
+			("Libraries", &report.libraries),
+			("Jars", &report.jars),
+		] {
+			cprintln!(
+				"<s>{name}:</> {} files, {}",
+				files.files.len(),
+				format_bytes(files.size)
+			);
+		}
+	}
+
+	Ok(())
+}
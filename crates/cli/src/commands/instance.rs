@@ -1,29 +1,36 @@
 use std::ops::DerefMut;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, bail};
 use clap::Subcommand;
-use color_print::{cprint, cprintln};
+use color_print::{cformat, cprint, cprintln};
 use inquire::Confirm;
 use itertools::Itertools;
 use nitrolaunch::config::modifications::{ConfigModification, apply_modifications_and_write};
 use nitrolaunch::config_crate::instance::InstanceConfig;
 use nitrolaunch::core::QuickPlayType;
+use nitrolaunch::core::io::update::FileVerifier;
 use nitrolaunch::instance::Instance;
+use nitrolaunch::instance::advisories::apply_mitigation;
+use nitrolaunch::instance::orchestrate;
 use nitrolaunch::instance::transfer::load_formats;
 use nitrolaunch::instance::update::manager::UpdateSettings;
 use nitrolaunch::instance::update::{InstanceUpdateContext, UpdateFacets};
+use nitrolaunch::instance::watch::InstanceContentWatcher;
+use nitrolaunch::io::cache::enforce_cache_limit;
 use nitrolaunch::io::lock::Lockfile;
+use nitrolaunch::io::prune::prune_if_scheduled;
 use nitrolaunch::shared::id::InstanceID;
 use nitrolaunch::shared::java_args::MemoryNum;
 use nitrolaunch::shared::output::{MessageContents, NoOp};
 use nitrolaunch::shared::util::to_string_json;
+use nitrolaunch::shared::versions::VersionInfo;
 
 use nitrolaunch::instance::launch::LaunchSettings;
 use nitrolaunch::shared::lang::translate::TranslationKey;
 use nitrolaunch::shared::{Side, UpdateDepth, output::NitroOutput};
-use reqwest::Client;
 
 use super::CmdData;
 use crate::commands::call_plugin_subcommand;
@@ -34,6 +41,9 @@ use crate::prompt::{
 };
 use crate::secrets::get_ms_client_id;
 
+/// How often to check for changes when watching the files of an instance
+const CONTENT_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
 #[derive(Debug, Subcommand)]
 pub enum InstanceSubcommand {
 	#[command(about = "List all instances")]
@@ -60,9 +70,26 @@ pub enum InstanceSubcommand {
 		/// Launch into a world or server. Can be either world:<world>, server:<ip> or realm:<realm>
 		#[arg(short, long)]
 		quick_play: Option<QuickPlayType>,
+		/// Print the command that would launch the instance instead of launching it
+		#[arg(long)]
+		print_cmd: bool,
 		/// The instance to launch
 		instance: Option<String>,
 	},
+	#[command(
+		about = "Launch a group of instances together",
+		long_about = "Launch every instance in an instance group. Servers are started first, and clients are launched once all of the servers have finished starting"
+	)]
+	LaunchGroup {
+		/// An optional account to choose when launching
+		#[arg(short, long)]
+		account: Option<String>,
+		/// Whether to launch in offline mode, skipping authentication
+		#[arg(short, long)]
+		offline: bool,
+		/// The instance group to launch
+		group: String,
+	},
 	#[command(about = "Update versions, files, and packages of an instance")]
 	Update {
 		/// Whether to force update files that have already been downloaded
@@ -93,6 +120,9 @@ pub enum InstanceSubcommand {
 	Delete {
 		/// The instance to delete
 		instance: Option<String>,
+		/// Delete the files permanently instead of moving them to the trash
+		#[arg(short, long)]
+		permanent: bool,
 	},
 	#[command(about = "Edit configuration for an instance")]
 	Edit {
@@ -127,6 +157,71 @@ pub enum InstanceSubcommand {
 	Logs {
 		/// The instance to view the logs of
 		instance: Option<String>,
+		/// Upload a log or crash bundle to a paste service and print a shareable URL instead of viewing it
+		#[arg(short, long)]
+		upload: bool,
+	},
+	#[command(
+		about = "Check the game files and addons of an instance and redownload any that are corrupted"
+	)]
+	Verify {
+		/// The instance to verify
+		instance: Option<String>,
+	},
+	#[command(
+		about = "Identify mods that were added to an instance by hand and replace them with packages"
+	)]
+	Adopt {
+		/// The instance to adopt files in
+		instance: Option<String>,
+		/// Only show which files would be adopted without changing anything
+		#[arg(long)]
+		dry_run: bool,
+		/// Don't ask for confirmation
+		#[arg(short, long)]
+		yes: bool,
+	},
+	#[command(
+		about = "Check for addon files that were added or deleted by hand and update the installed packages to match"
+	)]
+	CheckFiles {
+		/// The instance to check
+		instance: Option<String>,
+		/// Keep watching the instance for changes
+		#[arg(short, long)]
+		watch: bool,
+	},
+	#[command(about = "List the packages on an instance that have newer versions available")]
+	Outdated {
+		/// The instance to check
+		instance: Option<String>,
+		/// Print the packages as JSON
+		#[arg(short, long)]
+		raw: bool,
+	},
+	#[command(about = "List known security problems that affect an instance")]
+	Advisories {
+		/// The instance to check
+		instance: Option<String>,
+		/// Apply the known fixes for the problems to the instance's config
+		#[arg(short, long)]
+		mitigate: bool,
+	},
+	#[command(about = "Print a machine-readable inventory of everything installed on an instance")]
+	Inventory {
+		/// The instance to list
+		instance: Option<String>,
+		/// A file to write the inventory to instead of printing it
+		#[arg(short, long)]
+		output: Option<String>,
+	},
+	#[command(about = "Export logs and crash information for an instance into a shareable zip")]
+	CrashBundle {
+		/// The instance to create the crash bundle for
+		instance: Option<String>,
+		/// Where to save the crash bundle. Defaults to ./<instance-id>-crash.zip
+		#[arg(short, long)]
+		output: Option<String>,
 	},
 	#[command(about = "Duplicates an instance into a new one")]
 	Duplicate {
@@ -151,6 +246,37 @@ pub enum InstanceSubcommand {
 		/// The ID of the new template
 		new_id: Option<String>,
 	},
+	#[command(
+		about = "Save the setup of an instance as a new template, leaving the instance unchanged"
+	)]
+	SaveTemplate {
+		/// The instance to create the template from
+		instance: Option<String>,
+		/// The ID of the new template
+		new_id: Option<String>,
+	},
+	#[command(
+		about = "Encrypt a field in the config of an instance with the launcher passphrase",
+		long_about = "Encrypt a field in the config of an instance with the launcher passphrase. The field is a dot-separated path to a string value, like launch.env.TOKEN. It will only be decrypted in memory when the instance is launched"
+	)]
+	Encrypt {
+		/// The instance to encrypt the field of
+		instance: String,
+		/// The path to the field to encrypt
+		field: String,
+	},
+	#[command(about = "Create a server instance that is linked to a client instance")]
+	CreateServer {
+		/// The client instance to create the server for
+		instance: Option<String>,
+		/// The ID of the new server instance
+		new_id: Option<String>,
+	},
+	#[command(about = "Copy the shared config of an instance to the instance it is linked to")]
+	Sync {
+		/// The instance to copy the config from
+		instance: Option<String>,
+	},
 	#[command(about = "Print the directory of an instance")]
 	Dir {
 		/// The instance to print the directory of
@@ -167,8 +293,14 @@ pub async fn run(command: InstanceSubcommand, mut data: CmdData<'_>) -> anyhow::
 			account,
 			offline,
 			quick_play,
+			print_cmd,
 			instance,
-		} => launch(instance, account, offline, quick_play, data).await,
+		} => launch(instance, account, offline, quick_play, print_cmd, data).await,
+		InstanceSubcommand::LaunchGroup {
+			account,
+			offline,
+			group,
+		} => launch_group(group, account, offline, data).await,
 		InstanceSubcommand::Info { instance } => info(&mut data, instance).await,
 		InstanceSubcommand::Update {
 			force,
@@ -191,7 +323,10 @@ pub async fn run(command: InstanceSubcommand, mut data: CmdData<'_>) -> anyhow::
 			format,
 			output,
 		} => export(&mut data, instance, format, output).await,
-		InstanceSubcommand::Delete { instance } => delete(&mut data, instance).await,
+		InstanceSubcommand::Delete {
+			instance,
+			permanent,
+		} => delete(&mut data, instance, permanent).await,
 		InstanceSubcommand::Edit { instance } => edit(&mut data, instance).await,
 		InstanceSubcommand::Duplicate { instance, new_id } => {
 			duplicate(&mut data, instance, new_id).await
@@ -200,7 +335,36 @@ pub async fn run(command: InstanceSubcommand, mut data: CmdData<'_>) -> anyhow::
 		InstanceSubcommand::Extract { instance, new_id } => {
 			extract(&mut data, instance, new_id).await
 		}
-		InstanceSubcommand::Logs { instance } => logs(&mut data, instance).await,
+		InstanceSubcommand::SaveTemplate { instance, new_id } => {
+			save_template(&mut data, instance, new_id).await
+		}
+		InstanceSubcommand::Encrypt { instance, field } => {
+			encrypt(&mut data, instance, field).await
+		}
+		InstanceSubcommand::CreateServer { instance, new_id } => {
+			create_server(&mut data, instance, new_id).await
+		}
+		InstanceSubcommand::Sync { instance } => sync(&mut data, instance).await,
+		InstanceSubcommand::Logs { instance, upload } => logs(&mut data, instance, upload).await,
+		InstanceSubcommand::Verify { instance } => verify(&mut data, instance).await,
+		InstanceSubcommand::Adopt {
+			instance,
+			dry_run,
+			yes,
+		} => adopt(&mut data, instance, dry_run, yes).await,
+		InstanceSubcommand::CheckFiles { instance, watch } => {
+			check_files(&mut data, instance, watch).await
+		}
+		InstanceSubcommand::Outdated { instance, raw } => outdated(&mut data, instance, raw).await,
+		InstanceSubcommand::Advisories { instance, mitigate } => {
+			advisories(&mut data, instance, mitigate).await
+		}
+		InstanceSubcommand::Inventory { instance, output } => {
+			inventory(&mut data, instance, output).await
+		}
+		InstanceSubcommand::CrashBundle { instance, output } => {
+			crash_bundle(&mut data, instance, output).await
+		}
 		InstanceSubcommand::External(args) => {
 			call_plugin_subcommand(args, Some("instance"), &mut data).await
 		}
@@ -318,6 +482,7 @@ pub async fn launch(
 	account: Option<String>,
 	offline: bool,
 	quick_play: Option<QuickPlayType>,
+	print_cmd: bool,
 	mut data: CmdData<'_>,
 ) -> anyhow::Result<()> {
 	data.ensure_config(true).await?;
@@ -325,7 +490,7 @@ pub async fn launch(
 
 	let instance_id = pick_instance(instance, config).context("Failed to pick instance")?;
 
-	let client = Client::new();
+	let client = data.client.clone();
 	let core = config
 		.get_core(
 			Some(&get_ms_client_id()),
@@ -372,10 +537,23 @@ pub async fn launch(
 		core: &core,
 	};
 
+	if print_cmd {
+		let mut prepared = instance
+			.prepare_launch(launch_settings, &mut ctx)
+			.await
+			.map_err(|e| e.context("Failed to prepare launch"))?;
+		lock.finish(&data.paths)?;
+
+		prepared.censor_secrets();
+		println!("{}", prepared.to_script());
+
+		return Ok(());
+	}
+
 	let instance_handle = instance
 		.launch(launch_settings, &mut ctx)
 		.await
-		.context("Instance failed to launch")?;
+		.map_err(|e| e.context("Instance failed to launch"))?;
 
 	// Drop items early so that they aren't wasting memory while the instance is running
 	let plugins = config.plugins.clone();
@@ -392,6 +570,75 @@ pub async fn launch(
 	Ok(())
 }
 
+async fn launch_group(
+	group: String,
+	account: Option<String>,
+	offline: bool,
+	mut data: CmdData<'_>,
+) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get_mut();
+
+	let members = config
+		.instance_groups
+		.get(&Arc::from(group.clone()))
+		.with_context(|| format!("Instance group '{group}' does not exist"))?
+		.clone();
+
+	let client = data.client.clone();
+	let core = config
+		.get_core(
+			Some(&get_ms_client_id()),
+			&UpdateSettings {
+				depth: UpdateDepth::Shallow,
+				offline_auth: offline,
+			},
+			&client,
+			&config.plugins,
+			&data.paths,
+			data.output,
+		)
+		.await?;
+
+	if let Some(account) = account {
+		config
+			.accounts
+			.choose_account(&account)
+			.context("Failed to choose account")?;
+	}
+
+	let mut lock = Lockfile::open(&data.paths)?;
+
+	let mut ctx = InstanceUpdateContext {
+		packages: &mut config.packages,
+		accounts: &mut config.accounts,
+		plugins: &config.plugins,
+		prefs: &config.prefs,
+		paths: &data.paths,
+		lock: &mut lock,
+		client: &client,
+		output: data.output,
+		core: &core,
+	};
+
+	let handle = orchestrate::launch_group(&members, &mut config.instances, offline, &mut ctx)
+		.await
+		.with_context(|| format!("Failed to launch instance group '{group}'"))?;
+
+	let plugins = config.plugins.clone();
+	std::mem::drop(data.config);
+	lock.finish(&data.paths)?;
+	std::mem::drop(lock);
+	std::mem::drop(client);
+
+	handle
+		.wait(&plugins, &data.paths, data.output)
+		.await
+		.context("Failed to wait for instance group")?;
+
+	Ok(())
+}
+
 async fn dir(data: &mut CmdData<'_>, instance: Option<String>) -> anyhow::Result<()> {
 	data.ensure_config(true).await?;
 	let config = data.config.get();
@@ -443,7 +690,7 @@ async fn update(
 		ids = pick_instances(config)?;
 	}
 
-	let client = Client::new();
+	let client = data.client.clone();
 	let mut lock = Lockfile::open(&data.paths).context("Failed to open lockfile")?;
 	let core = config
 		.get_core(
@@ -499,6 +746,196 @@ async fn update(
 			.context("Failed to finish using lockfile")?;
 	}
 
+	prune_if_scheduled(config, &mut lock, &data.paths, data.output)
+		.context("Failed to prune unused game files")?;
+	enforce_cache_limit(config, &data.paths, data.output)
+		.context("Failed to enforce cache limit")?;
+	lock.finish(&data.paths)
+		.context("Failed to finish using lockfile")?;
+
+	Ok(())
+}
+
+async fn adopt(
+	data: &mut CmdData<'_>,
+	instance: Option<String>,
+	dry_run: bool,
+	yes: bool,
+) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get_mut();
+
+	let id = pick_instance(instance, config)?;
+	let instance = config
+		.instances
+		.get(&id)
+		.with_context(|| format!("Unknown instance '{id}'"))?;
+
+	let client = data.client.clone();
+	let files = instance
+		.identify_loose_files(&data.paths, &client, data.output)
+		.await
+		.context("Failed to identify loose files")?;
+
+	if files.identified.is_empty() && files.unidentified.is_empty() {
+		cprintln!("<s>No loose files found");
+		return Ok(());
+	}
+
+	if !files.identified.is_empty() {
+		cprintln!("<s>Identified files:");
+		for file in &files.identified {
+			cprintln!(
+				"{}{} -> <b>{}",
+				HYPHEN_POINT,
+				file.path.display(),
+				file.package.get_pkg_id()
+			);
+		}
+	}
+	if !files.unidentified.is_empty() {
+		cprintln!("<s>Files that could not be identified:");
+		for path in &files.unidentified {
+			cprintln!("{}{}", HYPHEN_POINT, path.display());
+		}
+	}
+
+	if dry_run || files.identified.is_empty() {
+		return Ok(());
+	}
+
+	if !yes
+		&& !Confirm::new("Replace the identified files with packages? (y/n)")
+			.prompt()
+			.context("Failed to get confirmation")?
+	{
+		cprintln!("<r>Cancelled.");
+		return Ok(());
+	}
+
+	// Don't add packages that are already configured or that multiple files were identified as
+	let mut added: Vec<_> = instance.packages().iter().map(|x| x.id.clone()).collect();
+	let mut modifications = Vec::new();
+	for file in &files.identified {
+		let package_id = file.package.get_pkg_id();
+		if added.contains(&package_id) {
+			continue;
+		}
+		added.push(package_id);
+		modifications.push(ConfigModification::AddPackage(
+			id.clone(),
+			file.package.clone(),
+		));
+	}
+
+	let mut config_raw = data.get_raw_config()?;
+	apply_modifications_and_write(
+		&mut config_raw,
+		modifications,
+		&data.paths,
+		&data.config.get().plugins,
+		data.output,
+	)
+	.await
+	.context("Failed to write modified config")?;
+
+	files
+		.remove_identified()
+		.context("Failed to remove adopted files")?;
+
+	data.output.display(MessageContents::Success(format!(
+		"Adopted {} files. Update the instance to install their packages.",
+		files.identified.len()
+	)));
+
+	Ok(())
+}
+
+async fn check_files(
+	data: &mut CmdData<'_>,
+	instance: Option<String>,
+	watch: bool,
+) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get_mut();
+
+	let id = pick_instance(instance, config)?;
+	let instance = config
+		.instances
+		.get(&id)
+		.with_context(|| format!("Unknown instance '{id}'"))?;
+
+	let report = instance
+		.reconcile_content(&data.paths, data.output)
+		.context("Failed to check instance files")?;
+	if report.is_empty() {
+		cprintln!("<s>All addon files are managed by packages");
+	}
+
+	if !watch {
+		return Ok(());
+	}
+
+	cprintln!("<s>Watching for changes. Press Ctrl+C to stop.");
+	let mut watcher = InstanceContentWatcher::new(instance);
+	loop {
+		tokio::time::sleep(CONTENT_CHECK_INTERVAL).await;
+		if watcher.check() {
+			instance
+				.reconcile_content(&data.paths, data.output)
+				.context("Failed to check instance files")?;
+		}
+	}
+}
+
+async fn verify(data: &mut CmdData<'_>, instance: Option<String>) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get_mut();
+
+	let id = pick_instance(instance, config)?;
+
+	let client = data.client.clone();
+	let mut lock = Lockfile::open(&data.paths).context("Failed to open lockfile")?;
+	let mut core = config
+		.get_core(
+			Some(&get_ms_client_id()),
+			&UpdateSettings {
+				depth: UpdateDepth::Shallow,
+				offline_auth: false,
+			},
+			&client,
+			&config.plugins,
+			&data.paths,
+			&mut NoOp,
+		)
+		.await?;
+	core.set_file_verifier(FileVerifier::new());
+
+	let instance = config
+		.instances
+		.get_mut(&id)
+		.with_context(|| format!("Unknown instance '{id}'"))?;
+
+	let mut ctx = InstanceUpdateContext {
+		packages: &config.packages,
+		accounts: &mut config.accounts,
+		plugins: &config.plugins,
+		prefs: &config.prefs,
+		paths: &data.paths,
+		lock: &mut lock,
+		client: &client,
+		output: data.output,
+		core: &core,
+	};
+
+	instance
+		.verify(&mut ctx)
+		.await
+		.context("Failed to verify instance")?;
+
+	lock.finish(&data.paths)
+		.context("Failed to finish using lockfile")?;
+
 	Ok(())
 }
 
@@ -512,7 +949,7 @@ async fn add(data: &mut CmdData<'_>, plugin: Option<String>) -> anyhow::Result<(
 
 	let side = pick_side(None)?;
 
-	let client = Client::new();
+	let client = data.client.clone();
 	let core = config
 		.get_core(
 			Some(&get_ms_client_id()),
@@ -683,30 +1120,290 @@ async fn export(
 	Ok(())
 }
 
-async fn delete(data: &mut CmdData<'_>, id: Option<String>) -> anyhow::Result<()> {
-	data.ensure_config(true).await?;
-	let config = data.config.get_mut();
-
-	let id = pick_instance(id, config)?;
+pub async fn outdated(
+	data: &mut CmdData<'_>,
+	instance: Option<String>,
+	raw: bool,
+) -> anyhow::Result<()> {
+	data.ensure_config(!raw).await?;
+	let config = data.config.get();
 
+	let id = pick_instance(instance, config)?;
 	let instance = config
 		.instances
-		.get_mut(&id)
+		.get(&id)
 		.with_context(|| format!("Unknown instance '{id}'"))?;
 
-	let prompt = Confirm::new(
-		"Are you SURE you want to delete this instance? This will remove world saves as well! (y/n)",
-	);
-	if !prompt.prompt()? {
-		cprintln!("<r>Cancelled.");
-		return Ok(());
-	}
-
-	let mut process = data.output.get_process();
+	let client = data.client.clone();
+	let core = config
+		.get_core(
+			Some(&get_ms_client_id()),
+			&UpdateSettings {
+				depth: UpdateDepth::Shallow,
+				offline_auth: false,
+			},
+			&client,
+			&config.plugins,
+			&data.paths,
+			&mut NoOp,
+		)
+		.await?;
+	let manifest = core
+		.get_version_manifest(None, UpdateDepth::Shallow, &mut NoOp)
+		.await?;
+
+	let packages = instance
+		.get_outdated_packages(
+			&manifest.list,
+			&config.packages,
+			&data.paths,
+			&client,
+			data.output,
+		)
+		.await
+		.context("Failed to check for outdated packages")?;
+
+	if raw {
+		let out = serde_json::to_string(&packages).context("Failed to serialize packages")?;
+		print!("{out}");
+
+		return Ok(());
+	}
+
+	if packages.is_empty() {
+		cprintln!("<s>All packages are up to date");
+		return Ok(());
+	}
+
+	cprintln!("<s>Packages with updates for instance <b>{}</>:", id);
+	for package in packages {
+		cprintln!(
+			"{}<s>{}</>: {} -> <g>{}",
+			HYPHEN_POINT,
+			package.id,
+			package.installed_version,
+			package.latest_version
+		);
+	}
+
+	Ok(())
+}
+
+pub async fn advisories(
+	data: &mut CmdData<'_>,
+	instance: Option<String>,
+	mitigate: bool,
+) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let mut raw_config = data.get_raw_config()?;
+	let config = data.config.get();
+
+	let id = pick_instance(instance, config)?;
+	let instance = config
+		.instances
+		.get(&id)
+		.with_context(|| format!("Unknown instance '{id}'"))?;
+
+	let lock = instance.get_lockfile(&data.paths)?;
+	let Some(version) = lock.get_minecraft_version() else {
+		bail!("Instance {id} has not been created yet. Update it first");
+	};
+
+	let client = data.client.clone();
+	let core = config
+		.get_core(
+			Some(&get_ms_client_id()),
+			&UpdateSettings {
+				depth: UpdateDepth::Shallow,
+				offline_auth: false,
+			},
+			&client,
+			&config.plugins,
+			&data.paths,
+			&mut NoOp,
+		)
+		.await?;
+	let manifest = core
+		.get_version_manifest(None, UpdateDepth::Shallow, &mut NoOp)
+		.await?;
+	let version_info = VersionInfo {
+		version: version.clone(),
+		versions: manifest.list.clone(),
+	};
+
+	let advisories = instance
+		.get_advisories(
+			&version_info,
+			lock.get_loader_version().map(|x| x.as_str()),
+			&data.paths,
+			&client,
+		)
+		.await
+		.context("Failed to check for security advisories")?;
+
+	if advisories.is_empty() {
+		cprintln!("<s>No known security problems affect this instance");
+		return Ok(());
+	}
+
+	cprintln!("<s>Security problems affecting instance <b>{}</>:", id);
+	for advisory in &advisories {
+		let status = if advisory.is_mitigated {
+			cformat!("<g>mitigated")
+		} else if advisory.mitigation.is_some() {
+			cformat!("<y>fix available")
+		} else {
+			cformat!("<r>no fix, update the instance")
+		};
+		cprintln!(
+			"{}<s>{}</> ({}) - {}",
+			HYPHEN_POINT,
+			advisory.advisory.title,
+			advisory.advisory.id,
+			status
+		);
+		cprintln!("   {}", advisory.advisory.description);
+		if let Some(url) = &advisory.advisory.url {
+			cprintln!("   <k!>{}", url);
+		}
+	}
+
+	if !mitigate {
+		return Ok(());
+	}
+
+	let mut instance_config = instance.original_config().clone();
+	if instance_config.source_plugin.is_some() && !instance_config.is_editable {
+		bail!("This plugin instance does not support editing");
+	}
+	let mut applied = 0;
+	for advisory in &advisories {
+		if advisory.is_mitigated {
+			continue;
+		}
+		if let Some(mitigation) = &advisory.mitigation {
+			apply_mitigation(&mut instance_config, mitigation);
+			applied += 1;
+		}
+	}
+
+	if applied == 0 {
+		cprintln!("<s>There are no fixes to apply");
+		return Ok(());
+	}
+
+	let modifications = vec![ConfigModification::UpdateInstance(
+		id.clone(),
+		instance_config,
+	)];
+	apply_modifications_and_write(
+		&mut raw_config,
+		modifications,
+		&data.paths,
+		&config.plugins,
+		data.output,
+	)
+	.await
+	.context("Failed to write modified config")?;
+
+	cprintln!("<g>Applied {} fixes to instance {}", applied, id);
+
+	Ok(())
+}
+
+pub async fn inventory(
+	data: &mut CmdData<'_>,
+	instance: Option<String>,
+	output: Option<String>,
+) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get();
+
+	let id = pick_instance(instance, config)?;
+	let instance = config
+		.instances
+		.get(&id)
+		.with_context(|| format!("Unknown instance '{id}'"))?;
+
+	let inventory = instance
+		.get_inventory(&data.paths)
+		.context("Failed to create inventory")?;
+	let inventory =
+		serde_json::to_string_pretty(&inventory).context("Failed to serialize inventory")?;
+
+	if let Some(output) = output {
+		std::fs::write(&output, inventory).context("Failed to write inventory")?;
+		data.output.display(MessageContents::Success(format!(
+			"Inventory saved to {output}"
+		)));
+	} else {
+		println!("{inventory}");
+	}
+
+	Ok(())
+}
+
+async fn crash_bundle(
+	data: &mut CmdData<'_>,
+	instance: Option<String>,
+	output: Option<String>,
+) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get();
+
+	let instance = pick_instance(instance, config)?;
+
+	let result_path = if let Some(output) = output {
+		PathBuf::from(output)
+	} else {
+		let current_dir = std::env::current_dir()?;
+		current_dir.join(format!("{instance}-crash.zip"))
+	};
+
+	let instance = config
+		.instances
+		.get(&instance)
+		.context("The provided instance does not exist")?;
+
+	instance
+		.export_crash_bundle(&config.accounts, &result_path)
+		.context("Failed to create crash bundle")?;
+
+	data.output.display(MessageContents::Success(format!(
+		"Crash bundle saved to {}",
+		result_path.to_string_lossy()
+	)));
+
+	Ok(())
+}
+
+async fn delete(data: &mut CmdData<'_>, id: Option<String>, permanent: bool) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get_mut();
+
+	let id = pick_instance(id, config)?;
+
+	let instance = config
+		.instances
+		.get_mut(&id)
+		.with_context(|| format!("Unknown instance '{id}'"))?;
+
+	let message = if permanent {
+		"Are you SURE you want to PERMANENTLY delete this instance? This will remove world saves as well! (y/n)"
+	} else {
+		"Are you sure you want to delete this instance? Its files, including world saves, will be moved to the trash (y/n)"
+	};
+	let prompt = Confirm::new(message);
+	if !prompt.prompt()? {
+		cprintln!("<r>Cancelled.");
+		return Ok(());
+	}
+
+	let mut process = data.output.get_process();
 	process.display(MessageContents::StartProcess("Deleting instance".into()));
 
 	instance
-		.delete(&data.paths, &config.plugins, process.deref_mut())
+		.delete(&data.paths, &config.plugins, permanent, process.deref_mut())
 		.await
 		.context("Failed to delete instance")?;
 
@@ -756,7 +1453,7 @@ async fn edit(data: &mut CmdData<'_>, id: Option<String>) -> anyhow::Result<()>
 	Ok(())
 }
 
-async fn logs(data: &mut CmdData<'_>, id: Option<String>) -> anyhow::Result<()> {
+async fn logs(data: &mut CmdData<'_>, id: Option<String>, upload: bool) -> anyhow::Result<()> {
 	data.ensure_config(true).await?;
 	let config = data.config.get_mut();
 
@@ -772,6 +1469,10 @@ async fn logs(data: &mut CmdData<'_>, id: Option<String>) -> anyhow::Result<()>
 		.await
 		.context("Failed to get instance logs")?;
 
+	if upload {
+		return upload_log(data, &id, logs).await;
+	}
+
 	if logs.is_empty() {
 		cprintln!("No logs available");
 		return Ok(());
@@ -799,6 +1500,55 @@ async fn logs(data: &mut CmdData<'_>, id: Option<String>) -> anyhow::Result<()>
 	Ok(())
 }
 
+/// Picks a log or the crash bundle of an instance and uploads it to the configured paste backend
+async fn upload_log(data: &mut CmdData<'_>, id: &str, logs: Vec<String>) -> anyhow::Result<()> {
+	let config = data.config.get_mut();
+	let instance = config
+		.instances
+		.get_mut(id)
+		.with_context(|| format!("Unknown instance '{id}'"))?;
+
+	let crash_bundle_option = "Crash bundle".to_string();
+	let mut options = vec![crash_bundle_option.clone()];
+	options.extend(logs);
+	let Some(selected) =
+		inquire::Select::new("Select what to upload", options).prompt_skippable()?
+	else {
+		return Ok(());
+	};
+
+	let client = data.client.clone();
+	let backend = config.prefs.paste_backend.as_deref();
+	let url = if selected == crash_bundle_option {
+		instance
+			.upload_crash_bundle(
+				backend,
+				&config.accounts,
+				&config.plugins,
+				&client,
+				&data.paths,
+				data.output,
+			)
+			.await
+	} else {
+		instance
+			.upload_log(
+				&selected,
+				backend,
+				&config.plugins,
+				&client,
+				&data.paths,
+				data.output,
+			)
+			.await
+	}
+	.context("Failed to upload")?;
+
+	cprintln!("<s>Shareable link: <b>{url}");
+
+	Ok(())
+}
+
 async fn duplicate(
 	data: &mut CmdData<'_>,
 	instance: Option<String>,
@@ -878,3 +1628,110 @@ async fn extract(
 
 	Ok(())
 }
+
+async fn save_template(
+	data: &mut CmdData<'_>,
+	instance: Option<String>,
+	new_id: Option<String>,
+) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get();
+
+	let instance = pick_instance(instance, config)?;
+	let instance = config
+		.instances
+		.get(&instance)
+		.context("Instance does not exist")?;
+
+	let new_id = if let Some(new_id) = new_id {
+		new_id.into()
+	} else {
+		pick_instance_id()?
+	};
+
+	instance
+		.save_as_template(&new_id, &data.paths, &config.plugins, data.output)
+		.await?;
+
+	data.output
+		.display(MessageContents::Success("Template saved".into()));
+
+	Ok(())
+}
+
+async fn encrypt(data: &mut CmdData<'_>, instance: String, field: String) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get();
+
+	let instance = config
+		.instances
+		.get(&InstanceID::from(instance))
+		.context("Instance does not exist")?;
+
+	instance
+		.encrypt_config_field(&field, &data.paths, &config.plugins, data.output)
+		.await?;
+
+	data.output
+		.display(MessageContents::Success("Field encrypted".into()));
+
+	Ok(())
+}
+
+async fn create_server(
+	data: &mut CmdData<'_>,
+	instance: Option<String>,
+	new_id: Option<String>,
+) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get();
+
+	let instance = pick_instance(instance, config)?;
+	let instance = config
+		.instances
+		.get(&instance)
+		.context("Instance does not exist")?;
+
+	let new_id = if let Some(new_id) = new_id {
+		new_id.into()
+	} else {
+		pick_instance_id()?
+	};
+
+	instance
+		.create_matching_server(&new_id, &data.paths, &config.plugins, data.output)
+		.await?;
+
+	data.output
+		.display(MessageContents::Success("Changes saved".into()));
+
+	Ok(())
+}
+
+async fn sync(data: &mut CmdData<'_>, instance: Option<String>) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get();
+
+	let instance = pick_instance(instance, config)?;
+	let instance = config
+		.instances
+		.get(&instance)
+		.context("Instance does not exist")?;
+
+	let linked = instance
+		.linked_instance()
+		.context("Instance is not linked to another instance")?;
+	let linked = config
+		.instances
+		.get(&linked)
+		.context("Linked instance does not exist")?;
+
+	instance
+		.sync_to_linked(linked, &data.paths, &config.plugins, data.output)
+		.await?;
+
+	data.output
+		.display(MessageContents::Success("Changes saved".into()));
+
+	Ok(())
+}
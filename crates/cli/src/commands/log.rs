@@ -9,6 +9,7 @@ use anyhow::Context;
 use clap::Subcommand;
 use color_print::{cprintln, cwrite};
 use nitrolaunch::io::logging::get_log_files;
+use nitrolaunch::io::reports::{self, SavedReport};
 
 use crate::commands::{CmdData, call_plugin_subcommand};
 
@@ -20,6 +21,12 @@ pub enum LogSubcommand {
 		#[arg(short, long)]
 		client_id: Option<String>,
 	},
+	#[command(about = "View reports of crashes and errors and submit them as issues")]
+	Reports {
+		/// Remove all of the saved reports
+		#[arg(long)]
+		clear: bool,
+	},
 	#[clap(external_subcommand)]
 	External(Vec<String>),
 }
@@ -27,6 +34,7 @@ pub enum LogSubcommand {
 pub async fn run(subcommand: LogSubcommand, data: &mut CmdData<'_>) -> anyhow::Result<()> {
 	match subcommand {
 		LogSubcommand::Browse { client_id } => browse(data, client_id).await,
+		LogSubcommand::Reports { clear } => view_reports(data, clear).await,
 		LogSubcommand::External(args) => call_plugin_subcommand(args, Some("log"), data).await,
 	}
 }
@@ -59,6 +67,54 @@ pub async fn browse(data: &mut CmdData<'_>, client_id: Option<String>) -> anyhow
 	Ok(())
 }
 
+pub async fn view_reports(data: &mut CmdData<'_>, clear: bool) -> anyhow::Result<()> {
+	if clear {
+		reports::clear_reports(&data.paths).context("Failed to clear reports")?;
+		cprintln!("<s>Reports cleared");
+		return Ok(());
+	}
+
+	let reports = reports::list_reports(&data.paths).context("Failed to get reports")?;
+	if reports.is_empty() {
+		if reports::is_enabled() {
+			cprintln!("<s>No reports saved");
+		} else {
+			cprintln!(
+				"<s>No reports saved. Set the <b>error_reports</> preference to true to save reports when something goes wrong."
+			);
+		}
+		return Ok(());
+	}
+
+	let entries: Vec<_> = reports.into_iter().map(ReportEntry).collect();
+	loop {
+		let select = inquire::Select::new(
+			"Select a report to view. Press Escape to exit.",
+			entries.clone(),
+		);
+		let Some(entry) = select.prompt_skippable()? else {
+			break;
+		};
+
+		cprintln!("<s>Report <g>{}", entry.0.id);
+		println!("{}", entry.0.report.to_issue_body());
+		cprintln!("<s>To submit this report as an issue, open this link:");
+		println!("{}", entry.0.report.issue_url());
+		inquire::Confirm::new("Press Escape to return to the report list").prompt_skippable()?;
+	}
+
+	Ok(())
+}
+
+#[derive(Clone)]
+struct ReportEntry(SavedReport);
+
+impl Display for ReportEntry {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		cwrite!(f, "<k!>{}</> {}", self.0.id, self.0.report.title())
+	}
+}
+
 #[derive(Clone)]
 struct BrowseEntry {
 	log_path: PathBuf,
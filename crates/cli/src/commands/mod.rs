@@ -4,8 +4,11 @@ mod files;
 mod instance;
 mod log;
 mod modpack;
+mod net;
+mod notification;
 mod package;
 mod plugin;
+mod self_update;
 mod template;
 mod r#try;
 mod version;
@@ -20,8 +23,11 @@ use nitrolaunch::config::modifications::{ConfigModification, apply_modifications
 use nitrolaunch::config::{Config, is_first_run};
 use nitrolaunch::config_crate::ConfigDeser;
 use nitrolaunch::core::QuickPlayType;
+use nitrolaunch::core::auth_crate::mc::MicrosoftLoginMethod;
 use nitrolaunch::instance::transfer::{load_formats, migrate_instances};
+use nitrolaunch::io::notifications::NotificationStore;
 use nitrolaunch::io::paths::Paths;
+use nitrolaunch::io::{net_stats, reports, self_update as self_update_io};
 use nitrolaunch::plugin::PluginManager;
 use nitrolaunch::plugin_crate::hook::hooks::{self, AddTranslations, SubcommandArg};
 use nitrolaunch::shared::id::InstanceID;
@@ -29,6 +35,7 @@ use nitrolaunch::shared::lang::translate::TranslationKey;
 use nitrolaunch::shared::later::Later;
 use nitrolaunch::shared::nitro_executable::{NitroClientId, NitroExecutableRegistry};
 use nitrolaunch::shared::output::{MessageContents, MessageLevel, NitroOutput};
+use reqwest::Client;
 
 use self::account::AccountSubcommand;
 use self::config::ConfigSubcommand;
@@ -36,6 +43,8 @@ use self::files::FilesSubcommand;
 use self::instance::InstanceSubcommand;
 use self::log::LogSubcommand;
 use self::modpack::ModpackSubcommand;
+use self::net::NetSubcommand;
+use self::notification::NotificationSubcommand;
 use self::package::PackageSubcommand;
 use self::plugin::PluginSubcommand;
 use self::template::TemplateSubcommand;
@@ -58,6 +67,9 @@ pub enum Command {
 		/// Launch into a world or server. Can be either world:<world>, server:<ip> or realm:<realm>
 		#[arg(short, long)]
 		quick_play: Option<QuickPlayType>,
+		/// Print the command that would launch the instance instead of launching it
+		#[arg(long)]
+		print_cmd: bool,
 		/// The instance to launch
 		instance: Option<String>,
 	},
@@ -122,11 +134,39 @@ pub enum Command {
 		#[command(subcommand)]
 		command: LogSubcommand,
 	},
+	#[command(about = "Inspect network activity")]
+	Net {
+		#[command(subcommand)]
+		command: NetSubcommand,
+	},
+	#[command(about = "View notifications about events that happened in the background")]
+	#[clap(alias = "notif")]
+	Notification {
+		#[command(subcommand)]
+		command: NotificationSubcommand,
+	},
 	#[command(about = "Deal with files created by Nitrolaunch")]
 	Files {
 		#[command(subcommand)]
 		command: FilesSubcommand,
 	},
+	#[command(about = "Update the Nitrolaunch CLI to the newest release")]
+	SelfUpdate {
+		/// Only check whether an update is available without installing it
+		#[arg(short, long)]
+		check: bool,
+		/// Update from the beta channel, which includes prereleases
+		#[arg(short, long)]
+		beta: bool,
+	},
+	#[command(about = "Print a machine-readable inventory of everything installed on an instance")]
+	Inventory {
+		/// The instance to list
+		instance: Option<String>,
+		/// A file to write the inventory to instead of printing it
+		#[arg(short, long)]
+		output: Option<String>,
+	},
 	#[command(about = "Print the Nitrolaunch version")]
 	Version {
 		#[command(subcommand)]
@@ -171,6 +211,19 @@ pub async fn run_cli() -> anyhow::Result<()> {
 		.await
 		.context("Failed to set up system paths")?;
 	let mut output = TerminalOutput::new(&paths).context("Failed to set up output")?;
+	reports::install_panic_hook(&paths);
+	let report_paths = paths.clone();
+
+	// Updates that couldn't replace the executable while it was running are applied now
+	match self_update_io::apply_staged_update() {
+		Ok(true) => output.display(MessageContents::Success(
+			"Applied a staged update. It will be used from the next command".into(),
+		)),
+		Ok(false) => {}
+		Err(e) => output.display(MessageContents::Warning(format!(
+			"Failed to apply staged update: {e:?}"
+		))),
+	}
 
 	if let Ok(mut exec_registry) = NitroExecutableRegistry::open(&paths.internal) {
 		let _ = exec_registry.add_this(NitroClientId::Cli);
@@ -228,6 +281,8 @@ Would you like to do that now?"
 		}
 	}
 
+	let is_notification_command = matches!(cli.command, Command::Notification { .. });
+
 	let res = {
 		let mut data = CmdData::new(paths, &mut output)?;
 		let log_level = get_log_level(&cli);
@@ -244,9 +299,12 @@ Would you like to do that now?"
 				account,
 				offline,
 				quick_play,
+				print_cmd,
 				instance,
-			} => instance::launch(instance, account, offline, quick_play, data).await,
+			} => instance::launch(instance, account, offline, quick_play, print_cmd, data).await,
 			Command::Files { command } => files::run(command, &mut data).await,
+			Command::Net { command } => net::run(command, &mut data).await,
+			Command::Notification { command } => notification::run(command, &mut data).await,
 			Command::Package { command } => package::run(command, data).await,
 			Command::Instance { command } => instance::run(command, data).await,
 			Command::Plugin { command } => plugin::run(command, &mut data).await,
@@ -260,16 +318,47 @@ Would you like to do that now?"
 			} => migrate(format, instances, copy, &mut data).await,
 			Command::Log { command } => log::run(command, &mut data).await,
 			Command::Try { command } => r#try::run(command, &mut data).await,
+			Command::Inventory { instance, output } => {
+				instance::inventory(&mut data, instance, output).await
+			}
+			Command::SelfUpdate { check, beta } => {
+				self_update::self_update(&mut data, check, beta).await
+			}
 			Command::Version { command } => version::run(command, &mut data).await,
 			Command::External(args) => call_plugin_subcommand(args, None, &mut data).await,
 		}
 	};
 
+	// Save stats about requests made during the command so that they can be shown later
+	if let Err(e) = net_stats::save(&report_paths) {
+		output.display(MessageContents::Warning(format!("{e:?}")));
+	}
+
+	// Point out events from the GUI or earlier commands that haven't been seen yet
+	if !is_notification_command && let Ok(store) = NotificationStore::open(&report_paths) {
+		let unread = store.unread_count();
+		if unread > 0 {
+			output.display(MessageContents::Notice(format!(
+				"You have {unread} unread notifications. Use `nitro notification list` to see them"
+			)));
+		}
+	}
+
 	if let Err(e) = &res {
 		// Don't use the existing process or section
 		output.end_process();
 		output.end_section();
 		output.display(MessageContents::Error(format!("{e:?}")));
+
+		// Empty errors are used to exit after the problem was already shown
+		if !e.to_string().is_empty() {
+			// Only the subcommand name is recorded, since its arguments can contain private information
+			let subcommand = std::env::args()
+				.skip(1)
+				.find(|x| !x.starts_with('-'))
+				.unwrap_or_default();
+			reports::report_error(&format!("nitro {subcommand}"), e, &report_paths);
+		}
 	}
 
 	res
@@ -291,14 +380,27 @@ pub struct CmdData<'a> {
 	pub paths: Paths,
 	pub config: Later<Config>,
 	pub output: &'a mut TerminalOutput,
+	/// Client for network requests, which uses the configured proxy
+	pub client: Client,
 }
 
 impl<'a> CmdData<'a> {
 	pub fn new(paths: Paths, output: &'a mut TerminalOutput) -> anyhow::Result<Self> {
+		let client = match Config::create_client(&paths) {
+			Ok(client) => client,
+			Err(e) => {
+				output.display(MessageContents::Warning(format!(
+					"Proxy will not be used: {e:?}"
+				)));
+				Client::new()
+			}
+		};
+
 		Ok(Self {
 			paths,
 			config: Later::new(),
 			output,
+			client,
 		})
 	}
 
@@ -321,6 +423,14 @@ impl<'a> CmdData<'a> {
 				.await
 				.context("Failed to load config")?,
 			);
+
+			// There is usually no browser to log in with over SSH
+			if is_ssh_session() {
+				self.config
+					.get_mut()
+					.accounts
+					.set_ms_login_method(MicrosoftLoginMethod::DeviceCode);
+			}
 		}
 
 		// Update the translation map from plugins
@@ -351,6 +461,11 @@ impl<'a> CmdData<'a> {
 	}
 }
 
+/// Checks whether the CLI is being used over SSH
+fn is_ssh_session() -> bool {
+	std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
 /// Print the Nitrolaunch version
 fn print_version() {
 	cprintln!("Nitrolaunch version: <g>{}</g>", nitrolaunch::VERSION);
@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use anyhow::{Context, bail};
 use clap::Subcommand;
 use nitrolaunch::{
@@ -6,10 +8,22 @@ use nitrolaunch::{
 		modifications::{ConfigModification, apply_modifications_and_write},
 	},
 	config_crate::instance::InstanceConfig,
-	instance::{Instance, update::manager::UpdateSettings},
+	instance::{
+		Instance,
+		changelog::Changelog,
+		publish::{MrpackSettings, PublishSettings, publish_mrpack},
+		update::manager::UpdateSettings,
+	},
+	instance_crate::lock::InstanceLockfile,
 	io::paths::Paths,
+	net_crate::modrinth::ReleaseChannel,
 	pkg_crate::{PkgRequest, PkgRequestSource},
-	shared::{Side, UpdateDepth, id::InstanceID, output::NitroOutput, pkg::ArcPkgReq},
+	shared::{
+		Side, UpdateDepth,
+		id::InstanceID,
+		output::{MessageContents, NitroOutput},
+		pkg::ArcPkgReq,
+	},
 };
 use reqwest::Client;
 
@@ -19,6 +33,9 @@ use crate::{
 	secrets::get_ms_client_id,
 };
 
+/// Environment variable containing the API token used to publish to Modrinth
+const MODRINTH_TOKEN_VAR: &str = "MODRINTH_TOKEN";
+
 #[derive(Debug, Subcommand)]
 pub enum ModpackSubcommand {
 	#[command(about = "Create an instance from a modpack package")]
@@ -31,6 +48,56 @@ pub enum ModpackSubcommand {
 		#[arg(short, long)]
 		side: Option<Side>,
 	},
+	#[command(about = "Generate a changelog between two snapshots of an instance lockfile")]
+	Changelog {
+		/// The path to the old lockfile
+		old: String,
+		/// The path to the new lockfile
+		new: String,
+		/// Output JSON instead of Markdown
+		#[arg(short, long)]
+		json: bool,
+		/// Don't look up the names and links of packages
+		#[arg(long)]
+		no_links: bool,
+		/// A file to write the changelog to instead of printing it
+		#[arg(short, long)]
+		output: Option<String>,
+	},
+	#[command(
+		about = "Build an instance into a Modrinth pack and upload it as a new version of a project"
+	)]
+	Publish {
+		/// The instance to publish
+		instance: String,
+		/// The ID or slug of the Modrinth project
+		#[arg(short, long)]
+		project: Option<String>,
+		/// The version number of the new version
+		#[arg(short, long)]
+		version: String,
+		/// The name of the pack. Defaults to the ID of the instance
+		#[arg(short, long)]
+		name: Option<String>,
+		/// The release channel of the version: release, beta, or alpha
+		#[arg(short = 't', long, default_value = "release")]
+		version_type: String,
+		/// A Markdown file to use as the changelog of the version
+		#[arg(short, long)]
+		changelog: Option<String>,
+		/// Generate the changelog by comparing the instance to an older snapshot of its lockfile
+		#[arg(long)]
+		changelog_from: Option<String>,
+		/// Files and directories in the instance to include in the pack, such as config
+		#[arg(long = "override")]
+		overrides: Vec<String>,
+		/// Where to save the pack. Defaults to the current directory
+		#[arg(short, long)]
+		output: Option<String>,
+		/// Only build the pack without uploading it
+		#[arg(long)]
+		dry_run: bool,
+	},
 	#[clap(external_subcommand)]
 	External(Vec<String>),
 }
@@ -42,12 +109,192 @@ pub async fn run(command: ModpackSubcommand, data: &mut CmdData<'_>) -> anyhow::
 			instance,
 			side,
 		} => install(data, modpack, instance, side).await,
+		ModpackSubcommand::Changelog {
+			old,
+			new,
+			json,
+			no_links,
+			output,
+		} => changelog(data, old, new, json, no_links, output).await,
+		ModpackSubcommand::Publish {
+			instance,
+			project,
+			version,
+			name,
+			version_type,
+			changelog,
+			changelog_from,
+			overrides,
+			output,
+			dry_run,
+		} => {
+			publish(
+				data,
+				instance,
+				project,
+				version,
+				name,
+				version_type,
+				changelog,
+				changelog_from,
+				overrides,
+				output,
+				dry_run,
+			)
+			.await
+		}
 		ModpackSubcommand::External(args) => {
 			call_plugin_subcommand(args, Some("modpack"), data).await
 		}
 	}
 }
 
+async fn changelog(
+	data: &mut CmdData<'_>,
+	old: String,
+	new: String,
+	json: bool,
+	no_links: bool,
+	output: Option<String>,
+) -> anyhow::Result<()> {
+	let mut changelog = Changelog::from_files(Path::new(&old), Path::new(&new))?;
+
+	if !no_links {
+		data.ensure_config(true).await?;
+		let config = data.config.get();
+		let client = data.client.clone();
+		changelog
+			.fill_package_info(&config.packages, &data.paths, &client, data.output)
+			.await;
+	}
+
+	let changelog = if json {
+		serde_json::to_string_pretty(&changelog).context("Failed to serialize changelog")?
+	} else {
+		changelog.to_markdown()
+	};
+
+	if let Some(output) = output {
+		std::fs::write(&output, changelog).context("Failed to write changelog")?;
+		data.output.display(MessageContents::Success(format!(
+			"Changelog saved to {output}"
+		)));
+	} else {
+		println!("{changelog}");
+	}
+
+	Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn publish(
+	data: &mut CmdData<'_>,
+	instance: String,
+	project: Option<String>,
+	version: String,
+	name: Option<String>,
+	version_type: String,
+	changelog: Option<String>,
+	changelog_from: Option<String>,
+	overrides: Vec<String>,
+	output: Option<String>,
+	dry_run: bool,
+) -> anyhow::Result<()> {
+	let version_type = match version_type.as_str() {
+		"release" => ReleaseChannel::Release,
+		"beta" => ReleaseChannel::Beta,
+		"alpha" => ReleaseChannel::Alpha,
+		other => bail!("Unknown version type '{other}'. Use release, beta, or alpha"),
+	};
+
+	// Check the upload settings before spending time building the pack
+	let (project, token) = if dry_run {
+		(String::new(), String::new())
+	} else {
+		let project = project.context("A project is required to publish. Use --project")?;
+		let token = std::env::var(MODRINTH_TOKEN_VAR)
+			.ok()
+			.filter(|x| !x.is_empty())
+			.with_context(|| {
+				format!("Set the {MODRINTH_TOKEN_VAR} environment variable to a Modrinth API token")
+			})?;
+		(project, token)
+	};
+
+	data.ensure_config(true).await?;
+	let config = data.config.get();
+	let instance_id = InstanceID::from(instance);
+	let instance = config
+		.instances
+		.get(&instance_id)
+		.with_context(|| format!("Unknown instance '{instance_id}'"))?;
+
+	let mut changelog = if let Some(changelog) = changelog {
+		Some(std::fs::read_to_string(changelog).context("Failed to read changelog")?)
+	} else {
+		None
+	};
+	let client = data.client.clone();
+	if let Some(old_lock) = changelog_from {
+		let current_lock =
+			InstanceLockfile::get_path(instance.dir(), &instance_id, &data.paths.internal);
+		let mut generated = Changelog::from_files(Path::new(&old_lock), &current_lock)?;
+		generated
+			.fill_package_info(&config.packages, &data.paths, &client, data.output)
+			.await;
+		changelog = Some(generated.to_markdown());
+	}
+
+	let settings = MrpackSettings {
+		name: name.unwrap_or_else(|| instance_id.to_string()),
+		version,
+		summary: None,
+		overrides,
+	};
+	let path = if let Some(output) = output {
+		PathBuf::from(output)
+	} else {
+		std::env::current_dir()?.join(format!("{}-{}.mrpack", settings.name, settings.version))
+	};
+
+	let mut process = data.output.get_process();
+	process.display(MessageContents::StartProcess("Building pack".into()));
+	let pack = instance
+		.build_mrpack(&settings, &path, &data.paths, &client)
+		.await
+		.context("Failed to build pack")?;
+	process.display(MessageContents::Success(format!(
+		"Pack saved to {} with {} downloaded and {} included files",
+		path.display(),
+		pack.downloaded_files,
+		pack.override_files
+	)));
+	process.finish();
+
+	if dry_run {
+		return Ok(());
+	}
+
+	let mut process = data.output.get_process();
+	process.display(MessageContents::StartProcess(
+		"Uploading pack to Modrinth".into(),
+	));
+	let publish = PublishSettings {
+		project_id: project,
+		changelog,
+		version_type,
+		featured: false,
+		token,
+	};
+	let version = publish_mrpack(&pack, &settings, &publish, &client).await?;
+	process.display(MessageContents::Success(format!(
+		"Published version {} ({})",
+		version.version_number, version.id
+	)));
+
+	Ok(())
+}
+
 async fn install(
 	data: &mut CmdData<'_>,
 	modpack: String,
@@ -71,6 +318,7 @@ async fn install(
 		side,
 		config,
 		&data.paths,
+		&data.client,
 		data.output,
 	)
 	.await?;
@@ -99,10 +347,9 @@ pub async fn install_into_config(
 	side: Option<Side>,
 	config: &Config,
 	paths: &Paths,
+	client: &Client,
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<InstanceConfig> {
-	let client = Client::new();
-
 	if config.instances.contains_key(&instance) {
 		bail!("An instance with that ID already exists");
 	}
@@ -116,7 +363,7 @@ pub async fn install_into_config(
 				depth: UpdateDepth::Shallow,
 				offline_auth: false,
 			},
-			&client,
+			client,
 			&config.plugins,
 			paths,
 			o,
@@ -134,7 +381,7 @@ pub async fn install_into_config(
 		version_manifest.list.clone(),
 		&config.packages,
 		&config.plugins,
-		&client,
+		client,
 		paths,
 		o,
 	)
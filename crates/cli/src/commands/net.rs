@@ -0,0 +1,79 @@
+use anyhow::Context;
+use clap::Subcommand;
+use color_print::cprintln;
+use nitrolaunch::core::net::stats::HostStats;
+use nitrolaunch::io::net_stats;
+use nitrolaunch::shared::util::format_bytes;
+
+use super::CmdData;
+
+#[derive(Debug, Subcommand)]
+pub enum NetSubcommand {
+	#[command(about = "Print statistics about requests made to each host")]
+	Stats {
+		/// Clear all of the recorded statistics
+		#[arg(long)]
+		reset: bool,
+		/// Print the statistics as JSON
+		#[arg(long)]
+		json: bool,
+	},
+}
+
+pub async fn run(subcommand: NetSubcommand, data: &mut CmdData<'_>) -> anyhow::Result<()> {
+	match subcommand {
+		NetSubcommand::Stats { reset, json } => stats(data, reset, json).await,
+	}
+}
+
+async fn stats(data: &mut CmdData<'_>, reset: bool, json: bool) -> anyhow::Result<()> {
+	if reset {
+		net_stats::reset(&data.paths)?;
+		cprintln!("<s>Network statistics cleared");
+		return Ok(());
+	}
+
+	let stats = net_stats::load(&data.paths)?;
+	if json {
+		let out = serde_json::to_string_pretty(&stats).context("Failed to serialize stats")?;
+		println!("{out}");
+		return Ok(());
+	}
+
+	if stats.hosts.is_empty() {
+		cprintln!("<s>No network statistics recorded yet");
+		return Ok(());
+	}
+
+	// Show the busiest hosts first
+	let mut hosts: Vec<_> = stats.hosts.iter().collect();
+	hosts.sort_by_key(|(_, x)| std::cmp::Reverse(x.requests + x.cache_hits));
+
+	for (host, stats) in hosts {
+		print_host_stats(host, stats);
+	}
+	print_host_stats("Total", &stats.total());
+
+	Ok(())
+}
+
+/// Prints the statistics for a single host
+fn print_host_stats(host: &str, stats: &HostStats) {
+	cprintln!("<s>{}</>", host);
+	cprintln!(
+		"   <s>Requests:</> {} ({} errors, {:.1}% error rate)",
+		stats.requests,
+		stats.errors,
+		stats.error_rate() * 100.0
+	);
+	cprintln!("   <s>Downloaded:</> {}", format_bytes(stats.bytes));
+	cprintln!(
+		"   <s>Cache hits:</> {} ({:.1}% hit ratio)",
+		stats.cache_hits,
+		stats.cache_hit_ratio() * 100.0
+	);
+	cprintln!(
+		"   <s>Average response time:</> {}ms",
+		stats.average_response_time_ms()
+	);
+}
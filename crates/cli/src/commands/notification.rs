@@ -0,0 +1,107 @@
+use anyhow::{Context, bail};
+use clap::Subcommand;
+use color_print::cprintln;
+use nitrolaunch::io::notifications::{Notification, NotificationStore};
+
+use super::CmdData;
+
+#[derive(Debug, Subcommand)]
+pub enum NotificationSubcommand {
+	#[command(about = "List unread notifications about background events")]
+	#[clap(alias = "ls")]
+	List {
+		/// Include notifications that have already been read
+		#[arg(short, long)]
+		all: bool,
+		/// Print the notifications as JSON
+		#[arg(long)]
+		json: bool,
+		/// Don't mark the listed notifications as read
+		#[arg(long)]
+		keep_unread: bool,
+	},
+	#[command(about = "Mark notifications as read")]
+	Read {
+		/// The ID of the notification to mark. If not specified, all of them will be marked
+		id: Option<u64>,
+	},
+	#[command(about = "Remove all notifications")]
+	Clear,
+}
+
+pub async fn run(subcommand: NotificationSubcommand, data: &mut CmdData<'_>) -> anyhow::Result<()> {
+	match subcommand {
+		NotificationSubcommand::List {
+			all,
+			json,
+			keep_unread,
+		} => list(data, all, json, keep_unread).await,
+		NotificationSubcommand::Read { id } => read(data, id).await,
+		NotificationSubcommand::Clear => clear(data).await,
+	}
+}
+
+async fn list(
+	data: &mut CmdData<'_>,
+	all: bool,
+	json: bool,
+	keep_unread: bool,
+) -> anyhow::Result<()> {
+	let _lock = NotificationStore::lock(&data.paths)?;
+	let mut store = NotificationStore::open(&data.paths)?;
+	let notifications: Vec<_> = store.iter().filter(|x| all || !x.read).collect();
+
+	if json {
+		let out = serde_json::to_string_pretty(&notifications)
+			.context("Failed to serialize notifications")?;
+		println!("{out}");
+	} else if notifications.is_empty() {
+		cprintln!("<s>No new notifications");
+	} else {
+		for notification in notifications {
+			print_notification(notification);
+		}
+	}
+
+	if !keep_unread && store.unread_count() > 0 {
+		store.mark_all_read();
+		store.write(&data.paths)?;
+	}
+
+	Ok(())
+}
+
+async fn read(data: &mut CmdData<'_>, id: Option<u64>) -> anyhow::Result<()> {
+	let _lock = NotificationStore::lock(&data.paths)?;
+	let mut store = NotificationStore::open(&data.paths)?;
+	if let Some(id) = id {
+		if !store.mark_read(id) {
+			bail!("Notification {id} does not exist");
+		}
+	} else {
+		store.mark_all_read();
+	}
+	store.write(&data.paths)?;
+
+	Ok(())
+}
+
+async fn clear(data: &mut CmdData<'_>) -> anyhow::Result<()> {
+	let _lock = NotificationStore::lock(&data.paths)?;
+	let mut store = NotificationStore::open(&data.paths)?;
+	store.clear();
+	store.write(&data.paths)?;
+	cprintln!("<s>Notifications cleared");
+
+	Ok(())
+}
+
+/// Prints a single notification
+fn print_notification(notification: &Notification) {
+	if notification.read {
+		cprintln!("<k!>[{}]</> <s>{}</>", notification.id, notification.title);
+	} else {
+		cprintln!("<y>[{}]</> <s>{}</>", notification.id, notification.title);
+	}
+	println!("   {}", notification.message);
+}
@@ -86,7 +86,7 @@ pub async fn run(
 		.flatten_all_results(&mut NoOp)
 		.await?;
 
-	let client = Client::new();
+	let client = data.client.clone();
 	let core = data
 		.config
 		.get()
@@ -117,6 +117,7 @@ pub async fn run(
 	let state = State::new(
 		data.config.take().unwrap(),
 		data.paths.clone(),
+		client,
 		filters,
 		repos,
 		versions,
@@ -231,9 +232,11 @@ struct State<'a> {
 
 impl<'a> State<'a> {
 	/// Initialize state with widgets and worker thread
+	#[allow(clippy::too_many_arguments)]
 	fn new(
 		config: Config,
 		paths: Paths,
+		client: Client,
 		filters: PackageSearchParameters,
 		repositories: Vec<AddCustomPackageRepositoriesResult>,
 		versions: Vec<String>,
@@ -251,6 +254,7 @@ impl<'a> State<'a> {
 		let handle = tokio::spawn(worker_thread(
 			config,
 			paths,
+			client.clone(),
 			state_tx,
 			task_rx,
 			results_tx,
@@ -279,7 +283,7 @@ impl<'a> State<'a> {
 			results_rx,
 			package_info_rx,
 			package_info: None,
-			image_cache: ImageCache::new(Client::new()),
+			image_cache: ImageCache::new(client),
 			image_available: Arc::new(AtomicBool::new(false)),
 			results: PackageSearchResults::default(),
 			search_params: SearchParams {
@@ -1610,13 +1614,12 @@ impl<'a> InstallPromptState<'a> {
 async fn worker_thread(
 	config: Config,
 	paths: Paths,
+	client: Client,
 	state_tx: Sender<WorkerState>,
 	mut task_rx: Receiver<Task>,
 	results_tx: Sender<PackageSearchResults>,
 	package_info_tx: Sender<PackageInfo>,
 ) {
-	let client = Client::new();
-
 	loop {
 		let Some(task) = task_rx.recv().await else {
 			continue;
@@ -1719,6 +1722,7 @@ async fn worker_thread(
 							Some(Side::Client),
 							&config,
 							&paths,
+							&client,
 							&mut NoOp,
 						)
 						.await;
@@ -17,9 +17,10 @@ use nitrolaunch::shared::output::{MessageContents, NitroOutput};
 use anyhow::{Context, bail};
 use clap::Subcommand;
 use color_print::{cformat, cprint, cprintln};
-use nitrolaunch::shared::pkg::{PackageID, PackageKind, PackageSearchParameters};
+use nitrolaunch::shared::pkg::{
+	PackageID, PackageKind, PackageSearchParameters, PackageSortMethod,
+};
 use nitrolaunch::shared::util::from_string_json;
-use reqwest::Client;
 use serde::Serialize;
 
 use crate::commands::call_plugin_subcommand;
@@ -89,6 +90,16 @@ This package does not need to be installed, it just has to be in the index."
 		/// The package to get info about
 		package: String,
 	},
+	#[command(
+		about = "Show which Minecraft versions and loaders each version of a package supports"
+	)]
+	Compat {
+		/// Whether to output the compatibility matrix as JSON
+		#[arg(short, long)]
+		raw: bool,
+		/// The package to get compatibility for
+		package: String,
+	},
 	#[command(about = "Query information about configured packages repositories")]
 	#[clap(alias = "repo")]
 	Repository {
@@ -127,6 +138,9 @@ This package does not need to be installed, it just has to be in the index."
 		/// The categories to search for
 		#[arg(short = 'c', long = "category")]
 		categories: Vec<String>,
+		/// How to sort the results. One of relevance, downloads, followers, updated, or newest
+		#[arg(short = 's', long)]
+		sort: Option<String>,
 	},
 	#[clap(external_subcommand)]
 	External(Vec<String>),
@@ -160,6 +174,7 @@ pub async fn run(subcommand: PackageSubcommand, mut data: CmdData<'_>) -> anyhow
 		PackageSubcommand::Cat { raw, package } => cat(&mut data, &package, raw).await,
 		PackageSubcommand::Info { raw, package } => info(&mut data, &package, raw).await,
 		PackageSubcommand::Versions { raw, package } => versions(&mut data, &package, raw).await,
+		PackageSubcommand::Compat { raw, package } => compat(&mut data, &package, raw).await,
 		PackageSubcommand::Repository { command } => repo(command, &mut data).await,
 		PackageSubcommand::ListAll {} => list_all(&mut data).await,
 		PackageSubcommand::Add { package, instance } => add(&mut data, package, instance).await,
@@ -171,6 +186,7 @@ pub async fn run(subcommand: PackageSubcommand, mut data: CmdData<'_>) -> anyhow
 			versions,
 			loaders,
 			categories,
+			sort,
 		} => {
 			let types = types
 				.into_iter()
@@ -187,6 +203,12 @@ pub async fn run(subcommand: PackageSubcommand, mut data: CmdData<'_>) -> anyhow
 				.filter_map(|x| from_string_json(&x).ok())
 				.collect();
 
+			let sort = if let Some(sort) = sort {
+				from_string_json(&sort).context("Invalid sort method")?
+			} else {
+				PackageSortMethod::default()
+			};
+
 			search(
 				&mut data,
 				PackageSearchParameters {
@@ -197,6 +219,9 @@ pub async fn run(subcommand: PackageSubcommand, mut data: CmdData<'_>) -> anyhow
 					minecraft_versions: versions,
 					loaders,
 					categories,
+					sort,
+					cursor: None,
+					allow_stale: false,
 				},
 				repo,
 			)
@@ -264,7 +289,7 @@ async fn sync(data: &mut CmdData<'_>, filter: Vec<String>) -> anyhow::Result<()>
 		.display(MessageContents::Header("Syncing Repositories".into()));
 	let mut section = data.output.get_section();
 
-	let client = Client::new();
+	let client = data.client.clone();
 	for repo in config.packages.repos.iter() {
 		// Skip repositories not in the filter
 		if !filter.is_empty() && !filter.contains(&repo.get_id().to_string()) {
@@ -318,7 +343,7 @@ async fn cat(data: &mut CmdData<'_>, id: &str, raw: bool) -> anyhow::Result<()>
 	data.ensure_config(!raw).await?;
 	let config = data.config.get_mut();
 
-	let client = Client::new();
+	let client = data.client.clone();
 
 	let req = Arc::new(PkgRequest::parse(id, PkgRequestSource::UserRequire));
 	let package = config
@@ -422,7 +447,7 @@ async fn info(data: &mut CmdData<'_>, id: &str, raw: bool) -> anyhow::Result<()>
 	data.ensure_config(true).await?;
 	let config = data.config.get_mut();
 
-	let client = Client::new();
+	let client = data.client.clone();
 
 	let req = Arc::new(PkgRequest::parse(id, PkgRequestSource::UserRequire));
 	let package = config
@@ -510,6 +535,12 @@ async fn info(data: &mut CmdData<'_>, id: &str, raw: bool) -> anyhow::Result<()>
 	{
 		cprintln!("   <s>License:</s> <b!>{}", license);
 	}
+	if let Some(downloads) = &metadata.downloads {
+		cprintln!("   <s>Downloads:</s> <g>{}", downloads);
+	}
+	if let Some(followers) = &metadata.followers {
+		cprintln!("   <s>Followers:</s> <g>{}", followers);
+	}
 
 	Ok(())
 }
@@ -518,7 +549,7 @@ async fn versions(data: &mut CmdData<'_>, id: &str, raw: bool) -> anyhow::Result
 	data.ensure_config(true).await?;
 	let config = data.config.get_mut();
 
-	let client = Client::new();
+	let client = data.client.clone();
 
 	let req = Arc::new(PkgRequest::parse(id, PkgRequestSource::UserRequire));
 
@@ -550,6 +581,51 @@ async fn versions(data: &mut CmdData<'_>, id: &str, raw: bool) -> anyhow::Result
 	Ok(())
 }
 
+async fn compat(data: &mut CmdData<'_>, id: &str, raw: bool) -> anyhow::Result<()> {
+	data.ensure_config(!raw).await?;
+	let config = data.config.get_mut();
+
+	let client = data.client.clone();
+
+	let req = Arc::new(PkgRequest::parse(id, PkgRequestSource::UserRequire));
+
+	let package = config
+		.packages
+		.get(&req, &data.paths, &client, data.output)
+		.await?;
+	let Some(matrix) = package
+		.get_compatibility_matrix(&data.paths, &client)
+		.await?
+	else {
+		bail!("Compatibility information is only available for declarative packages");
+	};
+
+	if raw {
+		let out = serde_json::to_string(&matrix).context("Failed to serialize matrix")?;
+		print!("{out}");
+
+		return Ok(());
+	}
+
+	cprintln!("<s>Compatibility for package <b>{}</>:", id);
+	for entry in matrix.entries {
+		let version = entry.content_version.as_deref().unwrap_or("Unknown");
+		let minecraft_versions = match entry.minecraft_versions {
+			Some(versions) => versions.iter().join(", "),
+			None => "Any".into(),
+		};
+		let loaders = match entry.loaders {
+			Some(loaders) => loaders.iter().join(", "),
+			None => "Any".into(),
+		};
+		cprintln!("{}<s>{}</>", HYPHEN_POINT, version);
+		cprintln!("   <s>Minecraft:</> {}", minecraft_versions);
+		cprintln!("   <s>Loaders:</> {}", loaders);
+	}
+
+	Ok(())
+}
+
 async fn repo(subcommand: RepoSubcommand, data: &mut CmdData<'_>) -> anyhow::Result<()> {
 	match subcommand {
 		RepoSubcommand::List { raw } => repo_list(data, raw).await,
@@ -598,7 +674,7 @@ async fn repo_info(data: &mut CmdData<'_>, repo_id: String) -> anyhow::Result<()
 	};
 
 	// Get the repo package count and metadata
-	let client = Client::new();
+	let client = data.client.clone();
 
 	let pkg_count = repo
 		.get_package_count(&data.paths, &client, data.output)
@@ -649,7 +725,7 @@ async fn list_all(data: &mut CmdData<'_>) -> anyhow::Result<()> {
 	data.ensure_config(true).await?;
 	let config = data.config.get_mut();
 
-	let client = Client::new();
+	let client = data.client.clone();
 	let mut packages = config
 		.packages
 		.get_all_available_packages(&data.paths, &client, data.output)
@@ -672,7 +748,7 @@ async fn add(
 	data.ensure_config(true).await?;
 	let config = data.config.get_mut();
 
-	let client = Client::new();
+	let client = data.client.clone();
 	let mut packages = config
 		.packages
 		.get_all_available_packages(&data.paths, &client, data.output)
@@ -721,14 +797,53 @@ async fn search(
 	data.ensure_config(true).await?;
 	let config = data.config.get_mut();
 
-	let client = Client::new();
-	let results = config
+	let sort = params.sort;
+	let count = params.count as usize;
+
+	let client = data.client.clone();
+
+	// Search all of the repositories at once and show where each package was found
+	if repo.is_none() {
+		let results = config
+			.packages
+			.search_all(params, &data.paths, &client, data.output)
+			.await
+			.context("Failed to search packages")?;
+
+		for (repo, error) in results.errors.iter().sorted() {
+			data.output.display(MessageContents::Warning(format!(
+				"Failed to search repository {repo}: {error}"
+			)));
+		}
+
+		for result in results.results.into_iter().take(count) {
+			if result.sources.len() > 1 {
+				let sources = result
+					.sources
+					.iter()
+					.map(|x| x.repository.as_str())
+					.join(", ");
+				cprintln!("{HYPHEN_POINT}{} <s>({sources})", result.package);
+			} else {
+				cprintln!("{HYPHEN_POINT}{}", result.package);
+			}
+		}
+
+		return Ok(());
+	}
+
+	let mut results = config
 		.packages
 		.search(params, repo.as_deref(), &data.paths, &client, data.output)
 		.await
 		.context("Failed to search packages")?;
 
-	for package in results.results.into_iter().sorted() {
+	// Keep the order from the repositories if a sort method was chosen
+	if sort == PackageSortMethod::Relevance {
+		results.results.sort();
+	}
+
+	for package in results.results {
 		cprintln!("{HYPHEN_POINT}{package}");
 	}
 
@@ -2,7 +2,7 @@ use anyhow::{Context, bail};
 use clap::Subcommand;
 use color_print::cprintln;
 use itertools::Itertools;
-use nitrolaunch::core::io::{json_from_file, json_to_file_pretty};
+use nitrolaunch::core::io::{json_from_file, json_to_file_atomic};
 use nitrolaunch::plugin::PluginManager;
 use nitrolaunch::plugin::install::get_verified_plugins;
 use nitrolaunch::plugin_crate::plugin::PluginManifest;
@@ -10,7 +10,6 @@ use nitrolaunch::shared::lang::translate::TranslationKey;
 use nitrolaunch::shared::output::{MessageContents, NitroOutput};
 use nitrolaunch::shared::translate;
 use nitrolaunch::shared::versions::parse_single_versioned_string;
-use reqwest::Client;
 use std::ops::DerefMut;
 use std::path::PathBuf;
 
@@ -64,6 +63,16 @@ pub enum PluginSubcommand {
 		/// The plugin to edit. Omit it to edit the plugins file
 		plugin: Option<String>,
 	},
+	#[command(
+		about = "Encrypt a field in the config of a plugin with the launcher passphrase",
+		long_about = "Encrypt a field in the config of a plugin in plugins.json with the launcher passphrase. The field is a dot-separated path to a string value, like token. It will only be decrypted in memory when an instance is launched"
+	)]
+	Encrypt {
+		/// The plugin to encrypt the field of
+		plugin: String,
+		/// The path to the field to encrypt
+		field: String,
+	},
 	#[clap(external_subcommand)]
 	External(Vec<String>),
 }
@@ -83,6 +92,7 @@ pub async fn run(command: PluginSubcommand, data: &mut CmdData<'_>) -> anyhow::R
 		PluginSubcommand::Enable { plugin } => enable(data, plugin).await,
 		PluginSubcommand::Disable { plugin } => disable(data, plugin).await,
 		PluginSubcommand::Edit { plugin } => edit(data, plugin).await,
+		PluginSubcommand::Encrypt { plugin, field } => encrypt(data, plugin, field).await,
 		PluginSubcommand::External(args) => {
 			call_plugin_subcommand(args, Some("plugin"), data).await
 		}
@@ -168,7 +178,7 @@ pub(crate) async fn install(
 		bail!("No plugins were provided to install");
 	}
 
-	let client = Client::new();
+	let client = data.client.clone();
 
 	let verified_list = get_verified_plugins(&client, false)
 		.await
@@ -273,7 +283,7 @@ pub(crate) async fn update(data: &mut CmdData<'_>, plugins: Vec<String>) -> anyh
 		plugins
 	};
 
-	let client = Client::new();
+	let client = data.client.clone();
 
 	let verified_list = get_verified_plugins(&client, false)
 		.await
@@ -307,7 +317,7 @@ pub(crate) async fn update(data: &mut CmdData<'_>, plugins: Vec<String>) -> anyh
 async fn browse(data: &mut CmdData<'_>) -> anyhow::Result<()> {
 	let _ = data;
 
-	let client = Client::new();
+	let client = data.client.clone();
 
 	let verified_list = get_verified_plugins(&client, false)
 		.await
@@ -347,6 +357,15 @@ async fn disable(data: &mut CmdData<'_>, plugin: String) -> anyhow::Result<()> {
 	Ok(())
 }
 
+async fn encrypt(data: &mut CmdData<'_>, plugin: String, field: String) -> anyhow::Result<()> {
+	PluginManager::encrypt_config_field(&plugin, &field, &data.paths, data.output).await?;
+
+	data.output
+		.display(MessageContents::Success("Field encrypted".into()));
+
+	Ok(())
+}
+
 async fn edit(data: &mut CmdData<'_>, id: Option<String>) -> anyhow::Result<()> {
 	let Some(id) = id else {
 		return edit_plugins(data).await;
@@ -373,7 +392,7 @@ async fn edit(data: &mut CmdData<'_>, id: Option<String>) -> anyhow::Result<()>
 
 	config.config.insert(id, new_config);
 
-	json_to_file_pretty(config_path, &config).context("Failed to write to plugin config file")?;
+	json_to_file_atomic(config_path, &config).context("Failed to write to plugin config file")?;
 
 	data.output
 		.display(MessageContents::Success("Changes saved".into()));
@@ -0,0 +1,65 @@
+use anyhow::Context;
+use nitrolaunch::config_crate::preferences::UpdateChannel;
+use nitrolaunch::io::self_update::{
+	InstallResult, check_for_update, download_update, install_update,
+};
+use nitrolaunch::shared::output::{MessageContents, NitroOutput};
+
+use super::CmdData;
+
+/// Updates the CLI to the newest version in the configured channel
+pub async fn self_update(data: &mut CmdData<'_>, check: bool, beta: bool) -> anyhow::Result<()> {
+	let channel = if beta {
+		UpdateChannel::Beta
+	} else {
+		data.get_raw_config()
+			.map(|x| x.preferences.update_channel)
+			.unwrap_or_default()
+	};
+
+	let client = data.client.clone();
+	let update = check_for_update(channel, &client)
+		.await
+		.context("Failed to check for updates")?;
+
+	let Some(update) = update else {
+		data.output.display(MessageContents::Success(format!(
+			"Nitrolaunch is up to date ({})",
+			nitrolaunch::VERSION
+		)));
+		return Ok(());
+	};
+
+	let prerelease = if update.prerelease {
+		" (prerelease)"
+	} else {
+		""
+	};
+	data.output.display(MessageContents::Header(format!(
+		"Nitrolaunch {}{prerelease} is available. You are on {}",
+		update.version,
+		nitrolaunch::VERSION
+	)));
+	if check {
+		return Ok(());
+	}
+
+	let mut process = data.output.get_process();
+	process.display(MessageContents::StartProcess(format!(
+		"Downloading Nitrolaunch {}",
+		update.version
+	)));
+	let binary = download_update(&update, &client).await?;
+	let result = install_update(&binary).context("Failed to install update")?;
+
+	let message = match result {
+		InstallResult::Replaced => format!("Updated to Nitrolaunch {}", update.version),
+		InstallResult::Staged => format!(
+			"Nitrolaunch {} will be installed the next time you run a command",
+			update.version
+		),
+	};
+	process.display(MessageContents::Success(message));
+
+	Ok(())
+}
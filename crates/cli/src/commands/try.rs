@@ -20,7 +20,6 @@ use nitrolaunch::{
 		versions::MinecraftVersionDeser,
 	},
 };
-use reqwest::Client;
 
 use crate::{
 	commands::{CmdData, call_plugin_subcommand},
@@ -83,6 +82,7 @@ pub async fn run(command: TrySubcommand, data: &mut CmdData<'_>) -> anyhow::Resu
 				Some(Side::Client),
 				config,
 				&data.paths,
+				&data.client,
 				data.output,
 			)
 			.await?;
@@ -101,7 +101,7 @@ pub async fn run(command: TrySubcommand, data: &mut CmdData<'_>) -> anyhow::Resu
 	};
 
 	let mut lock = Lockfile::open(&data.paths)?;
-	let client = Client::new();
+	let client = data.client.clone();
 
 	let core = config
 		.get_core(
@@ -138,7 +138,7 @@ pub async fn run(command: TrySubcommand, data: &mut CmdData<'_>) -> anyhow::Resu
 	let handle = instance
 		.launch(settings, &mut ctx)
 		.await
-		.context("Failed to launch instance")?;
+		.map_err(|e| e.context("Failed to launch instance"))?;
 
 	handle
 		.wait(&config.plugins, &data.paths, data.output)
@@ -171,7 +171,8 @@ pub async fn run(command: TrySubcommand, data: &mut CmdData<'_>) -> anyhow::Resu
 			"Removing instance files".into(),
 		));
 
-		instance.delete_files().await?;
+		// Temporary instances don't need to be kept in the trash
+		instance.delete_files(true).await?;
 
 		process.display(MessageContents::Success(
 			"Temporary instance removed".into(),
@@ -2,9 +2,13 @@ use clap::Subcommand;
 use color_print::{cprint, cprintln};
 use nitrolaunch::{
 	instance::update::manager::UpdateSettings,
-	shared::{UpdateDepth, minecraft::VersionType},
+	plugin_crate::hook::hooks::{GetLoaderVersions, GetLoaderVersionsArg},
+	shared::{
+		UpdateDepth,
+		loaders::{Loader, LoaderVersionChannel},
+		minecraft::VersionType,
+	},
 };
-use reqwest::Client;
 
 use crate::{
 	commands::{CmdData, call_plugin_subcommand},
@@ -25,6 +29,16 @@ pub enum VersionSubcommand {
 		#[arg(short, long)]
 		snapshot: bool,
 	},
+	#[command(about = "List the available versions of a loader for a Minecraft version")]
+	Loader {
+		/// The loader to list the versions of
+		loader: String,
+		/// The Minecraft version
+		minecraft_version: String,
+		/// Whether to include unstable versions such as betas and release candidates
+		#[arg(short, long)]
+		beta: bool,
+	},
 	#[clap(external_subcommand)]
 	External(Vec<String>),
 }
@@ -36,6 +50,11 @@ pub async fn run(command: VersionSubcommand, data: &mut CmdData<'_>) -> anyhow::
 			release,
 			snapshot,
 		} => list(data, all, release, snapshot).await,
+		VersionSubcommand::Loader {
+			loader,
+			minecraft_version,
+			beta,
+		} => loader_versions(data, loader, minecraft_version, beta).await,
 		VersionSubcommand::External(args) => {
 			call_plugin_subcommand(args, Some("version"), data).await
 		}
@@ -51,7 +70,7 @@ async fn list(
 	data.ensure_config(true).await?;
 	let config = data.config.get();
 
-	let client = Client::new();
+	let client = data.client.clone();
 
 	let core = config
 		.get_core(
@@ -105,3 +124,38 @@ async fn list(
 
 	Ok(())
 }
+
+async fn loader_versions(
+	data: &mut CmdData<'_>,
+	loader: String,
+	minecraft_version: String,
+	beta: bool,
+) -> anyhow::Result<()> {
+	data.ensure_config(true).await?;
+	let config = data.config.get();
+
+	let arg = GetLoaderVersionsArg {
+		loader: Loader::parse_from_str(&loader),
+		minecraft_version,
+		channel: if beta {
+			LoaderVersionChannel::Beta
+		} else {
+			LoaderVersionChannel::Stable
+		},
+	};
+	let results = config
+		.plugins
+		.call_hook(GetLoaderVersions, &arg, &data.paths, data.output)
+		.await?;
+	let versions = results.flatten_all_results(data.output).await?;
+
+	if versions.is_empty() {
+		cprintln!("<y>No versions found");
+	}
+
+	for version in versions {
+		cprintln!("{HYPHEN_POINT}{version}");
+	}
+
+	Ok(())
+}
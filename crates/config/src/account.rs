@@ -16,9 +16,56 @@ pub enum AccountConfig {
 		/// The variant of the account
 		#[serde(rename = "type")]
 		variant: AccountVariant,
+		/// The number of minutes that a passkey entered for this account is remembered
+		/// without being used before the account is locked again
+		#[serde(default)]
+		#[serde(skip_serializing_if = "Option::is_none")]
+		auto_lock: Option<u64>,
+		/// The username to play with for offline accounts, or to log in with for authlib-injector
+		/// accounts. Defaults to the ID of the account
+		#[serde(default)]
+		#[serde(skip_serializing_if = "Option::is_none")]
+		username: Option<String>,
+		/// The URL of the authentication server for authlib-injector accounts
+		#[serde(default)]
+		#[serde(skip_serializing_if = "Option::is_none")]
+		server: Option<String>,
 	},
 }
 
+impl AccountConfig {
+	/// Gets the variant of the account
+	pub fn variant(&self) -> &AccountVariant {
+		match self {
+			Self::Simple(variant) | Self::Advanced { variant, .. } => variant,
+		}
+	}
+
+	/// Gets the auto-lock timeout of the account in minutes
+	pub fn auto_lock(&self) -> Option<u64> {
+		match self {
+			Self::Simple(..) => None,
+			Self::Advanced { auto_lock, .. } => *auto_lock,
+		}
+	}
+
+	/// Gets the configured username of the account
+	pub fn username(&self) -> Option<&str> {
+		match self {
+			Self::Simple(..) => None,
+			Self::Advanced { username, .. } => username.as_deref(),
+		}
+	}
+
+	/// Gets the configured authentication server of the account
+	pub fn server(&self) -> Option<&str> {
+		match self {
+			Self::Simple(..) => None,
+			Self::Advanced { server, .. } => server.as_deref(),
+		}
+	}
+}
+
 /// Different variants of accounts for configuration
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -28,6 +75,10 @@ pub enum AccountVariant {
 	Microsoft,
 	/// A demo account
 	Demo,
+	/// An account for offline play that isn't authenticated
+	Offline,
+	/// An account on an authentication server that is compatible with authlib-injector
+	AuthlibInjector,
 	/// An unknown account
 	#[cfg_attr(not(feature = "schema"), serde(untagged))]
 	Unknown(String),
@@ -38,6 +89,8 @@ impl Display for AccountVariant {
 		match self {
 			Self::Microsoft {} => write!(f, "microsoft"),
 			Self::Demo {} => write!(f, "demo"),
+			Self::Offline {} => write!(f, "offline"),
+			Self::AuthlibInjector {} => write!(f, "authlib_injector"),
 			Self::Unknown(other) => write!(f, "{other}"),
 		}
 	}
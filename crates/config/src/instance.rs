@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use nitro_shared::Side;
 use nitro_shared::java_args::MemoryNum;
 use nitro_shared::loaders::Loader;
+use nitro_shared::minecraft::{GpuPreference, JvmPreset, NativesArch};
 use nitro_shared::pkg::{PackageOverrides, PackageStability};
 use nitro_shared::util::{DefaultExt, DeserListOrSingle, merge_options};
 use nitro_shared::versions::MinecraftVersionDeser;
@@ -36,6 +37,9 @@ pub struct InstanceConfig {
 	/// Configured loader
 	#[serde(skip_serializing_if = "DefaultExt::is_default")]
 	pub loader: Option<String>,
+	/// The ID of the instance on the other side that this instance is paired with
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub linked: Option<String>,
 
 	// In-depth config
 	/// Launch configuration
@@ -44,6 +48,9 @@ pub struct InstanceConfig {
 	/// Window configuration
 	#[serde(skip_serializing_if = "DefaultExt::is_default")]
 	pub window: ClientWindowConfig,
+	/// Network configuration for server instances
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub network: ServerNetworkConfig,
 
 	// Package config
 	/// Modpack package for this instance
@@ -110,6 +117,7 @@ impl InstanceConfig {
 		self.icon = other.icon.or(self.icon.clone());
 		self.side = other.side.or(self.side);
 		self.window.merge(other.window);
+		self.network.merge(other.network);
 
 		// These properties are not derived and instead just overrided
 		self.dir = other.dir;
@@ -118,6 +126,7 @@ impl InstanceConfig {
 		self.is_deletable = other.is_deletable;
 		self.custom_launch = other.custom_launch;
 		self.imported = other.imported;
+		self.linked = other.linked;
 	}
 
 	/// Removes fields that only plugins should be able to edit, for when serializing to user config
@@ -282,6 +291,58 @@ pub struct LaunchConfig {
 	#[serde(default)]
 	#[serde(skip_serializing_if = "DefaultExt::is_default")]
 	pub use_log4j_config: bool,
+	/// Whether to apply compatibility fixes for old versions, such as proxying skins
+	#[serde(default = "legacy_fixes_default")]
+	#[serde(skip_serializing_if = "is_legacy_fixes_default")]
+	pub legacy_fixes: bool,
+	/// Whether to automatically protect versions that are affected by Log4Shell
+	#[serde(default = "log4shell_mitigation_default")]
+	#[serde(skip_serializing_if = "is_log4shell_mitigation_default")]
+	pub log4shell_mitigation: bool,
+	/// Whether to launch the game in demo mode
+	#[serde(default)]
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub demo: bool,
+	/// Architecture to select native libraries for. Detected automatically if not set
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub natives_arch: Option<NativesArch>,
+	/// Java agents to attach to the game
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub java_agents: Vec<JavaAgentConfig>,
+	/// The GPU to prefer for the client. Uses the system default if not set
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub gpu: Option<GpuPreference>,
+	/// A set of tuned JVM arguments to use, which come before the custom ones
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub jvm_preset: Option<JvmPreset>,
+	/// Overrides for the launcher branding that is sent to the game
+	#[serde(default)]
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub branding: LaunchBrandingConfig,
+}
+
+/// Default value for legacy_fixes
+fn legacy_fixes_default() -> bool {
+	true
+}
+
+/// Checks if legacy_fixes is the default value
+fn is_legacy_fixes_default(value: &bool) -> bool {
+	*value == legacy_fixes_default()
+}
+
+/// Default value for log4shell_mitigation
+fn log4shell_mitigation_default() -> bool {
+	true
+}
+
+/// Checks if log4shell_mitigation is the default value
+fn is_log4shell_mitigation_default(value: &bool) -> bool {
+	*value == log4shell_mitigation_default()
 }
 
 impl LaunchConfig {
@@ -302,6 +363,30 @@ impl LaunchConfig {
 		if !matches!(other.quick_play, QuickPlay::None) {
 			self.quick_play = other.quick_play;
 		}
+		if !other.legacy_fixes {
+			self.legacy_fixes = false;
+		}
+		if !other.log4shell_mitigation {
+			self.log4shell_mitigation = false;
+		}
+		if other.demo {
+			self.demo = true;
+		}
+		if other.natives_arch.is_some() {
+			self.natives_arch = other.natives_arch;
+		}
+		if other.gpu.is_some() {
+			self.gpu = other.gpu;
+		}
+		if other.jvm_preset.is_some() {
+			self.jvm_preset = other.jvm_preset;
+		}
+		self.branding.merge(other.branding);
+		for agent in other.java_agents {
+			if !self.java_agents.contains(&agent) {
+				self.java_agents.push(agent);
+			}
+		}
 
 		self
 	}
@@ -320,10 +405,52 @@ impl Default for LaunchConfig {
 			wrapper: None,
 			quick_play: QuickPlay::default(),
 			use_log4j_config: false,
+			legacy_fixes: legacy_fixes_default(),
+			log4shell_mitigation: log4shell_mitigation_default(),
+			demo: false,
+			natives_arch: None,
+			java_agents: Vec::new(),
+			gpu: None,
+			jvm_preset: None,
+			branding: LaunchBrandingConfig::default(),
 		}
 	}
 }
 
+/// Overrides for the launcher branding that is sent to the game
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(default)]
+pub struct LaunchBrandingConfig {
+	/// The launcher name to send to the game
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub launcher_name: Option<String>,
+	/// The launcher version to send to the game
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub launcher_version: Option<String>,
+}
+
+impl LaunchBrandingConfig {
+	/// Merge two LaunchBrandingConfigs
+	pub fn merge(&mut self, other: Self) -> &mut Self {
+		self.launcher_name = merge_options(self.launcher_name.take(), other.launcher_name);
+		self.launcher_version = merge_options(self.launcher_version.take(), other.launcher_version);
+		self
+	}
+}
+
+/// A Java agent to attach to the game with the -javaagent JVM argument
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JavaAgentConfig {
+	/// The path to the agent JAR file. Relative paths are relative to the instance directory
+	pub path: String,
+	/// Options to pass to the agent
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub options: Option<String>,
+}
+
 /// A wrapper command
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -353,12 +480,44 @@ pub struct ClientWindowConfig {
 	/// The resolution of the window
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub resolution: Option<WindowResolution>,
+	/// A label shown after the game version on the title and debug screens
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub title_suffix: Option<String>,
 }
 
 impl ClientWindowConfig {
 	/// Merge two ClientWindowConfigs
 	pub fn merge(&mut self, other: Self) -> &mut Self {
 		self.resolution = merge_options(self.resolution, other.resolution);
+		self.title_suffix = merge_options(self.title_suffix.take(), other.title_suffix);
+		self
+	}
+}
+
+/// Network configuration for a server instance
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(default)]
+pub struct ServerNetworkConfig {
+	/// The IP address of the interface that the server listens on
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub address: Option<String>,
+	/// The port that the server listens on
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub port: Option<u16>,
+	/// Whether to switch to the next free port when the port is already in use, instead of failing to launch
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub auto_port: bool,
+}
+
+impl ServerNetworkConfig {
+	/// Merge two ServerNetworkConfigs
+	pub fn merge(&mut self, other: Self) -> &mut Self {
+		self.address = merge_options(self.address.take(), other.address);
+		self.port = merge_options(self.port, other.port);
+		if other.auto_port {
+			self.auto_port = true;
+		}
 		self
 	}
 }
@@ -437,4 +596,39 @@ mod tests {
 			}
 		);
 	}
+
+	#[test]
+	fn test_branding_merge() {
+		let mut branding = LaunchBrandingConfig {
+			launcher_name: Some("My Pack".into()),
+			launcher_version: Some("1.0".into()),
+		};
+		branding.merge(LaunchBrandingConfig {
+			launcher_name: None,
+			launcher_version: Some("2.0".into()),
+		});
+		assert_eq!(branding.launcher_name.as_deref(), Some("My Pack"));
+		assert_eq!(branding.launcher_version.as_deref(), Some("2.0"));
+	}
+
+	#[test]
+	fn test_java_agents_merge() {
+		let agent = |path: &str| JavaAgentConfig {
+			path: path.into(),
+			options: None,
+		};
+
+		let mut config = LaunchConfig {
+			java_agents: vec![agent("authlib-injector.jar")],
+			..Default::default()
+		};
+		config.merge(LaunchConfig {
+			java_agents: vec![agent("authlib-injector.jar"), agent("profiler.jar")],
+			..Default::default()
+		});
+		assert_eq!(
+			config.java_agents,
+			vec![agent("authlib-injector.jar"), agent("profiler.jar")]
+		);
+	}
 }
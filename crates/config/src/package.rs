@@ -1,8 +1,10 @@
 use std::borrow::Cow;
 use std::fmt::Display;
 
-use anyhow::bail;
-use nitro_shared::pkg::{PackageID, PackageStability, is_valid_package_id};
+use anyhow::{Context, bail};
+use nitro_shared::Side;
+use nitro_shared::minecraft::AddonKind;
+use nitro_shared::pkg::{AddonOptionalHashes, PackageID, PackageStability, is_valid_package_id};
 use nitro_shared::util::{DefaultExt, is_valid_identifier};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
@@ -16,7 +18,7 @@ pub enum PackageConfigDeser {
 	/// Basic configuration for a repository package with just the package ID
 	Basic(PackageID),
 	/// Full configuration for a package
-	Full(FullPackageConfig),
+	Full(Box<FullPackageConfig>),
 }
 
 /// Full configuration for a package
@@ -53,6 +55,79 @@ pub struct FullPackageConfig {
 	#[serde(default)]
 	#[serde(skip_serializing_if = "DefaultExt::is_default")]
 	pub optional: bool,
+	/// A file to install directly instead of getting the package from a repository
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub file: Option<PinnedPackageFile>,
+	/// How to resolve conflicts between this package's addons and another package's
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub on_conflict: Option<AddonConflictResolution>,
+	/// The only side to install this package on, overriding the sides the package supports
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub side: Option<Side>,
+}
+
+/// A way to resolve a conflict between the addons of two packages
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AddonConflictResolution {
+	/// Install this package's addon and skip the other package's
+	Priority,
+	/// Install this package's addon under a different filename
+	Rename,
+	/// Skip this package's addon
+	Skip,
+}
+
+impl Display for AddonConflictResolution {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Priority => "priority",
+				Self::Rename => "rename",
+				Self::Skip => "skip",
+			}
+		)
+	}
+}
+
+/// A single file that a package is pinned to, for content that isn't in any repository
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct PinnedPackageFile {
+	/// URL to download the file from
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub url: Option<String>,
+	/// Path to the file on the local filesystem
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub path: Option<String>,
+	/// What kind of addon the file is
+	#[serde(default = "pinned_file_kind_default")]
+	pub kind: AddonKind,
+	/// Hashes for the file
+	#[serde(default)]
+	#[serde(skip_serializing_if = "AddonOptionalHashes::is_empty")]
+	pub hashes: AddonOptionalHashes,
+	/// The filename to use for the file in the instance
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub filename: Option<String>,
+	/// A version identifier for the file. Changing this will make the file be downloaded again.
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub version: Option<String>,
+}
+
+/// Default value for the kind of a pinned file
+fn pinned_file_kind_default() -> AddonKind {
+	AddonKind::Mod
 }
 
 /// Trick enum used to make deserialization work in the way we want
@@ -76,7 +151,7 @@ impl Display for PackageConfigDeser {
 			"{}",
 			match self {
 				Self::Basic(id) => id,
-				Self::Full(FullPackageConfig { id, .. }) => id,
+				Self::Full(cfg) => &cfg.id,
 			}
 		)
 	}
@@ -147,6 +222,30 @@ impl PackageConfigDeser {
 		}
 	}
 
+	/// Get the pinned file of the config
+	pub fn get_file(&self) -> Option<&PinnedPackageFile> {
+		match &self {
+			Self::Basic(..) => None,
+			Self::Full(cfg) => cfg.file.as_ref(),
+		}
+	}
+
+	/// Get the conflict resolution of the config
+	pub fn get_on_conflict(&self) -> Option<AddonConflictResolution> {
+		match &self {
+			Self::Basic(..) => None,
+			Self::Full(cfg) => cfg.on_conflict,
+		}
+	}
+
+	/// Get the side of the config
+	pub fn get_side(&self) -> Option<Side> {
+		match &self {
+			Self::Basic(..) => None,
+			Self::Full(cfg) => cfg.side,
+		}
+	}
+
 	/// Validate this config
 	pub fn validate(&self) -> anyhow::Result<()> {
 		let id = self.get_pkg_id();
@@ -154,6 +253,11 @@ impl PackageConfigDeser {
 			bail!("Invalid package ID '{id}'");
 		}
 
+		if let Some(file) = self.get_file() {
+			file.validate()
+				.with_context(|| format!("Invalid pinned file for package '{id}'"))?;
+		}
+
 		for feature in self.get_features() {
 			if !is_valid_identifier(&feature) {
 				bail!("Invalid string '{feature}'");
@@ -164,6 +268,23 @@ impl PackageConfigDeser {
 	}
 }
 
+impl PinnedPackageFile {
+	/// Validate this pinned file
+	pub fn validate(&self) -> anyhow::Result<()> {
+		match (&self.url, &self.path) {
+			(Some(..), Some(..)) => bail!("Only one of url or path can be set"),
+			(None, None) => bail!("Either url or path must be set"),
+			// Files from the internet could change at any time, so we need a hash to make sure they don't
+			(Some(..), None) if self.hashes.is_empty() => {
+				bail!("A hash is required for files that are downloaded from a URL")
+			}
+			_ => {}
+		}
+
+		Ok(())
+	}
+}
+
 /// Permissions level for an evaluation
 #[derive(Deserialize, Serialize, Debug, Copy, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -177,3 +298,37 @@ pub enum EvalPermissions {
 	/// Allow execution of things that could compromise security
 	Elevated,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_pinned_file_validation() {
+		let config: PackageConfigDeser = serde_json::from_str(
+			r#"{ "id": "foo", "file": { "url": "https://example.com/foo.jar" } }"#,
+		)
+		.unwrap();
+		assert!(config.validate().is_err());
+
+		let config: PackageConfigDeser = serde_json::from_str(
+			r#"{ "id": "foo", "file": { "url": "https://example.com/foo.jar", "hashes": { "sha256": "abc" } } }"#,
+		)
+		.unwrap();
+		assert!(config.validate().is_ok());
+		assert_eq!(config.get_file().unwrap().kind, AddonKind::Mod);
+
+		let config: PackageConfigDeser =
+			serde_json::from_str(r#"{ "id": "foo", "file": { "path": "foo.jar" } }"#).unwrap();
+		assert!(config.validate().is_ok());
+	}
+	#[test]
+	fn test_package_side() {
+		let config: PackageConfigDeser =
+			serde_json::from_str(r#"{ "id": "foo", "side": "server" }"#).unwrap();
+		assert_eq!(config.get_side(), Some(Side::Server));
+
+		let config = PackageConfigDeser::Basic("foo".into());
+		assert_eq!(config.get_side(), None);
+	}
+}
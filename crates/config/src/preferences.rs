@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+
 use nitro_shared::lang::Language;
+use nitro_shared::util::DefaultExt;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -19,6 +22,136 @@ pub struct PrefDeser {
 	pub repositories: RepositoriesDeser,
 	/// The user's configured language
 	pub language: Language,
+	/// Whether to always require a successful game ownership check when logging in
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub strict_ownership_check: bool,
+	/// The paste backend to upload logs to
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub paste_backend: Option<String>,
+	/// The number of days between automatically pruning unused game files
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub prune_interval: Option<u32>,
+	/// The maximum total size of cached downloads in megabytes
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub cache_limit: Option<u64>,
+	/// Whether to save reports of crashes and errors locally so that they can be submitted as issues
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub error_reports: bool,
+	/// The release channel to update the CLI from
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub update_channel: UpdateChannel,
+	/// A proxy to send all network requests through
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub proxy: Option<ProxyDeser>,
+	/// Mirrors to download game files from before falling back to the official servers
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub mirrors: Vec<MirrorDeser>,
+	/// How often background maintenance tasks are run
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub maintenance: MaintenanceDeser,
+	/// How failed network requests are retried
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub retry: RetryDeser,
+	/// The maximum number of files to download at the same time
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub download_concurrency: Option<usize>,
+	/// Limits on download speed
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub bandwidth_limit: BandwidthLimitDeser,
+}
+
+/// Deserialization struct for proxy settings
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct ProxyDeser {
+	/// The URL of the HTTP or SOCKS5 proxy
+	pub url: String,
+	/// Hosts that are connected to directly instead of through the proxy
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub bypass: Vec<String>,
+}
+
+/// Deserialization struct for a game file download mirror
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum MirrorDeser {
+	/// The name of a built-in mirror
+	Preset(String),
+	/// A mirror with custom URLs
+	Custom {
+		/// The name of the mirror
+		name: String,
+		/// Map of official URL prefixes to the mirror URL prefixes that replace them
+		rewrites: HashMap<String, String>,
+	},
+}
+
+/// Deserialization struct for the intervals of background maintenance tasks, in hours.
+/// An interval of zero disables the task
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(default)]
+pub struct MaintenanceDeser {
+	/// How often to refresh the version manifest
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub refresh_manifest: Option<u64>,
+	/// How often to check instances for package updates
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub check_package_updates: Option<u64>,
+	/// How often to check for plugin updates
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub check_plugin_updates: Option<u64>,
+	/// How often to remove cached downloads that are over the cache limit
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub collect_cache: Option<u64>,
+	/// How often to check that installed Java versions work
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub verify_java: Option<u64>,
+}
+
+/// Deserialization struct for the retry policy of network requests
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(default)]
+pub struct RetryDeser {
+	/// The total number of times a request is attempted
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub attempts: Option<u32>,
+	/// The delay before the first retry in milliseconds
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub base_delay: Option<u64>,
+	/// The longest delay between attempts in milliseconds
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub max_delay: Option<u64>,
+	/// Response status codes that are retried
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub statuses: Option<Vec<u16>>,
+}
+
+/// Deserialization struct for download bandwidth limits, in bytes per second
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(default)]
+pub struct BandwidthLimitDeser {
+	/// The limit for all downloads combined
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub total: Option<u64>,
+	/// The limit for each individual download
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub per_download: Option<u64>,
+}
+
+/// Release channel that the CLI is updated from
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+	/// Only full releases
+	#[default]
+	Stable,
+	/// Full releases and prereleases
+	Beta,
 }
 
 /// Deserialization struct for a package repo
@@ -36,6 +169,16 @@ pub struct RepoDeser {
 	/// Whether to disable the repo and not add it to the list
 	#[serde(default)]
 	pub disable: bool,
+	/// Mirror URLs that are tried in order when the main URL can't be reached
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub mirrors: Vec<String>,
+	/// The priority of the repository. Repositories with a higher priority are checked first
+	#[serde(default, skip_serializing_if = "DefaultExt::is_default")]
+	pub priority: i32,
+	/// The number of seconds that the cached index of the repository is used for before it is updated.
+	/// By default, the index is only updated when syncing
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cache_ttl: Option<u64>,
 }
 
 /// Deserialization struct for all configured package repositories
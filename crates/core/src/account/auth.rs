@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use anyhow::{Context, bail};
 use nitro_auth::RsaPrivateKey;
+use nitro_shared::error::AuthError;
 use nitro_shared::minecraft::MinecraftUserProfile;
 use nitro_shared::output::{MessageContents, NitroOutput};
 use nitro_shared::translate;
@@ -11,9 +12,10 @@ use crate::Paths;
 use nitro_auth::db::{AuthDatabase, DatabaseAccount, SensitiveAccountInfo};
 use nitro_auth::mc::Keypair;
 use nitro_auth::mc::{
-	self as auth, AccessToken, ClientId, RefreshToken, authenticate_microsoft_account,
-	authenticate_microsoft_account_from_token,
+	self as auth, AccessToken, ClientId, MicrosoftLoginMethod, RefreshToken,
+	authenticate_microsoft_account, authenticate_microsoft_account_from_token,
 };
+use nitro_auth::yggdrasil;
 
 use super::{Account, AccountKind, AccountManagerHooks};
 
@@ -33,9 +35,7 @@ impl Account {
 						.await
 						.context("Failed to get account from database")?
 					else {
-						bail!(
-							"Account not present in database. Make sure to authenticate at least once before logging in in offline mode"
-						);
+						bail!(AuthError::NotInDatabase);
 					};
 
 					self.name = Some(account.username.clone());
@@ -45,7 +45,7 @@ impl Account {
 				} else {
 					let account_data = update_microsoft_account_auth(&self.id, params, o)
 						.await
-						.context("Failed to update account authentication")?;
+						.context(AuthError::Failed)?;
 
 					self.access_token = Some(account_data.access_token);
 					self.name = Some(account_data.profile.name);
@@ -54,7 +54,34 @@ impl Account {
 					*xbox_uid = account_data.xbox_uid;
 				}
 			}
-			AccountKind::Demo => {}
+			AccountKind::AuthlibInjector { server, login } => {
+				if params.offline {
+					let db = AuthDatabase::open(&params.paths.auth)
+						.context("Failed to open authentication database")?;
+					let Some(account) = db.get_valid_account(&self.id) else {
+						bail!(AuthError::NotInDatabase);
+					};
+					let sensitive = get_sensitive_info(account, o)
+						.await
+						.context("Failed to get sensitive information")?;
+
+					self.name = Some(account.username.clone());
+					self.uuid = Some(account.uuid.clone());
+					self.access_token = sensitive.access_token.map(AccessToken);
+				} else {
+					let server = server.clone();
+					let login = login.clone();
+					let account_data =
+						update_authlib_injector_account_auth(&self.id, &server, &login, params, o)
+							.await
+							.context(AuthError::Failed)?;
+
+					self.access_token = Some(account_data.access_token);
+					self.name = Some(account_data.name);
+					self.uuid = Some(account_data.uuid);
+				}
+			}
+			AccountKind::Demo | AccountKind::Offline { .. } => {}
 			AccountKind::Unknown(other) => {
 				if let Some(hooks) = params.custom_hooks {
 					o.debug(MessageContents::Simple(
@@ -84,14 +111,14 @@ impl Account {
 	/// function instead.
 	pub fn is_auth_valid(&self, paths: &Paths) -> bool {
 		match &self.kind {
-			AccountKind::Microsoft { .. } => {
+			AccountKind::Microsoft { .. } | AccountKind::AuthlibInjector { .. } => {
 				let Ok(db) = AuthDatabase::open(&paths.auth) else {
 					return false;
 				};
 
 				db.get_valid_account(&self.id).is_some()
 			}
-			AccountKind::Demo => true,
+			AccountKind::Demo | AccountKind::Offline { .. } => true,
 			AccountKind::Unknown(..) => true,
 		}
 	}
@@ -99,8 +126,10 @@ impl Account {
 	/// Checks if this account is currently authenticated and ready to be used
 	pub fn is_authenticated(&self) -> bool {
 		match &self.kind {
-			AccountKind::Microsoft { .. } => self.access_token.is_some() && self.uuid.is_some(),
-			AccountKind::Demo => true,
+			AccountKind::Microsoft { .. } | AccountKind::AuthlibInjector { .. } => {
+				self.access_token.is_some() && self.uuid.is_some()
+			}
+			AccountKind::Demo | AccountKind::Offline { .. } => true,
 			AccountKind::Unknown(..) => true,
 		}
 	}
@@ -178,14 +207,7 @@ pub(crate) async fn update_microsoft_account_auth(
 
 	// Force reauth if specified
 	if params.force {
-		return reauth_microsoft_account(
-			account_id,
-			&mut db,
-			params.client_id,
-			params.req_client,
-			o,
-		)
-		.await;
+		return reauth_microsoft_account(account_id, &mut db, &params, o).await;
 	}
 
 	// Check the authentication DB
@@ -225,8 +247,7 @@ pub(crate) async fn update_microsoft_account_auth(
 		}
 	} else {
 		// Authenticate with the server again
-		reauth_microsoft_account(account_id, &mut db, params.client_id, params.req_client, o)
-			.await?
+		reauth_microsoft_account(account_id, &mut db, &params, o).await?
 	};
 
 	Ok(account_data)
@@ -281,23 +302,26 @@ async fn update_using_refresh_token(
 async fn reauth_microsoft_account(
 	account_id: &str,
 	db: &mut AuthDatabase,
-	client_id: ClientId,
-	client: &reqwest::Client,
+	params: &AuthParameters<'_>,
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<MicrosoftAccountData> {
-	let auth_result = authenticate_microsoft_account(client_id, client, o)
-		.await
-		.context("Failed to authenticate account")?;
+	let client = params.req_client;
+	let auth_result =
+		authenticate_microsoft_account(params.client_id.clone(), params.ms_login_method, client, o)
+			.await
+			.context("Failed to authenticate account")?;
 
+	// The result of the ownership check is handled separately so that a failure
+	// can fall back to a previous check
 	let ownership_task = {
 		let client = client.clone();
 		let token = auth_result.access_token.0.clone();
 		async move {
 			let owns_game = auth::account_owns_game(&token, &client)
 				.await
-				.context("Failed to check for game ownership")?;
+				.context("Failed to check for game ownership");
 
-			Ok::<bool, anyhow::Error>(owns_game)
+			Ok::<anyhow::Result<bool>, anyhow::Error>(owns_game)
 		}
 	};
 
@@ -328,9 +352,23 @@ async fn reauth_microsoft_account(
 	let (owns_game, profile, certificate) =
 		tokio::try_join!(ownership_task, profile_task, certificate_task)?;
 
-	if !owns_game {
-		bail!("Specified account does not own Minecraft");
-	}
+	let previous_account = db.get_account(account_id);
+	let ownership_checked = match owns_game {
+		Ok(true) => utc_timestamp().ok(),
+		// The entitlement API sometimes fails or returns no entitlements even when the account owns the game,
+		// so use the last successful check if it's recent enough
+		_ if !params.strict_ownership_check
+			&& previous_account.is_some_and(|x| x.has_recent_ownership_check()) =>
+		{
+			o.display(MessageContents::Warning(translate!(
+				o,
+				UsingCachedOwnershipCheck
+			)));
+			previous_account.and_then(|x| x.ownership_checked)
+		}
+		Ok(false) => bail!(AuthError::GameNotOwned),
+		Err(e) => return Err(e),
+	};
 
 	// Calculate expiration time
 	let expiration_time = nitro_auth::db::calculate_expiration_date();
@@ -344,8 +382,9 @@ async fn reauth_microsoft_account(
 		access_token: Some(auth_result.access_token.0.clone()),
 		// Expires in 24 hours
 		access_token_expires: utc_timestamp().map(|x| x + 24 * 3600).ok(),
+		client_token: None,
 	};
-	let db_account = DatabaseAccount::new(
+	let mut db_account = DatabaseAccount::new(
 		account_id.to_string(),
 		profile.name.clone(),
 		profile.uuid.clone(),
@@ -353,6 +392,7 @@ async fn reauth_microsoft_account(
 		sensitive,
 	)
 	.context("Failed to create new account in database")?;
+	db_account.ownership_checked = ownership_checked;
 
 	db.update_account(db_account, account_id)
 		.context("Failed to update account in database")?;
@@ -365,6 +405,126 @@ async fn reauth_microsoft_account(
 	})
 }
 
+/// Data for an account on an authlib-injector authentication server
+pub(crate) struct AuthlibInjectorAccountData {
+	pub access_token: AccessToken,
+	pub name: String,
+	pub uuid: String,
+}
+
+/// Updates authentication for an account on an authlib-injector server, reusing or refreshing the stored
+/// access token if possible and logging in with a password otherwise
+pub(crate) async fn update_authlib_injector_account_auth(
+	account_id: &str,
+	server: &str,
+	login: &str,
+	params: AuthParameters<'_>,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<AuthlibInjectorAccountData> {
+	if server.is_empty() {
+		bail!("No authentication server is configured for account '{account_id}'");
+	}
+
+	let client = params.req_client;
+	let mut db =
+		AuthDatabase::open(&params.paths.auth).context("Failed to open authentication database")?;
+	let api_root = yggdrasil::resolve_api_root(server, client)
+		.await
+		.context("Failed to find authentication server API")?;
+
+	// Try to use the stored access token first
+	let mut client_token = None;
+	if !params.force
+		&& let Some(db_account) = db.get_valid_account(account_id)
+		&& db_account.is_logged_in()
+	{
+		let sensitive = get_sensitive_info(db_account, o)
+			.await
+			.context("Failed to get sensitive information")?;
+		if let (Some(access_token), Some(stored_client_token)) = (
+			sensitive.access_token.clone(),
+			sensitive.client_token.clone(),
+		) {
+			let access_token = AccessToken(access_token);
+			if yggdrasil::validate(&api_root, &access_token, &stored_client_token, client)
+				.await
+				.unwrap_or(false)
+			{
+				return Ok(AuthlibInjectorAccountData {
+					access_token,
+					name: db_account.username.clone(),
+					uuid: db_account.uuid.clone(),
+				});
+			}
+
+			match yggdrasil::refresh(&api_root, &access_token, &stored_client_token, client).await {
+				Ok(result) => {
+					let mut db_account = db_account.clone();
+					let mut sensitive = sensitive;
+					sensitive.access_token = Some(result.access_token.0.clone());
+					db_account
+						.set_sensitive_info(sensitive)
+						.context("Failed to set sensitive info for account")?;
+					db.update_account(db_account, account_id)
+						.context("Failed to update account in database")?;
+
+					return Ok(AuthlibInjectorAccountData {
+						access_token: result.access_token,
+						name: result.name,
+						uuid: result.uuid,
+					});
+				}
+				Err(e) => o.debug(MessageContents::Simple(format!(
+					"Failed to refresh authentication, logging in again: {e:?}"
+				))),
+			}
+		}
+		client_token = sensitive.client_token;
+	}
+
+	// Log in again with a password
+	let password = o
+		.prompt_password(MessageContents::Simple(format!(
+			"Enter the password for '{login}' on {server}"
+		)))
+		.await
+		.context("Failed to get password")?;
+	let client_token = client_token.unwrap_or_else(yggdrasil::generate_client_token);
+	let result = yggdrasil::authenticate(&api_root, login, &password, &client_token, client)
+		.await
+		.context("Failed to log in to authentication server")?;
+
+	o.display(MessageContents::Success(translate!(
+		o,
+		AuthenticationSuccessful
+	)));
+
+	let sensitive = SensitiveAccountInfo {
+		refresh_token: None,
+		xbox_uid: None,
+		keypair: None,
+		access_token: Some(result.access_token.0.clone()),
+		access_token_expires: None,
+		client_token: Some(result.client_token),
+	};
+	let db_account = DatabaseAccount::new(
+		account_id.to_string(),
+		result.name.clone(),
+		result.uuid.clone(),
+		nitro_auth::db::calculate_expiration_date(),
+		sensitive,
+	)
+	.context("Failed to create new account in database")?;
+	db.update_account(db_account, account_id)
+		.context("Failed to update account in database")?;
+
+	Ok(AuthlibInjectorAccountData {
+		access_token: result.access_token,
+		name: result.name,
+		uuid: result.uuid,
+	})
+}
+
 /// Tries to get a full valid account from the database along with a passkey prompt if applicable
 async fn get_full_account<'db>(
 	db: &'db AuthDatabase,
@@ -459,6 +619,8 @@ pub(crate) struct AuthParameters<'a> {
 	pub paths: &'a Paths,
 	pub req_client: &'a reqwest::Client,
 	pub custom_hooks: Option<Arc<dyn AccountManagerHooks>>,
+	pub strict_ownership_check: bool,
+	pub ms_login_method: MicrosoftLoginMethod,
 }
 
 /// Checks whether an account in the database is logged in
@@ -1,4 +1,4 @@
-use anyhow::Context;
+use anyhow::{Context, bail};
 use nitro_shared::{
 	minecraft::{Cape, Skin, SkinVariant},
 	output::NitroOutput,
@@ -17,7 +17,9 @@ impl Account {
 		o: &mut impl NitroOutput,
 	) -> anyhow::Result<(Vec<Skin>, Vec<Cape>)> {
 		match &self.kind {
-			AccountKind::Demo => Ok((Vec::new(), Vec::new())),
+			AccountKind::Demo
+			| AccountKind::Offline { .. }
+			| AccountKind::AuthlibInjector { .. } => Ok((Vec::new(), Vec::new())),
 			AccountKind::Microsoft { .. } => {
 				let client = params.req_client.clone();
 				let account_data = update_microsoft_account_auth(&self.id, params, o)
@@ -53,6 +55,10 @@ impl Account {
 	) -> anyhow::Result<()> {
 		match &self.kind {
 			AccountKind::Demo => Ok(()),
+			AccountKind::Offline { .. } => bail!("Offline accounts can't have skins uploaded"),
+			AccountKind::AuthlibInjector { .. } => {
+				bail!("Skins for this account have to be uploaded on its authentication server")
+			}
 			AccountKind::Microsoft { .. } => {
 				let client = params.req_client.clone();
 				self.authenticate(params, o).await?;
@@ -86,6 +92,10 @@ impl Account {
 	) -> anyhow::Result<()> {
 		match &self.kind {
 			AccountKind::Demo => Ok(()),
+			AccountKind::Offline { .. } => bail!("Offline accounts can't have capes"),
+			AccountKind::AuthlibInjector { .. } => {
+				bail!("Capes for this account have to be changed on its authentication server")
+			}
 			AccountKind::Microsoft { .. } => {
 				let client = params.req_client.clone();
 				self.authenticate(params, o).await?;
@@ -2,18 +2,23 @@
 pub mod auth;
 /// Account cosmetics
 pub mod cosmetics;
+/// Minecraft Realms access for accounts
+pub mod realms;
 
 use std::{collections::HashMap, ops::Deref, sync::Arc};
 
 use anyhow::{Context, bail};
-use nitro_auth::mc::{AccessToken, ClientId, Keypair};
+use nitro_auth::mc::{AccessToken, ClientId, Keypair, MicrosoftLoginMethod};
 use nitro_shared::{
+	error::{AuthError, NitroResult},
 	minecraft::{Cape, MinecraftUserProfile, Skin, SkinVariant},
 	output::NitroOutput,
+	uuid::offline_uuid,
 };
 use reqwest::Client;
 
 use crate::Paths;
+use crate::net::minecraft::realms::RealmsCredentials;
 
 use self::auth::AuthParameters;
 
@@ -47,6 +52,20 @@ pub enum AccountKind {
 	},
 	/// A demo account
 	Demo,
+	/// An offline account that isn't authenticated, which uses the same UUID that
+	/// servers in offline mode give to its username
+	Offline {
+		/// The username to play with
+		username: String,
+	},
+	/// An account on an alternative authentication server that is compatible with authlib-injector,
+	/// such as Ely.by or Blessing Skin
+	AuthlibInjector {
+		/// The URL of the authentication server
+		server: String,
+		/// The username or email to log in with
+		login: String,
+	},
 	/// An unknown account kind
 	Unknown(String),
 }
@@ -54,11 +73,18 @@ pub enum AccountKind {
 impl Account {
 	/// Create a new account
 	pub fn new(kind: AccountKind, id: AccountID) -> Self {
+		// Offline accounts already know everything they need to play
+		let (name, uuid) = if let AccountKind::Offline { username } = &kind {
+			(Some(username.clone()), Some(offline_uuid(username)))
+		} else {
+			(None, None)
+		};
+
 		Self {
 			kind,
 			id,
-			name: None,
-			uuid: None,
+			name,
+			uuid,
 			access_token: None,
 			keypair: None,
 		}
@@ -84,6 +110,25 @@ impl Account {
 		matches!(self.kind, AccountKind::Demo)
 	}
 
+	/// Checks if this account uses an authlib-injector authentication server
+	pub fn is_authlib_injector(&self) -> bool {
+		matches!(self.kind, AccountKind::AuthlibInjector { .. })
+	}
+
+	/// Gets the authentication server of this account, if it uses authlib-injector
+	pub fn get_authlib_injector_server(&self) -> Option<&str> {
+		if let AccountKind::AuthlibInjector { server, .. } = &self.kind {
+			Some(server)
+		} else {
+			None
+		}
+	}
+
+	/// Checks if this account is an offline account
+	pub fn is_offline(&self) -> bool {
+		matches!(self.kind, AccountKind::Offline { .. })
+	}
+
 	/// Gets the kind of this account
 	pub fn get_kind(&self) -> &AccountKind {
 		&self.kind
@@ -150,6 +195,11 @@ pub struct AccountManager {
 	offline: bool,
 	/// Custom hooks for plugin injection
 	custom_hooks: Option<Arc<dyn AccountManagerHooks>>,
+	/// Whether to always require a successful game ownership check instead of
+	/// falling back to a cached result
+	strict_ownership_check: bool,
+	/// How users sign in to Microsoft accounts
+	ms_login_method: MicrosoftLoginMethod,
 }
 
 /// State of authentication
@@ -170,6 +220,8 @@ impl AccountManager {
 			ms_client_id,
 			offline: false,
 			custom_hooks: None,
+			strict_ownership_check: false,
+			ms_login_method: MicrosoftLoginMethod::default(),
 		}
 	}
 
@@ -205,6 +257,16 @@ impl AccountManager {
 		self.accounts.iter()
 	}
 
+	/// Gets the secrets of all of the accounts that are loaded, like their access tokens,
+	/// so that they can be censored
+	pub fn get_secrets(&self) -> Vec<String> {
+		self.accounts
+			.values()
+			.filter_map(|x| x.get_access_token())
+			.map(|x| x.0.clone())
+			.collect()
+	}
+
 	/// Remove an account with an ID. Will unchoose the account if it is chosen.
 	pub fn remove_account(&mut self, account_id: &str) {
 		let is_chosen = if let Some(chosen) = self.get_chosen_account() {
@@ -273,7 +335,7 @@ impl AccountManager {
 		paths: &Paths,
 		client: &Client,
 		o: &mut impl NitroOutput,
-	) -> anyhow::Result<()> {
+	) -> NitroResult<()> {
 		if let AuthState::AccountChosen(account_id) = &mut self.state {
 			let account = self
 				.accounts
@@ -288,6 +350,8 @@ impl AccountManager {
 					offline,
 					client_id: self.ms_client_id.clone(),
 					custom_hooks: self.custom_hooks.clone(),
+					strict_ownership_check: self.strict_ownership_check,
+					ms_login_method: self.ms_login_method,
 				};
 				account.authenticate(params, o).await?;
 			}
@@ -303,11 +367,11 @@ impl AccountManager {
 		paths: &Paths,
 		client: &Client,
 		o: &mut impl NitroOutput,
-	) -> anyhow::Result<()> {
+	) -> NitroResult<()> {
 		let account = self
 			.accounts
 			.get_mut(account)
-			.context("Account does not exist")?;
+			.ok_or(AuthError::AccountNotFound)?;
 
 		if !account.is_authenticated() || !account.is_auth_valid(paths) {
 			let params = AuthParameters {
@@ -317,6 +381,8 @@ impl AccountManager {
 				offline: self.offline,
 				client_id: self.ms_client_id.clone(),
 				custom_hooks: self.custom_hooks.clone(),
+				strict_ownership_check: self.strict_ownership_check,
+				ms_login_method: self.ms_login_method,
 			};
 			account.authenticate(params, o).await?;
 		}
@@ -344,10 +410,12 @@ impl AccountManager {
 				offline: self.offline,
 				client_id: self.ms_client_id.clone(),
 				custom_hooks: self.custom_hooks.clone(),
+				strict_ownership_check: self.strict_ownership_check,
+				ms_login_method: self.ms_login_method,
 			};
 			account.get_cosmetics(params, o).await
 		} else {
-			bail!("No account chosen")
+			bail!(AuthError::NoAccountChosen)
 		}
 	}
 
@@ -371,6 +439,8 @@ impl AccountManager {
 			offline: self.offline,
 			client_id: self.ms_client_id.clone(),
 			custom_hooks: self.custom_hooks.clone(),
+			strict_ownership_check: self.strict_ownership_check,
+			ms_login_method: self.ms_login_method,
 		};
 		account.get_cosmetics(params, o).await
 	}
@@ -397,6 +467,8 @@ impl AccountManager {
 			offline: self.offline,
 			client_id: self.ms_client_id.clone(),
 			custom_hooks: self.custom_hooks.clone(),
+			strict_ownership_check: self.strict_ownership_check,
+			ms_login_method: self.ms_login_method,
 		};
 		account.upload_skin(variant, skin, params, o).await
 	}
@@ -422,10 +494,38 @@ impl AccountManager {
 			offline: self.offline,
 			client_id: self.ms_client_id.clone(),
 			custom_hooks: self.custom_hooks.clone(),
+			strict_ownership_check: self.strict_ownership_check,
+			ms_login_method: self.ms_login_method,
 		};
 		account.activate_cape(cape, params, o).await
 	}
 
+	/// Authenticates an account and gets credentials for using the Realms API with it
+	pub async fn get_realms_credentials(
+		&mut self,
+		account: &str,
+		paths: &Paths,
+		client: &Client,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<RealmsCredentials> {
+		let account = self
+			.accounts
+			.get_mut(account)
+			.context("Account does not exist")?;
+
+		let params = AuthParameters {
+			req_client: client,
+			paths,
+			force: false,
+			offline: self.offline,
+			client_id: self.ms_client_id.clone(),
+			custom_hooks: self.custom_hooks.clone(),
+			strict_ownership_check: self.strict_ownership_check,
+			ms_login_method: self.ms_login_method,
+		};
+		account.get_realms_credentials(params, o).await
+	}
+
 	/// Unchooses the current account, if one is chosen
 	pub fn unchoose_account(&mut self) {
 		self.state = AuthState::Offline;
@@ -443,6 +543,18 @@ impl AccountManager {
 		self.offline = offline;
 	}
 
+	/// Set whether game ownership checks must always succeed. When this is off, a recent successful check
+	/// will be used if the entitlement API fails
+	pub fn set_strict_ownership_check(&mut self, strict: bool) {
+		self.strict_ownership_check = strict;
+	}
+
+	/// Set how users sign in to Microsoft accounts. Use the device code method when there is no
+	/// browser on this device, so that the user can sign in on another one instead
+	pub fn set_ms_login_method(&mut self, method: MicrosoftLoginMethod) {
+		self.ms_login_method = method;
+	}
+
 	/// Set the manager's custom hooks
 	pub fn set_custom_hooks(&mut self, hooks: Arc<dyn AccountManagerHooks>) {
 		self.custom_hooks = Some(hooks);
@@ -542,4 +654,20 @@ mod tests {
 		assert!(!accounts.is_account_chosen());
 		assert!(!accounts.account_exists("foo"));
 	}
+
+	#[test]
+	fn test_offline_account() {
+		let account = Account::new(
+			AccountKind::Offline {
+				username: "Notch".into(),
+			},
+			"foo".into(),
+		);
+		assert!(account.is_authenticated());
+		assert_eq!(account.get_name().map(String::as_str), Some("Notch"));
+		assert_eq!(
+			account.get_uuid().map(String::as_str),
+			Some("b50ad385829d3141a2167e7d7539ba7f")
+		);
+	}
 }
@@ -0,0 +1,37 @@
+use anyhow::{Context, bail};
+use nitro_shared::output::NitroOutput;
+
+use crate::account::{Account, AccountKind, auth::AuthParameters};
+use crate::net::minecraft::realms::RealmsCredentials;
+
+impl Account {
+	/// Authenticates this account and gets credentials for the Realms API
+	pub(crate) async fn get_realms_credentials(
+		&mut self,
+		params: AuthParameters<'_>,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<RealmsCredentials> {
+		let AccountKind::Microsoft { .. } = &self.kind else {
+			bail!("Realms are only available for Microsoft accounts");
+		};
+
+		if params.offline {
+			bail!("Realms are not available in offline mode");
+		}
+
+		self.authenticate(params, o).await?;
+
+		let access_token = self
+			.access_token
+			.as_ref()
+			.context("Account has no access token")?;
+		let uuid = self.uuid.as_ref().context("Account has no UUID")?;
+		let name = self.name.as_ref().context("Account has no username")?;
+
+		Ok(RealmsCredentials {
+			access_token: access_token.0.clone(),
+			uuid: uuid.clone(),
+			name: name.clone(),
+		})
+	}
+}
@@ -1,4 +1,7 @@
 use nitro_auth::mc::ClientId;
+use nitro_net::download::ProxySettings;
+
+use crate::net::game_files::mirrors::DownloadMirror;
 
 use crate::util::secrets::get_ms_client_id;
 
@@ -23,6 +26,12 @@ pub struct Configuration {
 	pub(crate) disable_hardlinks: bool,
 	/// Launcher branding
 	pub(crate) branding: BrandingProperties,
+	/// The proxy to send network requests through
+	pub(crate) proxy: Option<ProxySettings>,
+	/// Mirrors to download game files from before falling back to the official servers
+	pub(crate) download_mirrors: Vec<DownloadMirror>,
+	/// The maximum number of game files to download at the same time. Uses a sensible default if not set
+	pub(crate) download_concurrency: Option<usize>,
 }
 
 impl Default for Configuration {
@@ -39,6 +48,9 @@ impl Configuration {
 			censor_secrets: true,
 			disable_hardlinks: false,
 			branding: BrandingProperties::default(),
+			proxy: None,
+			download_mirrors: Vec::new(),
+			download_concurrency: None,
 		}
 	}
 
@@ -85,6 +97,24 @@ impl ConfigBuilder {
 	);
 
 	builder_method!(branding, BrandingProperties, "Set the branding properties");
+
+	builder_method!(
+		proxy,
+		Option<ProxySettings>,
+		"Set the proxy that the core's client sends network requests through"
+	);
+
+	builder_method!(
+		download_mirrors,
+		Vec<DownloadMirror>,
+		"Set the mirrors to download game files from before falling back to the official servers"
+	);
+
+	builder_method!(
+		download_concurrency,
+		Option<usize>,
+		"Set the maximum number of assets and libraries to download at the same time"
+	);
 }
 
 impl Default for ConfigBuilder {
@@ -2,7 +2,8 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{Context, bail};
-use nitro_shared::io::update_link;
+use nitro_shared::error::NitroResult;
+use nitro_shared::io::{copy_file, update_link};
 use nitro_shared::output::{MessageContents, NitroOutput};
 use nitro_shared::versions::VersionName;
 use nitro_shared::{Side, translate};
@@ -17,7 +18,7 @@ use crate::io::java::install::{
 };
 use crate::io::persistent::PersistentData;
 use crate::io::update::UpdateManager;
-use crate::launch::{LaunchConfiguration, LaunchParameters};
+use crate::launch::{LaunchConfiguration, LaunchParameters, PreparedLaunch};
 use crate::net::game_files::client_meta::ClientMeta;
 use crate::net::game_files::version_manifest::VersionManifestAndList;
 use crate::net::game_files::{game_jar, libraries};
@@ -131,17 +132,17 @@ impl Instance {
 			let new_jar_path = config.path.join("server.jar");
 			// Don't hardlink if it's already in the right place
 			if new_jar_path != jar_path {
-				// Update the hardlink
-				if params.update_manager.should_update_file(&new_jar_path) {
+				// Update the hardlink. It is always updated when verifying in case the jar was redownloaded
+				if params.update_manager.should_update_file(&new_jar_path)
+					|| params.update_manager.is_verifying()
+				{
 					if new_jar_path.exists() {
 						tokio::fs::remove_file(&new_jar_path)
 							.await
 							.context("Failed to remove existing server.jar")?;
 					}
 					if params.disable_hardlinks {
-						tokio::fs::copy(&jar_path, &new_jar_path)
-							.await
-							.context("Failed to copy server.jar")?;
+						copy_file(&jar_path, &new_jar_path).context("Failed to copy server.jar")?;
 					} else {
 						update_link(&jar_path, &new_jar_path)
 							.context("Failed to link server.jar")?;
@@ -162,8 +163,8 @@ impl Instance {
 				version: &params.version,
 				paths: &params.paths,
 				req_client: &params.req_client,
-				version_manifest: &params.version_manifest,
 				update_manager: &params.update_manager,
+				natives_arch: config.launch.get_natives_arch(),
 			};
 			params
 				.client_assets_and_libs
@@ -175,9 +176,13 @@ impl Instance {
 		// Classpath
 		let mut classpath = Classpath::new();
 		if let Side::Client = config.side.get_side() {
-			let lib_classpath =
-				libraries::get_classpath(&params.client_meta.libraries, &params.paths.internal)
-					.context("Failed to extract classpath from game library list")?;
+			let lib_classpath = libraries::get_classpath(
+				&params.client_meta.libraries,
+				&params.paths.internal,
+				&params.paths.libraries,
+				config.launch.get_natives_arch(),
+			)
+			.context("Failed to extract classpath from game library list")?;
 			classpath.extend(lib_classpath);
 		}
 		for lib in &config.additional_libs {
@@ -233,7 +238,7 @@ impl Instance {
 		offline_auth: bool,
 		quick_play: Option<QuickPlayType>,
 		o: &mut impl NitroOutput,
-	) -> anyhow::Result<()> {
+	) -> NitroResult<()> {
 		let mut handle = self
 			.launch_with_handle(accounts, offline_auth, quick_play, o)
 			.await?;
@@ -250,12 +255,49 @@ impl Instance {
 		offline_auth: bool,
 		quick_play: Option<QuickPlayType>,
 		o: &mut impl NitroOutput,
-	) -> anyhow::Result<InstanceHandle> {
+	) -> NitroResult<InstanceHandle> {
+		let launch_config = self.get_launch_config(quick_play);
+		let params = self.get_launch_params(&launch_config, accounts, offline_auth);
+		let handle = crate::launch::launch(params, o)
+			.await
+			.context("Failed to run launch routine")?;
+		Ok(handle)
+	}
+
+	/// Resolve the command line, classpath, environment, and working directory for launching
+	/// the instance without spawning the process
+	pub async fn prepare_launch(
+		&mut self,
+		accounts: &mut AccountManager,
+		offline_auth: bool,
+		quick_play: Option<QuickPlayType>,
+		o: &mut impl NitroOutput,
+	) -> NitroResult<PreparedLaunch> {
+		let launch_config = self.get_launch_config(quick_play);
+		let params = self.get_launch_params(&launch_config, accounts, offline_auth);
+		let prepared = crate::launch::prepare(params, o)
+			.await
+			.context("Failed to prepare launch")?;
+		Ok(prepared)
+	}
+
+	/// Gets the launch configuration with overrides for a single launch
+	fn get_launch_config(&self, quick_play: Option<QuickPlayType>) -> LaunchConfiguration {
 		let mut launch_config = self.config.launch.clone();
 		if let Some(quick_play) = quick_play {
 			launch_config.quick_play = quick_play;
 		}
-		let params = LaunchParameters {
+		launch_config
+	}
+
+	/// Creates the parameters for the launch routine
+	fn get_launch_params<'a>(
+		&'a self,
+		launch_config: &'a LaunchConfiguration,
+		accounts: &'a mut AccountManager,
+		offline_auth: bool,
+	) -> LaunchParameters<'a> {
+		LaunchParameters {
 			version: &self.params.version,
 			version_manifest: &self.params.version_manifest,
 			side: &self.config.side,
@@ -264,7 +306,7 @@ impl Instance {
 			classpath: &self.classpath,
 			jar_path: &self.jar_path,
 			main_class: &self.main_class,
-			launch_config: &launch_config,
+			launch_config,
 			paths: &self.params.paths,
 			req_client: &self.params.req_client,
 			client_meta: &self.params.client_meta,
@@ -273,11 +315,7 @@ impl Instance {
 			censor_secrets: self.params.censor_secrets,
 			branding: &self.params.branding,
 			pipe_stdin: self.pipe_stdin,
-		};
-		let handle = crate::launch::launch(params, o)
-			.await
-			.context("Failed to run launch routine")?;
-		Ok(handle)
+		}
 	}
 
 	/// Set whether to pipe stdin for the instance
@@ -415,12 +453,17 @@ impl InstanceKind {
 pub struct ClientWindowConfig {
 	/// The resolution of the window
 	pub resolution: Option<WindowResolution>,
+	/// A label shown after the game version on the title and debug screens. Defaults to the launcher name
+	pub title_suffix: Option<String>,
 }
 
 impl ClientWindowConfig {
 	/// Construct a new ClientWindowConfig with default settings
 	pub fn new() -> Self {
-		Self { resolution: None }
+		Self {
+			resolution: None,
+			title_suffix: None,
+		}
 	}
 }
 
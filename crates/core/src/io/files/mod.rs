@@ -3,10 +3,12 @@ pub mod paths;
 
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
 
 use anyhow::ensure;
-use nitro_shared::io::{LinkMethod, get_link_method};
+use nitro_shared::io::reflink::reflink;
+use nitro_shared::io::{LinkMethod, copy_file, get_link_method};
 
 /// Create a directory that may already exist without an error
 pub fn create_dir(path: &Path) -> std::io::Result<()> {
@@ -40,6 +42,11 @@ pub async fn update_link_async(path: &Path, link: &Path) -> std::io::Result<()>
 	let method = get_link_method();
 
 	match method {
+		LinkMethod::Reflink => {
+			if !link.exists() && reflink(path, link).is_err() {
+				tokio::fs::hard_link(path, link).await?;
+			}
+		}
 		LinkMethod::Hard => {
 			if !link.exists() {
 				tokio::fs::hard_link(path, link).await?;
@@ -53,7 +60,7 @@ pub async fn update_link_async(path: &Path, link: &Path) -> std::io::Result<()>
 		}
 		LinkMethod::Copy => {
 			if !link.exists() {
-				tokio::fs::copy(path, link).await?;
+				copy_file(path, link)?;
 			}
 		}
 	}
@@ -74,6 +81,20 @@ pub fn dir_symlink(path: &Path, target: &Path) -> std::io::Result<()> {
 	Ok(())
 }
 
+/// Cross platform - create a file soft link
+#[cfg(target_os = "windows")]
+pub fn file_symlink(path: &Path, target: &Path) -> std::io::Result<()> {
+	std::os::windows::fs::symlink_file(path, target)?;
+	Ok(())
+}
+
+/// Cross platform - create a file soft link
+#[cfg(target_family = "unix")]
+pub fn file_symlink(path: &Path, target: &Path) -> std::io::Result<()> {
+	std::os::unix::fs::symlink(path, target)?;
+	Ok(())
+}
+
 /// Copy the contents of a directory recursively to another directory.
 /// Identical files will be overwritten
 pub fn copy_dir_contents(src: &Path, dest: &Path) -> anyhow::Result<()> {
@@ -86,10 +107,7 @@ pub fn copy_dir_contents(src: &Path, dest: &Path) -> anyhow::Result<()> {
 		let rel = src_path.strip_prefix(src)?;
 		let dest_path = dest.join(rel);
 
-		let mut src_file = std::io::BufReader::new(std::fs::File::open(src_path)?);
-		let mut dest_file = std::io::BufWriter::new(std::fs::File::create(dest_path)?);
-
-		std::io::copy(&mut src_file, &mut dest_file)?;
+		copy_file(&src_path, &dest_path)?;
 	}
 
 	Ok(())
@@ -107,10 +125,7 @@ pub async fn copy_dir_contents_async(src: &Path, dest: &Path) -> anyhow::Result<
 		let rel = src_path.strip_prefix(src)?;
 		let dest_path = dest.join(rel);
 
-		let mut src_file = std::io::BufReader::new(std::fs::File::open(src_path)?);
-		let mut dest_file = std::io::BufWriter::new(std::fs::File::create(dest_path)?);
-
-		std::io::copy(&mut src_file, &mut dest_file)?;
+		copy_file(&src_path, &dest_path)?;
 	}
 
 	Ok(())
@@ -125,6 +140,81 @@ pub fn write_buffered<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> s
 	inner(path.as_ref(), contents.as_ref())
 }
 
+/// Counter used to give temporary files from the same process unique names
+static TEMP_FILE_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// Writes a file atomically by writing to a temporary file and renaming it over the original,
+/// so that the file is never left partially written. The previous contents are kept in up to
+/// `backups` rotated backup files next to it, with the most recent being `{name}.bak.1`
+pub fn write_atomic(path: &Path, contents: &[u8], backups: u8) -> std::io::Result<()> {
+	let temp_path = get_temp_path(path)?;
+	let result = write_synced(&temp_path, contents);
+	if result.is_err() {
+		let _ = fs::remove_file(&temp_path);
+		return result;
+	}
+
+	if backups > 0 && path.exists() {
+		let backup_path = |n: u8| get_atomic_backup_path(path, n);
+		for n in (1..backups).rev() {
+			let src = backup_path(n);
+			if src.exists() {
+				let _ = fs::rename(&src, backup_path(n + 1));
+			}
+		}
+		// Copy instead of renaming so that the original always exists
+		let _ = nitro_shared::io::copy_file(path, &backup_path(1));
+	}
+
+	let result = fs::rename(&temp_path, path);
+	if result.is_err() {
+		let _ = fs::remove_file(&temp_path);
+	}
+
+	result
+}
+
+/// Replaces a file by writing to a temporary file and renaming it over the original.
+/// Unlike write_atomic, this doesn't wait for the disk or keep backups, which makes it
+/// suitable for small, frequently rewritten files that the launcher can regenerate
+pub fn write_replace(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+	let temp_path = get_temp_path(path)?;
+	let result = write_buffered(&temp_path, contents).and_then(|_| fs::rename(&temp_path, path));
+	if result.is_err() {
+		let _ = fs::remove_file(&temp_path);
+	}
+
+	result
+}
+
+/// Gets a unique temporary path next to a file to write to before renaming
+fn get_temp_path(path: &Path) -> std::io::Result<PathBuf> {
+	let file_name = path
+		.file_name()
+		.ok_or(std::io::ErrorKind::InvalidFilename)?
+		.to_string_lossy();
+
+	// Temporary files have unique names so that multiple processes writing at once don't clash
+	Ok(path.with_file_name(format!(
+		".{file_name}.tmp-{}-{}",
+		std::process::id(),
+		TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+	)))
+}
+
+/// Gets the path to a backup created by write_atomic, where 1 is the most recent
+pub fn get_atomic_backup_path(path: &Path, n: u8) -> PathBuf {
+	let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+	path.with_file_name(format!("{file_name}.bak.{n}"))
+}
+
+/// Writes a file and waits for its contents to be flushed to the disk
+fn write_synced(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+	let mut file = File::create(path)?;
+	file.write_all(contents)?;
+	file.sync_all()
+}
+
 /// Opens a file in append mode
 pub fn open_file_append(path: impl AsRef<Path>) -> std::io::Result<File> {
 	let mut options = OpenOptions::new();
@@ -132,3 +222,49 @@ pub fn open_file_append(path: impl AsRef<Path>) -> std::io::Result<File> {
 
 	options.open(path)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_atomic_write_backups() {
+		let dir = std::env::temp_dir().join("nitro_test_atomic_write_backups");
+		let _ = fs::remove_dir_all(&dir);
+		fs::create_dir_all(&dir).unwrap();
+		let path = dir.join("config.json");
+
+		for i in 0..5 {
+			write_atomic(&path, i.to_string().as_bytes(), 3).unwrap();
+		}
+
+		assert_eq!(fs::read_to_string(&path).unwrap(), "4");
+		for (n, expected) in [(1, "3"), (2, "2"), (3, "1")] {
+			let backup = fs::read_to_string(get_atomic_backup_path(&path, n)).unwrap();
+			assert_eq!(backup, expected);
+		}
+		assert!(!get_atomic_backup_path(&path, 4).exists());
+		// Only the file and its backups should be left over
+		assert_eq!(fs::read_dir(&dir).unwrap().count(), 4);
+
+		let _ = fs::remove_dir_all(dir);
+	}
+
+	#[test]
+	fn test_write_replace() {
+		let dir = std::env::temp_dir().join("nitro_test_write_replace");
+		let _ = fs::remove_dir_all(&dir);
+		fs::create_dir_all(&dir).unwrap();
+		let path = dir.join("store.json");
+
+		for i in 0..3 {
+			write_replace(&path, i.to_string().as_bytes()).unwrap();
+		}
+
+		assert_eq!(fs::read_to_string(&path).unwrap(), "2");
+		// No backups or temporary files should be left over
+		assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+		let _ = fs::remove_dir_all(dir);
+	}
+}
@@ -1,8 +1,18 @@
-use anyhow::anyhow;
+use anyhow::{Context, anyhow, bail};
 use directories::ProjectDirs;
 use nitro_shared::io::config::IO_CONFIG;
+use nitro_shared::io::copy_file;
 
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use super::{create_leading_dirs, dir_symlink, file_symlink};
+use crate::io::update::hash_file_sha1;
+use crate::io::{json_from_file, json_to_file_replace};
 
 /// Store for all of the paths that are used throughout the application
 #[derive(Debug, Clone)]
@@ -13,11 +23,11 @@ pub struct Paths {
 	pub data: PathBuf,
 	/// Holds internal data
 	pub internal: PathBuf,
-	/// Holds game assets
+	/// Holds game assets. Can be relocated out of the internal directory
 	pub assets: PathBuf,
-	/// Holds game libraries
+	/// Holds game libraries. Can be relocated out of the internal directory
 	pub libraries: PathBuf,
-	/// Holds Java installations
+	/// Holds Java installations. Can be relocated out of the internal directory
 	pub java: PathBuf,
 	/// Holds game jar files
 	pub jars: PathBuf,
@@ -31,6 +41,9 @@ pub struct Paths {
 	pub run: PathBuf,
 	/// Used for temporary stdio files for instances
 	pub stdio: PathBuf,
+	/// System-wide read-only store of game files that is shared between users, if one exists.
+	/// It has the same layout as the internal directory
+	pub shared: Option<PathBuf>,
 }
 
 impl Paths {
@@ -49,6 +62,7 @@ impl Paths {
 		let _ = std::fs::create_dir_all(&self.data);
 		let _ = std::fs::create_dir_all(&self.internal);
 		let _ = std::fs::create_dir_all(&self.assets);
+		let _ = std::fs::create_dir_all(&self.libraries);
 		let _ = std::fs::create_dir_all(&self.java);
 		let _ = std::fs::create_dir_all(&self.jars);
 		let _ = std::fs::create_dir_all(&self.auth);
@@ -76,6 +90,27 @@ impl Paths {
 			project.data_dir().to_owned()
 		};
 
+		let mut out = Self::from_dirs(config, data);
+		if let Some(dir) = get_relocated_dir("assets_path")? {
+			out.assets = dir;
+		}
+		if let Some(dir) = get_relocated_dir("libraries_path")? {
+			out.libraries = dir;
+		}
+		if let Some(dir) = get_relocated_dir("java_path")? {
+			out.java = dir;
+		}
+		if let Some(run) = project.runtime_dir() {
+			out.run = run.to_path_buf();
+		}
+		out.shared = get_shared_dir().filter(|x| x.is_dir());
+
+		Ok(out)
+	}
+
+	/// Create the paths from custom config and data directories without creating any directories.
+	/// Useful for keeping the files of the core separate from the user's, like in tests
+	pub fn from_dirs(config: PathBuf, data: PathBuf) -> Self {
 		let internal = data.join("internal");
 		let assets = internal.join("assets");
 		let libraries = internal.join("libraries");
@@ -84,13 +119,10 @@ impl Paths {
 		let auth = internal.join("auth");
 		let logs = data.join("logs");
 		let launch_logs = logs.join("launch");
-		let run = project
-			.runtime_dir()
-			.map(|x| x.to_path_buf())
-			.unwrap_or(internal.join("run"));
+		let run = internal.join("run");
 		let stdio = internal.join("stdio");
 
-		Ok(Paths {
+		Paths {
 			config,
 			data,
 			internal,
@@ -103,6 +135,366 @@ impl Paths {
 			launch_logs,
 			run,
 			stdio,
-		})
+			shared: None,
+		}
+	}
+
+	/// Moves the existing contents of the assets, libraries, and Java directories to their configured
+	/// locations if they were relocated out of the internal directory. A link to the new location is left
+	/// in place of the old directory so that paths which were saved before the move, like the classpaths
+	/// and Java installations of instances, still resolve
+	pub fn relocate_dirs(&self) -> anyhow::Result<()> {
+		let dirs = [
+			("assets", &self.assets),
+			("libraries", &self.libraries),
+			("java", &self.java),
+		];
+		for (name, dir) in dirs {
+			let default = self.internal.join(name);
+			if *dir == default {
+				continue;
+			}
+
+			relocate_dir(&default, dir)
+				.with_context(|| format!("Failed to move {name} directory to {}", dir.display()))?;
+		}
+
+		Ok(())
+	}
+
+	/// Gets the path in the shared store that corresponds to a path in the internal directory,
+	/// if the shared store has it
+	pub fn find_shared(&self, path: &Path) -> Option<PathBuf> {
+		let shared = self.shared.as_ref()?;
+		let relative = path.strip_prefix(&self.internal).ok()?;
+		let shared_path = shared.join(relative);
+
+		shared_path.exists().then_some(shared_path)
+	}
+
+	/// Links a file in the internal directory to the shared store if the shared store has it and the path
+	/// doesn't exist yet. Returns true if the path points to the shared store, in which case it should not
+	/// be downloaded or written to.
+	///
+	/// Other users can write to the shared store, so files are only used from it if they match their
+	/// expected SHA-1 hash, and files without one, including directories, are never used. If a file that
+	/// was already linked no longer matches, the link is removed so that the file is downloaded again
+	pub fn link_from_shared(&self, path: &Path, sha1: Option<&str>) -> bool {
+		let Some(shared_path) = self.find_shared(path) else {
+			return false;
+		};
+
+		let matches = sha1.is_some_and(|sha1| self.check_shared_hash(&shared_path, sha1));
+		if !matches {
+			if std::fs::read_link(path).is_ok_and(|x| x == shared_path) {
+				let _ = std::fs::remove_file(path);
+			}
+			return false;
+		}
+
+		if path.is_symlink() {
+			return std::fs::read_link(path).is_ok_and(|x| x == shared_path);
+		}
+		if path.exists() {
+			return false;
+		}
+
+		if create_leading_dirs(path).is_err() {
+			return false;
+		}
+		// Symlinks are used since the shared store is usually on a different filesystem and
+		// owned by another user, which rules out hard links
+		file_symlink(&shared_path, path).is_ok()
+	}
+
+	/// Checks a file in the shared store against its expected SHA-1 hash. Files that were already checked
+	/// aren't hashed again unless their size or modification time has changed
+	fn check_shared_hash(&self, shared_path: &Path, sha1: &str) -> bool {
+		let Ok(metadata) = std::fs::metadata(shared_path) else {
+			return false;
+		};
+		if !metadata.is_file() {
+			return false;
+		}
+		let file = VerifiedFile {
+			sha1: sha1.to_ascii_lowercase(),
+			size: metadata.len(),
+			modified: metadata
+				.modified()
+				.ok()
+				.and_then(|x| x.duration_since(UNIX_EPOCH).ok())
+				.map(|x| x.as_nanos() as u64)
+				.unwrap_or_default(),
+		};
+		let key = shared_path.to_string_lossy().to_string();
+
+		let cache_path = self.internal.join("shared_verified.json");
+		if let Ok(mut cache) = SHARED_VERIFICATION.lock() {
+			let cache = cache.get_or_insert_with(|| SharedVerificationCache::open(cache_path));
+			if cache.files.get(&key) == Some(&file) {
+				return true;
+			}
+		}
+
+		if !hash_file_sha1(shared_path).is_ok_and(|x| x.eq_ignore_ascii_case(sha1)) {
+			return false;
+		}
+
+		if let Ok(mut cache) = SHARED_VERIFICATION.lock()
+			&& let Some(cache) = cache.as_mut()
+		{
+			cache.files.insert(key, file);
+			cache.is_dirty = true;
+		}
+
+		true
+	}
+
+	/// Writes the record of files in the shared store that have been checked, if it has changed
+	pub fn save_shared_verification(&self) -> anyhow::Result<()> {
+		let Ok(mut cache) = SHARED_VERIFICATION.lock() else {
+			return Ok(());
+		};
+		let Some(cache) = cache.as_mut() else {
+			return Ok(());
+		};
+		if !cache.is_dirty {
+			return Ok(());
+		}
+
+		json_to_file_replace(&cache.path, &cache.files)
+			.context("Failed to write shared store verification record")?;
+		cache.is_dirty = false;
+
+		Ok(())
+	}
+}
+
+/// Record of the files in the shared store that have already been checked against their hashes
+static SHARED_VERIFICATION: Mutex<Option<SharedVerificationCache>> = Mutex::new(None);
+
+/// Files in the shared store that have been checked, stored so that they don't have to be hashed
+/// again every time they are used
+struct SharedVerificationCache {
+	/// Where the record is stored
+	path: PathBuf,
+	/// The checked files, by their path in the shared store
+	files: HashMap<String, VerifiedFile>,
+	/// Whether the record has changed since it was written
+	is_dirty: bool,
+}
+
+impl SharedVerificationCache {
+	/// Opens the record, or starts a new one if it doesn't exist or can't be read
+	fn open(path: PathBuf) -> Self {
+		let files = json_from_file(&path).unwrap_or_default();
+		Self {
+			path,
+			files,
+			is_dirty: false,
+		}
+	}
+}
+
+/// A file in the shared store that matched its hash
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+struct VerifiedFile {
+	/// The lowercase SHA-1 hash that the file matched
+	sha1: String,
+	/// The size of the file when it was checked
+	size: u64,
+	/// The modification time of the file when it was checked, in nanoseconds since the Unix epoch
+	modified: u64,
+}
+
+/// Gets the configured location of a directory that can be relocated out of the internal directory
+fn get_relocated_dir(key: &str) -> anyhow::Result<Option<PathBuf>> {
+	let Some(dir) = IO_CONFIG.get_string(key) else {
+		return Ok(None);
+	};
+	let dir = PathBuf::from(dir);
+	if !dir.is_absolute() {
+		bail!(
+			"Configured {key} '{}' must be an absolute path",
+			dir.display()
+		);
+	}
+
+	Ok(Some(dir))
+}
+
+/// Moves the contents of a directory to a new location and links the old location to it
+fn relocate_dir(old: &Path, new: &Path) -> anyhow::Result<()> {
+	std::fs::create_dir_all(new).context("Failed to create new directory")?;
+
+	// If the directory was relocated before, the files are moved from the previous location instead
+	let src = if old.is_symlink() {
+		let previous = std::fs::read_link(old).context("Failed to read old link")?;
+		if previous == new {
+			return Ok(());
+		}
+		remove_dir_symlink(old).context("Failed to remove old link")?;
+		previous
+	} else {
+		old.to_owned()
+	};
+
+	// Don't mix files into a location that is already in use. This also keeps loaders that always
+	// write to the internal directory, when links aren't supported, from being moved over and over
+	if src.is_dir() && is_dir_empty(new)? {
+		move_dir_contents(&src, new).context("Failed to move existing files")?;
+		std::fs::remove_dir_all(&src).context("Failed to remove old directory")?;
+	}
+
+	// Linking can fail on Windows without the right permissions. The files have already been moved, so
+	// this only affects paths that were saved before now, which are updated the next time an instance is updated
+	let _ = dir_symlink(new, old);
+
+	Ok(())
+}
+
+/// Moves the contents of a directory recursively, falling back to copying when the destination
+/// is on another filesystem
+fn move_dir_contents(src: &Path, dest: &Path) -> anyhow::Result<()> {
+	for entry in src.read_dir()? {
+		let src_path = entry?.path();
+		let Some(name) = src_path.file_name() else {
+			continue;
+		};
+		let dest_path = dest.join(name);
+		if std::fs::rename(&src_path, &dest_path).is_ok() {
+			continue;
+		}
+
+		if src_path.is_dir() {
+			std::fs::create_dir_all(&dest_path)?;
+			move_dir_contents(&src_path, &dest_path)?;
+		} else {
+			copy_file(&src_path, &dest_path)
+				.with_context(|| format!("Failed to copy file {}", src_path.display()))?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Checks if a directory has no entries
+fn is_dir_empty(path: &Path) -> anyhow::Result<bool> {
+	Ok(path.read_dir()?.next().is_none())
+}
+
+/// Removes a link to a directory
+fn remove_dir_symlink(path: &Path) -> std::io::Result<()> {
+	#[cfg(target_os = "windows")]
+	return std::fs::remove_dir(path);
+	#[cfg(not(target_os = "windows"))]
+	return std::fs::remove_file(path);
+}
+
+/// Gets the location of the shared store, which can be configured
+fn get_shared_dir() -> Option<PathBuf> {
+	if let Some(dir) = IO_CONFIG.get_string("shared_path") {
+		return Some(PathBuf::from(dir));
+	}
+
+	#[cfg(target_os = "linux")]
+	return Some(PathBuf::from("/usr/share/nitrolaunch"));
+	#[cfg(target_os = "windows")]
+	return std::env::var("ProgramData")
+		.ok()
+		.map(|x| PathBuf::from(x).join("nitrolaunch"));
+	#[cfg(target_os = "macos")]
+	return Some(PathBuf::from("/Library/Application Support/nitrolaunch"));
+	#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+	return None;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_link_from_shared() {
+		let dir = std::env::temp_dir().join("nitro_test_link_from_shared");
+		let _ = std::fs::remove_dir_all(&dir);
+		let shared = dir.join("shared");
+		std::fs::create_dir_all(shared.join("jars")).unwrap();
+		std::fs::write(shared.join("jars/1.20.1.jar"), "jar").unwrap();
+
+		let sha1 = hash_file_sha1(&shared.join("jars/1.20.1.jar")).unwrap();
+
+		let mut paths = Paths::from_dirs(dir.join("config"), dir.join("data"));
+		let jar = paths.jars.join("1.20.1.jar");
+		assert!(!paths.link_from_shared(&jar, Some(&sha1)));
+
+		paths.shared = Some(shared.clone());
+		assert_eq!(
+			paths.find_shared(&jar),
+			Some(shared.join("jars/1.20.1.jar"))
+		);
+		// Files without a matching hash aren't trusted
+		assert!(!paths.link_from_shared(&jar, None));
+		assert!(!paths.link_from_shared(&jar, Some("0000")));
+		assert!(!jar.exists());
+
+		assert!(paths.link_from_shared(&jar, Some(&sha1)));
+		assert_eq!(std::fs::read_to_string(&jar).unwrap(), "jar");
+		assert!(paths.link_from_shared(&jar, Some(&sha1)));
+		paths.save_shared_verification().unwrap();
+		assert!(paths.internal.join("shared_verified.json").exists());
+
+		// Directories can't be checked, so they are never used
+		std::fs::create_dir_all(shared.join("java/adoptium/jdk-21-jre")).unwrap();
+		let java = paths.java.join("adoptium/jdk-21-jre");
+		assert!(!paths.link_from_shared(&java, Some(&sha1)));
+		assert!(!java.exists());
+
+		// A linked file that was tampered with is unlinked
+		std::fs::write(shared.join("jars/1.20.1.jar"), "evil").unwrap();
+		assert!(!paths.link_from_shared(&jar, Some(&sha1)));
+		assert!(!jar.is_symlink());
+
+		// Files that the user already has are kept
+		let other = paths.jars.join("1.21.jar");
+		std::fs::write(&other, "user").unwrap();
+		std::fs::write(shared.join("jars/1.21.jar"), "shared").unwrap();
+		let other_sha1 = hash_file_sha1(&shared.join("jars/1.21.jar")).unwrap();
+		assert!(!paths.link_from_shared(&other, Some(&other_sha1)));
+
+		let _ = std::fs::remove_dir_all(dir);
+	}
+
+	#[test]
+	fn test_relocate_dirs() {
+		let dir = std::env::temp_dir().join("nitro_test_relocate_dirs");
+		let _ = std::fs::remove_dir_all(&dir);
+		let mut paths = Paths::from_dirs(dir.join("config"), dir.join("data"));
+		paths.create_dirs().unwrap();
+		let lib = paths.libraries.join("org/lwjgl/lwjgl.jar");
+		std::fs::create_dir_all(lib.parent().unwrap()).unwrap();
+		std::fs::write(&lib, "lib").unwrap();
+
+		paths.libraries = dir.join("disk/libraries");
+		paths.relocate_dirs().unwrap();
+		assert_eq!(
+			std::fs::read_to_string(paths.libraries.join("org/lwjgl/lwjgl.jar")).unwrap(),
+			"lib"
+		);
+		// Paths from before the move still resolve
+		assert_eq!(std::fs::read_to_string(&lib).unwrap(), "lib");
+		assert!(paths.internal.join("libraries").is_symlink());
+
+		// Relocating again moves the files from the previous location
+		let previous = paths.libraries.clone();
+		paths.libraries = dir.join("disk2/libraries");
+		paths.relocate_dirs().unwrap();
+		assert!(!previous.exists());
+		assert_eq!(std::fs::read_to_string(&lib).unwrap(), "lib");
+		assert_eq!(
+			std::fs::read_link(paths.internal.join("libraries")).unwrap(),
+			paths.libraries
+		);
+
+		let _ = std::fs::remove_dir_all(dir);
 	}
 }
@@ -22,7 +22,7 @@ use zip::ZipArchive;
 use crate::io::files::{self, paths::Paths};
 use crate::io::persistent::PersistentData;
 use crate::io::update::UpdateManager;
-use crate::net::{self, download};
+use crate::net;
 use nitro_shared::util::preferred_archive_extension;
 
 use super::JavaMajorVersion;
@@ -212,6 +212,53 @@ impl JavaInstallation {
 	}
 }
 
+/// Checks the Java installations that have been installed before. Returns the name and problem
+/// of each one that is broken. Installations that were removed are skipped, as they are installed
+/// again when they are needed
+pub fn verify_installed(paths: &Paths) -> anyhow::Result<Vec<(String, anyhow::Error)>> {
+	let persistent = PersistentData::open(paths).context("Failed to open persistent data")?;
+
+	let mut out = Vec::new();
+	for (java, major_version, path) in persistent.get_java_installations() {
+		if !path.exists() {
+			continue;
+		}
+		let Some(major_version) = JavaMajorVersion::parse(&major_version) else {
+			continue;
+		};
+
+		let installation = JavaInstallation {
+			major_version,
+			path,
+		};
+		if let Err(e) = installation.verify() {
+			out.push((format!("{java} {}", installation.major_version), e));
+		}
+	}
+
+	Ok(out)
+}
+
+/// Gets the Java installations that have been installed before and whose major version no longer
+/// gets updates at the given UTC timestamp. Returns the name of each one
+pub fn get_end_of_life_installations(paths: &Paths, now: u64) -> anyhow::Result<Vec<String>> {
+	let persistent = PersistentData::open(paths).context("Failed to open persistent data")?;
+
+	let out = persistent
+		.get_java_installations()
+		.into_iter()
+		.filter(|(_, _, path)| path.exists())
+		.filter_map(|(java, major_version, _)| {
+			let major_version = JavaMajorVersion::parse(&major_version)?;
+			major_version
+				.is_end_of_life(now)
+				.then(|| format!("{java} {major_version}"))
+		})
+		.collect();
+
+	Ok(out)
+}
+
 /// Container struct for parameters for loading Java installations
 pub(crate) struct JavaInstallParameters<'a> {
 	pub paths: &'a Paths,
@@ -327,7 +374,8 @@ async fn update_adoptium(
 		DownloadingAdoptium,
 		"version" = &release_name
 	)));
-	download::file(bin_url, &arc_path, params.req_client)
+	let partial_dir = net::resume::get_partial_dir(&params.paths.internal);
+	net::resume::file(&bin_url, &arc_path, &partial_dir, params.req_client)
 		.await
 		.context("Failed to download JRE binaries")?;
 
@@ -1,3 +1,4 @@
+use std::env::consts::EXE_SUFFIX;
 use std::path::{Path, PathBuf};
 
 use anyhow::Context;
@@ -17,15 +18,13 @@ macro_rules! scan {
 }
 
 /// Gets the optimal path to a system Java installation
-fn get_system_java_installation(#[allow(unused_variables)] major_version: &str) -> Option<PathBuf> {
+fn get_system_java_installation(major_version: &str) -> Option<PathBuf> {
 	// JAVA_HOME
 	if let Ok(home) = std::env::var("JAVA_HOME") {
 		// This isn't a directory holding Java installations, it IS a Java installation
-		if home.contains(major_version) {
-			let home = PathBuf::from(home);
-			if home.join("bin").exists() {
-				return Some(home);
-			}
+		let path = PathBuf::from(&home);
+		if is_valid_installation(&path, Some(&home), major_version, is_debug()) {
+			return Some(path);
 		}
 	}
 
@@ -54,14 +53,26 @@ fn get_system_java_installation(#[allow(unused_variables)] major_version: &str)
 /// Scan for Java on Windows
 #[cfg(target_os = "windows")]
 fn scan_windows(major_version: &str) -> Option<PathBuf> {
-	// OpenJDK
-	scan!(&PathBuf::from("C:/Program Files/Java"), major_version);
-	// Winget Adoptium
-	let dir = PathBuf::from(format!(
-		"C:/Program Files/Eclipse Adoptium/jdk{major_version}/jre{major_version}"
-	));
-	if dir.exists() {
-		return Some(dir);
+	for program_files in ["C:/Program Files", "C:/Program Files (x86)"] {
+		let program_files = PathBuf::from(program_files);
+		// OpenJDK and Oracle
+		scan!(&program_files.join("Java"), major_version);
+		// Adoptium, including Winget
+		scan!(&program_files.join("Eclipse Adoptium"), major_version);
+		// Microsoft Build of OpenJDK
+		scan!(&program_files.join("Microsoft"), major_version);
+		// Azul Zulu
+		scan!(&program_files.join("Zulu"), major_version);
+		// Amazon Corretto
+		scan!(&program_files.join("Amazon Corretto"), major_version);
+		// BellSoft Liberica
+		scan!(&program_files.join("BellSoft"), major_version);
+	}
+
+	if let Ok(home) = std::env::var("USERPROFILE") {
+		let home = PathBuf::from(home);
+		// IntelliJ
+		scan!(&home.join(".jdks"), major_version);
 	}
 
 	None
@@ -70,8 +81,24 @@ fn scan_windows(major_version: &str) -> Option<PathBuf> {
 /// Scan for Java on MacOS
 #[cfg(target_os = "macos")]
 fn scan_macos(major_version: &str) -> Option<PathBuf> {
+	// Installers from Oracle, Adoptium, and most other vendors
+	scan!(
+		&PathBuf::from("/Library/Java/JavaVirtualMachines"),
+		major_version
+	);
 	// Homebrew
 	scan!(&PathBuf::from("/opt/homebrew/opt/"), major_version);
+	scan!(&PathBuf::from("/usr/local/opt/"), major_version);
+
+	if let Ok(home) = std::env::var("HOME") {
+		let home = PathBuf::from(home);
+		scan!(
+			&home.join("Library/Java/JavaVirtualMachines"),
+			major_version
+		);
+		// SDKMan
+		scan!(&home.join(".sdkman/candidates/java"), major_version);
+	}
 
 	None
 }
@@ -106,9 +133,14 @@ fn scan_linux(major_version: &str) -> Option<PathBuf> {
 	None
 }
 
+/// Whether to print debug info while scanning
+fn is_debug() -> bool {
+	std::env::var("NITRO_JAVA_SCAN_DEBUG").is_ok_and(|x| x == "1")
+}
+
 /// Scan a directory for Java installations
 fn scan_dir(dir: &Path, major_version: &str) -> Option<PathBuf> {
-	let debug = std::env::var("NITRO_JAVA_SCAN_DEBUG").is_ok_and(|x| x == "1");
+	let debug = is_debug();
 	if debug {
 		println!("Scanning {dir:?}");
 		dbg!(&major_version);
@@ -123,9 +155,23 @@ fn scan_dir(dir: &Path, major_version: &str) -> Option<PathBuf> {
 			}
 			let name = path.file_name().to_string_lossy().to_string();
 			let path = path.path();
+			if !path.is_dir() {
+				if debug {
+					println!("Not directory");
+				}
+				continue;
+			}
 
-			if check_single_dir(&path, name, major_version, debug) {
-				return Some(path);
+			// MacOS bundles keep the actual installation deeper inside
+			let candidates = [
+				path.join("Contents/Home"),
+				path.join("libexec/openjdk.jdk/Contents/Home"),
+				path,
+			];
+			for candidate in candidates {
+				if is_valid_installation(&candidate, Some(&name), major_version, debug) {
+					return Some(candidate);
+				}
 			}
 		}
 	}
@@ -133,13 +179,37 @@ fn scan_dir(dir: &Path, major_version: &str) -> Option<PathBuf> {
 	None
 }
 
-fn check_single_dir(path: &Path, filename: String, major_version: &str, debug: bool) -> bool {
-	if !path.is_dir() {
+/// Checks if a directory is a Java installation with the correct major version.
+/// The version is read from the release file of the installation. If there isn't one,
+/// the name of the folder it was found in is used to guess instead
+fn is_valid_installation(
+	path: &Path,
+	filename: Option<&str>,
+	major_version: &str,
+	debug: bool,
+) -> bool {
+	// Make sure there is a JVM
+	if !path.join("bin").join(format!("java{EXE_SUFFIX}")).exists() {
 		if debug {
-			println!("Not directory");
+			println!("No JVM found");
 		}
 		return false;
 	}
+
+	if let Some(version) = get_installation_major_version(path) {
+		if version.to_string() != major_version {
+			if debug {
+				println!("Wrong major version {version}");
+			}
+			return false;
+		}
+
+		return true;
+	}
+
+	let Some(filename) = filename else {
+		return false;
+	};
 	if !filename.contains("java") && !filename.contains("jdk") {
 		if debug {
 			println!("Not a Java folder");
@@ -153,13 +223,55 @@ fn check_single_dir(path: &Path, filename: String, major_version: &str, debug: b
 		return false;
 	}
 
-	// Make sure there is a bin directory
-	if !path.join("bin").exists() {
-		if debug {
-			println!("No bin directory found");
-		}
-		return false;
+	true
+}
+
+/// Gets the major version of a Java installation from its release file
+fn get_installation_major_version(path: &Path) -> Option<u16> {
+	let release = std::fs::read_to_string(path.join("release")).ok()?;
+	release.lines().find_map(|line| {
+		let version = line.strip_prefix("JAVA_VERSION=")?;
+		parse_major_version(version.trim().trim_matches('"'))
+	})
+}
+
+/// Parses the major version out of a full Java version, like `17.0.2` or `1.8.0_392`
+fn parse_major_version(version: &str) -> Option<u16> {
+	let version = version.strip_prefix("1.").unwrap_or(version);
+	let end = version
+		.find(|c: char| !c.is_ascii_digit())
+		.unwrap_or(version.len());
+	version[..end].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_major_version_parsing() {
+		assert_eq!(parse_major_version("17.0.2"), Some(17));
+		assert_eq!(parse_major_version("1.8.0_392"), Some(8));
+		assert_eq!(parse_major_version("21"), Some(21));
+		assert_eq!(parse_major_version("22-ea"), Some(22));
+		assert_eq!(parse_major_version("abc"), None);
 	}
 
-	true
+	#[test]
+	fn test_release_file_validation() {
+		let dir = std::env::temp_dir().join("nitro_core_system_java_test");
+		let _ = std::fs::remove_dir_all(&dir);
+		std::fs::create_dir_all(dir.join("bin")).unwrap();
+		std::fs::write(dir.join("bin").join(format!("java{EXE_SUFFIX}")), "").unwrap();
+		std::fs::write(
+			dir.join("release"),
+			"IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"1.8.0_392\"\n",
+		)
+		.unwrap();
+
+		assert!(is_valid_installation(&dir, None, "8", false));
+		assert!(!is_valid_installation(&dir, Some("jdk-17"), "17", false));
+
+		let _ = std::fs::remove_dir_all(&dir);
+	}
 }
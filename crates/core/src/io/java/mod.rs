@@ -34,8 +34,31 @@ impl JavaMajorVersion {
 	pub fn parse(string: &str) -> Option<Self> {
 		string.parse().map(Self::new).ok()
 	}
+
+	/// Checks if this is a long-term support release, which vendors keep updating for years
+	pub fn is_lts(&self) -> bool {
+		matches!(self.0, 8 | 11) || (self.0 >= 17 && (self.0 - 17).is_multiple_of(4))
+	}
+
+	/// Checks if this version no longer gets updates at the given UTC timestamp. Versions that
+	/// aren't LTS are only updated until the next feature release six months later
+	pub fn is_end_of_life(&self, now: u64) -> bool {
+		if self.is_lts() {
+			return false;
+		}
+		if self.0 < 10 {
+			return true;
+		}
+
+		// Feature releases have come out every March and September since Java 10 in March 2018
+		let next_release_month = (2018 - 1970) * 12 + 2 + (self.0 as u64 + 1 - 10) * 6;
+		now / SECONDS_PER_MONTH >= next_release_month
+	}
 }
 
+/// The average length of a month in seconds
+const SECONDS_PER_MONTH: u64 = 2_629_746;
+
 /// Dealing with Maven
 pub mod maven {
 	/// Sections of a Maven library string
@@ -108,3 +131,23 @@ pub mod maven {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_java_end_of_life() {
+		// October 2026
+		let now = 1792195200;
+		assert!(!JavaMajorVersion(8).is_end_of_life(now));
+		assert!(!JavaMajorVersion(21).is_end_of_life(now));
+		assert!(!JavaMajorVersion(25).is_end_of_life(now));
+		assert!(JavaMajorVersion(7).is_end_of_life(now));
+		assert!(JavaMajorVersion(16).is_end_of_life(now));
+		assert!(JavaMajorVersion(26).is_end_of_life(now));
+		assert!(!JavaMajorVersion(27).is_end_of_life(now));
+		// Late March 2027, after Java 28 comes out
+		assert!(JavaMajorVersion(27).is_end_of_life(1805500800));
+	}
+}
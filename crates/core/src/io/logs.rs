@@ -4,6 +4,9 @@ use anyhow::{Context, bail};
 use itertools::Itertools;
 use libflate::gzip::Decoder;
 
+/// String that secrets are replaced with when they are censored
+pub const CENSOR_STR: &str = "***";
+
 /// Gets the list of log file names in the given Minecraft logs dir ordered from oldest to newest
 pub fn list_logs(logs_dir: &Path) -> anyhow::Result<Vec<String>> {
 	if !logs_dir.exists() {
@@ -52,3 +55,63 @@ pub fn read_log(path: &Path) -> anyhow::Result<String> {
 		std::io::read_to_string(file).context("Failed to read log")
 	}
 }
+
+/// Replaces known secrets, like the access tokens of accounts, in text
+pub fn censor_secrets(text: &str, secrets: &[String]) -> String {
+	let mut out = text.to_string();
+	for secret in secrets.iter().filter(|x| !x.is_empty()) {
+		out = out.replace(secret.as_str(), CENSOR_STR);
+	}
+
+	out
+}
+
+/// Censors account credentials like Minecraft access tokens from log text. This catches tokens
+/// that look like Minecraft access tokens, and should be used along with `censor_secrets` when the
+/// real tokens are known
+pub fn censor_log(text: &str) -> String {
+	// Minecraft access tokens are JWTs, which always start with an encoded '{"'
+	const TOKEN_START: &str = "eyJ";
+
+	let mut out = String::with_capacity(text.len());
+	let mut rest = text;
+	while let Some(start) = rest.find(TOKEN_START) {
+		out.push_str(&rest[..start]);
+		let token = &rest[start..];
+		let len = token
+			.find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+			.unwrap_or(token.len());
+		// JWTs have three parts separated by dots
+		if token[..len].matches('.').count() >= 2 {
+			out.push_str(CENSOR_STR);
+		} else {
+			out.push_str(&token[..len]);
+		}
+		rest = &token[len..];
+	}
+	out.push_str(rest);
+
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_log_censoring() {
+		assert_eq!(
+			censor_log("--accessToken eyJhbGc.eyJzdWIi.c2lnbmF0dXJl --version 1.21"),
+			"--accessToken *** --version 1.21"
+		);
+		assert_eq!(
+			censor_log("(Session ID is token:eyJhbGc.eyJzdWIi.c2ln:069a79f4)"),
+			"(Session ID is token:***:069a79f4)"
+		);
+		assert_eq!(censor_log("eyJ is not a token"), "eyJ is not a token");
+		assert_eq!(
+			censor_secrets("token=abc123 other", &["abc123".into(), String::new()]),
+			"token=*** other"
+		);
+	}
+}
@@ -23,6 +23,9 @@ pub mod game_jar {
 		/// The Minecraft data version number for this version, used for worlds, options.txt, etc.
 		#[serde(rename = "world_version")]
 		pub data_version: i32,
+		/// The network protocol number for this version, used to check compatibility with servers
+		#[serde(default)]
+		pub protocol_version: Option<i32>,
 	}
 
 	/// Extract the version.json file from the game jar
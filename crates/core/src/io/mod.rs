@@ -18,6 +18,10 @@ pub mod logs;
 pub mod minecraft;
 /// Use of a file for persistent data
 pub mod persistent;
+/// Removal of unused game files
+pub mod prune;
+/// Handles to the clock and filesystem that can be replaced with fakes
+pub mod system;
 /// Management of file updates
 pub mod update;
 
@@ -41,6 +45,27 @@ pub fn json_to_file_pretty<S: Serialize>(path: impl AsRef<Path>, data: &S) -> an
 	Ok(())
 }
 
+/// The number of backups kept for files written with `json_to_file_atomic`
+pub const ATOMIC_WRITE_BACKUPS: u8 = 3;
+
+/// Writes JSON to a file atomically with pretty formatting, keeping rotated backups
+/// of the previous contents. Should be used for files that the user edits, like config,
+/// where losing the contents would lose their work
+pub fn json_to_file_atomic<S: Serialize>(path: impl AsRef<Path>, data: &S) -> anyhow::Result<()> {
+	let data = serde_json::to_vec_pretty(data).context("Failed to serialize data")?;
+	files::write_atomic(path.as_ref(), &data, ATOMIC_WRITE_BACKUPS)
+		.context("Failed to write data to file")
+}
+
+/// Writes JSON to a file with pretty formatting by replacing it, so that it is never left
+/// partially written.
+/// Should be used for stores and caches that the launcher manages itself, where
+/// json_to_file_atomic would be too heavy
+pub fn json_to_file_replace<S: Serialize>(path: impl AsRef<Path>, data: &S) -> anyhow::Result<()> {
+	let data = serde_json::to_vec_pretty(data).context("Failed to serialize data")?;
+	files::write_replace(path.as_ref(), &data).context("Failed to write data to file")
+}
+
 /// Writes JSON to a file with less than ideal formatting, but at a higher speed
 pub fn json_to_file_pretty_fast<S: Serialize>(
 	path: impl AsRef<Path>,
@@ -6,7 +6,7 @@ use anyhow::Context;
 use serde::{Deserialize, Serialize};
 
 use super::files::paths::Paths;
-use super::{json_from_file, json_to_file_pretty};
+use super::{json_from_file, json_to_file_replace};
 
 /// A file that remembers important info like what versions and files are currently installed
 #[derive(Debug)]
@@ -54,7 +54,7 @@ impl PersistentData {
 	/// Finish using the persistent data file and write to the disk
 	pub async fn dump(&mut self, paths: &Paths) -> anyhow::Result<()> {
 		let path = Self::get_path(paths);
-		json_to_file_pretty(path, &self.contents)
+		json_to_file_replace(path, &self.contents)
 			.context("Failed to write persistent data contents")?;
 
 		Ok(())
@@ -98,6 +98,22 @@ impl PersistentData {
 		}
 	}
 
+	/// Gets all of the stored Java installations, as their Java type, major version, and path
+	pub fn get_java_installations(&self) -> Vec<(String, String, PathBuf)> {
+		let mut out = Vec::new();
+		for (java, versions) in &self.contents.java {
+			for (major_version, version) in versions {
+				out.push((
+					java.clone(),
+					major_version.clone(),
+					PathBuf::from(&version.path),
+				));
+			}
+		}
+
+		out
+	}
+
 	/// Gets the path to a Java installation
 	pub(crate) fn get_java_path(&self, installation: &str, version: &str) -> Option<PathBuf> {
 		let installation = self.contents.java.get(installation)?;
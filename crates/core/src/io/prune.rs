@@ -0,0 +1,326 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::net::game_files::assets::AssetIndex;
+use crate::net::game_files::client_meta::ClientMeta;
+
+use super::files::paths::Paths;
+use super::json_from_file;
+
+/// Game files that are still in use and must not be pruned
+#[derive(Debug, Default, Clone)]
+pub struct ReferencedFiles {
+	/// Minecraft versions that are installed on instances
+	pub versions: HashSet<String>,
+	/// Other files that are used by instances, such as libraries added by loaders
+	pub files: HashSet<PathBuf>,
+}
+
+/// Options for what to prune
+#[derive(Debug, Clone, Copy)]
+pub struct PruneOptions {
+	/// Whether to prune game assets
+	pub assets: bool,
+	/// Whether to prune game libraries
+	pub libraries: bool,
+	/// Whether to prune game jars
+	pub jars: bool,
+	/// Only report what would be removed without removing anything
+	pub dry_run: bool,
+}
+
+impl Default for PruneOptions {
+	fn default() -> Self {
+		Self {
+			assets: true,
+			libraries: true,
+			jars: true,
+			dry_run: false,
+		}
+	}
+}
+
+/// Report of files that were pruned, or would be pruned in a dry run
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct PruneReport {
+	/// Pruned assets and asset indexes
+	pub assets: PrunedFiles,
+	/// Pruned libraries and native library jars
+	pub libraries: PrunedFiles,
+	/// Pruned game jars
+	pub jars: PrunedFiles,
+}
+
+impl PruneReport {
+	/// Gets the total number of pruned files
+	pub fn file_count(&self) -> usize {
+		self.assets.files.len() + self.libraries.files.len() + self.jars.files.len()
+	}
+
+	/// Gets the total amount of reclaimed space in bytes
+	pub fn total_size(&self) -> u64 {
+		self.assets.size + self.libraries.size + self.jars.size
+	}
+}
+
+/// A set of pruned files
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct PrunedFiles {
+	/// The files that were pruned
+	pub files: Vec<PathBuf>,
+	/// The combined size of the files in bytes
+	pub size: u64,
+}
+
+impl PrunedFiles {
+	/// Adds a file if it exists and isn't referenced
+	fn add(&mut self, path: PathBuf, referenced: &ReferencedFiles) {
+		if referenced.files.contains(&path) {
+			return;
+		}
+		let Ok(meta) = path.metadata() else {
+			return;
+		};
+		if !meta.is_file() {
+			return;
+		}
+		self.size += meta.len();
+		self.files.push(path);
+	}
+
+	/// Removes all of the files
+	fn remove(&self) -> anyhow::Result<()> {
+		for file in &self.files {
+			if let Err(e) = std::fs::remove_file(file)
+				&& e.kind() != std::io::ErrorKind::NotFound
+			{
+				return Err(e).with_context(|| format!("Failed to remove file {file:?}"));
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Removes assets, libraries, and game jars that aren't referenced by any of the given versions or files.
+/// Only files that Nitrolaunch knows came from a Minecraft version are considered,
+/// so libraries installed by loaders and plugins are left alone
+pub fn prune(
+	referenced: &ReferencedFiles,
+	options: &PruneOptions,
+	paths: &Paths,
+) -> anyhow::Result<PruneReport> {
+	let mut report = PruneReport::default();
+
+	if options.assets {
+		report.assets =
+			get_unused_assets(referenced, paths).context("Failed to find unused assets")?;
+	}
+	if options.libraries {
+		report.libraries =
+			get_unused_libraries(referenced, paths).context("Failed to find unused libraries")?;
+	}
+	if options.jars {
+		report.jars = get_unused_jars(referenced, paths).context("Failed to find unused jars")?;
+	}
+
+	if !options.dry_run {
+		report.assets.remove()?;
+		report.libraries.remove()?;
+		report.jars.remove()?;
+	}
+
+	Ok(report)
+}
+
+/// Finds asset objects and indexes that are only used by unreferenced versions
+fn get_unused_assets(referenced: &ReferencedFiles, paths: &Paths) -> anyhow::Result<PrunedFiles> {
+	let mut out = PrunedFiles::default();
+	let indexes_dir = paths.assets.join("indexes");
+	let objects_dir = paths.assets.join("objects");
+
+	let mut used_objects = HashSet::new();
+	let mut unused_indexes = Vec::new();
+	for (version, path) in read_json_files(&indexes_dir)? {
+		let index: AssetIndex = json_from_file(&path)
+			.with_context(|| format!("Failed to read asset index for version {version}"))?;
+		let hash_paths = index.objects.values().map(|x| x.get_hash_path());
+		if referenced.versions.contains(&version) {
+			used_objects.extend(hash_paths);
+		} else {
+			unused_indexes.push((path, hash_paths.collect::<Vec<_>>()));
+		}
+	}
+
+	let mut seen = HashSet::new();
+	for (index_path, objects) in unused_indexes {
+		for object in objects {
+			if !used_objects.contains(&object) && seen.insert(object.clone()) {
+				out.add(objects_dir.join(object), referenced);
+			}
+		}
+		out.add(index_path, referenced);
+	}
+
+	Ok(out)
+}
+
+/// Finds libraries and native jars that are only used by unreferenced versions
+fn get_unused_libraries(
+	referenced: &ReferencedFiles,
+	paths: &Paths,
+) -> anyhow::Result<PrunedFiles> {
+	let mut out = PrunedFiles::default();
+	let natives_dir = paths.internal.join("natives");
+
+	let mut used = HashSet::new();
+	let mut unused = HashSet::new();
+	for (version, path) in read_json_files(&paths.internal.join("versions"))? {
+		// Other files may be stored alongside client metas, so don't fail on ones we can't read
+		let Ok(meta) = json_from_file::<ClientMeta>(&path) else {
+			continue;
+		};
+		let libraries = meta.libraries.iter().flat_map(|lib| {
+			let artifact = lib
+				.downloads
+				.artifact
+				.iter()
+				.map(|x| paths.libraries.join(&x.path));
+			let natives = lib
+				.downloads
+				.native_classifiers
+				.values()
+				.map(|x| natives_dir.join(&x.path));
+			artifact.chain(natives)
+		});
+		if referenced.versions.contains(&version) {
+			used.extend(libraries);
+		} else {
+			unused.extend(libraries);
+		}
+	}
+
+	let mut unused: Vec<_> = unused.difference(&used).cloned().collect();
+	unused.sort();
+	for path in unused {
+		out.add(path, referenced);
+	}
+
+	Ok(out)
+}
+
+/// Finds client and server jars of unreferenced versions
+fn get_unused_jars(referenced: &ReferencedFiles, paths: &Paths) -> anyhow::Result<PrunedFiles> {
+	let mut out = PrunedFiles::default();
+	if !paths.jars.exists() {
+		return Ok(out);
+	}
+
+	let mut jars = Vec::new();
+	for entry in paths
+		.jars
+		.read_dir()
+		.context("Failed to read jars directory")?
+	{
+		let path = entry?.path();
+		let Some(name) = path.file_name() else {
+			continue;
+		};
+		let name = name.to_string_lossy();
+		if !name.ends_with(".jar") || !(name.contains("_client") || name.contains("_server")) {
+			continue;
+		}
+
+		let is_used = referenced.versions.iter().any(|version| {
+			name.starts_with(&format!("{version}_client"))
+				|| name.starts_with(&format!("{version}_server"))
+		});
+		if !is_used {
+			jars.push(path);
+		}
+	}
+
+	jars.sort();
+	for path in jars {
+		out.add(path, referenced);
+	}
+
+	Ok(out)
+}
+
+/// Reads the JSON files in a directory, along with their file stems
+fn read_json_files(dir: &Path) -> anyhow::Result<Vec<(String, PathBuf)>> {
+	let mut out = Vec::new();
+	if !dir.exists() {
+		return Ok(out);
+	}
+
+	for entry in dir.read_dir().context("Failed to read directory")? {
+		let path = entry?.path();
+		if !path.is_file() || path.extension().is_none_or(|x| x != "json") {
+			continue;
+		}
+		let Some(stem) = path.file_stem() else {
+			continue;
+		};
+		out.push((stem.to_string_lossy().to_string(), path));
+	}
+
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_prune_jars() {
+		let dir = std::env::temp_dir().join("nitro_prune_test");
+		let _ = std::fs::remove_dir_all(&dir);
+		let mut paths = Paths::new_no_create().unwrap();
+		paths.jars = dir.clone();
+		paths.assets = dir.join("assets");
+		std::fs::create_dir_all(&dir).unwrap();
+		for name in [
+			"1.20.1_client.jar",
+			"1.19_client.jar",
+			"1.19_server.jar",
+			"other.jar",
+		] {
+			std::fs::write(dir.join(name), "jar").unwrap();
+		}
+
+		let referenced = ReferencedFiles {
+			versions: HashSet::from(["1.20.1".into()]),
+			files: HashSet::new(),
+		};
+		let options = PruneOptions {
+			assets: false,
+			libraries: false,
+			jars: true,
+			dry_run: true,
+		};
+
+		let report = prune(&referenced, &options, &paths).unwrap();
+		assert_eq!(
+			report.jars.files,
+			vec![dir.join("1.19_client.jar"), dir.join("1.19_server.jar")]
+		);
+		assert_eq!(report.total_size(), 6);
+		assert!(dir.join("1.19_client.jar").exists());
+
+		let options = PruneOptions {
+			dry_run: false,
+			..options
+		};
+		prune(&referenced, &options, &paths).unwrap();
+		assert!(!dir.join("1.19_client.jar").exists());
+		assert!(dir.join("1.20.1_client.jar").exists());
+		assert!(dir.join("other.jar").exists());
+
+		let _ = std::fs::remove_dir_all(&dir);
+	}
+}
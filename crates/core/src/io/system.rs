@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// Source of the current time
+pub trait Clock: Debug + Send + Sync {
+	/// Gets the current time
+	fn now(&self) -> SystemTime;
+}
+
+/// Clock that uses the time of the system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+	fn now(&self) -> SystemTime {
+		SystemTime::now()
+	}
+}
+
+/// Clock that only changes when it is told to, so that time-dependent behavior is deterministic.
+/// Clones of the clock share the same time
+#[derive(Debug, Clone)]
+pub struct FakeClock {
+	time: Arc<Mutex<SystemTime>>,
+}
+
+impl FakeClock {
+	/// Create a new FakeClock that starts at a time
+	pub fn new(time: SystemTime) -> Self {
+		Self {
+			time: Arc::new(Mutex::new(time)),
+		}
+	}
+
+	/// Sets the current time of the clock
+	pub fn set(&self, time: SystemTime) {
+		if let Ok(mut current) = self.time.lock() {
+			*current = time;
+		}
+	}
+
+	/// Moves the clock forward
+	pub fn advance(&self, duration: Duration) {
+		if let Ok(mut current) = self.time.lock() {
+			*current += duration;
+		}
+	}
+}
+
+impl Default for FakeClock {
+	fn default() -> Self {
+		Self::new(SystemTime::UNIX_EPOCH)
+	}
+}
+
+impl Clock for FakeClock {
+	fn now(&self) -> SystemTime {
+		self.time
+			.lock()
+			.map(|x| *x)
+			.unwrap_or(SystemTime::UNIX_EPOCH)
+	}
+}
+
+/// Checks on the filesystem that are used to decide whether files need to be updated.
+/// Reading and writing the files themselves is not done through this
+pub trait FileSystem: Debug + Send + Sync {
+	/// Checks if a file exists
+	fn exists(&self, path: &Path) -> bool;
+
+	/// Gets the time a file was last modified, if it exists
+	fn modified(&self, path: &Path) -> Option<SystemTime>;
+}
+
+/// FileSystem that checks the real files on the system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealFileSystem;
+
+impl FileSystem for RealFileSystem {
+	fn exists(&self, path: &Path) -> bool {
+		path.exists()
+	}
+
+	fn modified(&self, path: &Path) -> Option<SystemTime> {
+		path.metadata().and_then(|x| x.modified()).ok()
+	}
+}
+
+/// FileSystem with a set of files that only exist in memory. Clones share the same files
+#[derive(Debug, Clone, Default)]
+pub struct FakeFileSystem {
+	files: Arc<Mutex<HashMap<PathBuf, SystemTime>>>,
+}
+
+impl FakeFileSystem {
+	/// Create a new empty FakeFileSystem
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a file, or updates the modification time of an existing one
+	pub fn add_file(&self, path: impl Into<PathBuf>, modified: SystemTime) {
+		if let Ok(mut files) = self.files.lock() {
+			files.insert(path.into(), modified);
+		}
+	}
+
+	/// Removes a file
+	pub fn remove_file(&self, path: &Path) {
+		if let Ok(mut files) = self.files.lock() {
+			files.remove(path);
+		}
+	}
+}
+
+impl FileSystem for FakeFileSystem {
+	fn exists(&self, path: &Path) -> bool {
+		self.modified(path).is_some()
+	}
+
+	fn modified(&self, path: &Path) -> Option<SystemTime> {
+		self.files.lock().ok()?.get(path).copied()
+	}
+}
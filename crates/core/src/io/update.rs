@@ -1,7 +1,15 @@
 use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufReader, Read};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use nitro_shared::UpdateDepth;
+use ring::digest::{Context, SHA1_FOR_LEGACY_USE_ONLY};
+
+use super::system::{Clock, FileSystem, RealFileSystem, SystemClock};
+use crate::net::get_transfer_limit;
 
 /// Manager for when we are updating instance files.
 /// It will keep track of files we have already downloaded, manage task requirements, etc
@@ -11,6 +19,14 @@ pub struct UpdateManager {
 	pub(crate) update_depth: UpdateDepth,
 	/// File paths that are added when they have been updated by other functions
 	files: HashSet<PathBuf>,
+	/// Verifier for checking the hashes of existing files, if files should be verified
+	verifier: Option<FileVerifier>,
+	/// The clock used to check how old files are
+	clock: Arc<dyn Clock>,
+	/// The filesystem used to check which files exist
+	filesystem: Arc<dyn FileSystem>,
+	/// The maximum number of files to download at the same time
+	transfer_limit: usize,
 }
 
 impl UpdateManager {
@@ -19,9 +35,39 @@ impl UpdateManager {
 		Self {
 			update_depth: depth,
 			files: HashSet::new(),
+			verifier: None,
+			clock: Arc::new(SystemClock),
+			filesystem: Arc::new(RealFileSystem),
+			transfer_limit: get_transfer_limit(),
 		}
 	}
 
+	/// Set the verifier to check the hashes of existing files with
+	pub fn set_verifier(&mut self, verifier: Option<FileVerifier>) {
+		self.verifier = verifier;
+	}
+
+	/// Set the clock used to check how old files are
+	pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+		self.clock = clock;
+	}
+
+	/// Set the filesystem used to check which files exist
+	pub fn set_filesystem(&mut self, filesystem: Arc<dyn FileSystem>) {
+		self.filesystem = filesystem;
+	}
+
+	/// Set the maximum number of files to download at the same time
+	pub fn set_transfer_limit(&mut self, limit: usize) {
+		// Nothing would ever be downloaded with a limit of zero
+		self.transfer_limit = limit.max(1);
+	}
+
+	/// Gets the maximum number of files to download at the same time
+	pub fn get_transfer_limit(&self) -> usize {
+		self.transfer_limit
+	}
+
 	/// Add a single tracked file to the manager
 	pub fn add_file(&mut self, file: PathBuf) {
 		self.files.insert(file);
@@ -40,18 +86,117 @@ impl UpdateManager {
 	/// Whether a file needs to be updated
 	pub fn should_update_file(&self, file: &Path) -> bool {
 		if self.update_depth == UpdateDepth::Force {
-			!self.files.contains(file) || !file.exists()
+			!self.files.contains(file) || !self.file_exists(file)
 		} else {
-			!file.exists()
+			!self.file_exists(file)
 		}
 	}
 
+	/// Checks if a file exists
+	pub fn file_exists(&self, file: &Path) -> bool {
+		self.filesystem.exists(file)
+	}
+
+	/// Checks if a file is missing or was last modified longer ago than a maximum age
+	pub fn is_file_older_than(&self, file: &Path, max_age: Duration) -> bool {
+		let Some(modified) = self.filesystem.modified(file) else {
+			return true;
+		};
+
+		// Files modified in the future are treated as fresh
+		self.clock
+			.now()
+			.duration_since(modified)
+			.is_ok_and(|x| x > max_age)
+	}
+
+	/// Whether a file with a known SHA-1 hash needs to be updated. When verifying, existing files
+	/// are also updated if their hash doesn't match
+	pub fn should_update_file_with_hash(&self, file: &Path, sha1: Option<&str>) -> bool {
+		if self.should_update_file(file) {
+			return true;
+		}
+
+		match (&self.verifier, sha1) {
+			(Some(verifier), Some(sha1)) => !verifier.verify(file, sha1),
+			_ => false,
+		}
+	}
+
+	/// Whether existing files are being verified
+	pub fn is_verifying(&self) -> bool {
+		self.verifier.is_some()
+	}
+
 	/// Gets the update depth of the manager
 	pub fn get_depth(&self) -> UpdateDepth {
 		self.update_depth
 	}
 }
 
+/// Checks existing files against their expected hashes and keeps track of the ones that are corrupted
+#[derive(Debug, Clone, Default)]
+pub struct FileVerifier {
+	corrupted: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+impl FileVerifier {
+	/// Create a new FileVerifier
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Checks if a file matches its expected SHA-1 hash, recording it as corrupted if it doesn't
+	pub fn verify(&self, file: &Path, sha1: &str) -> bool {
+		let matches = hash_file_sha1(file).is_ok_and(|x| x.eq_ignore_ascii_case(sha1));
+		if !matches && let Ok(mut corrupted) = self.corrupted.lock() {
+			corrupted.push(file.to_path_buf());
+		}
+
+		matches
+	}
+
+	/// Records a file as corrupted that was checked in some other way
+	pub fn add_corrupted_file(&self, file: PathBuf) {
+		if let Ok(mut corrupted) = self.corrupted.lock() {
+			corrupted.push(file);
+		}
+	}
+
+	/// Gets the files that were found to be corrupted
+	pub fn get_corrupted_files(&self) -> Vec<PathBuf> {
+		self.corrupted.lock().map(|x| x.clone()).unwrap_or_default()
+	}
+}
+
+/// Computes the SHA-1 hash of a file as a hex string
+pub fn hash_file_sha1(path: &Path) -> std::io::Result<String> {
+	let mut file = BufReader::new(File::open(path)?);
+	let mut context = Context::new(&SHA1_FOR_LEGACY_USE_ONLY);
+	let mut buf = [0; 8192];
+	loop {
+		let count = file.read(&mut buf)?;
+		if count == 0 {
+			break;
+		}
+		context.update(&buf[..count]);
+	}
+
+	Ok(to_hex(context.finish().as_ref()))
+}
+
+/// Computes the SHA-1 hash of bytes as a hex string
+pub fn hash_sha1(bytes: &[u8]) -> String {
+	let mut context = Context::new(&SHA1_FOR_LEGACY_USE_ONLY);
+	context.update(bytes);
+	to_hex(context.finish().as_ref())
+}
+
+/// Converts a hash to a lowercase hex string
+fn to_hex(hash: &[u8]) -> String {
+	hash.iter().map(|x| format!("{x:02x}")).collect()
+}
+
 /// Struct returned by updating functions, with data like changed files
 #[derive(Default)]
 pub struct UpdateMethodResult {
@@ -70,3 +215,85 @@ impl UpdateMethodResult {
 		self.files_updated.extend(other.files_updated);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::time::SystemTime;
+
+	use crate::io::system::{FakeClock, FakeFileSystem};
+
+	use super::*;
+
+	#[test]
+	fn test_file_verification() {
+		let path = std::env::temp_dir().join("nitro_core_verify_test.txt");
+		std::fs::write(&path, "Hello").unwrap();
+
+		let mut manager = UpdateManager::new(UpdateDepth::Shallow);
+		let hash = "f7ff9e8b7bb2e09b70935a5d785e0cc5d9d0abf0";
+		assert!(!manager.should_update_file_with_hash(&path, Some(hash)));
+
+		let verifier = FileVerifier::new();
+		manager.set_verifier(Some(verifier.clone()));
+		assert!(!manager.should_update_file_with_hash(&path, Some(hash)));
+		assert!(!manager.should_update_file_with_hash(&path, None));
+		assert!(verifier.get_corrupted_files().is_empty());
+
+		std::fs::write(&path, "Corrupted").unwrap();
+		assert!(manager.should_update_file_with_hash(&path, Some(hash)));
+		assert_eq!(verifier.get_corrupted_files(), vec![path.clone()]);
+
+		let _ = std::fs::remove_file(&path);
+	}
+
+	#[test]
+	fn test_update_depth() {
+		let fs = FakeFileSystem::new();
+		let path = PathBuf::from("/fake/file.jar");
+
+		let mut manager = UpdateManager::new(UpdateDepth::Full);
+		manager.set_filesystem(Arc::new(fs.clone()));
+		assert!(manager.should_update_file(&path));
+		fs.add_file(&path, SystemTime::UNIX_EPOCH);
+		assert!(!manager.should_update_file(&path));
+
+		manager.update_depth = UpdateDepth::Force;
+		assert!(manager.should_update_file(&path));
+		manager.add_file(path.clone());
+		assert!(!manager.should_update_file(&path));
+		fs.remove_file(&path);
+		assert!(manager.should_update_file(&path));
+	}
+
+	#[test]
+	fn test_transfer_limit() {
+		let mut manager = UpdateManager::new(UpdateDepth::Shallow);
+		assert_eq!(manager.get_transfer_limit(), get_transfer_limit());
+		manager.set_transfer_limit(4);
+		assert_eq!(manager.get_transfer_limit(), 4);
+		manager.set_transfer_limit(0);
+		assert_eq!(manager.get_transfer_limit(), 1);
+	}
+
+	#[test]
+	fn test_file_age() {
+		let fs = FakeFileSystem::new();
+		let clock = FakeClock::default();
+		let path = PathBuf::from("/fake/manifest.json");
+		let max_age = Duration::from_secs(60);
+
+		let mut manager = UpdateManager::new(UpdateDepth::Shallow);
+		manager.set_filesystem(Arc::new(fs.clone()));
+		manager.set_clock(Arc::new(clock.clone()));
+		assert!(manager.is_file_older_than(&path, max_age));
+
+		fs.add_file(&path, clock.now());
+		assert!(!manager.is_file_older_than(&path, max_age));
+		clock.advance(Duration::from_secs(61));
+		assert!(manager.is_file_older_than(&path, max_age));
+
+		clock.set(SystemTime::UNIX_EPOCH);
+		fs.add_file(&path, SystemTime::UNIX_EPOCH + Duration::from_secs(10));
+		assert!(!manager.is_file_older_than(&path, max_age));
+	}
+}
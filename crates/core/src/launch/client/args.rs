@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use nitro_shared::output::{MessageContents, NitroOutput};
 use nitro_shared::util::{ARCH_STRING, OS_STRING};
 use nitro_shared::versions::VersionPattern;
@@ -7,18 +9,22 @@ use crate::launch::{LaunchParameters, QuickPlayType};
 
 use crate::account::AccountKind;
 use crate::io::files::paths::Paths;
-use crate::net::game_files::assets::get_virtual_dir_path;
 use crate::net::game_files::client_meta::args::ArgumentItem;
+use crate::net::game_files::libraries::get_natives_dir;
 
 /// Process an argument for the client from the client meta
-pub(crate) fn process_arg(arg: &ArgumentItem, params: &LaunchParameters) -> Vec<String> {
+pub(crate) fn process_arg(
+	arg: &ArgumentItem,
+	params: &LaunchParameters,
+	game_assets: &Path,
+) -> Vec<String> {
 	let mut out = Vec::new();
 	let InstanceKind::Client { window } = &params.side else {
 		panic!("Instance is not a client")
 	};
 	match arg {
 		ArgumentItem::Simple(arg) => {
-			let arg = process_simple_arg(arg, params);
+			let arg = process_simple_arg(arg, params, game_assets);
 			if let Some(arg) = arg {
 				out.push(arg);
 			}
@@ -46,14 +52,9 @@ pub(crate) fn process_arg(arg: &ArgumentItem, params: &LaunchParameters) -> Vec<
 				}
 				if let Some(is_demo_user) = &rule.features.is_demo_user
 					&& *is_demo_user
+					&& !use_demo(params)
 				{
-					let use_demo = match params.accounts.get_chosen_account() {
-						Some(account) => matches!(account.kind, AccountKind::Demo),
-						None => false,
-					};
-					if !use_demo {
-						return vec![];
-					}
+					return vec![];
 				}
 				if let Some(quick_play_support) = &rule.features.has_quick_play_support
 					&& *quick_play_support
@@ -84,7 +85,7 @@ pub(crate) fn process_arg(arg: &ArgumentItem, params: &LaunchParameters) -> Vec<
 			}
 
 			for arg in arg.value.iter() {
-				out.extend(process_simple_arg(arg, params));
+				out.extend(process_simple_arg(arg, params, game_assets));
 			}
 		}
 	};
@@ -92,9 +93,23 @@ pub(crate) fn process_arg(arg: &ArgumentItem, params: &LaunchParameters) -> Vec<
 	out
 }
 
+/// Checks whether the game should be launched in demo mode, either because it was
+/// requested in the launch config or because the chosen account is a demo account
+pub(crate) fn use_demo(params: &LaunchParameters) -> bool {
+	params.launch_config.demo
+		|| params
+			.accounts
+			.get_chosen_account()
+			.is_some_and(|account| matches!(account.kind, AccountKind::Demo))
+}
+
 /// Process a simple string argument
-pub(crate) fn process_simple_arg(arg: &str, params: &LaunchParameters) -> Option<String> {
-	replace_arg_placeholders(arg, params)
+pub(crate) fn process_simple_arg(
+	arg: &str,
+	params: &LaunchParameters,
+	game_assets: &Path,
+) -> Option<String> {
+	replace_arg_placeholders(arg, params, game_assets)
 }
 
 /// Get the string for a placeholder token in an argument
@@ -104,33 +119,40 @@ macro_rules! placeholder {
 	};
 }
 
-/// Replace placeholders in a string argument from the client meta
-pub(crate) fn replace_arg_placeholders(arg: &str, params: &LaunchParameters) -> Option<String> {
-	// Branding properties
-	let mut out = arg.replace(
-		placeholder!("launcher_name"),
-		&params.branding.launcher_name,
-	);
-	out = out.replace(
-		placeholder!("launcher_version"),
-		&params.branding.launcher_version,
-	);
+/// Replace placeholders in a string argument from the client meta.
+/// The game assets directory is where old versions read their assets from
+pub(crate) fn replace_arg_placeholders(
+	arg: &str,
+	params: &LaunchParameters,
+	game_assets: &Path,
+) -> Option<String> {
+	// Branding properties, which instances can override
+	let launcher_name = params
+		.launch_config
+		.launcher_name
+		.as_ref()
+		.unwrap_or(&params.branding.launcher_name);
+	let launcher_version = params
+		.launch_config
+		.launcher_version
+		.as_ref()
+		.unwrap_or(&params.branding.launcher_version);
+	let mut out = arg.replace(placeholder!("launcher_name"), launcher_name);
+	out = out.replace(placeholder!("launcher_version"), launcher_version);
 
 	// Game files information
 	out = out.replace(placeholder!("classpath"), &params.classpath.get_str());
 	out = out.replace(
 		placeholder!("natives_directory"),
-		params
-			.paths
-			.internal
-			.join("versions")
-			.join(params.version.to_string())
-			.join("natives")
-			.to_string_lossy()
-			.as_ref(),
+		get_natives_dir(
+			&params.paths.internal,
+			params.version,
+			params.launch_config.get_natives_arch(),
+		)
+		.to_string_lossy()
+		.as_ref(),
 	);
 	out = out.replace(placeholder!("version_name"), params.version);
-	out = out.replace(placeholder!("version_type"), "Nitrolaunch");
 	out = out.replace(
 		placeholder!("game_directory"),
 		params.launch_dir.to_string_lossy().as_ref(),
@@ -142,9 +164,7 @@ pub(crate) fn replace_arg_placeholders(arg: &str, params: &LaunchParameters) ->
 	out = out.replace(placeholder!("assets_index_name"), params.version);
 	out = out.replace(
 		placeholder!("game_assets"),
-		get_virtual_dir_path(params.paths)
-			.to_string_lossy()
-			.as_ref(),
+		game_assets.to_string_lossy().as_ref(),
 	);
 
 	out = out.replace(placeholder!("clientid"), "Nitrolaunch");
@@ -159,6 +179,11 @@ pub(crate) fn replace_arg_placeholders(arg: &str, params: &LaunchParameters) ->
 		out = out.replace(placeholder!("resolution_width"), &width.to_string());
 		out = out.replace(placeholder!("resolution_height"), &height.to_string());
 	}
+	// The version type is shown after the version on the title and debug screens
+	out = out.replace(
+		placeholder!("version_type"),
+		window.title_suffix.as_deref().unwrap_or("Nitrolaunch"),
+	);
 
 	// QuickPlayType
 	out = out.replace(placeholder!("quickPlayPath"), "quickPlay/log.json");
@@ -197,6 +222,8 @@ pub(crate) fn replace_arg_placeholders(arg: &str, params: &LaunchParameters) ->
 			// User type
 			let user_type = match account.get_kind() {
 				AccountKind::Microsoft { .. } => "msa",
+				AccountKind::Offline { .. } => "legacy",
+				AccountKind::AuthlibInjector { .. } => "mojang",
 				_ => "msa",
 			};
 			out = out.replace(placeholder!("user_type"), user_type);
@@ -209,6 +236,17 @@ pub(crate) fn replace_arg_placeholders(arg: &str, params: &LaunchParameters) ->
 			}
 			if let Some(access_token) = account.get_access_token() {
 				out = out.replace(placeholder!("auth_access_token"), &access_token.0);
+				// Old versions take the token and UUID together as a session ID
+				if let Some(uuid) = account.get_uuid() {
+					out = out.replace(
+						placeholder!("auth_session"),
+						&format!("token:{}:{uuid}", access_token.0),
+					);
+				}
+			} else if account.is_offline() {
+				// The game requires some access token even though it won't be checked
+				out = out.replace(placeholder!("auth_access_token"), "0");
+				out = out.replace(placeholder!("auth_session"), "-");
 			}
 			if let AccountKind::Microsoft {
 				xbox_uid: Some(xbox_uid),
@@ -220,6 +258,7 @@ pub(crate) fn replace_arg_placeholders(arg: &str, params: &LaunchParameters) ->
 			// Blank any args we don't replace since the game will complain if we don't
 			if out.contains(placeholder!("auth_player_name"))
 				|| out.contains(placeholder!("auth_access_token"))
+				|| out.contains(placeholder!("auth_session"))
 				|| out.contains(placeholder!("auth_uuid"))
 				|| out.contains(placeholder!("auth_xuid"))
 			{
@@ -231,6 +270,7 @@ pub(crate) fn replace_arg_placeholders(arg: &str, params: &LaunchParameters) ->
 				return Some("UnknownUser".into());
 			}
 			if out.contains(placeholder!("auth_access_token"))
+				|| out.contains(placeholder!("auth_session"))
 				|| out.contains(placeholder!("auth_uuid"))
 			{
 				return Some(String::new());
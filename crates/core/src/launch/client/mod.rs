@@ -5,23 +5,37 @@ use anyhow::{anyhow, bail};
 
 use std::collections::HashMap;
 
+use anyhow::Context;
+use nitro_shared::error::AuthError;
+use nitro_shared::output::NitroOutput;
 use nitro_shared::skip_none;
-#[cfg(target_os = "linux")]
 use nitro_shared::versions::VersionPattern;
 
 pub use args::create_quick_play_args;
+pub(crate) use args::use_demo;
 
+use crate::net::authlib_injector;
+use crate::net::game_files::assets::{get_legacy_assets_dir, get_virtual_dir_path};
 use crate::net::game_files::client_meta::args::Arguments;
+use crate::net::game_files::libraries::get_natives_dir;
 
-use super::{LaunchParameters, process::LaunchProcessProperties};
+use super::{JavaAgent, LaunchParameters, process::LaunchProcessProperties};
+
+/// Proxy that redirects the skin and session servers used by old versions to working ones
+const LEGACY_PROXY_HOST: &str = "betacraft.uk";
+/// Port for the legacy proxy
+const LEGACY_PROXY_PORT: u16 = 11705;
+/// The last version that uses the old skin servers
+const LAST_LEGACY_SKIN_VERSION: &str = "1.7.5";
 
 /// Create launch properties for the client
 pub(crate) async fn get_launch_props(
 	params: &LaunchParameters<'_>,
+	o: &mut impl NitroOutput,
 ) -> anyhow::Result<LaunchProcessProperties> {
 	// Ensure an account is picked
 	if !params.accounts.is_account_chosen() {
-		bail!("No account chosen");
+		bail!(AuthError::NoAccountChosen);
 	}
 
 	// Build up arguments
@@ -37,16 +51,51 @@ pub(crate) async fn get_launch_props(
 		jvm_args.push(logging_arg);
 	}
 
+	if params.launch_config.legacy_fixes
+		&& VersionPattern::Before(LAST_LEGACY_SKIN_VERSION.into())
+			.matches_single(params.version, &params.version_manifest.list)
+	{
+		jvm_args.push(format!("-Dhttp.proxyHost={LEGACY_PROXY_HOST}"));
+		jvm_args.push(format!("-Dhttp.proxyPort={LEGACY_PROXY_PORT}"));
+	}
+
+	// Accounts on alternative authentication servers need authlib-injector to redirect the game to them
+	if let Some(server) = params
+		.accounts
+		.get_chosen_account()
+		.and_then(|x| x.get_authlib_injector_server())
+	{
+		let path =
+			authlib_injector::get_agent(params.paths, params.req_client, params.offline_auth, o)
+				.await
+				.context("Failed to get authlib-injector")?;
+		let agent = JavaAgent {
+			path,
+			options: Some(server.to_string()),
+		};
+		jvm_args.push(agent.to_arg());
+	}
+
+	let game_assets = get_legacy_assets_dir(
+		params.client_meta,
+		params.paths,
+		params.version,
+		params.launch_dir,
+	)
+	.await
+	.context("Failed to set up legacy assets")?
+	.unwrap_or_else(|| get_virtual_dir_path(params.paths, params.client_meta.get_assets_id()));
+
 	match &params.client_meta.arguments {
 		Arguments::New(args) => {
 			for arg in &args.jvm {
-				for sub_arg in args::process_arg(arg, params) {
+				for sub_arg in args::process_arg(arg, params, &game_assets) {
 					jvm_args.push(sub_arg);
 				}
 			}
 
 			for arg in &args.game {
-				for sub_arg in args::process_arg(arg, params) {
+				for sub_arg in args::process_arg(arg, params, &game_assets) {
 					game_args.push(sub_arg);
 				}
 			}
@@ -54,25 +103,36 @@ pub(crate) async fn get_launch_props(
 		Arguments::Old(args) => {
 			jvm_args.push(format!(
 				"-Djava.library.path={}",
-				params
-					.paths
-					.internal
-					.join("versions")
-					.join(params.version.to_string())
-					.join("natives")
-					.to_string_lossy()
+				get_natives_dir(
+					&params.paths.internal,
+					params.version,
+					params.launch_config.get_natives_arch()
+				)
+				.to_string_lossy()
 			));
 			jvm_args.push("-cp".into());
 			jvm_args.push(params.classpath.get_str());
 
-			for arg in args.split(' ') {
-				game_args.push(skip_none!(args::replace_arg_placeholders(arg, params)));
+			for arg in args.split(' ').filter(|x| !x.is_empty()) {
+				game_args.push(skip_none!(args::replace_arg_placeholders(
+					arg,
+					params,
+					&game_assets
+				)));
+			}
+
+			// Old versions don't have a rule for demo mode in their arguments
+			if args::use_demo(params) {
+				game_args.push("--demo".into());
 			}
 		}
 	}
 
-	let env_vars =
+	let mut env_vars =
 		get_additional_environment_variables(params.version, &params.version_manifest.list);
+	if let Some(gpu_preference) = params.launch_config.gpu_preference {
+		env_vars.extend(super::gpu::get_gpu_env_vars(gpu_preference));
+	}
 
 	let props = LaunchProcessProperties {
 		jvm_args,
@@ -1,7 +1,8 @@
-use std::{collections::HashMap, convert::Infallible, fmt::Display, str::FromStr};
+use std::{collections::HashMap, convert::Infallible, fmt::Display, path::PathBuf, str::FromStr};
 
 use crate::io::java::install::JavaInstallationKind;
 use nitro_shared::java_args::MemoryNum;
+use nitro_shared::minecraft::{GpuPreference, JvmPreset, NativesArch};
 
 /// Options for launching an instance
 #[derive(Debug, Clone)]
@@ -10,6 +11,8 @@ pub struct LaunchConfiguration {
 	pub java: JavaInstallationKind,
 	/// JVM arguments
 	pub jvm_args: Vec<String>,
+	/// Preset of tuned JVM arguments that come before the other ones
+	pub jvm_preset: Option<JvmPreset>,
 	/// Game arguments
 	pub game_args: Vec<String>,
 	/// Minimum JVM memory
@@ -24,6 +27,22 @@ pub struct LaunchConfiguration {
 	pub quick_play: QuickPlayType,
 	/// Whether or not to use the Log4J configuration
 	pub use_log4j_config: bool,
+	/// Whether to automatically protect versions that are affected by Log4Shell
+	pub log4shell_mitigation: bool,
+	/// Whether to apply compatibility fixes for old versions, such as proxying skins
+	pub legacy_fixes: bool,
+	/// Whether to launch the game in demo mode
+	pub demo: bool,
+	/// Architecture to select native libraries for. Detected automatically if not set
+	pub natives_arch: Option<NativesArch>,
+	/// Java agents to attach to the game
+	pub java_agents: Vec<JavaAgent>,
+	/// The GPU to prefer for the client. Uses the system default if not set
+	pub gpu_preference: Option<GpuPreference>,
+	/// Override for the launcher name that is sent to the client
+	pub launcher_name: Option<String>,
+	/// Override for the launcher version that is sent to the client
+	pub launcher_version: Option<String>,
 }
 
 impl LaunchConfiguration {
@@ -32,6 +51,7 @@ impl LaunchConfiguration {
 		Self {
 			java: JavaInstallationKind::Auto,
 			jvm_args: Vec::new(),
+			jvm_preset: None,
 			game_args: Vec::new(),
 			min_mem: None,
 			max_mem: None,
@@ -39,9 +59,22 @@ impl LaunchConfiguration {
 			wrappers: Vec::new(),
 			quick_play: QuickPlayType::None,
 			use_log4j_config: false,
+			log4shell_mitigation: true,
+			legacy_fixes: true,
+			demo: false,
+			natives_arch: None,
+			java_agents: Vec::new(),
+			gpu_preference: None,
+			launcher_name: None,
+			launcher_version: None,
 		}
 	}
 
+	/// Gets the architecture to select native libraries for
+	pub fn get_natives_arch(&self) -> NativesArch {
+		self.natives_arch.unwrap_or_else(NativesArch::detect)
+	}
+
 	/// Get a builder for the configuration
 	pub fn builder() -> LaunchConfigBuilder {
 		LaunchConfigBuilder::new()
@@ -84,6 +117,12 @@ impl LaunchConfigBuilder {
 		self
 	}
 
+	/// Set the preset of tuned JVM arguments to use
+	pub fn jvm_preset(mut self, jvm_preset: JvmPreset) -> Self {
+		self.config.jvm_preset = Some(jvm_preset);
+		self
+	}
+
 	/// Set additional game arguments to use
 	pub fn game_args(mut self, game_args: Vec<String>) -> Self {
 		self.config.game_args = game_args;
@@ -125,6 +164,54 @@ impl LaunchConfigBuilder {
 		self.config.use_log4j_config = use_log4j_config;
 		self
 	}
+
+	/// Set whether to automatically protect versions that are affected by Log4Shell
+	pub fn log4shell_mitigation(mut self, log4shell_mitigation: bool) -> Self {
+		self.config.log4shell_mitigation = log4shell_mitigation;
+		self
+	}
+
+	/// Set whether to apply compatibility fixes for old versions
+	pub fn legacy_fixes(mut self, legacy_fixes: bool) -> Self {
+		self.config.legacy_fixes = legacy_fixes;
+		self
+	}
+
+	/// Set whether to launch the game in demo mode
+	pub fn demo(mut self, demo: bool) -> Self {
+		self.config.demo = demo;
+		self
+	}
+
+	/// Set the architecture to select native libraries for
+	pub fn natives_arch(mut self, natives_arch: NativesArch) -> Self {
+		self.config.natives_arch = Some(natives_arch);
+		self
+	}
+
+	/// Set the GPU to prefer for the client
+	pub fn gpu_preference(mut self, gpu_preference: GpuPreference) -> Self {
+		self.config.gpu_preference = Some(gpu_preference);
+		self
+	}
+
+	/// Override the launcher name that is sent to the client
+	pub fn launcher_name(mut self, launcher_name: String) -> Self {
+		self.config.launcher_name = Some(launcher_name);
+		self
+	}
+
+	/// Override the launcher version that is sent to the client
+	pub fn launcher_version(mut self, launcher_version: String) -> Self {
+		self.config.launcher_version = Some(launcher_version);
+		self
+	}
+
+	/// Add a Java agent to attach to the game
+	pub fn java_agent(mut self, agent: JavaAgent) -> Self {
+		self.config.java_agents.push(agent);
+		self
+	}
 }
 
 impl Default for LaunchConfigBuilder {
@@ -145,6 +232,27 @@ pub struct WrapperCommand {
 	pub args: Vec<String>,
 }
 
+/// A Java agent that is attached to the game with the -javaagent JVM argument
+#[derive(Debug, Clone)]
+pub struct JavaAgent {
+	/// The path to the agent JAR file
+	pub path: PathBuf,
+	/// Options to pass to the agent
+	pub options: Option<String>,
+}
+
+impl JavaAgent {
+	/// Creates the JVM argument for this agent
+	pub fn to_arg(&self) -> String {
+		let path = self.path.to_string_lossy();
+		if let Some(options) = &self.options {
+			format!("-javaagent:{path}={options}")
+		} else {
+			format!("-javaagent:{path}")
+		}
+	}
+}
+
 /// Options for the Minecraft QuickPlay feature
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
 pub enum QuickPlayType {
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use nitro_shared::minecraft::GpuPreference;
+
+use crate::io::files::paths::Paths;
+#[cfg(target_os = "windows")]
+use crate::io::{files::create_leading_dirs, json_from_file, json_to_file_replace};
+
+/// Gets environment variables that make the game use the preferred GPU. These are only
+/// used on Linux, where PRIME offloading is controlled by the environment
+pub(crate) fn get_gpu_env_vars(preference: GpuPreference) -> HashMap<String, String> {
+	#[cfg(target_os = "linux")]
+	{
+		let has_nvidia = Path::new("/proc/driver/nvidia/version").exists();
+		get_linux_gpu_env_vars(preference, has_nvidia)
+	}
+	#[cfg(not(target_os = "linux"))]
+	{
+		let _ = preference;
+		HashMap::new()
+	}
+}
+
+/// Gets the environment variables for PRIME offloading on Linux. The proprietary NVIDIA driver
+/// has its own variables, while Mesa drivers use DRI_PRIME
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn get_linux_gpu_env_vars(preference: GpuPreference, has_nvidia: bool) -> HashMap<String, String> {
+	let vars: &[(&str, &str)] = match preference {
+		GpuPreference::Discrete if has_nvidia => &[
+			("__NV_PRIME_RENDER_OFFLOAD", "1"),
+			("__GLX_VENDOR_LIBRARY_NAME", "nvidia"),
+			("__VK_LAYER_NV_optimus", "NVIDIA_only"),
+		],
+		GpuPreference::Discrete => &[("DRI_PRIME", "1")],
+		GpuPreference::Integrated => &[("DRI_PRIME", "0")],
+	};
+
+	vars.iter()
+		.map(|(key, value)| (key.to_string(), value.to_string()))
+		.collect()
+}
+
+/// Registry key where Windows stores the graphics preference for each executable
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const GPU_PREFERENCES_KEY: &str = r"HKCU\Software\Microsoft\DirectX\UserGpuPreferences";
+
+/// Registers the preferred GPU for the Java executable with Windows, which decides which GPU
+/// to use per executable. Since the preference is per executable, it applies to every instance
+/// that uses the same Java installation. When the preference is unset, the value that was there
+/// before Nitrolaunch first changed it is restored. Does nothing on other platforms
+pub(crate) fn register_gpu_preference(
+	jvm_path: &Path,
+	preference: Option<GpuPreference>,
+	paths: &Paths,
+) -> anyhow::Result<()> {
+	#[cfg(target_os = "windows")]
+	{
+		use anyhow::Context;
+
+		let record_path = get_record_path(paths);
+		let mut record: HashMap<String, Option<String>> = if record_path.exists() {
+			json_from_file(&record_path).context("Failed to read GPU preference record")?
+		} else {
+			HashMap::new()
+		};
+		let key = jvm_path.to_string_lossy().to_string();
+
+		match preference {
+			Some(preference) => {
+				// 1 is power saving and 2 is high performance
+				let value = match preference {
+					GpuPreference::Discrete => "GpuPreference=2;",
+					GpuPreference::Integrated => "GpuPreference=1;",
+				};
+
+				// Remember the original value so that it can be restored later
+				if !record.contains_key(&key) {
+					record.insert(key.clone(), query_registry_value(jvm_path)?);
+				}
+				set_registry_value(jvm_path, Some(value))?;
+			}
+			None => {
+				// Only touch values that Nitrolaunch has changed
+				let Some(original) = record.remove(&key) else {
+					return Ok(());
+				};
+				set_registry_value(jvm_path, original.as_deref())?;
+			}
+		}
+
+		create_leading_dirs(&record_path)?;
+		json_to_file_replace(&record_path, &record)
+			.context("Failed to write GPU preference record")?;
+	}
+	#[cfg(not(target_os = "windows"))]
+	{
+		let _ = (jvm_path, preference, paths);
+	}
+
+	Ok(())
+}
+
+/// Gets the path to the record of the registry values that Nitrolaunch has replaced
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn get_record_path(paths: &Paths) -> PathBuf {
+	paths.internal.join("gpu_preferences.json")
+}
+
+/// Gets the current graphics preference value for an executable from the registry
+#[cfg(target_os = "windows")]
+fn query_registry_value(jvm_path: &Path) -> anyhow::Result<Option<String>> {
+	use anyhow::Context;
+	use nitro_shared::no_window;
+
+	let mut command = std::process::Command::new("reg");
+	command.args(["query", GPU_PREFERENCES_KEY, "/v"]);
+	command.arg(jvm_path);
+	command.stderr(std::process::Stdio::null());
+	no_window!(command);
+
+	let output = command.output().context("Failed to run registry command")?;
+	// The command fails when the value doesn't exist
+	if !output.status.success() {
+		return Ok(None);
+	}
+
+	Ok(parse_registry_value(&String::from_utf8_lossy(
+		&output.stdout,
+	)))
+}
+
+/// Sets or deletes the graphics preference value for an executable in the registry
+#[cfg(target_os = "windows")]
+fn set_registry_value(jvm_path: &Path, value: Option<&str>) -> anyhow::Result<()> {
+	use anyhow::{Context, bail};
+	use nitro_shared::no_window;
+
+	let mut command = std::process::Command::new("reg");
+	if value.is_some() {
+		command.arg("add");
+	} else {
+		command.arg("delete");
+	}
+	command.args([GPU_PREFERENCES_KEY, "/v"]);
+	command.arg(jvm_path);
+	if let Some(value) = value {
+		command.args(["/t", "REG_SZ", "/d", value]);
+	}
+	command.arg("/f");
+	command.stdout(std::process::Stdio::null());
+	no_window!(command);
+
+	let status = command.status().context("Failed to run registry command")?;
+	if !status.success() {
+		bail!("Registry command returned non-zero status: {status}");
+	}
+
+	Ok(())
+}
+
+/// Parses the data of a string value from the output of `reg query`
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn parse_registry_value(output: &str) -> Option<String> {
+	output.lines().find_map(|line| {
+		let (_, data) = line.split_once("REG_SZ")?;
+		Some(data.trim().to_string())
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_linux_gpu_env_vars() {
+		let vars = get_linux_gpu_env_vars(GpuPreference::Discrete, false);
+		assert_eq!(vars.get("DRI_PRIME").map(String::as_str), Some("1"));
+		assert!(!vars.contains_key("__GLX_VENDOR_LIBRARY_NAME"));
+
+		let vars = get_linux_gpu_env_vars(GpuPreference::Discrete, true);
+		assert_eq!(
+			vars.get("__NV_PRIME_RENDER_OFFLOAD").map(String::as_str),
+			Some("1")
+		);
+
+		let vars = get_linux_gpu_env_vars(GpuPreference::Integrated, true);
+		assert_eq!(vars.get("DRI_PRIME").map(String::as_str), Some("0"));
+	}
+
+	#[test]
+	fn test_registry_value_parsing() {
+		let output = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\DirectX\\UserGpuPreferences\r\n    C:\\java\\bin\\javaw.exe    REG_SZ    GpuPreference=2;\r\n\r\n";
+		assert_eq!(
+			parse_registry_value(output).as_deref(),
+			Some("GpuPreference=2;")
+		);
+		assert_eq!(parse_registry_value(""), None);
+	}
+}
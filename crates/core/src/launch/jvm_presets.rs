@@ -0,0 +1,100 @@
+use nitro_shared::minecraft::JvmPreset;
+
+use crate::io::java::JavaMajorVersion;
+
+/// The first version of Java where ZGC can be used without enabling experimental options
+const MIN_ZGC_JAVA_VERSION: u16 = 15;
+
+/// Aikar's flags, from https://docs.papermc.io/paper/aikars-flags
+const AIKAR_ARGS: &[&str] = &[
+	"-XX:+UseG1GC",
+	"-XX:+ParallelRefProcEnabled",
+	"-XX:MaxGCPauseMillis=200",
+	"-XX:+UnlockExperimentalVMOptions",
+	"-XX:+DisableExplicitGC",
+	"-XX:+AlwaysPreTouch",
+	"-XX:G1NewSizePercent=30",
+	"-XX:G1MaxNewSizePercent=40",
+	"-XX:G1HeapRegionSize=8M",
+	"-XX:G1ReservePercent=20",
+	"-XX:G1HeapWastePercent=5",
+	"-XX:G1MixedGCCountTarget=4",
+	"-XX:InitiatingHeapOccupancyPercent=15",
+	"-XX:G1MixedGCLiveThresholdPercent=90",
+	"-XX:G1RSetUpdatingPauseTimePercent=5",
+	"-XX:SurvivorRatio=32",
+	"-XX:+PerfDisableSharedMem",
+	"-XX:MaxTenuringThreshold=1",
+	"-Dusing.aikars.flags=https://mcflags.emc.gs",
+	"-Daikars.new.flags=true",
+];
+
+/// The G1GC arguments that the official launcher gives to the client
+const G1GC_CLIENT_ARGS: &[&str] = &[
+	"-XX:+UseG1GC",
+	"-XX:+UnlockExperimentalVMOptions",
+	"-XX:G1NewSizePercent=20",
+	"-XX:G1ReservePercent=20",
+	"-XX:MaxGCPauseMillis=50",
+	"-XX:G1HeapRegionSize=32M",
+];
+
+/// Arguments for using ZGC
+const ZGC_ARGS: &[&str] = &[
+	"-XX:+UseZGC",
+	"-XX:+AlwaysPreTouch",
+	"-XX:+DisableExplicitGC",
+	"-XX:+PerfDisableSharedMem",
+];
+
+/// Gets the JVM arguments for a preset
+pub(crate) fn get_preset_args(preset: JvmPreset) -> Vec<String> {
+	let args = match preset {
+		JvmPreset::Aikar => AIKAR_ARGS,
+		JvmPreset::G1gcClient => G1GC_CLIENT_ARGS,
+		JvmPreset::Zgc => ZGC_ARGS,
+	};
+
+	args.iter().map(|x| x.to_string()).collect()
+}
+
+/// Checks if a preset can be used with a major version of Java
+pub(crate) fn is_preset_supported(preset: JvmPreset, java_version: &JavaMajorVersion) -> bool {
+	match preset {
+		JvmPreset::Zgc => java_version.0 >= MIN_ZGC_JAVA_VERSION,
+		JvmPreset::Aikar | JvmPreset::G1gcClient => true,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::launch::LaunchConfiguration;
+
+	#[test]
+	fn test_preset_args_order() {
+		let config = LaunchConfiguration {
+			jvm_preset: Some(JvmPreset::Zgc),
+			jvm_args: vec!["-XX:-AlwaysPreTouch".into()],
+			..Default::default()
+		};
+
+		let args = config.generate_jvm_args(Some(&JavaMajorVersion(21)));
+		assert_eq!(args.first().map(String::as_str), Some("-XX:+UseZGC"));
+		assert_eq!(args.last().map(String::as_str), Some("-XX:-AlwaysPreTouch"));
+	}
+
+	#[test]
+	fn test_unsupported_preset() {
+		let config = LaunchConfiguration {
+			jvm_preset: Some(JvmPreset::Zgc),
+			..Default::default()
+		};
+
+		let args = config.generate_jvm_args(Some(&JavaMajorVersion(8)));
+		assert!(!args.iter().any(|x| x == "-XX:+UseZGC"));
+		// When the version isn't known, the preset is trusted
+		let args = config.generate_jvm_args(None);
+		assert!(args.iter().any(|x| x == "-XX:+UseZGC"));
+	}
+}
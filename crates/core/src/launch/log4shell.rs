@@ -0,0 +1,167 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use nitro_net::advisories::get_builtin_advisories;
+use nitro_shared::versions::VersionPattern;
+
+use crate::io::files::paths::Paths;
+
+/// ID of the Log4Shell advisory, which decides the versions that are affected
+const LOG4SHELL_ADVISORY_ID: &str = "CVE-2021-44228";
+
+/// JVM argument that disables message lookups in Log4J versions 2.10 and newer
+const NO_LOOKUPS_ARG: &str = "-Dlog4j2.formatMsgNoLookups=true";
+
+/// Logging configuration for Log4J versions that don't support turning off lookups,
+/// which drops any message that contains a lookup instead
+const FILTER_CONFIG: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Configuration status="WARN">
+	<Appenders>
+		<Console name="SysOut" target="SYSTEM_OUT">
+			<PatternLayout pattern="[%d{HH:mm:ss}] [%t/%level]: %msg%n" />
+		</Console>
+		<RollingRandomAccessFile name="File" fileName="logs/latest.log" filePattern="logs/%d{yyyy-MM-dd}-%i.log.gz">
+			<PatternLayout pattern="[%d{HH:mm:ss}] [%t/%level]: %msg%n" />
+			<Policies>
+				<TimeBasedTriggeringPolicy />
+				<OnStartupTriggeringPolicy />
+			</Policies>
+		</RollingRandomAccessFile>
+	</Appenders>
+	<Loggers>
+		<Root level="info">
+			<filters>
+				<RegexFilter regex="(?s).*\$\{[^}]*\}.*" onMatch="DENY" onMismatch="NEUTRAL" />
+			</filters>
+			<AppenderRef ref="SysOut" />
+			<AppenderRef ref="File" />
+		</Root>
+	</Loggers>
+</Configuration>
+"#;
+
+/// Logging configuration that prints messages without evaluating lookups in them
+const NO_LOOKUPS_CONFIG: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Configuration status="WARN">
+	<Appenders>
+		<Console name="SysOut" target="SYSTEM_OUT">
+			<PatternLayout pattern="[%d{HH:mm:ss}] [%t/%level]: %msg{nolookups}%n" />
+		</Console>
+		<RollingRandomAccessFile name="File" fileName="logs/latest.log" filePattern="logs/%d{yyyy-MM-dd}-%i.log.gz">
+			<PatternLayout pattern="[%d{HH:mm:ss}] [%t/%level]: %msg{nolookups}%n" />
+			<Policies>
+				<TimeBasedTriggeringPolicy />
+				<OnStartupTriggeringPolicy />
+			</Policies>
+		</RollingRandomAccessFile>
+	</Appenders>
+	<Loggers>
+		<Root level="info">
+			<AppenderRef ref="SysOut" />
+			<AppenderRef ref="File" />
+		</Root>
+	</Loggers>
+</Configuration>
+"#;
+
+/// How a version of the game has to be fixed to protect it from Log4Shell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Log4ShellFix {
+	/// Use a logging configuration that drops messages with lookups (1.7 - 1.11)
+	Filter,
+	/// Use a logging configuration that turns off lookups (1.12 - 1.16)
+	NoLookupsConfig,
+	/// Turn off lookups with a JVM argument (1.17 and newer)
+	NoLookupsArg,
+}
+
+/// Gets the fix that a version needs, if it is affected by Log4Shell. The affected versions come
+/// from the built-in advisory, so that they are the same as the ones that are warned about
+fn get_fix(version: &str, version_list: &[String]) -> Option<Log4ShellFix> {
+	let advisories = get_builtin_advisories().ok()?;
+	let advisory = advisories.iter().find(|x| x.id == LOG4SHELL_ADVISORY_ID)?;
+	let is_affected = advisory
+		.affected
+		.iter()
+		.any(|x| x.loader.is_none() && x.minecraft_versions.matches_single(version, version_list));
+	if !is_affected {
+		return None;
+	}
+
+	// Older versions of Log4J need different fixes
+	let ranges = [
+		("1.7.2", "1.11.2", Log4ShellFix::Filter),
+		("1.12", "1.16.5", Log4ShellFix::NoLookupsConfig),
+	];
+	let fix = ranges
+		.into_iter()
+		.find_map(|(start, end, fix)| {
+			VersionPattern::Range(start.into(), end.into())
+				.matches_single(version, version_list)
+				.then_some(fix)
+		})
+		.unwrap_or(Log4ShellFix::NoLookupsArg);
+
+	Some(fix)
+}
+
+/// Gets the JVM arguments that protect a version of the game from Log4Shell, writing the
+/// logging configuration that they use if needed. When `has_logging_config` is true,
+/// the game already uses a patched logging configuration, so only JVM flags are added
+pub(crate) fn get_mitigation_args(
+	version: &str,
+	version_list: &[String],
+	has_logging_config: bool,
+	paths: &Paths,
+) -> anyhow::Result<Vec<String>> {
+	let Some(fix) = get_fix(version, version_list) else {
+		return Ok(Vec::new());
+	};
+
+	let (file_name, contents) = match fix {
+		Log4ShellFix::NoLookupsArg => return Ok(vec![NO_LOOKUPS_ARG.into()]),
+		_ if has_logging_config => return Ok(Vec::new()),
+		Log4ShellFix::Filter => ("log4j2_filter.xml", FILTER_CONFIG),
+		Log4ShellFix::NoLookupsConfig => ("log4j2_no_lookups.xml", NO_LOOKUPS_CONFIG),
+	};
+
+	let dir = get_config_dir(paths);
+	std::fs::create_dir_all(&dir).context("Failed to create Log4J config directory")?;
+	let path = dir.join(file_name);
+	std::fs::write(&path, contents).context("Failed to write Log4J config")?;
+
+	Ok(vec![format!(
+		"-Dlog4j.configurationFile={}",
+		path.to_string_lossy()
+	)])
+}
+
+/// Gets the directory where the Log4Shell logging configurations are stored
+fn get_config_dir(paths: &Paths) -> PathBuf {
+	paths.internal.join("log4j")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_log4shell_fix() {
+		let versions: Vec<String> = [
+			"1.6.4", "1.7.2", "1.11.2", "1.12", "1.16.5", "1.17", "1.18", "1.18.1",
+		]
+		.into_iter()
+		.map(String::from)
+		.collect();
+
+		assert_eq!(get_fix("1.6.4", &versions), None);
+		assert_eq!(get_fix("1.7.2", &versions), Some(Log4ShellFix::Filter));
+		assert_eq!(
+			get_fix("1.12", &versions),
+			Some(Log4ShellFix::NoLookupsConfig)
+		);
+		assert_eq!(get_fix("1.18", &versions), Some(Log4ShellFix::NoLookupsArg));
+		// Fixed by Mojang, so it isn't in the advisory
+		assert_eq!(get_fix("1.18.1", &versions), None);
+	}
+}
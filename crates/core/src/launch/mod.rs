@@ -2,6 +2,12 @@
 mod client;
 /// Configuration for launch settings
 mod configuration;
+/// Selection of the GPU to launch with
+mod gpu;
+/// Presets of tuned JVM arguments
+mod jvm_presets;
+/// Protection against the Log4Shell vulnerability in old versions
+mod log4shell;
 /// Actual launching of the game process
 mod process;
 /// Server-specific launch functionality
@@ -13,37 +19,77 @@ use std::ops::DerefMut;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, bail};
+use nitro_shared::error::{AuthError, LaunchError};
 use nitro_shared::java_args::MemoryArg;
 use nitro_shared::output::{MessageContents, NitroOutput};
 use nitro_shared::versions::VersionName;
 use nitro_shared::{Side, translate};
 
 use self::client::create_quick_play_args;
-use self::process::{LaunchGameProcessParameters, launch_game_process};
+use self::process::{LaunchGameProcessParameters, launch_game_process, prepare_game_process};
 use crate::account::AccountManager;
 use crate::account::auth::check_game_ownership;
 use crate::config::BrandingProperties;
 use crate::instance::InstanceKind;
 use crate::io::files::paths::Paths;
+use crate::io::java::JavaMajorVersion;
 use crate::io::java::classpath::Classpath;
 use crate::io::java::install::JavaInstallation;
 use crate::net::game_files::client_meta::ClientMeta;
 use crate::net::game_files::version_manifest::VersionManifestAndList;
 
 pub use self::configuration::{
-	LaunchConfigBuilder, LaunchConfiguration, QuickPlayType, WrapperCommand,
+	JavaAgent, LaunchConfigBuilder, LaunchConfiguration, QuickPlayType, WrapperCommand,
 };
 
+pub use self::process::PreparedLaunch;
 pub use self::process::get_stdio_file_path;
 pub use self::process::launch_process;
 pub use self::process::{LaunchProcessParameters, LaunchProcessProperties};
 
+/// Part of the message that servers print once they have finished starting
+pub const SERVER_READY_MESSAGE: &str = "]: Done (";
+
 pub(crate) async fn launch(
-	params: LaunchParameters<'_>,
+	mut params: LaunchParameters<'_>,
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<InstanceHandle> {
 	let command = params.java.get_jvm_path();
+	let props = get_launch_props(&mut params, o).await?;
+
+	if let InstanceKind::Client { .. } = &params.side
+		&& let Err(e) = self::gpu::register_gpu_preference(
+			&command,
+			params.launch_config.gpu_preference,
+			params.paths,
+		) {
+		o.display(MessageContents::Warning(format!(
+			"Failed to set the preferred GPU: {e:?}"
+		)));
+	}
+
+	let proc_params = get_process_params(&params, &command, props);
+
+	launch_game_process(proc_params, o).context(LaunchError::ProcessFailed)
+}
+
+/// Resolves the launch command without spawning the game process
+pub(crate) async fn prepare(
+	mut params: LaunchParameters<'_>,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<PreparedLaunch> {
+	let command = params.java.get_jvm_path();
+	let props = get_launch_props(&mut params, o).await?;
+	let proc_params = get_process_params(&params, &command, props);
+
+	Ok(prepare_game_process(proc_params, o))
+}
 
+/// Authenticates and gets the side-specific properties for launching
+async fn get_launch_props(
+	params: &mut LaunchParameters<'_>,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<LaunchProcessProperties> {
 	// Make sure we are authenticated
 	if let InstanceKind::Client { .. } = &params.side {
 		let mut process = o.get_process();
@@ -60,35 +106,70 @@ pub(crate) async fn launch(
 				process.deref_mut(),
 			)
 			.await
-			.context("Failed to ensure authentication")?;
+			.map_err(|e| e.context("Failed to ensure authentication"))?;
 
-		// Ensure game ownership in case we are using an alternative auth system
-		let owns_game =
-			check_game_ownership(params.paths).context("Failed to check for game ownership")?;
+		// Ensure game ownership in case we are using an alternative auth system.
+		// The demo is free to play, so it doesn't need ownership
+		if !self::client::use_demo(params) {
+			let owns_game =
+				check_game_ownership(params.paths).context("Failed to check for game ownership")?;
 
-		if !owns_game {
-			bail!(
-				"Could not prove game ownership. If using an alternative auth system, like from a plugin, you must login with a Microsoft account that owns Minecraft first."
-			);
+			if !owns_game {
+				bail!(AuthError::OwnershipNotProven);
+			}
 		}
 
 		let message = translate!(process, FinishAuthenticating);
 		process.display(MessageContents::Success(message));
 	}
 
+	if let Some(preset) = params.launch_config.jvm_preset
+		&& !self::jvm_presets::is_preset_supported(preset, params.java.get_major_version())
+	{
+		o.display(MessageContents::Warning(format!(
+			"JVM preset {preset:?} is not supported by Java {}, so it won't be used",
+			params.java.get_major_version()
+		)));
+	}
+
 	// Get side-specific launch properties
-	let props = match params.side.get_side() {
-		Side::Client => self::client::get_launch_props(&params).await,
-		Side::Server => self::server::get_launch_props(&params),
+	let side = params.side.get_side();
+	let mut props = match side {
+		Side::Client => self::client::get_launch_props(params, o).await,
+		Side::Server => self::server::get_launch_props(params),
 	}
 	.context("Failed to generate side-specific launch properties")?;
 
+	if params.launch_config.log4shell_mitigation {
+		// The logging config from Mojang is already patched
+		let has_logging_config = side == Side::Client
+			&& params.launch_config.use_log4j_config
+			&& params.client_meta.logging.is_some();
+		let args = self::log4shell::get_mitigation_args(
+			params.version,
+			&params.version_manifest.list,
+			has_logging_config,
+			params.paths,
+		)
+		.context("Failed to set up Log4Shell mitigation")?;
+		props.jvm_args.splice(0..0, args);
+	}
+
+	Ok(props)
+}
+
+/// Creates the parameters for the game process
+fn get_process_params<'a>(
+	params: &'a LaunchParameters<'a>,
+	command: &'a Path,
+	props: LaunchProcessProperties,
+) -> LaunchGameProcessParameters<'a> {
 	let account_access_token = params
 		.accounts
 		.get_chosen_account()
 		.and_then(|x| x.get_access_token());
 
-	let proc_params = LaunchGameProcessParameters {
+	LaunchGameProcessParameters {
 		command: command.as_os_str(),
 		cwd: params.launch_dir,
 		main_class: Some(params.main_class),
@@ -96,15 +177,14 @@ pub(crate) async fn launch(
 		paths: params.paths,
 		props,
 		launch_config: params.launch_config,
+		java_version: Some(params.java.get_major_version()),
 		version: params.version,
 		version_list: &params.version_manifest.list,
 		side: params.side,
 		account_access_token,
 		censor_secrets: params.censor_secrets,
 		pipe_stdin: params.pipe_stdin,
-	};
-
-	launch_game_process(proc_params, o).context("Failed to launch game process")
+	}
 }
 
 /// Container struct for parameters for launching an instance
@@ -129,9 +209,16 @@ pub(crate) struct LaunchParameters<'a> {
 }
 
 impl LaunchConfiguration {
-	/// Create the args for the JVM when launching the game
-	pub fn generate_jvm_args(&self) -> Vec<String> {
-		let mut out = self.jvm_args.clone();
+	/// Create the args for the JVM when launching the game. Presets that the given
+	/// major version of Java doesn't support are left out
+	pub fn generate_jvm_args(&self, java_version: Option<&JavaMajorVersion>) -> Vec<String> {
+		// Preset arguments come first so that custom ones can override them
+		let mut out = self
+			.jvm_preset
+			.filter(|x| java_version.is_none_or(|v| self::jvm_presets::is_preset_supported(*x, v)))
+			.map(self::jvm_presets::get_preset_args)
+			.unwrap_or_default();
+		out.extend(self.jvm_args.iter().cloned());
 
 		if let Some(n) = &self.min_mem {
 			out.push(MemoryArg::Min.to_string(n));
@@ -139,6 +226,7 @@ impl LaunchConfiguration {
 		if let Some(n) = &self.max_mem {
 			out.push(MemoryArg::Max.to_string(n));
 		}
+		out.extend(self.java_agents.iter().map(|x| x.to_arg()));
 
 		out
 	}
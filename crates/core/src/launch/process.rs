@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::OsStr;
 use std::fs::File;
 use std::path::{Path, PathBuf};
@@ -10,11 +10,14 @@ use nitro_auth::mc::AccessToken;
 use nitro_shared::output::{MessageContents, NitroOutput};
 use nitro_shared::versions::VersionName;
 use nitro_shared::{no_window, translate};
+use serde::{Deserialize, Serialize};
 
 use crate::instance::InstanceKind;
 use crate::io::create_named_pipe;
 use crate::io::files::open_file_append;
+use crate::io::java::JavaMajorVersion;
 use crate::io::java::classpath::Classpath;
+use crate::io::logs;
 use crate::{InstanceHandle, Paths, WrapperCommand};
 
 use super::LaunchConfiguration;
@@ -25,16 +28,7 @@ pub(crate) fn launch_game_process(
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<InstanceHandle> {
 	// Modify the parameters based on game-specific properties
-
-	// Prepend generated game args to the beginning
-	let previous_game_args = params.props.game_args.clone();
-	params.props.game_args = params.launch_config.generate_game_args(
-		params.version,
-		params.version_list,
-		params.side.get_side(),
-		o,
-	);
-	params.props.game_args.extend(previous_game_args);
+	add_generated_game_args(&mut params, o);
 
 	// Create the parameters for the process
 	let proc_params = LaunchProcessParameters {
@@ -43,6 +37,7 @@ pub(crate) fn launch_game_process(
 		main_class: params.main_class,
 		props: params.props,
 		launch_config: params.launch_config,
+		java_version: params.java_version,
 	};
 
 	o.display(MessageContents::Success(translate!(o, Launch)));
@@ -81,6 +76,61 @@ pub(crate) fn launch_game_process(
 	))
 }
 
+/// Resolve the command for the game process without spawning it
+pub(crate) fn prepare_game_process(
+	mut params: LaunchGameProcessParameters<'_>,
+	o: &mut impl NitroOutput,
+) -> PreparedLaunch {
+	add_generated_game_args(&mut params, o);
+
+	let main_class = params.main_class.map(|x| x.to_string());
+	let proc_params = LaunchProcessParameters {
+		command: params.command,
+		cwd: params.cwd,
+		main_class: params.main_class,
+		props: params.props,
+		launch_config: params.launch_config,
+		java_version: params.java_version,
+	};
+	let cmd = create_process_command(proc_params);
+
+	PreparedLaunch {
+		program: cmd.get_program().to_string_lossy().to_string(),
+		args: cmd
+			.get_args()
+			.map(|x| x.to_string_lossy().to_string())
+			.collect(),
+		env: cmd
+			.get_envs()
+			.filter_map(|(key, val)| {
+				Some((
+					key.to_string_lossy().to_string(),
+					val?.to_string_lossy().to_string(),
+				))
+			})
+			.collect(),
+		cwd: params.cwd.to_owned(),
+		classpath: params.classpath.get_paths(),
+		main_class,
+		secrets: params
+			.account_access_token
+			.map(|x| vec![x.0.clone()])
+			.unwrap_or_default(),
+	}
+}
+
+/// Prepends the generated game args to the beginning of the game args
+fn add_generated_game_args(params: &mut LaunchGameProcessParameters<'_>, o: &mut impl NitroOutput) {
+	let previous_game_args = std::mem::take(&mut params.props.game_args);
+	params.props.game_args = params.launch_config.generate_game_args(
+		params.version,
+		params.version_list,
+		params.side.get_side(),
+		o,
+	);
+	params.props.game_args.extend(previous_game_args);
+}
+
 /// Launch a generic process with the core's config system
 pub fn launch_process(
 	params: LaunchProcessParameters<'_>,
@@ -99,6 +149,25 @@ pub fn get_process_launch_command(
 	stdout_path: &Path,
 	stdin_path: Option<&Path>,
 ) -> anyhow::Result<Command> {
+	let mut cmd = create_process_command(params);
+
+	// Capture stdio
+	let stdout = File::create_new(stdout_path).context("Failed to open stdout")?;
+	cmd.stdout(std::process::Stdio::from(stdout));
+	if let Some(stdin_path) = stdin_path {
+		let stdin = create_named_pipe(stdin_path)?;
+		cmd.stdin(stdin);
+	} else {
+		cmd.stdin(std::process::Stdio::inherit());
+	}
+
+	no_window!(cmd);
+
+	Ok(cmd)
+}
+
+/// Creates the command for a generic process, without setting up its stdio
+fn create_process_command(params: LaunchProcessParameters<'_>) -> Command {
 	// Create the base command based on wrapper settings
 	let mut cmd = create_wrapped_command(params.command, &params.launch_config.wrappers);
 
@@ -108,26 +177,86 @@ pub fn get_process_launch_command(
 	cmd.envs(params.props.additional_env_vars);
 
 	// Add the arguments
-	cmd.args(params.launch_config.generate_jvm_args());
+	cmd.args(params.launch_config.generate_jvm_args(params.java_version));
 	cmd.args(params.props.jvm_args);
 	if let Some(main_class) = params.main_class {
 		cmd.arg(main_class);
 	}
 	cmd.args(params.props.game_args);
 
-	// Capture stdio
-	let stdout = File::create_new(stdout_path).context("Failed to open stdout")?;
-	cmd.stdout(std::process::Stdio::from(stdout));
-	if let Some(stdin_path) = stdin_path {
-		let stdin = create_named_pipe(stdin_path)?;
-		cmd.stdin(stdin);
-	} else {
-		cmd.stdin(std::process::Stdio::inherit());
+	cmd
+}
+
+/// The fully resolved command for launching an instance, which can be inspected or run externally
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PreparedLaunch {
+	/// The program to run, usually the path to the JVM or the outermost wrapper command
+	pub program: String,
+	/// The arguments for the program
+	pub args: Vec<String>,
+	/// Environment variables to set in addition to the ones inherited from the launcher
+	pub env: BTreeMap<String, String>,
+	/// The working directory to run the program in
+	pub cwd: PathBuf,
+	/// The Java classpath
+	pub classpath: Vec<PathBuf>,
+	/// The Java main class
+	pub main_class: Option<String>,
+	/// Secrets such as access tokens that appear in the command
+	#[serde(skip)]
+	secrets: Vec<String>,
+}
+
+impl PreparedLaunch {
+	/// Replaces any secrets such as access tokens in the arguments and environment
+	pub fn censor_secrets(&mut self) {
+		let secrets = std::mem::take(&mut self.secrets);
+		for arg in self.args.iter_mut().chain(self.env.values_mut()) {
+			*arg = logs::censor_secrets(arg, &secrets);
+		}
 	}
 
-	no_window!(cmd);
+	/// Creates a command that runs this launch. Stdio is inherited from the current process
+	pub fn to_command(&self) -> Command {
+		let mut cmd = Command::new(&self.program);
+		cmd.args(&self.args);
+		cmd.envs(&self.env);
+		cmd.current_dir(&self.cwd);
+		cmd
+	}
 
-	Ok(cmd)
+	/// Formats the command line so that it can be pasted into a shell
+	pub fn to_command_line(&self) -> String {
+		let mut out = quote_arg(&self.program);
+		for arg in &self.args {
+			out.push(' ');
+			out.push_str(&quote_arg(arg));
+		}
+		out
+	}
+
+	/// Formats the working directory, environment, and command line as a shell script
+	pub fn to_script(&self) -> String {
+		let mut out = format!("cd {}\n", quote_arg(&self.cwd.to_string_lossy()));
+		for (key, value) in &self.env {
+			out.push_str(&format!("export {key}={}\n", quote_arg(value)));
+		}
+		out.push_str(&self.to_command_line());
+		out
+	}
+}
+
+/// Quotes a command line argument if it contains characters that a shell would interpret
+fn quote_arg(arg: &str) -> String {
+	let is_plain = !arg.is_empty()
+		&& arg
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || "-_./:=+,@%".contains(c));
+	if is_plain {
+		arg.to_string()
+	} else {
+		format!("'{}'", arg.replace('\'', "'\\''"))
+	}
 }
 
 /// Display the launch command in our own way,
@@ -139,7 +268,10 @@ fn output_launch_command(
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<()> {
 	o.end_process();
-	let access_token = if censor_secrets { access_token } else { None };
+	let secrets: Vec<_> = access_token
+		.filter(|_| censor_secrets)
+		.map(|x| vec![x.0.clone()])
+		.unwrap_or_default();
 	o.debug(MessageContents::Property(
 		"Launch command".into(),
 		Box::new(MessageContents::Simple(
@@ -149,12 +281,8 @@ fn output_launch_command(
 
 	o.debug(MessageContents::Header("Launch command arguments".into()));
 
-	const CENSOR_STR: &str = "***";
 	for arg in command.get_args() {
-		let mut arg = arg.to_string_lossy().to_string();
-		if let Some(access_token) = &access_token {
-			arg = arg.replace(&access_token.0, CENSOR_STR);
-		}
+		let arg = logs::censor_secrets(&arg.to_string_lossy(), &secrets);
 		o.debug(MessageContents::ListItem(Box::new(
 			MessageContents::Simple(arg),
 		)));
@@ -227,6 +355,7 @@ pub(crate) struct LaunchGameProcessParameters<'a> {
 	pub paths: &'a Paths,
 	pub props: LaunchProcessProperties,
 	pub launch_config: &'a LaunchConfiguration,
+	pub java_version: Option<&'a JavaMajorVersion>,
 	pub version: &'a VersionName,
 	pub version_list: &'a [String],
 	pub side: &'a InstanceKind,
@@ -247,6 +376,8 @@ pub struct LaunchProcessParameters<'a> {
 	pub props: LaunchProcessProperties,
 	/// The launch configuration
 	pub launch_config: &'a LaunchConfiguration,
+	/// The major version of the Java that is launched, if it is known
+	pub java_version: Option<&'a JavaMajorVersion>,
 }
 
 /// Properties for launching the game process that are created by
@@ -265,6 +396,34 @@ pub struct LaunchProcessProperties {
 mod tests {
 	use super::*;
 
+	use crate::io::logs::CENSOR_STR;
+
+	#[test]
+	fn test_prepared_launch() {
+		let mut launch = PreparedLaunch {
+			program: "java".into(),
+			args: vec![
+				"-cp".into(),
+				"a.jar:b.jar".into(),
+				"--accessToken".into(),
+				"secret".into(),
+				"--title".into(),
+				"My World's End".into(),
+			],
+			env: BTreeMap::new(),
+			cwd: PathBuf::from("."),
+			classpath: Vec::new(),
+			main_class: None,
+			secrets: vec!["secret".into()],
+		};
+		launch.censor_secrets();
+		assert_eq!(launch.args[3], CENSOR_STR);
+		assert_eq!(
+			launch.to_command_line(),
+			"java -cp a.jar:b.jar --accessToken '***' --title 'My World'\\''s End'"
+		);
+	}
+
 	#[test]
 	fn test_wrappers() {
 		let wrappers = vec![
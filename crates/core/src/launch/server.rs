@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use anyhow::bail;
+use nitro_shared::error::LaunchError;
 
 use crate::InstanceKind;
 
@@ -11,7 +12,7 @@ pub(crate) fn get_launch_props(
 	params: &LaunchParameters,
 ) -> anyhow::Result<LaunchProcessProperties> {
 	let InstanceKind::Server { show_gui, .. } = &params.side else {
-		bail!("Instance is not a server");
+		bail!(LaunchError::NotAServer);
 	};
 	let mut jvm_args = Vec::new();
 	let mut game_args = Vec::new();
@@ -25,6 +25,9 @@ pub mod io;
 pub mod launch;
 /// Networking interfaces
 pub mod net;
+/// Harness for end-to-end tests that launch real instances
+#[cfg(feature = "testing")]
+pub mod testing;
 /// Common utilities
 pub mod util;
 /// Installable versions of the game
@@ -33,7 +36,9 @@ pub mod version;
 use anyhow::Context;
 use io::java::JavaMajorVersion;
 use io::java::install::{JavaInstallParameters, JavaInstallation, JavaInstallationKind};
-use io::{persistent::PersistentData, update::UpdateManager};
+use io::persistent::PersistentData;
+use io::system::{Clock, FileSystem, RealFileSystem, SystemClock};
+use io::update::{FileVerifier, UpdateManager};
 use net::game_files::version_manifest::{VersionManifestAndList, make_version_list};
 use nitro_shared::UpdateDepth;
 use nitro_shared::minecraft::VersionEntry;
@@ -62,6 +67,9 @@ pub struct NitroCore {
 	versions: VersionRegistry,
 	java_installations: JavaInstallationRegistry,
 	custom_java_fn: Option<Arc<dyn CustomJavaFunction>>,
+	file_verifier: Option<FileVerifier>,
+	clock: Arc<dyn Clock>,
+	filesystem: Arc<dyn FileSystem>,
 }
 
 impl NitroCore {
@@ -74,9 +82,13 @@ impl NitroCore {
 	pub fn with_config_and_paths(config: Configuration, paths: Paths) -> anyhow::Result<Self> {
 		let persistent =
 			PersistentData::open(&paths).context("Failed to open persistent data file")?;
+		let req_client = net::download::create_client(config.proxy.as_ref())
+			.context("Failed to create HTTP client")?;
+		// Game files are downloaded by free functions, so the mirrors are global
+		net::game_files::mirrors::set_mirrors(config.download_mirrors.clone());
 		let out = Self {
 			paths: Arc::new(paths),
-			req_client: reqwest::Client::new(),
+			req_client,
 			persistent: Arc::new(Mutex::new(persistent)),
 			versions: VersionRegistry::new(),
 			config,
@@ -84,6 +96,9 @@ impl NitroCore {
 				installations: Arc::new(Mutex::new(HashMap::new())),
 			},
 			custom_java_fn: None,
+			file_verifier: None,
+			clock: Arc::new(SystemClock),
+			filesystem: Arc::new(RealFileSystem),
 		};
 		Ok(out)
 	}
@@ -110,17 +125,18 @@ impl NitroCore {
 		&self.paths
 	}
 
-	/// Get the version manifest
+	/// Get the version manifest. It is only loaded again if it is requested at a higher depth
+	/// than it was previously loaded at
 	pub async fn get_version_manifest(
 		&self,
 		requested_version: Option<&MinecraftVersion>,
 		depth: UpdateDepth,
 		o: &mut impl NitroOutput,
-	) -> anyhow::Result<&Arc<VersionManifestAndList>> {
+	) -> anyhow::Result<Arc<VersionManifestAndList>> {
 		let params = LoadVersionManifestParameters {
 			requested_version,
 			paths: &self.paths,
-			update_manager: &UpdateManager::new(depth),
+			update_manager: &self.create_update_manager(depth),
 			req_client: &self.req_client,
 		};
 		self.versions.load_version_manifest(params, o).await
@@ -137,7 +153,8 @@ impl NitroCore {
 			.context("Version does not exist")
 	}
 
-	/// Load or install a version of the game
+	/// Load or install a version of the game. Versions that were already loaded by this core
+	/// are only updated again if they are requested at a higher depth than before
 	pub async fn get_version(
 		&self,
 		version: &MinecraftVersion,
@@ -152,7 +169,8 @@ impl NitroCore {
 			.get_version(&version_manifest.manifest)
 			.context("Latest release or snapshot is not present in manifest")?;
 
-		let manager = UpdateManager::new(depth);
+		let mut manager = self.create_update_manager(depth);
+		manager.set_verifier(self.file_verifier.clone());
 
 		let params = LoadVersionParameters {
 			paths: &self.paths,
@@ -218,7 +236,7 @@ impl NitroCore {
 
 		let java_params = JavaInstallParameters {
 			paths: &self.paths,
-			update_manager: &UpdateManager::new(depth),
+			update_manager: &self.create_update_manager(depth),
 			persistent: self.persistent.clone(),
 			req_client: &self.req_client,
 			custom_install_func: self.custom_java_fn.clone(),
@@ -246,4 +264,37 @@ impl NitroCore {
 	pub fn set_custom_java_install_fn(&mut self, func: Arc<dyn CustomJavaFunction>) {
 		self.custom_java_fn = Some(func);
 	}
+
+	/// Set a verifier to check the hashes of existing game files against the version metadata
+	/// when versions and instances are loaded, redownloading any that are corrupted
+	pub fn set_file_verifier(&mut self, verifier: FileVerifier) {
+		self.file_verifier = Some(verifier);
+	}
+
+	/// Get the file verifier of the core, if one is set
+	pub fn get_file_verifier(&self) -> Option<&FileVerifier> {
+		self.file_verifier.as_ref()
+	}
+
+	/// Set the clock used to check whether cached files are out of date
+	pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+		self.clock = clock;
+	}
+
+	/// Set the filesystem used to check whether files need to be updated
+	pub fn set_filesystem(&mut self, filesystem: Arc<dyn FileSystem>) {
+		self.filesystem = filesystem;
+	}
+
+	/// Creates an UpdateManager that uses the clock, filesystem, and download settings of the core
+	fn create_update_manager(&self, depth: UpdateDepth) -> UpdateManager {
+		let mut manager = UpdateManager::new(depth);
+		manager.set_clock(self.clock.clone());
+		manager.set_filesystem(self.filesystem.clone());
+		if let Some(limit) = self.config.download_concurrency {
+			manager.set_transfer_limit(limit);
+		}
+
+		manager
+	}
 }
@@ -0,0 +1,167 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+use nitro_shared::output::{MessageContents, NitroOutput};
+use nitro_shared::util::utc_timestamp;
+use reqwest::Client;
+use ring::digest::{SHA256, digest};
+use serde::{Deserialize, Serialize};
+
+use crate::io::files::create_leading_dirs;
+use crate::io::files::paths::Paths;
+use crate::io::{json_from_file, json_to_file_replace};
+use crate::net::download;
+
+/// Metadata for the latest build of authlib-injector
+const LATEST_URL: &str = "https://authlib-injector.yushi.moe/artifact/latest.json";
+
+/// How long to wait before checking for a new version of authlib-injector again, in seconds
+const CHECK_INTERVAL: u64 = 60 * 60 * 24;
+
+/// Gets the authlib-injector agent JAR, downloading it if it isn't there yet or if there is a newer
+/// version. The latest version is only checked once in a while. When offline, or when the latest
+/// version can't be checked, the existing JAR is used as long as it hasn't been modified
+pub async fn get_agent(
+	paths: &Paths,
+	client: &Client,
+	offline: bool,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<PathBuf> {
+	let path = get_agent_path(paths);
+	let info_path = get_agent_info_path(paths);
+	let existing: Option<AgentInfo> = if path.exists() {
+		json_from_file(&info_path).ok()
+	} else {
+		None
+	};
+	let existing_valid = existing.as_ref().is_some_and(|x| is_jar_valid(&path, x));
+
+	if offline {
+		if !path.exists() {
+			bail!(
+				"authlib-injector has not been downloaded yet and can't be downloaded while offline"
+			);
+		}
+		if !existing_valid {
+			bail!(
+				"The downloaded authlib-injector has been modified and can't be replaced while offline"
+			);
+		}
+		return Ok(path);
+	}
+
+	let now = utc_timestamp().unwrap_or_default();
+	if existing_valid
+		&& existing
+			.as_ref()
+			.is_some_and(|x| now.saturating_sub(x.last_checked) < CHECK_INTERVAL)
+	{
+		return Ok(path);
+	}
+
+	let latest: LatestBuild = match download::json(LATEST_URL, client).await {
+		Ok(latest) => latest,
+		Err(e) if existing_valid => {
+			o.debug(MessageContents::Warning(format!(
+				"Failed to check for a new version of authlib-injector: {e:#}"
+			)));
+			return Ok(path);
+		}
+		Err(e) => return Err(e.context("Failed to get latest authlib-injector build")),
+	};
+
+	// Make sure that the existing JAR is the latest version and hasn't been modified
+	if let Some(mut existing) = existing
+		&& existing_valid
+		&& existing.version == latest.version
+		&& existing
+			.sha256
+			.eq_ignore_ascii_case(&latest.checksums.sha256)
+	{
+		existing.last_checked = now;
+		json_to_file_replace(&info_path, &existing)
+			.context("Failed to write authlib-injector info")?;
+		return Ok(path);
+	}
+
+	o.display(MessageContents::StartProcess(
+		"Downloading authlib-injector".into(),
+	));
+
+	let bytes = download::bytes(&latest.download_url, client)
+		.await
+		.context("Failed to download authlib-injector")?;
+	let hash = hash_sha256(&bytes);
+	if !hash.eq_ignore_ascii_case(&latest.checksums.sha256) {
+		bail!(
+			"Checksum of the downloaded authlib-injector did not match. Expected {}, got {hash}",
+			latest.checksums.sha256
+		);
+	}
+
+	create_leading_dirs(&path)?;
+	std::fs::write(&path, bytes).context("Failed to write authlib-injector")?;
+	let info = AgentInfo {
+		version: latest.version.clone(),
+		sha256: hash,
+		last_checked: now,
+	};
+	json_to_file_replace(&info_path, &info).context("Failed to write authlib-injector info")?;
+
+	o.display(MessageContents::Success(format!(
+		"Downloaded authlib-injector {}",
+		latest.version
+	)));
+
+	Ok(path)
+}
+
+/// Gets the path to the authlib-injector agent JAR
+pub fn get_agent_path(paths: &Paths) -> PathBuf {
+	paths.internal.join("authlib-injector.jar")
+}
+
+/// Gets the path to the info about the downloaded authlib-injector JAR
+fn get_agent_info_path(paths: &Paths) -> PathBuf {
+	paths.internal.join("authlib-injector.json")
+}
+
+/// Checks that the downloaded JAR still has the hash that it was downloaded with
+fn is_jar_valid(path: &Path, info: &AgentInfo) -> bool {
+	std::fs::read(path).is_ok_and(|x| hash_sha256(&x).eq_ignore_ascii_case(&info.sha256))
+}
+
+/// Computes the SHA-256 hash of bytes as a lowercase hex string
+fn hash_sha256(bytes: &[u8]) -> String {
+	digest(&SHA256, bytes)
+		.as_ref()
+		.iter()
+		.map(|x| format!("{x:02x}"))
+		.collect()
+}
+
+/// Metadata for an authlib-injector build
+#[derive(Deserialize)]
+struct LatestBuild {
+	version: String,
+	download_url: String,
+	checksums: Checksums,
+}
+
+/// Checksums for an authlib-injector build
+#[derive(Deserialize)]
+struct Checksums {
+	sha256: String,
+}
+
+/// Info about the authlib-injector JAR that has been downloaded
+#[derive(Serialize, Deserialize)]
+struct AgentInfo {
+	/// The version of the JAR
+	version: String,
+	/// The SHA-256 hash of the JAR
+	sha256: String,
+	/// When the latest version was last checked, as a UTC timestamp in seconds
+	#[serde(default)]
+	last_checked: u64,
+}
@@ -4,25 +4,42 @@ use std::sync::Arc;
 
 use anyhow::Context;
 use nitro_shared::output::{MessageContents, NitroOutput};
+use nitro_shared::util::DefaultExt;
 use nitro_shared::versions::VersionName;
-use nitro_shared::versions::VersionPattern;
 use nitro_shared::{UpdateDepth, translate, try_3};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use tokio::{sync::Semaphore, task::JoinSet};
 
 use crate::io::files::{self, paths::Paths};
-use crate::io::update::{UpdateManager, UpdateMethodResult};
+use crate::io::update::{UpdateManager, UpdateMethodResult, hash_sha1};
 use crate::io::{json_from_file, json_to_file};
-use crate::net::{download, get_transfer_limit};
+use crate::net::{download, stats};
 
 use super::client_meta::ClientMeta;
+use super::mirrors;
 
 /// Structure for the assets index
 #[derive(Deserialize, Serialize)]
 pub struct AssetIndex {
 	/// The map of asset resource locations to index entries
 	pub objects: HashMap<String, IndexEntry>,
+	/// Whether the game reads assets by their names from a virtual directory, for older versions
+	#[serde(default, rename = "virtual")]
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub is_virtual: bool,
+	/// Whether the game reads assets from the resources folder in the game directory,
+	/// for versions before 1.6
+	#[serde(default)]
+	#[serde(skip_serializing_if = "DefaultExt::is_default")]
+	pub map_to_resources: bool,
+}
+
+impl AssetIndex {
+	/// Checks if the assets need to be stored by their names instead of their hashes
+	pub fn is_legacy(&self) -> bool {
+		self.is_virtual || self.map_to_resources
+	}
 }
 
 /// A single asset in the index
@@ -47,7 +64,6 @@ pub async fn get(
 	client_meta: &ClientMeta,
 	paths: &Paths,
 	version: &VersionName,
-	version_list: &[String],
 	manager: &UpdateManager,
 	client: &Client,
 	o: &mut impl NitroOutput,
@@ -63,36 +79,43 @@ pub async fn get(
 
 	let index_path = indexes_dir.join(version_string + ".json");
 	let index_url = &asset_info.url;
+	let index_sha1 = asset_info.sha1.as_deref();
+
+	let index =
+		match download_index(index_url, index_sha1, &index_path, manager, client, false).await {
+			Ok(val) => val,
+			Err(err) => {
+				o.display(MessageContents::Error(translate!(o, AssetIndexFailed)));
+				o.display(MessageContents::Error(format!("{}", err)));
+				o.display(MessageContents::StartProcess(translate!(o, Redownloading)));
+				download_index(index_url, index_sha1, &index_path, manager, client, true)
+					.await
+					.context("Failed to obtain asset index")?
+			}
+		};
 
-	let (objects_dir, virtual_dir) = create_dirs(paths, version, version_list)
+	let (objects_dir, virtual_dir) = create_dirs(paths, client_meta, &index)
 		.await
 		.context("Failed to create directories for assets")?;
 
-	let index = match download_index(index_url, &index_path, manager, client, false).await {
-		Ok(val) => val,
-		Err(err) => {
-			o.display(MessageContents::Error(translate!(o, AssetIndexFailed)));
-			o.display(MessageContents::Error(format!("{}", err)));
-			o.display(MessageContents::StartProcess(translate!(o, Redownloading)));
-			download_index(index_url, &index_path, manager, client, true)
-				.await
-				.context("Failed to obtain asset index")?
-		}
-	};
-
 	let mut assets_to_download = Vec::new();
 	for (name, asset) in index.objects {
 		let hash_path = asset.get_hash_path();
 		let url = format!("https://resources.download.minecraft.net/{hash_path}");
 
 		let path = objects_dir.join(&hash_path);
-		let virtual_path = virtual_dir.as_ref().map(|x| x.join(&hash_path));
-		if !manager.should_update_file(&path) {
+		let virtual_path = virtual_dir.as_ref().map(|x| x.join(&name));
+		// JSON assets are minified when they are stored, so they won't match their hash
+		let sha1 = (!name.ends_with(".json")).then_some(asset.hash.as_str());
+		if paths.link_from_shared(&path, sha1) || !manager.should_update_file_with_hash(&path, sha1)
+		{
 			if let Some(virtual_path) = &virtual_path {
 				if !manager.should_update_file(virtual_path) {
+					stats::record_cache_hit(&url);
 					continue;
 				}
 			} else {
+				stats::record_cache_hit(&url);
 				continue;
 			}
 		}
@@ -105,12 +128,14 @@ pub async fn get(
 		let data = AssetData {
 			name,
 			url,
+			sha1: asset.hash.clone(),
 			path,
 			virtual_path,
 			size: asset.size,
 		};
 		assets_to_download.push(data);
 	}
+	paths.save_shared_verification()?;
 	// Sort downloads by biggest first
 	assets_to_download.sort_by_key(|x| std::cmp::Reverse(x.size));
 
@@ -127,7 +152,7 @@ pub async fn get(
 
 	let mut join = JoinSet::new();
 	// Used to limit the number of open file descriptors
-	let sem = Arc::new(Semaphore::new(get_transfer_limit()));
+	let sem = Arc::new(Semaphore::new(manager.get_transfer_limit()));
 	for asset in assets_to_download {
 		let client = client.clone();
 		let sem = sem.clone();
@@ -204,9 +229,14 @@ pub async fn get(
 
 /// Downloads and loads a single asset
 async fn download_asset(asset: &AssetData, client: &Client) -> anyhow::Result<()> {
-	let response = download::bytes(&asset.url, client)
-		.await
-		.context("Failed to download asset")?;
+	let response = mirrors::with_fallback_verified(
+		&asset.url,
+		Some(&asset.sha1),
+		|url| download::bytes(url, client),
+		|x| Ok(hash_sha1(x)),
+	)
+	.await
+	.context("Failed to download asset")?;
 
 	// Write JSON as minified to save storage space, if there are no errors
 	let result = if asset.name.ends_with(".json") {
@@ -237,6 +267,7 @@ async fn download_asset(asset: &AssetData, client: &Client) -> anyhow::Result<()
 struct AssetData {
 	name: String,
 	url: String,
+	sha1: String,
 	path: PathBuf,
 	virtual_path: Option<PathBuf>,
 	size: usize,
@@ -245,17 +276,26 @@ struct AssetData {
 /// Downloads the asset index which contains all of the assets that need to be downloaded
 async fn download_index(
 	url: &str,
+	sha1: Option<&str>,
 	path: &Path,
 	manager: &UpdateManager,
 	client: &Client,
 	force: bool,
 ) -> anyhow::Result<AssetIndex> {
-	let index = if manager.update_depth < UpdateDepth::Force && !force && path.exists() {
+	let index = if manager.update_depth < UpdateDepth::Force && !force && manager.file_exists(path)
+	{
 		json_from_file(path).context("Failed to read asset index contents from file")?
 	} else {
-		let index = download::json(url, client)
-			.await
-			.context("Failed to download asset index")?;
+		let bytes = mirrors::with_fallback_verified(
+			url,
+			sha1,
+			|url| download::bytes(url, client),
+			|x| Ok(hash_sha1(x)),
+		)
+		.await
+		.context("Failed to download asset index")?;
+		let index: AssetIndex =
+			serde_json::from_slice(&bytes).context("Failed to deserialize asset index")?;
 
 		json_to_file(path, &index).context("Failed to serialize asset index to a file")?;
 
@@ -268,22 +308,82 @@ async fn download_index(
 /// Create the directories needed to store assets
 async fn create_dirs(
 	paths: &Paths,
-	version: &VersionName,
-	version_list: &[String],
+	client_meta: &ClientMeta,
+	index: &AssetIndex,
 ) -> anyhow::Result<(PathBuf, Option<PathBuf>)> {
 	let objects_dir = paths.assets.join("objects");
 	files::create_dir(&objects_dir)?;
-	// Apparently this directory name is used for older game versions
-	let virtual_dir =
-		if VersionPattern::Before("13w48b".into()).matches_single(version, version_list) {
-			Some(get_virtual_dir_path(paths))
-		} else {
-			None
-		};
+	// Older game versions read assets by name instead of from the objects directory
+	let virtual_dir = if index.is_legacy() {
+		let dir = get_virtual_dir_path(paths, client_meta.get_assets_id());
+		files::create_dir(&dir)?;
+		Some(dir)
+	} else {
+		None
+	};
 	Ok((objects_dir, virtual_dir))
 }
 
-/// Get the virtual assets directory path
-pub fn get_virtual_dir_path(paths: &Paths) -> PathBuf {
-	paths.assets.join("virtual").join("legacy")
+/// Get the virtual assets directory path for an asset index
+pub fn get_virtual_dir_path(paths: &Paths, assets_id: &str) -> PathBuf {
+	paths.assets.join("virtual").join(assets_id)
+}
+
+/// Gets the directory that an old version should read its assets from, linking them into
+/// the resources folder of the game directory for versions that need it.
+/// Returns None if the version doesn't use legacy assets
+pub(crate) async fn get_legacy_assets_dir(
+	client_meta: &ClientMeta,
+	paths: &Paths,
+	version: &VersionName,
+	game_dir: &Path,
+) -> anyhow::Result<Option<PathBuf>> {
+	let index_path = paths.assets.join(format!("indexes/{version}.json"));
+	if !index_path.exists() {
+		return Ok(None);
+	}
+	let index: AssetIndex =
+		json_from_file(&index_path).context("Failed to read asset index contents from file")?;
+
+	if !index.is_legacy() {
+		return Ok(None);
+	}
+
+	let virtual_dir = get_virtual_dir_path(paths, client_meta.get_assets_id());
+	if !index.map_to_resources {
+		return Ok(Some(virtual_dir));
+	}
+
+	let resources_dir = game_dir.join("resources");
+	for name in index.objects.keys() {
+		let src = virtual_dir.join(name);
+		if !src.exists() {
+			continue;
+		}
+		let dest = resources_dir.join(name);
+		files::create_leading_dirs_async(&dest).await?;
+		files::update_link_async(&src, &dest)
+			.await
+			.context("Failed to link asset into resources directory")?;
+	}
+
+	Ok(Some(resources_dir))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_legacy_index_deserialization() {
+		let index: AssetIndex = serde_json::from_str(
+			r#"{"map_to_resources": true, "objects": {"sound/step/grass1.ogg": {"hash": "227ab99bf7c6cf0b2002e0f7957d0ff7e5cb0c96", "size": 7126}}}"#,
+		)
+		.unwrap();
+		assert!(index.is_legacy());
+		assert!(!index.is_virtual);
+
+		let index: AssetIndex = serde_json::from_str(r#"{"objects": {}}"#).unwrap();
+		assert!(!index.is_legacy());
+	}
 }
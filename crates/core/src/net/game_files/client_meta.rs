@@ -2,7 +2,7 @@ use std::io::{Cursor, Read};
 use std::path::PathBuf;
 
 use anyhow::{Context, bail};
-use nitro_shared::minecraft::VersionManifest;
+use nitro_shared::minecraft::{NativesArch, VersionManifest};
 use nitro_shared::output::{MessageContents, NitroOutput};
 use nitro_shared::util::DeserListOrSingle;
 use nitro_shared::{UpdateDepth, translate};
@@ -17,6 +17,8 @@ use crate::io::json_from_file;
 use crate::io::update::UpdateManager;
 use crate::net::download::ProgressiveDownload;
 
+use super::mirrors;
+
 /// The client metadata, which is used for information about
 /// how to set up and launch the client for every version of the game.
 #[derive(Deserialize, Debug, Clone)]
@@ -50,11 +52,21 @@ pub struct ClientMeta {
 	pub logging: Option<LogInfo>,
 }
 
+impl ClientMeta {
+	/// Gets the ID of the assets used by this version
+	pub fn get_assets_id(&self) -> &str {
+		self.assets_version.as_deref().unwrap_or("legacy")
+	}
+}
+
 /// Information in the meta about the assets index
 #[derive(Deserialize, Debug, Clone)]
 pub struct AssetIndexInfo {
 	/// The URL to the assets index for this version
 	pub url: String,
+	/// The SHA-1 hash of the assets index
+	#[serde(default)]
+	pub sha1: Option<String>,
 }
 
 /// Download information for different files
@@ -71,6 +83,9 @@ pub struct Downloads {
 pub struct DownloadInfo {
 	/// The URL to the file
 	pub url: String,
+	/// The SHA-1 hash of the file
+	#[serde(default)]
+	pub sha1: Option<String>,
 }
 
 /// Information about Java for this version
@@ -189,6 +204,9 @@ pub mod libraries {
 		pub path: String,
 		/// URL to download the artifact from
 		pub url: String,
+		/// The SHA-1 hash of the artifact
+		#[serde(default)]
+		pub sha1: Option<String>,
 	}
 
 	/// Extraction rules for a library
@@ -339,6 +357,17 @@ pub mod conditions {
 			)
 		}
 	}
+
+	impl OSArch {
+		/// Checks if this architecture condition matches the architecture that natives are selected for
+		pub fn matches(&self, arch: NativesArch) -> bool {
+			match self {
+				Self::X86 => arch == NativesArch::X86,
+				Self::X86_64 => arch == NativesArch::X64,
+				Self::Arm => arch == NativesArch::Arm64,
+			}
+		}
+	}
 }
 
 /// Gets the specific client info JSON file for a Minecraft version
@@ -363,10 +392,12 @@ pub async fn get(
 	let path = get_path(&version_string, paths);
 	create_leading_dirs(&path)?;
 
-	let meta = if manager.update_depth < UpdateDepth::Full && path.exists() {
+	let meta = if manager.update_depth < UpdateDepth::Full && manager.file_exists(&path) {
 		json_from_file(path).context("Failed to read client meta contents from file")?
 	} else {
-		let mut download = ProgressiveDownload::bytes(&entry.url, client).await?;
+		let mut download =
+			mirrors::with_fallback(&entry.url, |url| ProgressiveDownload::bytes(url, client))
+				.await?;
 
 		while !download.is_finished() {
 			download.poll_download().await?;
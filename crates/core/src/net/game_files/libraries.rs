@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 use std::fs::File;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{Context, anyhow};
+use nitro_shared::minecraft::NativesArch;
 use nitro_shared::output::{MessageContents, NitroOutput};
 use nitro_shared::translate;
 use reqwest::Client;
@@ -11,42 +12,57 @@ use tokio::{sync::Semaphore, task::JoinSet};
 use zip::ZipArchive;
 
 use crate::io::files;
+use crate::io::files::paths::Paths;
 use crate::io::java::classpath::Classpath;
 use crate::io::java::maven::MavenLibraryParts;
-use crate::io::update::{UpdateManager, UpdateMethodResult};
-use crate::net::{download, get_transfer_limit};
+use crate::io::update::{UpdateManager, UpdateMethodResult, hash_sha1};
+use crate::net::{download, stats};
 use nitro_shared::skip_none;
 use nitro_shared::util;
 
-use super::client_meta::libraries::ExtractionRules;
-use super::client_meta::libraries::Library;
+use super::client_meta::libraries::{Artifact, Downloads, ExtractionRules, Library};
+use super::mirrors;
+
+/// The LWJGL 3 version that older versions are replaced with when they don't have ARM64 natives
+const LWJGL_ARM64_VERSION: &str = "3.3.1";
+/// Maven repository to download replacement LWJGL libraries from
+const LWJGL_MAVEN_URL: &str = "https://repo1.maven.org/maven2/";
 
 /// Downloads base client libraries.
 /// Returns a set of files to be added to the update manager.
 pub async fn get(
 	libraries: &[Library],
-	internal_dir: &Path,
+	paths: &Paths,
 	version: &str,
+	arch: NativesArch,
 	manager: &UpdateManager,
 	client: &Client,
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<UpdateMethodResult> {
 	let mut out = UpdateMethodResult::new();
-	let libraries_path = internal_dir.join("libraries");
-	tokio::fs::create_dir_all(&libraries_path).await?;
-	let natives_path = internal_dir.join("versions").join(version).join("natives");
+	let internal_dir = &paths.internal;
+	let libraries_path = &paths.libraries;
+	tokio::fs::create_dir_all(libraries_path).await?;
+	let natives_path = get_natives_dir(internal_dir, version, arch);
 	tokio::fs::create_dir_all(&natives_path).await?;
 	let natives_jars_path = internal_dir.join("natives");
 
+	if arch == NativesArch::Arm64
+		&& !has_natives_for_arch(libraries, arch)
+		&& !libraries.iter().any(is_lwjgl_3)
+	{
+		o.display(MessageContents::Warning(translate!(o, NoArm64Natives)));
+	}
+
 	let mut natives = Vec::new();
 
-	let libraries = get_list(libraries);
+	let libraries = get_list(libraries, arch);
 
 	let mut libs_to_download = Vec::new();
 
-	for lib in libraries {
+	for lib in &libraries {
 		if !lib.natives.is_empty() {
-			let key = skip_none!(get_natives_classifier_key(&lib.natives));
+			let key = skip_none!(get_natives_classifier_key(&lib.natives, arch));
 
 			let classifier = lib
 				.downloads
@@ -57,19 +73,30 @@ pub async fn get(
 			let path = natives_jars_path.join(classifier.path.clone());
 
 			natives.push((path.clone(), &lib.name, &lib.extract));
-			if !manager.should_update_file(&path) {
+			if paths.link_from_shared(&path, classifier.sha1.as_deref())
+				|| !manager.should_update_file_with_hash(&path, classifier.sha1.as_deref())
+			{
+				stats::record_cache_hit(&classifier.url);
 				continue;
 			}
-			libs_to_download.push((lib.name.clone(), classifier.url.clone(), path));
+			libs_to_download.push((
+				lib.name.clone(),
+				classifier.url.clone(),
+				classifier.sha1.clone(),
+				path,
+			));
 			continue;
 		}
 
-		let (url, path) = if let Some(artifact) = &lib.downloads.artifact {
+		let (url, sha1, path) = if let Some(artifact) = &lib.downloads.artifact {
 			let path = libraries_path.join(&artifact.path);
-			if !manager.should_update_file(&path) {
+			if paths.link_from_shared(&path, artifact.sha1.as_deref())
+				|| !manager.should_update_file_with_hash(&path, artifact.sha1.as_deref())
+			{
+				stats::record_cache_hit(&artifact.url);
 				continue;
 			}
-			(artifact.url.clone(), path)
+			(artifact.url.clone(), artifact.sha1.clone(), path)
 		} else {
 			let url = lib
 				.url
@@ -83,11 +110,12 @@ pub async fn get(
 			let path = path_start + &path_end;
 
 			let url = url + &path;
-			(url, libraries_path.join(path))
+			(url, None, libraries_path.join(path))
 		};
 
-		libs_to_download.push((lib.name.clone(), url, path));
+		libs_to_download.push((lib.name.clone(), url, sha1, path));
 	}
+	paths.save_shared_verification()?;
 
 	let count = libs_to_download.len();
 	if count > 0 {
@@ -102,8 +130,8 @@ pub async fn get(
 
 	let mut join = JoinSet::new();
 	// Used to limit the number of open file descriptors
-	let sem = Arc::new(Semaphore::new(get_transfer_limit()));
-	for (name, url, path) in libs_to_download {
+	let sem = Arc::new(Semaphore::new(manager.get_transfer_limit()));
+	for (name, url, sha1, path) in libs_to_download {
 		let client = client.clone();
 		let sem = sem.clone();
 		let path_clone = path.clone();
@@ -112,9 +140,14 @@ pub async fn get(
 
 			let _permit = sem.acquire().await;
 
-			let response = download::bytes(url, &client)
-				.await
-				.context("Failed to download library")?;
+			let response = mirrors::with_fallback_verified(
+				&url,
+				sha1.as_deref(),
+				|url| download::bytes(url, &client),
+				|x| Ok(hash_sha1(x)),
+			)
+			.await
+			.context("Failed to download library")?;
 			tokio::fs::write(&path_clone, response)
 				.await
 				.context("Failed to write library file")?;
@@ -172,15 +205,19 @@ pub async fn get(
 }
 
 /// Gets the classpath from Minecraft libraries
-pub fn get_classpath(libraries: &[Library], internal_dir: &Path) -> anyhow::Result<Classpath> {
+pub fn get_classpath(
+	libraries: &[Library],
+	internal_dir: &Path,
+	libraries_path: &Path,
+	arch: NativesArch,
+) -> anyhow::Result<Classpath> {
 	let natives_jars_path = internal_dir.join("natives");
-	let libraries_path = internal_dir.join("libraries");
 
 	let mut classpath = Classpath::new();
-	let libraries = get_list(libraries);
+	let libraries = get_list(libraries, arch);
 	for lib in libraries {
 		if !lib.natives.is_empty() {
-			let key = skip_none!(get_natives_classifier_key(&lib.natives));
+			let key = skip_none!(get_natives_classifier_key(&lib.natives, arch));
 
 			let classifier = lib
 				.downloads
@@ -202,18 +239,32 @@ pub fn get_classpath(libraries: &[Library], internal_dir: &Path) -> anyhow::Resu
 	Ok(classpath)
 }
 
+/// Gets the directory that native libraries for a version are extracted to.
+/// Natives for architectures other than the system one are kept separate so that they don't overwrite each other
+pub fn get_natives_dir(internal_dir: &Path, version: &str, arch: NativesArch) -> PathBuf {
+	let dir = internal_dir.join("versions").join(version);
+	if arch == NativesArch::detect() {
+		dir.join("natives")
+	} else {
+		dir.join(format!("natives-{arch}"))
+	}
+}
+
 /// Get the key for the natives classifier
-fn get_natives_classifier_key(classifiers: &HashMap<String, String>) -> Option<String> {
+fn get_natives_classifier_key(
+	classifiers: &HashMap<String, String>,
+	arch: NativesArch,
+) -> Option<String> {
 	let key = classifiers
 		.get(&format!("natives-{}", util::OS_STRING))
 		.or_else(|| classifiers.get(util::OS_STRING))?;
-	let key = key.replace("${arch}", util::TARGET_BITS_STR);
+	let key = key.replace("${arch}", arch.bits());
 
 	Some(key)
 }
 
 /// Checks the rules of a game library to see if it should be installed
-fn is_allowed(lib: &Library) -> bool {
+fn is_allowed(lib: &Library, arch: NativesArch) -> bool {
 	for rule in &lib.rules {
 		let allowed = rule.action.is_allowed();
 		if let Some(os_name) = &rule.os.name
@@ -222,7 +273,7 @@ fn is_allowed(lib: &Library) -> bool {
 			return false;
 		}
 		if let Some(os_arch) = &rule.os.arch
-			&& allowed != (os_arch.to_string() == util::ARCH_STRING)
+			&& allowed != os_arch.matches(arch)
 		{
 			return false;
 		}
@@ -231,6 +282,116 @@ fn is_allowed(lib: &Library) -> bool {
 	true
 }
 
+/// Gets the classifier of a library from its Maven name, like natives-linux in org.lwjgl:lwjgl:3.3.1:natives-linux
+fn get_maven_classifier(name: &str) -> Option<&str> {
+	name.split(':').nth(3)
+}
+
+/// Gets the architecture that a natives classifier is for
+fn get_classifier_arch(classifier: &str) -> Option<NativesArch> {
+	let natives = classifier.strip_prefix("natives-")?;
+	let arch = natives.split_once('-').map(|x| x.1).unwrap_or_default();
+	match arch {
+		"" | "64" | "x86_64" => Some(NativesArch::X64),
+		"x86" | "32" => Some(NativesArch::X86),
+		"arm64" | "aarch_64" => Some(NativesArch::Arm64),
+		_ => None,
+	}
+}
+
+/// Checks if the client meta provides any natives for the given architecture
+fn has_natives_for_arch(libraries: &[Library], arch: NativesArch) -> bool {
+	libraries
+		.iter()
+		.filter(|lib| is_allowed(lib, arch))
+		.any(|lib| {
+			get_maven_classifier(&lib.name).and_then(get_classifier_arch) == Some(arch)
+				|| lib
+					.downloads
+					.native_classifiers
+					.keys()
+					.any(|x| get_classifier_arch(x) == Some(arch))
+		})
+}
+
+/// Checks if a library is part of LWJGL 3
+fn is_lwjgl_3(lib: &Library) -> bool {
+	lib.name.starts_with("org.lwjgl:")
+		&& MavenLibraryParts::parse_from_str(&lib.name).is_some_and(|x| x.version.starts_with("3."))
+}
+
+/// Gets replacement libraries for an LWJGL 3 library that has no ARM64 natives.
+/// The main library is updated to a version with ARM64 support if needed and given a separate natives library
+/// that LWJGL will extract on its own
+fn get_lwjgl_arm64_replacement(lib: &Library) -> Option<Vec<Library>> {
+	if !is_lwjgl_3(lib) {
+		return None;
+	}
+
+	// Natives for other architectures are replaced along with the main library
+	if get_maven_classifier(&lib.name).is_some() {
+		return Some(Vec::new());
+	}
+
+	let os = match util::OS_STRING {
+		"linux" | "macos" | "windows" => util::OS_STRING,
+		_ => return None,
+	};
+	let parts = MavenLibraryParts::parse_from_str(&lib.name)?;
+	let version = if is_version_older(&parts.version, LWJGL_ARM64_VERSION) {
+		LWJGL_ARM64_VERSION
+	} else {
+		&parts.version
+	};
+
+	Some(vec![
+		create_lwjgl_library(&parts.package, version, None),
+		create_lwjgl_library(
+			&parts.package,
+			version,
+			Some(&format!("natives-{os}-arm64")),
+		),
+	])
+}
+
+/// Creates a library for an LWJGL artifact from Maven Central
+fn create_lwjgl_library(package: &str, version: &str, classifier: Option<&str>) -> Library {
+	let (name, file_name) = if let Some(classifier) = classifier {
+		(
+			format!("org.lwjgl:{package}:{version}:{classifier}"),
+			format!("{package}-{version}-{classifier}.jar"),
+		)
+	} else {
+		(
+			format!("org.lwjgl:{package}:{version}"),
+			format!("{package}-{version}.jar"),
+		)
+	};
+	let path = format!("org/lwjgl/{package}/{version}/{file_name}");
+
+	Library {
+		downloads: Downloads {
+			artifact: Some(Artifact {
+				url: format!("{LWJGL_MAVEN_URL}{path}"),
+				path,
+				sha1: None,
+			}),
+			native_classifiers: HashMap::new(),
+		},
+		name,
+		natives: HashMap::new(),
+		rules: Vec::new(),
+		extract: ExtractionRules::default(),
+		url: None,
+	}
+}
+
+/// Checks if a dotted version number is older than another one
+fn is_version_older(version: &str, other: &str) -> bool {
+	let parse = |x: &str| -> Vec<u32> { x.split('.').filter_map(|x| x.parse().ok()).collect() };
+	parse(version) < parse(other)
+}
+
 /// Extract the files of a native library into the natives directory.
 fn extract_native(
 	path: &Path,
@@ -256,7 +417,8 @@ fn extract_native(
 			match extension.to_str() {
 				Some("so" | "dylib" | "dll") => {
 					let out_path = natives_dir.join(rel_path);
-					if !manager.should_update_file(&out_path) {
+					// Natives are always extracted again when verifying in case their jar was corrupted
+					if !manager.should_update_file(&out_path) && !manager.is_verifying() {
 						continue;
 					}
 					let mut out_file =
@@ -278,7 +440,86 @@ fn extract_native(
 	Ok(out)
 }
 
-/// Gets the list of allowed libraries from the client meta
-pub fn get_list(libraries: &[Library]) -> impl Iterator<Item = &Library> {
-	libraries.iter().filter(|lib| is_allowed(lib))
+/// Gets the list of allowed libraries from the client meta, selecting natives for the given architecture.
+/// LWJGL 3 is replaced with a version that has ARM64 natives if the client meta doesn't provide any
+pub fn get_list(libraries: &[Library], arch: NativesArch) -> Vec<Library> {
+	let replace_lwjgl = arch == NativesArch::Arm64 && !has_natives_for_arch(libraries, arch);
+
+	let mut out = Vec::new();
+	for lib in libraries.iter().filter(|lib| is_allowed(lib, arch)) {
+		// Separate natives libraries for other architectures
+		if let Some(lib_arch) = get_maven_classifier(&lib.name).and_then(get_classifier_arch)
+			&& lib_arch != arch
+		{
+			continue;
+		}
+
+		if replace_lwjgl && let Some(replacement) = get_lwjgl_arm64_replacement(lib) {
+			out.extend(replacement);
+			continue;
+		}
+
+		out.push(lib.clone());
+	}
+
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn create_library(name: &str) -> Library {
+		Library {
+			downloads: Downloads::default(),
+			name: name.into(),
+			natives: HashMap::new(),
+			rules: Vec::new(),
+			extract: ExtractionRules::default(),
+			url: None,
+		}
+	}
+
+	#[test]
+	fn test_natives_arch_selection() {
+		let libraries = vec![
+			create_library("org.lwjgl:lwjgl:3.3.1"),
+			create_library("org.lwjgl:lwjgl:3.3.1:natives-windows"),
+			create_library("org.lwjgl:lwjgl:3.3.1:natives-windows-x86"),
+			create_library("org.lwjgl:lwjgl:3.3.1:natives-windows-arm64"),
+		];
+
+		let list = get_list(&libraries, NativesArch::X86);
+		let names: Vec<_> = list.iter().map(|x| x.name.as_str()).collect();
+		assert_eq!(
+			names,
+			vec![
+				"org.lwjgl:lwjgl:3.3.1",
+				"org.lwjgl:lwjgl:3.3.1:natives-windows-x86"
+			]
+		);
+	}
+
+	#[test]
+	fn test_lwjgl_arm64_replacement() {
+		let libraries = vec![
+			create_library("org.lwjgl:lwjgl:3.2.2"),
+			create_library("com.mojang:brigadier:1.0.17"),
+		];
+
+		let list = get_list(&libraries, NativesArch::Arm64);
+		if matches!(util::OS_STRING, "linux" | "macos" | "windows") {
+			assert_eq!(list.len(), 3);
+			assert_eq!(list[0].name, "org.lwjgl:lwjgl:3.3.1");
+			assert!(list[1].name.ends_with("-arm64"));
+			assert_eq!(list[2].name, "com.mojang:brigadier:1.0.17");
+		}
+	}
+
+	#[test]
+	fn test_version_older() {
+		assert!(is_version_older("3.2.2", "3.3.1"));
+		assert!(!is_version_older("3.3.1", "3.3.1"));
+		assert!(!is_version_older("3.3.3", "3.3.1"));
+	}
 }
@@ -0,0 +1,222 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Number of failed downloads after which a mirror is no longer used for the rest of the process
+const MAX_MIRROR_FAILURES: usize = 5;
+
+/// The mirrors that game file downloads are tried from, in order
+static MIRRORS: RwLock<Vec<Arc<MirrorState>>> = RwLock::new(Vec::new());
+
+/// A mirror that serves copies of the official game files from different URLs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadMirror {
+	/// The name of the mirror
+	pub name: String,
+	/// Pairs of official URL prefixes and the mirror URL prefixes that they are replaced with
+	pub rewrites: Vec<(String, String)>,
+}
+
+impl DownloadMirror {
+	/// Gets one of the built-in mirrors by its name
+	pub fn preset(name: &str) -> Option<Self> {
+		match name {
+			"bmclapi" => Some(Self::bmclapi()),
+			_ => None,
+		}
+	}
+
+	/// The BMCLAPI mirror
+	pub fn bmclapi() -> Self {
+		let base = "https://bmclapi2.bangbang93.com/";
+		let rewrites = [
+			("https://piston-meta.mojang.com/", base.to_string()),
+			("https://piston-data.mojang.com/", base.to_string()),
+			("https://launchermeta.mojang.com/", base.to_string()),
+			("https://launcher.mojang.com/", base.to_string()),
+			(
+				"https://resources.download.minecraft.net/",
+				format!("{base}assets/"),
+			),
+			("https://libraries.minecraft.net/", format!("{base}maven/")),
+		];
+
+		Self {
+			name: "bmclapi".into(),
+			rewrites: rewrites
+				.into_iter()
+				.map(|(official, mirror)| (official.to_string(), mirror))
+				.collect(),
+		}
+	}
+
+	/// Rewrites an official URL to the URL of the same file on this mirror.
+	/// Returns None if the mirror doesn't serve the URL
+	pub fn rewrite(&self, url: &str) -> Option<String> {
+		// The most specific prefix wins
+		let (official, mirror) = self
+			.rewrites
+			.iter()
+			.filter(|(official, _)| url.starts_with(official.as_str()))
+			.max_by_key(|(official, _)| official.len())?;
+
+		Some(format!("{mirror}{}", &url[official.len()..]))
+	}
+}
+
+/// A mirror along with how often it has failed
+#[derive(Debug)]
+struct MirrorState {
+	mirror: DownloadMirror,
+	failures: AtomicUsize,
+}
+
+/// Sets the mirrors that game files are downloaded from. The official servers are used when
+/// none of them work
+pub fn set_mirrors(mirrors: Vec<DownloadMirror>) {
+	let mirrors = mirrors
+		.into_iter()
+		.map(|mirror| {
+			Arc::new(MirrorState {
+				mirror,
+				failures: AtomicUsize::new(0),
+			})
+		})
+		.collect();
+
+	if let Ok(mut lock) = MIRRORS.write() {
+		*lock = mirrors;
+	}
+}
+
+/// Runs a download of an official URL with each mirror that serves it, falling back to the
+/// official URL if all of them fail
+pub async fn with_fallback<T, F, Fut>(url: &str, f: F) -> anyhow::Result<T>
+where
+	F: FnMut(String) -> Fut,
+	Fut: Future<Output = anyhow::Result<T>>,
+{
+	let mirrors = MIRRORS.read().map(|x| x.clone()).unwrap_or_default();
+	fallback_with_mirrors(&mirrors, url, f, |_| true).await
+}
+
+/// Runs a download like `with_fallback`, but also checks the SHA-1 hash of files from mirrors
+/// using the hash function. A mirror that serves a file with the wrong hash counts as failing,
+/// so the next mirror or the official URL is used instead
+pub async fn with_fallback_verified<T, F, Fut>(
+	url: &str,
+	sha1: Option<&str>,
+	f: F,
+	hash: impl Fn(&T) -> anyhow::Result<String>,
+) -> anyhow::Result<T>
+where
+	F: FnMut(String) -> Fut,
+	Fut: Future<Output = anyhow::Result<T>>,
+{
+	let mirrors = MIRRORS.read().map(|x| x.clone()).unwrap_or_default();
+	fallback_with_mirrors(&mirrors, url, f, |out| {
+		sha1.is_none_or(|sha1| hash(out).is_ok_and(|x| x.eq_ignore_ascii_case(sha1)))
+	})
+	.await
+}
+
+/// Implementation of `with_fallback` with a specific list of mirrors and a check for the
+/// results from mirrors
+async fn fallback_with_mirrors<T, F, Fut>(
+	mirrors: &[Arc<MirrorState>],
+	url: &str,
+	mut f: F,
+	is_valid: impl Fn(&T) -> bool,
+) -> anyhow::Result<T>
+where
+	F: FnMut(String) -> Fut,
+	Fut: Future<Output = anyhow::Result<T>>,
+{
+	for state in mirrors {
+		if state.failures.load(Ordering::Relaxed) >= MAX_MIRROR_FAILURES {
+			continue;
+		}
+		let Some(mirrored) = state.mirror.rewrite(url) else {
+			continue;
+		};
+
+		match f(mirrored).await {
+			Ok(out) if is_valid(&out) => return Ok(out),
+			_ => {
+				state.failures.fetch_add(1, Ordering::Relaxed);
+			}
+		}
+	}
+
+	f(url.to_string()).await
+}
+
+#[cfg(test)]
+mod tests {
+	use anyhow::bail;
+
+	use super::*;
+
+	#[test]
+	fn test_mirror_rewrite() {
+		let mirror = DownloadMirror::bmclapi();
+		assert_eq!(
+			mirror.rewrite("https://resources.download.minecraft.net/ab/abcd"),
+			Some("https://bmclapi2.bangbang93.com/assets/ab/abcd".into())
+		);
+		assert_eq!(
+			mirror.rewrite("https://piston-meta.mojang.com/mc/game/version_manifest_v2.json"),
+			Some("https://bmclapi2.bangbang93.com/mc/game/version_manifest_v2.json".into())
+		);
+		assert_eq!(mirror.rewrite("https://maven.fabricmc.net/foo.jar"), None);
+	}
+
+	#[tokio::test]
+	async fn test_mirror_fallback() {
+		let mirrors = vec![Arc::new(MirrorState {
+			mirror: DownloadMirror {
+				name: "test".into(),
+				rewrites: vec![("https://official/".into(), "https://mirror/".into())],
+			},
+			failures: AtomicUsize::new(0),
+		})];
+
+		let result = fallback_with_mirrors(
+			&mirrors,
+			"https://official/file",
+			async |url| Ok(url),
+			|_| true,
+		)
+		.await
+		.unwrap();
+		assert_eq!(result, "https://mirror/file");
+
+		let result = fallback_with_mirrors(
+			&mirrors,
+			"https://official/file",
+			async |url| {
+				if url.starts_with("https://mirror/") {
+					bail!("Mirror is down");
+				}
+				Ok(url)
+			},
+			|_| true,
+		)
+		.await
+		.unwrap();
+		assert_eq!(result, "https://official/file");
+		assert_eq!(mirrors[0].failures.load(Ordering::Relaxed), 1);
+
+		// A mirror that serves the wrong file is treated like it failed
+		let result = fallback_with_mirrors(
+			&mirrors,
+			"https://official/file",
+			async |url| Ok(url),
+			|x| !x.starts_with("https://mirror/"),
+		)
+		.await
+		.unwrap();
+		assert_eq!(result, "https://official/file");
+		assert_eq!(mirrors[0].failures.load(Ordering::Relaxed), 2);
+	}
+}
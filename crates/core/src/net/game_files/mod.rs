@@ -4,24 +4,29 @@ pub mod assets;
 pub mod client_meta;
 /// Downloading game Java libraries
 pub mod libraries;
+/// Mirrors that game files can be downloaded from instead of the official servers
+pub mod mirrors;
 /// Downloading and using the version manifest
 pub mod version_manifest;
 
 use crate::io::files::paths::Paths;
-use crate::io::update::UpdateManager;
+use crate::io::update::{UpdateManager, hash_file_sha1};
+use anyhow::Context;
 use nitro_shared::Side;
 use nitro_shared::translate;
 use nitro_shared::util::cap_first_letter;
 
 use reqwest::Client;
 
-use super::download;
+use super::{download, stats};
 
 /// Downloading the game JAR file
 pub mod game_jar {
+	use std::sync::Mutex;
+
 	use nitro_shared::output::{MessageContents, NitroOutput, OutputProcess};
 
-	use self::download::ProgressiveDownload;
+	use crate::net::resume::{ResumableDownload, get_partial_dir};
 
 	use super::{client_meta::ClientMeta, *};
 
@@ -37,7 +42,17 @@ pub mod game_jar {
 	) -> anyhow::Result<()> {
 		let side_str = side.to_string();
 		let path = crate::io::minecraft::game_jar::get_path(side, version, None, &paths.jars);
-		if !manager.should_update_file(&path) {
+		let download = client_meta.downloads.as_ref().map(|downloads| match side {
+			Side::Client => &downloads.client,
+			Side::Server => &downloads.server,
+		});
+		let sha1 = download.and_then(|x| x.sha1.as_deref());
+		let is_shared = paths.link_from_shared(&path, sha1);
+		paths.save_shared_verification()?;
+		if is_shared || !manager.should_update_file_with_hash(&path, sha1) {
+			if let Some(download) = download {
+				stats::record_cache_hit(&download.url);
+			}
 			return Ok(());
 		}
 
@@ -45,23 +60,40 @@ pub mod game_jar {
 		let download_message = translate!(process, StartDownloadingGameJar, "side" = &side_str);
 		process.display(MessageContents::StartProcess(download_message.clone()));
 
-		let Some(downloads) = &client_meta.downloads else {
+		let Some(download) = download else {
 			return Ok(());
 		};
 
-		let download = match side {
-			Side::Client => &downloads.client,
-			Side::Server => &downloads.server,
-		};
-
-		let mut download = ProgressiveDownload::file(&download.url, path, client).await?;
-		while !download.is_finished() {
-			download.poll_download().await?;
-			process.display(MessageContents::Associated(
-				Box::new(download.get_progress()),
-				Box::new(MessageContents::Simple(download_message.clone())),
-			));
-		}
+		let partial_dir = get_partial_dir(&paths.internal);
+		// Every attempt shows its progress on the same process
+		let shared_process = Mutex::new(process);
+		mirrors::with_fallback_verified(
+			&download.url,
+			sha1,
+			|url| {
+				let (path, partial_dir, process) = (&path, &partial_dir, &shared_process);
+				let download_message = download_message.clone();
+				async move {
+					let mut download =
+						ResumableDownload::new(url, path, partial_dir, client).await?;
+					while !download.is_finished() {
+						download.poll_download().await?;
+						if let Ok(mut process) = process.lock() {
+							process.display(MessageContents::Associated(
+								Box::new(download.get_progress()),
+								Box::new(MessageContents::Simple(download_message.clone())),
+							));
+						}
+					}
+					Ok(())
+				}
+			},
+			|_| hash_file_sha1(&path).context("Failed to hash game JAR"),
+		)
+		.await?;
+		let mut process = shared_process
+			.into_inner()
+			.unwrap_or_else(|e| e.into_inner());
 
 		let side_str = cap_first_letter(&side_str);
 
@@ -100,8 +132,14 @@ pub mod log_config {
 			return Ok(());
 		};
 
-		let url = &logging.client.file.url;
-		download::file(url, &path, client).await?;
+		let file = &logging.client.file;
+		mirrors::with_fallback_verified(
+			&file.url,
+			file.sha1.as_deref(),
+			|url| download::file(url, &path, client),
+			|_| hash_file_sha1(&path).context("Failed to hash logging config"),
+		)
+		.await?;
 
 		Ok(())
 	}
@@ -1,4 +1,5 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::Context;
 use nitro_shared::minecraft::{VersionManifest, VersionType};
@@ -13,6 +14,18 @@ use crate::io::{json_from_file, json_to_file};
 use crate::net::download::ProgressiveDownload;
 use crate::util::versions::MinecraftVersion;
 
+use super::mirrors;
+
+/// How long a cached version manifest is used for versions like `latest` before checking for new versions
+const MOVING_VERSION_MAX_AGE: Duration = Duration::from_secs(60 * 60 * 6);
+/// URL to the version manifest
+pub(crate) const MANIFEST_URL: &str =
+	"https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+
+/// Lock for tests that install fixtures for the manifest URL, since installed fixtures are global
+#[cfg(test)]
+pub(crate) static TEST_FIXTURE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
 /// Get the version manifest
 pub async fn get(
 	requested_version: Option<&MinecraftVersion>,
@@ -46,7 +59,8 @@ pub async fn get_with_output(
 	client: &Client,
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<VersionManifest> {
-	let show_output = !(manager.get_depth() < UpdateDepth::Full && get_path(paths).exists());
+	let show_output =
+		!(manager.get_depth() < UpdateDepth::Full && manager.file_exists(&get_path(paths)));
 
 	if show_output {
 		o.start_process();
@@ -82,7 +96,7 @@ async fn get_contents(
 	if let Some(requested_version) = requested_version
 		&& !force
 		&& manager.update_depth < UpdateDepth::Full
-		&& path.exists()
+		&& manager.file_exists(&path)
 	{
 		let contents: VersionManifest =
 			json_from_file(&path).context("Failed to read manifest contents from file")?;
@@ -94,15 +108,34 @@ async fn get_contents(
 				.iter()
 				.any(|x| x.id.as_str() == version.as_ref())
 		{
+			// Versions that follow a channel need a recent manifest to advance to new versions.
+			// If it can't be refreshed, the cached one is still good enough to launch with
+			if requested_version.is_moving()
+				&& manager.is_file_older_than(&path, MOVING_VERSION_MAX_AGE)
+			{
+				match download(&path, client, o).await {
+					Ok(manifest) => return Ok(manifest),
+					Err(e) => o.debug(MessageContents::Warning(format!(
+						"Failed to refresh version manifest: {e:#}"
+					))),
+				}
+			}
+
 			return Ok(contents);
 		}
 	}
 
-	let mut download = ProgressiveDownload::bytes(
-		"https://piston-meta.mojang.com/mc/game/version_manifest_v2.json",
-		client,
-	)
-	.await?;
+	download(&path, client, o).await
+}
+
+/// Downloads the version manifest and saves it to the given path
+async fn download(
+	path: &Path,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<VersionManifest> {
+	let mut download =
+		mirrors::with_fallback(MANIFEST_URL, |url| ProgressiveDownload::bytes(url, client)).await?;
 
 	while !download.is_finished() {
 		download.poll_download().await?;
@@ -175,3 +208,47 @@ impl VersionManifestAndList {
 			.collect()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use nitro_net::fixtures::FixtureSet;
+	use nitro_shared::output::NoOp;
+
+	use super::*;
+
+	#[tokio::test]
+	async fn test_manifest_download() {
+		let _lock = TEST_FIXTURE_LOCK.lock().await;
+		let mut fixtures = FixtureSet::new();
+		fixtures.add(
+			MANIFEST_URL,
+			200,
+			r#"{
+				"latest": { "release": "1.20.1", "snapshot": "23w31a" },
+				"versions": [
+					{ "id": "23w31a", "type": "snapshot", "url": "https://example.com/23w31a.json" },
+					{ "id": "1.20.1", "type": "release", "url": "https://example.com/1.20.1.json" }
+				]
+			}"#,
+		);
+		let _guard = fixtures.install();
+
+		let dir = std::env::temp_dir().join("nitro_test_manifest_download");
+		let paths = Paths::from_dirs(dir.join("config"), dir.join("data"));
+		let manager = UpdateManager::new(UpdateDepth::Force);
+		let manifest = get(
+			Some(&MinecraftVersion::Latest),
+			&paths,
+			&manager,
+			&Client::new(),
+			&mut NoOp,
+		)
+		.await
+		.unwrap();
+
+		assert_eq!(make_version_list(&manifest), vec!["1.20.1", "23w31a"]);
+		assert!(get_path(&paths).exists());
+
+		let _ = std::fs::remove_dir_all(dir);
+	}
+}
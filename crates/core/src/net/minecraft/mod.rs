@@ -1,3 +1,6 @@
+/// Listing and joining Minecraft Realms
+pub mod realms;
+
 use nitro_auth::mc::{Keypair, call_mc_api};
 use nitro_shared::minecraft::{MinecraftUserProfile, SkinVariant};
 use reqwest::{
@@ -0,0 +1,277 @@
+use std::time::Duration;
+
+use anyhow::{Context, bail};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+/// Base URL for the Realms API
+pub const REALMS_API_URL: &str = "https://pc.realms.minecraft.net";
+/// The game version sent to the Realms API. The API requires a version in the cookie,
+/// but doesn't check it when listing or joining realms
+const REALMS_CLIENT_VERSION: &str = "1.21.4";
+/// How many times to try getting the address of a realm that is still starting up
+const JOIN_ATTEMPTS: u8 = 5;
+/// How long to wait between attempts to get the address of a realm
+const JOIN_RETRY_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Credentials for making requests to the Realms API
+#[derive(Debug, Clone)]
+pub struct RealmsCredentials {
+	/// The Minecraft access token of the account
+	pub access_token: String,
+	/// The UUID of the account
+	pub uuid: String,
+	/// The username of the account
+	pub name: String,
+}
+
+impl RealmsCredentials {
+	/// Gets the cookie that the Realms API uses for authentication
+	fn get_cookie(&self) -> String {
+		format!(
+			"sid=token:{}:{};user={};version={REALMS_CLIENT_VERSION}",
+			self.access_token, self.uuid, self.name
+		)
+	}
+
+	/// Adds authentication to a request
+	fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+		request.header("Cookie", self.get_cookie())
+	}
+}
+
+/// A realm that the user owns or has been invited to
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Realm {
+	/// The ID of the realm, used for Quick Play
+	pub id: u64,
+	/// The name of the realm
+	#[serde(default)]
+	pub name: Option<String>,
+	/// The message of the day for the realm
+	#[serde(default)]
+	pub motd: Option<String>,
+	/// The username of the owner
+	#[serde(default)]
+	pub owner: Option<String>,
+	/// The UUID of the owner
+	#[serde(default, rename = "ownerUUID")]
+	pub owner_uuid: Option<String>,
+	/// The state of the realm
+	pub state: RealmState,
+	/// Whether the subscription for the realm has expired
+	#[serde(default)]
+	pub expired: bool,
+	/// The maximum number of players on the realm
+	#[serde(default)]
+	pub max_players: Option<u32>,
+	/// The game version the realm is running
+	#[serde(default)]
+	pub active_version: Option<String>,
+}
+
+impl Realm {
+	/// Checks if the realm is owned by the user with the given UUID
+	pub fn is_owned_by(&self, uuid: &str) -> bool {
+		self.owner_uuid
+			.as_ref()
+			.is_some_and(|x| x.replace('-', "") == uuid.replace('-', ""))
+	}
+}
+
+/// State of a realm
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RealmState {
+	/// The realm is open for players to join
+	Open,
+	/// The realm is closed by the owner
+	Closed,
+	/// The realm has been bought but no world has been set up yet
+	Uninitialized,
+	/// An unknown state
+	#[serde(other)]
+	Unknown,
+}
+
+/// A pending invite to a realm
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RealmInvite {
+	/// The ID of the invite
+	pub invitation_id: String,
+	/// The name of the realm
+	pub world_name: String,
+	/// The description of the realm
+	#[serde(default)]
+	pub world_description: Option<String>,
+	/// The username of the realm owner
+	pub world_owner_name: String,
+	/// When the invite was sent, as a timestamp in milliseconds
+	#[serde(default)]
+	pub date: Option<u64>,
+}
+
+/// Address of a realm server to connect to
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RealmAddress {
+	/// The server address, including the port
+	pub address: String,
+	/// Whether the realm needs to be updated before joining
+	#[serde(default)]
+	pub pending_update: bool,
+}
+
+#[derive(Deserialize)]
+struct RealmsList {
+	servers: Vec<Realm>,
+}
+
+#[derive(Deserialize)]
+struct RealmInviteList {
+	invites: Vec<RealmInvite>,
+}
+
+/// Gets all realms that the user owns or is a member of
+pub async fn get_realms(
+	credentials: &RealmsCredentials,
+	client: &Client,
+) -> anyhow::Result<Vec<Realm>> {
+	let list: RealmsList = get(&format!("{REALMS_API_URL}/worlds"), credentials, client).await?;
+
+	Ok(list.servers)
+}
+
+/// Gets pending invites to realms
+pub async fn get_invites(
+	credentials: &RealmsCredentials,
+	client: &Client,
+) -> anyhow::Result<Vec<RealmInvite>> {
+	let list: RealmInviteList = get(
+		&format!("{REALMS_API_URL}/invites/pending"),
+		credentials,
+		client,
+	)
+	.await?;
+
+	Ok(list.invites)
+}
+
+/// Accepts an invite to a realm
+pub async fn accept_invite(
+	invite_id: &str,
+	credentials: &RealmsCredentials,
+	client: &Client,
+) -> anyhow::Result<()> {
+	credentials
+		.authorize(client.put(format!("{REALMS_API_URL}/invites/accept/{invite_id}")))
+		.send()
+		.await?
+		.error_for_status()
+		.context("Failed to accept invite")?;
+
+	Ok(())
+}
+
+/// Rejects an invite to a realm
+pub async fn reject_invite(
+	invite_id: &str,
+	credentials: &RealmsCredentials,
+	client: &Client,
+) -> anyhow::Result<()> {
+	credentials
+		.authorize(client.put(format!("{REALMS_API_URL}/invites/reject/{invite_id}")))
+		.send()
+		.await?
+		.error_for_status()
+		.context("Failed to reject invite")?;
+
+	Ok(())
+}
+
+/// Gets the server address of a realm to join. Waits for the realm to start if it isn't running yet
+pub async fn get_join_address(
+	realm_id: u64,
+	credentials: &RealmsCredentials,
+	client: &Client,
+) -> anyhow::Result<RealmAddress> {
+	let url = format!("{REALMS_API_URL}/worlds/v1/{realm_id}/join/pc");
+	for _ in 0..JOIN_ATTEMPTS {
+		let response = credentials.authorize(client.get(&url)).send().await?;
+		// The realm is still starting up
+		if response.status() == StatusCode::SERVICE_UNAVAILABLE {
+			tokio::time::sleep(JOIN_RETRY_INTERVAL).await;
+			continue;
+		}
+
+		let address = response
+			.error_for_status()
+			.context("Failed to get realm address")?
+			.json()
+			.await?;
+
+		return Ok(address);
+	}
+
+	bail!("Timed out waiting for the realm to start")
+}
+
+/// Sends an authenticated GET request to the Realms API
+async fn get<T: DeserializeOwned>(
+	url: &str,
+	credentials: &RealmsCredentials,
+	client: &Client,
+) -> anyhow::Result<T> {
+	let response = credentials
+		.authorize(client.get(url))
+		.send()
+		.await?
+		.error_for_status()?
+		.json()
+		.await?;
+
+	Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_realms_deserialization() {
+		let list: RealmsList = serde_json::from_str(
+			r#"{
+				"servers": [
+					{
+						"id": 1234567,
+						"remoteSubscriptionId": "abc",
+						"owner": "Steve",
+						"ownerUUID": "069a79f444e94726a5befca90e38aaf5",
+						"name": "Steve's Realm",
+						"motd": "Welcome!",
+						"state": "OPEN",
+						"daysLeft": 20,
+						"expired": false,
+						"expiredTrial": false,
+						"worldType": "NORMAL",
+						"maxPlayers": 10,
+						"activeVersion": "1.21.4"
+					},
+					{
+						"id": 7654321,
+						"state": "SOMETHING_NEW"
+					}
+				]
+			}"#,
+		)
+		.unwrap();
+
+		let realm = &list.servers[0];
+		assert_eq!(realm.state, RealmState::Open);
+		assert!(realm.is_owned_by("069a79f4-44e9-4726-a5be-fca90e38aaf5"));
+		assert!(!realm.is_owned_by("853c80ef3c3749fdaa49938b674adae6"));
+		assert_eq!(list.servers[1].state, RealmState::Unknown);
+	}
+}
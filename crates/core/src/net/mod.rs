@@ -1,3 +1,5 @@
+/// Downloading the authlib-injector agent for alternative authentication servers
+pub mod authlib_injector;
 /// Downloading essential files for launching the game
 pub mod game_files;
 /// Downloading different flavors of the JRE
@@ -6,7 +8,10 @@ pub mod java;
 pub mod minecraft;
 
 // Re-export
+pub use nitro_net::activity;
 pub use nitro_net::download;
+pub use nitro_net::resume;
+pub use nitro_net::stats;
 use nitro_shared::io::config::IO_CONFIG;
 
 /// Sensible open file descriptor limit for asynchronous transfers
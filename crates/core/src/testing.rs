@@ -0,0 +1,187 @@
+use std::path::PathBuf;
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, bail};
+use nitro_auth::mc::ClientId;
+use nitro_shared::UpdateDepth;
+use nitro_shared::output::NitroOutput;
+
+use crate::account::AccountManager;
+use crate::util::versions::MinecraftVersion;
+use crate::{
+	Configuration, Instance, InstanceConfiguration, InstanceHandle, InstanceKind, NitroCore, Paths,
+};
+
+pub use crate::launch::SERVER_READY_MESSAGE;
+
+/// How often to check the output of a running server
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Installs and launches headless vanilla servers in an isolated directory,
+/// so that the whole installation and launch pipeline can be tested
+pub struct HeadlessServerHarness {
+	core: NitroCore,
+	dir: PathBuf,
+	properties: Vec<(String, String)>,
+}
+
+impl HeadlessServerHarness {
+	/// Create a new harness that stores all of its files in a directory
+	pub fn new(dir: PathBuf) -> anyhow::Result<Self> {
+		let paths = Paths::from_dirs(dir.join("config"), dir.join("data"));
+		paths.create_dirs()?;
+		let core = NitroCore::with_config_and_paths(Configuration::new(), paths)?;
+
+		Ok(Self {
+			core,
+			dir,
+			// Test servers shouldn't need to authenticate players
+			properties: vec![("online-mode".into(), "false".into())],
+		})
+	}
+
+	/// Get the core used by the harness
+	pub fn core(&self) -> &NitroCore {
+		&self.core
+	}
+
+	/// Set a property that will be written to the server.properties file of installed servers
+	pub fn set_property(&mut self, key: &str, value: &str) {
+		self.properties.retain(|x| x.0 != key);
+		self.properties.push((key.into(), value.into()));
+	}
+
+	/// Install a server of a version, accepting the EULA and writing the configured properties
+	pub async fn install(
+		&self,
+		version: &MinecraftVersion,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<Instance> {
+		let version = self
+			.core
+			.get_version(version, UpdateDepth::Shallow, o)
+			.await
+			.context("Failed to install version")?;
+
+		let server_dir = self.dir.join("server");
+		let kind = InstanceKind::Server {
+			create_eula: true,
+			show_gui: false,
+		};
+		let config = InstanceConfiguration::new(kind, server_dir.clone());
+		let instance = version
+			.get_instance(config, o)
+			.await
+			.context("Failed to create server")?;
+
+		let properties: String = self
+			.properties
+			.iter()
+			.map(|(key, value)| format!("{key}={value}\n"))
+			.collect();
+		tokio::fs::write(server_dir.join("server.properties"), properties)
+			.await
+			.context("Failed to write server.properties")?;
+
+		Ok(instance)
+	}
+
+	/// Launch an installed server without blocking
+	pub async fn launch(
+		&self,
+		instance: &mut Instance,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<RunningServer> {
+		// Servers don't use accounts, but launching requires a manager
+		let mut accounts = AccountManager::new(ClientId::new(String::new()));
+		let handle = instance
+			.launch_with_handle(&mut accounts, true, None, o)
+			.await
+			.map_err(|e| e.context("Failed to launch server"))?;
+
+		Ok(RunningServer { handle })
+	}
+}
+
+/// A server launched by the harness. The server process is killed when this is dropped
+pub struct RunningServer {
+	handle: InstanceHandle,
+}
+
+impl RunningServer {
+	/// Reads all of the output that the server has written so far
+	pub async fn read_output(&self) -> anyhow::Result<String> {
+		let output = tokio::fs::read(self.handle.stdout_path())
+			.await
+			.context("Failed to read server output")?;
+
+		Ok(String::from_utf8_lossy(&output).into_owned())
+	}
+
+	/// Waits until the server writes a line containing a pattern and returns that line.
+	/// Fails if the server exits or the timeout is reached first
+	pub async fn wait_for_output(
+		&mut self,
+		pattern: &str,
+		timeout: Duration,
+	) -> anyhow::Result<String> {
+		let start = Instant::now();
+		loop {
+			let output = self.read_output().await?;
+			if let Some(line) = output.lines().find(|x| x.contains(pattern)) {
+				return Ok(line.to_string());
+			}
+
+			if let Some(status) = self.handle.try_wait()? {
+				bail!("Server exited with {status} before writing '{pattern}'. Output:\n{output}");
+			}
+			if start.elapsed() > timeout {
+				bail!("Timed out waiting for server to write '{pattern}'. Output:\n{output}");
+			}
+
+			tokio::time::sleep(POLL_INTERVAL).await;
+		}
+	}
+
+	/// Waits until the server has finished starting
+	pub async fn wait_until_ready(&mut self, timeout: Duration) -> anyhow::Result<()> {
+		self.wait_for_output(SERVER_READY_MESSAGE, timeout)
+			.await
+			.map(|_| ())
+	}
+
+	/// Sends a command to the server console
+	pub fn send_command(&mut self, command: &str) -> anyhow::Result<()> {
+		self.handle
+			.write_stdin(format!("{command}\n").as_bytes())
+			.context("Failed to write to server stdin")
+	}
+
+	/// Stops the server with the stop command, killing it if it doesn't exit before the timeout
+	pub async fn stop(mut self, timeout: Duration) -> anyhow::Result<ExitStatus> {
+		self.send_command("stop")?;
+
+		let start = Instant::now();
+		loop {
+			if let Some(status) = self.handle.try_wait()? {
+				return Ok(status);
+			}
+			if start.elapsed() > timeout {
+				self.handle.kill()?;
+				bail!("Server did not stop before the timeout and was killed");
+			}
+
+			tokio::time::sleep(POLL_INTERVAL).await;
+		}
+	}
+}
+
+impl Drop for RunningServer {
+	fn drop(&mut self) {
+		if let Ok(None) = self.handle.try_wait() {
+			let _ = self.handle.kill();
+			let _ = self.handle.wait();
+		}
+	}
+}
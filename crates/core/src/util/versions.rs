@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use nitro_shared::{
-	minecraft::VersionManifest,
+	minecraft::{VersionChannel, VersionManifest},
 	versions::{MinecraftLatestVersion, MinecraftVersionDeser, VersionName},
 };
 
@@ -14,6 +14,8 @@ pub enum MinecraftVersion {
 	Latest,
 	/// The latest release or development version available
 	LatestSnapshot,
+	/// The newest version in one of the older or special channels
+	LatestInChannel(VersionChannel),
 }
 
 impl MinecraftVersion {
@@ -23,15 +25,24 @@ impl MinecraftVersion {
 			MinecraftVersionDeser::Version(version) => Self::Version(version.clone()),
 			MinecraftVersionDeser::Latest(MinecraftLatestVersion::Release) => Self::Latest,
 			MinecraftVersionDeser::Latest(MinecraftLatestVersion::Snapshot) => Self::LatestSnapshot,
+			MinecraftVersionDeser::Latest(latest) => Self::LatestInChannel(latest.get_channel()),
 		}
 	}
 
+	/// Checks if this version automatically advances when new versions come out
+	pub fn is_moving(&self) -> bool {
+		!matches!(self, Self::Version(..))
+	}
+
 	/// Get the correct version from the version manifest
 	pub fn get_version(&self, manifest: &VersionManifest) -> Option<VersionName> {
 		match self {
 			Self::Version(version) => Some(version.clone()),
 			Self::Latest => manifest.latest.as_ref().map(|x| x.release.clone()),
 			Self::LatestSnapshot => manifest.latest.as_ref().map(|x| x.snapshot.clone()),
+			Self::LatestInChannel(channel) => manifest
+				.get_latest_in_channel(*channel)
+				.map(|x| x.id.clone().into()),
 		}
 	}
 
@@ -40,6 +51,13 @@ impl MinecraftVersion {
 		match self {
 			Self::Latest => MinecraftVersionDeser::Latest(MinecraftLatestVersion::Release),
 			Self::LatestSnapshot => MinecraftVersionDeser::Latest(MinecraftLatestVersion::Snapshot),
+			Self::LatestInChannel(channel) => MinecraftVersionDeser::Latest(match channel {
+				VersionChannel::Release => MinecraftLatestVersion::Release,
+				VersionChannel::Snapshot => MinecraftLatestVersion::Snapshot,
+				VersionChannel::Beta => MinecraftLatestVersion::Beta,
+				VersionChannel::Alpha => MinecraftLatestVersion::Alpha,
+				VersionChannel::AprilFools => MinecraftLatestVersion::AprilFools,
+			}),
 			Self::Version(version) => MinecraftVersionDeser::Version(version),
 		}
 	}
@@ -47,15 +65,12 @@ impl MinecraftVersion {
 
 impl Display for MinecraftVersion {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(
-			f,
-			"{}",
-			match self {
-				Self::Version(version) => version,
-				Self::Latest => "Latest",
-				Self::LatestSnapshot => "Latest Snapshot",
-			}
-		)
+		match self {
+			Self::Version(version) => write!(f, "{version}"),
+			Self::Latest => write!(f, "Latest"),
+			Self::LatestSnapshot => write!(f, "Latest Snapshot"),
+			Self::LatestInChannel(channel) => write!(f, "Latest {channel}"),
+		}
 	}
 }
 
@@ -92,5 +107,49 @@ mod tests {
 				.version,
 			MinecraftVersionDeser::Latest(MinecraftLatestVersion::Snapshot)
 		);
+
+		assert_eq!(
+			serde_json::from_str::<Test>(r#"{"version": "latest_april_fools"}"#)
+				.unwrap()
+				.version,
+			MinecraftVersionDeser::Latest(MinecraftLatestVersion::AprilFools)
+		);
+	}
+
+	#[test]
+	fn test_latest_in_channel() {
+		let manifest: VersionManifest = serde_json::from_str(
+			r#"{
+				"latest": {"release": "1.21.5", "snapshot": "25w15a"},
+				"versions": [
+					{"id": "25w15a", "type": "snapshot", "url": ""},
+					{"id": "25w14craftmine", "type": "snapshot", "url": ""},
+					{"id": "1.21.5", "type": "release", "url": ""},
+					{"id": "b1.8.1", "type": "old_beta", "url": ""},
+					{"id": "a1.2.6", "type": "old_alpha", "url": ""}
+				]
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			MinecraftVersion::LatestInChannel(VersionChannel::AprilFools)
+				.get_version(&manifest)
+				.as_deref(),
+			Some("25w14craftmine")
+		);
+		assert_eq!(
+			MinecraftVersion::LatestInChannel(VersionChannel::Beta)
+				.get_version(&manifest)
+				.as_deref(),
+			Some("b1.8.1")
+		);
+		assert_eq!(
+			MinecraftVersion::LatestInChannel(VersionChannel::Snapshot)
+				.get_version(&manifest)
+				.as_deref(),
+			Some("25w15a")
+		);
+		assert!(!MinecraftVersion::Version("1.21.5".into()).is_moving());
 	}
 }
@@ -1,10 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 
 use anyhow::Context;
-use nitro_shared::minecraft::{VersionEntry, VersionManifest};
+use nitro_shared::minecraft::{NativesArch, VersionEntry, VersionManifest};
 use nitro_shared::output::MessageContents;
 use nitro_shared::output::NitroOutput;
 use nitro_shared::versions::{VersionInfo, VersionName};
@@ -93,8 +92,8 @@ impl InstalledVersion {
 			version: &self.inner.version,
 			paths: &self.params.paths,
 			req_client: &self.params.req_client,
-			version_manifest: &self.inner.version_manifest,
 			update_manager: &self.params.update_manager,
+			natives_arch: NativesArch::detect(),
 		};
 		self.inner.client_assets_and_libs.load(params, o).await
 	}
@@ -199,7 +198,9 @@ impl InstalledVersionInner {
 	) -> anyhow::Result<Self> {
 		// Get the client meta
 		let show_output = !(params.update_manager.get_depth() < UpdateDepth::Full
-			&& client_meta::get_path(&version, params.paths).exists());
+			&& params
+				.update_manager
+				.file_exists(&client_meta::get_path(&version, params.paths)));
 
 		if show_output {
 			o.start_process();
@@ -235,8 +236,10 @@ impl InstalledVersionInner {
 
 /// A registry of installed versions
 pub(crate) struct VersionRegistry {
-	versions: Mutex<HashMap<VersionName, InstalledVersionInner>>,
-	version_manifest: OnceLock<Arc<VersionManifestAndList>>,
+	/// Loaded versions along with the depth they were loaded at
+	versions: Mutex<HashMap<VersionName, (InstalledVersionInner, UpdateDepth)>>,
+	/// The loaded version manifest along with the depth it was loaded at
+	version_manifest: Mutex<Option<(Arc<VersionManifestAndList>, UpdateDepth)>>,
 	additional_versions: Vec<VersionEntry>,
 }
 
@@ -244,12 +247,13 @@ impl VersionRegistry {
 	pub fn new() -> Self {
 		Self {
 			versions: Mutex::new(HashMap::new()),
-			version_manifest: OnceLock::new(),
+			version_manifest: Mutex::new(None),
 			additional_versions: Vec::new(),
 		}
 	}
 
-	/// Load a version if it is not already loaded, and get it otherwise
+	/// Load a version if it is not already loaded, and get it otherwise.
+	/// Versions are loaded again if they are requested at a higher depth than they were loaded at
 	pub async fn get_version(
 		&self,
 		version: &VersionName,
@@ -264,58 +268,60 @@ impl VersionRegistry {
 			req_client: params.req_client,
 			update_manager: params.update_manager,
 		};
-		self.load_version_manifest(vm_params, o)
+		let version_manifest = self
+			.load_version_manifest(vm_params, o)
 			.await
 			.context("Failed to get version manifest")?;
 
-		let exists = self.versions.lock().await.contains_key(version);
-		if !exists {
-			let installed_version = InstalledVersionInner::load(
-				version.clone(),
-				self.version_manifest.get().unwrap(),
-				params,
-				o,
-			)
-			.await?;
-			self.versions
-				.lock()
-				.await
-				.insert(version.clone(), installed_version);
+		let depth = params.update_manager.get_depth();
+		if let Some((existing, loaded_depth)) = self.versions.lock().await.get(version)
+			&& depth <= *loaded_depth
+		{
+			return Ok(existing.clone());
 		}
-		Ok(self
-			.versions
+
+		let installed_version =
+			InstalledVersionInner::load(version.clone(), &version_manifest, params, o).await?;
+		self.versions
 			.lock()
 			.await
-			.get(version)
-			.cloned()
-			.expect("Version should exist in map"))
+			.insert(version.clone(), (installed_version.clone(), depth));
+
+		Ok(installed_version)
 	}
 
-	/// Load the version manifest
+	/// Load the version manifest if it is not already loaded, and get it otherwise.
+	/// The manifest is loaded again if it is requested at a higher depth than it was loaded at
 	pub async fn load_version_manifest(
 		&self,
 		params: LoadVersionManifestParameters<'_>,
 		o: &mut impl NitroOutput,
-	) -> anyhow::Result<&Arc<VersionManifestAndList>> {
-		if self.version_manifest.get().is_none() {
-			let mut manifest = version_manifest::get_with_output(
-				params.requested_version,
-				params.paths,
-				params.update_manager,
-				params.req_client,
-				o,
-			)
-			.await
-			.context("Failed to get version manifest")?;
+	) -> anyhow::Result<Arc<VersionManifestAndList>> {
+		let depth = params.update_manager.get_depth();
+		let mut version_manifest = self.version_manifest.lock().await;
+		if let Some((existing, loaded_depth)) = &*version_manifest
+			&& depth <= *loaded_depth
+		{
+			return Ok(existing.clone());
+		}
 
-			// Add additional versions
-			add_versions(&mut manifest, self.additional_versions.clone());
+		let mut manifest = version_manifest::get_with_output(
+			params.requested_version,
+			params.paths,
+			params.update_manager,
+			params.req_client,
+			o,
+		)
+		.await
+		.context("Failed to get version manifest")?;
 
-			let combo = VersionManifestAndList::new(manifest);
+		// Add additional versions
+		add_versions(&mut manifest, self.additional_versions.clone());
 
-			let _ = self.version_manifest.set(Arc::new(combo));
-		}
-		Ok(self.version_manifest.get().unwrap())
+		let combo = Arc::new(VersionManifestAndList::new(manifest));
+		*version_manifest = Some((combo.clone(), depth));
+
+		Ok(combo)
 	}
 
 	/// Add additional versions to the manifest. Must be called before the manifest is obtained.
@@ -358,29 +364,34 @@ pub(crate) struct LoadVersionManifestParameters<'a> {
 /// loaded when a client needs them
 #[derive(Clone)]
 pub(crate) struct ClientAssetsAndLibraries {
-	loaded: Arc<AtomicBool>,
+	/// The native architectures that have been loaded
+	loaded: Arc<std::sync::Mutex<HashSet<NativesArch>>>,
 }
 
 impl ClientAssetsAndLibraries {
 	pub fn new() -> Self {
 		Self {
-			loaded: Arc::new(AtomicBool::new(false)),
+			loaded: Arc::new(std::sync::Mutex::new(HashSet::new())),
 		}
 	}
 
+	/// Checks if the assets and libraries have already been loaded for an architecture
+	fn is_loaded(&self, arch: NativesArch) -> bool {
+		self.loaded.lock().is_ok_and(|x| x.contains(&arch))
+	}
+
 	pub async fn load(
 		&self,
 		params: ClientAssetsAndLibsParameters<'_>,
 		o: &mut impl NitroOutput,
 	) -> anyhow::Result<()> {
-		if self.loaded.load(Ordering::Relaxed) {
+		if self.is_loaded(params.natives_arch) {
 			return Ok(());
 		}
 		assets::get(
 			params.client_meta,
 			params.paths,
 			params.version,
-			&params.version_manifest.list,
 			params.update_manager,
 			params.req_client,
 			o,
@@ -390,8 +401,9 @@ impl ClientAssetsAndLibraries {
 
 		libraries::get(
 			&params.client_meta.libraries,
-			&params.paths.internal,
+			params.paths,
 			params.version,
+			params.natives_arch,
 			params.update_manager,
 			params.req_client,
 			o,
@@ -399,7 +411,9 @@ impl ClientAssetsAndLibraries {
 		.await
 		.context("Failed to get game libraries")?;
 
-		self.loaded.store(true, Ordering::Relaxed);
+		if let Ok(mut loaded) = self.loaded.lock() {
+			loaded.insert(params.natives_arch);
+		}
 		Ok(())
 	}
 }
@@ -410,8 +424,8 @@ pub(crate) struct ClientAssetsAndLibsParameters<'a> {
 	pub version: &'a VersionName,
 	pub paths: &'a Paths,
 	pub req_client: &'a reqwest::Client,
-	pub version_manifest: &'a VersionManifestAndList,
 	pub update_manager: &'a UpdateManager,
+	pub natives_arch: NativesArch,
 }
 
 /// Adds extra versions to a manifest
@@ -429,3 +443,56 @@ pub fn add_versions(manifest: &mut VersionManifest, additional_versions: Vec<Ver
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use nitro_net::fixtures::FixtureSet;
+	use nitro_shared::output::NoOp;
+
+	use crate::net::game_files::version_manifest::{MANIFEST_URL, TEST_FIXTURE_LOCK};
+
+	use super::*;
+
+	#[tokio::test]
+	async fn test_manifest_reload_depth() {
+		let _lock = TEST_FIXTURE_LOCK.lock().await;
+		let dir = std::env::temp_dir().join("nitro_test_manifest_reload_depth");
+		let _ = std::fs::remove_dir_all(&dir);
+		let paths = Paths::from_dirs(dir.join("config"), dir.join("data"));
+		let client = reqwest::Client::new();
+		let registry = VersionRegistry::new();
+
+		let load = async |version: &str, depth: UpdateDepth| {
+			let mut fixtures = FixtureSet::new();
+			fixtures.add(
+				MANIFEST_URL,
+				200,
+				format!(
+					r#"{{
+						"latest": {{ "release": "{version}", "snapshot": "{version}" }},
+						"versions": [{{ "id": "{version}", "type": "release", "url": "" }}]
+					}}"#
+				),
+			);
+			let _guard = fixtures.install();
+
+			let params = LoadVersionManifestParameters {
+				requested_version: None,
+				paths: &paths,
+				update_manager: &UpdateManager::new(depth),
+				req_client: &client,
+			};
+			registry
+				.load_version_manifest(params, &mut NoOp)
+				.await
+				.unwrap()
+		};
+
+		assert_eq!(load("1.20", UpdateDepth::Shallow).await.list, vec!["1.20"]);
+		assert_eq!(load("1.21", UpdateDepth::Shallow).await.list, vec!["1.20"]);
+		assert_eq!(load("1.21", UpdateDepth::Full).await.list, vec!["1.21"]);
+		assert_eq!(load("1.22", UpdateDepth::Full).await.list, vec!["1.21"]);
+
+		let _ = std::fs::remove_dir_all(dir);
+	}
+}
@@ -0,0 +1,33 @@
+//! End-to-end tests that install and launch real game instances. These need network access
+//! and Java, so they are only built with the `testing` feature
+
+use std::time::Duration;
+
+use nitro_core::testing::HeadlessServerHarness;
+use nitro_core::util::versions::MinecraftVersion;
+use nitro_shared::output::NoOp;
+
+/// The version of the server to test
+const TEST_VERSION: &str = "1.20.1";
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_vanilla_server_launch() {
+	let dir = std::env::temp_dir().join("nitro_test_headless_server");
+	let _ = std::fs::remove_dir_all(dir.join("server"));
+
+	let mut harness = HeadlessServerHarness::new(dir).unwrap();
+	// Avoid conflicting with anything else running on the default port
+	harness.set_property("server-port", "25585");
+
+	let version = MinecraftVersion::Version(TEST_VERSION.into());
+	let mut instance = harness.install(&version, &mut NoOp).await.unwrap();
+
+	let mut server = harness.launch(&mut instance, &mut NoOp).await.unwrap();
+	server
+		.wait_until_ready(Duration::from_secs(300))
+		.await
+		.unwrap();
+
+	let status = server.stop(Duration::from_secs(60)).await.unwrap();
+	assert!(status.success());
+}
@@ -0,0 +1,384 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, bail};
+use nitrolaunch::config::Config;
+use nitrolaunch::config::modifications::ConfigModification;
+use nitrolaunch::config_crate::account::{AccountConfig, AccountVariant};
+use nitrolaunch::core::account::AccountKind;
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::net_crate::load_from_uri;
+use nitrolaunch::plugin_crate::hook::hooks::{
+	AccountTypeInfo, AddAccountTypes, AddSkinRepositories, SearchSkinRepository,
+	SearchSkinRepositoryArg, SkinRepository,
+};
+use nitrolaunch::shared::minecraft::{Cape, Skin, SkinVariant};
+use nitrolaunch::shared::output::NitroOutput;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::config::modify_config;
+
+/// Info about an account, for displaying in account lists
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AccountInfo {
+	/// The ID of the account
+	pub id: String,
+	/// The type of the account
+	pub r#type: AccountType,
+	/// The username of the account, if it has been logged in before
+	pub username: Option<String>,
+	/// The UUID of the account, if it has been logged in before
+	pub uuid: Option<String>,
+}
+
+/// Simplified account type for frontends
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountType {
+	/// A Microsoft account
+	Microsoft,
+	/// A demo account
+	Demo,
+	/// An offline account
+	Offline,
+	/// An account on an authlib-injector authentication server
+	AuthlibInjector,
+	/// An account type from a plugin
+	Other,
+}
+
+/// Gets info about all accounts in the config, by their IDs.
+///
+/// This authenticates offline to load cached account info, so it won't prompt the user to log in
+pub async fn get_accounts(
+	config: &mut Config,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> HashMap<String, AccountInfo> {
+	let account_ids: Vec<_> = config
+		.accounts
+		.iter_accounts()
+		.map(|x| x.0.clone())
+		.collect();
+
+	let mut accounts = HashMap::with_capacity(account_ids.len());
+	config.accounts.set_offline(true);
+	for id in account_ids {
+		let _ = config
+			.accounts
+			.authenticate_account(&id, &paths.core, client, o)
+			.await;
+
+		let Some(account) = config.accounts.get_account(&id) else {
+			continue;
+		};
+
+		let ty = match account.get_kind() {
+			AccountKind::Microsoft { .. } => AccountType::Microsoft,
+			AccountKind::Demo => AccountType::Demo,
+			AccountKind::Offline { .. } => AccountType::Offline,
+			AccountKind::AuthlibInjector { .. } => AccountType::AuthlibInjector,
+			AccountKind::Unknown(..) => AccountType::Other,
+		};
+
+		let info = AccountInfo {
+			id: id.to_string(),
+			r#type: ty,
+			username: account.get_name().cloned(),
+			uuid: account.get_uuid().cloned(),
+		};
+
+		accounts.insert(id.to_string(), info);
+	}
+	config.accounts.set_offline(false);
+
+	accounts
+}
+
+/// Logs in to an account, prompting the user if its login has expired
+pub async fn login_account(
+	config: &mut Config,
+	account: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	config
+		.accounts
+		.authenticate_account(account, &paths.core, client, o)
+		.await?;
+
+	Ok(())
+}
+
+/// Logs out of an account, removing its stored login
+pub fn logout_account(config: &mut Config, account: &str, paths: &Paths) -> anyhow::Result<()> {
+	let Some(account) = config.accounts.get_account_mut(account) else {
+		bail!("Account does not exist");
+	};
+
+	account.logout(&paths.core)
+}
+
+/// Adds a new account to the config
+pub async fn create_account(
+	id: &str,
+	kind: AccountVariant,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let modifications = vec![ConfigModification::AddAccount(
+		id.into(),
+		AccountConfig::Simple(kind),
+	)];
+	modify_config(modifications, paths, o).await
+}
+
+/// Logs out of an account and removes it from the config
+pub async fn remove_account(
+	config: &mut Config,
+	account: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	logout_account(config, account, paths)?;
+
+	let modifications = vec![ConfigModification::RemoveAccount(account.into())];
+	modify_config(modifications, paths, o).await
+}
+
+/// Sets how many minutes an account stays unlocked without being used. None disables auto-locking
+pub async fn set_account_auto_lock(
+	account: &str,
+	minutes: Option<u64>,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let modifications = vec![ConfigModification::SetAccountAutoLock(
+		account.into(),
+		minutes,
+	)];
+	modify_config(modifications, paths, o).await
+}
+
+/// Gets the account types that plugins add
+pub async fn get_supported_account_types(
+	config: &Config,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<AccountTypeInfo>> {
+	let results = config
+		.plugins
+		.call_hook(AddAccountTypes, &(), paths, o)
+		.await
+		.context("Failed to get new account types from plugins")?;
+
+	results.flatten_all_results(o).await
+}
+
+/// Gets the skins and capes of an account
+pub async fn get_cosmetics(
+	config: &mut Config,
+	account: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<(Vec<Skin>, Vec<Cape>)> {
+	config
+		.accounts
+		.get_account_cosmetics(account, &paths.core, client, o)
+		.await
+		.context("Failed to get cosmetics")
+}
+
+/// Uploads a skin from a file path or URL to an account
+pub async fn upload_skin(
+	config: &mut Config,
+	account: &str,
+	skin_uri: &str,
+	variant: SkinVariant,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let skin = load_from_uri(skin_uri, client)
+		.await
+		.context("Failed to load skin")?;
+
+	config
+		.accounts
+		.upload_skin(account, variant, &skin, &paths.core, client, o)
+		.await
+		.context("Failed to upload skin")
+}
+
+/// Activates a cape on an account, or hides the current cape if none is given
+pub async fn activate_cape(
+	config: &mut Config,
+	account: &str,
+	cape: Option<&str>,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	config
+		.accounts
+		.activate_cape(account, cape, &paths.core, client, o)
+		.await
+		.context("Failed to activate cape")
+}
+
+/// Gets the skin repositories that plugins add
+pub async fn get_skin_repositories(
+	config: &Config,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<SkinRepository>> {
+	let results = config
+		.plugins
+		.call_hook(AddSkinRepositories, &(), paths, o)
+		.await?;
+
+	results.flatten_all_results(o).await
+}
+
+/// Searches a skin repository from a plugin
+pub async fn search_skins(
+	config: &Config,
+	repository: String,
+	search: Option<String>,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<Skin>> {
+	let arg = SearchSkinRepositoryArg { repository, search };
+	let results = config
+		.plugins
+		.call_hook(SearchSkinRepository, &arg, paths, o)
+		.await?;
+
+	results.flatten_all_results(o).await
+}
+
+/// Passkeys that the user has entered for accounts, so that they don't have to be entered again
+/// on every launch. A passkey is forgotten, locking its account, once it hasn't been used for the
+/// auto-lock timeout of the account
+#[derive(Debug, Default)]
+pub struct PasskeyCache {
+	passkeys: HashMap<String, CachedPasskey>,
+}
+
+/// A passkey in the cache
+#[derive(Debug)]
+struct CachedPasskey {
+	passkey: String,
+	last_used: Instant,
+	auto_lock: Option<Duration>,
+}
+
+impl CachedPasskey {
+	fn is_expired(&self, now: Instant) -> bool {
+		self.auto_lock
+			.is_some_and(|timeout| now.duration_since(self.last_used) >= timeout)
+	}
+}
+
+impl PasskeyCache {
+	/// Creates an empty cache
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Gets the passkey for an account if it is unlocked, counting this as a use of the passkey
+	pub fn get(&mut self, account: &str) -> Option<String> {
+		self.remove_expired();
+		let cached = self.passkeys.get_mut(account)?;
+		cached.last_used = Instant::now();
+		Some(cached.passkey.clone())
+	}
+
+	/// Remembers the passkey for an account, with the auto-lock timeout of the account
+	pub fn insert(&mut self, account: &str, passkey: String, auto_lock: Option<Duration>) {
+		self.passkeys.insert(
+			account.to_string(),
+			CachedPasskey {
+				passkey,
+				last_used: Instant::now(),
+				auto_lock,
+			},
+		);
+	}
+
+	/// Locks an account, forgetting its passkey. Returns whether the account was unlocked
+	pub fn lock(&mut self, account: &str) -> bool {
+		self.passkeys.remove(account).is_some()
+	}
+
+	/// Locks every account
+	pub fn lock_all(&mut self) {
+		self.passkeys.clear();
+	}
+
+	/// Locks accounts that haven't had their passkey used within their auto-lock timeout.
+	/// Returns the accounts that were locked
+	pub fn remove_expired(&mut self) -> Vec<String> {
+		let now = Instant::now();
+		let expired: Vec<_> = self
+			.passkeys
+			.iter()
+			.filter(|(_, cached)| cached.is_expired(now))
+			.map(|(account, _)| account.clone())
+			.collect();
+		for account in &expired {
+			self.passkeys.remove(account);
+		}
+
+		expired
+	}
+
+	/// Gets the accounts that are currently unlocked
+	pub fn get_unlocked(&self) -> Vec<String> {
+		let now = Instant::now();
+		self.passkeys
+			.iter()
+			.filter(|(_, cached)| !cached.is_expired(now))
+			.map(|(account, _)| account.clone())
+			.collect()
+	}
+}
+
+/// Gets the configured auto-lock timeout of an account
+pub fn get_auto_lock_timeout(paths: &Paths, account: &str) -> anyhow::Result<Option<Duration>> {
+	let config = Config::open(&Config::get_path(paths))?;
+	let timeout = config
+		.accounts
+		.get(account)
+		.and_then(|x| x.auto_lock())
+		.map(|x| Duration::from_secs(x * 60));
+
+	Ok(timeout)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_passkey_cache() {
+		let mut cache = PasskeyCache::new();
+		cache.insert("alice", "1234".into(), Some(Duration::ZERO));
+		cache.insert("bob", "5678".into(), None);
+		cache.insert("carol", "abcd".into(), Some(Duration::from_secs(3600)));
+
+		assert_eq!(cache.remove_expired(), vec!["alice".to_string()]);
+		assert_eq!(cache.get("alice"), None);
+		assert_eq!(cache.get("bob").as_deref(), Some("5678"));
+
+		assert!(cache.lock("carol"));
+		assert!(!cache.lock("carol"));
+		assert_eq!(cache.get_unlocked(), vec!["bob".to_string()]);
+
+		cache.lock_all();
+		assert!(cache.get_unlocked().is_empty());
+	}
+}
@@ -0,0 +1,84 @@
+use anyhow::{Context, bail};
+use nitrolaunch::config::Config;
+use nitrolaunch::config::modifications::ConfigModification;
+use nitrolaunch::instance::advisories::{InstanceAdvisory, apply_mitigation};
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::shared::id::InstanceID;
+use nitrolaunch::shared::output::NitroOutput;
+use nitrolaunch::shared::versions::VersionInfo;
+use reqwest::Client;
+
+use crate::config::modify_config;
+use crate::version::get_minecraft_versions;
+
+/// Gets the known security advisories that affect an instance. Instances that haven't been
+/// created yet have no advisories, as their versions aren't known
+pub async fn get_instance_advisories(
+	config: &Config,
+	paths: &Paths,
+	instance: &str,
+	client: &Client,
+) -> anyhow::Result<Vec<InstanceAdvisory>> {
+	let Some(instance) = config.instances.get(&InstanceID::from(instance)) else {
+		bail!("Instance {instance} does not exist");
+	};
+
+	let lock = instance.get_lockfile(paths)?;
+	let Some(version) = lock.get_minecraft_version() else {
+		return Ok(Vec::new());
+	};
+
+	let versions = get_minecraft_versions(config, false, paths, client)
+		.await
+		.context("Failed to get Minecraft versions")?;
+	let version_info = VersionInfo {
+		version: version.clone(),
+		versions,
+	};
+
+	instance
+		.get_advisories(
+			&version_info,
+			lock.get_loader_version().map(|x| x.as_str()),
+			paths,
+			client,
+		)
+		.await
+}
+
+/// Applies the mitigation for an advisory to the configuration of an instance
+pub async fn mitigate_advisory(
+	config: &Config,
+	paths: &Paths,
+	instance: &str,
+	advisory_id: &str,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let advisories = get_instance_advisories(config, paths, instance, client).await?;
+	let Some(advisory) = advisories
+		.into_iter()
+		.find(|x| x.advisory.id == advisory_id)
+	else {
+		bail!("Instance {instance} is not affected by advisory {advisory_id}");
+	};
+	let Some(mitigation) = advisory.mitigation else {
+		bail!("There is no known mitigation for advisory {advisory_id}");
+	};
+	if advisory.is_mitigated {
+		return Ok(());
+	}
+
+	let Some(instance) = config.instances.get(&InstanceID::from(instance)) else {
+		bail!("Instance {instance} does not exist");
+	};
+
+	let mut instance_config = instance.original_config().clone();
+	apply_mitigation(&mut instance_config, &mitigation);
+
+	let modifications = vec![ConfigModification::UpdateInstance(
+		instance.id().clone(),
+		instance_config,
+	)];
+	modify_config(modifications, paths, o).await
+}
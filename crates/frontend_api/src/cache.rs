@@ -0,0 +1,33 @@
+use anyhow::Context;
+use nitrolaunch::config::Config;
+use nitrolaunch::config::modifications::ConfigModification;
+use nitrolaunch::io::cache::{self, CacheEntry, CacheUsage};
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::shared::output::NitroOutput;
+
+use crate::config::modify_config;
+
+/// Gets how much space cached downloads are using, along with the configured limit
+pub fn get_cache_usage(config: &Config, paths: &Paths) -> anyhow::Result<CacheUsage> {
+	cache::get_cache_usage(config, paths).context("Failed to get cache usage")
+}
+
+/// Sets the limit on the size of cached downloads in megabytes, or removes it
+pub async fn set_cache_limit(
+	limit: Option<u64>,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	modify_config(vec![ConfigModification::SetCacheLimit(limit)], paths, o)
+		.await
+		.context("Failed to set cache limit")
+}
+
+/// Removes the least recently used cached downloads until the cache is under the configured limit
+pub fn enforce_cache_limit(
+	config: &Config,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<CacheEntry>> {
+	cache::enforce_cache_limit(config, paths, o).context("Failed to enforce cache limit")
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use nitrolaunch::config::Config;
+use nitrolaunch::config::modifications::{ConfigModification, apply_modifications_and_write};
+use nitrolaunch::core::auth_crate::mc::ClientId;
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::plugin::PluginManager;
+use nitrolaunch::plugin_crate::hook::wasm::loader::WASMLoader;
+use nitrolaunch::shared::output::{NitroOutput, NoOp};
+use tokio::sync::Mutex;
+
+/// Loads the config along with all plugins.
+///
+/// A WASM loader can be given to share compiled WASM plugins between loads
+pub async fn load_config(
+	paths: &Paths,
+	wasm_loader: Option<&Arc<Mutex<WASMLoader>>>,
+	ms_client_id: ClientId,
+	show_warnings: bool,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Config> {
+	let plugins = PluginManager::load(paths, o)
+		.await
+		.context("Failed to load plugin manager")?;
+
+	if let Some(wasm_loader) = wasm_loader {
+		plugins.set_wasm_loader(wasm_loader.clone()).await;
+	}
+
+	Config::load(
+		&Config::get_path(paths),
+		plugins,
+		show_warnings,
+		paths,
+		ms_client_id,
+		o,
+	)
+	.await
+	.context("Failed to load config")
+}
+
+/// Opens the raw config, applies modifications to it, and writes it back
+pub async fn modify_config(
+	modifications: Vec<ConfigModification>,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let mut config = Config::open(&Config::get_path(paths)).context("Failed to load config")?;
+
+	let plugins = PluginManager::load(paths, &mut NoOp)
+		.await
+		.context("Failed to load plugin manager")?;
+
+	apply_modifications_and_write(&mut config, modifications, paths, &plugins, o)
+		.await
+		.context("Failed to modify and write config")
+}
@@ -0,0 +1,230 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, bail};
+use image::{ImageFormat, ImageReader};
+use nitrolaunch::core::io::files::create_leading_dirs;
+use nitrolaunch::io::cache::CacheKind;
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::net_crate::download;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The largest image that will be downloaded to the cache, in bytes
+const MAX_IMAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Where an image comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageSource {
+	/// An image on the internet
+	Remote(String),
+	/// An image file on this computer
+	Local(PathBuf),
+}
+
+impl ImageSource {
+	/// Parses a source from either a URL or a path, which is how frontends usually have them
+	pub fn parse(src: &str) -> Self {
+		if src.starts_with("https://") || src.starts_with("http://") {
+			Self::Remote(src.to_string())
+		} else {
+			Self::Local(PathBuf::from(src))
+		}
+	}
+}
+
+/// The resolution of an image that a frontend would prefer, so that large images aren't
+/// decoded just to be shown as a small icon
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageResolution {
+	/// Small icons in lists
+	Small,
+	/// Larger icons and avatars
+	Medium,
+	/// Banners and other wide images
+	Large,
+	/// The original image
+	#[default]
+	Full,
+}
+
+impl ImageResolution {
+	/// Parses a resolution from its name
+	pub fn parse(resolution: &str) -> Option<Self> {
+		match resolution {
+			"small" => Some(Self::Small),
+			"medium" => Some(Self::Medium),
+			"large" => Some(Self::Large),
+			"full" => Some(Self::Full),
+			_ => None,
+		}
+	}
+
+	/// The largest width or height of images at this resolution, if they are resized at all
+	fn max_dimension(&self) -> Option<u32> {
+		match self {
+			Self::Small => Some(64),
+			Self::Medium => Some(256),
+			Self::Large => Some(1024),
+			Self::Full => None,
+		}
+	}
+
+	fn name(&self) -> &'static str {
+		match self {
+			Self::Small => "small",
+			Self::Medium => "medium",
+			Self::Large => "large",
+			Self::Full => "full",
+		}
+	}
+}
+
+/// Gets an image from the cache at a resolution, downloading and resizing it first if it isn't
+/// cached yet. Returns the path to the cached file. Cached images count towards the cache limit
+pub async fn get_cached_image(
+	source: &ImageSource,
+	resolution: ImageResolution,
+	paths: &Paths,
+	client: &Client,
+) -> anyhow::Result<PathBuf> {
+	let path = get_cached_image_path(source, resolution, paths);
+	if path.exists() {
+		mark_used(&path);
+		return Ok(path);
+	}
+
+	let bytes = match source {
+		ImageSource::Remote(url) => {
+			let bytes = download::bytes(url, client)
+				.await
+				.context("Failed to download image")?;
+			if bytes.len() > MAX_IMAGE_SIZE {
+				bail!("Image is too large to cache");
+			}
+			bytes.to_vec()
+		}
+		ImageSource::Local(local) => tokio::fs::read(local)
+			.await
+			.context("Failed to read image")?,
+	};
+
+	let bytes = tokio::task::spawn_blocking(move || resize(bytes, resolution))
+		.await
+		.context("Failed to join resize task")??;
+
+	// Write to a temporary file first so that a partially written image is never served
+	create_leading_dirs(&path)?;
+	let temp_path = path.with_extension("tmp");
+	tokio::fs::write(&temp_path, bytes)
+		.await
+		.context("Failed to write image to cache")?;
+	tokio::fs::rename(&temp_path, &path)
+		.await
+		.context("Failed to move image into cache")?;
+
+	Ok(path)
+}
+
+/// Gets the path where an image is cached. The extension is kept so that frontends know what type of image it is.
+/// Local images are keyed by their modification time as well so that changes to them show up
+pub fn get_cached_image_path(
+	source: &ImageSource,
+	resolution: ImageResolution,
+	paths: &Paths,
+) -> PathBuf {
+	let (key, extension) = match source {
+		ImageSource::Remote(url) => {
+			let extension = url
+				.split(['?', '#'])
+				.next()
+				.and_then(|x| x.rsplit_once('.'))
+				.map(|x| x.1.to_string());
+			(url.clone(), extension)
+		}
+		ImageSource::Local(path) => {
+			let modified = std::fs::metadata(path)
+				.and_then(|x| x.modified())
+				.ok()
+				.and_then(|x| x.duration_since(UNIX_EPOCH).ok())
+				.map(|x| x.as_secs())
+				.unwrap_or_default();
+			let extension = path.extension().map(|x| x.to_string_lossy().to_string());
+			(format!("{}@{modified}", path.to_string_lossy()), extension)
+		}
+	};
+
+	let hash = hex::encode(Sha256::digest(key.as_bytes()));
+	let extension = extension
+		.filter(|x| x.len() <= 4 && x.chars().all(|c| c.is_ascii_alphanumeric()))
+		.map(|x| x.to_ascii_lowercase());
+	// Resized images are re-encoded as PNGs, except for GIFs which would lose their animation
+	let extension = match (resolution, extension) {
+		(ImageResolution::Full, extension) => extension,
+		(_, Some(extension)) if extension == "gif" => Some(extension),
+		_ => Some("png".into()),
+	};
+	let filename = match extension {
+		Some(extension) => format!("{hash}-{}.{extension}", resolution.name()),
+		None => format!("{hash}-{}", resolution.name()),
+	};
+
+	CacheKind::Images.get_dir(paths).join(filename)
+}
+
+/// Shrinks an image to fit a resolution and re-encodes it as a PNG. GIFs are left alone so that they stay animated,
+/// but anything that isn't an image at all is rejected
+fn resize(bytes: Vec<u8>, resolution: ImageResolution) -> anyhow::Result<Vec<u8>> {
+	let format = image::guess_format(&bytes).context("File is not a supported image")?;
+	let Some(max_dimension) = resolution.max_dimension() else {
+		return Ok(bytes);
+	};
+	if format == ImageFormat::Gif {
+		return Ok(bytes);
+	}
+
+	let image = ImageReader::with_format(std::io::Cursor::new(&bytes), format)
+		.decode()
+		.context("Failed to decode image")?;
+	if image.width() <= max_dimension && image.height() <= max_dimension {
+		return reencode(&image);
+	}
+
+	reencode(&image.thumbnail(max_dimension, max_dimension))
+}
+
+/// Encodes an image as a PNG
+fn reencode(image: &image::DynamicImage) -> anyhow::Result<Vec<u8>> {
+	let mut out = std::io::Cursor::new(Vec::new());
+	image
+		.write_to(&mut out, ImageFormat::Png)
+		.context("Failed to encode image")?;
+	Ok(out.into_inner())
+}
+
+/// Updates the modification time of a cached image so that it isn't evicted before less recently used files
+fn mark_used(path: &Path) {
+	if let Ok(file) = File::options().write(true).open(path) {
+		let _ = file.set_modified(SystemTime::now());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_image_source_parsing() {
+		assert_eq!(
+			ImageSource::parse("https://example.com/icon.png"),
+			ImageSource::Remote("https://example.com/icon.png".into())
+		);
+		assert_eq!(
+			ImageSource::parse("/home/user/icon.png"),
+			ImageSource::Local(PathBuf::from("/home/user/icon.png"))
+		);
+	}
+}
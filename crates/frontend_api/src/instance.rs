@@ -0,0 +1,799 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, bail};
+use itertools::Itertools;
+use nitrolaunch::config::Config;
+use nitrolaunch::config::modifications::ConfigModification;
+use nitrolaunch::config_crate::instance::InstanceConfig;
+use nitrolaunch::config_crate::template::TemplateConfig;
+use nitrolaunch::core::auth_crate::mc::ClientId;
+use nitrolaunch::core::io::files::create_leading_dirs;
+use nitrolaunch::core::io::json_to_file_atomic;
+use nitrolaunch::core::io::update::FileVerifier;
+use nitrolaunch::core::util::versions::MinecraftVersion;
+use nitrolaunch::instance::Instance;
+use nitrolaunch::instance::inventory::InstanceInventory;
+use nitrolaunch::instance::outdated::OutdatedPackage;
+use nitrolaunch::instance::update::manager::UpdateSettings;
+use nitrolaunch::instance::update::{InstanceUpdateContext, UpdateFacets};
+use nitrolaunch::instance::verify::VerifyResult;
+use nitrolaunch::io::cache::enforce_cache_limit;
+use nitrolaunch::io::lock::Lockfile;
+use nitrolaunch::io::notifications::{NotificationKind, notify};
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::io::prune::prune_if_scheduled;
+use nitrolaunch::pkg::reg::PkgRegistry;
+use nitrolaunch::pkg_crate::{PkgRequest, PkgRequestSource};
+use nitrolaunch::plugin_crate::hook::hooks::{DeleteTemplate, SaveTemplateConfigArg};
+use nitrolaunch::shared::id::{InstanceID, TemplateID};
+use nitrolaunch::shared::java_args::MemoryNum;
+use nitrolaunch::shared::loaders::Loader;
+use nitrolaunch::shared::output::{NitroOutput, NoOp};
+use nitrolaunch::shared::versions::{MinecraftVersionDeser, parse_versioned_string};
+use nitrolaunch::shared::{Side, UpdateDepth};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::config::modify_config;
+use crate::image_cache::{self, ImageResolution, ImageSource};
+use crate::version::get_minecraft_versions;
+
+/// Simple info about an instance or template, for displaying in lists
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InstanceInfo {
+	/// The ID of the instance or template
+	pub id: String,
+	/// The display name
+	pub name: Option<String>,
+	/// The side of the instance
+	pub side: Option<Side>,
+	/// The icon of the instance
+	pub icon: Option<String>,
+	/// Whether the instance is pinned. Pinning is handled by the frontend, so this is always false here
+	pub pinned: bool,
+	/// Whether the instance was added by a plugin
+	pub from_plugin: bool,
+	/// The Minecraft version
+	pub version: Option<String>,
+	/// The loader
+	pub loader: Loader,
+	/// Whether the config can be edited
+	pub is_editable: bool,
+	/// Whether the instance or template can be deleted
+	pub is_deletable: bool,
+}
+
+/// Gets info about all instances, sorted by ID
+pub fn get_instances(config: &Config) -> Vec<InstanceInfo> {
+	config
+		.instances
+		.iter()
+		.sorted_by_key(|x| x.0)
+		.map(|(id, instance)| {
+			let config = instance.original_config();
+			InstanceInfo {
+				id: id.to_string(),
+				icon: instance.config().icon.clone(),
+				pinned: false,
+				name: instance.config().name.clone(),
+				side: Some(instance.side()),
+				from_plugin: config.source_plugin.is_some(),
+				version: Some(instance.version().to_string()),
+				loader: instance.loader().clone(),
+				is_editable: config.is_editable || config.source_plugin.is_none(),
+				is_deletable: config.is_deletable || config.source_plugin.is_none(),
+			}
+		})
+		.collect()
+}
+
+/// Gets info about all templates, sorted by ID
+pub fn get_templates(config: &Config) -> Vec<InstanceInfo> {
+	config
+		.templates
+		.iter()
+		.sorted_by_key(|x| x.0)
+		.map(|(id, template)| InstanceInfo {
+			id: id.to_string(),
+			icon: template.instance.icon.clone(),
+			pinned: false,
+			name: template.instance.name.clone(),
+			side: template.instance.side,
+			from_plugin: template.instance.source_plugin.is_some(),
+			version: template
+				.instance
+				.version
+				.as_ref()
+				.map(|x| MinecraftVersion::from_deser(x).to_string()),
+			loader: template
+				.instance
+				.loader
+				.as_ref()
+				.map(|x| Loader::parse_from_str(parse_versioned_string(x).0))
+				.unwrap_or_default(),
+			is_editable: template.instance.is_editable || template.instance.source_plugin.is_none(),
+			is_deletable: template.instance.is_deletable
+				|| template.instance.source_plugin.is_none(),
+		})
+		.collect()
+}
+
+/// Whether something is an instance or a template
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceOrTemplate {
+	/// An instance
+	Instance,
+	/// A template
+	Template,
+}
+
+/// An instance group and the instances in it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstanceGroupInfo {
+	/// The ID of the group
+	pub id: String,
+	/// The IDs of the instances in the group
+	pub contents: Vec<String>,
+}
+
+/// Gets all instance groups, sorted by ID
+pub fn get_instance_groups(config: &Config) -> Vec<InstanceGroupInfo> {
+	config
+		.instance_groups
+		.iter()
+		.sorted_by_key(|x| x.0)
+		.map(|(id, instances)| InstanceGroupInfo {
+			id: id.to_string(),
+			contents: instances.iter().map(ToString::to_string).collect(),
+		})
+		.collect()
+}
+
+/// An instance config as the user wrote it, along with its plugin fields
+#[derive(Serialize, Debug, Clone)]
+pub struct InstanceConfigAndPluginFields {
+	/// The config
+	pub config: InstanceConfig,
+	/// Config fields added by plugins
+	pub plugin_config: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Gets the config of an instance before templates are applied, for editing
+pub fn get_editable_instance_config(
+	config: &Config,
+	id: &str,
+) -> Option<InstanceConfigAndPluginFields> {
+	let instance = config.instances.get(&InstanceID::from(id))?;
+
+	Some(InstanceConfigAndPluginFields {
+		config: instance.original_config().clone(),
+		plugin_config: instance.original_config().plugin_config.clone(),
+	})
+}
+
+/// A template config as the user wrote it, along with its plugin fields
+#[derive(Serialize, Clone)]
+pub struct TemplateConfigAndPluginFields {
+	/// The config
+	pub config: TemplateConfig,
+	/// Config fields added by plugins
+	pub plugin_config: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Gets the config of a template before its parents are applied, for editing
+pub fn get_editable_template_config(
+	config: &Config,
+	id: &str,
+) -> Option<TemplateConfigAndPluginFields> {
+	let template = config.templates.get(&TemplateID::from(id))?;
+
+	Some(TemplateConfigAndPluginFields {
+		config: template.clone(),
+		plugin_config: template.instance.plugin_config.clone(),
+	})
+}
+
+/// Gets the base template that all instances and templates inherit from
+pub fn get_base_template(config: &Config) -> TemplateConfigAndPluginFields {
+	TemplateConfigAndPluginFields {
+		config: config.base_template.clone(),
+		plugin_config: config.base_template.instance.plugin_config.clone(),
+	}
+}
+
+/// Gets the config of an instance, if it exists
+pub fn get_instance_config(config: &Config, id: &str) -> Option<InstanceConfig> {
+	config
+		.instances
+		.get(&InstanceID::from(id))
+		.map(|x| x.config().clone())
+}
+
+/// Gets the consolidated config of a template, if it exists
+pub fn get_template_config(config: &Config, id: &str) -> Option<TemplateConfig> {
+	config
+		.consolidated_templates
+		.get(&TemplateID::from(id))
+		.cloned()
+}
+
+/// Writes the config of an instance, creating the instance if it doesn't exist
+pub async fn write_instance_config(
+	id: String,
+	instance_config: InstanceConfig,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let modifications = vec![ConfigModification::UpdateInstance(
+		id.into(),
+		instance_config,
+	)];
+	modify_config(modifications, paths, o).await
+}
+
+/// Writes the config of a template, creating the template if it doesn't exist
+pub async fn write_template_config(
+	id: String,
+	template_config: TemplateConfig,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let modifications = vec![ConfigModification::UpdateTemplate(
+		id.into(),
+		template_config,
+	)];
+	modify_config(modifications, paths, o).await
+}
+
+/// Writes the base template that all instances and templates derive from
+pub fn write_base_template(template_config: TemplateConfig, paths: &Paths) -> anyhow::Result<()> {
+	let mut config = Config::open(&Config::get_path(paths)).context("Failed to load config")?;
+
+	config.base_template = Some(template_config);
+	json_to_file_atomic(Config::get_path(paths), &config)
+		.context("Failed to write modified configuration")
+}
+
+/// Deletes a template, either from the config or through the plugin that provides it
+pub async fn delete_template(
+	config: &Config,
+	template_id: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let Some(template) = config.templates.get(template_id) else {
+		bail!("Template does not exist");
+	};
+
+	if let Some(source_plugin) = &template.instance.source_plugin {
+		if !template.instance.is_deletable {
+			bail!("Plugin template does not support deletion");
+		}
+
+		let arg = SaveTemplateConfigArg {
+			id: template_id.to_string(),
+			config: template.clone(),
+		};
+
+		let result = config
+			.plugins
+			.call_hook_on_plugin(DeleteTemplate, source_plugin, &arg, paths, o)
+			.await?;
+		if let Some(result) = result {
+			result.result(o).await?;
+		}
+	} else {
+		let modifications = vec![ConfigModification::RemoveTemplate(template_id.into())];
+		modify_config(modifications, paths, o).await?;
+	}
+
+	Ok(())
+}
+
+/// Gets a list of the instances and templates that derive a specific template
+pub fn get_template_users(
+	paths: &Paths,
+	template: &str,
+) -> anyhow::Result<Vec<(Arc<str>, InstanceOrTemplate)>> {
+	let config = Config::open(&Config::get_path(paths)).context("Failed to load config")?;
+
+	let template = template.to_string();
+
+	let mut out = Vec::new();
+
+	for (id, config) in &config.instances {
+		if config.from.contains(&template) {
+			out.push((id.clone(), InstanceOrTemplate::Instance));
+		}
+	}
+
+	for (id, config) in &config.templates {
+		if config.instance.from.contains(&template) {
+			out.push((id.clone(), InstanceOrTemplate::Template));
+		}
+	}
+
+	Ok(out)
+}
+
+/// Gets the Minecraft version of an instance or template from the lockfile, used for `latest` and `latest_snapshot`.
+/// If it is not in the lockfile, or is a template, returns the proper latest version
+pub async fn canonicalize_version(
+	config: &mut Config,
+	version: MinecraftVersionDeser,
+	id: Option<&str>,
+	instance_or_template: InstanceOrTemplate,
+	paths: &Paths,
+	client: &Client,
+) -> anyhow::Result<String> {
+	if let MinecraftVersionDeser::Version(version) = &version {
+		return Ok(version.to_string());
+	}
+
+	if let Some(id) = id
+		&& instance_or_template == InstanceOrTemplate::Instance
+	{
+		let Some(instance) = config.instances.get_mut(id) else {
+			bail!("Instance does not exist");
+		};
+
+		let inst_lock = instance.get_lockfile(paths)?;
+
+		if let Some(version) = inst_lock.get_minecraft_version() {
+			return Ok(version.clone());
+		}
+	}
+
+	// Get the latest version
+	let core = config
+		.get_core(
+			None,
+			&UpdateSettings {
+				depth: UpdateDepth::Shallow,
+				offline_auth: true,
+			},
+			client,
+			&config.plugins,
+			paths,
+			&mut NoOp,
+		)
+		.await?;
+
+	let manifest = core
+		.get_version_manifest(None, UpdateDepth::Shallow, &mut NoOp)
+		.await?;
+	let version = MinecraftVersion::from_deser(&version)
+		.get_version(&manifest.manifest)
+		.context("No versions are available in this channel")?;
+
+	Ok(version.to_string())
+}
+
+/// A plugin ID along with its display name
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct PluginAndName {
+	/// The ID of the plugin
+	pub id: String,
+	/// The display name of the plugin
+	pub name: Option<String>,
+}
+
+/// Gets the plugins supporting creation of custom instances or templates
+pub async fn get_plugins_supporting_creation(
+	config: &Config,
+	instance_or_template: InstanceOrTemplate,
+) -> Vec<PluginAndName> {
+	config
+		.plugins
+		.get_lock()
+		.await
+		.manager
+		.iter_plugins()
+		.filter(|x| match instance_or_template {
+			InstanceOrTemplate::Instance => x.manifest.supports_instance_creation,
+			InstanceOrTemplate::Template => x.manifest.supports_template_creation,
+		})
+		.map(|x| PluginAndName {
+			id: x.get_id().clone(),
+			name: x.manifest.meta.name.clone(),
+		})
+		.collect()
+}
+
+/// Gets the size of an instance's files, formatted for display
+pub async fn get_instance_size(config: &Config, instance: &str) -> anyhow::Result<String> {
+	let Some(instance) = config.instances.get(&InstanceID::from(instance)) else {
+		bail!("Instance {instance} does not exist");
+	};
+
+	let size = instance.get_size().await?;
+	let size = MemoryNum::from_bytes(size).nicefy();
+
+	Ok(size.to_string())
+}
+
+/// Gets an inventory of everything that is installed on an instance
+pub fn get_instance_inventory(
+	config: &Config,
+	paths: &Paths,
+	instance: &str,
+) -> anyhow::Result<InstanceInventory> {
+	let Some(instance) = config.instances.get(&InstanceID::from(instance)) else {
+		bail!("Instance {instance} does not exist");
+	};
+
+	instance.get_inventory(paths)
+}
+
+/// Gets the packages on an instance that have newer versions available
+pub async fn get_outdated_packages(
+	config: &Config,
+	paths: &Paths,
+	instance: &str,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<OutdatedPackage>> {
+	let Some(instance) = config.instances.get(&InstanceID::from(instance)) else {
+		bail!("Instance {instance} does not exist");
+	};
+
+	let version_list = get_minecraft_versions(config, false, paths, client)
+		.await
+		.context("Failed to get Minecraft versions")?;
+
+	let outdated = instance
+		.get_outdated_packages(&version_list, &config.packages, paths, client, o)
+		.await?;
+
+	if !outdated.is_empty() {
+		let id = instance.id();
+		let message = format!(
+			"{} packages on instance {id} can be updated",
+			outdated.len()
+		);
+		let _ = notify(
+			paths,
+			NotificationKind::PackageUpdates,
+			"Package updates available",
+			message,
+			Some(id.as_ref()),
+		);
+	}
+
+	Ok(outdated)
+}
+
+/// Updates an instance at the given depth
+#[allow(clippy::too_many_arguments)]
+pub async fn update_instance(
+	config: &mut Config,
+	instance_id: &str,
+	depth: UpdateDepth,
+	facets: UpdateFacets,
+	ms_client_id: ClientId,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let mut lock = Lockfile::open(paths).context("Failed to open lockfile")?;
+
+	let core = config
+		.get_core(
+			Some(&ms_client_id),
+			&UpdateSettings {
+				depth,
+				offline_auth: false,
+			},
+			client,
+			&config.plugins,
+			paths,
+			o,
+		)
+		.await?;
+
+	let Some(instance) = config.instances.get_mut(&InstanceID::from(instance_id)) else {
+		bail!("Instance does not exist");
+	};
+
+	let mut ctx = InstanceUpdateContext {
+		packages: &mut config.packages,
+		accounts: &mut config.accounts,
+		plugins: &config.plugins,
+		prefs: &config.prefs,
+		paths,
+		lock: &mut lock,
+		client,
+		output: o,
+		core: &core,
+	};
+
+	instance
+		.update(depth, facets, &mut ctx)
+		.await
+		.context("Failed to update instance")?;
+
+	prune_if_scheduled(config, &mut lock, paths, o).context("Failed to prune unused game files")?;
+	enforce_cache_limit(config, paths, o).context("Failed to enforce cache limit")?;
+	lock.finish(paths)
+		.context("Failed to finish using lockfile")
+}
+
+/// Verifies the files of an instance, redownloading any that are corrupted
+pub async fn verify_instance(
+	config: &mut Config,
+	instance_id: &str,
+	ms_client_id: ClientId,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<VerifyResult> {
+	let mut lock = Lockfile::open(paths).context("Failed to open lockfile")?;
+
+	let mut core = config
+		.get_core(
+			Some(&ms_client_id),
+			&UpdateSettings {
+				depth: UpdateDepth::Shallow,
+				offline_auth: false,
+			},
+			client,
+			&config.plugins,
+			paths,
+			o,
+		)
+		.await?;
+	core.set_file_verifier(FileVerifier::new());
+
+	let Some(instance) = config.instances.get_mut(&InstanceID::from(instance_id)) else {
+		bail!("Instance does not exist");
+	};
+
+	let mut ctx = InstanceUpdateContext {
+		packages: &mut config.packages,
+		accounts: &mut config.accounts,
+		plugins: &config.plugins,
+		prefs: &config.prefs,
+		paths,
+		lock: &mut lock,
+		client,
+		output: o,
+		core: &core,
+	};
+
+	let result = instance
+		.verify(&mut ctx)
+		.await
+		.context("Failed to verify instance")?;
+
+	lock.finish(paths)
+		.context("Failed to finish using lockfile")?;
+
+	Ok(result)
+}
+
+/// Gets the package whose icon should be used for an instance. This is the modpack if the instance
+/// has one, and otherwise its first package
+pub fn get_icon_package(config: &InstanceConfig) -> Option<String> {
+	if let Some(modpack) = &config.modpack {
+		return Some(modpack.clone());
+	}
+
+	config.packages.first().map(|x| x.get_pkg_id().to_string())
+}
+
+/// Gets the icon of an instance's modpack or primary package and stores a copy of it with the
+/// launcher's data, so that it doesn't disappear when the image cache is cleaned.
+/// Returns the path to the stored icon, or None if there is no package with an icon
+pub async fn download_package_icon(
+	instance_id: &str,
+	config: &InstanceConfig,
+	packages: &PkgRegistry,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Option<PathBuf>> {
+	let Some(package) = get_icon_package(config) else {
+		return Ok(None);
+	};
+
+	let req = Arc::new(PkgRequest::parse(package, PkgRequestSource::UserRequire));
+	let package = packages
+		.get(&req, paths, client, o)
+		.await
+		.context("Failed to get package")?;
+	let meta = package
+		.get_metadata(paths, client)
+		.await
+		.context("Failed to get package metadata")?;
+	let Some(icon) = &meta.icon else {
+		return Ok(None);
+	};
+
+	let cached = image_cache::get_cached_image(
+		&ImageSource::parse(icon),
+		ImageResolution::Medium,
+		paths,
+		client,
+	)
+	.await
+	.context("Failed to download package icon")?;
+
+	let mut path = paths.data.join("instance_icons").join(instance_id);
+	if let Some(extension) = cached.extension() {
+		path.set_extension(extension);
+	}
+	create_leading_dirs(&path)?;
+	tokio::fs::copy(&cached, &path)
+		.await
+		.context("Failed to copy icon")?;
+
+	Ok(Some(path))
+}
+
+/// Sets the icon of an existing instance to the icon of its modpack or primary package again,
+/// for when the package has changed. Returns the path to the new icon if there was one
+pub async fn sync_instance_icon(
+	config: &Config,
+	instance_id: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Option<PathBuf>> {
+	let Some(instance) = config.instances.get(&InstanceID::from(instance_id)) else {
+		bail!("Instance {instance_id} does not exist");
+	};
+
+	let Some(icon) = download_package_icon(
+		instance_id,
+		instance.original_config(),
+		&config.packages,
+		paths,
+		client,
+		o,
+	)
+	.await?
+	else {
+		return Ok(None);
+	};
+
+	let modifications = vec![ConfigModification::SetInstanceIcon(
+		instance_id.into(),
+		Some(icon.to_string_lossy().to_string()),
+	)];
+	modify_config(modifications, paths, o).await?;
+
+	Ok(Some(icon))
+}
+
+/// Gets an instance from the config, failing if it does not exist
+fn get_instance<'config>(config: &'config Config, id: &str) -> anyhow::Result<&'config Instance> {
+	config
+		.instances
+		.get(&InstanceID::from(id))
+		.context("Instance does not exist")
+}
+
+/// Deletes an instance, moving its files to the trash unless the deletion is permanent
+pub async fn delete_instance(
+	config: &Config,
+	instance: &str,
+	permanent: bool,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	get_instance(config, instance)?
+		.delete(paths, &config.plugins, permanent, o)
+		.await
+}
+
+/// Consolidates the templates of an instance into its config
+pub async fn consolidate_instance(
+	config: &Config,
+	instance: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	get_instance(config, instance)?
+		.consolidate(paths, &config.plugins, o)
+		.await
+}
+
+/// Duplicates an instance to create a new one
+pub async fn duplicate_instance(
+	config: &Config,
+	instance: &str,
+	new_id: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	get_instance(config, instance)?
+		.duplicate(&new_id.into(), paths, &config.plugins, o)
+		.await
+}
+
+/// Extracts a template from the config of an instance
+pub async fn extract_instance(
+	config: &Config,
+	instance: &str,
+	new_id: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	get_instance(config, instance)?
+		.extract(&new_id.into(), paths, &config.plugins, o)
+		.await
+}
+
+/// Saves the setup of an instance as a new template, leaving the instance as it is
+pub async fn save_instance_as_template(
+	config: &Config,
+	instance: &str,
+	new_id: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	get_instance(config, instance)?
+		.save_as_template(&new_id.into(), paths, &config.plugins, o)
+		.await
+}
+
+/// Creates a server instance that matches a client instance and links the two together
+pub async fn create_matching_server(
+	config: &Config,
+	instance: &str,
+	new_id: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	get_instance(config, instance)?
+		.create_matching_server(&new_id.into(), paths, &config.plugins, o)
+		.await
+}
+
+/// Copies the shared configuration of an instance to the instance it is linked to
+pub async fn sync_linked_instance(
+	config: &Config,
+	instance: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let instance = get_instance(config, instance)?;
+	let Some(linked) = instance
+		.linked_instance()
+		.and_then(|x| config.instances.get(&x))
+	else {
+		bail!("Linked instance does not exist");
+	};
+
+	instance
+		.sync_to_linked(linked, paths, &config.plugins, o)
+		.await
+}
+
+#[cfg(test)]
+mod tests {
+	use nitrolaunch::config_crate::package::PackageConfigDeser;
+
+	use super::*;
+
+	#[test]
+	fn test_icon_package() {
+		let mut config = InstanceConfig::default();
+		assert_eq!(get_icon_package(&config), None);
+
+		config.packages = vec![
+			PackageConfigDeser::Basic("sodium".into()),
+			PackageConfigDeser::Basic("lithium".into()),
+		];
+		assert_eq!(get_icon_package(&config), Some("sodium".into()));
+
+		config.modpack = Some("fabulously-optimized".into());
+		assert_eq!(
+			get_icon_package(&config),
+			Some("fabulously-optimized".into())
+		);
+	}
+}
@@ -0,0 +1,171 @@
+use anyhow::Context;
+use nitrolaunch::config::Config;
+use nitrolaunch::core::QuickPlayType;
+use nitrolaunch::core::auth_crate::mc::ClientId;
+use nitrolaunch::core::launch::PreparedLaunch;
+use nitrolaunch::core::net::download;
+use nitrolaunch::instance::launch::{InstanceHandle, LaunchSettings};
+use nitrolaunch::instance::update::InstanceUpdateContext;
+use nitrolaunch::instance::update::manager::UpdateSettings;
+use nitrolaunch::io::lock::Lockfile;
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::shared::UpdateDepth;
+use nitrolaunch::shared::error::NitroResult;
+use nitrolaunch::shared::id::InstanceID;
+use nitrolaunch::shared::output::NitroOutput;
+use reqwest::Client;
+
+/// Options for launching an instance
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+	/// The account to launch with. Uses the default account if not set
+	pub account: Option<String>,
+	/// Whether to authenticate offline
+	pub offline: bool,
+	/// Quick play option for the game
+	pub quick_play: Option<QuickPlayType>,
+}
+
+/// Updates and launches an instance, returning the handle to the running game.
+///
+/// Authentication is done offline automatically when there is no network connection.
+/// Errors can be classified with their kind to decide whether to retry, go offline, or log in again.
+/// Waiting on the handle is left to the caller, so that it can track the game however it likes
+pub async fn launch_instance(
+	config: &mut Config,
+	instance_id: &InstanceID,
+	options: LaunchOptions,
+	ms_client_id: ClientId,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> NitroResult<InstanceHandle> {
+	let offline = options.offline || download::is_offline();
+
+	if let Some(account) = &options.account {
+		config.accounts.choose_account(account)?;
+	}
+
+	let core = config
+		.get_core(
+			Some(&ms_client_id),
+			&UpdateSettings {
+				depth: UpdateDepth::Shallow,
+				offline_auth: offline,
+			},
+			client,
+			&config.plugins,
+			paths,
+			o,
+		)
+		.await?;
+
+	let instance = config
+		.instances
+		.get_mut(instance_id)
+		.context("Instance does not exist")?;
+
+	let settings = LaunchSettings {
+		offline_auth: offline,
+		pipe_stdin: false,
+		quick_play: options.quick_play,
+	};
+
+	let mut lock = Lockfile::open(paths)?;
+	let mut ctx = InstanceUpdateContext {
+		packages: &config.packages,
+		accounts: &mut config.accounts,
+		plugins: &config.plugins,
+		prefs: &config.prefs,
+		paths,
+		lock: &mut lock,
+		client,
+		output: o,
+		core: &core,
+	};
+
+	instance
+		.launch(settings, &mut ctx)
+		.await
+		.map_err(|e| e.context("Failed to launch instance"))
+}
+
+/// Updates an instance and resolves the command line, classpath, environment, and working directory
+/// that would be used to launch it, without spawning the game or running launch hooks
+pub async fn prepare_instance_launch(
+	config: &mut Config,
+	instance_id: &InstanceID,
+	options: LaunchOptions,
+	ms_client_id: ClientId,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> NitroResult<PreparedLaunch> {
+	let offline = options.offline || download::is_offline();
+
+	if let Some(account) = &options.account {
+		config.accounts.choose_account(account)?;
+	}
+
+	let core = config
+		.get_core(
+			Some(&ms_client_id),
+			&UpdateSettings {
+				depth: UpdateDepth::Shallow,
+				offline_auth: offline,
+			},
+			client,
+			&config.plugins,
+			paths,
+			o,
+		)
+		.await?;
+
+	let instance = config
+		.instances
+		.get_mut(instance_id)
+		.context("Instance does not exist")?;
+
+	let settings = LaunchSettings {
+		offline_auth: offline,
+		pipe_stdin: false,
+		quick_play: options.quick_play,
+	};
+
+	let mut lock = Lockfile::open(paths)?;
+	let mut ctx = InstanceUpdateContext {
+		packages: &config.packages,
+		accounts: &mut config.accounts,
+		plugins: &config.plugins,
+		prefs: &config.prefs,
+		paths,
+		lock: &mut lock,
+		client,
+		output: o,
+		core: &core,
+	};
+
+	instance
+		.prepare_launch(settings, &mut ctx)
+		.await
+		.map_err(|e| e.context("Failed to prepare instance launch"))
+}
+
+/// Runs a command on a running server instance through RCON, returning the response
+pub async fn send_rcon_command(
+	config: &Config,
+	instance_id: &str,
+	command: &str,
+) -> anyhow::Result<String> {
+	let instance = config
+		.instances
+		.get(instance_id)
+		.context("Instance does not exist")?;
+
+	let mut client = instance
+		.connect_rcon()
+		.await
+		.context("Failed to connect to server")?;
+
+	client.command(command).await
+}
@@ -0,0 +1,43 @@
+//! Backend logic shared between the different Nitrolaunch frontends, such as the GUIs.
+//!
+//! Functions here are plain async functions that take the loaded config and other launcher state,
+//! so that each frontend only has to handle its own state management and event plumbing.
+
+#![warn(missing_docs)]
+
+/// Listing and authenticating accounts
+pub mod account;
+/// Checking instances for known security problems
+pub mod advisory;
+/// Cached download usage and limits
+pub mod cache;
+/// Loading and modifying the config
+pub mod config;
+/// Disk cache for icons, banners, and other images
+pub mod image_cache;
+/// Listing instances and templates
+pub mod instance;
+/// Launching instances
+pub mod launch;
+/// Instance logs and crash bundles
+pub mod logs;
+/// Periodic background maintenance tasks
+pub mod maintenance;
+/// Rendering package and plugin descriptions from Markdown for the GUI
+pub mod markdown;
+/// Download speed limits
+pub mod network;
+/// Minecraft news feed
+pub mod news;
+/// Searching and viewing packages
+pub mod package;
+/// Installing plugins and getting the things they add to the frontend
+pub mod plugin;
+/// Listing and joining Minecraft Realms
+pub mod realms;
+/// Custom GUI themes
+pub mod theme;
+/// Importing, exporting, and migrating instances
+pub mod transfer;
+/// Minecraft and loader versions
+pub mod version;
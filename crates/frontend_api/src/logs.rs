@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use anyhow::Context;
+use nitrolaunch::config::Config;
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::shared::output::NitroOutput;
+use reqwest::Client;
+
+/// Gets the IDs of the logs of an instance
+pub async fn get_instance_logs(
+	config: &mut Config,
+	instance_id: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<String>> {
+	let instance = config
+		.instances
+		.get_mut(instance_id)
+		.context("Instance does not exist")?;
+
+	instance.get_logs(&config.plugins, paths, o).await
+}
+
+/// Gets the contents of a log of an instance
+pub async fn get_instance_log(
+	config: &mut Config,
+	instance_id: &str,
+	log_id: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<String> {
+	let instance = config
+		.instances
+		.get_mut(instance_id)
+		.context("Instance does not exist")?;
+
+	instance.get_log(log_id, &config.plugins, paths, o).await
+}
+
+/// Uploads a log of an instance to the configured paste backend, returning the URL
+pub async fn upload_instance_log(
+	config: &mut Config,
+	instance_id: &str,
+	log_id: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<String> {
+	let instance = config
+		.instances
+		.get_mut(instance_id)
+		.context("Instance does not exist")?;
+
+	instance
+		.upload_log(
+			log_id,
+			config.prefs.paste_backend.as_deref(),
+			&config.plugins,
+			client,
+			paths,
+			o,
+		)
+		.await
+}
+
+/// Exports a crash bundle for an instance to the given path
+pub fn export_crash_bundle(config: &Config, instance_id: &str, path: &Path) -> anyhow::Result<()> {
+	let instance = config
+		.instances
+		.get(instance_id)
+		.context("Instance does not exist")?;
+
+	instance.export_crash_bundle(&config.accounts, path)
+}
+
+/// Uploads a crash bundle for an instance to the configured paste backend, returning the URL
+pub async fn upload_crash_bundle(
+	config: &Config,
+	instance_id: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<String> {
+	let instance = config
+		.instances
+		.get(instance_id)
+		.context("Instance does not exist")?;
+
+	instance
+		.upload_crash_bundle(
+			config.prefs.paste_backend.as_deref(),
+			&config.accounts,
+			&config.plugins,
+			client,
+			paths,
+			o,
+		)
+		.await
+}
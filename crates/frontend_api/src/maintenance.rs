@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use nitrolaunch::config::Config;
+use nitrolaunch::config_crate::preferences::MaintenanceDeser;
+use nitrolaunch::core::io::java::install::{get_end_of_life_installations, verify_installed};
+use nitrolaunch::core::io::{json_from_file, json_to_file_replace};
+use nitrolaunch::core::net::download;
+use nitrolaunch::instance::update::manager::UpdateSettings;
+use nitrolaunch::io::notifications::{NotificationKind, notify};
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::plugin::PluginManager;
+use nitrolaunch::plugin::install::get_verified_plugins;
+use nitrolaunch::shared::UpdateDepth;
+use nitrolaunch::shared::output::{MessageContents, NitroOutput};
+use nitrolaunch::shared::util::utc_timestamp;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::cache::enforce_cache_limit;
+use crate::instance::get_outdated_packages;
+
+/// A task that is run periodically in the background to keep the launcher in good shape
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceTask {
+	/// Downloads the newest version manifest
+	RefreshManifest,
+	/// Checks every instance for packages that can be updated
+	CheckPackageUpdates,
+	/// Checks installed plugins for newer versions
+	CheckPluginUpdates,
+	/// Removes cached downloads that are over the cache limit
+	CollectCache,
+	/// Checks that installed Java versions work
+	VerifyJava,
+}
+
+impl MaintenanceTask {
+	/// All of the maintenance tasks, in the order that they are run
+	pub const ALL: [Self; 5] = [
+		Self::RefreshManifest,
+		Self::CheckPackageUpdates,
+		Self::CheckPluginUpdates,
+		Self::CollectCache,
+		Self::VerifyJava,
+	];
+
+	/// Gets the interval in hours that this task runs at, or None if it is disabled
+	pub fn get_interval(&self, prefs: &MaintenanceDeser) -> Option<u64> {
+		let (configured, default) = match self {
+			Self::RefreshManifest => (prefs.refresh_manifest, 6),
+			Self::CheckPackageUpdates => (prefs.check_package_updates, 24),
+			Self::CheckPluginUpdates => (prefs.check_plugin_updates, 24),
+			Self::CollectCache => (prefs.collect_cache, 24),
+			Self::VerifyJava => (prefs.verify_java, 24 * 7),
+		};
+
+		let interval = configured.unwrap_or(default);
+		(interval > 0).then_some(interval)
+	}
+
+	/// Whether this task needs to use the network
+	pub fn is_online(&self) -> bool {
+		matches!(
+			self,
+			Self::RefreshManifest | Self::CheckPackageUpdates | Self::CheckPluginUpdates
+		)
+	}
+
+	/// Gets a readable name for this task
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::RefreshManifest => "Refreshing the version manifest",
+			Self::CheckPackageUpdates => "Checking for package updates",
+			Self::CheckPluginUpdates => "Checking for plugin updates",
+			Self::CollectCache => "Cleaning up the download cache",
+			Self::VerifyJava => "Verifying Java installations",
+		}
+	}
+}
+
+/// Persistent record of when maintenance tasks were last run
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct MaintenanceSchedule {
+	/// UTC timestamps of the last time each task was run
+	last_runs: HashMap<MaintenanceTask, u64>,
+}
+
+impl MaintenanceSchedule {
+	/// Opens the schedule
+	pub fn open(paths: &Paths) -> anyhow::Result<Self> {
+		let path = Self::get_path(paths);
+		if path.exists() {
+			json_from_file(path).context("Failed to open maintenance schedule")
+		} else {
+			Ok(Self::default())
+		}
+	}
+
+	/// Writes the schedule
+	pub fn write(&self, paths: &Paths) -> anyhow::Result<()> {
+		json_to_file_replace(Self::get_path(paths), self)
+			.context("Failed to write maintenance schedule")
+	}
+
+	/// Gets the path to the schedule file
+	fn get_path(paths: &Paths) -> PathBuf {
+		paths.internal.join("maintenance.json")
+	}
+
+	/// Gets the tasks that are due to run at the given time
+	pub fn get_due_tasks(&self, prefs: &MaintenanceDeser, now: u64) -> Vec<MaintenanceTask> {
+		MaintenanceTask::ALL
+			.into_iter()
+			.filter(|task| {
+				let Some(interval) = task.get_interval(prefs) else {
+					return false;
+				};
+				let Some(last_run) = self.last_runs.get(task) else {
+					return true;
+				};
+				now.saturating_sub(*last_run) >= interval * 60 * 60
+			})
+			.collect()
+	}
+
+	/// Records that a task was run at the given time
+	pub fn set_last_run(&mut self, task: MaintenanceTask, now: u64) {
+		self.last_runs.insert(task, now);
+	}
+}
+
+/// Runs all of the maintenance tasks that are due. Tasks that need the network are skipped while
+/// offline. Failures are saved as notifications instead of being returned
+pub async fn run_due_tasks(
+	config: &Config,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let mut schedule = MaintenanceSchedule::open(paths)?;
+	let now = utc_timestamp()?;
+
+	for task in schedule.get_due_tasks(&config.prefs.maintenance, now) {
+		if task.is_online() && download::is_offline() {
+			continue;
+		}
+
+		if let Err(e) = run_task(task, config, paths, client, o).await {
+			o.display(MessageContents::Error(format!("{e:?}")));
+			let _ = notify(
+				paths,
+				NotificationKind::TaskFailed,
+				"Maintenance failed",
+				format!("{} failed: {e}", task.name()),
+				Some(&format!("maintenance_{task:?}")),
+			);
+		}
+
+		// Failed tasks are still recorded so that they aren't retried constantly
+		schedule.set_last_run(task, now);
+		schedule.write(paths)?;
+	}
+
+	Ok(())
+}
+
+/// Runs a single maintenance task
+pub async fn run_task(
+	task: MaintenanceTask,
+	config: &Config,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	match task {
+		MaintenanceTask::RefreshManifest => {
+			let core = config
+				.get_core(
+					None,
+					&UpdateSettings {
+						depth: UpdateDepth::Full,
+						offline_auth: false,
+					},
+					client,
+					&config.plugins,
+					paths,
+					o,
+				)
+				.await?;
+			core.get_version_manifest(None, UpdateDepth::Full, o)
+				.await
+				.context("Failed to refresh version manifest")?;
+		}
+		MaintenanceTask::CheckPackageUpdates => {
+			// Instances that have updates are notified about by the check itself
+			for instance in config.instances.keys() {
+				if let Err(e) = get_outdated_packages(config, paths, instance, client, o).await {
+					o.display(MessageContents::Warning(format!(
+						"Failed to check instance {instance} for package updates: {e:?}"
+					)));
+				}
+			}
+		}
+		MaintenanceTask::CheckPluginUpdates => {
+			let outdated = get_outdated_plugins(paths, client).await?;
+			if !outdated.is_empty() {
+				let message = format!(
+					"Updates are available for the plugins {}",
+					outdated.join(", ")
+				);
+				notify(
+					paths,
+					NotificationKind::PluginUpdates,
+					"Plugin updates available",
+					message,
+					Some("plugins"),
+				)?;
+			}
+		}
+		MaintenanceTask::CollectCache => {
+			enforce_cache_limit(config, paths, o)?;
+		}
+		MaintenanceTask::VerifyJava => {
+			for (java, error) in verify_installed(&paths.core)? {
+				notify(
+					paths,
+					NotificationKind::BrokenJava,
+					"Java installation is broken",
+					format!("Java installation {java} does not work: {error}"),
+					Some(&java),
+				)?;
+			}
+
+			let now = utc_timestamp()?;
+			for java in get_end_of_life_installations(&paths.core, now)? {
+				notify(
+					paths,
+					NotificationKind::JavaEndOfLife,
+					"Java installation is out of date",
+					format!(
+						"Java installation {java} no longer gets security updates. Instances that use it should be switched to a long-term support version of Java"
+					),
+					Some(&java),
+				)?;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Gets the IDs of installed plugins that have a newer version in the verified list
+async fn get_outdated_plugins(paths: &Paths, client: &Client) -> anyhow::Result<Vec<String>> {
+	let plugins = PluginManager::open_config(paths).context("Failed to open plugins config")?;
+	let verified_list = get_verified_plugins(client, false)
+		.await
+		.context("Failed to get verified plugin list")?;
+
+	let mut out = Vec::new();
+	for plugin in plugins.plugins {
+		let Some(latest) = verified_list.get(&plugin).and_then(|x| x.version.as_ref()) else {
+			continue;
+		};
+		let Ok(manifest) = PluginManager::read_plugin_manifest(&plugin, paths) else {
+			continue;
+		};
+
+		if manifest.version.as_ref().is_some_and(|x| x != latest) {
+			out.push(plugin);
+		}
+	}
+	out.sort();
+
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_due_tasks() {
+		let prefs = MaintenanceDeser {
+			verify_java: Some(0),
+			collect_cache: Some(1),
+			..Default::default()
+		};
+		let mut schedule = MaintenanceSchedule::default();
+		let now = 100_000;
+
+		let due = schedule.get_due_tasks(&prefs, now);
+		assert!(due.contains(&MaintenanceTask::CollectCache));
+		assert!(!due.contains(&MaintenanceTask::VerifyJava));
+
+		schedule.set_last_run(MaintenanceTask::CollectCache, now);
+		assert!(
+			!schedule
+				.get_due_tasks(&prefs, now + 60)
+				.contains(&MaintenanceTask::CollectCache)
+		);
+		assert!(
+			schedule
+				.get_due_tasks(&prefs, now + 60 * 60)
+				.contains(&MaintenanceTask::CollectCache)
+		);
+	}
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use nitrolaunch::io::cache::CacheKind;
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::shared::output::{MessageContents, NitroOutput};
+use pulldown_cmark::{Options, Parser};
+use reqwest::Client;
+use tokio::task::JoinSet;
+
+use crate::image_cache::{ImageResolution, ImageSource, get_cached_image};
+
+/// The most images that will be cached for a single document. Any more are left as remote links
+const MAX_CACHED_IMAGES: usize = 32;
+
+/// Source that cached images are given while sanitizing, before being replaced with their local URLs
+const CACHED_IMAGE_PLACEHOLDER: &str = "https://cached-image.invalid/";
+
+/// Renders a package or plugin description from Markdown to HTML, removing anything that could run
+/// scripts or otherwise be unsafe to show in the GUI. Raw HTML in the Markdown, which is common
+/// in package descriptions, is kept as long as it is safe. Only the Tauri GUI shows rendered HTML;
+/// changelogs are left as Markdown since they are only shown by the CLI
+pub fn render_markdown(markdown: &str) -> String {
+	sanitize(&to_html(markdown), |_| None)
+}
+
+/// Renders Markdown like `render_markdown`, but also downloads the remote images it contains to a
+/// local cache. The `image_src` function turns the path to a cached image into a URL that the frontend can load.
+/// Images that fail to download are left as remote links
+pub async fn render_markdown_with_cached_images(
+	markdown: &str,
+	paths: &Paths,
+	client: &Client,
+	image_src: impl Fn(&Path) -> String,
+	o: &mut impl NitroOutput,
+) -> String {
+	let html = to_html(markdown);
+
+	// Find all of the images first so that they can be downloaded at once
+	let images = Arc::new(Mutex::new(Vec::new()));
+	{
+		let images = images.clone();
+		sanitize(&html, move |url| {
+			if let Ok(mut images) = images.lock()
+				&& images.len() < MAX_CACHED_IMAGES
+				&& !images.iter().any(|x| x == url)
+			{
+				images.push(url.to_string());
+			}
+			None
+		});
+	}
+	let images = std::mem::take(&mut *images.lock().expect("Lock should not be poisoned"));
+
+	let mut tasks = JoinSet::new();
+	for url in images {
+		let paths = paths.clone();
+		let client = client.clone();
+		tasks.spawn(async move {
+			let source = ImageSource::Remote(url.clone());
+			let result = get_cached_image(&source, ImageResolution::Full, &paths, &client).await;
+			(url, result)
+		});
+	}
+
+	let image_dir = CacheKind::Images.get_dir(paths);
+	let mut cached = Vec::new();
+	while let Some(task) = tasks.join_next().await {
+		let Ok((url, result)) = task else {
+			continue;
+		};
+		match result {
+			// Only files from the image cache are ever given to the frontend
+			Ok(path) if path.starts_with(&image_dir) => cached.push((url, image_src(&path))),
+			Ok(..) => {}
+			Err(e) => o.debug(MessageContents::Warning(format!(
+				"Failed to cache image {url}: {e:#}"
+			))),
+		}
+	}
+
+	let placeholders: HashMap<_, _> = cached
+		.iter()
+		.enumerate()
+		.map(|(i, (url, _))| (url.clone(), format!("{CACHED_IMAGE_PLACEHOLDER}{i}")))
+		.collect();
+	let html = sanitize(&html, move |url| placeholders.get(url).cloned());
+
+	replace_cached_images(html, &cached)
+}
+
+/// Replaces the placeholder sources of cached images in sanitized HTML with their local URLs.
+/// This happens after sanitizing so that the sanitizer never has to allow local URL schemes
+fn replace_cached_images(mut html: String, cached: &[(String, String)]) -> String {
+	for (i, (_, src)) in cached.iter().enumerate() {
+		html = html.replace(
+			&format!("src=\"{CACHED_IMAGE_PLACEHOLDER}{i}\""),
+			&format!("src=\"{}\"", escape_attribute(src)),
+		);
+	}
+
+	html
+}
+
+/// Escapes a value to be put inside of a double quoted HTML attribute
+fn escape_attribute(value: &str) -> String {
+	value
+		.replace('&', "&amp;")
+		.replace('"', "&quot;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+}
+
+/// Converts Markdown to unsanitized HTML
+fn to_html(markdown: &str) -> String {
+	let options = Options::ENABLE_TABLES
+		| Options::ENABLE_STRIKETHROUGH
+		| Options::ENABLE_TASKLISTS
+		| Options::ENABLE_FOOTNOTES;
+	let parser = Parser::new_ext(markdown, options);
+
+	let mut out = String::with_capacity(markdown.len() * 3 / 2);
+	pulldown_cmark::html::push_html(&mut out, parser);
+	out
+}
+
+/// Sanitizes HTML. The image function is given the source of every remote image and
+/// can return a replacement for it
+fn sanitize(html: &str, image: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> String {
+	ammonia::Builder::default()
+		.add_tags(["input"])
+		.add_tag_attributes("input", ["type", "checked", "disabled"])
+		.add_generic_attributes(["align"])
+		.link_rel(Some("noopener noreferrer"))
+		.attribute_filter(move |element, attribute, value| {
+			match (element, attribute) {
+				// Only task list checkboxes are allowed
+				("input", "type") if value != "checkbox" => None,
+				// Placeholders can only come from the launcher
+				("img", "src") if value.starts_with(CACHED_IMAGE_PLACEHOLDER) => None,
+				("img", "src") if value.starts_with("https://") || value.starts_with("http://") => {
+					Some(image(value).map(Into::into).unwrap_or(value.into()))
+				}
+				_ => Some(value.into()),
+			}
+		})
+		.clean(html)
+		.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_sanitize_markdown() {
+		let html = render_markdown(
+			"# Title\n<script>alert(1)</script>\n\n<p align=\"center\"><img src=\"https://example.com/a.png\" onerror=\"alert(1)\"></p>\n\n- [x] Done\n\n[link](javascript:alert(1))",
+		);
+		assert!(html.contains("<h1>Title</h1>"));
+		assert!(!html.contains("script"));
+		assert!(!html.contains("onerror"));
+		assert!(!html.contains("javascript"));
+		assert!(html.contains("<p align=\"center\"><img src=\"https://example.com/a.png\"></p>"));
+		assert!(html.contains("type=\"checkbox\""));
+	}
+
+	#[test]
+	fn test_cached_image_replacement() {
+		let html = sanitize(
+			"<img src=\"https://example.com/a.png\"><img src=\"https://cached-image.invalid/0\"><img src=\"asset://localhost/a.png\">",
+			|url| {
+				(url == "https://example.com/a.png").then(|| format!("{CACHED_IMAGE_PLACEHOLDER}0"))
+			},
+		);
+		let html = replace_cached_images(
+			html,
+			&[(
+				"https://example.com/a.png".into(),
+				"asset://localhost/%2Fimages%2Fa.png".into(),
+			)],
+		);
+		assert_eq!(
+			html,
+			"<img src=\"asset://localhost/%2Fimages%2Fa.png\"><img><img>"
+		);
+	}
+}
@@ -0,0 +1,23 @@
+use anyhow::Context;
+use nitrolaunch::config::modifications::ConfigModification;
+use nitrolaunch::core::net::activity;
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::shared::output::NitroOutput;
+
+use crate::config::modify_config;
+
+/// Sets the limit on the total download speed in bytes per second, or removes it. The limit is
+/// saved in the preferences and applied to downloads that are already running
+pub async fn set_bandwidth_limit(
+	limit: Option<u64>,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let limit = limit.filter(|x| *x > 0);
+	modify_config(vec![ConfigModification::SetBandwidthLimit(limit)], paths, o)
+		.await
+		.context("Failed to set bandwidth limit")?;
+	activity::set_bandwidth_limit(limit);
+
+	Ok(())
+}
@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use nitrolaunch::{
+	core::io::{files::create_leading_dirs, json_from_file, json_to_file_replace},
+	io::paths::Paths,
+	net_crate::mojang::{self, PatchNotes},
+	shared::util::utc_timestamp,
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::version::get_banner_images;
+
+/// How long cached news is used before it is fetched again, in seconds
+const NEWS_CACHE_MAX_AGE: u64 = 60 * 60;
+/// The maximum number of news entries to show
+const MAX_NEWS_ENTRIES: usize = 12;
+
+/// A single entry in the news feed
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NewsEntry {
+	/// Unique ID for the entry
+	pub id: String,
+	/// The title of the article
+	pub title: String,
+	/// The Minecraft version the entry is about
+	pub version: String,
+	/// The type of the version, such as release or snapshot
+	pub version_type: String,
+	/// The date of the article
+	pub date: String,
+	/// A short summary of the article
+	pub summary: String,
+	/// URL to the image for the article
+	pub image: String,
+	/// Panorama images from the game assets, if the version is installed
+	pub banner_images: Option<(String, String)>,
+}
+
+/// Gets the latest Minecraft release and snapshot news, using a cache when possible.
+///
+/// Falls back to old cached news if new news can't be fetched
+pub async fn get_minecraft_news(
+	paths: &Paths,
+	client: &Client,
+	refresh: bool,
+) -> anyhow::Result<Vec<NewsEntry>> {
+	let cache_path = get_cache_path(paths);
+	let cached: Option<NewsCache> = json_from_file(&cache_path).ok();
+
+	let is_fresh = cached.as_ref().is_some_and(|cached| {
+		utc_timestamp().is_ok_and(|now| now.saturating_sub(cached.timestamp) < NEWS_CACHE_MAX_AGE)
+	});
+
+	let notes = match cached {
+		Some(cached) if is_fresh && !refresh => cached.notes,
+		cached => match mojang::get_patch_notes(client).await {
+			Ok(notes) => {
+				if let Err(e) = write_cache(&cache_path, &notes) {
+					eprintln!("Failed to write news cache: {e:?}");
+				}
+				notes
+			}
+			// Fall back to old news if we can't fetch it
+			Err(e) => match cached {
+				Some(cached) => cached.notes,
+				None => return Err(e.context("Failed to get news")),
+			},
+		},
+	};
+
+	let out = notes
+		.entries
+		.into_iter()
+		.take(MAX_NEWS_ENTRIES)
+		.map(|entry| NewsEntry {
+			banner_images: get_banner_images(paths, &entry.version).ok().flatten(),
+			image: entry.image.get_url(),
+			id: entry.id,
+			title: entry.title,
+			version: entry.version,
+			version_type: entry.version_type,
+			date: entry.date,
+			summary: entry.short_text,
+		})
+		.collect();
+
+	Ok(out)
+}
+
+fn write_cache(path: &Path, notes: &PatchNotes) -> anyhow::Result<()> {
+	create_leading_dirs(path)?;
+	let cache = NewsCache {
+		timestamp: utc_timestamp()?,
+		notes: notes.clone(),
+	};
+	json_to_file_replace(path, &cache).context("Failed to write cache")
+}
+
+fn get_cache_path(paths: &Paths) -> PathBuf {
+	paths.internal.join("news/patch_notes.json")
+}
+
+/// Cached news on disk
+#[derive(Serialize, Deserialize)]
+struct NewsCache {
+	timestamp: u64,
+	notes: PatchNotes,
+}
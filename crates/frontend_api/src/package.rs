@@ -0,0 +1,372 @@
+use std::collections::HashMap;
+use std::ops::DerefMut;
+use std::sync::Arc;
+
+use anyhow::{Context, bail};
+use nitrolaunch::config::Config;
+use nitrolaunch::instance_crate::lock::LockfilePackage;
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::pkg::Package;
+use nitrolaunch::pkg_crate::compat::CompatibilityMatrix;
+use nitrolaunch::pkg_crate::declarative::DeclarativePackage;
+use nitrolaunch::pkg_crate::details::{AuthorProfile, PackageDetails};
+use nitrolaunch::pkg_crate::metadata::PackageMetadata;
+use nitrolaunch::pkg_crate::properties::PackageProperties;
+use nitrolaunch::pkg_crate::repo::RepoMetadata;
+use nitrolaunch::pkg_crate::{
+	AggregatedSearchResults, PackageSearchResults, PkgRequest, PkgRequestSource,
+};
+use nitrolaunch::shared::output::{MessageContents, NitroOutput, NoOp};
+use nitrolaunch::shared::pkg::PackageSearchParameters;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinSet;
+
+/// The number of packages shown on each page of search results
+pub const PACKAGES_PER_PAGE: u8 = 12;
+
+/// Searches for packages in a single repository
+pub async fn search_packages(
+	config: &Config,
+	repo: &str,
+	params: PackageSearchParameters,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<PackageSearchResults> {
+	config
+		.packages
+		.search(params, Some(repo), paths, client, o)
+		.await
+		.context("Failed to get list of available packages")
+}
+
+/// Searches for packages in all repositories at once
+pub async fn search_all_packages(
+	config: &Config,
+	params: PackageSearchParameters,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<AggregatedSearchResults> {
+	config
+		.packages
+		.search_all(params, paths, client, o)
+		.await
+		.context("Failed to search package repositories")
+}
+
+/// Loads packages ahead of time so that they are quicker to view later, either from a single repository or from all of them
+pub async fn preload_packages(
+	config: &Config,
+	packages: Vec<String>,
+	repo: Option<&str>,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let packages = packages
+		.into_iter()
+		.map(|x| Arc::new(PkgRequest::parse(x, PkgRequestSource::UserRequire)))
+		.collect();
+
+	if let Some(repo) = repo {
+		let Some(repo) = config.packages.repos.iter().find(|x| x.get_id() == repo) else {
+			bail!("Repository does not exist");
+		};
+
+		repo.preload(packages, paths, &config.plugins, o)
+			.await
+			.context("Failed to preload packages from repository")
+	} else {
+		config
+			.packages
+			.preload_packages(packages.iter(), paths, client, o)
+			.await
+			.context("Failed to preload packages from repositories")
+	}
+}
+
+/// Gets a package from the registry
+async fn get_package(
+	config: &Config,
+	package: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Arc<Package>> {
+	let req = Arc::new(PkgRequest::parse(package, PkgRequestSource::UserRequire));
+
+	config.packages.get(&req, paths, client, o).await
+}
+
+/// Gets the metadata of a package
+pub async fn get_package_meta(
+	config: &Config,
+	package: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Arc<PackageMetadata>> {
+	let package = get_package(config, package, paths, client, o).await?;
+
+	package
+		.get_metadata(paths, client)
+		.await
+		.context("Failed to get metadata")
+}
+
+/// Gets the properties of a package
+pub async fn get_package_props(
+	config: &Config,
+	package: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Arc<PackageProperties>> {
+	let package = get_package(config, package, paths, client, o).await?;
+
+	package
+		.get_properties(paths, client)
+		.await
+		.context("Failed to get properties")
+}
+
+/// Gets both the metadata and properties of a package
+pub async fn get_package_meta_and_props(
+	config: &Config,
+	package: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<(Arc<PackageMetadata>, Arc<PackageProperties>)> {
+	let package = get_package(config, package, paths, client, o).await?;
+
+	let meta = package
+		.get_metadata(paths, client)
+		.await
+		.context("Failed to get metadata")?;
+	let props = package
+		.get_properties(paths, client)
+		.await
+		.context("Failed to get properties")?;
+
+	Ok((meta, props))
+}
+
+/// The metadata and properties of one of multiple packages, or the error from getting them
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MultiPackageMetaAndPropsResult {
+	/// The package could not be loaded
+	Error(String),
+	/// The metadata and properties of the package
+	MetaAndProps(Arc<PackageMetadata>, Arc<PackageProperties>),
+}
+
+/// Gets the metadata and properties of multiple packages at once, by package. Packages that fail
+/// to load have their error returned instead of failing the whole operation
+pub async fn get_multiple_package_meta_and_props(
+	config: &Config,
+	packages: Vec<&str>,
+	paths: &Paths,
+	client: &Client,
+	o: &impl NitroOutput,
+) -> anyhow::Result<HashMap<String, MultiPackageMetaAndPropsResult>> {
+	let paths = Arc::new(paths.clone());
+
+	let mut tasks = JoinSet::new();
+	for pkg in packages {
+		let req = Arc::new(PkgRequest::parse(pkg, PkgRequestSource::UserRequire));
+
+		let pkg = pkg.to_string();
+		let reg = config.packages.clone();
+		let paths = paths.clone();
+		let client = client.clone();
+		let mut o = o.get_greater_copy();
+		tasks.spawn(async move {
+			let result = async {
+				let package = reg.get(&req, &paths, &client, &mut o).await?;
+				let meta = package.get_metadata(&paths, &client).await?;
+				let props = package.get_properties(&paths, &client).await?;
+
+				Ok::<_, anyhow::Error>((meta, props))
+			}
+			.await;
+
+			let result = match result {
+				Ok((meta, props)) => MultiPackageMetaAndPropsResult::MetaAndProps(meta, props),
+				Err(e) => MultiPackageMetaAndPropsResult::Error(format!("{e:?}")),
+			};
+
+			(pkg, result)
+		});
+	}
+
+	let mut out = HashMap::new();
+	while let Some(task) = tasks.join_next().await {
+		let (pkg, result) = task?;
+		out.insert(pkg, result);
+	}
+
+	Ok(out)
+}
+
+/// Gets the details of a package that are shown on its page
+pub async fn get_package_details(
+	config: &Config,
+	package: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<PackageDetails> {
+	let req = Arc::new(PkgRequest::parse(package, PkgRequestSource::UserRequire));
+
+	config
+		.packages
+		.get_details(&req, paths, client, o)
+		.await
+		.context("Failed to get package details")
+}
+
+/// Gets the profile of a package author from a repository
+pub async fn get_package_author(
+	config: &Config,
+	repository: &str,
+	author: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Option<AuthorProfile>> {
+	config
+		.packages
+		.get_author(repository, author, paths, o)
+		.await
+		.context("Failed to get author")
+}
+
+/// Gets the contents of a package if it is declarative
+pub async fn get_declarative_package_contents(
+	config: &Config,
+	package: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Option<DeclarativePackage>> {
+	let package = get_package(config, package, paths, client, o).await?;
+
+	let contents = package
+		.get_declarative_contents(paths, client)
+		.await
+		.context("Failed to get declarative contents")?;
+
+	Ok(contents.cloned())
+}
+
+/// Gets the Minecraft versions and loaders that a package supports
+pub async fn get_package_compatibility(
+	config: &Config,
+	package: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Option<CompatibilityMatrix>> {
+	let package = get_package(config, package, paths, client, o).await?;
+
+	package
+		.get_compatibility_matrix(paths, client)
+		.await
+		.context("Failed to get compatibility matrix")
+}
+
+/// A package repository along with its metadata
+#[derive(Serialize, Deserialize)]
+pub struct RepoInfo {
+	/// The ID of the repository
+	pub id: String,
+	/// The metadata of the repository
+	pub meta: RepoMetadata,
+}
+
+/// Gets all of the package repositories
+pub async fn get_package_repos(
+	config: &Config,
+	paths: &Paths,
+	client: &Client,
+) -> anyhow::Result<Vec<RepoInfo>> {
+	let mut repos = Vec::new();
+	for repo in &config.packages.repos {
+		let id = repo.get_id().to_string();
+		let meta = repo
+			.get_metadata(paths, client, &mut NoOp)
+			.await
+			.context("Failed to get metadata for repository")?;
+		repos.push(RepoInfo {
+			id,
+			meta: meta.into_owned(),
+		})
+	}
+
+	Ok(repos)
+}
+
+/// Gets the packages installed on an instance from the lockfile, by package ID
+pub fn get_instance_packages(
+	config: &mut Config,
+	instance: &str,
+	paths: &Paths,
+) -> anyhow::Result<HashMap<String, LockfilePackage>> {
+	let Some(instance) = config.instances.get_mut(instance) else {
+		return Ok(HashMap::new());
+	};
+
+	let lock = instance.get_lockfile(paths)?;
+
+	Ok(lock.get_packages().clone())
+}
+
+/// Syncs every package repository and updates the cached standard packages.
+/// Repositories that fail to sync are reported without stopping the others
+pub async fn sync_packages(
+	config: &Config,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	for repo in config.packages.repos.iter() {
+		o.display(MessageContents::StartProcess(format!(
+			"Syncing repository {}",
+			repo.get_id()
+		)));
+		let mut process = o.get_process();
+		match repo
+			.sync(paths, &config.plugins, client, process.deref_mut())
+			.await
+		{
+			Ok(..) => {
+				process.display(MessageContents::Success(format!(
+					"Synced repository {}",
+					repo.get_id()
+				)));
+			}
+			Err(e) => {
+				process.display(MessageContents::Error(format!(
+					"Failed to sync repository {}: {e}",
+					repo.get_id()
+				)));
+			}
+		};
+	}
+
+	let mut process = o.get_process();
+	process.display(MessageContents::StartProcess(
+		"Updating standard packages".into(),
+	));
+	config
+		.packages
+		.update_cached_packages(paths, client, process.deref_mut())
+		.await
+		.context("Failed to update cached packages")?;
+	process.display(MessageContents::Success("Packages updated".into()));
+
+	Ok(())
+}
@@ -0,0 +1,415 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, bail};
+use itertools::Itertools;
+use nitrolaunch::config::Config;
+use nitrolaunch::config_crate::ConfigKind;
+use nitrolaunch::core::io::json_to_file_atomic;
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::plugin::PluginManager;
+use nitrolaunch::plugin::install::{get_verified_plugins, is_official_install};
+use nitrolaunch::plugin_crate::control::Control;
+use nitrolaunch::plugin_crate::hook::hooks::{
+	AddDropdownButtons, AddInstanceConfigControls, AddInstanceConfigControlsArg, AddInstanceTiles,
+	AddPluginConfigControls, AddSidebarButtons, AddThemes, CustomAction, CustomActionArg,
+	DropdownButton, DropdownButtonLocation, GetPage, InjectPageScript, InjectPageScriptArg,
+	InstanceTile, SidebarButton, Theme,
+};
+use nitrolaunch::plugin_crate::plugin::PluginMetadata;
+use nitrolaunch::shared::output::{NitroOutput, NoOp};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::theme::get_theme_css;
+
+/// Plugins that are installed when the user chooses to install the defaults
+pub const DEFAULT_PLUGINS: [&str; 7] = [
+	"fabric_quilt",
+	"modrinth",
+	"smithed",
+	"stats",
+	"docs",
+	"multimc_transfer",
+	"xmcl_transfer",
+];
+
+/// Info about a plugin, for displaying in plugin lists
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PluginInfo {
+	/// The ID of the plugin
+	pub id: String,
+	/// The version of the plugin
+	pub version: Option<String>,
+	/// Display metadata for the plugin
+	#[serde(flatten)]
+	pub meta: PluginMetadata,
+	/// Whether the plugin is enabled
+	pub enabled: bool,
+	/// Whether the plugin is installed
+	pub installed: bool,
+	/// Whether this is an official Nitrolaunch plugin
+	pub is_official: bool,
+}
+
+/// Gets info about all installed plugins, sorted by ID
+pub fn get_local_plugins(paths: &Paths) -> anyhow::Result<Vec<PluginInfo>> {
+	let config = PluginManager::open_config(paths).context("Failed to open plugin config")?;
+
+	let plugins =
+		PluginManager::get_available_plugins(paths).context("Failed to get available plugins")?;
+
+	let plugins = plugins.into_iter().filter_map(|x| {
+		let id = x.0;
+		let manifest = PluginManager::read_plugin_manifest(&id, paths).ok()?;
+
+		Some(PluginInfo {
+			enabled: config.plugins.contains(&id),
+			id,
+			version: manifest.version,
+			meta: manifest.meta,
+			installed: true,
+			is_official: false,
+		})
+	});
+
+	Ok(plugins.sorted_by_cached_key(|x| x.id.clone()).collect())
+}
+
+/// Gets info about all plugins that can be installed, sorted by ID
+pub async fn get_remote_plugins(client: &Client, offline: bool) -> anyhow::Result<Vec<PluginInfo>> {
+	let verified_plugins = get_verified_plugins(client, offline)
+		.await
+		.context("Failed to get verified plugins")?;
+
+	let verified_plugins = verified_plugins.into_values().map(|x| PluginInfo {
+		id: x.id,
+		meta: x.meta,
+		version: x.version,
+		enabled: false,
+		installed: false,
+		is_official: x.github_owner == "Nitrolaunch",
+	});
+
+	Ok(verified_plugins
+		.sorted_by_cached_key(|x| x.id.clone())
+		.collect())
+}
+
+/// Enables or disables a plugin
+pub fn set_plugin_enabled(plugin: &str, enabled: bool, paths: &Paths) -> anyhow::Result<()> {
+	if enabled {
+		PluginManager::enable_plugin(plugin, paths).context("Failed to enable plugin")
+	} else {
+		PluginManager::disable_plugin(plugin, paths).context("Failed to disable plugin")
+	}
+}
+
+/// Installs a plugin from the verified list, using the latest version if none is given
+pub async fn install_plugin(
+	plugin: &str,
+	version: Option<&str>,
+	paths: &Paths,
+	client: &Client,
+) -> anyhow::Result<()> {
+	let verified_list = get_verified_plugins(client, false)
+		.await
+		.context("Failed to get verified plugin list")?;
+
+	let Some(plugin) = verified_list.get(plugin) else {
+		bail!("Unknown plugin '{plugin}'");
+	};
+
+	plugin
+		.install(version, paths, client, &mut NoOp)
+		.await
+		.context("Failed to install plugin")
+}
+
+/// Gets the versions of a plugin from the verified list that can be installed
+pub async fn get_plugin_versions(plugin: &str, client: &Client) -> anyhow::Result<Vec<String>> {
+	let verified_list = get_verified_plugins(client, true)
+		.await
+		.context("Failed to get verified plugin list")?;
+
+	let Some(plugin) = verified_list.get(plugin) else {
+		bail!("Unknown plugin '{plugin}'");
+	};
+
+	let assets = plugin
+		.get_candidate_assets(None, client)
+		.await
+		.context("Failed to get plugin versions")?;
+
+	Ok(assets.into_iter().map(|x| x.version).unique().collect())
+}
+
+/// Installs and enables the default plugins
+pub async fn install_default_plugins(paths: &Paths, client: &Client) -> anyhow::Result<()> {
+	let verified_list = get_verified_plugins(client, false)
+		.await
+		.context("Failed to get verified plugin list")?;
+
+	for plugin in DEFAULT_PLUGINS {
+		let Some(plugin) = verified_list.get(plugin) else {
+			bail!("Unknown plugin '{plugin}'");
+		};
+
+		plugin
+			.install(None, paths, client, &mut NoOp)
+			.await
+			.with_context(|| format!("Failed to install plugin {}", plugin.id))?;
+
+		let _ = PluginManager::enable_plugin(&plugin.id, paths);
+	}
+
+	Ok(())
+}
+
+/// Gets the scripts that plugins inject into a page, joined together
+pub async fn get_page_inject_script(
+	config: &Config,
+	page: String,
+	object: Option<String>,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<String> {
+	let arg = InjectPageScriptArg { page, object };
+	let mut results = config
+		.plugins
+		.call_hook(InjectPageScript, &arg, paths, o)
+		.await?;
+
+	let mut out = String::new();
+	while let Some(result) = results.next_result(o).await? {
+		out.push_str(&result);
+		out.push('\n');
+	}
+
+	Ok(out)
+}
+
+/// Gets the sidebar buttons that plugins add
+pub async fn get_sidebar_buttons(
+	config: &Config,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<SidebarButton>> {
+	let results = config
+		.plugins
+		.call_hook(AddSidebarButtons, &(), paths, o)
+		.await?;
+
+	results.flatten_all_results(o).await
+}
+
+/// A custom page provided by a plugin
+#[derive(Serialize)]
+pub struct PluginPage {
+	/// The plugin that provided the page
+	pub plugin: String,
+	/// The HTML contents of the page
+	pub html: String,
+	/// Whether the page should be rendered in a sandbox
+	pub sandboxed: bool,
+}
+
+/// Gets a custom page from the first plugin that provides it
+pub async fn get_plugin_page(
+	config: &Config,
+	page: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Option<PluginPage>> {
+	let mut results = config
+		.plugins
+		.call_hook(GetPage, &page.to_string(), paths, o)
+		.await?;
+
+	while let Some(handle) = results.next() {
+		let plugin = handle.get_id().clone();
+		if let Some(html) = handle.result(o).await? {
+			// Pages are only given full access to the launcher if they ask for it and the plugin
+			// was downloaded from an official repo. The ID alone can't be trusted, as any plugin
+			// installed from a file can use it
+			let trusted = PluginManager::read_plugin_manifest(&plugin, paths)
+				.is_ok_and(|x| x.trusted_pages)
+				&& is_official_install(&plugin, paths);
+			let sandboxed = !trusted;
+
+			return Ok(Some(PluginPage {
+				plugin,
+				html,
+				sandboxed,
+			}));
+		}
+	}
+
+	Ok(None)
+}
+
+/// Gets the themes from plugins along with custom themes, with their CSS filled in
+pub async fn get_themes(
+	config: &Config,
+	custom_themes: impl IntoIterator<Item = Theme>,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<Theme>> {
+	let results = config.plugins.call_hook(AddThemes, &(), paths, o).await?;
+
+	let mut out = results.flatten_all_results(o).await?;
+	out.extend(custom_themes);
+	for theme in &mut out {
+		theme.css = get_theme_css(theme);
+	}
+
+	Ok(out)
+}
+
+/// Runs a custom action on a plugin and returns its result
+pub async fn call_custom_action(
+	config: &Config,
+	plugin: &str,
+	action: String,
+	payload: serde_json::Value,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<serde_json::Value> {
+	let result = config
+		.plugins
+		.call_hook_on_plugin(
+			CustomAction,
+			plugin,
+			&CustomActionArg {
+				id: action,
+				payload,
+			},
+			paths,
+			o,
+		)
+		.await
+		.context("Failed to call custom action")?;
+
+	let Some(result) = result else {
+		bail!("Plugin did not return a result");
+	};
+
+	result.result(o).await
+}
+
+/// Runs a custom action from a sandboxed plugin page. Only actions that the plugin
+/// has declared in its manifest can be called
+pub async fn call_plugin_page_action(
+	config: &Config,
+	plugin: &str,
+	action: String,
+	payload: serde_json::Value,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<serde_json::Value> {
+	let manifest = PluginManager::read_plugin_manifest(plugin, paths)
+		.context("Failed to read plugin manifest")?;
+	if !manifest.page_actions.contains(&action) {
+		bail!("Plugin '{plugin}' does not allow its pages to run the action '{action}'");
+	}
+
+	call_custom_action(config, plugin, action, payload, paths, o).await
+}
+
+/// Gets the dropdown buttons that plugins add to a location
+pub async fn get_dropdown_buttons(
+	config: &Config,
+	location: DropdownButtonLocation,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<DropdownButton>> {
+	let mut results = config
+		.plugins
+		.call_hook(AddDropdownButtons, &(), paths, o)
+		.await?;
+
+	let mut out = Vec::new();
+	while let Some(result) = results.next_result(o).await? {
+		out.extend(result.into_iter().filter(|x| x.location == location));
+	}
+
+	Ok(out)
+}
+
+/// Gets the tiles that plugins add to an instance's page
+pub async fn get_instance_tiles(
+	config: &Config,
+	instance_id: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<InstanceTile>> {
+	let results = config
+		.plugins
+		.call_hook(AddInstanceTiles, &instance_id.to_string(), paths, o)
+		.await?;
+
+	results.flatten_all_results(o).await
+}
+
+/// Gets the controls that plugins add to the config editor of instances and templates
+pub async fn get_instance_config_controls(
+	config: &Config,
+	id: Option<String>,
+	kind: ConfigKind,
+	plugin: Option<String>,
+	paths: &Paths,
+) -> anyhow::Result<Vec<Control>> {
+	let arg = AddInstanceConfigControlsArg { id, kind, plugin };
+	let mut results = config
+		.plugins
+		.call_hook(AddInstanceConfigControls, &arg, paths, &mut NoOp)
+		.await?;
+
+	let mut out = Vec::new();
+	while let Some(result) = results.next_result(&mut NoOp).await? {
+		out.extend(result.controls);
+	}
+
+	Ok(out)
+}
+
+/// Gets the controls for the config of each plugin, by plugin ID
+pub async fn get_plugin_config_controls(
+	config: &Config,
+	paths: &Paths,
+) -> anyhow::Result<HashMap<String, Vec<Control>>> {
+	let mut results = config
+		.plugins
+		.call_hook(AddPluginConfigControls, &(), paths, &mut NoOp)
+		.await?;
+
+	let mut out = HashMap::new();
+	while let Some(result) = results.next() {
+		let plugin_id = result.get_id().clone();
+		let result = result.result(&mut NoOp).await?;
+
+		out.insert(plugin_id, result);
+	}
+
+	Ok(out)
+}
+
+/// Gets the config of each plugin, by plugin ID
+pub fn get_plugin_config(paths: &Paths) -> anyhow::Result<HashMap<String, serde_json::Value>> {
+	let config = PluginManager::open_config(paths).context("Failed to open plugin config")?;
+
+	Ok(config.config)
+}
+
+/// Replaces the config of every plugin
+pub fn write_plugin_config(
+	config: HashMap<String, serde_json::Value>,
+	paths: &Paths,
+) -> anyhow::Result<()> {
+	let mut base_config =
+		PluginManager::open_config(paths).context("Failed to open plugin config")?;
+
+	base_config.config = config;
+
+	json_to_file_atomic(PluginManager::get_config_path(paths), &base_config)
+		.context("Failed to write to plugin config file")
+}
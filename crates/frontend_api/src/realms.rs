@@ -0,0 +1,107 @@
+use nitrolaunch::config::Config;
+use nitrolaunch::core::QuickPlayType;
+use nitrolaunch::core::net::minecraft::realms::{self, RealmInvite, RealmState};
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::shared::output::NitroOutput;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+/// Info about a realm, for displaying in realm lists
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RealmInfo {
+	/// The ID of the realm
+	pub id: u64,
+	/// The name of the realm
+	pub name: String,
+	/// The message of the day for the realm
+	pub motd: Option<String>,
+	/// The username of the owner
+	pub owner: Option<String>,
+	/// Whether the account owns this realm, instead of being invited to it
+	pub owned: bool,
+	/// The state of the realm
+	pub state: RealmState,
+	/// Whether the subscription for the realm has expired
+	pub expired: bool,
+	/// The game version the realm is running
+	pub version: Option<String>,
+}
+
+/// Gets the realms that an account owns or has joined
+pub async fn get_realms(
+	config: &mut Config,
+	account: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<RealmInfo>> {
+	let credentials = config
+		.accounts
+		.get_realms_credentials(account, &paths.core, client, o)
+		.await?;
+
+	let realms = realms::get_realms(&credentials, client).await?;
+
+	let mut out: Vec<_> = realms
+		.into_iter()
+		.map(|realm| RealmInfo {
+			owned: realm.is_owned_by(&credentials.uuid),
+			id: realm.id,
+			name: realm.name.unwrap_or_else(|| realm.id.to_string()),
+			motd: realm.motd.filter(|x| !x.is_empty()),
+			owner: realm.owner,
+			state: realm.state,
+			expired: realm.expired,
+			version: realm.active_version,
+		})
+		.collect();
+	// Show owned realms first
+	out.sort_by_key(|x| !x.owned);
+
+	Ok(out)
+}
+
+/// Gets the pending realm invites for an account
+pub async fn get_realm_invites(
+	config: &mut Config,
+	account: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<RealmInvite>> {
+	let credentials = config
+		.accounts
+		.get_realms_credentials(account, &paths.core, client, o)
+		.await?;
+
+	realms::get_invites(&credentials, client).await
+}
+
+/// Accepts or rejects a realm invite for an account
+pub async fn answer_realm_invite(
+	config: &mut Config,
+	account: &str,
+	invite: &str,
+	accept: bool,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let credentials = config
+		.accounts
+		.get_realms_credentials(account, &paths.core, client, o)
+		.await?;
+
+	if accept {
+		realms::accept_invite(invite, &credentials, client).await
+	} else {
+		realms::reject_invite(invite, &credentials, client).await
+	}
+}
+
+/// Gets the Quick Play to launch straight into a realm
+pub fn get_realm_quick_play(realm: u64) -> QuickPlayType {
+	QuickPlayType::Realm {
+		realm: realm.to_string(),
+	}
+}
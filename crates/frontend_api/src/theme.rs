@@ -0,0 +1,105 @@
+use anyhow::bail;
+use itertools::Itertools;
+use nitrolaunch::plugin_crate::hook::hooks::Theme;
+
+/// IDs of the themes that are built into the launcher
+const BUILTIN_THEMES: [&str; 2] = ["dark", "light"];
+
+/// Gets the full CSS for a theme, including its variables and font
+pub fn get_theme_css(theme: &Theme) -> String {
+	let mut out = String::new();
+	if !theme.variables.is_empty() {
+		out.push_str(":root {\n");
+		for (name, value) in theme.variables.iter().sorted_by_key(|x| x.0) {
+			out.push_str(&format!("\t{name}: {value};\n"));
+		}
+		out.push_str("}\n");
+	}
+	if let Some(font) = &theme.font {
+		out.push_str(&format!("body {{\n\tfont-family: {font};\n}}\n"));
+	}
+	out.push_str(&theme.css);
+
+	out
+}
+
+/// Checks that a theme created by the user is valid and can't inject anything other than its variables
+pub fn validate_custom_theme(theme: &Theme) -> anyhow::Result<()> {
+	if theme.id.is_empty()
+		|| !theme
+			.id
+			.chars()
+			.all(|x| x.is_ascii_alphanumeric() || x == '_' || x == '-')
+	{
+		bail!("Theme ID '{}' is invalid", theme.id);
+	}
+	if BUILTIN_THEMES.contains(&theme.id.as_str()) {
+		bail!("Theme ID '{}' is used by a built-in theme", theme.id);
+	}
+	if theme.name.is_empty() {
+		bail!("Theme name cannot be empty");
+	}
+	if !theme.css.is_empty() {
+		bail!("Custom themes can only set variables and fonts");
+	}
+
+	for (name, value) in &theme.variables {
+		let is_valid_name = name.len() > 2
+			&& name.starts_with("--")
+			&& name
+				.chars()
+				.all(|x| x.is_ascii_alphanumeric() || x == '-' || x == '_');
+		if !is_valid_name {
+			bail!("Theme variable '{name}' is invalid");
+		}
+		if !is_valid_value(value) {
+			bail!("Value for theme variable '{name}' is invalid");
+		}
+	}
+	if let Some(font) = &theme.font
+		&& !is_valid_value(font)
+	{
+		bail!("Theme font is invalid");
+	}
+
+	Ok(())
+}
+
+/// Checks that a CSS value can't escape its declaration
+fn is_valid_value(value: &str) -> bool {
+	!value.is_empty() && !value.contains([';', '{', '}', '<', '>', '\\'])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_custom_theme() {
+		let mut theme = Theme {
+			id: "ocean".into(),
+			name: "Ocean".into(),
+			variables: [
+				("--bg".to_string(), "#001122".to_string()),
+				("--round".to_string(), "0.5rem".to_string()),
+			]
+			.into(),
+			font: Some("\"Fira Sans\", sans-serif".into()),
+			..Default::default()
+		};
+		validate_custom_theme(&theme).unwrap();
+
+		let css = get_theme_css(&theme);
+		assert!(css.starts_with(":root {\n\t--bg: #001122;\n\t--round: 0.5rem;\n}"));
+		assert!(css.contains("font-family: \"Fira Sans\", sans-serif;"));
+
+		theme
+			.variables
+			.insert("--fg".into(), "red} body { display: none".into());
+		assert!(validate_custom_theme(&theme).is_err());
+
+		theme.variables.remove("--fg");
+		theme.id = "dark".into();
+		assert!(validate_custom_theme(&theme).is_err());
+	}
+}
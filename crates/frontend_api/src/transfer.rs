@@ -0,0 +1,250 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+use nitrolaunch::config::Config;
+use nitrolaunch::config::modifications::ConfigModification;
+use nitrolaunch::config_crate::instance::InstanceConfig;
+use nitrolaunch::instance::update::manager::UpdateSettings;
+use nitrolaunch::instance::{Instance, transfer};
+use nitrolaunch::io::lock::Lockfile;
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::pkg_crate::{PkgRequest, PkgRequestSource};
+use nitrolaunch::plugin_crate::hook::hooks::{
+	AddInstanceTransferFormats, CheckMigration, CheckMigrationResult, InstanceTransferFormat,
+};
+use nitrolaunch::shared::id::InstanceID;
+use nitrolaunch::shared::output::{MessageContents, NitroOutput, NoOp};
+use nitrolaunch::shared::{Side, UpdateDepth};
+use reqwest::Client;
+
+use crate::config::modify_config;
+use crate::instance::download_package_icon;
+
+/// Gets the instance transfer formats that plugins support
+pub async fn get_instance_transfer_formats(
+	config: &Config,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<InstanceTransferFormat>> {
+	let results = config
+		.plugins
+		.call_hook(AddInstanceTransferFormats, &(), paths, o)
+		.await?;
+
+	results.flatten_all_results(o).await
+}
+
+/// Imports an instance from a file, returning its new config. The config is not written
+pub async fn import_instance(
+	config: &Config,
+	id: &str,
+	format: &str,
+	path: &Path,
+	side: Option<Side>,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<InstanceConfig> {
+	let formats = transfer::load_formats(&config.plugins, paths, o)
+		.await
+		.context("Failed to load transfer formats")?;
+
+	Instance::import(id, format, path, side, &formats, &config.plugins, paths, o)
+		.await
+		.context("Failed to import instance")
+}
+
+/// Downloads an instance from a URL and imports it, returning its new config. The format is detected
+/// if it is not given. The config is not written
+#[allow(clippy::too_many_arguments)]
+pub async fn import_instance_from_url(
+	config: &Config,
+	id: &str,
+	format: Option<String>,
+	url: &str,
+	side: Option<Side>,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<InstanceConfig> {
+	let formats = transfer::load_formats(&config.plugins, paths, o)
+		.await
+		.context("Failed to load transfer formats")?;
+
+	let path = transfer::download_import_source(url, paths, client, o)
+		.await
+		.context("Failed to download instance")?;
+
+	let format = match format {
+		Some(format) => format,
+		None => match formats.detect(&path) {
+			Ok(Some(format)) => format.to_string(),
+			Ok(None) => {
+				let _ = std::fs::remove_file(&path);
+				bail!("Could not detect the format of the instance. Try selecting one.");
+			}
+			Err(e) => {
+				let _ = std::fs::remove_file(&path);
+				return Err(e);
+			}
+		},
+	};
+
+	let result = Instance::import(
+		id,
+		&format,
+		&path,
+		side,
+		&formats,
+		&config.plugins,
+		paths,
+		o,
+	)
+	.await
+	.context("Failed to import instance");
+	let _ = std::fs::remove_file(&path);
+
+	result
+}
+
+/// Exports an instance to a file
+pub async fn export_instance(
+	config: &mut Config,
+	id: &str,
+	format: &str,
+	path: &Path,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let formats = transfer::load_formats(&config.plugins, paths, o)
+		.await
+		.context("Failed to load transfer formats")?;
+
+	let Some(instance) = config.instances.get_mut(&InstanceID::from(id)) else {
+		bail!("Instance does not exist");
+	};
+
+	let lock = Lockfile::open(paths).context("Failed to open lockfile")?;
+
+	instance
+		.export(format, path, &formats, &config.plugins, &lock, paths, o)
+		.await
+		.context("Failed to export instance")
+}
+
+/// Checks whether instances can be migrated from another launcher
+pub async fn check_migration(
+	config: &Config,
+	format: &str,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Option<CheckMigrationResult>> {
+	let result = config
+		.plugins
+		.call_hook(CheckMigration, &format.to_string(), paths, o)
+		.await?;
+
+	result.first_some(o).await
+}
+
+/// Migrates instances from another launcher and adds them to the config, returning how many were migrated.
+/// All instances are migrated if none are given
+pub async fn migrate_instances(
+	config: &Config,
+	format: &str,
+	instances: Option<Vec<String>>,
+	link: bool,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<usize> {
+	let formats = transfer::load_formats(&config.plugins, paths, o)
+		.await
+		.context("Failed to load transfer formats")?;
+
+	let instances =
+		transfer::migrate_instances(format, instances, link, &formats, &config.plugins, paths, o)
+			.await
+			.context("Failed to migrate instances")?;
+
+	let count = instances.len();
+
+	let modifications = instances
+		.into_iter()
+		.map(|(id, config)| ConfigModification::AddInstance(id.into(), config))
+		.collect();
+	modify_config(modifications, paths, &mut NoOp).await?;
+
+	Ok(count)
+}
+
+/// Creates the config for a new instance from a modpack package. The config is not written.
+/// If the modpack doesn't give the instance an icon, the icon of the package is downloaded and
+/// returned so that the frontend can save it
+pub async fn create_instance_from_modpack(
+	config: &Config,
+	instance_id: &str,
+	modpack: String,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<(InstanceConfig, Option<PathBuf>)> {
+	let req = PkgRequest::parse(modpack, PkgRequestSource::UserRequire).arc();
+
+	let core = config
+		.get_core(
+			None,
+			&UpdateSettings {
+				depth: UpdateDepth::Shallow,
+				offline_auth: true,
+			},
+			client,
+			&config.plugins,
+			paths,
+			&mut NoOp,
+		)
+		.await?;
+
+	let version_manifest = core
+		.get_version_manifest(None, UpdateDepth::Full, &mut NoOp)
+		.await?;
+
+	let mut instance_config = Instance::create_from_modpack_package(
+		instance_id,
+		&req,
+		Side::Client,
+		version_manifest.list.clone(),
+		&config.packages,
+		&config.plugins,
+		client,
+		paths,
+		o,
+	)
+	.await
+	.context("Failed to import the new instance")?;
+
+	// Use the modpack's icon if the import didn't give the instance one. This is allowed to fail
+	// since the instance is still usable without it
+	let mut icon = None;
+	if instance_config.icon.is_none() {
+		match download_package_icon(
+			instance_id,
+			&instance_config,
+			&config.packages,
+			paths,
+			client,
+			o,
+		)
+		.await
+		{
+			Ok(Some(path)) => {
+				instance_config.icon = Some(path.to_string_lossy().to_string());
+				icon = Some(path);
+			}
+			Ok(None) => {}
+			Err(e) => o.display(MessageContents::Warning(format!(
+				"Failed to get modpack icon: {e:?}"
+			))),
+		}
+	}
+
+	Ok((instance_config, icon))
+}
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use nitrolaunch::config::Config;
+use nitrolaunch::core::io::json_from_file;
+use nitrolaunch::core::net::game_files::assets::AssetIndex;
+use nitrolaunch::core::net::game_files::version_manifest::VersionManifestAndList;
+use nitrolaunch::instance::update::manager::UpdateSettings;
+use nitrolaunch::io::paths::Paths;
+use nitrolaunch::plugin_crate::hook::hooks::{
+	AddSupportedLoaders, GetLoaderVersions, GetLoaderVersionsArg,
+};
+use nitrolaunch::shared::UpdateDepth;
+use nitrolaunch::shared::loaders::{Loader, LoaderVersionChannel};
+use nitrolaunch::shared::minecraft::{VersionChannel, VersionType};
+use nitrolaunch::shared::output::NoOp;
+use reqwest::Client;
+
+/// Gets all loaders that are supported, including from plugins
+pub async fn get_supported_loaders(config: &Config, paths: &Paths) -> anyhow::Result<Vec<Loader>> {
+	let results = config
+		.plugins
+		.call_hook(AddSupportedLoaders, &(), paths, &mut NoOp)
+		.await
+		.context("Failed to get supported loaders from plugins")?;
+
+	results.flatten_all_results(&mut NoOp).await
+}
+
+/// Gets the available versions of a loader for a Minecraft version in a channel, from newest to oldest
+pub async fn get_loader_versions(
+	config: &Config,
+	loader: Loader,
+	minecraft_version: String,
+	channel: LoaderVersionChannel,
+	paths: &Paths,
+) -> anyhow::Result<Vec<String>> {
+	let arg = GetLoaderVersionsArg {
+		loader,
+		minecraft_version,
+		channel,
+	};
+
+	let results = config
+		.plugins
+		.call_hook(GetLoaderVersions, &arg, paths, &mut NoOp)
+		.await
+		.context("Failed to get loader versions from plugins")?;
+
+	results.flatten_all_results(&mut NoOp).await
+}
+
+/// Get a list of all Minecraft versions, including from plugins
+pub async fn get_minecraft_versions(
+	config: &Config,
+	releases_only: bool,
+	paths: &Paths,
+	client: &Client,
+) -> anyhow::Result<Vec<String>> {
+	let version_manifest = get_version_manifest(config, paths, client).await?;
+
+	if releases_only {
+		Ok(version_manifest
+			.manifest
+			.versions
+			.iter()
+			.filter_map(|x| {
+				if let VersionType::Release = &x.ty {
+					Some(x.id.clone())
+				} else {
+					None
+				}
+			})
+			.rev()
+			.collect())
+	} else {
+		Ok(version_manifest.list.clone())
+	}
+}
+
+/// Get a list of the Minecraft versions in any of the given channels, from oldest to newest
+pub async fn get_minecraft_versions_in_channels(
+	config: &Config,
+	channels: &[VersionChannel],
+	paths: &Paths,
+	client: &Client,
+) -> anyhow::Result<Vec<String>> {
+	let version_manifest = get_version_manifest(config, paths, client).await?;
+
+	Ok(version_manifest
+		.manifest
+		.versions
+		.iter()
+		.filter(|x| channels.contains(&x.get_channel()))
+		.map(|x| x.id.clone())
+		.rev()
+		.collect())
+}
+
+/// Gets the version manifest, including versions from plugins
+async fn get_version_manifest(
+	config: &Config,
+	paths: &Paths,
+	client: &Client,
+) -> anyhow::Result<Arc<VersionManifestAndList>> {
+	let core = config
+		.get_core(
+			None,
+			&UpdateSettings {
+				depth: UpdateDepth::Shallow,
+				offline_auth: false,
+			},
+			client,
+			&config.plugins,
+			paths,
+			&mut NoOp,
+		)
+		.await?;
+
+	let version_manifest = core
+		.get_version_manifest(None, UpdateDepth::Shallow, &mut NoOp)
+		.await?;
+
+	Ok(version_manifest.clone())
+}
+
+/// Finds the panorama images for a version in the game assets, if the version is installed.
+///
+/// Returns filesystem paths to two panorama images to be stitched left-to-right
+pub fn get_banner_images(paths: &Paths, version: &str) -> anyhow::Result<Option<(String, String)>> {
+	let index_path = paths
+		.internal
+		.join(format!("assets/indexes/{version}.json"));
+
+	if !index_path.exists() {
+		return Ok(None);
+	}
+
+	let contents: AssetIndex = json_from_file(index_path).context("Failed to open asset index")?;
+
+	let pano1 = contents
+		.objects
+		.get("minecraft/textures/gui/title/background/panorama_0.png");
+	let pano2 = contents
+		.objects
+		.get("minecraft/textures/gui/title/background/panorama_1.png");
+
+	let Some(pano1) = pano1 else {
+		return Ok(None);
+	};
+
+	let Some(pano2) = pano2 else {
+		return Ok(None);
+	};
+
+	let path1 = paths
+		.internal
+		.join(format!("assets/objects/{}", pano1.get_hash_path()));
+	let path2 = paths
+		.internal
+		.join(format!("assets/objects/{}", pano2.get_hash_path()));
+
+	if !path1.exists() || !path2.exists() {
+		return Ok(None);
+	}
+
+	Ok(Some((
+		path1.to_string_lossy().to_string(),
+		path2.to_string_lossy().to_string(),
+	)))
+}
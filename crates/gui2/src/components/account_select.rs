@@ -0,0 +1,125 @@
+use nitro_frontend_api::account::AccountInfo;
+
+use crate::{ops::account::FetchAccounts, prelude::*};
+
+/// Footer control for choosing which account to launch with
+#[derive(PartialEq)]
+pub struct AccountSelect;
+
+impl Component for AccountSelect {
+	fn render(&self) -> impl IntoElement {
+		let theme = use_theme();
+		let back_state = use_consume::<BackState>();
+		let front_state = use_front_state();
+		front_state.read().subscribe(FrontChannel::Account);
+		let accounts_query = use_query(FetchAccounts::new(back_state));
+
+		let mut is_open = use_state(|| false);
+
+		let accounts = accounts_query
+			.read()
+			.state()
+			.ok()
+			.cloned()
+			.unwrap_or_default();
+		let selected = front_state.read().account().cloned();
+
+		let selected_name = match &selected {
+			Some(id) => accounts
+				.iter()
+				.find(|x| &x.id == id)
+				.map(account_name)
+				.unwrap_or(id.clone()),
+			None => "Default Account".into(),
+		};
+
+		let indicator = rect()
+			.width(Size::fill())
+			.height(Size::px(36.0))
+			.item_colorway(&theme, false, false)
+			.background(theme.bg)
+			.corner_radius(theme.round2)
+			.cont()
+			.center()
+			.on_press(move |_| is_open.toggle())
+			.clickable()
+			.child(icon("user", 16.0))
+			.child(selected_name);
+
+		let popout = if *is_open.read() {
+			let options = std::iter::once((None, "Default Account".to_string()))
+				.chain(
+					accounts
+						.iter()
+						.map(|x| (Some(x.id.clone()), account_name(x))),
+				)
+				.map(|(id, name)| {
+					AccountOption {
+						is_selected: id == selected,
+						id,
+						name,
+						is_open,
+					}
+					.into_element()
+				});
+
+			Some(
+				rect()
+					.width(Size::fill())
+					.vertical()
+					.spacing(4.0)
+					.padding(6.0)
+					.item_colorway(&theme, false, false)
+					.corner_radius(theme.round2)
+					.margin((0.0, 0.0, 8.0, 0.0))
+					.children(options),
+			)
+		} else {
+			None
+		};
+
+		Attached::new(indicator).top().maybe_child(popout)
+	}
+}
+
+#[derive(PartialEq)]
+struct AccountOption {
+	id: Option<String>,
+	name: String,
+	is_selected: bool,
+	is_open: State<bool>,
+}
+
+impl Component for AccountOption {
+	fn render(&self) -> impl IntoElement {
+		let theme = use_theme();
+		let front_state = use_front_state();
+		let is_hovered = use_state(|| false);
+
+		let id = self.id.clone();
+		let mut is_open = self.is_open.clone();
+
+		rect()
+			.width(Size::fill())
+			.height(Size::px(theme.input_height))
+			.padding((6.0, 12.0))
+			.corner_radius(theme.round)
+			.cont()
+			.cross_align(Alignment::Center)
+			.item_colorway(&theme, *is_hovered.read(), self.is_selected)
+			.hover(is_hovered)
+			.on_press(move |_| {
+				front_state.write().set_account(id.clone());
+				is_open.set(false);
+			})
+			.child(self.name.as_str())
+	}
+}
+
+/// Gets the name to show for an account
+fn account_name(account: &AccountInfo) -> String {
+	account
+		.username
+		.clone()
+		.unwrap_or_else(|| account.id.clone())
+}
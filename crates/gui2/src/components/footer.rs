@@ -2,7 +2,8 @@ use nitrolaunch::config_crate::ConfigKind;
 
 use crate::{
 	components::{
-		instance::running_instances::RunningInstances, output_indicator::OutputIndicator,
+		account_select::AccountSelect, instance::running_instances::RunningInstances,
+		output_indicator::OutputIndicator,
 	},
 	ops::{
 		instance::InstanceItemInfo,
@@ -37,8 +38,10 @@ impl Component for Footer {
 			.width(Size::flex(1.0))
 			.cont()
 			.cross_align(Alignment::Center)
+			.spacing(8.0)
+			.padding((0.0, 10.0))
 			.child(rect().width(Size::flex(2.0)).child(OutputIndicator))
-			.child(rect().width(Size::flex(1.0)));
+			.child(rect().width(Size::flex(1.0)).child(AccountSelect));
 
 		rect()
 			.width(Size::fill())
@@ -61,6 +64,9 @@ impl Component for FooterButton {
 	fn render(&self) -> impl IntoElement {
 		let theme = use_theme();
 		let back_state = use_consume::<BackState>();
+		let state = use_front_state();
+		state.read().subscribe(FrontChannel::Account);
+		let account = state.read().account().cloned();
 		let launch_instance = use_mutation(LaunchInstance::new(back_state));
 
 		let left = rect().height(Size::fill()).width(Size::flex(1.0));
@@ -78,7 +84,7 @@ impl Component for FooterButton {
 				ConfigKind::Instance => {
 					launch_instance.mutate(LaunchInstanceParams {
 						id: info.id.clone(),
-						account: None,
+						account: account.clone(),
 						offline: false,
 					});
 				}
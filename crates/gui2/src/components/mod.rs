@@ -11,6 +11,7 @@ use freya::{
 
 use crate::theme::{HexColor, Theme};
 
+pub mod account_select;
 pub mod footer;
 pub mod input;
 pub mod instance;
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use nitrolaunch::shared::output::MessageContents;
 
-use crate::{prelude::*, state::BackEvent};
+use crate::{prelude::*, state::BackEvent, theme::Theme};
 
 #[derive(PartialEq)]
 pub struct OutputIndicator;
@@ -46,9 +46,19 @@ impl Component for OutputIndicator {
 							if let Some(task) = tasks.write().get_mut(&task) {
 								match message {
 									MessageContents::StartProcess(msg) => task.process = Some(msg),
-									MessageContents::Success(..) => task.process = None,
+									MessageContents::Success(..) => {
+										task.process = None;
+										task.progress = None;
+									}
 									MessageContents::Header(header) => task.section = Some(header),
-									other => task.messages.push(other),
+									MessageContents::Associated(assoc, msg) => {
+										if let MessageContents::Progress { current, total } = *assoc
+										{
+											task.progress = Some((current, total));
+										}
+										task.process = Some(msg.default_format());
+									}
+									other => task.push_message(other),
 								}
 							}
 						}
@@ -85,13 +95,25 @@ impl Component for OutputIndicator {
 			.child(indicator_text);
 
 		let popout = if *is_open.read() {
+			let task_elems = tasks
+				.read()
+				.iter()
+				.map(|(id, task)| task_element(id, task, &theme).into_element())
+				.collect::<Vec<_>>();
+
 			Some(
 				rect()
 					.width(Size::fill())
-					.height(Size::px(128.0))
+					.height(Size::px(192.0))
 					.item_colorway(&theme, false, false)
 					.corner_radius(theme.round2)
-					.margin((0.0, 0.0, 8.0, 0.0)),
+					.margin((0.0, 0.0, 8.0, 0.0))
+					.child(
+						ScrollView::new()
+							.width(Size::fill())
+							.height(Size::fill())
+							.child(rect().width(Size::fill()).padding(8.0).children(task_elems)),
+					),
 			)
 		} else {
 			None
@@ -101,9 +123,45 @@ impl Component for OutputIndicator {
 	}
 }
 
+/// Renders the output of a single task
+fn task_element(id: &str, task: &Task, theme: &Theme) -> impl IntoElement {
+	let mut out = rect()
+		.width(Size::fill())
+		.vertical()
+		.spacing(2.0)
+		.margin((0.0, 0.0, 6.0, 0.0))
+		.child(rect().font_weight(FontWeight::BOLD).child(id));
+
+	if let Some(section) = &task.section {
+		out = out.child(rect().color(theme.fg3).child(section.as_str()));
+	}
+
+	if let Some(process) = &task.process {
+		let text = match task.progress {
+			Some((current, total)) if total > 0 => format!("{process} ({current}/{total})"),
+			_ => process.clone(),
+		};
+		out = out.child(rect().color(theme.primary).child(text));
+	}
+
+	for message in &task.messages {
+		out = out.child(
+			rect()
+				.color(theme.fg3)
+				.child(message.clone().default_format()),
+		);
+	}
+
+	out
+}
+
+/// The maximum number of past messages to keep for each task
+const MAX_TASK_MESSAGES: usize = 5;
+
 struct Task {
 	messages: Vec<MessageContents>,
 	process: Option<String>,
+	progress: Option<(u32, u32)>,
 	section: Option<String>,
 }
 
@@ -112,7 +170,15 @@ impl Task {
 		Self {
 			messages: Vec::new(),
 			process: None,
+			progress: None,
 			section: None,
 		}
 	}
+
+	fn push_message(&mut self, message: MessageContents) {
+		self.messages.push(message);
+		if self.messages.len() > MAX_TASK_MESSAGES {
+			self.messages.remove(0);
+		}
+	}
 }
@@ -1,6 +1,6 @@
 use freya::{prelude::spawn, query::QueriesStorage};
 
-use crate::ops::{instance::FetchItems, launch::FetchRunningInstances};
+use crate::ops::{account::FetchAccounts, instance::FetchItems, launch::FetchRunningInstances};
 
 /// Backend dependency that can be invalidated
 pub enum BackDependency {
@@ -14,6 +14,7 @@ impl BackDependency {
 		match self {
 			Self::Config => {
 				spawn(QueriesStorage::<FetchItems>::invalidate_all());
+				spawn(QueriesStorage::<FetchAccounts>::invalidate_all());
 			}
 			Self::RunningInstances => {
 				spawn(QueriesStorage::<FetchRunningInstances>::invalidate_all());
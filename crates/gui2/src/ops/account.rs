@@ -0,0 +1,46 @@
+use nitro_frontend_api::account::{self as account_api, AccountInfo};
+use nitrolaunch::shared::output::NoOp;
+
+use crate::prelude::*;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct FetchAccounts {
+	back_state: Captured<BackState>,
+}
+
+impl FetchAccounts {
+	pub fn new(back_state: BackState) -> Query<Self> {
+		Query::new(
+			(),
+			Self {
+				back_state: Captured(back_state),
+			},
+		)
+	}
+}
+
+impl QueryCapability for FetchAccounts {
+	type Ok = Vec<AccountInfo>;
+	type Err = anyhow::Error;
+	type Keys = ();
+
+	fn run(&self, _: &Self::Keys) -> impl Future<Output = Result<Self::Ok, Self::Err>> {
+		let back_state = self.back_state.clone();
+
+		query_spawn(async move {
+			let mut config = back_state.config().await?;
+			let accounts = account_api::get_accounts(
+				&mut config,
+				&back_state.paths,
+				&back_state.client,
+				&mut NoOp,
+			)
+			.await;
+
+			let mut accounts: Vec<_> = accounts.into_values().collect();
+			accounts.sort_by(|x, y| x.id.cmp(&y.id));
+
+			Ok(accounts)
+		})
+	}
+}
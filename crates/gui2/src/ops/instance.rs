@@ -1,10 +1,8 @@
 use std::time::Duration;
 
-use itertools::Itertools;
+use nitro_frontend_api::instance::{self as instance_api, InstanceInfo};
 use nitrolaunch::{
 	config_crate::ConfigKind,
-	core::util::versions::MinecraftVersion,
-	instance::parse_loader_config,
 	shared::{Side, loaders::Loader},
 };
 
@@ -38,37 +36,13 @@ impl QueryCapability for FetchItems {
 		query_spawn(async move {
 			let config = back_state.config().await?;
 
-			let instances = config
-				.instances
-				.values()
-				.sorted_by_cached_key(|x| x.id())
-				.map(|x| InstanceItemInfo {
-					id: x.id().to_string(),
-					ty: ConfigKind::Instance,
-					name: x.config().name.clone(),
-					icon: x.config().icon.clone(),
-					side: Some(x.side()),
-					version: Some(x.version().clone()),
-					loader: Some(x.loader().clone()),
-				});
+			let instances = instance_api::get_instances(&config)
+				.into_iter()
+				.map(|x| InstanceItemInfo::from_info(x, ConfigKind::Instance));
 
-			let templates = config
-				.consolidated_templates
-				.iter()
-				.sorted_by_cached_key(|x| x.0.clone())
-				.map(|(id, x)| InstanceItemInfo {
-					id: id.to_string(),
-					ty: ConfigKind::Template,
-					name: x.instance.name.clone(),
-					icon: x.instance.icon.clone(),
-					side: x.instance.side,
-					version: x
-						.instance
-						.version
-						.as_ref()
-						.map(|x| MinecraftVersion::from_deser(&x)),
-					loader: x.instance.loader.as_ref().map(|x| parse_loader_config(x).0),
-				});
+			let templates = instance_api::get_templates(&config)
+				.into_iter()
+				.map(|x| InstanceItemInfo::from_info(x, ConfigKind::Template));
 
 			let base_template = InstanceItemInfo {
 				id: "base".into(),
@@ -96,10 +70,24 @@ pub struct InstanceItemInfo {
 	pub name: Option<String>,
 	pub icon: Option<String>,
 	pub side: Option<Side>,
-	pub version: Option<MinecraftVersion>,
+	pub version: Option<String>,
 	pub loader: Option<Loader>,
 }
 
+impl InstanceItemInfo {
+	fn from_info(info: InstanceInfo, ty: ConfigKind) -> Self {
+		Self {
+			id: info.id,
+			ty,
+			name: info.name,
+			icon: info.icon,
+			side: info.side,
+			version: info.version,
+			loader: Some(info.loader),
+		}
+	}
+}
+
 #[derive(Clone, Default)]
 pub struct InstancesAndTemplates {
 	pub instances: Vec<InstanceItemInfo>,
@@ -1,13 +1,5 @@
-use anyhow::Context;
-use nitrolaunch::{
-	instance::{
-		launch::LaunchSettings,
-		tracking::RunningInstanceEntry,
-		update::{InstanceUpdateContext, manager::UpdateSettings},
-	},
-	io::lock::Lockfile,
-	shared::{UpdateDepth, id::InstanceID},
-};
+use nitro_frontend_api::launch::{self as launch_api, LaunchOptions};
+use nitrolaunch::{instance::tracking::RunningInstanceEntry, shared::id::InstanceID};
 
 use crate::{ops::MakeSend, prelude::*, secrets::get_ms_client_id};
 
@@ -48,52 +40,22 @@ impl MutationCapability for LaunchInstance {
 			output.set_task(&format!("launch_instance_{id}"));
 			output.set_instance(id.clone().into());
 
-			if let Some(account) = account {
-				let _ = config.accounts.choose_account(&account);
-			}
-
-			let core = config
-				.get_core(
-					Some(&get_ms_client_id()),
-					&UpdateSettings {
-						depth: UpdateDepth::Shallow,
-						offline_auth: offline,
-					},
-					&back_state.client,
-					&config.plugins,
-					&back_state.paths,
-					&mut output,
-				)
-				.await?;
-
-			let instance = config
-				.instances
-				.get_mut(&InstanceID::from(id))
-				.context("Instance does not exist")?;
-
-			let settings = LaunchSettings {
-				offline_auth: offline,
-				pipe_stdin: false,
+			let options = LaunchOptions {
+				account,
+				offline,
 				quick_play: None,
 			};
 
-			let mut lock = Lockfile::open(&back_state.paths)?;
-			let mut ctx = InstanceUpdateContext {
-				packages: &config.packages,
-				accounts: &mut config.accounts,
-				plugins: &config.plugins,
-				prefs: &config.prefs,
-				paths: &back_state.paths,
-				lock: &mut lock,
-				client: &back_state.client,
-				output: &mut output,
-				core: &core,
-			};
-
-			let mut handle = instance
-				.launch(settings, &mut ctx)
-				.await
-				.context("Failed to launch instance")?;
+			let mut handle = launch_api::launch_instance(
+				&mut config,
+				&InstanceID::from(id),
+				options,
+				get_ms_client_id(),
+				&back_state.paths,
+				&back_state.client,
+				&mut output,
+			)
+			.await?;
 
 			handle.silence_output(true);
 			output.finish_task();
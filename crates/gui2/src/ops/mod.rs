@@ -1,5 +1,6 @@
 use std::pin::Pin;
 
+pub mod account;
 pub mod instance;
 pub mod launch;
 pub mod task;
@@ -35,6 +35,7 @@ pub struct FrontState {
 	navigator: Navigator,
 	radio: RadioStation<(), FrontChannel>,
 	footer: FooterItem,
+	account: Option<String>,
 	event_rx: Rc<broadcast::Receiver<BackEvent>>,
 }
 
@@ -47,6 +48,8 @@ pub enum FrontChannel {
 	FooterItem,
 	/// Changes to the theme
 	Theme,
+	/// Changes to the selected account
+	Account,
 }
 
 impl RadioChannel<()> for FrontChannel {}
@@ -61,6 +64,7 @@ impl FrontState {
 			navigator: Navigator::new(),
 			radio,
 			footer: FooterItem::None,
+			account: None,
 			event_rx: Rc::new(event_rx),
 		}
 	}
@@ -130,6 +134,16 @@ impl FrontState {
 	pub fn footer(&self) -> &FooterItem {
 		&self.footer
 	}
+
+	/// Sets the account to launch with. None uses the default account from the config
+	pub fn set_account(&mut self, account: Option<String>) {
+		self.account = account;
+		self.invalidate(FrontChannel::Account);
+	}
+
+	pub fn account(&self) -> Option<&String> {
+		self.account.as_ref()
+	}
 }
 
 /// Gives access to front state
@@ -153,6 +167,14 @@ impl BackState {
 	pub async fn new(event_tx: broadcast::Sender<BackEvent>) -> anyhow::Result<Self> {
 		let paths = Paths::new_no_create()?;
 		let plugins = PluginManager::load(&paths, &mut NoOp).await?;
+		// The proxy is only read on startup, so the launcher has to be restarted for changes to it to apply
+		let client = match Config::create_client(&paths) {
+			Ok(client) => client,
+			Err(e) => {
+				eprintln!("Proxy will not be used: {e:?}");
+				Client::new()
+			}
+		};
 
 		let running_instances = RunningInstanceManager::new(&paths, event_tx.clone())
 			.context("Failed to create running instance manager")?;
@@ -182,7 +204,7 @@ impl BackState {
 			event_tx,
 			paths,
 			plugins,
-			client: Client::new(),
+			client,
 			running_instances,
 		})
 	}
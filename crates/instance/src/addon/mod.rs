@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use anyhow::Context;
 use nitro_shared::{
 	Side,
-	io::update_link,
+	io::{mark_file_used, update_link},
 	minecraft::AddonKind,
 	pkg::AddonOptionalHashes,
 	versions::{VersionInfo, VersionPattern},
@@ -76,6 +76,7 @@ impl Addon {
 		let Some(source) = &self.source else {
 			return Ok(());
 		};
+		mark_file_used(source);
 		let mut result = Ok(());
 
 		for target in &self.target_paths {
@@ -7,7 +7,8 @@ use std::{
 use anyhow::Context;
 use nitro_shared::{Side, minecraft::AddonKind, pkg::AddonOptionalHashes};
 use serde::{Deserialize, Serialize};
-use zip::ZipArchive;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
 
 use crate::addon::{
 	Addon,
@@ -233,6 +234,11 @@ impl<R: Read + Seek> ModrinthPack<R> {
 		Ok(out)
 	}
 
+	/// Gets the index of this pack mutably, for changing which files are installed
+	pub fn index_mut(&mut self) -> &mut ModrinthIndex {
+		&mut self.index
+	}
+
 	/// Replace the link method of this pack
 	pub fn set_link_method(&mut self, method: Box<dyn LinkMethod + Send + 'static>) {
 		self.link_method = method;
@@ -243,6 +249,12 @@ impl<R: Read + Seek> ModrinthPack<R> {
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModrinthIndex {
+	/// Version of the pack format
+	#[serde(default = "default_format_version")]
+	pub format_version: u32,
+	/// The game the pack is for
+	#[serde(default = "default_game")]
+	pub game: String,
 	/// Name of the modpack
 	pub name: String,
 	/// Version of the modpack
@@ -256,6 +268,40 @@ pub struct ModrinthIndex {
 	pub dependencies: ModrinthPackDependencies,
 }
 
+impl ModrinthIndex {
+	/// Writes a pack with this index. Overrides are pairs of paths relative to the
+	/// instance directory and the files they come from
+	pub fn write_pack(&self, overrides: &[(String, PathBuf)], path: &Path) -> anyhow::Result<()> {
+		let file = File::create(path).context("Failed to create pack file")?;
+		let mut zip = ZipWriter::new(file);
+
+		zip.start_file("modrinth.index.json", SimpleFileOptions::default())?;
+		serde_json::to_writer_pretty(&mut zip, self).context("Failed to write index")?;
+
+		for (rel_path, source) in overrides {
+			zip.start_file(
+				format!("overrides/{rel_path}"),
+				SimpleFileOptions::default(),
+			)?;
+			let mut source = File::open(source)
+				.with_context(|| format!("Failed to open override {}", source.display()))?;
+			std::io::copy(&mut source, &mut zip).context("Failed to copy override")?;
+		}
+
+		zip.finish().context("Failed to finish pack")?;
+
+		Ok(())
+	}
+}
+
+fn default_format_version() -> u32 {
+	1
+}
+
+fn default_game() -> String {
+	"minecraft".into()
+}
+
 /// File in the Modrinth pack index
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -269,9 +315,26 @@ pub struct ModrinthPackFile {
 	pub env: Option<ModrinthFileEnv>,
 	/// URLs for the file
 	pub downloads: Vec<String>,
+	/// Size of the file in bytes
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub file_size: Option<u64>,
 }
 
 impl ModrinthPackFile {
+	/// Checks if this file should be installed on the given side
+	pub fn supports_side(&self, side: Side) -> bool {
+		let Some(env) = &self.env else {
+			return true;
+		};
+		let support = match side {
+			Side::Client => env.client,
+			Side::Server => env.server,
+		};
+
+		!matches!(support, SideSupport::Unsupported)
+	}
+
 	/// Gets the Modrinth project and version ID of this file, if it is available
 	pub fn get_modrinth_info(&self) -> (Option<&str>, Option<&str>) {
 		for url in &self.downloads {
@@ -301,6 +364,10 @@ impl ModrinthPackFile {
 pub struct ModrinthHashes {
 	/// SHA-512 hash
 	pub sha512: String,
+	/// SHA-1 hash
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub sha1: Option<String>,
 }
 
 /// Environment requirements for a Modrinth pack file
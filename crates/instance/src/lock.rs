@@ -152,6 +152,15 @@ impl InstanceLockfile {
 		Ok(addons_to_remove)
 	}
 
+	/// Removes files from the addons that installed them, for example when they were deleted
+	/// outside of Nitrolaunch. Addons that have no files left are removed entirely
+	pub fn remove_addon_files(&mut self, files: &[String]) {
+		for addon in &mut self.contents.addons {
+			addon.files.retain(|x| !files.contains(x));
+		}
+		self.contents.addons.retain(|x| !x.files.is_empty());
+	}
+
 	/// Gets the current Minecraft version
 	pub fn get_minecraft_version(&self) -> Option<&String> {
 		self.contents.minecraft_version.as_ref()
@@ -182,11 +191,36 @@ impl InstanceLockfile {
 		self.contents.loader_version = version;
 	}
 
+	/// Gets the classpath that the instance was last set up with
+	pub fn get_classpath(&self) -> &[String] {
+		&self.contents.classpath
+	}
+
+	/// Updates the classpath of the instance
+	pub fn update_classpath(&mut self, classpath: Vec<String>) {
+		self.contents.classpath = classpath;
+	}
+
+	/// Gets the Java installation that the instance was last set up with
+	pub fn get_java(&self) -> Option<&LockfileJava> {
+		self.contents.java.as_ref()
+	}
+
+	/// Updates the Java installation of the instance
+	pub fn update_java(&mut self, java: LockfileJava) {
+		self.contents.java = Some(java);
+	}
+
 	/// Get the locked packages
 	pub fn get_packages(&self) -> &HashMap<String, LockfilePackage> {
 		&self.contents.packages
 	}
 
+	/// Get the installed addons
+	pub fn get_addons(&self) -> &[LockfileAddon] {
+		&self.contents.addons
+	}
+
 	/// Get the locked modpack
 	pub fn get_modpack(&self) -> Option<&LockfileModpack> {
 		self.contents.modpack.as_ref()
@@ -238,6 +272,14 @@ pub(crate) struct InstanceLockfileContents {
 	/// Currently installed modpack on the instance
 	#[serde(default)]
 	pub modpack: Option<LockfileModpack>,
+	/// The classpath the instance was last set up with, used to keep its libraries when pruning files
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub classpath: Vec<String>,
+	/// The Java installation the instance was last set up with
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub java: Option<LockfileJava>,
 }
 
 /// Package stored in the instance lockfile
@@ -247,6 +289,15 @@ pub struct LockfilePackage {
 	pub content_version: Option<String>,
 }
 
+/// Java installation stored in the instance lockfile
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LockfileJava {
+	/// The major version of Java
+	pub major_version: u16,
+	/// The path to the installation
+	pub path: String,
+}
+
 /// Addon stored in the instance lockfile
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct LockfileAddon {
@@ -8,8 +8,9 @@ use nitro_core::io::java::maven::MavenLibraryParts;
 use nitro_core::io::json_from_file;
 use nitro_core::io::{files, json_to_file};
 use nitro_core::net::download;
+use nitro_shared::loaders::{LoaderVersion, is_unstable_version_name, select_loader_version};
 use nitro_shared::output::{MessageContents, NitroOutput, OutputProcess};
-use nitro_shared::versions::VersionInfo;
+use nitro_shared::versions::{VersionInfo, VersionPattern};
 use nitro_shared::{Side, UpdateDepth};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -60,9 +61,12 @@ pub async fn install_from_core(
 	side: Side,
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<(Classpath, String)> {
+	let fq_version = fq_version
+		.map(|x| VersionPattern::Single(x.to_string()))
+		.unwrap_or_default();
 	let meta = get_meta(
 		&version_info.version,
-		fq_version,
+		&fq_version,
 		&mode,
 		&core.get_paths().internal,
 		UpdateDepth::Full,
@@ -142,6 +146,10 @@ fn default_library_url() -> String {
 pub struct MainLibrary {
 	/// The Maven identifier for the library
 	pub maven: String,
+	/// Whether this version of the library is stable. Only Fabric provides this
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stable: Option<bool>,
 }
 
 impl MainLibrary {
@@ -223,29 +231,53 @@ pub async fn get_all_meta(
 	Ok(meta)
 }
 
-/// Get the Fabric/Quilt metadata file
+/// Get the Fabric/Quilt metadata file for the desired loader version
 pub async fn get_meta(
 	version: &str,
-	fq_version: Option<&str>,
+	fq_version: &VersionPattern,
 	mode: &Mode,
 	internal_dir: &Path,
 	depth: UpdateDepth,
 	client: &Client,
 ) -> anyhow::Result<FabricQuiltMeta> {
 	let meta = get_all_meta(version, mode, internal_dir, depth, client).await?;
-	let meta = if let Some(fq_version) = fq_version {
-		meta.into_iter()
-			.find(|x| x.loader.maven.contains(fq_version))
-			.with_context(|| format!("Specified version {fq_version} does not exist"))?
-	} else {
-		meta.first()
-			.ok_or(anyhow!("Could not find a valid {mode} version"))?
-			.clone()
-	};
+	let versions = get_loader_versions(&meta);
+	let selected = select_loader_version(fq_version, &versions)
+		.with_context(|| format!("Could not find a {mode} version matching {fq_version}"))?;
+	let meta = meta
+		.into_iter()
+		.find(|x| get_loader_version_name(x) == selected.version)
+		.ok_or(anyhow!("Could not find a valid {mode} version"))?;
 
 	Ok(meta)
 }
 
+/// Gets the loader versions in a list of metadata, sorted from oldest to newest
+pub fn get_loader_versions(meta: &[FabricQuiltMeta]) -> Vec<LoaderVersion> {
+	// The metadata is sorted from newest to oldest
+	meta.iter()
+		.rev()
+		.map(|x| {
+			let version = get_loader_version_name(x);
+			LoaderVersion {
+				stable: x
+					.loader
+					.stable
+					.unwrap_or_else(|| !is_unstable_version_name(&version)),
+				version,
+			}
+		})
+		.collect()
+}
+
+/// Gets the version of the loader in metadata
+fn get_loader_version_name(meta: &FabricQuiltMeta) -> String {
+	meta.loader
+		.get_maven()
+		.map(|x| x.version)
+		.unwrap_or_else(|| meta.loader.maven.clone())
+}
+
 /// Download files for Quilt/Fabric that are common for both client and server
 pub async fn download_files(
 	meta: &FabricQuiltMeta,
@@ -1,11 +1,17 @@
-use std::{fmt::Display, ops::DerefMut, path::Path, process::Command};
+use std::{
+	collections::HashSet,
+	fmt::Display,
+	ops::DerefMut,
+	path::{Path, PathBuf},
+	process::Command,
+};
 
 use anyhow::{Context, bail};
 use nitro_core::{
 	io::{
 		files::create_leading_dirs,
 		java::classpath::{CLASSPATH_SEP, Classpath},
-		json_from_file,
+		json_from_file, json_to_file_replace,
 	},
 	net::game_files::{
 		client_meta::{
@@ -16,12 +22,17 @@ use nitro_core::{
 	},
 };
 use nitro_net::neoforge;
+use nitro_net::resume::get_partial_dir;
 use nitro_shared::{
-	Side, UpdateDepth, no_window,
+	Side, UpdateDepth,
+	io::mark_file_used,
+	minecraft::NativesArch,
+	no_window,
 	output::{MessageContents, NitroOutput},
 	versions::VersionInfo,
 };
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 
 /// Mode we are in (Forge / NeoForge)
 /// This way we don't have to duplicate a lot of functions since these both
@@ -78,13 +89,20 @@ pub async fn install(
 		)));
 
 		match mode {
-			Mode::NeoForge => neoforge::download_installer(forge_version, &installer_path, client)
-				.await
-				.context("Failed to download installer")?,
+			Mode::NeoForge => neoforge::download_installer(
+				forge_version,
+				&installer_path,
+				&get_partial_dir(internal_dir),
+				client,
+			)
+			.await
+			.context("Failed to download installer")?,
 		}
 		process.display(MessageContents::Success(format!(
 			"{mode} installer downloaded"
 		)));
+	} else {
+		mark_file_used(&installer_path);
 	}
 
 	if side == Side::Client {
@@ -97,29 +115,33 @@ pub async fn install(
 		.join(format!("{}-{forge_version}", mode.to_str()))
 		.join(format!("{}-{forge_version}.json", mode.to_str()));
 
-	let server_jar_path = match mode {
-		Mode::NeoForge => internal_dir
-			.join("libraries")
-			.join("net/neoforged/neoforge/{forge_version}/neoforge-{forge_version}-server.jar"),
-	};
-
-	let already_installed = match side {
-		Side::Client => client_meta_path.exists(),
-		Side::Server => server_jar_path.exists(),
-	};
-	let already_installed = already_installed || update_depth == UpdateDepth::Force;
-
 	let mut process = o.get_process();
 	process.display(MessageContents::StartProcess(format!(
 		"Checking {mode} version info"
 	)));
 
+	// The installer processors take a long time, so their outputs are cached and the installer is only run
+	// again if they are missing or changed
+	let cache_path =
+		get_installer_cache_path(&forge_dir, &version_info.version, forge_version, side);
+	let already_installed =
+		update_depth != UpdateDepth::Force && is_installer_cache_valid(&cache_path, internal_dir);
+
 	// Run the installer if not not installed
 	if !already_installed {
 		process.display(MessageContents::StartProcess(format!(
 			"Running {mode} installer"
 		)));
 
+		let output_dirs = [
+			internal_dir.join("libraries"),
+			internal_dir.join("versions"),
+		];
+		let mut files_before = HashSet::new();
+		for dir in &output_dirs {
+			list_files(dir, &mut files_before).context("Failed to list installed files")?;
+		}
+
 		let result = run_installer(
 			&installer_path,
 			side,
@@ -138,6 +160,17 @@ pub async fn install(
 			bail!("Failed to run installer: {e}");
 		}
 
+		let mut files_after = HashSet::new();
+		for dir in &output_dirs {
+			list_files(dir, &mut files_after).context("Failed to list installed files")?;
+		}
+		let mut outputs: Vec<_> = files_after.difference(&files_before).cloned().collect();
+		if side == Side::Client && !outputs.contains(&client_meta_path) {
+			outputs.push(client_meta_path.clone());
+		}
+		write_installer_cache(&cache_path, &outputs, internal_dir)
+			.context("Failed to write installer cache")?;
+
 		process.display(MessageContents::Success(format!("{mode} installer ran")));
 	}
 
@@ -178,8 +211,14 @@ pub async fn install(
 				})
 				.collect();
 
-			let classpath = get_classpath(&client_meta.libraries, internal_dir)
-				.context("Failed to get classpath")?;
+			// The installer always puts libraries in the internal directory
+			let classpath = get_classpath(
+				&client_meta.libraries,
+				internal_dir,
+				&internal_dir.join("libraries"),
+				NativesArch::detect(),
+			)
+			.context("Failed to get classpath")?;
 
 			process.display(MessageContents::Success(format!("{mode} installed")));
 
@@ -212,6 +251,94 @@ pub struct ForgeInstallResult {
 	pub exclude_game_jar: bool,
 }
 
+/// A record of the files that running the installer produced for a Minecraft and loader version,
+/// such as patched JARs and generated libraries
+#[derive(Serialize, Deserialize, Default)]
+struct InstallerCache {
+	/// The output files of the installer
+	files: Vec<InstallerCacheFile>,
+}
+
+/// A single output file in the installer cache
+#[derive(Serialize, Deserialize)]
+struct InstallerCacheFile {
+	/// The path to the file, relative to the internal directory
+	path: String,
+	/// The size of the file in bytes
+	size: u64,
+}
+
+/// Gets the path to the installer cache for a Minecraft and loader version
+fn get_installer_cache_path(
+	forge_dir: &Path,
+	minecraft_version: &str,
+	forge_version: &str,
+	side: Side,
+) -> PathBuf {
+	forge_dir
+		.join("installer_cache")
+		.join(format!("{minecraft_version}-{forge_version}-{side}.json"))
+}
+
+/// Checks whether the installer has already been run and all of its output files are still intact
+fn is_installer_cache_valid(cache_path: &Path, internal_dir: &Path) -> bool {
+	let Ok(cache) = json_from_file::<InstallerCache>(cache_path) else {
+		return false;
+	};
+
+	!cache.files.is_empty()
+		&& cache.files.iter().all(|file| {
+			internal_dir
+				.join(&file.path)
+				.metadata()
+				.is_ok_and(|x| x.is_file() && x.len() == file.size)
+		})
+}
+
+/// Records the output files of the installer in the cache
+fn write_installer_cache(
+	cache_path: &Path,
+	files: &[PathBuf],
+	internal_dir: &Path,
+) -> anyhow::Result<()> {
+	let mut cache = InstallerCache::default();
+	for file in files {
+		let Ok(rel_path) = file.strip_prefix(internal_dir) else {
+			continue;
+		};
+		let size = file
+			.metadata()
+			.with_context(|| format!("Failed to get metadata of file {}", file.display()))?
+			.len();
+		cache.files.push(InstallerCacheFile {
+			path: rel_path.to_string_lossy().to_string(),
+			size,
+		});
+	}
+	cache.files.sort_by(|a, b| a.path.cmp(&b.path));
+
+	create_leading_dirs(cache_path)?;
+	json_to_file_replace(cache_path, &cache)
+}
+
+/// Recursively lists all of the files in a directory
+fn list_files(dir: &Path, out: &mut HashSet<PathBuf>) -> anyhow::Result<()> {
+	if !dir.exists() {
+		return Ok(());
+	}
+
+	for entry in dir.read_dir()? {
+		let path = entry?.path();
+		if path.is_dir() {
+			list_files(&path, out)?;
+		} else {
+			out.insert(path);
+		}
+	}
+
+	Ok(())
+}
+
 /// Runs the installer at the given path
 fn run_installer(
 	path: &Path,
@@ -264,3 +391,36 @@ fn process_arg(arg: &str, libraries_dir: &Path, version_name: &str) -> String {
 
 	arg.replace("${version_name}", version_name)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_installer_cache() {
+		let dir = std::env::temp_dir().join("nitro_test_forge_installer_cache");
+		let _ = std::fs::remove_dir_all(&dir);
+		let lib_path = dir.join("libraries/net/neoforged/client-srg.jar");
+		create_leading_dirs(&lib_path).unwrap();
+		std::fs::write(&lib_path, "patched").unwrap();
+
+		let cache_path = get_installer_cache_path(
+			&dir.join("forge/neoforge"),
+			"1.21.1",
+			"21.1.1",
+			Side::Client,
+		);
+		assert!(!is_installer_cache_valid(&cache_path, &dir));
+
+		let mut files = HashSet::new();
+		list_files(&dir.join("libraries"), &mut files).unwrap();
+		let files: Vec<_> = files.into_iter().collect();
+		write_installer_cache(&cache_path, &files, &dir).unwrap();
+		assert!(is_installer_cache_valid(&cache_path, &dir));
+
+		std::fs::write(&lib_path, "truncated").unwrap();
+		assert!(!is_installer_cache_valid(&cache_path, &dir));
+
+		let _ = std::fs::remove_dir_all(&dir);
+	}
+}
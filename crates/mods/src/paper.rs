@@ -2,7 +2,7 @@ use std::{fmt::Display, path::PathBuf};
 
 use anyhow::{Context, anyhow, bail};
 use nitro_core::{NitroCore, net::download};
-use nitro_shared::{Side, output::NitroOutput, versions::VersionInfo};
+use nitro_shared::{Side, loaders::LoaderVersion, output::NitroOutput, versions::VersionInfo};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
@@ -149,6 +149,43 @@ pub async fn get_builds(mode: Mode, version: &str, client: &Client) -> anyhow::R
 	Ok(resp.builds)
 }
 
+/// Get all available builds of a PaperMC project version, sorted from oldest to newest.
+/// Experimental builds are marked as unstable
+pub async fn get_build_versions(
+	mode: Mode,
+	version: &str,
+	client: &Client,
+) -> anyhow::Result<Vec<LoaderVersion>> {
+	let url = format!(
+		"https://api.papermc.io/v2/projects/{}/versions/{version}/builds",
+		mode.to_str(),
+	);
+	let resp: BuildsResponse = download::json(url, client).await?;
+
+	let mut builds = resp.builds;
+	builds.sort_by_key(|x| x.build);
+
+	Ok(builds
+		.into_iter()
+		.map(|x| LoaderVersion {
+			version: x.build.to_string(),
+			stable: x.channel != "experimental",
+		})
+		.collect())
+}
+
+#[derive(Deserialize)]
+struct BuildsResponse {
+	builds: Vec<BuildsResponseBuild>,
+}
+
+#[derive(Deserialize)]
+struct BuildsResponseBuild {
+	build: u16,
+	#[serde(default)]
+	channel: String,
+}
+
 /// Get the newest build number of a PaperMC project version
 pub async fn get_newest_build(mode: Mode, version: &str, client: &Client) -> anyhow::Result<u16> {
 	let builds = get_builds(mode, version, client).await?;
@@ -3,6 +3,7 @@ use std::{collections::HashMap, path::PathBuf};
 use anyhow::Context;
 use nitro_core::net::download;
 use nitro_shared::Side;
+use nitro_shared::loaders::LoaderVersion;
 use reqwest::Client;
 use serde::Deserialize;
 
@@ -28,24 +29,40 @@ impl Mode {
 	}
 }
 
-/// Get the available artifacts of a Sponge project
+/// Get the available artifacts of a Sponge project, sorted from oldest to newest.
+/// Artifacts that are not recommended by Sponge are marked as unstable
 pub async fn get_artifacts(
 	mode: Mode,
 	version: &str,
 	client: &Client,
-) -> anyhow::Result<Vec<String>> {
+) -> anyhow::Result<Vec<LoaderVersion>> {
 	let url = format!(
 		"https://dl-api.spongepowered.org/v2/groups/org.spongepowered/artifacts/{}/versions?tags=,minecraft:{version}",
 		mode.to_str(),
 	);
 	let resp: Versions = download::json(url, client).await?;
 
-	let mut artifacts: Vec<_> = resp.artifacts.into_keys().collect();
-	artifacts.reverse();
+	let mut artifacts: Vec<_> = resp
+		.artifacts
+		.into_iter()
+		.map(|(version, artifact)| LoaderVersion {
+			version,
+			stable: artifact.recommended,
+		})
+		.collect();
+	artifacts.sort_by(|a, b| compare_artifacts(&a.version, &b.version));
 
 	Ok(artifacts)
 }
 
+/// Compares two artifact versions
+fn compare_artifacts(a: &str, b: &str) -> std::cmp::Ordering {
+	version_compare::compare(a, b)
+		.ok()
+		.and_then(|x| x.ord())
+		.unwrap_or_else(|| a.cmp(b))
+}
+
 #[derive(Deserialize, Clone)]
 struct Versions {
 	artifacts: HashMap<String, Artifact>,
@@ -53,7 +70,11 @@ struct Versions {
 
 /// A single download artifact
 #[derive(Deserialize, Debug, Clone)]
-struct Artifact {}
+struct Artifact {
+	/// Whether this artifact is recommended for use
+	#[serde(default)]
+	recommended: bool,
+}
 
 /// Fetches information about an artifact from the API
 pub async fn get_artifact_info(
@@ -120,3 +141,22 @@ pub fn get_local_jar_path(mode: Mode, version: &str, paths: &Paths) -> PathBuf {
 		&paths.jars,
 	)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_artifact_order() {
+		let mut artifacts = [
+			"1.21.1-12.1.0-RC1970",
+			"1.21.1-12.0.2",
+			"1.21.1-12.0.10",
+			"1.21.1-12.0.9",
+		];
+		artifacts.sort_by(|a, b| compare_artifacts(a, b));
+		assert_eq!(artifacts[0], "1.21.1-12.0.2");
+		assert_eq!(artifacts[2], "1.21.1-12.0.10");
+		assert_eq!(artifacts[3], "1.21.1-12.1.0-RC1970");
+	}
+}
@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Downloads that are currently in progress, by their ID
+static DOWNLOADS: LazyLock<Mutex<HashMap<u64, DownloadEntry>>> =
+	LazyLock::new(|| Mutex::new(HashMap::new()));
+/// The ID for the next tracked download
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+/// Total number of bytes downloaded by this process
+static TOTAL_DOWNLOADED: AtomicU64 = AtomicU64::new(0);
+/// Whether all downloads are paused
+static PAUSED: AtomicBool = AtomicBool::new(false);
+/// The global bandwidth limit in bytes per second, or zero for no limit
+static BANDWIDTH_LIMIT: AtomicU64 = AtomicU64::new(0);
+/// The bandwidth limit for each individual download in bytes per second, or zero for no limit
+static DOWNLOAD_BANDWIDTH_LIMIT: AtomicU64 = AtomicU64::new(0);
+/// The environment variable used to pass the global bandwidth limit to plugin processes
+pub const BANDWIDTH_LIMIT_ENV: &str = "NITRO_BANDWIDTH_LIMIT";
+/// The environment variable used to pass the per-download bandwidth limit to plugin processes
+pub const DOWNLOAD_BANDWIDTH_LIMIT_ENV: &str = "NITRO_DOWNLOAD_BANDWIDTH_LIMIT";
+/// The global bandwidth limit given by the process that started us
+static BANDWIDTH_LIMIT_FROM_ENV: LazyLock<u64> =
+	LazyLock::new(|| read_limit_env(BANDWIDTH_LIMIT_ENV));
+/// The per-download bandwidth limit given by the process that started us
+static DOWNLOAD_BANDWIDTH_LIMIT_FROM_ENV: LazyLock<u64> =
+	LazyLock::new(|| read_limit_env(DOWNLOAD_BANDWIDTH_LIMIT_ENV));
+/// Bytes downloaded in the current bandwidth limiting window, and when that window started
+static LIMIT_WINDOW: LazyLock<Mutex<(Instant, u64)>> =
+	LazyLock::new(|| Mutex::new((Instant::now(), 0)));
+
+/// How long to wait between checks when downloads are paused
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Information about a download that is in progress
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DownloadActivity {
+	/// The URL being downloaded
+	pub url: String,
+	/// The number of bytes downloaded so far
+	pub downloaded: u64,
+	/// The total size of the download, if known
+	pub total: Option<u64>,
+	/// Average download speed in bytes per second
+	pub speed: u64,
+}
+
+/// Overview of all download activity
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DownloadActivitySummary {
+	/// Downloads that are in progress
+	pub downloads: Vec<DownloadActivity>,
+	/// Total number of bytes downloaded since the launcher started
+	pub total_downloaded: u64,
+	/// Combined speed of all current downloads in bytes per second
+	pub total_speed: u64,
+	/// Whether downloads are paused
+	pub paused: bool,
+	/// The bandwidth limit in bytes per second
+	pub bandwidth_limit: Option<u64>,
+	/// The bandwidth limit for each download in bytes per second
+	pub download_bandwidth_limit: Option<u64>,
+}
+
+struct DownloadEntry {
+	url: String,
+	downloaded: u64,
+	total: Option<u64>,
+	started: Instant,
+}
+
+/// A download registered with the activity tracker. The download is removed from the tracker when this is dropped
+pub struct TrackedDownload {
+	id: u64,
+	/// The bandwidth limiting window for just this download
+	limit_window: Mutex<(Instant, u64)>,
+}
+
+impl TrackedDownload {
+	/// Starts tracking a new download
+	pub fn new(url: &str, total: Option<u64>) -> Self {
+		let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+		if let Ok(mut downloads) = DOWNLOADS.lock() {
+			downloads.insert(
+				id,
+				DownloadEntry {
+					url: url.to_string(),
+					downloaded: 0,
+					total,
+					started: Instant::now(),
+				},
+			);
+		}
+
+		Self {
+			id,
+			limit_window: Mutex::new((Instant::now(), 0)),
+		}
+	}
+
+	/// Records downloaded bytes. Waits while downloads are paused or over the bandwidth limit,
+	/// so this should be called for every chunk before the next one is requested
+	pub async fn record(&self, bytes: usize) {
+		let bytes = bytes as u64;
+		TOTAL_DOWNLOADED.fetch_add(bytes, Ordering::Relaxed);
+		if let Ok(mut downloads) = DOWNLOADS.lock()
+			&& let Some(entry) = downloads.get_mut(&self.id)
+		{
+			entry.downloaded += bytes;
+		}
+
+		while is_paused() {
+			tokio::time::sleep(PAUSE_POLL_INTERVAL).await;
+		}
+
+		// Both limits are counted so that neither of them is exceeded
+		let global_delay = get_limit_delay(bytes);
+		let download_delay = get_download_bandwidth_limit().and_then(|limit| {
+			let mut window = self.limit_window.lock().ok()?;
+			get_window_delay(&mut window, bytes, limit)
+		});
+		if let Some(delay) = global_delay.max(download_delay) {
+			tokio::time::sleep(delay).await;
+		}
+	}
+}
+
+impl Drop for TrackedDownload {
+	fn drop(&mut self) {
+		if let Ok(mut downloads) = DOWNLOADS.lock() {
+			downloads.remove(&self.id);
+		}
+	}
+}
+
+/// Adds bytes to the bandwidth limiting window and gets how long to wait to stay under the limit
+fn get_limit_delay(bytes: u64) -> Option<Duration> {
+	let limit = get_bandwidth_limit()?;
+	let mut window = LIMIT_WINDOW.lock().ok()?;
+	get_window_delay(&mut window, bytes, limit)
+}
+
+/// Adds bytes to a bandwidth limiting window and gets how long to wait to stay under a limit
+fn get_window_delay(window: &mut (Instant, u64), bytes: u64, limit: u64) -> Option<Duration> {
+	// Start a new window every second so that old bursts don't affect current downloads
+	if window.0.elapsed() > Duration::from_secs(1) {
+		*window = (Instant::now(), 0);
+	}
+	window.1 += bytes;
+
+	let allowed_time = Duration::from_secs_f64(window.1 as f64 / limit as f64);
+	allowed_time.checked_sub(window.0.elapsed())
+}
+
+/// Gets information about all downloads that are currently in progress
+pub fn get_downloads() -> Vec<DownloadActivity> {
+	let Ok(downloads) = DOWNLOADS.lock() else {
+		return Vec::new();
+	};
+
+	let mut out: Vec<_> = downloads
+		.iter()
+		.map(|(id, entry)| {
+			let elapsed = entry.started.elapsed().as_secs_f64();
+			let speed = if elapsed > 0.0 {
+				(entry.downloaded as f64 / elapsed) as u64
+			} else {
+				0
+			};
+
+			(
+				*id,
+				DownloadActivity {
+					url: entry.url.clone(),
+					downloaded: entry.downloaded,
+					total: entry.total,
+					speed,
+				},
+			)
+		})
+		.collect();
+	// Keep a stable order, oldest first
+	out.sort_by_key(|x| x.0);
+
+	out.into_iter().map(|x| x.1).collect()
+}
+
+/// Gets an overview of all download activity
+pub fn get_summary() -> DownloadActivitySummary {
+	let downloads = get_downloads();
+	let total_speed = downloads.iter().map(|x| x.speed).sum();
+
+	DownloadActivitySummary {
+		downloads,
+		total_downloaded: TOTAL_DOWNLOADED.load(Ordering::Relaxed),
+		total_speed,
+		paused: is_paused(),
+		bandwidth_limit: get_bandwidth_limit(),
+		download_bandwidth_limit: get_download_bandwidth_limit(),
+	}
+}
+
+/// Pauses or resumes all downloads
+pub fn set_paused(paused: bool) {
+	PAUSED.store(paused, Ordering::Relaxed);
+}
+
+/// Checks whether downloads are paused
+pub fn is_paused() -> bool {
+	PAUSED.load(Ordering::Relaxed)
+}
+
+/// Sets the global bandwidth limit in bytes per second
+pub fn set_bandwidth_limit(limit: Option<u64>) {
+	BANDWIDTH_LIMIT.store(limit.unwrap_or_default(), Ordering::Relaxed);
+}
+
+/// Gets the global bandwidth limit in bytes per second. Falls back to the limit
+/// given by the process that started us
+pub fn get_bandwidth_limit() -> Option<u64> {
+	match BANDWIDTH_LIMIT.load(Ordering::Relaxed) {
+		0 => Some(*BANDWIDTH_LIMIT_FROM_ENV).filter(|x| *x > 0),
+		limit => Some(limit),
+	}
+}
+
+/// Sets the bandwidth limit for each individual download in bytes per second
+pub fn set_download_bandwidth_limit(limit: Option<u64>) {
+	DOWNLOAD_BANDWIDTH_LIMIT.store(limit.unwrap_or_default(), Ordering::Relaxed);
+}
+
+/// Gets the bandwidth limit for each individual download in bytes per second. Falls back to the limit
+/// given by the process that started us
+pub fn get_download_bandwidth_limit() -> Option<u64> {
+	match DOWNLOAD_BANDWIDTH_LIMIT.load(Ordering::Relaxed) {
+		0 => Some(*DOWNLOAD_BANDWIDTH_LIMIT_FROM_ENV).filter(|x| *x > 0),
+		limit => Some(limit),
+	}
+}
+
+/// Gets the environment variables that pass the current bandwidth limits to plugin processes.
+/// Each process limits its own downloads separately
+pub fn get_bandwidth_limit_env_vars() -> Vec<(&'static str, String)> {
+	let mut out = Vec::new();
+	if let Some(limit) = get_bandwidth_limit() {
+		out.push((BANDWIDTH_LIMIT_ENV, limit.to_string()));
+	}
+	if let Some(limit) = get_download_bandwidth_limit() {
+		out.push((DOWNLOAD_BANDWIDTH_LIMIT_ENV, limit.to_string()));
+	}
+
+	out
+}
+
+/// Reads a bandwidth limit from an environment variable, or zero if it is not set
+fn read_limit_env(var: &str) -> u64 {
+	std::env::var(var)
+		.ok()
+		.and_then(|x| x.parse().ok())
+		.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_tracked_download_removed_on_drop() {
+		let download = TrackedDownload::new("https://example.com/tracked", Some(10));
+		assert!(
+			get_downloads()
+				.iter()
+				.any(|x| x.url == "https://example.com/tracked")
+		);
+		std::mem::drop(download);
+		assert!(
+			!get_downloads()
+				.iter()
+				.any(|x| x.url == "https://example.com/tracked")
+		);
+	}
+
+	#[test]
+	fn test_window_delay() {
+		let mut window = (Instant::now(), 0);
+		let delay = get_window_delay(&mut window, 1000, 1000).expect("Should be over the limit");
+		assert!(delay > Duration::from_millis(900));
+		assert_eq!(window.1, 1000);
+
+		// An old window is started over
+		let Some(start) = Instant::now().checked_sub(Duration::from_secs(5)) else {
+			return;
+		};
+		let mut window = (start, 100_000);
+		assert!(
+			get_window_delay(&mut window, 10, 1000).unwrap_or_default() < Duration::from_millis(20)
+		);
+		assert_eq!(window.1, 10);
+	}
+}
@@ -0,0 +1,240 @@
+use std::path::Path;
+
+use anyhow::Context;
+use nitro_shared::Side;
+use nitro_shared::loaders::Loader;
+use nitro_shared::util::utc_timestamp;
+use nitro_shared::versions::{VersionInfo, VersionPattern};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::download;
+
+/// URL to the newest list of advisories
+pub const ADVISORIES_URL: &str =
+	"https://raw.githubusercontent.com/Nitrolaunch/nitrolaunch/main/crates/net/src/advisories.json";
+/// How long the cached list of advisories is used before it is fetched again, in seconds
+const ADVISORIES_CACHE_MAX_AGE: u64 = 60 * 60 * 24;
+
+/// A known security problem with some versions of the game or a loader
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Advisory {
+	/// Unique ID for the advisory, like a CVE number
+	pub id: String,
+	/// A short name for the problem
+	pub title: String,
+	/// An explanation of the problem and what it can do
+	pub description: String,
+	/// How bad the problem is
+	pub severity: AdvisorySeverity,
+	/// A link to more information
+	#[serde(default)]
+	pub url: Option<String>,
+	/// The versions that have the problem
+	pub affected: Vec<AffectedVersions>,
+	/// Ways to fix the problem without changing versions
+	#[serde(default)]
+	pub mitigations: Vec<Mitigation>,
+}
+
+impl Advisory {
+	/// Checks if an instance with the given version and loader is affected by this advisory
+	pub fn affects(
+		&self,
+		version_info: &VersionInfo,
+		loader: &Loader,
+		loader_version: Option<&str>,
+	) -> bool {
+		self.affected
+			.iter()
+			.any(|x| x.matches(version_info, loader, loader_version))
+	}
+
+	/// Gets the mitigation for this advisory that applies to the given version and side, if any
+	pub fn get_mitigation(&self, version_info: &VersionInfo, side: Side) -> Option<&Mitigation> {
+		self.mitigations.iter().find(|x| {
+			x.minecraft_versions.matches_info(version_info) && x.side.is_none_or(|x| x == side)
+		})
+	}
+}
+
+/// How bad the problem in an advisory is
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum AdvisorySeverity {
+	/// Minor problems
+	Low,
+	/// Problems that need specific conditions to be used
+	Medium,
+	/// Serious problems
+	High,
+	/// Problems that can easily be used to take over the user's computer
+	Critical,
+}
+
+/// A set of versions that an advisory applies to
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AffectedVersions {
+	/// The Minecraft versions that are affected
+	#[serde(default)]
+	pub minecraft_versions: VersionPattern,
+	/// The loader that is affected. If not specified, all loaders are affected
+	#[serde(default)]
+	pub loader: Option<Loader>,
+	/// The loader versions that are affected, if a loader is specified
+	#[serde(default)]
+	pub loader_versions: Vec<String>,
+}
+
+impl AffectedVersions {
+	/// Checks if a version and loader are in this set
+	pub fn matches(
+		&self,
+		version_info: &VersionInfo,
+		loader: &Loader,
+		loader_version: Option<&str>,
+	) -> bool {
+		if !self.minecraft_versions.matches_info(version_info) {
+			return false;
+		}
+
+		let Some(affected_loader) = &self.loader else {
+			return true;
+		};
+		if affected_loader != loader {
+			return false;
+		}
+
+		// Without a known loader version, we have to assume that it is affected
+		self.loader_versions.is_empty()
+			|| loader_version.is_none_or(|x| self.loader_versions.iter().any(|y| x == y))
+	}
+}
+
+/// A way to fix the problem in an advisory
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Mitigation {
+	/// The Minecraft versions that this mitigation works for
+	#[serde(default)]
+	pub minecraft_versions: VersionPattern,
+	/// The side that this mitigation works for. If not specified, it works for both
+	#[serde(default)]
+	pub side: Option<Side>,
+	/// What the mitigation does
+	pub description: String,
+	/// JVM arguments to add
+	#[serde(default)]
+	pub jvm_args: Vec<String>,
+	/// Whether to use the logging configuration from the version metadata
+	#[serde(default)]
+	pub use_log4j_config: bool,
+}
+
+/// Gets the list of advisories, using the given cache file when it is recent enough.
+/// Falls back to an old cache or the built-in list if the newest one can't be fetched
+pub async fn get_advisories(cache_path: &Path, client: &Client) -> anyhow::Result<Vec<Advisory>> {
+	let cached: Option<AdvisoriesCache> = std::fs::read(cache_path)
+		.ok()
+		.and_then(|x| serde_json::from_slice(&x).ok());
+
+	let is_fresh = cached.as_ref().is_some_and(|cached| {
+		utc_timestamp()
+			.is_ok_and(|now| now.saturating_sub(cached.timestamp) < ADVISORIES_CACHE_MAX_AGE)
+	});
+
+	if let Some(cached) = &cached
+		&& (is_fresh || download::is_offline())
+	{
+		return Ok(cached.advisories.clone());
+	}
+
+	match download::json::<Vec<Advisory>>(ADVISORIES_URL, client).await {
+		Ok(advisories) => {
+			let cache = AdvisoriesCache {
+				timestamp: utc_timestamp().unwrap_or_default(),
+				advisories,
+			};
+			if let Ok(data) = serde_json::to_vec(&cache) {
+				let _ = std::fs::write(cache_path, data);
+			}
+
+			Ok(cache.advisories)
+		}
+		Err(..) => match cached {
+			Some(cached) => Ok(cached.advisories),
+			None => get_builtin_advisories(),
+		},
+	}
+}
+
+/// Gets the list of advisories that is included with the launcher
+pub fn get_builtin_advisories() -> anyhow::Result<Vec<Advisory>> {
+	serde_json::from_str(include_str!("advisories.json"))
+		.context("Failed to deserialize built-in advisories")
+}
+
+/// Cached list of advisories
+#[derive(Serialize, Deserialize)]
+struct AdvisoriesCache {
+	/// When the advisories were fetched
+	timestamp: u64,
+	/// The advisories
+	advisories: Vec<Advisory>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn version_info(version: &str) -> VersionInfo {
+		VersionInfo {
+			version: version.into(),
+			versions: ["1.6.4", "1.7.2", "1.16.5", "1.17", "1.18", "1.18.1"]
+				.into_iter()
+				.map(String::from)
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn test_builtin_advisories() {
+		let advisories = get_builtin_advisories().unwrap();
+		let log4shell = advisories
+			.iter()
+			.find(|x| x.id == "CVE-2021-44228")
+			.unwrap();
+
+		assert!(log4shell.affects(&version_info("1.7.2"), &Loader::Vanilla, None));
+		assert!(log4shell.affects(&version_info("1.18"), &Loader::Fabric, None));
+		assert!(!log4shell.affects(&version_info("1.6.4"), &Loader::Vanilla, None));
+		assert!(!log4shell.affects(&version_info("1.18.1"), &Loader::Vanilla, None));
+
+		let mitigation = log4shell
+			.get_mitigation(&version_info("1.17"), Side::Server)
+			.unwrap();
+		assert!(!mitigation.jvm_args.is_empty());
+		assert!(
+			log4shell
+				.get_mitigation(&version_info("1.16.5"), Side::Server)
+				.is_none()
+		);
+		assert!(
+			log4shell
+				.get_mitigation(&version_info("1.16.5"), Side::Client)
+				.is_some_and(|x| x.use_log4j_config)
+		);
+	}
+
+	#[test]
+	fn test_loader_advisory() {
+		let affected = AffectedVersions {
+			minecraft_versions: VersionPattern::Any,
+			loader: Some(Loader::Fabric),
+			loader_versions: vec!["0.1.0".into()],
+		};
+
+		assert!(affected.matches(&version_info("1.18"), &Loader::Fabric, Some("0.1.0")));
+		assert!(!affected.matches(&version_info("1.18"), &Loader::Fabric, Some("0.2.0")));
+		assert!(!affected.matches(&version_info("1.18"), &Loader::Quilt, Some("0.1.0")));
+	}
+}
@@ -2,7 +2,7 @@ use anyhow::Context;
 use reqwest::Client;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
-use crate::download::user_agent;
+use crate::download::{self, user_agent};
 
 /// Requests a sub-url from the CurseForge API
 pub async fn request_api<D: DeserializeOwned>(
@@ -10,17 +10,15 @@ pub async fn request_api<D: DeserializeOwned>(
 	api_key: &str,
 	client: &Client,
 ) -> anyhow::Result<D> {
-	let resp = client
-		.get(String::from("https://api.curseforge.com/") + url_path)
-		.header("User-Agent", user_agent())
-		.header("x-api-key", api_key)
-		.send()
-		.await
-		.context("Failed to send request")?
-		.error_for_status()
-		.context("Server reported an error")?;
-
-	Ok(resp.error_for_status()?.json().await?)
+	let resp = download::send(
+		client
+			.get(String::from("https://api.curseforge.com/") + url_path)
+			.header("User-Agent", user_agent())
+			.header("x-api-key", api_key),
+	)
+	.await?;
+
+	Ok(download::check_status(resp)?.json().await?)
 }
 
 /// Requests a sub-url from the CurseForge API for text
@@ -29,17 +27,15 @@ pub async fn request_api_raw(
 	api_key: &str,
 	client: &Client,
 ) -> anyhow::Result<String> {
-	let resp = client
-		.get(String::from("https://api.curseforge.com/") + url_path)
-		.header("x-api-key", api_key)
-		.header("User-Agent", user_agent())
-		.send()
-		.await
-		.context("Failed to send request")?
-		.error_for_status()
-		.context("Server reported an error")?;
-
-	Ok(resp.error_for_status()?.text().await?)
+	let resp = download::send(
+		client
+			.get(String::from("https://api.curseforge.com/") + url_path)
+			.header("x-api-key", api_key)
+			.header("User-Agent", user_agent()),
+	)
+	.await?;
+
+	Ok(download::check_status(resp)?.text().await?)
 }
 
 /// Gets a CurseForge mod with the given ID from the API
@@ -77,3 +73,146 @@ pub struct CurseMod {
 	/// How many downloads the mod has
 	pub download_count: u32,
 }
+
+/// Sends a POST request with a JSON body to the CurseForge API
+pub async fn post_api<B: Serialize, D: DeserializeOwned>(
+	url_path: &str,
+	body: &B,
+	api_key: &str,
+	client: &Client,
+) -> anyhow::Result<D> {
+	let resp = download::send(
+		client
+			.post(String::from("https://api.curseforge.com/") + url_path)
+			.header("User-Agent", user_agent())
+			.header("x-api-key", api_key)
+			.json(body),
+	)
+	.await?
+	.error_for_status()
+	.context("Server reported an error")?;
+
+	Ok(resp.json().await?)
+}
+
+/// Finds the CurseForge files that match a list of file fingerprints
+pub async fn get_fingerprint_matches(
+	fingerprints: &[u32],
+	api_key: &str,
+	client: &Client,
+) -> anyhow::Result<Vec<FingerprintMatch>> {
+	if fingerprints.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	let body = serde_json::json!({ "fingerprints": fingerprints });
+	let response: FingerprintMatchesResponse =
+		post_api("v1/fingerprints", &body, api_key, client).await?;
+	Ok(response.data.exact_matches)
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FingerprintMatchesResponse {
+	data: FingerprintMatches,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FingerprintMatches {
+	#[serde(default)]
+	exact_matches: Vec<FingerprintMatch>,
+}
+
+/// A file that matched a fingerprint
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FingerprintMatch {
+	/// The ID of the mod the file is from
+	pub id: u32,
+	/// The file that matched
+	pub file: CurseFile,
+}
+
+/// A file of a CurseForge mod
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseFile {
+	/// Unique ID of the file
+	pub id: u32,
+	/// The ID of the mod the file is from
+	pub mod_id: u32,
+	/// The name of the file
+	pub file_name: String,
+	/// The URL to download the file from. Will be missing if the author disabled third-party downloads
+	#[serde(default)]
+	pub download_url: Option<String>,
+	/// The fingerprint of the file
+	pub file_fingerprint: u32,
+}
+
+/// Computes the CurseForge fingerprint of file contents, which is a MurmurHash2 of the contents
+/// with whitespace bytes removed
+pub fn compute_fingerprint(data: &[u8]) -> u32 {
+	let normalized: Vec<u8> = data
+		.iter()
+		.copied()
+		.filter(|x| !matches!(x, 9 | 10 | 13 | 32))
+		.collect();
+
+	murmur2(&normalized, 1)
+}
+
+/// 32-bit MurmurHash2
+fn murmur2(data: &[u8], seed: u32) -> u32 {
+	const M: u32 = 0x5bd1e995;
+	const R: u32 = 24;
+
+	let mut h = seed ^ (data.len() as u32);
+
+	let mut chunks = data.chunks_exact(4);
+	for chunk in &mut chunks {
+		let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+		k = k.wrapping_mul(M);
+		k ^= k >> R;
+		k = k.wrapping_mul(M);
+
+		h = h.wrapping_mul(M);
+		h ^= k;
+	}
+
+	let tail = chunks.remainder();
+	if !tail.is_empty() {
+		if tail.len() >= 3 {
+			h ^= (tail[2] as u32) << 16;
+		}
+		if tail.len() >= 2 {
+			h ^= (tail[1] as u32) << 8;
+		}
+		h ^= tail[0] as u32;
+		h = h.wrapping_mul(M);
+	}
+
+	h ^= h >> 13;
+	h = h.wrapping_mul(M);
+	h ^= h >> 15;
+
+	h
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_fingerprint_ignores_whitespace() {
+		assert_eq!(
+			compute_fingerprint(b"hello world\r\n"),
+			compute_fingerprint(b"helloworld")
+		);
+		assert_ne!(
+			compute_fingerprint(b"hello world"),
+			compute_fingerprint(b"hello")
+		);
+	}
+}
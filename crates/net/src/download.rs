@@ -1,31 +1,342 @@
 use std::fs::File;
+use std::hash::{BuildHasher, Hasher, RandomState};
 use std::io::{BufWriter, Cursor, Write};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, Instant};
 
-use anyhow::{Context, ensure};
+use anyhow::{Context, bail, ensure};
+use nitro_shared::error::NetworkError;
 use nitro_shared::output::MessageContents;
-use reqwest::{IntoUrl, Url};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{IntoUrl, RequestBuilder, Url};
 use serde::de::DeserializeOwned;
 
+use crate::activity::TrackedDownload;
+use crate::{fixtures, stats, throttle};
+
 /// Re-export of reqwest::Client for users of this download module
 pub use reqwest::Client;
 
+/// Settings for sending requests through a proxy
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxySettings {
+	/// The URL of the proxy, such as `http://proxy.example.com:8080` or `socks5://127.0.0.1:1080`
+	pub url: String,
+	/// Hosts that are connected to directly instead of through the proxy. Domains, wildcard domains like
+	/// `*.example.com`, IP addresses, and IP ranges like `192.168.0.0/16` are supported
+	pub bypass: Vec<String>,
+}
+
+/// Creates a client for making requests, which sends them through a proxy if one is given
+pub fn create_client(proxy: Option<&ProxySettings>) -> anyhow::Result<Client> {
+	let mut builder = Client::builder();
+	if let Some(proxy) = proxy {
+		builder = builder.proxy(create_proxy(proxy)?);
+	}
+
+	builder.build().context("Failed to create HTTP client")
+}
+
+/// Creates the reqwest proxy from proxy settings
+fn create_proxy(settings: &ProxySettings) -> anyhow::Result<reqwest::Proxy> {
+	let scheme = settings.url.split_once("://").map(|x| x.0);
+	ensure!(
+		matches!(scheme, Some("http" | "https" | "socks5" | "socks5h")),
+		"Proxy URL '{}' must start with http://, https://, socks5://, or socks5h://",
+		settings.url
+	);
+
+	let proxy = reqwest::Proxy::all(&settings.url)
+		.with_context(|| format!("Invalid proxy URL '{}'", settings.url))?;
+
+	Ok(proxy.no_proxy(reqwest::NoProxy::from_string(&settings.bypass.join(","))))
+}
+
+/// The proxy that the launcher sends requests through, so that it can be passed to plugin processes
+static PROXY: RwLock<Option<ProxySettings>> = RwLock::new(None);
+
+/// Sets the proxy that the launcher sends requests through
+pub fn set_proxy(proxy: Option<ProxySettings>) {
+	if let Ok(mut lock) = PROXY.write() {
+		*lock = proxy;
+	}
+}
+
+/// Gets the proxy that the launcher sends requests through
+pub fn get_proxy() -> Option<ProxySettings> {
+	PROXY.read().ok().and_then(|x| x.clone())
+}
+
+/// Gets the standard environment variables that make other programs, including plugins
+/// using reqwest, send their requests through a proxy
+pub fn get_proxy_env_vars(proxy: &ProxySettings) -> Vec<(&'static str, String)> {
+	let mut out = vec![
+		("HTTP_PROXY", proxy.url.clone()),
+		("HTTPS_PROXY", proxy.url.clone()),
+		("ALL_PROXY", proxy.url.clone()),
+		// Some programs only read the lowercase version
+		("http_proxy", proxy.url.clone()),
+	];
+	if !proxy.bypass.is_empty() {
+		out.push(("NO_PROXY", proxy.bypass.join(",")));
+	}
+
+	out
+}
+
 /// The User-Agent header for requests
 pub fn user_agent() -> String {
 	let version = env!("CARGO_PKG_VERSION");
 	format!("nitro_core_{version}")
 }
 
+/// Error message for requests made while in offline mode
+pub const OFFLINE_ERROR: &str = "Cannot download while in offline mode";
+
+/// The environment variable used to pass offline mode to plugin processes
+pub const OFFLINE_ENV: &str = "NITRO_OFFLINE";
+
+/// Whether we are in offline mode, where only cached data is used
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Whether offline mode was enabled by the process that started us
+static OFFLINE_FROM_ENV: LazyLock<bool> =
+	LazyLock::new(|| std::env::var(OFFLINE_ENV).is_ok_and(|x| x == "1"));
+
+/// Sets whether we are in offline mode. In offline mode, downloads fail immediately
+/// instead of waiting on the network, so that cached data can be used instead
+pub fn set_offline(offline: bool) {
+	OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Checks whether we are in offline mode
+pub fn is_offline() -> bool {
+	OFFLINE.load(Ordering::Relaxed) || *OFFLINE_FROM_ENV
+}
+
+/// Fails if we are in offline mode
+pub fn ensure_online() -> anyhow::Result<()> {
+	if is_offline() {
+		bail!(NetworkError::Offline);
+	}
+
+	Ok(())
+}
+
+/// Checks whether the internet is reachable, regardless of offline mode. Several hosts are
+/// checked at once, along with the extra URLs such as download mirrors, and we are only
+/// considered offline when none of them respond
+pub async fn check_connectivity(client: &Client) -> bool {
+	let mut urls: Vec<String> = CONNECTIVITY_CHECK_URLS
+		.iter()
+		.map(ToString::to_string)
+		.collect();
+	if let Ok(extra) = CONNECTIVITY_CHECK_EXTRA_URLS.read() {
+		urls.extend(extra.iter().cloned());
+	}
+
+	let mut tasks = tokio::task::JoinSet::new();
+	for url in urls {
+		let client = client.clone();
+		tasks.spawn(async move {
+			client
+				.head(url)
+				.header("User-Agent", user_agent())
+				.timeout(Duration::from_secs(5))
+				.send()
+				.await
+				.is_ok()
+		});
+	}
+
+	while let Some(result) = tasks.join_next().await {
+		if result.unwrap_or_default() {
+			return true;
+		}
+	}
+
+	false
+}
+
+/// Sets extra URLs that are checked for connectivity, such as the ones of download mirrors
+pub fn set_connectivity_check_urls(urls: Vec<String>) {
+	if let Ok(mut lock) = CONNECTIVITY_CHECK_EXTRA_URLS.write() {
+		*lock = urls;
+	}
+}
+
+/// URLs on different hosts that are used to check for internet connectivity
+const CONNECTIVITY_CHECK_URLS: &[&str] = &[
+	"https://piston-meta.mojang.com/mc/game/version_manifest_v2.json",
+	"https://api.modrinth.com/",
+	"https://github.com/",
+];
+
+/// Extra URLs that are used to check for internet connectivity
+static CONNECTIVITY_CHECK_EXTRA_URLS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Sends a request. If any fixtures are installed, the response comes from them instead of
+/// the network, and if recording is enabled, the response is recorded as a fixture.
+/// Requests that fail because of temporary problems are retried according to the retry policy
+pub async fn send(request: RequestBuilder) -> anyhow::Result<reqwest::Response> {
+	let (client, request) = request.build_split();
+	let mut request = request.context("Failed to create request")?;
+
+	if fixtures::is_active() {
+		return fixtures::get_response(request.url())
+			.with_context(|| format!("No fixture exists for request to {}", request.url()))?;
+	}
+
+	ensure_online()?;
+
+	let policy = get_retry_policy();
+	let mut attempt = 1;
+	let resp = loop {
+		// Requests with streaming bodies can't be sent again
+		let next = if attempt < policy.attempts {
+			request.try_clone()
+		} else {
+			None
+		};
+
+		let result = execute(&client, request).await;
+		let Some(next) = next else {
+			break result?;
+		};
+
+		let delay = match &result {
+			Ok(resp) if policy.is_retryable_status(resp.status().as_u16()) => {
+				policy.get_delay(attempt).max(
+					get_retry_after(resp)
+						.unwrap_or_default()
+						.min(policy.max_delay),
+				)
+			}
+			Ok(..) => break result?,
+			Err(..) => policy.get_delay(attempt),
+		};
+		tokio::time::sleep(delay).await;
+
+		request = next;
+		attempt += 1;
+	};
+
+	if let Some(dir) = fixtures::get_recording_dir() {
+		fixtures::record(&dir, resp).await
+	} else {
+		Ok(resp)
+	}
+}
+
+/// Sends a single attempt of a request and records statistics about it
+async fn execute(client: &Client, request: reqwest::Request) -> anyhow::Result<reqwest::Response> {
+	let url = request.url().clone();
+	let start = Instant::now();
+	let resp = client.execute(request).await.map_err(|e| {
+		stats::record_failure(&url, start.elapsed());
+		let kind = if e.is_timeout() {
+			NetworkError::Timeout
+		} else {
+			NetworkError::Connection
+		};
+		anyhow::Error::new(e).context(kind)
+	})?;
+	stats::record_response(
+		&url,
+		resp.content_length(),
+		start.elapsed(),
+		resp.status().is_client_error() || resp.status().is_server_error(),
+	);
+	throttle::record_headers(&url, resp.status(), resp.headers());
+
+	Ok(resp)
+}
+
+/// Gets how long a response asked us to wait before retrying
+fn get_retry_after(resp: &reqwest::Response) -> Option<Duration> {
+	let seconds = resp
+		.headers()
+		.get(RETRY_AFTER)?
+		.to_str()
+		.ok()?
+		.parse()
+		.ok()?;
+	Some(Duration::from_secs(seconds))
+}
+
+/// Policy for retrying requests that fail because of temporary problems, like connection
+/// errors or servers that are overloaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+	/// The total number of times a request is attempted
+	pub attempts: u32,
+	/// The delay before the first retry, which doubles with each attempt after
+	pub base_delay: Duration,
+	/// The longest delay between attempts
+	pub max_delay: Duration,
+	/// Response status codes that are retried
+	pub retryable_statuses: Vec<u16>,
+}
+
+impl RetryPolicy {
+	/// Gets the delay before retrying after the given attempt, starting at 1. A random amount
+	/// of up to half of the delay is taken off so that many failed requests don't all retry at once
+	pub fn get_delay(&self, attempt: u32) -> Duration {
+		let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+		let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+
+		let jitter = (RandomState::new().build_hasher().finish() % 1000) as f64 / 1000.0;
+		delay.mul_f64(1.0 - jitter / 2.0)
+	}
+
+	/// Checks whether a response with the given status code should be retried
+	pub fn is_retryable_status(&self, status: u16) -> bool {
+		self.retryable_statuses.contains(&status)
+	}
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self {
+			attempts: 3,
+			base_delay: Duration::from_millis(500),
+			max_delay: Duration::from_secs(10),
+			retryable_statuses: vec![408, 429, 500, 502, 503, 504],
+		}
+	}
+}
+
+/// The policy that all requests are retried with
+static RETRY_POLICY: LazyLock<RwLock<RetryPolicy>> =
+	LazyLock::new(|| RwLock::new(RetryPolicy::default()));
+
+/// Sets the policy that all requests are retried with
+pub fn set_retry_policy(policy: RetryPolicy) {
+	if let Ok(mut lock) = RETRY_POLICY.write() {
+		*lock = policy;
+	}
+}
+
+/// Gets the policy that all requests are retried with
+pub fn get_retry_policy() -> RetryPolicy {
+	RETRY_POLICY.read().map(|x| x.clone()).unwrap_or_default()
+}
+
 /// Downloads data from a remote location
 pub async fn download(url: impl IntoUrl, client: &Client) -> anyhow::Result<reqwest::Response> {
-	let resp = client
-		.get(url)
-		.header("User-Agent", user_agent())
-		.send()
-		.await
-		.context("Failed to send request")?
-		.error_for_status()
-		.context("Server reported an error")?;
+	let resp = send(client.get(url).header("User-Agent", user_agent())).await?;
+
+	check_status(resp)
+}
+
+/// Fails with a NetworkError if a response has an error status code
+pub fn check_status(resp: reqwest::Response) -> anyhow::Result<reqwest::Response> {
+	if let Err(e) = resp.error_for_status_ref() {
+		let status = resp.status().as_u16();
+		return Err(anyhow::Error::new(e).context(NetworkError::Status(status)));
+	}
 
 	Ok(resp)
 }
@@ -44,14 +355,20 @@ pub async fn text(url: impl IntoUrl, client: &Client) -> anyhow::Result<String>
 
 /// Downloads and returns bytes
 pub async fn bytes(url: impl IntoUrl, client: &Client) -> anyhow::Result<bytes::Bytes> {
-	let bytes = download(url, client)
-		.await
-		.context("Failed to download")?
-		.bytes()
+	let mut response = download(url, client).await.context("Failed to download")?;
+
+	let tracker = TrackedDownload::new(response.url().as_str(), response.content_length());
+	let mut out = Vec::with_capacity(response.content_length().unwrap_or_default() as usize);
+	while let Some(chunk) = response
+		.chunk()
 		.await
-		.context("Failed to convert download to raw bytes")?;
+		.context("Failed to convert download to raw bytes")?
+	{
+		out.extend_from_slice(&chunk);
+		tracker.record(chunk.len()).await;
+	}
 
-	Ok(bytes)
+	Ok(out.into())
 }
 
 /// Downloads and puts the contents in a file
@@ -86,6 +403,7 @@ pub async fn json<T: DeserializeOwned>(url: impl IntoUrl, client: &Client) -> an
 /// A persistent single download that can be used to track progress
 pub struct ProgressiveDownload<W: Write> {
 	response: reqwest::Response,
+	tracker: TrackedDownload,
 	writer: W,
 	content_length: u64,
 	bytes_downloaded: usize,
@@ -97,6 +415,7 @@ impl<W: Write> ProgressiveDownload<W> {
 	pub fn from_response(response: reqwest::Response, writer: W) -> Self {
 		Self {
 			content_length: response.content_length().unwrap_or_default(),
+			tracker: TrackedDownload::new(response.url().as_str(), response.content_length()),
 			response,
 			writer,
 			bytes_downloaded: 0,
@@ -133,8 +452,12 @@ impl<W: Write> ProgressiveDownload<W> {
 				.write_all(&bytes)
 				.context("Failed to write downloaded bytes")?;
 			self.bytes_downloaded += bytes.len();
+			self.tracker.record(bytes.len()).await;
 		} else {
 			self.finished = true;
+			self.writer
+				.flush()
+				.context("Failed to flush downloaded bytes")?;
 			// Ensure that we downloaded the correct amount
 			ensure!(
 				self.get_downloaded() == self.get_total_length(),
@@ -199,3 +522,30 @@ pub fn validate_url(url: &str) -> anyhow::Result<()> {
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_retry_delay() {
+		let policy = RetryPolicy {
+			attempts: 5,
+			base_delay: Duration::from_secs(1),
+			max_delay: Duration::from_secs(3),
+			retryable_statuses: vec![503],
+		};
+
+		for _ in 0..20 {
+			let first = policy.get_delay(1);
+			assert!(first >= Duration::from_millis(500) && first <= Duration::from_secs(1));
+			let second = policy.get_delay(2);
+			assert!(second >= Duration::from_secs(1) && second <= Duration::from_secs(2));
+			let capped = policy.get_delay(10);
+			assert!(capped >= Duration::from_millis(1500) && capped <= Duration::from_secs(3));
+		}
+
+		assert!(policy.is_retryable_status(503));
+		assert!(!policy.is_retryable_status(404));
+	}
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+
+use anyhow::Context;
+use bytes::Bytes;
+use reqwest::{Response, ResponseBuilderExt, Url};
+use serde::{Deserialize, Serialize};
+
+/// The environment variable used to set a directory that responses from the network
+/// are recorded into, so that they can be used as fixtures later
+pub const RECORD_ENV: &str = "NITRO_RECORD_FIXTURES";
+
+/// The name of the index file in a fixture directory
+const INDEX_FILE_NAME: &str = "index.json";
+
+/// Fixture sets that are currently installed, with their IDs, in the order they were installed
+static INSTALLED: LazyLock<Mutex<Vec<InstalledFixtureSet>>> =
+	LazyLock::new(|| Mutex::new(Vec::new()));
+/// The ID for the next installed fixture set
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+/// Lock for updating the index of the recording directory
+static RECORD_LOCK: Mutex<()> = Mutex::new(());
+
+type InstalledFixtureSet = (u64, Arc<FixtureSet>);
+
+/// A recorded response to a request
+#[derive(Debug, Clone)]
+pub struct Fixture {
+	/// The HTTP status code of the response
+	pub status: u16,
+	/// The body of the response
+	pub body: Bytes,
+}
+
+/// A set of recorded responses. Requests are matched to responses by their URL only,
+/// so requests with different methods or bodies to the same URL will get the same response
+#[derive(Debug, Clone, Default)]
+pub struct FixtureSet {
+	responses: HashMap<String, Fixture>,
+}
+
+impl FixtureSet {
+	/// Create a new empty FixtureSet
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Add a response for a URL
+	pub fn add(&mut self, url: &str, status: u16, body: impl Into<Bytes>) {
+		self.responses.insert(
+			url.to_string(),
+			Fixture {
+				status,
+				body: body.into(),
+			},
+		);
+	}
+
+	/// Add a successful JSON response for a URL
+	pub fn add_json(&mut self, url: &str, value: &impl Serialize) -> anyhow::Result<()> {
+		let body = serde_json::to_vec(value).context("Failed to serialize fixture")?;
+		self.add(url, 200, body);
+
+		Ok(())
+	}
+
+	/// Get the response for a URL
+	pub fn get(&self, url: &str) -> Option<&Fixture> {
+		self.responses.get(url)
+	}
+
+	/// Load a fixture set from a directory containing an index file and the response bodies
+	pub fn load(dir: &Path) -> anyhow::Result<Self> {
+		let index = read_index(dir)?;
+		let mut out = Self::new();
+		for (url, entry) in index {
+			let body = std::fs::read(dir.join(&entry.file))
+				.with_context(|| format!("Failed to read fixture file {}", entry.file))?;
+			out.add(&url, entry.status, body);
+		}
+
+		Ok(out)
+	}
+
+	/// Save this fixture set to a directory, so that it can be loaded again
+	pub fn save(&self, dir: &Path) -> anyhow::Result<()> {
+		std::fs::create_dir_all(dir).context("Failed to create fixture directory")?;
+
+		let mut index = HashMap::new();
+		let mut urls: Vec<_> = self.responses.keys().collect();
+		urls.sort();
+		for (i, url) in urls.into_iter().enumerate() {
+			let fixture = &self.responses[url];
+			let file = i.to_string();
+			std::fs::write(dir.join(&file), &fixture.body)
+				.context("Failed to write fixture file")?;
+			index.insert(
+				url.clone(),
+				IndexEntry {
+					status: fixture.status,
+					file,
+				},
+			);
+		}
+
+		write_index(dir, &index)
+	}
+
+	/// Install this fixture set. Until the returned guard is dropped, all requests are answered
+	/// using installed fixtures instead of the network, and requests without a fixture will fail
+	pub fn install(self) -> FixtureGuard {
+		let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+		if let Ok(mut installed) = INSTALLED.lock() {
+			installed.push((id, Arc::new(self)));
+		}
+
+		FixtureGuard { id }
+	}
+}
+
+/// Guard for an installed fixture set. The set is uninstalled when this is dropped
+pub struct FixtureGuard {
+	id: u64,
+}
+
+impl Drop for FixtureGuard {
+	fn drop(&mut self) {
+		if let Ok(mut installed) = INSTALLED.lock() {
+			installed.retain(|x| x.0 != self.id);
+		}
+	}
+}
+
+/// Checks whether any fixture sets are installed
+pub fn is_active() -> bool {
+	INSTALLED.lock().is_ok_and(|x| !x.is_empty())
+}
+
+/// Gets the response to a request to a URL from the installed fixture sets, with later sets
+/// taking precedence
+pub(crate) fn get_response(url: &Url) -> Option<anyhow::Result<Response>> {
+	let installed = INSTALLED.lock().ok()?;
+	let fixture = installed.iter().rev().find_map(|x| x.1.get(url.as_str()))?;
+
+	Some(create_response(url, fixture.status, fixture.body.clone()))
+}
+
+/// Gets the directory that responses should be recorded into, if recording is enabled
+pub(crate) fn get_recording_dir() -> Option<PathBuf> {
+	std::env::var_os(RECORD_ENV).map(PathBuf::from)
+}
+
+/// Records a response from the network into a fixture directory, returning an equivalent response
+pub(crate) async fn record(dir: &Path, response: Response) -> anyhow::Result<Response> {
+	let url = response.url().clone();
+	let status = response.status().as_u16();
+	let body = response
+		.bytes()
+		.await
+		.context("Failed to read response for recording")?;
+
+	{
+		let _lock = RECORD_LOCK.lock();
+		std::fs::create_dir_all(dir).context("Failed to create fixture directory")?;
+		let mut index = if dir.join(INDEX_FILE_NAME).exists() {
+			read_index(dir)?
+		} else {
+			HashMap::new()
+		};
+
+		let file = index
+			.get(url.as_str())
+			.map(|x| x.file.clone())
+			.unwrap_or_else(|| index.len().to_string());
+		std::fs::write(dir.join(&file), &body).context("Failed to write fixture file")?;
+		index.insert(url.to_string(), IndexEntry { status, file });
+		write_index(dir, &index)?;
+	}
+
+	create_response(&url, status, body)
+}
+
+/// Creates a response with a body
+fn create_response(url: &Url, status: u16, body: Bytes) -> anyhow::Result<Response> {
+	let response = http::Response::builder()
+		.status(status)
+		.url(url.clone())
+		.body(body)
+		.context("Failed to create response from fixture")?;
+
+	Ok(Response::from(response))
+}
+
+/// An entry in the index of a fixture directory
+#[derive(Serialize, Deserialize)]
+struct IndexEntry {
+	status: u16,
+	file: String,
+}
+
+fn read_index(dir: &Path) -> anyhow::Result<HashMap<String, IndexEntry>> {
+	let index = std::fs::read(dir.join(INDEX_FILE_NAME)).context("Failed to read fixture index")?;
+	serde_json::from_slice(&index).context("Failed to deserialize fixture index")
+}
+
+fn write_index(dir: &Path, index: &HashMap<String, IndexEntry>) -> anyhow::Result<()> {
+	let index = serde_json::to_vec_pretty(index).context("Failed to serialize fixture index")?;
+	std::fs::write(dir.join(INDEX_FILE_NAME), index).context("Failed to write fixture index")
+}
+
+#[cfg(test)]
+mod tests {
+	use reqwest::Client;
+
+	use crate::download;
+
+	use super::*;
+
+	#[tokio::test]
+	async fn test_fixture_responses() {
+		let mut fixtures = FixtureSet::new();
+		fixtures.add("https://example.com/fixture/text", 200, "Hello");
+		fixtures.add("https://example.com/fixture/missing", 404, "");
+		let guard = fixtures.install();
+
+		let client = Client::new();
+		let text = download::text("https://example.com/fixture/text", &client)
+			.await
+			.unwrap();
+		assert_eq!(text, "Hello");
+		assert!(
+			download::text("https://example.com/fixture/missing", &client)
+				.await
+				.is_err()
+		);
+		assert!(
+			download::text("https://example.com/fixture/other", &client)
+				.await
+				.is_err()
+		);
+
+		drop(guard);
+	}
+
+	#[test]
+	fn test_fixture_save_and_load() {
+		let dir = std::env::temp_dir().join("nitro_test_fixture_save_and_load");
+		let mut fixtures = FixtureSet::new();
+		fixtures.add("https://example.com/a", 200, "a");
+		fixtures
+			.add_json("https://example.com/b", &vec![1, 2])
+			.unwrap();
+		fixtures.save(&dir).unwrap();
+
+		let loaded = FixtureSet::load(&dir).unwrap();
+		assert_eq!(loaded.get("https://example.com/a").unwrap().body, "a");
+		assert_eq!(loaded.get("https://example.com/b").unwrap().body, "[1,2]");
+		assert!(loaded.get("https://example.com/c").is_none());
+
+		let _ = std::fs::remove_dir_all(dir);
+	}
+}
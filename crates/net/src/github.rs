@@ -8,6 +8,8 @@ pub struct GithubRelease {
 	pub tag_name: String,
 	pub name: String,
 	pub body: Option<String>,
+	#[serde(default)]
+	pub prerelease: bool,
 	pub assets: Vec<GithubAsset>,
 }
 
@@ -5,18 +5,36 @@ use anyhow::Context;
 use bytes::Bytes;
 use reqwest::{Client, Url};
 
+/// Tracking of download progress, pausing, and bandwidth limits
+pub mod activity;
+/// Known security problems with versions of the game and loaders
+pub mod advisories;
 /// Interacting with the CurseForge API
 pub mod curseforge;
 /// Download utilities
 pub mod download;
+/// Recorded responses that can be used instead of the network in tests
+pub mod fixtures;
 /// GitHub releases API
 pub mod github;
 /// Interacting with the Modrinth API
 pub mod modrinth;
+/// Mojang's launcher content API for news and patch notes
+pub mod mojang;
 /// Downloading the NeoForge installer
 pub mod neoforge;
+/// Uploading logs to paste services
+pub mod paste;
+/// Resuming interrupted downloads of large files
+pub mod resume;
+/// Resolving and pinging Minecraft servers
+pub mod server;
 /// Interacting with the Smithed API
 pub mod smithed;
+/// Per-host network statistics
+pub mod stats;
+/// Adapting request batches to host latency and rate limits
+pub mod throttle;
 
 /// Loads bytes from a file path or URL
 pub async fn load_from_uri(uri: &str, client: &Client) -> anyhow::Result<Bytes> {
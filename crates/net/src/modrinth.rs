@@ -1,8 +1,10 @@
+use std::collections::HashMap;
+
 use crate::download::{self, user_agent};
 use anyhow::{Context, anyhow};
 use nitro_shared::{
 	loaders::Loader,
-	pkg::{PackageCategory, PackageKind, PackageSearchParameters},
+	pkg::{PackageCategory, PackageKind, PackageSearchParameters, PackageSortMethod},
 };
 use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
@@ -56,6 +58,10 @@ pub struct Project {
 	pub categories: Vec<String>,
 	/// Number of downloads for the project
 	pub downloads: u32,
+	/// Number of followers for the project
+	pub followers: u32,
+	/// The date the project was last updated in ISO-8601
+	pub updated: Option<String>,
 }
 
 /// The type of a Modrinth project
@@ -92,20 +98,12 @@ pub async fn get_project_optional(
 	client: &Client,
 ) -> anyhow::Result<Option<Project>> {
 	let url = format_get_project_url(project_id);
-
-	let resp = client
-		.get(url)
-		.header("User-Agent", user_agent())
-		.send()
-		.await
-		.context("Failed to send request")?;
+	let resp = download::send(client.get(url).header("User-Agent", user_agent())).await?;
 	if resp.status() == StatusCode::NOT_FOUND {
 		return Ok(None);
 	}
 
-	let resp = resp
-		.error_for_status()
-		.context("Server returned an error")?;
+	let resp = download::check_status(resp)?;
 
 	resp.json()
 		.await
@@ -302,6 +300,89 @@ pub async fn get_multiple_versions(
 	download::json(url, client).await
 }
 
+/// Gets the versions that contain files with the given SHA-512 hashes, keyed by hash.
+/// Hashes that don't match any version are not included
+pub async fn get_versions_from_hashes(
+	hashes: &[String],
+	client: &Client,
+) -> anyhow::Result<HashMap<String, Version>> {
+	if hashes.is_empty() {
+		return Ok(HashMap::new());
+	}
+
+	let body = serde_json::json!({
+		"hashes": hashes,
+		"algorithm": "sha512",
+	});
+	let resp = download::send(
+		client
+			.post("https://api.modrinth.com/v2/version_files")
+			.header("User-Agent", user_agent())
+			.json(&body),
+	)
+	.await?;
+	let resp = download::check_status(resp)?;
+
+	Ok(resp.json().await?)
+}
+
+/// Data for creating a new version of a Modrinth project
+#[derive(Serialize, Clone)]
+pub struct NewVersion {
+	/// The ID of the project to create the version in
+	pub project_id: String,
+	/// The name of the version
+	pub name: String,
+	/// The version number of the version
+	pub version_number: String,
+	/// The changelog of the version, in Markdown
+	pub changelog: Option<String>,
+	/// The release channel of the version
+	pub version_type: ReleaseChannel,
+	/// The game versions the version supports
+	pub game_versions: Vec<String>,
+	/// The loaders the version supports
+	pub loaders: Vec<String>,
+	/// Whether the version is featured
+	pub featured: bool,
+}
+
+/// Creates a new version of a Modrinth project with a single file, using an API token.
+/// Returns the created version
+pub async fn create_version(
+	version: &NewVersion,
+	file_name: &str,
+	file: Vec<u8>,
+	token: &str,
+	client: &Client,
+) -> anyhow::Result<Version> {
+	const FILE_PART: &str = "file";
+
+	let mut data = serde_json::to_value(version).context("Failed to serialize version data")?;
+	data["dependencies"] = serde_json::json!([]);
+	data["file_parts"] = serde_json::json!([FILE_PART]);
+	data["primary_file"] = serde_json::json!(FILE_PART);
+
+	let file = reqwest::multipart::Part::bytes(file)
+		.file_name(file_name.to_string())
+		.mime_str("application/x-modrinth-modpack+zip")?;
+	let form = reqwest::multipart::Form::new()
+		.text("data", data.to_string())
+		.part(FILE_PART, file);
+
+	let resp = download::send(
+		client
+			.post("https://api.modrinth.com/v2/version")
+			.header("User-Agent", user_agent())
+			.header("Authorization", token)
+			.multipart(form),
+	)
+	.await?;
+	let resp = download::check_status(resp).context("Modrinth rejected the version")?;
+
+	Ok(resp.json().await?)
+}
+
 /// A file download from the Modrinth API
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Download {
@@ -313,6 +394,9 @@ pub struct Download {
 	pub primary: bool,
 	/// Hashes for this file
 	pub hashes: Hashes,
+	/// The size of the file in bytes
+	#[serde(default)]
+	pub size: Option<u64>,
 }
 
 /// Hashes for a Modrinth file
@@ -320,6 +404,9 @@ pub struct Download {
 pub struct Hashes {
 	/// SHA-512 hash
 	pub sha512: String,
+	/// SHA-1 hash
+	#[serde(default)]
+	pub sha1: Option<String>,
 }
 
 /// A version dependency
@@ -375,6 +462,9 @@ pub struct LongLicense {
 pub struct DonationLink {
 	/// The URL of the link
 	pub url: String,
+	/// The name of the donation platform
+	#[serde(default)]
+	pub platform: Option<String>,
 }
 
 /// An entry in a project's gallery
@@ -404,6 +494,12 @@ pub struct FullGalleryEntry {
 	pub raw_url: String,
 	/// Whether the gallery image is a featured banner on the project page
 	pub featured: bool,
+	/// The title of the gallery image
+	#[serde(default)]
+	pub title: Option<String>,
+	/// The description of the gallery image
+	#[serde(default)]
+	pub description: Option<String>,
 }
 
 /// Support status for a project on a specific side
@@ -451,6 +547,9 @@ pub struct Member {
 	pub user: User,
 	/// The ID of the team this member is a part of
 	pub team_id: String,
+	/// The member's role in the team
+	#[serde(default)]
+	pub role: Option<String>,
 }
 
 /// A user on the platform
@@ -458,6 +557,31 @@ pub struct Member {
 pub struct User {
 	/// The user's username
 	pub username: String,
+	/// The user's ID
+	#[serde(default)]
+	pub id: String,
+	/// A link to the user's avatar
+	#[serde(default)]
+	pub avatar_url: Option<String>,
+	/// The user's biography
+	#[serde(default)]
+	pub bio: Option<String>,
+}
+
+/// Get a user from the API
+pub async fn get_user(user_id: &str, client: &Client) -> anyhow::Result<User> {
+	let url = format!("https://api.modrinth.com/v2/user/{user_id}");
+	download::json(url, client)
+		.await
+		.context("Failed to download Modrinth user")
+}
+
+/// Get the projects that a user is a member of
+pub async fn get_user_projects(user_id: &str, client: &Client) -> anyhow::Result<Vec<Project>> {
+	let url = format!("https://api.modrinth.com/v2/user/{user_id}/projects");
+	download::json(url, client)
+		.await
+		.context("Failed to download Modrinth user projects")
 }
 
 /// Search projects from the Modrinth API. Note that the projects returned by this have many default fields and should NOT be used as the final projects.
@@ -562,8 +686,16 @@ pub async fn search_projects(
 	} else {
 		format!("&facets=[{facets_inside}]")
 	};
+	let index = match params.sort {
+		PackageSortMethod::Relevance => "relevance",
+		PackageSortMethod::Downloads => "downloads",
+		PackageSortMethod::Followers => "follows",
+		PackageSortMethod::Updated => "updated",
+		PackageSortMethod::Newest => "newest",
+	};
+
 	let url = format!(
-		"https://api.modrinth.com/v2/search?limit={limit}{search}{facets}&offset={}",
+		"https://api.modrinth.com/v2/search?limit={limit}{search}{facets}&index={index}&offset={}",
 		params.skip
 	);
 
@@ -605,6 +737,10 @@ pub struct SearchedProject {
 	pub featured_gallery: Option<String>,
 	/// Number of downloads for this project
 	pub downloads: u32,
+	/// Number of followers for this project
+	pub follows: u32,
+	/// The date this project was last modified in ISO-8601
+	pub date_modified: Option<String>,
 }
 
 fn convert_category(category: PackageCategory) -> &'static [&'static str] {
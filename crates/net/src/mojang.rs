@@ -0,0 +1,98 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::download;
+
+/// Base URL for Mojang's launcher content API. Paths to images are relative to this
+pub static LAUNCHER_CONTENT_URL: &str = "https://launchercontent.mojang.com";
+
+/// The list of Java Edition patch notes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PatchNotes {
+	/// The patch notes, newest first
+	pub entries: Vec<PatchNotesEntry>,
+}
+
+/// Patch notes for a single release or snapshot
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchNotesEntry {
+	/// The title of the article
+	pub title: String,
+	/// The Minecraft version this entry is for
+	pub version: String,
+	/// The type of version, such as release or snapshot
+	#[serde(rename = "type")]
+	pub version_type: String,
+	/// The image shown with the article
+	pub image: PatchNotesImage,
+	/// The date of the article
+	pub date: String,
+	/// A short summary of the article
+	pub short_text: String,
+	/// Unique ID for the article
+	pub id: String,
+}
+
+/// An image for a patch notes entry
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PatchNotesImage {
+	/// Alt text for the image
+	pub title: String,
+	/// Path to the image relative to the launcher content URL
+	pub url: String,
+}
+
+impl PatchNotesImage {
+	/// Gets the full URL to this image
+	pub fn get_url(&self) -> String {
+		format!("{LAUNCHER_CONTENT_URL}{}", self.url)
+	}
+}
+
+/// Gets the list of Java Edition patch notes
+pub async fn get_patch_notes(client: &Client) -> anyhow::Result<PatchNotes> {
+	download::json(
+		format!("{LAUNCHER_CONTENT_URL}/v2/javaPatchNotes.json"),
+		client,
+	)
+	.await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_patch_notes_deserialization() {
+		let notes: PatchNotes = serde_json::from_str(
+			r#"{
+				"version": 1,
+				"entries": [
+					{
+						"title": "Minecraft Java Edition 1.21.4",
+						"type": "release",
+						"version": "1.21.4",
+						"image": {
+							"title": "Version 1.21.4",
+							"url": "/images/1.21.4.jpg"
+						},
+						"contentPath": "1.21.4.json",
+						"id": "1.21.4",
+						"date": "2024-12-03T12:00:00.000Z",
+						"shortText": "The Garden Awakens"
+					}
+				]
+			}"#,
+		)
+		.unwrap();
+
+		let entry = &notes.entries[0];
+		assert_eq!(entry.version_type, "release");
+		assert_eq!(entry.short_text, "The Garden Awakens");
+		assert_eq!(
+			entry.image.get_url(),
+			"https://launchercontent.mojang.com/images/1.21.4.jpg"
+		);
+	}
+}
@@ -3,7 +3,7 @@ use std::path::Path;
 use reqwest::Client;
 use serde::Deserialize;
 
-use crate::download;
+use crate::{download, resume};
 
 /// Base URL for installer versions
 pub static VERSIONS_URL: &str =
@@ -34,22 +34,40 @@ pub fn get_latest_neoforge_version<'a>(
 		.find(|neoforge_version| is_version_compatible(neoforge_version, minecraft_version))
 }
 
+/// Gets the NeoForge versions made for a Minecraft version, sorted from oldest to newest
+pub fn get_compatible_versions<'a>(
+	versions: &'a [String],
+	minecraft_version: &str,
+) -> Vec<&'a String> {
+	versions
+		.iter()
+		.filter(|neoforge_version| is_version_compatible(neoforge_version, minecraft_version))
+		.collect()
+}
+
 /// Checks if a NeoForge version is made for a given Minecraft version
 pub fn is_version_compatible(neoforge_version: &str, minecraft_version: &str) -> bool {
-	// Get the major version and patch of a release (remove the 1.)
-	let minecraft_major_version = &minecraft_version[2..];
-	neoforge_version.starts_with(minecraft_major_version)
+	// NeoForge versions start with the minor version and patch of the Minecraft version, like 21.1 for 1.21.1
+	let Some(minecraft_version) = minecraft_version.strip_prefix("1.") else {
+		return false;
+	};
+	let mut parts = minecraft_version.split('.');
+	let minor = parts.next().unwrap_or_default();
+	let patch = parts.next().unwrap_or("0");
+	neoforge_version.starts_with(&format!("{minor}.{patch}."))
 }
 
-/// Downloads the installer for the given NeoForge version
+/// Downloads the installer for the given NeoForge version, resuming a partial download in the
+/// given directory if there is one
 pub async fn download_installer(
 	neoforge_version: &str,
 	path: &Path,
+	partial_dir: &Path,
 	client: &Client,
 ) -> anyhow::Result<()> {
 	let url = format!(
 		"https://maven.neoforged.net/releases/net/neoforged/neoforge/{neoforge_version}/neoforge-{neoforge_version}-installer.jar"
 	);
 
-	download::file(&url, path, client).await
+	resume::file(&url, path, partial_dir, client).await
 }
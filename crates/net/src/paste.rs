@@ -0,0 +1,71 @@
+use anyhow::{Context, bail};
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::download;
+
+/// ID of the built-in mclo.gs paste backend
+pub const MCLOGS_BACKEND_ID: &str = "mclogs";
+/// Base URL for the mclo.gs API
+pub const MCLOGS_API_URL: &str = "https://api.mclo.gs/1";
+
+/// Response from uploading a log to mclo.gs
+#[derive(Deserialize)]
+struct MclogsResponse {
+	success: bool,
+	#[serde(default)]
+	url: Option<String>,
+	#[serde(default)]
+	error: Option<String>,
+}
+
+/// Uploads text to mclo.gs and returns the URL to view it
+pub async fn upload_to_mclogs(contents: &str, client: &Client) -> anyhow::Result<String> {
+	let response = download::send(
+		client
+			.post(format!("{MCLOGS_API_URL}/log"))
+			.form(&[("content", contents)]),
+	)
+	.await?;
+	let response: MclogsResponse = download::check_status(response)
+		.context("Failed to upload to mclo.gs")?
+		.json()
+		.await
+		.context("Failed to parse response from mclo.gs")?;
+
+	parse_mclogs_response(response)
+}
+
+fn parse_mclogs_response(response: MclogsResponse) -> anyhow::Result<String> {
+	if !response.success {
+		bail!(
+			"mclo.gs rejected the upload: {}",
+			response.error.unwrap_or_default()
+		);
+	}
+
+	response.url.context("mclo.gs did not return a URL")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_mclogs_response() {
+		let response = serde_json::from_str(
+			r#"{"success": true, "id": "HpAwPry", "url": "https://mclo.gs/HpAwPry", "raw": "https://api.mclo.gs/1/raw/HpAwPry"}"#,
+		)
+		.unwrap();
+		assert_eq!(
+			parse_mclogs_response(response).unwrap(),
+			"https://mclo.gs/HpAwPry"
+		);
+
+		let response = serde_json::from_str(
+			r#"{"success": false, "error": "Required POST argument 'content' is empty."}"#,
+		)
+		.unwrap();
+		assert!(parse_mclogs_response(response).is_err());
+	}
+}
@@ -0,0 +1,260 @@
+use std::fs::{File, OpenOptions};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use nitro_shared::output::MessageContents;
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use serde::{Deserialize, Serialize};
+
+use crate::download::{self, Client, ProgressiveDownload, user_agent};
+
+/// Gets the directory where partial downloads are stored, from the internal directory
+pub fn get_partial_dir(internal_dir: &Path) -> PathBuf {
+	internal_dir.join("partial_downloads")
+}
+
+/// Downloads a file, resuming from a previous partial download of the same URL if the server
+/// supports it. If the download is interrupted, the partial file is kept so that the next attempt
+/// can continue from where this one stopped
+pub async fn file(
+	url: impl AsRef<str>,
+	path: impl AsRef<Path>,
+	partial_dir: &Path,
+	client: &Client,
+) -> anyhow::Result<()> {
+	let mut download = ResumableDownload::new(url, path, partial_dir, client).await?;
+	while !download.is_finished() {
+		download.poll_download().await?;
+	}
+
+	Ok(())
+}
+
+/// A download of a file that is written to a partial file first, and can be resumed with HTTP
+/// range requests if it is interrupted
+pub struct ResumableDownload {
+	/// The inner download. Taken once the download is finished so that the partial file is closed
+	inner: Option<ProgressiveDownload<BufWriter<File>>>,
+	/// The number of bytes that were already downloaded before this download started
+	offset: u64,
+	/// The path to the partial file
+	part_path: PathBuf,
+	/// The path to the bookkeeping file for the partial file
+	info_path: PathBuf,
+	/// The final path of the file
+	path: PathBuf,
+}
+
+impl ResumableDownload {
+	/// Starts a new resumable download, continuing an existing partial download if possible
+	pub async fn new(
+		url: impl AsRef<str>,
+		path: impl AsRef<Path>,
+		partial_dir: &Path,
+		client: &Client,
+	) -> anyhow::Result<Self> {
+		let url = url.as_ref();
+		std::fs::create_dir_all(partial_dir)
+			.context("Failed to create partial downloads directory")?;
+
+		let name = get_partial_name(url);
+		let part_path = partial_dir.join(format!("{name}.part"));
+		let info_path = partial_dir.join(format!("{name}.json"));
+
+		let existing = get_resumable_length(url, &part_path, &info_path);
+		let previous_validator = existing.as_ref().map(|x| x.1.clone());
+		let (response, offset) = match existing {
+			Some((length, validator)) => {
+				let request = client
+					.get(url)
+					.header("User-Agent", user_agent())
+					.header(RANGE, format!("bytes={length}-"))
+					.header(IF_RANGE, validator);
+				let response = download::send(request).await?;
+
+				match response.status() {
+					StatusCode::PARTIAL_CONTENT
+						if content_range_start(&response) == Some(length) =>
+					{
+						(response, length)
+					}
+					// The file changed on the server or the range is wrong, so start over
+					StatusCode::OK => (response, 0),
+					_ => (download::download(url, client).await?, 0),
+				}
+			}
+			None => (download::download(url, client).await?, 0),
+		};
+		let response = download::check_status(response)?;
+
+		let info = PartialInfo {
+			url: url.to_string(),
+			// Resumed responses don't always repeat the validator
+			validator: get_validator(&response).or(previous_validator.filter(|_| offset > 0)),
+		};
+		let info =
+			serde_json::to_vec(&info).context("Failed to serialize partial download info")?;
+		std::fs::write(&info_path, info).context("Failed to write partial download info")?;
+
+		let file = if offset > 0 {
+			OpenOptions::new().append(true).open(&part_path)
+		} else {
+			File::create(&part_path)
+		};
+		let file = BufWriter::new(file.context("Failed to open partial file")?);
+
+		Ok(Self {
+			inner: Some(ProgressiveDownload::from_response(response, file)),
+			offset,
+			part_path,
+			info_path,
+			path: path.as_ref().to_path_buf(),
+		})
+	}
+
+	/// Get the number of bytes that have been downloaded, including ones from the previous attempt
+	pub fn get_downloaded(&self) -> usize {
+		self.offset as usize
+			+ self
+				.inner
+				.as_ref()
+				.map(|x| x.get_downloaded())
+				.unwrap_or_default()
+	}
+
+	/// Get the total length of the content, including the part from the previous attempt
+	pub fn get_total_length(&self) -> usize {
+		self.offset as usize
+			+ self
+				.inner
+				.as_ref()
+				.map(|x| x.get_total_length())
+				.unwrap_or_default()
+	}
+
+	/// Get the progress message corresponding to this download
+	pub fn get_progress(&self) -> MessageContents {
+		let current = (self.get_downloaded() / 2) as u32;
+		let total = (self.get_total_length() / 2) as u32;
+		MessageContents::Progress { current, total }
+	}
+
+	/// Poll the download. Once all of the data is downloaded, the partial file is moved to the
+	/// final path
+	pub async fn poll_download(&mut self) -> anyhow::Result<()> {
+		let Some(inner) = &mut self.inner else {
+			return Ok(());
+		};
+		inner.poll_download().await?;
+		if !inner.is_finished() {
+			return Ok(());
+		}
+
+		// Close the partial file before moving it
+		let inner = self.inner.take();
+		self.offset += inner.map(|x| x.get_downloaded() as u64).unwrap_or_default();
+
+		if std::fs::rename(&self.part_path, &self.path).is_err() {
+			// Renaming doesn't work across filesystems
+			std::fs::copy(&self.part_path, &self.path)
+				.context("Failed to copy partial file to destination")?;
+			let _ = std::fs::remove_file(&self.part_path);
+		}
+		let _ = std::fs::remove_file(&self.info_path);
+
+		Ok(())
+	}
+
+	/// Check if the download is finished
+	pub fn is_finished(&self) -> bool {
+		self.inner.is_none()
+	}
+}
+
+/// Bookkeeping for a partial download
+#[derive(Serialize, Deserialize)]
+struct PartialInfo {
+	/// The URL that the file is downloaded from
+	url: String,
+	/// The ETag or Last-Modified date of the file, used to make sure that it hasn't changed
+	/// before resuming
+	validator: Option<String>,
+}
+
+/// Gets the length of an existing partial download of a URL and the validator to resume it with.
+/// Returns None if the download can't be resumed
+fn get_resumable_length(url: &str, part_path: &Path, info_path: &Path) -> Option<(u64, String)> {
+	let info = std::fs::read(info_path).ok()?;
+	let info: PartialInfo = serde_json::from_slice(&info).ok()?;
+	if info.url != url {
+		return None;
+	}
+	let validator = info.validator?;
+
+	let length = part_path.metadata().ok()?.len();
+	(length > 0).then_some((length, validator))
+}
+
+/// Gets the name of the partial files for a URL
+fn get_partial_name(url: &str) -> String {
+	let mut hasher = DefaultHasher::new();
+	url.hash(&mut hasher);
+	format!("{:016x}", hasher.finish())
+}
+
+/// Gets a validator for a response that can be used in an If-Range header. Weak ETags can't be
+/// used for ranges
+fn get_validator(response: &reqwest::Response) -> Option<String> {
+	let headers = response.headers();
+	if let Some(etag) = headers.get(ETAG).and_then(|x| x.to_str().ok())
+		&& !etag.starts_with("W/")
+	{
+		return Some(etag.to_string());
+	}
+
+	headers
+		.get(LAST_MODIFIED)
+		.and_then(|x| x.to_str().ok())
+		.map(ToString::to_string)
+}
+
+/// Gets the start of the range in the Content-Range header of a response
+fn content_range_start(response: &reqwest::Response) -> Option<u64> {
+	let range = response.headers().get(CONTENT_RANGE)?.to_str().ok()?;
+	parse_content_range_start(range)
+}
+
+/// Parses the start of a Content-Range header, like `bytes 100-199/200`
+fn parse_content_range_start(range: &str) -> Option<u64> {
+	let range = range.strip_prefix("bytes ")?;
+	let (start, _) = range.split_once('-')?;
+	start.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_content_range_parsing() {
+		assert_eq!(parse_content_range_start("bytes 100-199/200"), Some(100));
+		assert_eq!(parse_content_range_start("bytes 0-0/*"), Some(0));
+		assert_eq!(parse_content_range_start("bytes */200"), None);
+		assert_eq!(parse_content_range_start("items 1-2/3"), None);
+	}
+
+	#[test]
+	fn test_partial_names() {
+		assert_eq!(
+			get_partial_name("https://example.com/a.jar"),
+			get_partial_name("https://example.com/a.jar")
+		);
+		assert_ne!(
+			get_partial_name("https://example.com/a.jar"),
+			get_partial_name("https://example.com/b.jar")
+		);
+	}
+}
@@ -0,0 +1,528 @@
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, bail};
+use hickory_resolver::TokioResolver;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// The port that servers listen on when an address doesn't include one
+pub const DEFAULT_PORT: u16 = 25565;
+/// How long to wait for DNS and server responses
+const TIMEOUT: Duration = Duration::from_secs(5);
+/// Largest packet that will be accepted from a server
+const MAX_PACKET_LENGTH: usize = 2 * 1024 * 1024;
+
+/// Diagnostics for joining a server from an instance
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ServerDiagnostics {
+	/// Where the server address resolved to
+	pub resolved: ResolvedServer,
+	/// The status that the server reported
+	pub status: ServerStatus,
+	/// Whether the server runs the same version as the instance
+	pub compatibility: VersionCompatibility,
+}
+
+/// Resolves and pings a server, and checks if it can be joined by the given version of the game.
+/// The protocol number of the version should be given when it is known, since it is more reliable than the version name
+pub async fn diagnose(
+	address: &str,
+	port: Option<u16>,
+	instance_version: &str,
+	instance_protocol: Option<i32>,
+) -> anyhow::Result<ServerDiagnostics> {
+	let resolved = resolve(address, port)
+		.await
+		.context("Failed to resolve server address")?;
+	let status = ping(&resolved, instance_protocol)
+		.await
+		.context("Failed to ping server")?;
+	let compatibility = check_version_compatibility(&status, instance_version, instance_protocol);
+
+	Ok(ServerDiagnostics {
+		resolved,
+		status,
+		compatibility,
+	})
+}
+
+/// A server address that was resolved to the host and port that will actually be connected to
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedServer {
+	/// The host that was originally given
+	pub host: String,
+	/// The host that is connected to, which is different from the original one if an SRV record was found
+	pub target: String,
+	/// The port to connect to
+	pub port: u16,
+	/// Whether the target and port came from an SRV record
+	pub from_srv: bool,
+	/// The IP addresses that the target resolved to
+	pub addresses: Vec<SocketAddr>,
+}
+
+/// Resolves a server address like `play.example.com` or `example.com:25566`, including looking up
+/// its `_minecraft._tcp` SRV record like the game does. The port parameter overrides any port in the address
+pub async fn resolve(address: &str, port: Option<u16>) -> anyhow::Result<ResolvedServer> {
+	let (host, address_port) = split_address(address)?;
+	let port = port.or(address_port);
+
+	let mut target = host.clone();
+	let mut target_port = port.unwrap_or(DEFAULT_PORT);
+	let mut from_srv = false;
+	// The game only looks up SRV records when no port is given. A failed lookup doesn't stop
+	// the normal one from working, so errors are ignored
+	if port.is_none()
+		&& host.parse::<IpAddr>().is_err()
+		&& let Ok(Some(record)) = lookup_srv(&format!("_minecraft._tcp.{host}")).await
+	{
+		target = record.target;
+		target_port = record.port;
+		from_srv = true;
+	}
+
+	let addresses: Vec<_> = tokio::time::timeout(
+		TIMEOUT,
+		tokio::net::lookup_host((target.as_str(), target_port)),
+	)
+	.await
+	.context("Timed out while resolving server address")?
+	.with_context(|| format!("Failed to look up host '{target}'"))?
+	.collect();
+	if addresses.is_empty() {
+		bail!("Host '{target}' has no addresses");
+	}
+
+	Ok(ResolvedServer {
+		host,
+		target,
+		port: target_port,
+		from_srv,
+		addresses,
+	})
+}
+
+/// Splits a server address into its host and port
+fn split_address(address: &str) -> anyhow::Result<(String, Option<u16>)> {
+	let address = address.trim();
+	let (host, port) = if let Some(rest) = address.strip_prefix('[') {
+		// Bracketed IPv6 address
+		let (host, rest) = rest
+			.split_once(']')
+			.context("Unclosed bracket in server address")?;
+		(host, rest.strip_prefix(':'))
+	} else if address.parse::<IpAddr>().is_ok() {
+		// Bare IPv6 addresses have colons but no port
+		(address, None)
+	} else {
+		match address.rsplit_once(':') {
+			Some((host, port)) => (host, Some(port)),
+			None => (address, None),
+		}
+	};
+
+	if host.is_empty() {
+		bail!("Server address is empty");
+	}
+	let port = port
+		.map(|x| x.parse::<u16>())
+		.transpose()
+		.with_context(|| format!("Invalid port in server address '{address}'"))?;
+
+	Ok((host.to_string(), port))
+}
+
+/// A DNS SRV record
+#[derive(Debug, PartialEq, Eq)]
+struct SrvRecord {
+	/// Priority of the record, where lower is preferred
+	priority: u16,
+	/// Weight of the record among ones with the same priority, where higher is preferred
+	weight: u16,
+	/// Port to connect to
+	port: u16,
+	/// Host to connect to
+	target: String,
+}
+
+/// Looks up the most preferred SRV record for a name, using the DNS configuration of the system
+async fn lookup_srv(name: &str) -> anyhow::Result<Option<SrvRecord>> {
+	let resolver = TokioResolver::builder_tokio()
+		.context("Failed to read the DNS configuration of the system")?
+		.build();
+
+	let lookup = match tokio::time::timeout(TIMEOUT, resolver.srv_lookup(name))
+		.await
+		.context("Timed out waiting for DNS response")?
+	{
+		Ok(lookup) => lookup,
+		Err(e) if e.is_no_records_found() => return Ok(None),
+		Err(e) => return Err(e).context("Failed to look up SRV record"),
+	};
+
+	let records = lookup.iter().map(|x| SrvRecord {
+		priority: x.priority(),
+		weight: x.weight(),
+		port: x.port(),
+		target: x.target().to_utf8().trim_end_matches('.').to_string(),
+	});
+
+	Ok(get_preferred_record(records))
+}
+
+/// Picks the most preferred out of a set of SRV records
+fn get_preferred_record(records: impl IntoIterator<Item = SrvRecord>) -> Option<SrvRecord> {
+	records
+		.into_iter()
+		.min_by_key(|x| (x.priority, std::cmp::Reverse(x.weight)))
+}
+
+/// Status of a server from the server list ping
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ServerStatus {
+	/// Name of the version the server runs. Often includes the server software, like `Paper 1.20.4`
+	pub version_name: String,
+	/// Protocol version of the server
+	pub protocol: i32,
+	/// Number of players online
+	pub online_players: u32,
+	/// Maximum number of players
+	pub max_players: u32,
+	/// Round trip time of a ping to the server, in milliseconds
+	pub latency_ms: u64,
+}
+
+/// Format of the JSON status response from a server
+#[derive(Deserialize)]
+struct StatusResponse {
+	version: StatusVersion,
+	#[serde(default)]
+	players: Option<StatusPlayers>,
+}
+
+/// Version in a status response
+#[derive(Deserialize)]
+struct StatusVersion {
+	name: String,
+	protocol: i32,
+}
+
+/// Player counts in a status response
+#[derive(Deserialize)]
+struct StatusPlayers {
+	online: u32,
+	max: u32,
+}
+
+/// Gets the status of a server and measures its latency using the server list ping.
+/// Proxies often report the protocol they were pinged with if they support it, so the protocol of the client should be given if known
+pub async fn ping(server: &ResolvedServer, protocol: Option<i32>) -> anyhow::Result<ServerStatus> {
+	tokio::time::timeout(TIMEOUT, ping_impl(server, protocol))
+		.await
+		.context("Timed out waiting for the server to respond")?
+}
+
+/// Implementation of the server list ping without a timeout
+async fn ping_impl(server: &ResolvedServer, protocol: Option<i32>) -> anyhow::Result<ServerStatus> {
+	let address = server
+		.addresses
+		.first()
+		.context("Server has no addresses")?;
+	let mut stream = TcpStream::connect(address)
+		.await
+		.context("Failed to connect to server")?;
+
+	let mut handshake = Vec::new();
+	write_varint(&mut handshake, 0x00);
+	// Servers respond to status requests for any protocol version
+	write_varint(&mut handshake, protocol.unwrap_or(-1));
+	write_varint(&mut handshake, server.target.len() as i32);
+	handshake.extend_from_slice(server.target.as_bytes());
+	handshake.extend_from_slice(&server.port.to_be_bytes());
+	// Status intent
+	write_varint(&mut handshake, 1);
+	write_packet(&mut stream, &handshake).await?;
+	write_packet(&mut stream, &[0x00]).await?;
+
+	let response = read_packet(&mut stream).await?;
+	let mut reader = response.as_slice();
+	if read_varint(&mut reader)? != 0x00 {
+		bail!("Server sent an unexpected packet");
+	}
+	let length = read_varint(&mut reader)? as usize;
+	let json = reader
+		.get(..length)
+		.context("Status response is truncated")?;
+	let status: StatusResponse =
+		serde_json::from_slice(json).context("Failed to deserialize server status")?;
+
+	let start = Instant::now();
+	let mut ping = vec![0x01];
+	ping.extend_from_slice(&0i64.to_be_bytes());
+	write_packet(&mut stream, &ping).await?;
+	read_packet(&mut stream)
+		.await
+		.context("Server did not respond to ping")?;
+	let latency = start.elapsed();
+
+	let (online_players, max_players) = status
+		.players
+		.map(|x| (x.online, x.max))
+		.unwrap_or_default();
+
+	Ok(ServerStatus {
+		version_name: status.version.name,
+		protocol: status.version.protocol,
+		online_players,
+		max_players,
+		latency_ms: latency.as_millis() as u64,
+	})
+}
+
+/// Writes a length-prefixed packet to a server
+async fn write_packet(stream: &mut TcpStream, data: &[u8]) -> anyhow::Result<()> {
+	let mut packet = Vec::with_capacity(data.len() + 5);
+	write_varint(&mut packet, data.len() as i32);
+	packet.extend_from_slice(data);
+	stream
+		.write_all(&packet)
+		.await
+		.context("Failed to send packet to server")
+}
+
+/// Reads a length-prefixed packet from a server
+async fn read_packet(stream: &mut TcpStream) -> anyhow::Result<Vec<u8>> {
+	let mut length = 0usize;
+	for i in 0..5 {
+		let byte = stream
+			.read_u8()
+			.await
+			.context("Failed to read packet from server")?;
+		length |= ((byte & 0x7F) as usize) << (7 * i);
+		if byte & 0x80 == 0 {
+			if length > MAX_PACKET_LENGTH {
+				bail!("Packet from server is too large");
+			}
+			let mut buf = vec![0; length];
+			stream
+				.read_exact(&mut buf)
+				.await
+				.context("Failed to read packet from server")?;
+			return Ok(buf);
+		}
+	}
+
+	bail!("Packet length from server is invalid")
+}
+
+/// Writes a protocol VarInt
+fn write_varint(out: &mut Vec<u8>, value: i32) {
+	let mut value = value as u32;
+	loop {
+		if value & !0x7F == 0 {
+			out.push(value as u8);
+			return;
+		}
+		out.push((value & 0x7F) as u8 | 0x80);
+		value >>= 7;
+	}
+}
+
+/// Reads a protocol VarInt, advancing the reader past it
+fn read_varint(reader: &mut &[u8]) -> anyhow::Result<i32> {
+	let mut value = 0u32;
+	for i in 0..5 {
+		let (byte, rest) = reader.split_first().context("VarInt is truncated")?;
+		*reader = rest;
+		value |= ((byte & 0x7F) as u32) << (7 * i);
+		if byte & 0x80 == 0 {
+			return Ok(value as i32);
+		}
+	}
+
+	bail!("VarInt is too long")
+}
+
+/// Whether an instance can join a server
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "type")]
+pub enum VersionCompatibility {
+	/// The server runs the same version as the instance
+	Compatible,
+	/// The server runs a different version than the instance
+	Incompatible {
+		/// The version that the server runs
+		server_version: String,
+	},
+	/// The versions can't be compared, like when the server accepts a range of versions through a proxy
+	Unknown,
+}
+
+/// Compares the status of a server with the version of an instance. The protocol numbers are compared
+/// if the protocol of the instance is known, falling back to comparing the version names
+pub fn check_version_compatibility(
+	status: &ServerStatus,
+	instance_version: &str,
+	instance_protocol: Option<i32>,
+) -> VersionCompatibility {
+	let by_name = check_version_name_compatibility(&status.version_name, instance_version);
+	// Some servers report a negative protocol when they don't have a single one
+	match instance_protocol {
+		Some(protocol) if status.protocol >= 0 => {
+			if status.protocol == protocol {
+				VersionCompatibility::Compatible
+			} else if let VersionCompatibility::Incompatible { server_version } = by_name {
+				VersionCompatibility::Incompatible { server_version }
+			} else {
+				VersionCompatibility::Incompatible {
+					server_version: status.version_name.clone(),
+				}
+			}
+		}
+		_ => by_name,
+	}
+}
+
+/// Compares the version name from the status of a server with the version of an instance
+fn check_version_name_compatibility(
+	server_version: &str,
+	instance_version: &str,
+) -> VersionCompatibility {
+	let mut versions = Vec::new();
+	// Version names are often prefixed with the server software, like `Paper 1.20.4`, or list
+	// multiple versions, like `Velocity 1.7.2-1.21.1` or `1.20.5, 1.20.6`
+	for part in server_version.split(|c: char| c.is_whitespace() || c == ',') {
+		if part == instance_version {
+			return VersionCompatibility::Compatible;
+		}
+		if part.starts_with(|c: char| c.is_ascii_digit()) && part.contains('.') {
+			// Ranges and wildcards can't be checked
+			if !part.chars().all(|c| c.is_ascii_digit() || c == '.') {
+				return VersionCompatibility::Unknown;
+			}
+			versions.push(part);
+		}
+	}
+
+	match versions.as_slice() {
+		[version] => VersionCompatibility::Incompatible {
+			server_version: version.to_string(),
+		},
+		_ => VersionCompatibility::Unknown,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_address_splitting() {
+		assert_eq!(
+			split_address("play.example.com").unwrap(),
+			("play.example.com".into(), None)
+		);
+		assert_eq!(
+			split_address("example.com:25566").unwrap(),
+			("example.com".into(), Some(25566))
+		);
+		assert_eq!(split_address("::1").unwrap(), ("::1".into(), None));
+		assert_eq!(
+			split_address("[::1]:25570").unwrap(),
+			("::1".into(), Some(25570))
+		);
+		assert!(split_address("example.com:abc").is_err());
+	}
+
+	#[test]
+	fn test_preferred_srv_record() {
+		let record = |priority, weight, target: &str| SrvRecord {
+			priority,
+			weight,
+			port: 25565,
+			target: target.into(),
+		};
+		let records = vec![
+			record(20, 5, "backup.example.com"),
+			record(10, 1, "light.example.com"),
+			record(10, 5, "mc.example.com"),
+		];
+
+		assert_eq!(
+			get_preferred_record(records),
+			Some(record(10, 5, "mc.example.com"))
+		);
+		assert_eq!(get_preferred_record(Vec::new()), None);
+	}
+
+	#[test]
+	fn test_varints() {
+		for value in [0, 1, 127, 128, 25565, -1, i32::MAX] {
+			let mut out = Vec::new();
+			write_varint(&mut out, value);
+			assert_eq!(read_varint(&mut out.as_slice()).unwrap(), value);
+		}
+	}
+
+	#[test]
+	fn test_version_compatibility() {
+		let status = |name: &str, protocol: i32| ServerStatus {
+			version_name: name.into(),
+			protocol,
+			online_players: 0,
+			max_players: 0,
+			latency_ms: 0,
+		};
+		assert_eq!(
+			check_version_compatibility(&status("Paper 1.21", 767), "1.21.1", Some(767)),
+			VersionCompatibility::Compatible
+		);
+		assert_eq!(
+			check_version_compatibility(&status("Paper 1.20.4", 765), "1.21", Some(767)),
+			VersionCompatibility::Incompatible {
+				server_version: "1.20.4".into()
+			}
+		);
+		assert_eq!(
+			check_version_compatibility(&status("Velocity 1.7.2-1.21.1", 760), "1.21", Some(767)),
+			VersionCompatibility::Incompatible {
+				server_version: "Velocity 1.7.2-1.21.1".into()
+			}
+		);
+		assert_eq!(
+			check_version_compatibility(&status("Paper 1.20.4", 765), "1.21", None),
+			VersionCompatibility::Incompatible {
+				server_version: "1.20.4".into()
+			}
+		);
+		assert_eq!(
+			check_version_compatibility(&status("Custom", -1), "1.21", Some(767)),
+			VersionCompatibility::Unknown
+		);
+	}
+
+	#[test]
+	fn test_version_name_compatibility() {
+		assert_eq!(
+			check_version_name_compatibility("Paper 1.21", "1.21"),
+			VersionCompatibility::Compatible
+		);
+		assert_eq!(
+			check_version_name_compatibility("Paper 1.20.4", "1.21"),
+			VersionCompatibility::Incompatible {
+				server_version: "1.20.4".into()
+			}
+		);
+		assert_eq!(
+			check_version_name_compatibility("Velocity 1.7.2-1.21.1", "1.21"),
+			VersionCompatibility::Unknown
+		);
+		assert_eq!(
+			check_version_name_compatibility("1.20.5, 1.20.6", "1.20.6"),
+			VersionCompatibility::Compatible
+		);
+	}
+}
@@ -1,6 +1,6 @@
 use crate::download::{self, user_agent};
 use anyhow::Context;
-use nitro_shared::pkg::{PackageCategory, PackageSearchParameters};
+use nitro_shared::pkg::{PackageCategory, PackageSearchParameters, PackageSortMethod};
 use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
 
@@ -13,20 +13,12 @@ pub async fn get_pack(id: &str, client: &Client) -> anyhow::Result<Pack> {
 /// Get a Smithed pack from the API, returning None on 404
 pub async fn get_pack_optional(id: &str, client: &Client) -> anyhow::Result<Option<Pack>> {
 	let url = format!("{API_URL}/packs/{id}");
-
-	let resp = client
-		.get(url)
-		.header("User-Agent", user_agent())
-		.send()
-		.await
-		.context("Failed to send request")?;
+	let resp = download::send(client.get(url).header("User-Agent", user_agent())).await?;
 	if resp.status() == StatusCode::NOT_FOUND {
 		return Ok(None);
 	}
 
-	let resp = resp
-		.error_for_status()
-		.context("Server returned an error")?;
+	let resp = download::check_status(resp)?;
 
 	resp.json()
 		.await
@@ -45,6 +37,26 @@ pub struct Pack {
 	pub versions: Vec<PackVersion>,
 	#[serde(default)]
 	pub categories: Vec<String>,
+	#[serde(default)]
+	pub stats: PackStats,
+}
+
+/// Statistics for a Smithed pack
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct PackStats {
+	pub downloads: PackDownloadStats,
+	/// When the pack was added, as a UTC timestamp in milliseconds
+	pub added: Option<u64>,
+	/// When the pack was last updated, as a UTC timestamp in milliseconds
+	pub updated: Option<u64>,
+}
+
+/// Download statistics for a Smithed pack
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct PackDownloadStats {
+	pub total: Option<u32>,
 }
 
 /// Get a Smithed pack meta from the API
@@ -148,8 +160,17 @@ pub async fn search_packs(
 
 	let filters = create_search_filters(params.minecraft_versions, params.categories);
 
+	// Smithed has no way to sort by followers or update date
+	let sort = match params.sort {
+		PackageSortMethod::Downloads => "&sort=downloads",
+		PackageSortMethod::Newest => "&sort=newest",
+		PackageSortMethod::Relevance
+		| PackageSortMethod::Followers
+		| PackageSortMethod::Updated => "",
+	};
+
 	let url = format!(
-		"{API_URL}/packs?limit={limit}{search}&page={page}{filters}&scope=data&scope=meta.rawId"
+		"{API_URL}/packs?limit={limit}{search}&page={page}{filters}{sort}&scope=data&scope=meta.rawId"
 	);
 
 	download::json(url, client).await
@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use anyhow::Context;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// Statistics collected by this process that have not been saved yet
+static STATS: LazyLock<Mutex<NetworkStats>> = LazyLock::new(|| Mutex::new(NetworkStats::default()));
+
+/// Network statistics for every host that was contacted
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct NetworkStats {
+	/// Statistics for each host
+	pub hosts: BTreeMap<String, HostStats>,
+}
+
+/// Network statistics for a single host
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct HostStats {
+	/// Number of requests sent to the host
+	pub requests: u64,
+	/// Number of requests that failed to connect or returned an error status
+	pub errors: u64,
+	/// Number of bytes received from the host, as reported by its responses
+	pub bytes: u64,
+	/// Number of downloads that were skipped because the file was already cached
+	pub cache_hits: u64,
+	/// Total time spent waiting for responses, in milliseconds
+	pub response_time_ms: u64,
+}
+
+impl HostStats {
+	/// Gets the fraction of downloads that were served from the cache
+	pub fn cache_hit_ratio(&self) -> f64 {
+		ratio(self.cache_hits, self.cache_hits + self.requests)
+	}
+
+	/// Gets the fraction of requests that failed
+	pub fn error_rate(&self) -> f64 {
+		ratio(self.errors, self.requests)
+	}
+
+	/// Gets the average time to receive a response, in milliseconds
+	pub fn average_response_time_ms(&self) -> u64 {
+		self.response_time_ms
+			.checked_div(self.requests)
+			.unwrap_or(0)
+	}
+
+	/// Adds the statistics from another set to this one
+	pub fn merge(&mut self, other: &HostStats) {
+		self.requests += other.requests;
+		self.errors += other.errors;
+		self.bytes += other.bytes;
+		self.cache_hits += other.cache_hits;
+		self.response_time_ms += other.response_time_ms;
+	}
+}
+
+impl NetworkStats {
+	/// Adds the statistics from another set to this one
+	pub fn merge(&mut self, other: &NetworkStats) {
+		for (host, stats) in &other.hosts {
+			self.hosts.entry(host.clone()).or_default().merge(stats);
+		}
+	}
+
+	/// Gets the combined statistics for all hosts
+	pub fn total(&self) -> HostStats {
+		let mut out = HostStats::default();
+		for stats in self.hosts.values() {
+			out.merge(stats);
+		}
+		out
+	}
+
+	/// Loads stored statistics from a file, or returns empty statistics if it does not exist
+	pub fn load(path: &Path) -> anyhow::Result<Self> {
+		if !path.exists() {
+			return Ok(Self::default());
+		}
+		let data = std::fs::read(path).context("Failed to read network stats file")?;
+		serde_json::from_slice(&data).context("Failed to deserialize network stats")
+	}
+
+	/// Writes statistics to a file. A temporary file is used so that other processes never read partial contents
+	pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+		let data = serde_json::to_vec(self).context("Failed to serialize network stats")?;
+		let tmp_path = path.with_extension("json.tmp");
+		std::fs::write(&tmp_path, data).context("Failed to write network stats file")?;
+		std::fs::rename(tmp_path, path).context("Failed to move network stats file into place")
+	}
+}
+
+fn ratio(part: u64, whole: u64) -> f64 {
+	if whole == 0 {
+		0.0
+	} else {
+		part as f64 / whole as f64
+	}
+}
+
+/// Gets the key that a URL's statistics are stored under
+fn get_host(url: &Url) -> String {
+	match (url.host_str(), url.port()) {
+		(Some(host), Some(port)) => format!("{host}:{port}"),
+		(Some(host), None) => host.to_string(),
+		_ => url.scheme().to_string(),
+	}
+}
+
+/// Runs a function on the statistics for the host of a URL
+fn update(url: &Url, f: impl FnOnce(&mut HostStats)) {
+	if let Ok(mut stats) = STATS.lock() {
+		f(stats.hosts.entry(get_host(url)).or_default());
+	}
+}
+
+/// Records a response from a host
+pub fn record_response(url: &Url, bytes: Option<u64>, time: Duration, is_error: bool) {
+	update(url, |stats| {
+		stats.requests += 1;
+		stats.bytes += bytes.unwrap_or_default();
+		stats.response_time_ms += time.as_millis() as u64;
+		if is_error {
+			stats.errors += 1;
+		}
+	});
+}
+
+/// Records a request to a host that failed before a response was received
+pub fn record_failure(url: &Url, time: Duration) {
+	update(url, |stats| {
+		stats.requests += 1;
+		stats.errors += 1;
+		stats.response_time_ms += time.as_millis() as u64;
+	});
+}
+
+/// Records a download that was skipped because the file is already cached
+pub fn record_cache_hit(url: &str) {
+	if let Ok(url) = Url::parse(url) {
+		update(&url, |stats| stats.cache_hits += 1);
+	}
+}
+
+/// Gets the statistics collected by this process that have not been saved yet
+pub fn get_stats() -> NetworkStats {
+	STATS.lock().map(|x| x.clone()).unwrap_or_default()
+}
+
+/// Adds the statistics collected by this process to the ones stored in a file,
+/// and clears them so that they are not saved twice
+pub fn save(path: &Path) -> anyhow::Result<()> {
+	let new = {
+		let Ok(mut stats) = STATS.lock() else {
+			return Ok(());
+		};
+		std::mem::take(&mut *stats)
+	};
+	if new.hosts.is_empty() {
+		return Ok(());
+	}
+
+	let mut stored = NetworkStats::load(path).unwrap_or_default();
+	stored.merge(&new);
+	stored.write(path)
+}
+
+/// Loads the statistics stored in a file along with the ones collected by this process
+pub fn load(path: &Path) -> anyhow::Result<NetworkStats> {
+	let mut out = NetworkStats::load(path)?;
+	out.merge(&get_stats());
+	Ok(out)
+}
+
+/// Clears all stored statistics and the ones collected by this process
+pub fn reset(path: &Path) -> anyhow::Result<()> {
+	if let Ok(mut stats) = STATS.lock() {
+		*stats = NetworkStats::default();
+	}
+	if path.exists() {
+		std::fs::remove_file(path).context("Failed to remove network stats file")?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_stats_merge() {
+		let mut stats = NetworkStats::default();
+		stats.hosts.insert(
+			"cdn.modrinth.com".into(),
+			HostStats {
+				requests: 3,
+				errors: 1,
+				cache_hits: 1,
+				response_time_ms: 300,
+				..Default::default()
+			},
+		);
+
+		let mut other = NetworkStats::default();
+		other.hosts.insert(
+			"cdn.modrinth.com".into(),
+			HostStats {
+				requests: 1,
+				cache_hits: 3,
+				..Default::default()
+			},
+		);
+		other
+			.hosts
+			.insert("libraries.minecraft.net".into(), HostStats::default());
+		stats.merge(&other);
+
+		assert_eq!(stats.hosts.len(), 2);
+		let host = &stats.hosts["cdn.modrinth.com"];
+		assert_eq!(host.requests, 4);
+		assert_eq!(host.cache_hit_ratio(), 0.5);
+		assert_eq!(host.error_rate(), 0.25);
+		assert_eq!(host.average_response_time_ms(), 75);
+		assert_eq!(stats.hosts["libraries.minecraft.net"].error_rate(), 0.0);
+	}
+}
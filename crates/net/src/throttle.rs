@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+use reqwest::{StatusCode, Url};
+
+/// The most recent rate limit reported by each host
+static RATE_LIMITS: LazyLock<Mutex<HashMap<String, RateLimit>>> =
+	LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The longest we will wait for a rate limit to reset
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// A rate limit reported by a host through response headers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+	/// The number of requests allowed in each window
+	pub limit: u64,
+	/// The number of requests remaining in the current window
+	pub remaining: u64,
+	/// When the current window ends
+	pub reset: Instant,
+}
+
+impl RateLimit {
+	/// Checks whether the host has asked us to slow down, meaning less than a tenth of the window is remaining
+	pub fn is_near_limit(&self) -> bool {
+		self.reset > Instant::now() && self.remaining * 10 < self.limit
+	}
+}
+
+/// Records the rate limit headers from a response, such as the ones sent by Modrinth
+pub(crate) fn record_headers(url: &Url, status: StatusCode, headers: &HeaderMap) {
+	let get = |name: &str| -> Option<u64> { headers.get(name)?.to_str().ok()?.parse().ok() };
+
+	let rate_limit = if status == StatusCode::TOO_MANY_REQUESTS {
+		let retry_after = get("retry-after").or(get("x-ratelimit-reset")).unwrap_or(1);
+		RateLimit {
+			limit: get("x-ratelimit-limit").unwrap_or(1),
+			remaining: 0,
+			reset: Instant::now() + Duration::from_secs(retry_after),
+		}
+	} else {
+		let (Some(limit), Some(remaining)) =
+			(get("x-ratelimit-limit"), get("x-ratelimit-remaining"))
+		else {
+			return;
+		};
+		RateLimit {
+			limit,
+			remaining,
+			reset: Instant::now() + Duration::from_secs(get("x-ratelimit-reset").unwrap_or(0)),
+		}
+	};
+
+	if let (Some(host), Ok(mut rate_limits)) = (url.host_str(), RATE_LIMITS.lock()) {
+		rate_limits.insert(host.to_string(), rate_limit);
+	}
+}
+
+/// Gets the most recent rate limit reported by the host of a URL
+pub fn get_rate_limit(url: &Url) -> Option<RateLimit> {
+	let rate_limits = RATE_LIMITS.lock().ok()?;
+	rate_limits.get(url.host_str()?).copied()
+}
+
+/// Waits until the rate limit for the host of a URL resets if we have run out of requests
+pub async fn wait_for_rate_limit(url: &Url) {
+	let Some(rate_limit) = get_rate_limit(url) else {
+		return;
+	};
+	if rate_limit.remaining > 0 {
+		return;
+	}
+	if let Some(wait) = rate_limit.reset.checked_duration_since(Instant::now()) {
+		tokio::time::sleep(wait.min(MAX_RATE_LIMIT_WAIT)).await;
+	}
+}
+
+/// Chooses sizes for batches of requests to a single host. Batches grow while the host responds
+/// quickly and shrink when it is slow or close to its rate limit, so that large background loads
+/// don't take over the connection
+pub struct AdaptiveBatcher {
+	url: Url,
+	size: usize,
+	min: usize,
+	max: usize,
+	target_latency: Duration,
+}
+
+impl AdaptiveBatcher {
+	/// Creates a new batcher for the host of the given URL, with batches between the minimum and maximum size
+	pub fn new(url: &str, min: usize, max: usize) -> Self {
+		Self {
+			url: Url::parse(url).expect("Batcher URL should be valid"),
+			size: min,
+			min,
+			max,
+			target_latency: Duration::from_millis(750),
+		}
+	}
+
+	/// Sets how long a batch can take before the batch size is reduced
+	pub fn target_latency(mut self, latency: Duration) -> Self {
+		self.target_latency = latency;
+		self
+	}
+
+	/// Gets the size of the next batch
+	pub fn next_size(&self) -> usize {
+		match get_rate_limit(&self.url) {
+			Some(rate_limit) if rate_limit.is_near_limit() => self.min,
+			_ => self.size,
+		}
+	}
+
+	/// Takes the next batch off the front of a list of items, waiting first if the host's rate limit has been used up
+	pub async fn next_batch<'a, T>(&self, items: &mut &'a [T]) -> &'a [T] {
+		wait_for_rate_limit(&self.url).await;
+		let (batch, rest) = items.split_at(self.next_size().min(items.len()));
+		*items = rest;
+		batch
+	}
+
+	/// Records how long the last batch took, adjusting the size of future batches
+	pub fn record(&mut self, latency: Duration) {
+		if latency <= self.target_latency {
+			self.size = (self.size * 2).min(self.max);
+		} else if latency > self.target_latency * 2 {
+			self.size = (self.size / 2).max(self.min);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_adaptive_batch_size() {
+		let mut batcher = AdaptiveBatcher::new("https://example.com", 10, 50)
+			.target_latency(Duration::from_secs(1));
+		assert_eq!(batcher.next_size(), 10);
+
+		batcher.record(Duration::from_millis(100));
+		assert_eq!(batcher.next_size(), 20);
+		batcher.record(Duration::from_millis(100));
+		batcher.record(Duration::from_millis(100));
+		assert_eq!(batcher.next_size(), 50);
+
+		// Slightly slow responses keep the same size, but very slow ones shrink it
+		batcher.record(Duration::from_millis(1500));
+		assert_eq!(batcher.next_size(), 50);
+		batcher.record(Duration::from_secs(5));
+		assert_eq!(batcher.next_size(), 25);
+
+		let url = Url::parse("https://example.com").unwrap();
+		let mut headers = HeaderMap::new();
+		headers.insert("x-ratelimit-limit", "300".parse().unwrap());
+		headers.insert("x-ratelimit-remaining", "20".parse().unwrap());
+		headers.insert("x-ratelimit-reset", "30".parse().unwrap());
+		record_headers(&url, StatusCode::OK, &headers);
+		assert_eq!(batcher.next_size(), 10);
+	}
+}
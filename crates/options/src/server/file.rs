@@ -20,21 +20,30 @@ pub fn write_server_properties(
 	path: &Path,
 ) -> anyhow::Result<()> {
 	let options = merge_server_properties(path, options).context("Failed to merge properties")?;
-	let file = File::create(path).context("Failed to open file")?;
+	// Write to a temporary file first so that the server never sees a partially written file
+	let tmp_path = path.with_extension("properties.tmp");
+	let file = File::create(&tmp_path).context("Failed to open file")?;
 	let mut file = BufWriter::new(file);
 	for (key, value) in options.iter().sorted_by_key(|x| x.0) {
 		write_key(key, value, &mut file)
 			.with_context(|| format!("Failed to write line for option {key} with value {value}"))?;
 	}
+	file.flush().context("Failed to flush file")?;
+	std::mem::drop(file);
+	std::fs::rename(&tmp_path, path).context("Failed to move file into place")?;
 
 	Ok(())
 }
 
-/// Collect a hashmap from an existing server.properties file so we can compare with it
-fn read_server_properties(path: &Path) -> anyhow::Result<HashMap<String, String>> {
+/// Reads the keys from an existing server.properties file, with escaped colons removed
+pub fn read_server_properties(path: &Path) -> anyhow::Result<HashMap<String, String>> {
 	if path.exists() {
-		let contents = std::fs::read_to_string(path).context("Failed to read options.txt")?;
-		read_options_file(&contents, SEP)
+		let contents = std::fs::read_to_string(path).context("Failed to read server.properties")?;
+		let mut out = read_options_file(&contents, SEP)?;
+		for value in out.values_mut() {
+			*value = value.replace("\\:", ":");
+		}
+		Ok(out)
 	} else {
 		Ok(HashMap::new())
 	}
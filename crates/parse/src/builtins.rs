@@ -0,0 +1,322 @@
+use std::cmp::Ordering;
+
+use anyhow::{Context, anyhow, bail};
+
+/// An operation for the str instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringOperation {
+	/// Join all of the arguments together
+	Concat,
+	/// Replace all occurrences of a pattern in a string
+	Replace,
+	/// Convert a string to lowercase
+	Lower,
+	/// Convert a string to uppercase
+	Upper,
+	/// Remove whitespace from both ends of a string
+	Trim,
+	/// Get the length of a string in characters
+	Length,
+	/// Get a range of characters from a string
+	Substring,
+	/// Split a string by a separator into a JSON array
+	Split,
+}
+
+impl StringOperation {
+	/// Parse a StringOperation from a string
+	pub fn parse_from_str(string: &str) -> Option<Self> {
+		match string {
+			"concat" => Some(Self::Concat),
+			"replace" => Some(Self::Replace),
+			"lower" => Some(Self::Lower),
+			"upper" => Some(Self::Upper),
+			"trim" => Some(Self::Trim),
+			"length" => Some(Self::Length),
+			"substring" => Some(Self::Substring),
+			"split" => Some(Self::Split),
+			_ => None,
+		}
+	}
+
+	/// Checks if a number of arguments is valid for this operation
+	pub fn is_valid_arg_count(&self, count: usize) -> bool {
+		match self {
+			Self::Concat => count >= 1,
+			Self::Lower | Self::Upper | Self::Trim | Self::Length => count == 1,
+			Self::Split => count == 2,
+			Self::Substring => count == 2 || count == 3,
+			Self::Replace => count == 3,
+		}
+	}
+
+	/// Run this operation on a list of arguments
+	pub fn run(&self, args: &[String]) -> anyhow::Result<String> {
+		if !self.is_valid_arg_count(args.len()) {
+			bail!("Invalid number of arguments for string operation");
+		}
+
+		let out = match self {
+			Self::Concat => args.concat(),
+			Self::Replace => args[0].replace(&args[1], &args[2]),
+			Self::Lower => args[0].to_lowercase(),
+			Self::Upper => args[0].to_uppercase(),
+			Self::Trim => args[0].trim().to_string(),
+			Self::Length => args[0].chars().count().to_string(),
+			Self::Substring => {
+				let start = parse_index(&args[1])?;
+				let chars = args[0].chars().skip(start);
+				if let Some(len) = args.get(2) {
+					chars.take(parse_index(len)?).collect()
+				} else {
+					chars.collect()
+				}
+			}
+			Self::Split => {
+				if args[1].is_empty() {
+					bail!("Split separator cannot be empty");
+				}
+				let parts: Vec<_> = args[0].split(args[1].as_str()).collect();
+				serde_json::to_string(&parts)?
+			}
+		};
+
+		Ok(out)
+	}
+}
+
+/// Parses a non-negative index argument
+fn parse_index(string: &str) -> anyhow::Result<usize> {
+	string
+		.parse()
+		.with_context(|| format!("Invalid index '{string}'"))
+}
+
+/// Comparison operator for the semver condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemverOperator {
+	/// Versions are equal
+	Eq,
+	/// Left version is less than the right version
+	Lt,
+	/// Left version is less than or equal to the right version
+	Lte,
+	/// Left version is greater than the right version
+	Gt,
+	/// Left version is greater than or equal to the right version
+	Gte,
+}
+
+impl SemverOperator {
+	/// Parse a SemverOperator from a string
+	pub fn parse_from_str(string: &str) -> Option<Self> {
+		match string {
+			"eq" => Some(Self::Eq),
+			"lt" => Some(Self::Lt),
+			"lte" => Some(Self::Lte),
+			"gt" => Some(Self::Gt),
+			"gte" => Some(Self::Gte),
+			_ => None,
+		}
+	}
+
+	/// Checks if an ordering satisfies this operator
+	pub fn matches(&self, ordering: Ordering) -> bool {
+		match self {
+			Self::Eq => ordering.is_eq(),
+			Self::Lt => ordering.is_lt(),
+			Self::Lte => ordering.is_le(),
+			Self::Gt => ordering.is_gt(),
+			Self::Gte => ordering.is_ge(),
+		}
+	}
+}
+
+/// Compares two semantic versions. Missing minor and patch numbers are treated as zero
+/// and build metadata is ignored, so that versions like 1.20 can be compared as well.
+pub fn compare_semver(left: &str, right: &str) -> anyhow::Result<Ordering> {
+	let left = SemanticVersion::parse(left)?;
+	let right = SemanticVersion::parse(right)?;
+
+	Ok(left.cmp(&right))
+}
+
+/// A parsed semantic version
+#[derive(Debug, PartialEq, Eq)]
+struct SemanticVersion<'a> {
+	numbers: [u64; 3],
+	pre_release: Vec<&'a str>,
+}
+
+impl<'a> SemanticVersion<'a> {
+	fn parse(string: &'a str) -> anyhow::Result<Self> {
+		let trimmed = string.trim().trim_start_matches('v');
+		let trimmed = trimmed.split_once('+').map_or(trimmed, |x| x.0);
+		let (core, pre_release) = match trimmed.split_once('-') {
+			Some((core, pre_release)) => (core, pre_release.split('.').collect()),
+			None => (trimmed, Vec::new()),
+		};
+
+		let mut numbers = [0; 3];
+		let mut parts = core.split('.');
+		for number in &mut numbers {
+			if let Some(part) = parts.next() {
+				*number = part
+					.parse()
+					.map_err(|_| anyhow!("Invalid semantic version '{string}'"))?;
+			}
+		}
+		if parts.next().is_some() {
+			bail!("Invalid semantic version '{string}'");
+		}
+
+		Ok(Self {
+			numbers,
+			pre_release,
+		})
+	}
+}
+
+impl Ord for SemanticVersion<'_> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.numbers.cmp(&other.numbers).then_with(|| {
+			// Pre-releases come before the release
+			match (self.pre_release.is_empty(), other.pre_release.is_empty()) {
+				(true, true) => Ordering::Equal,
+				(true, false) => Ordering::Greater,
+				(false, true) => Ordering::Less,
+				(false, false) => {
+					for (left, right) in self.pre_release.iter().zip(&other.pre_release) {
+						let ordering = compare_pre_release_identifier(left, right);
+						if ordering.is_ne() {
+							return ordering;
+						}
+					}
+					self.pre_release.len().cmp(&other.pre_release.len())
+				}
+			}
+		})
+	}
+}
+
+impl PartialOrd for SemanticVersion<'_> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+/// Compares pre-release identifiers. Numeric identifiers are lower than alphanumeric ones
+fn compare_pre_release_identifier(left: &str, right: &str) -> Ordering {
+	match (left.parse::<u64>(), right.parse::<u64>()) {
+		(Ok(left), Ok(right)) => left.cmp(&right),
+		(Ok(..), Err(..)) => Ordering::Less,
+		(Err(..), Ok(..)) => Ordering::Greater,
+		(Err(..), Err(..)) => left.cmp(right),
+	}
+}
+
+/// Gets a value from a JSON string using a dot-separated path of object keys and array indices.
+/// Strings are returned without quotes, and any other values are returned as JSON.
+pub fn get_json_value(json: &str, path: &str) -> anyhow::Result<String> {
+	let mut value: serde_json::Value = serde_json::from_str(json).context("Invalid JSON")?;
+	if !path.is_empty() {
+		for key in path.split('.') {
+			let next = match &mut value {
+				serde_json::Value::Object(object) => object.remove(key),
+				serde_json::Value::Array(array) => key
+					.parse::<usize>()
+					.ok()
+					.filter(|x| *x < array.len())
+					.map(|x| array.swap_remove(x)),
+				_ => None,
+			};
+			value = next.ok_or_else(|| anyhow!("JSON path '{path}' does not exist"))?;
+		}
+	}
+
+	json_value_to_string(value)
+}
+
+/// Gets the elements of a JSON array string, in the same format as get_json_value
+pub fn get_json_list(json: &str) -> anyhow::Result<Vec<String>> {
+	let value: serde_json::Value = serde_json::from_str(json).context("Invalid JSON")?;
+	let serde_json::Value::Array(array) = value else {
+		bail!("JSON value is not an array");
+	};
+
+	array.into_iter().map(json_value_to_string).collect()
+}
+
+fn json_value_to_string(value: serde_json::Value) -> anyhow::Result<String> {
+	match value {
+		serde_json::Value::String(string) => Ok(string),
+		value => Ok(serde_json::to_string(&value)?),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_string_operations() {
+		let args = |x: &[&str]| x.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+
+		assert_eq!(
+			StringOperation::Concat
+				.run(&args(&["foo", "-", "bar"]))
+				.unwrap(),
+			"foo-bar"
+		);
+		assert_eq!(
+			StringOperation::Replace
+				.run(&args(&["1.20.1", ".", "_"]))
+				.unwrap(),
+			"1_20_1"
+		);
+		assert_eq!(
+			StringOperation::Substring
+				.run(&args(&["fabric-api", "7", "2"]))
+				.unwrap(),
+			"ap"
+		);
+		assert_eq!(
+			StringOperation::Split.run(&args(&["a,b", ","])).unwrap(),
+			r#"["a","b"]"#
+		);
+		assert!(StringOperation::Lower.run(&args(&["A", "B"])).is_err());
+	}
+
+	#[test]
+	fn test_semver_comparison() {
+		assert!(compare_semver("1.2.3", "1.10.0").unwrap().is_lt());
+		assert!(compare_semver("1.20", "1.20.0").unwrap().is_eq());
+		assert!(compare_semver("v2.0.0", "2.0.0+build.5").unwrap().is_eq());
+		assert!(compare_semver("1.0.0-alpha", "1.0.0").unwrap().is_lt());
+		assert!(
+			compare_semver("1.0.0-alpha.2", "1.0.0-alpha.10")
+				.unwrap()
+				.is_lt()
+		);
+		assert!(
+			compare_semver("1.0.0-beta", "1.0.0-alpha.1")
+				.unwrap()
+				.is_gt()
+		);
+		assert!(compare_semver("1.0.0.0", "1.0.0").is_err());
+		assert!(compare_semver("foo", "1.0.0").is_err());
+	}
+
+	#[test]
+	fn test_json_access() {
+		let json = r#"{"versions": [{"id": "abc", "size": 5}], "name": "Foo"}"#;
+		assert_eq!(get_json_value(json, "name").unwrap(), "Foo");
+		assert_eq!(get_json_value(json, "versions.0.id").unwrap(), "abc");
+		assert_eq!(get_json_value(json, "versions.0.size").unwrap(), "5");
+		assert!(get_json_value(json, "versions.1").is_err());
+		assert_eq!(
+			get_json_list(r#"["a", 1, true]"#).unwrap(),
+			vec!["a", "1", "true"]
+		);
+	}
+}
@@ -10,6 +10,7 @@ use crate::unexpected_token;
 use nitro_shared::Side;
 use nitro_shared::loaders::LoaderMatch;
 
+use super::builtins::SemverOperator;
 use super::instruction::parse_arg;
 use super::lex::{TextPos, Token};
 use super::vars::Value;
@@ -69,6 +70,8 @@ pub enum ConditionKind {
 	ContentVersion(Value),
 	/// Check if a plugin is present
 	Plugin(Value),
+	/// Compare two semantic versions
+	Semver(Value, Later<SemverOperator>, Value),
 }
 
 /// Value for the OS condition
@@ -146,6 +149,7 @@ impl ConditionKind {
 			"language" => Some(Self::Language(Later::Empty)),
 			// TODO: Content version
 			"plugin" => Some(Self::Plugin(Value::None)),
+			"semver" => Some(Self::Semver(Value::None, Later::Empty, Value::None)),
 			_ => None,
 		}
 	}
@@ -171,6 +175,7 @@ impl ConditionKind {
 			Self::Language(val) => val.is_full(),
 			Self::Value(left, right) => left.is_some() && right.is_some(),
 			Self::Plugin(plugin) => plugin.is_some(),
+			Self::Semver(left, op, right) => left.is_some() && op.is_full() && right.is_some(),
 		}
 	}
 
@@ -285,6 +290,22 @@ impl ConditionKind {
 					*plugin = parse_arg(tok, pos)?;
 				}
 			}
+			Self::Semver(left, op, right) => {
+				if !left.is_some() {
+					*left = parse_arg(tok, pos)?;
+				} else if op.is_empty() {
+					match tok {
+						Token::Ident(name) => op.fill(check_enum_condition_argument(
+							SemverOperator::parse_from_str(name),
+							name,
+							pos,
+						)?),
+						_ => unexpected_token!(tok, pos),
+					}
+				} else {
+					*right = parse_arg(tok, pos)?;
+				}
+			}
 		}
 		Ok(())
 	}
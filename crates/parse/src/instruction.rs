@@ -9,6 +9,7 @@ use nitro_shared::util::yes_no;
 use nitro_shared::versions::VersionPattern;
 
 use super::FailReason;
+use super::builtins::StringOperation;
 use super::conditions::Condition;
 use super::lex::{TextPos, Token};
 use super::parse::BlockId;
@@ -123,6 +124,19 @@ pub enum InstrKind {
 	},
 	/// Set a variable to a value
 	Set(Later<String>, Value),
+	/// Set a variable to the result of a string operation
+	Str(Later<String>, Later<StringOperation>, Vec<Value>),
+	/// Set a variable to a value from a JSON string
+	Json(Later<String>, Value, Value),
+	/// Run a block for every item in a list
+	For {
+		/// The variable that each item is assigned to
+		variable: String,
+		/// The list to iterate over
+		list: ForList,
+		/// The block to run for each item
+		block: BlockId,
+	},
 	/// Require a package
 	Require(Vec<Vec<super::parse::require::Package>>),
 	/// Refuse a package
@@ -151,6 +165,15 @@ pub enum InstrKind {
 	Custom(Later<String>, Vec<String>),
 }
 
+/// The list of items that a for instruction iterates over
+#[derive(Debug, Clone)]
+pub enum ForList {
+	/// A list of values
+	Values(Vec<Value>),
+	/// The elements of a JSON array
+	Json(Value),
+}
+
 /// A non-nested else / else if block connected to an if
 #[derive(Debug, Clone)]
 pub struct ElseBlock {
@@ -201,6 +224,9 @@ impl Display for InstrKind {
 				Self::OpenSource(..) => "open_source",
 				Self::Addon { .. } => "addon",
 				Self::Set(..) => "set",
+				Self::Str(..) => "str",
+				Self::Json(..) => "json",
+				Self::For { .. } => "for",
 				Self::Require(..) => "require",
 				Self::Refuse(..) => "refuse",
 				Self::Recommend(..) => "recommend",
@@ -260,6 +286,8 @@ impl Instruction {
 			"tags" => Ok(InstrKind::Tags(Vec::new())),
 			"open_source" => Ok(InstrKind::OpenSource(Later::Empty)),
 			"set" => Ok(InstrKind::Set(Later::Empty, Value::None)),
+			"str" => Ok(InstrKind::Str(Later::Empty, Later::Empty, Vec::new())),
+			"json" => Ok(InstrKind::Json(Later::Empty, Value::None, Value::None)),
 			"finish" => Ok(InstrKind::Finish()),
 			"fail" => Ok(InstrKind::Fail(None)),
 			"refuse" => Ok(InstrKind::Refuse(Value::None)),
@@ -322,10 +350,17 @@ impl Instruction {
 			InstrKind::OpenSource(val) => val.is_full(),
 			InstrKind::Compat(val1, val2) => val1.is_some() && val2.is_some(),
 			InstrKind::Set(var, val) => var.is_full() && val.is_some(),
+			InstrKind::Str(var, op, args) => {
+				var.is_full() && op.is_full() && op.get().is_valid_arg_count(args.len())
+			}
+			InstrKind::Json(var, json, path) => var.is_full() && json.is_some() && path.is_some(),
 			InstrKind::Cmd(list) => !list.is_empty(),
 			InstrKind::Downloads(val) => !val.is_empty(),
 			InstrKind::Fail(..) | InstrKind::Finish() => true,
-			InstrKind::If { .. } | InstrKind::Addon { .. } | InstrKind::Require(..) => {
+			InstrKind::If { .. }
+			| InstrKind::For { .. }
+			| InstrKind::Addon { .. }
+			| InstrKind::Require(..) => {
 				unimplemented!()
 			}
 		}
@@ -462,6 +497,35 @@ impl Instruction {
 						}
 					}
 				}
+				InstrKind::Str(var, op, args) => match tok {
+					Token::Ident(name) if var.is_empty() => var.fill(name.clone()),
+					Token::Ident(name) if op.is_empty() => {
+						match StringOperation::parse_from_str(name) {
+							Some(operation) => op.fill(operation),
+							None => bail!("Unknown string operation '{name}' {}", pos.clone()),
+						}
+					}
+					_ => {
+						if op.is_empty() {
+							unexpected_token!(tok, pos);
+						}
+						args.push(parse_arg(tok, pos)?);
+					}
+				},
+				InstrKind::Json(var, json, path) => {
+					if var.is_empty() {
+						match tok {
+							Token::Ident(name) => var.fill(name.clone()),
+							_ => unexpected_token!(tok, pos),
+						}
+					} else if let Value::None = json {
+						*json = parse_arg(tok, pos)?;
+					} else if let Value::None = path {
+						*path = parse_arg(tok, pos)?;
+					} else {
+						unexpected_token!(tok, pos);
+					}
+				}
 				InstrKind::Fail(reason) => match tok {
 					Token::Ident(name) => {
 						if reason.is_none() {
@@ -156,7 +156,7 @@ pub fn lex(text: &str) -> anyhow::Result<Vec<(Token, TextPos)>> {
 						if num_str == "-" {
 							bail!("Invalid number '{num_str}', {pos}");
 						}
-						*num = num_str.parse().expect("Number contains invalid characters");
+						*num = parse_num(&num_str, &tok_start_pos)?;
 						tokens.push((tok, tok_start_pos.clone()));
 						tok_start_pos = pos.clone();
 						tok = Token::None;
@@ -180,7 +180,7 @@ pub fn lex(text: &str) -> anyhow::Result<Vec<(Token, TextPos)>> {
 
 	match &mut tok {
 		Token::Num(num) => {
-			*num = num_str.parse().expect("Number contains invalid characters");
+			*num = parse_num(&num_str, &tok_start_pos)?;
 			tokens.push((tok, tok_start_pos.clone()));
 		}
 		Token::None => {}
@@ -340,6 +340,14 @@ fn lex_string_char(c: char, escape: bool) -> StrLexResult {
 	}
 }
 
+/// Parses the string of a number token, which could be out of range
+fn parse_num(num_str: &str, pos: &TextPos) -> anyhow::Result<i64> {
+	match num_str.parse() {
+		Ok(num) => Ok(num),
+		Err(..) => bail!("Invalid number '{num_str}', {pos}"),
+	}
+}
+
 fn is_whitespace(c: char) -> bool {
 	c.is_whitespace()
 }
@@ -542,6 +550,11 @@ mod tests {
 		};
 	}
 
+	#[test]
+	fn test_num_out_of_range() {
+		assert!(lex("99999999999999999999").is_err());
+	}
+
 	#[test]
 	fn test_token_pos_simple() {
 		assert_token_positions!(
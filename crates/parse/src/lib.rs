@@ -6,6 +6,8 @@
 //!
 //! - `schema`: Enable generation of JSON schemas using the `schemars` crate
 
+/// Implementations of the built-in string, version, and JSON operations
+pub mod builtins;
 /// Parsing for conditions, used in if instructions
 pub mod conditions;
 /// Parsing for most instructions, with the exception of a few complex ones
@@ -1,8 +1,9 @@
 use anyhow::anyhow;
 use anyhow::{Context, bail};
+use nitro_shared::later::Later;
 use nitro_shared::pkg::AddonHashes;
 
-use crate::instruction::ElseBlock;
+use crate::instruction::{ElseBlock, ForList};
 use crate::routine::RESERVED_ROUTINES;
 use crate::routine::can_call_routines;
 
@@ -33,6 +34,11 @@ pub fn parse<'a>(tokens: impl Iterator<Item = &'a TokenAndPos>) -> anyhow::Resul
 	// Whether or not a block just ended
 	let mut block_just_ended = false;
 	for (tok, pos) in tokens {
+		// Block IDs would overflow
+		if prs.parsed.blocks.len() >= BlockId::MAX as usize {
+			bail!("Script contains too many blocks {pos}");
+		}
+
 		let mut instr_to_push = None;
 		let mut mode_to_set = None;
 		let mut block_to_set = None;
@@ -69,6 +75,14 @@ pub fn parse<'a>(tokens: impl Iterator<Item = &'a TokenAndPos>) -> anyhow::Resul
 							prs.mode = ParseMode::CheckForElseIf;
 							block_just_ended = false;
 						}
+						"for" => {
+							prs.mode = ParseMode::For {
+								variable: Later::Empty,
+								has_in: false,
+								list: None,
+							};
+							block_just_ended = false;
+						}
 						"addon" => {
 							prs.mode = ParseMode::Addon {
 								state: addon::State::Id,
@@ -219,6 +233,62 @@ pub fn parse<'a>(tokens: impl Iterator<Item = &'a TokenAndPos>) -> anyhow::Resul
 
 				Ok(())
 			}
+			ParseMode::For {
+				variable,
+				has_in,
+				list,
+			} => {
+				match tok {
+					Token::Ident(name) if variable.is_empty() => variable.fill(name.clone()),
+					Token::Ident(name) if !*has_in => {
+						if name != "in" {
+							unexpected_token!(tok, pos);
+						}
+						*has_in = true;
+					}
+					Token::Ident(name) if name == "json" && list.is_none() => {
+						*list = Some(ForList::Json(Value::None));
+					}
+					Token::Curly(Side::Left) => {
+						let is_finished = match list {
+							Some(ForList::Values(values)) => !values.is_empty(),
+							Some(ForList::Json(value)) => value.is_some(),
+							None => false,
+						};
+						if !is_finished {
+							unexpected_token!(tok, pos);
+						}
+
+						let block = prs.parsed.new_block(Some(prs.block));
+						block_to_set = Some(block);
+						instr_to_push = Some(Instruction::new(
+							InstrKind::For {
+								variable: variable.get_clone(),
+								list: list.take().expect("List should exist"),
+								block,
+							},
+							pos.clone(),
+						));
+					}
+					_ => {
+						if !*has_in {
+							unexpected_token!(tok, pos);
+						}
+						match list {
+							None => *list = Some(ForList::Values(vec![parse_arg(tok, pos)?])),
+							Some(ForList::Values(values)) => values.push(parse_arg(tok, pos)?),
+							Some(ForList::Json(value)) => {
+								if value.is_some() {
+									unexpected_token!(tok, pos);
+								}
+								*value = parse_arg(tok, pos)?;
+							}
+						}
+					}
+				}
+
+				Ok(())
+			}
 			ParseMode::Instruction(instr) => {
 				if instr
 					.parse(tok, pos)
@@ -525,6 +595,11 @@ enum ParseMode {
 		is_if_else: bool,
 	},
 	CheckForElseIf,
+	For {
+		variable: Later<String>,
+		has_in: bool,
+		list: Option<ForList>,
+	},
 	Addon {
 		state: addon::State,
 		key: addon::Key,
@@ -709,6 +784,10 @@ fn check_recursion(parsed: &Parsed) -> anyhow::Result<()> {
 						check_block(parsed, parent_routine, else_block, stack)?;
 					}
 				}
+				InstrKind::For { block, .. } => {
+					let block = parsed.blocks.get(block).expect("For block does not exist");
+					check_block(parsed, parent_routine, block, stack)?;
+				}
 				_ => {}
 			}
 		}
@@ -894,6 +973,44 @@ mod tests {
 		lex_and_parse(text).unwrap();
 	}
 
+	#[test]
+	fn test_for_parse() {
+		let text = r#"@install {
+			for item in "foo" $bar {
+				str item upper $item;
+			}
+			for version in json $versions {}
+		}"#;
+		let parsed = lex_and_parse(text).unwrap();
+		let block = parsed
+			.blocks
+			.get(parsed.routines.get(INSTALL_ROUTINE).unwrap())
+			.unwrap();
+		assert_eq!(block.contents.len(), 2);
+		let InstrKind::For {
+			variable,
+			list: ForList::Values(values),
+			block,
+		} = &block.contents[0].kind
+		else {
+			panic!("Instruction is not a for loop");
+		};
+		assert_eq!(variable, "item");
+		assert_eq!(
+			values,
+			&vec![Value::Literal("foo".into()), Value::Var("bar".into())]
+		);
+		let block = parsed.blocks.get(block).unwrap();
+		assert!(matches!(block.contents[0].kind, InstrKind::Str(..)));
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_incomplete_str_parse() {
+		let text = r#"@install { str out replace "foo" "bar"; }"#;
+		lex_and_parse(text).unwrap();
+	}
+
 	#[test]
 	fn test_addon_parse() {
 		let text = r#"@install { addon "mod" "H.jar" (kind: mod); addon "pack" (kind: mod); }"#;
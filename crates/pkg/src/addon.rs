@@ -23,6 +23,8 @@ pub struct PackageAddon {
 	pub modpack_format: Option<String>,
 	/// Hashes of the addon
 	pub hashes: AddonOptionalHashes,
+	/// Size of the addon file in bytes, if it is known
+	pub size: Option<u64>,
 }
 
 impl PackageAddon {
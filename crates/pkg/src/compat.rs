@@ -0,0 +1,249 @@
+use nitro_shared::loaders::{Loader, LoaderMatch};
+use nitro_shared::versions::VersionPattern;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::declarative::{DeclarativeAddon, DeclarativePackage};
+
+/// Matrix of the Minecraft versions and loaders that each content version of a package supports
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct CompatibilityMatrix {
+	/// Entries for each content version of the package. Ordered the same as the package's
+	/// content version list if it has one, and by first appearance otherwise
+	pub entries: Vec<CompatibilityEntry>,
+}
+
+/// Support information for a single content version of a package
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct CompatibilityEntry {
+	/// The content version, or the version ID for versions without a content version.
+	/// Will be None for versions that have neither
+	pub content_version: Option<String>,
+	/// The Minecraft versions that this content version supports. None if any version is supported
+	pub minecraft_versions: Option<Vec<VersionPattern>>,
+	/// The loaders that this content version supports. None if any loader is supported
+	pub loaders: Option<Vec<LoaderMatch>>,
+}
+
+impl CompatibilityEntry {
+	/// Checks if this entry supports a Minecraft version
+	pub fn supports_minecraft_version(&self, version: &str, version_list: &[String]) -> bool {
+		self.minecraft_versions
+			.as_ref()
+			.is_none_or(|x| x.iter().any(|x| x.matches_single(version, version_list)))
+	}
+
+	/// Checks if this entry supports a loader
+	pub fn supports_loader(&self, loader: &Loader) -> bool {
+		self.loaders
+			.as_ref()
+			.is_none_or(|x| x.iter().any(|x| x.matches(loader)))
+	}
+
+	/// Gets the concrete list of Minecraft versions this entry supports from a list of versions
+	pub fn get_minecraft_versions(&self, version_list: &[String]) -> Vec<String> {
+		let Some(patterns) = &self.minecraft_versions else {
+			return version_list.to_vec();
+		};
+
+		// Keep the order of the version list
+		version_list
+			.iter()
+			.filter(|x| patterns.iter().any(|p| p.matches_single(x, version_list)))
+			.cloned()
+			.collect()
+	}
+
+	/// Merges the support of another addon version into this entry
+	fn merge(
+		&mut self,
+		minecraft_versions: Option<Vec<VersionPattern>>,
+		loaders: Option<Vec<LoaderMatch>>,
+	) {
+		merge_option_list(&mut self.minecraft_versions, minecraft_versions);
+		merge_option_list(&mut self.loaders, loaders);
+	}
+}
+
+/// Merges an optional list where None means that anything is allowed
+fn merge_option_list<T: PartialEq>(list: &mut Option<Vec<T>>, other: Option<Vec<T>>) {
+	let Some(existing) = list else {
+		return;
+	};
+	let Some(other) = other else {
+		*list = None;
+		return;
+	};
+
+	for item in other {
+		if !existing.contains(&item) {
+			existing.push(item);
+		}
+	}
+}
+
+impl CompatibilityMatrix {
+	/// Creates the compatibility matrix for a declarative package
+	pub fn from_declarative(package: &DeclarativePackage) -> Self {
+		let mut out = Self::default();
+		for addon in package.addons.values() {
+			out.add_addon(addon);
+		}
+
+		if let Some(content_versions) = &package.properties.content_versions {
+			out.entries.sort_by_cached_key(|x| {
+				x.content_version
+					.as_ref()
+					.and_then(|x| content_versions.iter().position(|y| y == x))
+					.unwrap_or(usize::MAX)
+			});
+		}
+
+		out
+	}
+
+	/// Creates the compatibility matrix for a single addon of a declarative package
+	pub fn from_addon(addon: &DeclarativeAddon) -> Self {
+		let mut out = Self::default();
+		out.add_addon(addon);
+		out
+	}
+
+	/// Adds the versions of an addon to the matrix
+	fn add_addon(&mut self, addon: &DeclarativeAddon) {
+		let entries = &mut self.entries;
+		for version in &addon.versions {
+			let props = &version.conditional_properties;
+			let content_version = props
+				.content_versions
+				.as_ref()
+				.and_then(|x| x.first())
+				.or(version.version.as_ref())
+				.cloned();
+			let minecraft_versions = props.minecraft_versions.as_ref().map(|x| x.get_vec());
+			let loaders = props.loaders.as_ref().map(|x| x.get_vec());
+
+			if let Some(entry) = entries
+				.iter_mut()
+				.find(|x| x.content_version == content_version)
+			{
+				entry.merge(minecraft_versions, loaders);
+			} else {
+				entries.push(CompatibilityEntry {
+					content_version,
+					minecraft_versions,
+					loaders,
+				});
+			}
+		}
+	}
+
+	/// Gets the entries that support both a Minecraft version and loader
+	pub fn get_supporting_entries<'a>(
+		&'a self,
+		version: &'a str,
+		loader: &'a Loader,
+		version_list: &'a [String],
+	) -> impl Iterator<Item = &'a CompatibilityEntry> + 'a {
+		self.entries.iter().filter(move |x| {
+			x.supports_minecraft_version(version, version_list) && x.supports_loader(loader)
+		})
+	}
+
+	/// Checks if any entry supports a Minecraft version
+	pub fn supports_minecraft_version(&self, version: &str, version_list: &[String]) -> bool {
+		self.entries
+			.iter()
+			.any(|x| x.supports_minecraft_version(version, version_list))
+	}
+
+	/// Gets all of the Minecraft versions that the package supports from a list of versions
+	pub fn get_all_minecraft_versions(&self, version_list: &[String]) -> Vec<String> {
+		version_list
+			.iter()
+			.filter(|x| {
+				self.entries
+					.iter()
+					.any(|e| e.supports_minecraft_version(x, version_list))
+			})
+			.cloned()
+			.collect()
+	}
+
+	/// Gets all of the loaders that the package supports. Returns None if any loader is supported
+	pub fn get_all_loaders(&self) -> Option<Vec<LoaderMatch>> {
+		let mut out = Some(Vec::new());
+		for entry in &self.entries {
+			merge_option_list(&mut out, entry.loaders.clone());
+		}
+
+		out
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::declarative::deserialize_declarative_package;
+
+	#[test]
+	fn test_compatibility_matrix() {
+		let contents = r#"
+			{
+				"properties": {
+					"content_versions": ["1.0", "2.0"]
+				},
+				"addons": {
+					"mod": {
+						"kind": "mod",
+						"versions": [
+							{
+								"url": "example.com",
+								"content_versions": "2.0",
+								"minecraft_versions": "1.21.4",
+								"loaders": "fabric"
+							},
+							{
+								"url": "example.com",
+								"content_versions": "1.0",
+								"minecraft_versions": "1.20.1",
+								"loaders": "fabric"
+							},
+							{
+								"url": "example.com",
+								"content_versions": "2.0",
+								"minecraft_versions": "1.21.4",
+								"loaders": "neoforged"
+							}
+						]
+					}
+				}
+			}
+		"#;
+		let package = deserialize_declarative_package(contents).unwrap();
+		let matrix = CompatibilityMatrix::from_declarative(&package);
+
+		assert_eq!(matrix.entries.len(), 2);
+		assert_eq!(matrix.entries[0].content_version.as_deref(), Some("1.0"));
+		assert_eq!(
+			matrix.entries[1].loaders,
+			Some(vec![
+				LoaderMatch::Loader(Loader::Fabric),
+				LoaderMatch::Loader(Loader::NeoForged)
+			])
+		);
+
+		let version_list = vec!["1.20.1".to_string(), "1.21.4".to_string()];
+		assert!(matrix.supports_minecraft_version("1.21.4", &version_list));
+		assert_eq!(
+			matrix
+				.get_supporting_entries("1.20.1", &Loader::NeoForged, &version_list)
+				.count(),
+			0
+		);
+	}
+}
@@ -214,6 +214,9 @@ pub struct DeclarativeAddonVersion {
 	/// Hashes for this version file
 	#[serde(skip_serializing_if = "AddonOptionalHashes::is_empty")]
 	pub hashes: AddonOptionalHashes,
+	/// Size of the version file in bytes
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub size: Option<u64>,
 }
 
 impl DeclarativeAddonVersion {
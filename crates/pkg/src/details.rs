@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+
+use crate::metadata::PackageMetadata;
+
+/// Full details about a package for showing on its project page, which are more than
+/// the metadata that is used for listing and installing it
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct PackageDetails {
+	/// The body of the project page, in Markdown
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub body: Option<String>,
+	/// All of the images in the project's gallery
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub gallery: Vec<GalleryImage>,
+	/// Links to external sites for the project
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub links: Vec<ExternalLink>,
+	/// The authors of the project
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub authors: Vec<PackageAuthor>,
+}
+
+impl PackageDetails {
+	/// Creates details from the metadata of a package, for repositories that can't give any more
+	pub fn from_metadata(meta: &PackageMetadata) -> Self {
+		let gallery = meta
+			.gallery
+			.iter()
+			.flatten()
+			.map(|url| GalleryImage {
+				url: url.clone(),
+				..Default::default()
+			})
+			.collect();
+
+		let links = [
+			(LinkKind::Website, &meta.website),
+			(LinkKind::Documentation, &meta.documentation),
+			(LinkKind::Source, &meta.source),
+			(LinkKind::Issues, &meta.issues),
+			(LinkKind::Community, &meta.community),
+			(LinkKind::Donation, &meta.support_link),
+		]
+		.into_iter()
+		.filter_map(|(kind, url)| {
+			Some(ExternalLink {
+				kind,
+				name: None,
+				url: url.clone()?,
+			})
+		})
+		.collect();
+
+		let authors = meta
+			.authors
+			.iter()
+			.flatten()
+			.map(|name| PackageAuthor {
+				name: name.clone(),
+				..Default::default()
+			})
+			.collect();
+
+		Self {
+			body: meta.long_description.clone(),
+			gallery,
+			links,
+			authors,
+		}
+	}
+}
+
+/// An image in a project's gallery
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct GalleryImage {
+	/// The URL to the image
+	pub url: String,
+	/// The URL to a full quality version of the image, if it is different
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub raw_url: Option<String>,
+	/// The title of the image
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub title: Option<String>,
+	/// A description of the image
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+	/// Whether the image is featured on the project page
+	pub featured: bool,
+}
+
+/// A link from a project to an external site
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExternalLink {
+	/// What the link is for
+	pub kind: LinkKind,
+	/// A name for the link, such as the platform for donation links
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+	/// The URL of the link
+	pub url: String,
+}
+
+/// Kinds of external links
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+	/// The project's website
+	Website,
+	/// The project's documentation or wiki
+	Documentation,
+	/// The project's source code
+	Source,
+	/// The project's issue tracker
+	Issues,
+	/// The project's online community, such as a Discord server
+	Community,
+	/// A place to donate to the project
+	Donation,
+	/// Any other link
+	Other,
+}
+
+/// An author of a project, as shown on the project page
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct PackageAuthor {
+	/// The ID of the author in the repository, which can be used to get their full profile.
+	/// Not present if the repository does not have author profiles
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub id: Option<String>,
+	/// The display name of the author
+	pub name: String,
+	/// The role of the author in the project
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub role: Option<String>,
+	/// A link to the author's avatar
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub avatar: Option<String>,
+}
+
+/// The full profile of an author in a repository
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AuthorProfile {
+	/// The ID of the author in the repository
+	pub id: String,
+	/// The display name of the author
+	pub name: String,
+	/// The author's biography
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub bio: Option<String>,
+	/// A link to the author's avatar
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub avatar: Option<String>,
+	/// A link to the author's page on the repository's website
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub url: Option<String>,
+	/// The packages in the repository that the author is a member of
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub packages: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_details_from_metadata() {
+		let meta = PackageMetadata {
+			long_description: Some("# Sodium".into()),
+			authors: Some(vec!["jellysquid3".into()]),
+			source: Some("https://github.com/CaffeineMC/sodium".into()),
+			support_link: Some("https://ko-fi.com/jellysquid_".into()),
+			gallery: Some(vec!["https://example.com/image.png".into()]),
+			..Default::default()
+		};
+
+		let details = PackageDetails::from_metadata(&meta);
+		assert_eq!(details.body.as_deref(), Some("# Sodium"));
+		assert_eq!(details.gallery.len(), 1);
+		assert_eq!(details.authors[0].name, "jellysquid3");
+		assert_eq!(details.authors[0].id, None);
+		let kinds: Vec<_> = details.links.iter().map(|x| x.kind).collect();
+		assert_eq!(kinds, vec![LinkKind::Source, LinkKind::Donation]);
+	}
+}
@@ -8,8 +8,12 @@
 
 /// Addons from packages
 pub mod addon;
+/// Compatibility information for packages
+pub mod compat;
 /// Standard declarative package format
 pub mod declarative;
+/// Full details for showing packages on their project pages
+pub mod details;
 /// Package metadata
 pub mod metadata;
 /// Package properties
@@ -209,4 +213,148 @@ pub struct PackageSearchResults {
 	pub total_results: usize,
 	/// Limited versions of package metadata to be used for previews
 	pub previews: HashMap<String, (PackageMetadata, PackageProperties)>,
+	/// Whether these results are out of date cached ones that should be refreshed
+	#[serde(default)]
+	pub stale: bool,
+	/// Cursor to pass in the search parameters to get the next page of results.
+	/// Will be none if there are no more results
+	#[serde(default)]
+	pub next_cursor: Option<String>,
+}
+
+/// Results for a package search across all repositories
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct AggregatedSearchResults {
+	/// The merged results returned by the search
+	pub results: Vec<AggregatedSearchResult>,
+	/// The total number of results from all of the repositories, that weren't limited out.
+	/// This may count the same package multiple times if it is in more than one repository
+	pub total_results: usize,
+	/// Repositories that failed to be searched, along with their errors
+	pub errors: HashMap<String, String>,
+	/// Whether any of the results are out of date cached ones that should be refreshed
+	#[serde(default)]
+	pub stale: bool,
+	/// Cursor to pass in the search parameters to get the next page of results.
+	/// Will be none if there are no more results
+	#[serde(default)]
+	pub next_cursor: Option<String>,
+}
+
+/// A single package from a search across repositories, which may have been found in multiple of them
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AggregatedSearchResult {
+	/// The package from the highest priority repository it was found in
+	pub package: String,
+	/// All of the repositories the package was found in, in order of priority
+	pub sources: Vec<SearchResultSource>,
+	/// The preview for the package, if one was provided by any of the repositories
+	pub preview: Option<(PackageMetadata, PackageProperties)>,
+}
+
+/// A repository that a search result came from
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SearchResultSource {
+	/// The ID of the repository
+	pub repository: String,
+	/// The package in that repository
+	pub package: String,
+}
+
+/// The position of a search across one or more repositories, which is given
+/// to users as an opaque cursor so that they can get the next page of results
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct SearchCursor {
+	/// The position in the results of each repository
+	pub repos: HashMap<String, RepoSearchPosition>,
+}
+
+/// The position of a search in a single repository
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct RepoSearchPosition {
+	/// The number of results that have been returned from this repository so far
+	pub skip: usize,
+	/// The cursor returned by the repository for its next page, if it uses them
+	pub cursor: Option<String>,
+	/// The total number of results in this repository
+	pub total: usize,
+	/// Whether all of the results from this repository have been returned
+	pub finished: bool,
+}
+
+impl SearchCursor {
+	/// Parses a cursor that was given to a user
+	pub fn parse(cursor: &str) -> anyhow::Result<Self> {
+		serde_json::from_str(cursor).context("Search cursor is invalid")
+	}
+
+	/// Gets the position for a repository, starting at the given offset if it has not been searched yet
+	pub fn get_position(&mut self, repo: &str, start: usize) -> &mut RepoSearchPosition {
+		self.repos
+			.entry(repo.to_string())
+			.or_insert_with(|| RepoSearchPosition {
+				skip: start,
+				..Default::default()
+			})
+	}
+
+	/// Gets the total number of results across all repositories
+	pub fn get_total(&self) -> usize {
+		self.repos.values().map(|x| x.total).sum()
+	}
+
+	/// Gets the cursor to give to the user for the next page, or none if every repository is finished
+	pub fn get_next(&self) -> Option<String> {
+		if self.repos.values().all(|x| x.finished) {
+			None
+		} else {
+			serde_json::to_string(self).ok()
+		}
+	}
+}
+
+impl RepoSearchPosition {
+	/// Advances past a page of results from this repository. The repository is finished
+	/// once it returns less results than were requested or there are no more left
+	pub fn advance(
+		&mut self,
+		requested: usize,
+		count: usize,
+		total: usize,
+		next_cursor: Option<String>,
+	) {
+		self.skip += count;
+		self.total = total;
+		self.finished = next_cursor.is_none() && (self.skip >= total || count < requested);
+		self.cursor = next_cursor;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_search_cursor() {
+		let mut cursor = SearchCursor::default();
+		cursor.get_position("modrinth", 0).advance(20, 20, 45, None);
+		cursor
+			.get_position("custom", 0)
+			.advance(20, 20, 100, Some("abc".into()));
+		cursor.get_position("core", 0).advance(20, 5, 5, None);
+		assert_eq!(cursor.get_total(), 150);
+
+		let mut cursor = SearchCursor::parse(&cursor.get_next().unwrap()).unwrap();
+		let position = cursor.get_position("custom", 0);
+		assert_eq!(position.skip, 20);
+		assert_eq!(position.cursor.as_deref(), Some("abc"));
+		assert!(cursor.get_position("core", 0).finished);
+
+		cursor.get_position("modrinth", 0).advance(20, 20, 45, None);
+		cursor.get_position("modrinth", 0).advance(20, 5, 45, None);
+		cursor.get_position("custom", 0).advance(20, 20, 100, None);
+		assert!(cursor.get_next().is_some());
+		cursor.get_position("custom", 0).advance(20, 0, 100, None);
+		assert!(cursor.get_next().is_none());
+	}
 }
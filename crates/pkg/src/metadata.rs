@@ -66,6 +66,12 @@ pub struct PackageMetadata {
 	/// How many downloads the package has
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub downloads: Option<u32>,
+	/// How many followers the package has
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub followers: Option<u32>,
+	/// When the package was last updated, as a UTC timestamp in seconds
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub last_updated: Option<u64>,
 }
 
 impl PackageMetadata {
@@ -95,6 +101,8 @@ impl PackageMetadata {
 			&& self.keywords.is_none()
 			&& self.categories.is_none()
 			&& self.downloads.is_none()
+			&& self.followers.is_none()
+			&& self.last_updated.is_none()
 	}
 }
 
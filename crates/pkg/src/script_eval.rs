@@ -1,10 +1,13 @@
 use nitro_parse::FailReason;
+use nitro_parse::builtins::{get_json_list, get_json_value};
 use nitro_parse::conditions::ConditionKind;
-use nitro_parse::instruction::{InstrKind, Instruction};
+use nitro_parse::instruction::{ForList, InstrKind, Instruction};
 use nitro_parse::parse::{Block, Parsed};
 use nitro_parse::routine::INSTALL_ROUTINE;
 use nitro_parse::vars::{Value, VariableStore};
 
+use std::time::{Duration, Instant};
+
 use anyhow::{Context, anyhow, bail};
 use nitro_shared::minecraft::AddonKind;
 use nitro_shared::pkg::{AddonOptionalHashes, PackageID};
@@ -105,6 +108,79 @@ pub trait ScriptEvaluator {
 pub struct ScriptEvalConfig {
 	/// The reason for evaluation
 	pub reason: EvalReason,
+	/// Limits on the resources that the script can use
+	pub limits: ScriptEvalLimits,
+}
+
+/// Limits on the resources that a script can use while it is evaluated, so that
+/// scripts from untrusted repositories can't hang the launcher
+#[derive(Debug, Clone)]
+pub struct ScriptEvalLimits {
+	/// The maximum number of instructions that can be run
+	pub max_instructions: u32,
+	/// The maximum depth of nested blocks and routine calls
+	pub max_depth: u16,
+	/// The maximum amount of time that evaluation can take
+	pub max_time: Duration,
+	/// The maximum length of a variable's value in bytes
+	pub max_variable_length: usize,
+}
+
+impl Default for ScriptEvalLimits {
+	fn default() -> Self {
+		Self {
+			max_instructions: 100_000,
+			max_depth: 64,
+			max_time: Duration::from_secs(5),
+			max_variable_length: 1024 * 1024,
+		}
+	}
+}
+
+/// Mutable state that is tracked while evaluating a script
+pub struct ScriptEvalState {
+	/// The number of instructions that have been run
+	instruction_count: u32,
+	/// The current depth of nested blocks
+	depth: u16,
+	/// When evaluation started
+	start_time: Instant,
+}
+
+impl ScriptEvalState {
+	/// Creates a new ScriptEvalState, starting the evaluation timer
+	pub fn new() -> Self {
+		Self {
+			instruction_count: 0,
+			depth: 0,
+			start_time: Instant::now(),
+		}
+	}
+
+	/// Counts an instruction that is about to be run and checks the instruction and time limits
+	fn count_instruction(&mut self, limits: &ScriptEvalLimits) -> anyhow::Result<()> {
+		self.instruction_count += 1;
+		if self.instruction_count > limits.max_instructions {
+			bail!(
+				"Script exceeded the maximum number of instructions ({})",
+				limits.max_instructions
+			);
+		}
+		if self.start_time.elapsed() > limits.max_time {
+			bail!(
+				"Script exceeded the maximum evaluation time ({}s)",
+				limits.max_time.as_secs_f32()
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Default for ScriptEvalState {
+	fn default() -> Self {
+		Self::new()
+	}
 }
 
 /// For what reason we are evaluating the script, which determines
@@ -133,12 +209,9 @@ pub async fn eval_script_package<E: ScriptEvaluator>(
 		.get(routine_id)
 		.ok_or(anyhow!("Routine {} does not exist", INSTALL_ROUTINE))?;
 
-	for instr in &block.contents {
-		let result = eval_instr(instr, parsed, e, shared, config).await?;
-		if result.finish {
-			break;
-		}
-	}
+	let mut state = ScriptEvalState::new();
+	eval_block(block, parsed, e, shared, config, &mut state).await?;
+
 	Ok(())
 }
 
@@ -149,17 +222,28 @@ async fn eval_block<E: ScriptEvaluator>(
 	e: &mut E,
 	shared: &mut E::Shared<'_>,
 	config: &ScriptEvalConfig,
+	state: &mut ScriptEvalState,
 ) -> anyhow::Result<EvalResult> {
 	let mut out = EvalResult::new();
 
+	if state.depth >= config.limits.max_depth {
+		bail!(
+			"Script exceeded the maximum block depth ({})",
+			config.limits.max_depth
+		);
+	}
+	state.depth += 1;
+
 	for instr in &block.contents {
-		let result = eval_instr(instr, parsed, e, shared, config).await?;
+		let result = eval_instr(instr, parsed, e, shared, config, state).await?;
 		if result.finish {
 			out.finish = true;
 			break;
 		}
 	}
 
+	state.depth -= 1;
+
 	Ok(out)
 }
 
@@ -170,9 +254,12 @@ pub async fn eval_instr<E: ScriptEvaluator>(
 	e: &mut E,
 	shared: &mut E::Shared<'_>,
 	config: &ScriptEvalConfig,
+	state: &mut ScriptEvalState,
 ) -> anyhow::Result<EvalResult> {
 	let mut out = EvalResult::new();
 
+	state.count_instruction(&config.limits)?;
+
 	// Used to put a nice anyhow context on all of them
 	let result = {
 		match config.reason {
@@ -184,7 +271,7 @@ pub async fn eval_instr<E: ScriptEvaluator>(
 				} => {
 					if e.eval_condition(shared, &condition.kind)? {
 						let block = parsed.blocks.get(if_block).expect("If block missing");
-						out = Box::pin(eval_block(block, parsed, e, shared, config)).await?;
+						out = Box::pin(eval_block(block, parsed, e, shared, config, state)).await?;
 					} else {
 						// Eval the else block chain
 						for else_block in else_blocks {
@@ -197,7 +284,8 @@ pub async fn eval_instr<E: ScriptEvaluator>(
 								.blocks
 								.get(&else_block.block)
 								.expect("If else block missing");
-							out = Box::pin(eval_block(block, parsed, e, shared, config)).await?;
+							out = Box::pin(eval_block(block, parsed, e, shared, config, state))
+								.await?;
 						}
 					}
 				}
@@ -207,14 +295,48 @@ pub async fn eval_instr<E: ScriptEvaluator>(
 						"Call instruction routine '{routine}' does not exist"
 					))?;
 					let block = parsed.blocks.get(routine).expect("Block does not exist");
-					out = Box::pin(eval_block(block, parsed, e, shared, config)).await?;
+					out = Box::pin(eval_block(block, parsed, e, shared, config, state)).await?;
 				}
 				InstrKind::Set(var, val) => {
-					let var = var.get();
 					let val = val.get(e.get_variable_store(shared))?;
-					e.get_variable_store(shared)
-						.try_set_var(var.to_owned(), val)
-						.with_context(|| "Failed to set variable".to_string())?;
+					set_var(e.get_variable_store(shared), var.get_clone(), val, config)
+						.context("Failed to set variable")?;
+				}
+				InstrKind::Str(var, op, args) => {
+					let args = get_value_vec(args, e.get_variable_store(shared))?;
+					let val = op.get().run(&args)?;
+					set_var(e.get_variable_store(shared), var.get_clone(), val, config)
+						.context("Failed to set variable")?;
+				}
+				InstrKind::Json(var, json, path) => {
+					let json = json.get(e.get_variable_store(shared))?;
+					let path = path.get(e.get_variable_store(shared))?;
+					let val = get_json_value(&json, &path)?;
+					set_var(e.get_variable_store(shared), var.get_clone(), val, config)
+						.context("Failed to set variable")?;
+				}
+				InstrKind::For {
+					variable,
+					list,
+					block,
+				} => {
+					let items = match list {
+						ForList::Values(values) => {
+							get_value_vec(values, e.get_variable_store(shared))?
+						}
+						ForList::Json(json) => {
+							get_json_list(&json.get(e.get_variable_store(shared))?)?
+						}
+					};
+					let block = parsed.blocks.get(block).expect("For block missing");
+					for item in items {
+						set_var(e.get_variable_store(shared), variable.clone(), item, config)
+							.context("Failed to set loop variable")?;
+						out = Box::pin(eval_block(block, parsed, e, shared, config, state)).await?;
+						if out.finish {
+							break;
+						}
+					}
 				}
 				InstrKind::Finish() => out.finish = true,
 				InstrKind::Fail(reason) => {
@@ -292,7 +414,7 @@ pub async fn eval_instr<E: ScriptEvaluator>(
 				} => {
 					if let EvalReason::Install = config.reason {
 						let id = id.get(e.get_variable_store(shared))?;
-						let kind = kind.as_ref().expect("Addon kind missing");
+						let kind = kind.as_ref().context("Addon kind is missing")?;
 						let hashes = AddonOptionalHashes {
 							sha256: hashes.sha256.get_as_option(e.get_variable_store(shared))?,
 							sha512: hashes.sha512.get_as_option(e.get_variable_store(shared))?,
@@ -307,6 +429,7 @@ pub async fn eval_instr<E: ScriptEvaluator>(
 							modpack_format: modpack_format
 								.get_as_option(e.get_variable_store(shared))?,
 							hashes,
+							size: None,
 						};
 						e.add_addon(shared, data)?;
 					}
@@ -345,6 +468,23 @@ impl Default for EvalResult {
 	}
 }
 
+/// Sets a variable while checking the length limit
+fn set_var(
+	vars: &mut impl VariableStore,
+	var: String,
+	val: String,
+	config: &ScriptEvalConfig,
+) -> anyhow::Result<()> {
+	if val.len() > config.limits.max_variable_length {
+		bail!(
+			"Variable value is too long (>{} bytes)",
+			config.limits.max_variable_length
+		);
+	}
+
+	vars.try_set_var(var, val)
+}
+
 /// Utility function to convert a vec of values to a vec of strings
 fn get_value_vec(vec: &[Value], vars: &impl VariableStore) -> anyhow::Result<Vec<String>> {
 	let out = vec.iter().map(|x| x.get(vars));
@@ -371,4 +511,7 @@ pub struct AddonInstructionData {
 	pub modpack_format: Option<String>,
 	/// The addon's hashes
 	pub hashes: AddonOptionalHashes,
+	/// The size of the addon file in bytes, if it is known
+	#[serde(default)]
+	pub size: Option<u64>,
 }
@@ -76,6 +76,12 @@ pub async fn generate(
 		name: Some(project.title),
 		description: Some(project.description),
 		downloads: Some(project.downloads),
+		followers: Some(project.followers),
+		last_updated: project
+			.updated
+			.as_deref()
+			.and_then(|x| chrono::DateTime::parse_from_rfc3339(x).ok())
+			.and_then(|x| u64::try_from(x.timestamp()).ok()),
 		..Default::default()
 	};
 	if let Some(body) = project.body {
@@ -399,6 +405,7 @@ pub async fn generate(
 			};
 			pkg_version.url = Some(download.url.clone());
 			pkg_version.filename = Some(download.filename.clone());
+			pkg_version.size = download.size;
 			pkg_version.hashes = AddonHashes {
 				sha256: None,
 				sha512: Some(download.hashes.sha512.clone()),
@@ -449,6 +456,8 @@ pub fn get_preview(result: SearchedProject) -> Project {
 							url: x.clone(),
 							raw_url: x,
 							featured: true,
+							title: None,
+							description: None,
 						})
 					} else {
 						GalleryEntry::Simple(x)
@@ -457,6 +466,8 @@ pub fn get_preview(result: SearchedProject) -> Project {
 				.collect()
 		}),
 		downloads: result.downloads,
+		followers: result.follows,
+		updated: result.date_modified,
 		..Default::default()
 	}
 }
@@ -55,6 +55,8 @@ pub async fn generate(
 		pack.display.icon.clone()
 	};
 
+	let last_updated = pack.stats.updated.or(pack.stats.added).map(|x| x / 1000);
+
 	let meta = PackageMetadata {
 		name: Some(pack.display.name),
 		slug: meta.map(|x| x.raw_id),
@@ -76,6 +78,8 @@ pub async fn generate(
 				.flat_map(|x| convert_category(&x).into_iter())
 				.collect(),
 		),
+		downloads: pack.stats.downloads.total,
+		last_updated,
 		..Default::default()
 	};
 
@@ -21,6 +21,17 @@ impl ExecutablePluginOutput {
 			stdout: std::io::stdout(),
 		}
 	}
+
+	/// Sends an event to any of this plugin's pages in the GUI that are listening for it
+	pub fn emit_event(&mut self, event: &str, data: serde_json::Value) {
+		let action = OutputAction::EmitEvent {
+			event: event.to_string(),
+			data,
+		};
+		if let Ok(text) = action.serialize(self.use_base64, self.protocol_version) {
+			let _ = writeln!(&mut self.stdout, "{text}");
+		}
+	}
 }
 
 impl Default for ExecutablePluginOutput {
@@ -119,6 +119,16 @@ hook_interface!(
 	"sync_custom_package_repository",
 	SyncCustomPackageRepository
 );
+hook_interface!(
+	get_custom_package_details,
+	"get_custom_package_details",
+	GetCustomPackageDetails
+);
+hook_interface!(
+	get_custom_package_author,
+	"get_custom_package_author",
+	GetCustomPackageAuthor
+);
 hook_interface!(add_themes, "add_themes", AddThemes);
 hook_interface!(custom_action, "custom_action", CustomAction);
 hook_interface!(
@@ -197,3 +207,5 @@ hook_interface!(
 	AddModpackFormats
 );
 hook_interface!(install_modpack, "install_modpack", InstallModpack);
+hook_interface!(add_paste_backends, "add_paste_backends", AddPasteBackends);
+hook_interface!(upload_paste, "upload_paste", UploadPaste);
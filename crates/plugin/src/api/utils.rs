@@ -8,9 +8,13 @@ use anyhow::Context;
 use nitro_shared::{pkg::PackageSearchParameters, util::utc_timestamp};
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
+/// How long cached search results can be served while they are refreshed, in seconds
+const DEFAULT_STALE_AGE: u64 = 60 * 60 * 24;
+
 /// A cache for search results in a custom plugin repository that holds entries for a certain amount of time
 pub struct PackageSearchCache {
 	max_age: u64,
+	stale_age: u64,
 	contents: CacheContents,
 	path: PathBuf,
 }
@@ -28,11 +32,18 @@ impl PackageSearchCache {
 
 		Ok(Self {
 			max_age,
+			stale_age: DEFAULT_STALE_AGE,
 			contents,
 			path: path.as_ref().to_path_buf(),
 		})
 	}
 
+	/// Sets how old in seconds an entry can be while still being served as stale results
+	pub fn with_stale_age(mut self, stale_age: u64) -> Self {
+		self.stale_age = stale_age;
+		self
+	}
+
 	/// Checks the cache for cached results
 	pub fn check<D: DeserializeOwned>(&self, search: &PackageSearchParameters) -> Option<D> {
 		// Don't cache searches with queries as users will want up to date results
@@ -40,8 +51,7 @@ impl PackageSearchCache {
 			return None;
 		}
 
-		let search = serde_json::to_string(search).ok()?;
-		let entry = self.contents.entries.get(&search)?;
+		let entry = self.get_entry(search)?;
 
 		// Invalidate the entry if it is too old
 		let timestamp = utc_timestamp().ok()?;
@@ -53,13 +63,49 @@ impl PackageSearchCache {
 		}
 	}
 
+	/// Checks the cache for results regardless of how old they are, for when new results can't be fetched
+	pub fn check_stale<D: DeserializeOwned>(&self, search: &PackageSearchParameters) -> Option<D> {
+		let entry = self.get_entry(search)?;
+
+		serde_json::from_value(entry.results.clone()).ok()
+	}
+
+	/// Checks the cache for results, also giving results that are out of date if the search allows stale results.
+	/// Returns the results and whether they are stale. Stale results should be shown right away while
+	/// the search is repeated to refresh them
+	pub fn check_allow_stale<D: DeserializeOwned>(
+		&self,
+		search: &PackageSearchParameters,
+	) -> Option<(D, bool)> {
+		if let Some(results) = self.check(search) {
+			return Some((results, false));
+		}
+		if !search.allow_stale {
+			return None;
+		}
+
+		let entry = self.get_entry(search)?;
+		let timestamp = utc_timestamp().ok()?;
+		if timestamp - entry.timestamp > self.stale_age {
+			return None;
+		}
+
+		let results = serde_json::from_value(entry.results.clone()).ok()?;
+		Some((results, true))
+	}
+
+	/// Gets the entry for a search
+	fn get_entry(&self, search: &PackageSearchParameters) -> Option<&CacheEntry> {
+		self.contents.entries.get(&get_key(search).ok()?)
+	}
+
 	/// Writes to the cache
 	pub fn write<S: Serialize>(
 		&mut self,
 		search: &PackageSearchParameters,
 		results: S,
 	) -> anyhow::Result<()> {
-		let search = serde_json::to_string(search).context("Failed to stringify search")?;
+		let search = get_key(search)?;
 		let results = serde_json::to_value(results).context("Failed to serialize results")?;
 		let timestamp = utc_timestamp().context("Failed to get UTC timestamp")?;
 
@@ -72,6 +118,13 @@ impl PackageSearchCache {
 	}
 }
 
+/// Gets the key for a search in the cache, which doesn't depend on whether stale results are allowed
+fn get_key(search: &PackageSearchParameters) -> anyhow::Result<String> {
+	let mut search = search.clone();
+	search.allow_stale = false;
+	serde_json::to_string(&search).context("Failed to stringify search")
+}
+
 #[derive(Serialize, Deserialize, Default)]
 struct CacheContents {
 	entries: HashMap<String, CacheEntry>,
@@ -82,3 +135,30 @@ struct CacheEntry {
 	timestamp: u64,
 	results: serde_json::Value,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_search_cache_stale_results() {
+		let path = std::env::temp_dir().join("nitro_test_search_cache.json");
+		let mut cache = PackageSearchCache::open(&path, 100).unwrap();
+		let mut search = PackageSearchParameters::default();
+		cache.write(&search, vec!["sodium"]).unwrap();
+
+		// Age the entry past the max age
+		for entry in cache.contents.entries.values_mut() {
+			entry.timestamp -= 200;
+		}
+		assert!(cache.check::<Vec<String>>(&search).is_none());
+		assert!(cache.check_allow_stale::<Vec<String>>(&search).is_none());
+
+		search.allow_stale = true;
+		let (results, stale) = cache.check_allow_stale::<Vec<String>>(&search).unwrap();
+		assert_eq!(results, vec!["sodium".to_string()]);
+		assert!(stale);
+
+		let _ = std::fs::remove_file(path);
+	}
+}
@@ -3,7 +3,7 @@ use std::{
 	path::{Path, PathBuf},
 };
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 
 /// Gets the Nitrolaunch data directory
 pub fn get_data_dir() -> PathBuf {
@@ -63,6 +63,35 @@ pub fn update_link(src: impl AsRef<Path>, tgt: impl AsRef<Path>) -> anyhow::Resu
 	.map_err(|e| anyhow!("{e:?}"))
 }
 
+/// Copies a file, using a reflink if the filesystem supports them
+pub fn copy_file(src: impl AsRef<Path>, tgt: impl AsRef<Path>) -> anyhow::Result<()> {
+	super::interface::copy_file(
+		&src.as_ref().to_string_lossy(),
+		&tgt.as_ref().to_string_lossy(),
+	)
+	.map_err(|e| anyhow!("{e:?}"))
+}
+
+/// Deletes a file or directory. Unless the deletion is permanent, it is moved to the trash or recycle bin
+/// of the system instead, so that it can be restored if it was deleted by mistake
+pub fn delete_path(path: impl AsRef<Path>, permanent: bool) -> anyhow::Result<()> {
+	let path = path.as_ref();
+	if !path.exists() && !path.is_symlink() {
+		return Ok(());
+	}
+
+	if !permanent {
+		return super::interface::move_to_trash(&path.to_string_lossy())
+			.map_err(|e| anyhow!("Failed to move to the trash: {e}"));
+	}
+
+	if path.is_dir() && !path.is_symlink() {
+		std::fs::remove_dir_all(path).context("Failed to remove directory")
+	} else {
+		std::fs::remove_file(path).context("Failed to remove file")
+	}
+}
+
 /// Runs a command
 pub fn run_command(
 	cmd: impl AsRef<OsStr>,
@@ -1,7 +1,9 @@
 use std::{collections::VecDeque, env::consts::EXE_SUFFIX, sync::Arc, time::Instant};
 
 use anyhow::{Context, anyhow, bail};
+use nitro_net::{activity, download};
 use nitro_shared::{
+	error::PluginError,
 	no_window,
 	output::{MessageContents, NitroOutput},
 };
@@ -57,6 +59,13 @@ pub(crate) async fn call_executable<H: Hook + Sized>(
 		cmd.env(NITRO_VERSION_ENV, nitro_version);
 	}
 	cmd.env(NITRO_PLUGIN_ENV, "1");
+	if download::is_offline() {
+		cmd.env(download::OFFLINE_ENV, "1");
+	}
+	if let Some(proxy) = download::get_proxy() {
+		cmd.envs(download::get_proxy_env_vars(&proxy));
+	}
+	cmd.envs(activity::get_bandwidth_limit_env_vars());
 	if let Some(working_dir) = arg.working_dir {
 		cmd.current_dir(working_dir);
 	}
@@ -288,6 +297,9 @@ impl<H: Hook> ExecutableHookHandle<H> {
 						OutputAction::EndSection => {
 							o.end_section();
 						}
+						OutputAction::EmitEvent { event, data } => {
+							o.emit_plugin_event(&self.plugin_id, &event, data);
+						}
 					}
 				}
 
@@ -321,18 +333,10 @@ impl<H: Hook> ExecutableHookHandle<H> {
 		let cmd_result = child.wait().await?;
 
 		if !cmd_result.success() {
-			if let Some(exit_code) = cmd_result.code() {
-				bail!(
-					"Hook from plugin '{}' returned a non-zero exit code of {}",
-					self.plugin_id,
-					exit_code
-				);
-			} else {
-				bail!(
-					"Hook from plugin '{}' returned a non-zero exit code",
-					self.plugin_id
-				);
-			}
+			bail!(PluginError::NonZeroExit {
+				plugin: self.plugin_id.clone(),
+				code: cmd_result.code(),
+			});
 		}
 
 		let result = result.with_context(|| {
@@ -4,13 +4,14 @@ use nitro_config::ConfigKind;
 use nitro_config::instance::{InstanceConfig, WrapperCommand};
 use nitro_config::template::TemplateConfig;
 use nitro_instance::addon::Addon;
+use nitro_pkg::details::{AuthorProfile, PackageDetails};
 use nitro_pkg::repo::{PackageFlag, RepoMetadata};
 use nitro_pkg::script_eval::AddonInstructionData;
 use nitro_pkg::{PackageContentType, PackageSearchResults, RecommendedPackage, RequiredPackage};
 use nitro_shared::UpdateDepth;
 use nitro_shared::id::{InstanceID, TemplateID};
 use nitro_shared::lang::translate::LanguageMap;
-use nitro_shared::loaders::Loader;
+use nitro_shared::loaders::{Loader, LoaderVersionChannel};
 use nitro_shared::minecraft::VersionEntry;
 use nitro_shared::minecraft::{AddonKind, SkinVariant};
 use nitro_shared::minecraft::{Cape, MinecraftUserProfile, Skin};
@@ -415,6 +416,18 @@ pub struct InstanceTransferFormat {
 	pub migrate: Option<InstanceTransferFormatDirection>,
 	/// Whether a side must be specified when importing this format
 	pub needs_import_side: bool,
+	/// Info used to automatically detect this format from a file
+	pub detect: Option<InstanceTransferFormatDetection>,
+}
+
+/// Information used to detect which transfer format an imported file is in
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct InstanceTransferFormatDetection {
+	/// File extensions (without the dot) that are unique to this format
+	pub extensions: Vec<String>,
+	/// Files at the root of the archive or directory that indicate this format
+	pub files: Vec<String>,
 }
 
 /// Information about a side of an instance transfer format
@@ -760,6 +773,42 @@ pub struct SyncCustomPackageRepositoryArg {
 	pub repository: String,
 }
 
+def_hook!(
+	GetCustomPackageDetails,
+	"get_custom_package_details",
+	"Hook for getting the full project details of a package from a custom repository",
+	GetCustomPackageDetailsArg,
+	Option<PackageDetails>,
+	1,
+);
+
+/// Argument for the GetCustomPackageDetails hook
+#[derive(Serialize, Deserialize, Default)]
+pub struct GetCustomPackageDetailsArg {
+	/// The repository that the package is from
+	pub repository: String,
+	/// The package to get details for
+	pub package: String,
+}
+
+def_hook!(
+	GetCustomPackageAuthor,
+	"get_custom_package_author",
+	"Hook for getting the profile of an author from a custom repository",
+	GetCustomPackageAuthorArg,
+	Option<AuthorProfile>,
+	1,
+);
+
+/// Argument for the GetCustomPackageAuthor hook
+#[derive(Serialize, Deserialize, Default)]
+pub struct GetCustomPackageAuthorArg {
+	/// The repository that the author is from
+	pub repository: String,
+	/// The ID of the author
+	pub author: String,
+}
+
 def_hook!(
 	AddThemes,
 	"add_themes",
@@ -771,7 +820,7 @@ def_hook!(
 );
 
 /// Data for a GUI theme
-#[derive(Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize, Default, Clone)]
 #[serde(default)]
 pub struct Theme {
 	/// A unique ID for the theme
@@ -786,6 +835,12 @@ pub struct Theme {
 	pub css: String,
 	/// A css color that identifies this theme
 	pub color: String,
+	/// Values for theme variables like `--bg` or `--round`, which are applied along with the CSS
+	#[serde(skip_serializing_if = "HashMap::is_empty")]
+	pub variables: HashMap<String, String>,
+	/// The font family for the launcher to use
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub font: Option<String>,
 }
 
 /// Types of GUI themes
@@ -906,7 +961,7 @@ def_hook!(
 def_hook!(
 	GetLoaderVersions,
 	"get_loader_versions",
-	"Gets the list of versions for a loader",
+	"Gets the list of versions for a loader, sorted from newest to oldest",
 	GetLoaderVersionsArg,
 	Vec<String>,
 	1,
@@ -921,6 +976,8 @@ pub struct GetLoaderVersionsArg {
 	pub loader: Loader,
 	/// The Minecraft version of the instance
 	pub minecraft_version: String,
+	/// Which versions to include
+	pub channel: LoaderVersionChannel,
 }
 
 def_hook!(
@@ -1314,3 +1371,45 @@ pub struct InstallModpackResult {
 	/// The addons installed by this modpack
 	pub addons: Vec<Addon>,
 }
+
+def_hook!(
+	AddPasteBackends,
+	"add_paste_backends",
+	"Add new services for uploading logs to",
+	(),
+	Vec<PasteBackend>,
+	1,
+	true,
+);
+
+/// A service for uploading logs to
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PasteBackend {
+	/// ID for the backend
+	pub id: String,
+	/// Display name of the backend
+	pub name: String,
+}
+
+def_hook!(
+	UploadPaste,
+	"upload_paste",
+	"Uploads text to a paste backend, returning the URL to view it",
+	UploadPasteArg,
+	String,
+	1,
+	true,
+);
+
+/// Argument for the UploadPaste hook
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct UploadPasteArg {
+	/// The ID of the backend to upload to
+	pub backend: String,
+	/// A name for what is being uploaded, such as the log file name
+	pub name: String,
+	/// The text to upload
+	pub contents: String,
+}
@@ -11,10 +11,11 @@ use std::{
 };
 
 use anyhow::{Context, bail};
+use nitro_net::activity;
 use nitro_net::download::{self, Client};
 use nitro_shared::{
 	Side,
-	io::{home_dir, update_link},
+	io::{copy_file, home_dir, update_link},
 	nitro_executable::NitroExecutableRegistry,
 	no_window,
 	output::{Message, MessageContents, MessageLevel, NitroOutput},
@@ -181,7 +182,7 @@ impl<H: Hook> WASMHookHandle<H> {
 			data_dir: self.data_dir.clone(),
 			config_dir: self.config_dir.clone(),
 			plugin_dir: self.plugin_dir.clone(),
-			client: Client::new(),
+			client: download::create_client(download::get_proxy().as_ref()).unwrap_or_default(),
 			o: self.o.clone(),
 		};
 
@@ -384,6 +385,22 @@ impl bindings::InterfaceWorldImports for State {
 		}
 	}
 
+	async fn copy_file(&mut self, src: String, tgt: String) -> Result<(), String> {
+		let result = copy_file(Path::new(&src), Path::new(&tgt));
+		match result {
+			Ok(..) => Ok(()),
+			Err(e) => Err(format!("{e:?}")),
+		}
+	}
+
+	async fn move_to_trash(&mut self, path: String) -> Result<(), String> {
+		let result = trash::delete(Path::new(&path));
+		match result {
+			Ok(..) => Ok(()),
+			Err(e) => Err(format!("{e:?}")),
+		}
+	}
+
 	async fn download_bytes(&mut self, url: String) -> Result<Vec<u8>, String> {
 		let result = download::bytes(url, &self.client).await;
 		match result {
@@ -452,6 +469,10 @@ impl bindings::InterfaceWorldImports for State {
 	) -> Result<(i32, u32), String> {
 		let mut command = Command::new(cmd);
 		command.args(args);
+		if let Some(proxy) = download::get_proxy() {
+			command.envs(download::get_proxy_env_vars(&proxy));
+		}
+		command.envs(activity::get_bandwidth_limit_env_vars());
 		if let Some(working_dir) = working_dir {
 			command.current_dir(working_dir);
 		}
@@ -15,6 +15,7 @@ use anyhow::{Context, bail};
 use itertools::Itertools;
 use nitro_config::instance::InstanceConfig;
 use nitro_config::template::TemplateConfig;
+use nitro_shared::error::PluginError;
 use nitro_shared::output::NitroOutput;
 use tokio::sync::Mutex;
 
@@ -64,7 +65,7 @@ impl CorePluginManager {
 			.unwrap_or(DEFAULT_PROTOCOL_VERSION)
 			> NEWEST_PROTOCOL_VERSION
 		{
-			bail!("Plugin has a newer protocol version than Nitrolaunch");
+			bail!(PluginError::NewerProtocolVersion);
 		}
 
 		// Update the plugin list
@@ -139,7 +140,7 @@ impl CorePluginManager {
 					o,
 				)
 				.await
-				.with_context(|| format!("Hook failed for plugin {}", plugin.get_id()))?;
+				.with_context(|| PluginError::HookFailed(plugin.get_id().to_string()))?;
 			out.extend(result);
 		}
 
@@ -178,7 +179,7 @@ impl CorePluginManager {
 			}
 		}
 
-		bail!("No plugin found that matched the given ID")
+		bail!(PluginError::NotFound)
 	}
 
 	/// Iterate over the plugins
@@ -186,6 +187,11 @@ impl CorePluginManager {
 		self.plugins.iter()
 	}
 
+	/// Iterate over mutable references to the plugins
+	pub fn iter_plugins_mut(&mut self) -> impl Iterator<Item = &mut Plugin> {
+		self.plugins.iter_mut()
+	}
+
 	/// Checks whether the given plugin is present and enabled in the manager
 	pub fn has_plugin(&self, plugin_id: &str) -> bool {
 		self.plugin_list.iter().any(|x| x == plugin_id)
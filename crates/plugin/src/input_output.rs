@@ -37,6 +37,13 @@ pub enum OutputAction {
 		/// The argument/input to the command
 		payload: serde_json::Value,
 	},
+	/// Send an event to the plugin's pages in the GUI
+	EmitEvent {
+		/// The name of the event
+		event: String,
+		/// Data for the event
+		data: serde_json::Value,
+	},
 }
 
 impl OutputAction {
@@ -331,6 +331,13 @@ pub struct PluginManifest {
 	pub supports_template_creation: bool,
 	/// The subcommands the plugin provides
 	pub subcommands: HashMap<String, PluginProvidedSubcommand>,
+	/// Deprecated, as the plugin's GUI pages are always rendered in a sandbox unless they are trusted
+	pub sandbox_pages: bool,
+	/// Whether the plugin's GUI pages are injected directly into the launcher instead of
+	/// being sandboxed. Only honored for plugins installed from an official repo
+	pub trusted_pages: bool,
+	/// The custom actions that the plugin's sandboxed pages are allowed to call
+	pub page_actions: Vec<String>,
 }
 
 impl PluginManifest {
@@ -0,0 +1,256 @@
+use std::fmt::{Debug, Display};
+
+use crate::pkg::ResolutionError;
+
+/// Result type for public functions that return classified errors
+pub type NitroResult<T> = Result<T, NitroError>;
+
+/// Error from a request over the network
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+	/// A request was made while in offline mode
+	#[error("Cannot download while in offline mode")]
+	Offline,
+	/// The server could not be reached
+	#[error("Failed to send request")]
+	Connection,
+	/// The server did not respond in time
+	#[error("Request timed out")]
+	Timeout,
+	/// The server responded with an error status code
+	#[error("Server reported an error (status {0})")]
+	Status(u16),
+}
+
+impl NetworkError {
+	/// Checks whether the request could succeed if it was tried again later
+	pub fn is_retryable(&self) -> bool {
+		match self {
+			Self::Offline => false,
+			Self::Connection | Self::Timeout => true,
+			Self::Status(status) => *status == 429 || *status >= 500,
+		}
+	}
+}
+
+/// Error from authenticating an account
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+	/// An account is required but none is chosen
+	#[error("No account chosen")]
+	NoAccountChosen,
+	/// The account to authenticate does not exist
+	#[error("Account does not exist")]
+	AccountNotFound,
+	/// Offline authentication was used for an account that was never authenticated online
+	#[error(
+		"Account not present in database. Make sure to authenticate at least once before logging in in offline mode"
+	)]
+	NotInDatabase,
+	/// The account does not own the game
+	#[error(
+		"Specified account does not own Minecraft. You can still play the demo by using a demo account instead."
+	)]
+	GameNotOwned,
+	/// Ownership of the game could not be proven, for example when using an alternative auth system
+	#[error(
+		"Could not prove game ownership. If using an alternative auth system, like from a plugin, you must login with a Microsoft account that owns Minecraft first. If you don't own the game, you can still play the demo by setting launch.demo to true."
+	)]
+	OwnershipNotProven,
+	/// Authentication failed, and the user should be asked to log in again
+	#[error("Failed to update account authentication")]
+	Failed,
+}
+
+/// Error from launching an instance
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum LaunchError {
+	/// The instance is not a server, but server launch was requested
+	#[error("Instance is not a server")]
+	NotAServer,
+	/// The game process could not be started
+	#[error("Failed to launch game process")]
+	ProcessFailed,
+	/// The instance uses a custom launch, but no plugin handled it
+	#[error("No plugins handled custom launch for this instance")]
+	CustomLaunchNotHandled,
+}
+
+/// Error from a plugin
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum PluginError {
+	/// A hook of a plugin failed
+	#[error("Hook failed for plugin {0}")]
+	HookFailed(String),
+	/// A hook process of a plugin exited with an error
+	#[error("Hook from plugin '{plugin}' returned a non-zero exit code{code}", code = .code.map(|x| format!(" of {x}")).unwrap_or_default())]
+	NonZeroExit {
+		/// The plugin that the hook is from
+		plugin: String,
+		/// The exit code of the process, if it has one
+		code: Option<i32>,
+	},
+	/// A plugin uses a newer version of the plugin protocol than this version of Nitrolaunch
+	#[error("Plugin has a newer protocol version than Nitrolaunch")]
+	NewerProtocolVersion,
+	/// A plugin was not found
+	#[error("No plugin found that matched the given ID")]
+	NotFound,
+}
+
+/// Error returned at the public boundaries of Nitrolaunch, which can be classified
+/// so that frontends can react to different kinds of failures. Errors are still created
+/// and given context using anyhow, and the typed errors are found in the chain of causes
+pub struct NitroError {
+	inner: anyhow::Error,
+}
+
+impl NitroError {
+	/// Gets what kind of error this is. Network errors take precedence over the others,
+	/// since they are usually the root cause when they appear alongside them
+	pub fn kind(&self) -> ErrorKind<'_> {
+		if let Some(error) = self.find() {
+			ErrorKind::Network(error)
+		} else if let Some(error) = self.find() {
+			ErrorKind::Auth(error)
+		} else if let Some(error) = self.find() {
+			ErrorKind::Resolution(error)
+		} else if let Some(error) = self.find() {
+			ErrorKind::Launch(error)
+		} else if let Some(error) = self.find() {
+			ErrorKind::Plugin(error)
+		} else {
+			ErrorKind::Other
+		}
+	}
+
+	/// Finds an error of a type in the chain of causes
+	pub fn find<E: Display + Debug + Send + Sync + 'static>(&self) -> Option<&E> {
+		self.inner.downcast_ref()
+	}
+
+	/// Adds context to this error
+	pub fn context<C: Display + Send + Sync + 'static>(self, context: C) -> Self {
+		Self {
+			inner: self.inner.context(context),
+		}
+	}
+
+	/// Gets the underlying anyhow error
+	pub fn into_inner(self) -> anyhow::Error {
+		self.inner
+	}
+}
+
+impl From<anyhow::Error> for NitroError {
+	fn from(value: anyhow::Error) -> Self {
+		Self { inner: value }
+	}
+}
+
+impl From<NitroError> for anyhow::Error {
+	fn from(value: NitroError) -> Self {
+		value.inner
+	}
+}
+
+macro_rules! from_typed_error {
+	($($ty:ty),*) => {
+		$(
+			impl From<$ty> for NitroError {
+				fn from(value: $ty) -> Self {
+					Self {
+						inner: value.into(),
+					}
+				}
+			}
+		)*
+	};
+}
+
+from_typed_error!(
+	NetworkError,
+	AuthError,
+	ResolutionError,
+	LaunchError,
+	PluginError
+);
+
+impl Display for NitroError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		Display::fmt(&self.inner, f)
+	}
+}
+
+impl Debug for NitroError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		Debug::fmt(&self.inner, f)
+	}
+}
+
+/// The kind of a NitroError, with the typed error that caused it
+#[derive(Debug, Clone, Copy)]
+pub enum ErrorKind<'a> {
+	/// Error from the network
+	Network(&'a NetworkError),
+	/// Error from authentication
+	Auth(&'a AuthError),
+	/// Error from package resolution
+	Resolution(&'a ResolutionError),
+	/// Error from launching
+	Launch(&'a LaunchError),
+	/// Error from a plugin
+	Plugin(&'a PluginError),
+	/// Any other error
+	Other,
+}
+
+impl ErrorKind<'_> {
+	/// Checks whether this error was caused by being offline, either by choice or because the network is unreachable
+	pub fn is_offline(&self) -> bool {
+		matches!(
+			self,
+			Self::Network(NetworkError::Offline | NetworkError::Connection)
+		)
+	}
+
+	/// Checks whether the user should be asked to log in again
+	pub fn needs_reauth(&self) -> bool {
+		matches!(
+			self,
+			Self::Auth(AuthError::Failed | AuthError::NotInDatabase)
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use anyhow::Context;
+
+	use super::*;
+
+	#[test]
+	fn test_error_kind() {
+		let error: anyhow::Result<()> = Err(anyhow::Error::new(NetworkError::Timeout))
+			.context("Failed to download")
+			.context(AuthError::Failed)
+			.context("Failed to launch");
+		let error = NitroError::from(error.unwrap_err());
+		assert!(matches!(
+			error.kind(),
+			ErrorKind::Network(NetworkError::Timeout)
+		));
+		assert_eq!(error.find::<AuthError>(), Some(&AuthError::Failed));
+		assert!(format!("{error:?}").contains("Failed to download"));
+
+		let error =
+			NitroError::from(anyhow::anyhow!("Something else")).context(LaunchError::ProcessFailed);
+		assert!(matches!(
+			error.kind(),
+			ErrorKind::Launch(LaunchError::ProcessFailed)
+		));
+
+		let error = NitroError::from(anyhow::anyhow!("Something else"));
+		assert!(matches!(error.kind(), ErrorKind::Other));
+	}
+}
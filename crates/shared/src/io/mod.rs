@@ -4,6 +4,10 @@ use crate::io::config::IO_CONFIG;
 
 /// IO configuration
 pub mod config;
+/// Copy-on-write copies of files
+pub mod reflink;
+
+pub use reflink::copy_file;
 
 /// Tries to get the user's home dir
 pub fn home_dir() -> anyhow::Result<PathBuf> {
@@ -26,11 +30,13 @@ pub fn get_link_method() -> LinkMethod {
 		// Hard links require admin privileges on Windows
 		#[cfg(target_os = "windows")]
 		return LinkMethod::Soft;
+		// Reflinks aren't the default since some shared files, like world files, rely on changes being shared
 		#[cfg(not(target_os = "windows"))]
 		return LinkMethod::Hard;
 	};
 
 	match method.as_str() {
+		"reflink" => LinkMethod::Reflink,
 		"hard" => LinkMethod::Hard,
 		"soft" => LinkMethod::Soft,
 		"copy" => LinkMethod::Copy,
@@ -40,6 +46,8 @@ pub fn get_link_method() -> LinkMethod {
 
 /// Different methods for files to be linked with
 pub enum LinkMethod {
+	/// Reflink on filesystems that support them, falling back to a hardlink
+	Reflink,
 	/// Hardlink
 	Hard,
 	/// Symlink
@@ -53,6 +61,11 @@ pub fn update_link(path: &Path, link: &Path) -> std::io::Result<()> {
 	let method = get_link_method();
 
 	match method {
+		LinkMethod::Reflink => {
+			if !link.exists() && reflink::reflink(path, link).is_err() {
+				std::fs::hard_link(path, link)?;
+			}
+		}
 		LinkMethod::Hard => {
 			if !link.exists() {
 				std::fs::hard_link(path, link)?;
@@ -66,7 +79,7 @@ pub fn update_link(path: &Path, link: &Path) -> std::io::Result<()> {
 		}
 		LinkMethod::Copy => {
 			if !link.exists() {
-				std::fs::copy(path, link)?;
+				copy_file(path, link)?;
 			}
 		}
 	}
@@ -74,6 +87,14 @@ pub fn update_link(path: &Path, link: &Path) -> std::io::Result<()> {
 	Ok(())
 }
 
+/// Updates the modification time of a file to now, which is used to track when cached files were last used.
+/// Errors are ignored since the file may be read-only
+pub fn mark_file_used(path: &Path) {
+	if let Ok(file) = std::fs::File::options().write(true).open(path) {
+		let _ = file.set_modified(std::time::SystemTime::now());
+	}
+}
+
 /// Gets the size of a directory recursively
 pub fn dir_size(path: &Path) -> anyhow::Result<usize> {
 	if !path.exists() {
@@ -0,0 +1,106 @@
+use std::path::Path;
+
+#[cfg(target_os = "linux")]
+unsafe extern "C" {
+	fn ioctl(fd: i32, request: std::ffi::c_ulong, ...) -> i32;
+}
+
+#[cfg(target_os = "macos")]
+unsafe extern "C" {
+	fn clonefile(src: *const std::ffi::c_char, dst: *const std::ffi::c_char, flags: u32) -> i32;
+}
+
+/// Copies a file, using a reflink if the filesystem supports them and falling back
+/// to a full copy otherwise. Existing files at the destination are overwritten
+pub fn copy_file(src: &Path, dest: &Path) -> std::io::Result<()> {
+	if reflink(src, dest).is_ok() {
+		return Ok(());
+	}
+
+	std::fs::copy(src, dest)?;
+	Ok(())
+}
+
+/// Creates a copy-on-write clone of a file, which shares its data with the original until
+/// either of them is modified. Only works on filesystems that support it, like btrfs, XFS, and APFS,
+/// and fails if the files are on different filesystems
+#[cfg(target_os = "linux")]
+pub fn reflink(src: &Path, dest: &Path) -> std::io::Result<()> {
+	use std::fs::File;
+	use std::os::fd::AsRawFd;
+
+	/// ioctl for cloning a whole file
+	const FICLONE: std::ffi::c_ulong = 0x40049409;
+
+	let src_file = File::open(src)?;
+	let permissions = src_file.metadata()?.permissions();
+	let dest_file = File::create(dest)?;
+
+	// SAFETY: Both file descriptors are valid and owned by files that stay open for the whole call,
+	// and FICLONE takes the source descriptor as its only argument without touching any memory
+	let result = unsafe { ioctl(dest_file.as_raw_fd(), FICLONE, src_file.as_raw_fd()) };
+	if result != 0 {
+		let error = std::io::Error::last_os_error();
+		std::mem::drop(dest_file);
+		let _ = std::fs::remove_file(dest);
+		return Err(error);
+	}
+
+	dest_file.set_permissions(permissions)
+}
+
+/// Creates a copy-on-write clone of a file, which shares its data with the original until
+/// either of them is modified. Only works on filesystems that support it, like btrfs, XFS, and APFS,
+/// and fails if the files are on different filesystems
+#[cfg(target_os = "macos")]
+pub fn reflink(src: &Path, dest: &Path) -> std::io::Result<()> {
+	use std::ffi::CString;
+	use std::os::unix::ffi::OsStrExt;
+
+	let src = CString::new(src.as_os_str().as_bytes())?;
+	// clonefile fails if the destination already exists
+	if dest.exists() {
+		std::fs::remove_file(dest)?;
+	}
+	let dest = CString::new(dest.as_os_str().as_bytes())?;
+
+	// SAFETY: Both pointers come from CStrings that are nul-terminated and outlive the call
+	let result = unsafe { clonefile(src.as_ptr(), dest.as_ptr(), 0) };
+	if result != 0 {
+		return Err(std::io::Error::last_os_error());
+	}
+
+	Ok(())
+}
+
+/// Creates a copy-on-write clone of a file. Not supported on this platform, so this always fails
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn reflink(_src: &Path, _dest: &Path) -> std::io::Result<()> {
+	Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_copy_file() {
+		let dir = std::env::temp_dir().join("nitro_test_copy_file");
+		let _ = std::fs::remove_dir_all(&dir);
+		std::fs::create_dir_all(&dir).unwrap();
+
+		let src = dir.join("src.txt");
+		let dest = dir.join("dest.txt");
+		std::fs::write(&src, "Hello").unwrap();
+		std::fs::write(&dest, "Existing file").unwrap();
+
+		copy_file(&src, &dest).unwrap();
+		assert_eq!(std::fs::read_to_string(&dest).unwrap(), "Hello");
+
+		// Copies should never share modifications, even when they are reflinks
+		std::fs::write(&dest, "Modified").unwrap();
+		assert_eq!(std::fs::read_to_string(&src).unwrap(), "Hello");
+
+		let _ = std::fs::remove_dir_all(dir);
+	}
+}
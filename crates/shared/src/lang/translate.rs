@@ -91,6 +91,7 @@ define_translations! {
 	TransferModsFeature, "Instance transfer modloader feature", "mods";
 	TransferLaunchSettingsFeature, "Instance transfer launch settings feature", "launch settings";
 	AuthenticationSuccessful, "When authentication succeeds", "Authentication successful";
+	UsingCachedOwnershipCheck, "When the game ownership check fails and a recent successful check is used instead", "Could not verify game ownership, using the result of a previous check";
 	StartInstallingPlugin, "When starting to install a plugin", "Installing plugin %plugin";
 	FinishInstallingPlugin, "When finishing installing a plugin", "Plugin installed";
 	StartExporting, "When starting to export an instance", "Exporting instance '%instance' in format '%format' using plugin '%plugin'";
@@ -99,16 +100,40 @@ define_translations! {
 	StartImporting, "When starting to import an instance", "Importing instance '%instance' in format '%format' using plugin '%plugin'";
 	ImportPluginNoResult, "When the plugin used for instance import doesn't return anything", "Import plugin did not return a result";
 	FinishImporting, "When finishing importing an instance", "Import finished";
+	StartDownloadingImport, "When starting to download an instance file to import", "Downloading instance from %url";
+	FinishDownloadingImport, "When finishing downloading an instance file to import", "Instance downloaded";
 	PluginNotFound, "When a plugin cannot be found", "Could not find files for plugin %plugin";
 	PluginDependencyMissing, "When a plugin dependency is missing", "Dependency %dependency is missing for plugin %plugin";
 	PluginForNewerVersion, "When a plugin is made for a newer version of Nitrolaunch", "Plugin %plugin is made for a newer version of Nitrolaunch";
 	StartAuthenticating, "When starting authentication", "Authenticating";
 	FinishAuthenticating, "When finishing authentication", "Authenticated";
+	NoArm64Natives, "When ARM64 natives are selected but the game version has none and they can't be replaced", "ARM64 native libraries are not available for this version. The game may not launch";
+	CrashBundleCreated, "When an instance exits abnormally and a crash bundle is created", "The instance exited abnormally. A crash bundle with diagnostic information was saved to %path";
+	StartUploadingPaste, "When starting to upload a log to a paste backend", "Uploading %name to %backend";
+	FinishUploadingPaste, "When finishing uploading a log to a paste backend", "Uploaded to %url";
+	StartVerifyingInstance, "When starting to verify the files of an instance", "Verifying files for instance %inst";
+	CorruptedFileFound, "When a file fails verification and will be redownloaded", "File %file is corrupted";
+	FinishVerifyingInstance, "When finishing verifying an instance and corrupted files were repaired", "Repaired %count corrupted files";
+	NoCorruptedFiles, "When finishing verifying an instance and no corrupted files were found", "All files are intact";
+	StartPruningFiles, "When starting to prune unused game files", "Pruning unused game files";
+	FinishPruningFiles, "When finishing pruning unused game files", "Removed %count unused files, freeing %size";
+	PruneDryRun, "When reporting what pruning unused game files would remove", "Pruning would remove %count unused files, freeing %size";
+	CacheLimitEnforced, "When cached files are removed to stay under the configured cache limit", "Removed %count cached files to stay under the cache limit, freeing %size";
+	EstimatedDownloadSize, "When showing how much will be downloaded to install packages", "About %size will be downloaded";
+	EstimatedDownloadSizeIncomplete, "When showing how much will be downloaded to install packages and some file sizes are unknown", "At least %size will be downloaded";
+	AddonConflictSamePath, "When two packages install addons to the same file", "Packages %pkg1 and %pkg2 both install the file '%file'";
+	AddonConflictSameContent, "When two packages install the same mod or plugin", "Packages %pkg1 and %pkg2 both install the same content";
+	AddonConflictSkipped, "When an addon is skipped to resolve a conflict", "Skipped addon '%addon' from package %pkg";
+	AddonConflictRenamed, "When an addon is renamed to resolve a conflict", "Installing addon '%addon' from package %pkg as '%file'";
+	AddonConflictOptions, "When showing how to resolve an addon conflict", "Set on_conflict for one of the packages to resolve this: %options";
+	AddonConflictsUnresolved, "When package addon conflicts could not be resolved", "%count addon conflicts could not be resolved";
 	AssetFailed, "When a single asset fails to download", "Asset failed to download:\n%error";
 	AssetsFailed, "When one or more assets fail to download", "%num assets failed to download. Minecraft may not load properly.";
 	StartUpdatingInstanceVersion, "When starting to update an instance version", "Updating instance from %version1 to %version2";
+	InstanceAdvancedChannel, "When an instance that follows a version channel is moved to a newer version", "This instance follows the %channel channel and was moved to the newest version automatically";
 	StartUpdatingInstanceLoader, "When starting to change an instance's loader", "Removing current loader from the instance";
 	FinishUpdatingInstanceVersion, "When finishing updating an instance version", "Finished update";
+	LoaderVersionRolledBack, "When setting up a new loader version fails and the previously working version is used instead", "Failed to set up the new %loader version, so the previous version %version will be used instead:\n%error";
 	InvalidInstanceConfig, "When the configuration for an instance is invalid", "Configuration for instance '%instance' is invalid:\n%error";
 	AgreeToEula, "Notice that the user agrees to the server EULA", "By creating a server instance, you agree to the terms of the Minecraft EULA";
 	StartMigrating, "When starting to migrate instances", "Importing instances using format '%format' and plugin '%plugin'";
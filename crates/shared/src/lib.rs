@@ -6,6 +6,8 @@
 //!
 //! - `schema`: Enable generation of JSON schemas using the `schemars` crate
 
+/// Typed errors that can be classified by frontends
+pub mod error;
 /// Filesystem
 pub mod io;
 /// Operating Java memory arguments
@@ -4,6 +4,8 @@ use std::fmt::Display;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::versions::VersionPattern;
+
 /// A modification applied to a client or server, such as a modloader or plugin loader
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, Hash)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -272,3 +274,109 @@ impl ProxyMatch {
 		}
 	}
 }
+
+/// Version pattern that selects the newest version of a loader, even if it is unstable, as in `fabric@beta`
+pub const BETA_LOADER_VERSION: &str = "beta";
+
+/// A version of a loader, along with whether it is a stable release
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LoaderVersion {
+	/// The version
+	pub version: String,
+	/// Whether the version is stable
+	pub stable: bool,
+}
+
+impl LoaderVersion {
+	/// Creates a loader version, guessing whether it is stable from its name
+	pub fn from_name(version: String) -> Self {
+		let stable = !is_unstable_version_name(&version);
+		Self { version, stable }
+	}
+}
+
+/// Which versions of a loader to use
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LoaderVersionChannel {
+	/// Only stable versions
+	#[default]
+	Stable,
+	/// All versions, including betas and release candidates
+	Beta,
+}
+
+/// Selects the version of a loader to install for a version pattern from a list of versions, sorted from oldest
+/// to newest. When the version isn't pinned, the newest stable version is used, or the newest version if there
+/// aren't any stable ones yet
+pub fn select_loader_version<'a>(
+	desired: &VersionPattern,
+	versions: &'a [LoaderVersion],
+) -> Option<&'a LoaderVersion> {
+	match desired {
+		VersionPattern::Any | VersionPattern::Latest(None) => {
+			versions.iter().rev().find(|x| x.stable).or(versions.last())
+		}
+		VersionPattern::Single(version) if version == BETA_LOADER_VERSION => versions.last(),
+		desired => {
+			let names: Vec<_> = versions.iter().map(|x| x.version.clone()).collect();
+			let version = desired.get_match(&names)?;
+			versions.iter().find(|x| x.version == version)
+		}
+	}
+}
+
+/// Gets the names of the versions of a loader in a channel from a list of versions sorted from oldest to newest.
+/// The names are returned from newest to oldest
+pub fn list_loader_versions(
+	versions: &[LoaderVersion],
+	channel: LoaderVersionChannel,
+) -> Vec<String> {
+	versions
+		.iter()
+		.rev()
+		.filter(|x| channel == LoaderVersionChannel::Beta || x.stable)
+		.map(|x| x.version.clone())
+		.collect()
+}
+
+/// Guesses whether a version is unstable from its name
+pub fn is_unstable_version_name(version: &str) -> bool {
+	let version = version.to_ascii_lowercase();
+	["alpha", "beta", "pre", "rc", "snapshot"]
+		.iter()
+		.any(|x| version.contains(x))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_select_loader_version() {
+		let versions: Vec<_> = ["0.15.0", "0.16.0", "0.17.0-beta.1"]
+			.into_iter()
+			.map(|x| LoaderVersion::from_name(x.into()))
+			.collect();
+
+		let select = |pattern: &str| {
+			select_loader_version(&VersionPattern::from(pattern), &versions)
+				.map(|x| x.version.as_str())
+		};
+		assert_eq!(select("*"), Some("0.16.0"));
+		assert_eq!(select("latest"), Some("0.16.0"));
+		assert_eq!(select("beta"), Some("0.17.0-beta.1"));
+		assert_eq!(select("0.15.0"), Some("0.15.0"));
+		assert_eq!(select("0.14.0"), None);
+
+		assert_eq!(
+			list_loader_versions(&versions, LoaderVersionChannel::Stable),
+			vec!["0.16.0", "0.15.0"]
+		);
+		assert_eq!(
+			list_loader_versions(&versions, LoaderVersionChannel::Beta)[0],
+			"0.17.0-beta.1"
+		);
+	}
+}
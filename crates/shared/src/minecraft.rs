@@ -66,6 +66,152 @@ pub struct LatestVersions {
 	pub snapshot: VersionName,
 }
 
+impl VersionManifest {
+	/// Gets the newest version in a channel
+	pub fn get_latest_in_channel(&self, channel: VersionChannel) -> Option<&VersionEntry> {
+		self.versions.iter().find(|x| x.get_channel() == channel)
+	}
+}
+
+impl VersionEntry {
+	/// Gets the channel that this version belongs to
+	pub fn get_channel(&self) -> VersionChannel {
+		VersionChannel::from_version(&self.id, &self.ty)
+	}
+}
+
+/// Release channel that a Minecraft version belongs to
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum VersionChannel {
+	/// Full releases
+	Release,
+	/// Snapshots, pre-releases, and release candidates
+	Snapshot,
+	/// Old beta versions
+	Beta,
+	/// Old alpha versions
+	Alpha,
+	/// Joke versions released on April Fools' Day
+	AprilFools,
+}
+
+/// IDs of April Fools versions, which the version manifest lists as snapshots
+const APRIL_FOOLS_VERSIONS: [&str; 8] = [
+	"15w14a",
+	"1.RV-Pre1",
+	"3D Shareware v1.34",
+	"20w14infinite",
+	"22w13oneblockatatime",
+	"23w13a_or_b",
+	"24w14potato",
+	"25w14craftmine",
+];
+
+impl VersionChannel {
+	/// Gets the channel of a version from its ID and type in the version manifest
+	pub fn from_version(id: &str, ty: &VersionType) -> Self {
+		if APRIL_FOOLS_VERSIONS.contains(&id) {
+			return Self::AprilFools;
+		}
+
+		match ty {
+			VersionType::Release | VersionType::Other(..) => Self::Release,
+			VersionType::Snapshot => Self::Snapshot,
+			VersionType::OldBeta => Self::Beta,
+			VersionType::OldAlpha => Self::Alpha,
+		}
+	}
+}
+
+impl Display for VersionChannel {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Release => "Release",
+				Self::Snapshot => "Snapshot",
+				Self::Beta => "Beta",
+				Self::Alpha => "Alpha",
+				Self::AprilFools => "April Fools",
+			}
+		)
+	}
+}
+
+/// Which GPU to prefer when launching the game on systems that have more than one
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum GpuPreference {
+	/// The discrete, high performance GPU
+	Discrete,
+	/// The integrated, power saving GPU
+	Integrated,
+}
+
+/// A well-known set of tuned JVM arguments
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum JvmPreset {
+	/// Aikar's flags, which tune G1GC for servers
+	Aikar,
+	/// The G1GC settings that the official launcher uses for the client
+	#[serde(alias = "g1gc-client")]
+	G1gcClient,
+	/// The Z Garbage Collector, which has very short pauses. Requires Java 15 or newer
+	Zgc,
+}
+
+/// CPU architecture to select native libraries for
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum NativesArch {
+	/// 32-bit x86
+	X86,
+	/// 64-bit x86
+	X64,
+	/// 64-bit ARM
+	Arm64,
+}
+
+impl NativesArch {
+	/// Detects the architecture of the current system
+	pub fn detect() -> Self {
+		match std::env::consts::ARCH {
+			"x86" => Self::X86,
+			"aarch64" => Self::Arm64,
+			_ => Self::X64,
+		}
+	}
+
+	/// Gets the pointer width of this architecture, as used in old natives classifiers
+	pub fn bits(&self) -> &'static str {
+		match self {
+			Self::X86 => "32",
+			Self::X64 | Self::Arm64 => "64",
+		}
+	}
+}
+
+impl Display for NativesArch {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::X86 => "x86",
+				Self::X64 => "x64",
+				Self::Arm64 => "arm64",
+			}
+		)
+	}
+}
+
 /// Different kinds of addons
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
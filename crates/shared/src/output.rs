@@ -168,6 +168,11 @@ pub trait NitroOutput: Send {
 		.await
 	}
 
+	/// Sends an event from a plugin to any of its pages that are listening for it
+	fn emit_plugin_event(&mut self, plugin: &str, event: &str, data: serde_json::Value) {
+		let _ = (plugin, event, data);
+	}
+
 	/// Gets a copy of this output that may technically be used in asynchronous tasks,
 	/// but will most likely be used for something synchronous like the output of a plugin command
 	fn get_greater_copy(&self) -> Box<dyn NitroOutput + Sync> {
@@ -255,6 +260,10 @@ impl<T: NitroOutput + Sync + ?Sized> NitroOutput for Box<T> {
 		self.deref_mut().prompt_special_package_diffs(diffs).await
 	}
 
+	fn emit_plugin_event(&mut self, plugin: &str, event: &str, data: serde_json::Value) {
+		self.deref_mut().emit_plugin_event(plugin, event, data)
+	}
+
 	fn get_greater_copy(&self) -> Box<dyn NitroOutput + Sync> {
 		self.deref().get_lesser_copy()
 	}
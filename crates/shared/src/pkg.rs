@@ -186,6 +186,16 @@ impl PkgRequest {
 		self.debug_sources_inner(String::new())
 	}
 
+	/// Gets the request at the top of the source chain of this request, which is usually the package required by the user
+	pub fn root_request(self: &Arc<Self>) -> ArcPkgReq {
+		match &self.source {
+			PkgRequestSource::Dependency(source)
+			| PkgRequestSource::Refused(source)
+			| PkgRequestSource::Bundled(source) => source.root_request(),
+			PkgRequestSource::UserRequire | PkgRequestSource::Repository => self.clone(),
+		}
+	}
+
 	/// Converts to repository:id or id
 	pub fn to_string_no_version(&self) -> String {
 		if let Some(repo) = &self.repository {
@@ -350,6 +360,19 @@ impl PackageKind {
 	}
 }
 
+impl From<AddonKind> for PackageKind {
+	fn from(value: AddonKind) -> Self {
+		match value {
+			AddonKind::Mod => Self::Mod,
+			AddonKind::ResourcePack => Self::ResourcePack,
+			AddonKind::Datapack => Self::Datapack,
+			AddonKind::Plugin => Self::Plugin,
+			AddonKind::Shader => Self::Shader,
+			AddonKind::Modpack => Self::Modpack,
+		}
+	}
+}
+
 impl FromStr for PackageKind {
 	type Err = anyhow::Error;
 
@@ -402,6 +425,33 @@ pub struct PackageSearchParameters {
 	pub loaders: Vec<Loader>,
 	/// The package categories to include
 	pub categories: Vec<PackageCategory>,
+	/// How to sort the results
+	#[serde(default)]
+	pub sort: PackageSortMethod,
+	/// The cursor from the previous page of results to continue from. If this is set, it is used instead of skip
+	#[serde(default)]
+	pub cursor: Option<String>,
+	/// Whether results that are cached but out of date can be returned right away. These
+	/// results will be marked as stale, and the search should be repeated without this to refresh them
+	#[serde(default)]
+	pub allow_stale: bool,
+}
+
+/// Method for sorting package search results
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageSortMethod {
+	/// Sort by how well packages match the search, as decided by the repository
+	#[default]
+	Relevance,
+	/// Sort by number of downloads
+	Downloads,
+	/// Sort by number of followers
+	Followers,
+	/// Sort by when packages were last updated
+	Updated,
+	/// Sort by when packages were created
+	Newest,
 }
 
 /// How much of a package we want to query depending on what operation we are doing.
@@ -536,6 +586,100 @@ pub enum ResolutionError {
 	Misc(anyhow::Error),
 }
 
+impl ResolutionError {
+	/// Suggests changes the user could make to their config to fix this error
+	pub fn suggest_fixes(&self) -> Vec<ResolutionFix> {
+		let mut out = Vec::new();
+		match self {
+			Self::PackageContext(_, error) => return error.suggest_fixes(),
+			Self::FailedToPreload(..) | Self::Misc(..) => {}
+			Self::FailedToGetProperties(req, _) | Self::FailedToEvaluate(req, _) => {
+				out.extend(ResolutionFix::remove(req));
+			}
+			Self::NoValidVersionsFound(req, constraints) => {
+				for constraint in constraints {
+					if let VersionPattern::Single(version) = constraint {
+						let fix = ResolutionFix::PinVersion {
+							package: req.clone(),
+							version: version.clone(),
+						};
+						if !out.contains(&fix) {
+							out.push(fix);
+						}
+					}
+				}
+				out.extend(ResolutionFix::remove(req));
+			}
+			Self::ExtensionNotFulfilled(extension, req) => {
+				out.push(ResolutionFix::AddPackage {
+					package: req.clone(),
+				});
+				if let Some(extension) = extension {
+					out.extend(ResolutionFix::remove(extension));
+				}
+			}
+			Self::ExplicitRequireNotFulfilled(req, requirer) => {
+				out.push(ResolutionFix::AddPackage {
+					package: req.clone(),
+				});
+				out.extend(ResolutionFix::remove(requirer));
+			}
+			Self::IncompatiblePackage(req, refusers) => {
+				out.extend(ResolutionFix::remove(req));
+				for refuser in refusers {
+					let fix = ResolutionFix::RemovePackage {
+						package: Arc::new(PkgRequest::parse(
+							refuser,
+							PkgRequestSource::UserRequire,
+						)),
+					};
+					if !out.contains(&fix) {
+						out.push(fix);
+					}
+				}
+			}
+		}
+
+		out
+	}
+}
+
+/// A suggested change to fix a package resolution error
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "type")]
+pub enum ResolutionFix {
+	/// Remove a package that was required by the user
+	RemovePackage {
+		/// The package to remove
+		package: ArcPkgReq,
+	},
+	/// Add a package to the user's config
+	AddPackage {
+		/// The package to add
+		package: ArcPkgReq,
+	},
+	/// Pin a package to a specific content version
+	PinVersion {
+		/// The package to pin
+		package: ArcPkgReq,
+		/// The version to pin to
+		version: String,
+	},
+}
+
+impl ResolutionFix {
+	/// Creates a fix that removes the user-required package responsible for a request, if there is one
+	fn remove(req: &ArcPkgReq) -> Option<Self> {
+		let root = req.root_request();
+		if root.source == PkgRequestSource::UserRequire {
+			Some(Self::RemovePackage { package: root })
+		} else {
+			None
+		}
+	}
+}
+
 /// A change to an installed package, used for user display
 #[derive(Clone)]
 pub enum PackageDiff {
@@ -568,6 +712,41 @@ mod tests {
 		));
 	}
 
+	#[test]
+	fn test_resolution_fixes() {
+		let user = Arc::new(PkgRequest::parse("foo", PkgRequestSource::UserRequire));
+		let dep = Arc::new(PkgRequest::parse(
+			"bar",
+			PkgRequestSource::Dependency(user.clone()),
+		));
+
+		let error = ResolutionError::PackageContext(
+			user.clone(),
+			Box::new(ResolutionError::NoValidVersionsFound(
+				dep.clone(),
+				vec![
+					VersionPattern::Single("1.2".into()),
+					VersionPattern::Single("1.3".into()),
+					VersionPattern::Single("1.2".into()),
+				],
+			)),
+		);
+		assert_eq!(
+			error.suggest_fixes(),
+			vec![
+				ResolutionFix::PinVersion {
+					package: dep.clone(),
+					version: "1.2".into()
+				},
+				ResolutionFix::PinVersion {
+					package: dep,
+					version: "1.3".into()
+				},
+				ResolutionFix::RemovePackage { package: user },
+			]
+		);
+	}
+
 	#[test]
 	fn test_request_source_debug() {
 		let req = PkgRequest::parse(
@@ -157,6 +157,30 @@ pub fn utc_timestamp() -> anyhow::Result<u64> {
 	Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
 }
 
+/// Formats a number of bytes as a human-readable size
+/// ```
+/// use nitro_shared::util::format_bytes;
+///
+/// assert_eq!(format_bytes(512), "512 B");
+/// assert_eq!(format_bytes(1536), "1.5 KiB");
+/// assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+/// ```
+pub fn format_bytes(bytes: u64) -> String {
+	const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+	if bytes < 1024 {
+		return format!("{bytes} B");
+	}
+
+	let mut size = bytes as f64 / 1024.0;
+	let mut unit = 0;
+	while size >= 1024.0 && unit < UNITS.len() - 1 {
+		size /= 1024.0;
+		unit += 1;
+	}
+
+	format!("{size:.1} {}", UNITS[unit])
+}
+
 /// Trait for a value that can be converted to an integer
 pub trait ToInt {
 	/// Get this value as an i32
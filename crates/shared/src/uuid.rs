@@ -1,4 +1,5 @@
 use anyhow::ensure;
+use md5::{Digest, Md5};
 
 /// Converts a hexadecimal uuid to the hyphenated form
 pub fn hyphenate_uuid(uuid: &str) -> anyhow::Result<String> {
@@ -14,6 +15,16 @@ pub fn hyphenate_uuid(uuid: &str) -> anyhow::Result<String> {
 	Ok(out)
 }
 
+/// Generates the UUID that the game and servers use for a player in offline mode. This is a
+/// version 3 UUID of their name, the same as `UUID.nameUUIDFromBytes` in Java. Returned without hyphens
+pub fn offline_uuid(name: &str) -> String {
+	let mut hash: [u8; 16] = Md5::digest(format!("OfflinePlayer:{name}").as_bytes()).into();
+	hash[6] = (hash[6] & 0x0f) | 0x30;
+	hash[8] = (hash[8] & 0x3f) | 0x80;
+
+	hash.iter().map(|x| format!("{x:02x}")).collect()
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -26,4 +37,9 @@ mod tests {
 			"8b447756-e180-44d3-bfbf-dbc8df722db4".to_string()
 		);
 	}
+
+	#[test]
+	fn test_offline_uuid() {
+		assert_eq!(offline_uuid("Notch"), "b50ad385829d3141a2167e7d7539ba7f");
+	}
 }
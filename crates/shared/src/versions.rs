@@ -4,6 +4,8 @@ use std::{fmt::Display, sync::Arc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::minecraft::VersionChannel;
+
 /// Pattern matching for the version of Minecraft, a package, etc.
 #[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -320,6 +322,38 @@ pub enum MinecraftLatestVersion {
 	#[serde(rename = "latest_snapshot")]
 	/// A snapshot version of Minecraft
 	Snapshot,
+	#[serde(rename = "latest_beta")]
+	/// An old beta version of Minecraft
+	Beta,
+	#[serde(rename = "latest_alpha")]
+	/// An old alpha version of Minecraft
+	Alpha,
+	#[serde(rename = "latest_april_fools")]
+	/// An April Fools version of Minecraft
+	AprilFools,
+}
+
+impl MinecraftLatestVersion {
+	/// Gets the channel that this matcher follows
+	pub fn get_channel(&self) -> VersionChannel {
+		match self {
+			Self::Release => VersionChannel::Release,
+			Self::Snapshot => VersionChannel::Snapshot,
+			Self::Beta => VersionChannel::Beta,
+			Self::Alpha => VersionChannel::Alpha,
+			Self::AprilFools => VersionChannel::AprilFools,
+		}
+	}
+}
+
+impl MinecraftVersionDeser {
+	/// Gets the channel this version follows if it automatically advances to new versions
+	pub fn get_tracked_channel(&self) -> Option<VersionChannel> {
+		match self {
+			Self::Latest(latest) => Some(latest.get_channel()),
+			Self::Version(..) => None,
+		}
+	}
 }
 
 /// String name for a Minecraft version
@@ -1,27 +1,17 @@
 use crate::State;
 use crate::output::LauncherOutput;
 use anyhow::Context;
+use nitro_frontend_api::account::{self as account_api, AccountInfo};
 use nitrolaunch::{
-	config::{
-		Config,
-		modifications::{ConfigModification, apply_modifications_and_write},
-	},
-	config_crate::account::{AccountConfig, AccountVariant},
-	core::account::AccountKind,
-	net_crate::load_from_uri,
-	plugin::PluginManager,
-	plugin_crate::hook::hooks::{
-		AccountTypeInfo, AddAccountTypes, AddSkinRepositories, SearchSkinRepository,
-		SearchSkinRepositoryArg, SkinRepository,
-	},
+	config::secrets,
+	config_crate::account::AccountVariant,
+	plugin_crate::hook::hooks::{AccountTypeInfo, SkinRepository},
 	shared::{
 		minecraft::{Cape, Skin, SkinVariant},
 		output::NoOp,
 	},
 };
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fmt::Debug;
 
 use super::{fmt_err, load_config};
 
@@ -38,41 +28,8 @@ pub async fn get_accounts(
 			.await
 			.context("Failed to load config"),
 	)?;
-	let account_ids: Vec<_> = config
-		.accounts
-		.iter_accounts()
-		.map(|x| x.0.clone())
-		.collect();
-
-	let mut accounts = HashMap::with_capacity(account_ids.len());
-	config.accounts.set_offline(true);
-	for id in account_ids {
-		let _ = config
-			.accounts
-			.authenticate_account(&id, &state.paths.core, &state.client, &mut output)
-			.await
-			.context("Failed to authenticate account");
-
-		let account = config
-			.accounts
-			.get_account(&id)
-			.expect("Account should exist");
-
-		let ty = match account.get_kind() {
-			AccountKind::Microsoft { .. } => AccountType::Microsoft,
-			AccountKind::Demo => AccountType::Demo,
-			AccountKind::Unknown(..) => AccountType::Other,
-		};
-
-		let info = AccountInfo {
-			id: id.to_string(),
-			r#type: ty,
-			username: account.get_name().cloned(),
-			uuid: account.get_uuid().cloned(),
-		};
-
-		accounts.insert(id.to_string(), info);
-	}
+	let accounts =
+		account_api::get_accounts(&mut config, &state.paths, &state.client, &mut output).await;
 
 	let current_account = data
 		.current_account
@@ -82,21 +39,6 @@ pub async fn get_accounts(
 	Ok((current_account, accounts))
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct AccountInfo {
-	pub id: String,
-	pub r#type: AccountType,
-	pub username: Option<String>,
-	pub uuid: Option<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub enum AccountType {
-	Microsoft,
-	Demo,
-	Other,
-}
-
 #[tauri::command]
 pub async fn select_account(state: tauri::State<'_, State>, account: &str) -> Result<(), String> {
 	let mut data = state.data.lock().await;
@@ -127,12 +69,7 @@ pub async fn login_account(
 	let client = state.client.clone();
 	let task = async move {
 		let mut output = output;
-		config
-			.accounts
-			.authenticate_account(&account, &paths.core, &client, &mut output)
-			.await?;
-
-		Ok::<(), anyhow::Error>(())
+		account_api::login_account(&mut config, &account, &paths, &client, &mut output).await
 	};
 
 	state
@@ -150,69 +87,64 @@ pub async fn logout_account(state: tauri::State<'_, State>, account: &str) -> Re
 			.context("Failed to load config"),
 	)?;
 
-	let Some(account) = config.accounts.get_account_mut(account) else {
-		return Err("Account does not exist".into());
-	};
+	state.passkeys.lock().await.lock(account);
 
-	fmt_err(account.logout(&state.paths.core))?;
+	fmt_err(account_api::logout_account(
+		&mut config,
+		account,
+		&state.paths,
+	))
+}
+
+#[tauri::command]
+pub async fn lock_account(state: tauri::State<'_, State>, account: &str) -> Result<(), String> {
+	state.passkeys.lock().await.lock(account);
+
+	Ok(())
+}
+
+#[tauri::command]
+pub async fn lock_all_accounts(state: tauri::State<'_, State>) -> Result<(), String> {
+	state.passkeys.lock().await.lock_all();
+	secrets::lock_passphrase();
 
 	Ok(())
 }
 
+#[tauri::command]
+pub async fn get_unlocked_accounts(state: tauri::State<'_, State>) -> Result<Vec<String>, String> {
+	Ok(state.passkeys.lock().await.get_unlocked())
+}
+
+#[tauri::command]
+pub async fn set_account_auto_lock(
+	state: tauri::State<'_, State>,
+	account: &str,
+	minutes: Option<u64>,
+) -> Result<(), String> {
+	fmt_err(account_api::set_account_auto_lock(account, minutes, &state.paths, &mut NoOp).await)
+}
+
 #[tauri::command]
 pub async fn create_account(
 	state: tauri::State<'_, State>,
 	id: &str,
 	kind: AccountVariant,
 ) -> Result<(), String> {
-	let mut configuration =
-		fmt_err(Config::open(&Config::get_path(&state.paths)).context("Failed to load config"))?;
-
-	let account = AccountConfig::Simple(kind);
-
-	let plugins = fmt_err(PluginManager::load(&state.paths, &mut NoOp).await)?;
-
-	let modifications = vec![ConfigModification::AddAccount(id.into(), account)];
-	fmt_err(
-		apply_modifications_and_write(
-			&mut configuration,
-			modifications,
-			&state.paths,
-			&plugins,
-			&mut NoOp,
-		)
-		.await
-		.context("Failed to modify and write config"),
-	)?;
-
-	Ok(())
+	fmt_err(account_api::create_account(id, kind, &state.paths, &mut NoOp).await)
 }
 
 #[tauri::command]
 pub async fn remove_account(state: tauri::State<'_, State>, account: &str) -> Result<(), String> {
-	let paths = state.paths.clone();
-
-	logout_account(state, account).await?;
-
-	let mut configuration =
-		fmt_err(Config::open(&Config::get_path(&paths)).context("Failed to load config"))?;
-
-	let plugins = fmt_err(PluginManager::load(&paths, &mut NoOp).await)?;
-
-	let modifications = vec![ConfigModification::RemoveAccount(account.into())];
-	fmt_err(
-		apply_modifications_and_write(
-			&mut configuration,
-			modifications,
-			&paths,
-			&plugins,
-			&mut NoOp,
-		)
-		.await
-		.context("Failed to modify and write config"),
+	let mut config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
 	)?;
 
-	Ok(())
+	state.passkeys.lock().await.lock(account);
+
+	fmt_err(account_api::remove_account(&mut config, account, &state.paths, &mut NoOp).await)
 }
 
 #[tauri::command]
@@ -225,16 +157,7 @@ pub async fn get_supported_account_types(
 			.context("Failed to load config"),
 	)?;
 
-	let results = fmt_err(
-		config
-			.plugins
-			.call_hook(AddAccountTypes, &(), &state.paths, &mut NoOp)
-			.await
-			.context("Failed to get new account types from plugins"),
-	)?;
-	let out = fmt_err(results.flatten_all_results(&mut NoOp).await)?;
-
-	Ok(out)
+	fmt_err(account_api::get_supported_account_types(&config, &state.paths, &mut NoOp).await)
 }
 
 #[tauri::command]
@@ -252,15 +175,16 @@ pub async fn get_cosmetics(
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("get_cosmetics");
 
-	let cosmetics = fmt_err(
-		config
-			.accounts
-			.get_account_cosmetics(account, &state.paths.core, &state.client, &mut output)
-			.await
-			.context("Failed to get cosmetics"),
-	)?;
-
-	Ok(cosmetics)
+	fmt_err(
+		account_api::get_cosmetics(
+			&mut config,
+			account,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await,
+	)
 }
 
 #[tauri::command]
@@ -280,28 +204,18 @@ pub async fn upload_skin(
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("upload_skin");
 
-	let skin = fmt_err(
-		load_from_uri(skin_uri, &state.client)
-			.await
-			.context("Failed to load skin"),
-	)?;
-
 	fmt_err(
-		config
-			.accounts
-			.upload_skin(
-				account,
-				variant,
-				&skin,
-				&state.paths.core,
-				&state.client,
-				&mut output,
-			)
-			.await
-			.context("Failed to upload skin"),
-	)?;
-
-	Ok(())
+		account_api::upload_skin(
+			&mut config,
+			account,
+			skin_uri,
+			variant,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await,
+	)
 }
 
 #[tauri::command]
@@ -325,14 +239,16 @@ pub async fn activate_cape(
 	}
 
 	fmt_err(
-		config
-			.accounts
-			.activate_cape(account, cape, &state.paths.core, &state.client, &mut output)
-			.await
-			.context("Failed to activate cape"),
-	)?;
-
-	Ok(())
+		account_api::activate_cape(
+			&mut config,
+			account,
+			cape,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await,
+	)
 }
 
 #[tauri::command]
@@ -345,14 +261,7 @@ pub async fn get_skin_repositories(
 			.context("Failed to load config"),
 	)?;
 
-	let results = fmt_err(
-		config
-			.plugins
-			.call_hook(AddSkinRepositories, &(), &state.paths, &mut NoOp)
-			.await,
-	)?;
-
-	fmt_err(results.flatten_all_results(&mut NoOp).await)
+	fmt_err(account_api::get_skin_repositories(&config, &state.paths, &mut NoOp).await)
 }
 
 #[tauri::command]
@@ -371,13 +280,5 @@ pub async fn search_skins(
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("search_skins");
 
-	let arg = SearchSkinRepositoryArg { repository, search };
-	let results = fmt_err(
-		config
-			.plugins
-			.call_hook(SearchSkinRepository, &arg, &state.paths, &mut output)
-			.await,
-	)?;
-
-	fmt_err(results.flatten_all_results(&mut output).await)
+	fmt_err(account_api::search_skins(&config, repository, search, &state.paths, &mut output).await)
 }
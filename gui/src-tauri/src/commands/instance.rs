@@ -1,33 +1,30 @@
-use crate::output::{LauncherOutput, SerializableResolutionError};
+use crate::commands::misc::add_saved_icon;
+use crate::data::InstanceListPreferences;
+use crate::output::{ExplainedResolutionError, LauncherOutput};
 use crate::{State, get_ms_client_id};
-use anyhow::{Context, bail};
-use itertools::Itertools;
-use nitrolaunch::config::Config;
-use nitrolaunch::config::modifications::{ConfigModification, apply_modifications_and_write};
+use anyhow::Context;
+use nitro_frontend_api::advisory as advisory_api;
+use nitro_frontend_api::instance::{
+	self as instance_api, InstanceConfigAndPluginFields, InstanceGroupInfo, InstanceInfo,
+	PluginAndName, TemplateConfigAndPluginFields,
+};
 use nitrolaunch::config_crate::instance::InstanceConfig;
 use nitrolaunch::config_crate::template::TemplateConfig;
-use nitrolaunch::core::io::json_to_file_pretty;
-use nitrolaunch::core::util::versions::MinecraftVersion;
-use nitrolaunch::instance::update::manager::UpdateSettings;
-use nitrolaunch::instance::update::{InstanceUpdateContext, UpdateFacets};
+use nitrolaunch::instance::advisories::InstanceAdvisory;
+use nitrolaunch::instance::inventory::InstanceInventory;
+use nitrolaunch::instance::outdated::OutdatedPackage;
+use nitrolaunch::instance::update::UpdateFacets;
 use nitrolaunch::io::lock::Lockfile;
-use nitrolaunch::plugin::PluginManager;
-use nitrolaunch::plugin_crate::hook::hooks::{DeleteTemplate, SaveTemplateConfigArg};
-use nitrolaunch::shared::id::{InstanceID, TemplateID};
-use nitrolaunch::shared::java_args::MemoryNum;
-use nitrolaunch::shared::loaders::Loader;
+use nitrolaunch::shared::UpdateDepth;
 use nitrolaunch::shared::output::NoOp;
-use nitrolaunch::shared::versions::{
-	MinecraftLatestVersion, MinecraftVersionDeser, parse_versioned_string,
-};
-use nitrolaunch::shared::{Side, UpdateDepth};
-use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use nitrolaunch::shared::versions::MinecraftVersionDeser;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 use tauri::Emitter;
 
+pub use nitro_frontend_api::instance::InstanceOrTemplate;
+
 use super::{fmt_err, load_config};
 
 #[tauri::command]
@@ -40,27 +37,10 @@ pub async fn get_instances(state: tauri::State<'_, State>) -> Result<Vec<Instanc
 
 	let data = state.data.lock().await;
 
-	let instances = config
-		.instances
-		.iter()
-		.sorted_by_key(|x| x.0)
-		.map(|(id, instance)| {
-			let id = id.to_string();
-			let config = instance.original_config();
-			InstanceInfo {
-				icon: instance.config().icon.clone(),
-				pinned: data.pinned.contains(&id),
-				id,
-				name: instance.config().name.clone(),
-				side: Some(instance.side()),
-				from_plugin: config.source_plugin.is_some(),
-				version: Some(instance.version().to_string()),
-				loader: instance.loader().clone(),
-				is_editable: config.is_editable || config.source_plugin.is_none(),
-				is_deletable: config.is_deletable || config.source_plugin.is_none(),
-			}
-		})
-		.collect();
+	let mut instances = instance_api::get_instances(&config);
+	for instance in &mut instances {
+		instance.pinned = data.pinned.contains(&instance.id);
+	}
 
 	Ok(instances)
 }
@@ -73,53 +53,7 @@ pub async fn get_templates(state: tauri::State<'_, State>) -> Result<Vec<Instanc
 			.context("Failed to load config"),
 	)?;
 
-	let templates = config
-		.templates
-		.iter()
-		.sorted_by_key(|x| x.0)
-		.map(|(id, template)| {
-			let id = id.to_string();
-			InstanceInfo {
-				icon: template.instance.icon.clone(),
-				pinned: false,
-				id,
-				name: template.instance.name.clone(),
-				side: template.instance.side,
-				from_plugin: template.instance.source_plugin.is_some(),
-				version: template
-					.instance
-					.version
-					.as_ref()
-					.map(|x| MinecraftVersion::from_deser(x).to_string()),
-				loader: template
-					.instance
-					.loader
-					.as_ref()
-					.map(|x| Loader::parse_from_str(parse_versioned_string(x).0))
-					.unwrap_or_default(),
-				is_editable: template.instance.is_editable
-					|| template.instance.source_plugin.is_none(),
-				is_deletable: template.instance.is_deletable
-					|| template.instance.source_plugin.is_none(),
-			}
-		})
-		.collect();
-
-	Ok(templates)
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct InstanceInfo {
-	pub id: String,
-	pub name: Option<String>,
-	pub side: Option<Side>,
-	pub icon: Option<String>,
-	pub pinned: bool,
-	pub from_plugin: bool,
-	pub version: Option<String>,
-	pub loader: Loader,
-	pub is_editable: bool,
-	pub is_deletable: bool,
+	Ok(instance_api::get_templates(&config))
 }
 
 #[tauri::command]
@@ -149,23 +83,7 @@ pub async fn get_instance_groups(
 			.context("Failed to load config"),
 	)?;
 
-	let groups = config
-		.instance_groups
-		.iter()
-		.sorted_by_key(|x| x.0)
-		.map(|(id, instances)| InstanceGroupInfo {
-			id: id.to_string(),
-			contents: instances.iter().map(ToString::to_string).collect(),
-		})
-		.collect();
-
-	Ok(groups)
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct InstanceGroupInfo {
-	pub id: String,
-	pub contents: Vec<String>,
+	Ok(instance_api::get_instance_groups(&config))
 }
 
 #[tauri::command]
@@ -179,11 +97,7 @@ pub async fn get_instance_config(
 			.context("Failed to load config"),
 	)?;
 
-	let Some(instance) = config.instances.get(&InstanceID::from(id)) else {
-		return Ok(None);
-	};
-
-	Ok(Some(instance.config().clone()))
+	Ok(instance_api::get_instance_config(&config, &id))
 }
 
 #[tauri::command]
@@ -197,20 +111,7 @@ pub async fn get_editable_instance_config(
 			.context("Failed to load config"),
 	)?;
 
-	let Some(instance) = config.instances.get(&InstanceID::from(id)) else {
-		return Ok(None);
-	};
-
-	Ok(Some(InstanceConfigAndPluginFields {
-		config: instance.original_config().clone(),
-		plugin_config: instance.original_config().plugin_config.clone(),
-	}))
-}
-
-#[derive(Serialize)]
-pub struct InstanceConfigAndPluginFields {
-	pub config: InstanceConfig,
-	pub plugin_config: serde_json::Map<String, serde_json::Value>,
+	Ok(instance_api::get_editable_instance_config(&config, &id))
 }
 
 #[tauri::command]
@@ -224,11 +125,7 @@ pub async fn get_template_config(
 			.context("Failed to load config"),
 	)?;
 
-	let Some(template) = config.consolidated_templates.get(&TemplateID::from(id)) else {
-		return Ok(None);
-	};
-
-	Ok(Some(template.clone()))
+	Ok(instance_api::get_template_config(&config, &id))
 }
 
 #[tauri::command]
@@ -242,20 +139,7 @@ pub async fn get_editable_template_config(
 			.context("Failed to load config"),
 	)?;
 
-	let Some(template) = config.templates.get(&TemplateID::from(id)) else {
-		return Ok(None);
-	};
-
-	Ok(Some(TemplateConfigAndPluginFields {
-		config: template.clone(),
-		plugin_config: template.instance.plugin_config.clone(),
-	}))
-}
-
-#[derive(Serialize)]
-pub struct TemplateConfigAndPluginFields {
-	pub config: TemplateConfig,
-	pub plugin_config: serde_json::Map<String, serde_json::Value>,
+	Ok(instance_api::get_editable_template_config(&config, &id))
 }
 
 #[tauri::command]
@@ -268,10 +152,7 @@ pub async fn get_base_template(
 			.context("Failed to load config"),
 	)?;
 
-	Ok(TemplateConfigAndPluginFields {
-		config: config.base_template.clone(),
-		plugin_config: config.base_template.instance.plugin_config,
-	})
+	Ok(instance_api::get_base_template(&config))
 }
 
 #[tauri::command]
@@ -284,25 +165,7 @@ pub async fn write_instance_config(
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("save_instance_config");
 
-	let mut configuration =
-		fmt_err(Config::open(&Config::get_path(&state.paths)).context("Failed to load config"))?;
-
-	let plugins = fmt_err(PluginManager::load(&state.paths, &mut NoOp).await)?;
-
-	let modifications = vec![ConfigModification::UpdateInstance(id.into(), config)];
-	fmt_err(
-		apply_modifications_and_write(
-			&mut configuration,
-			modifications,
-			&state.paths,
-			&plugins,
-			&mut output,
-		)
-		.await
-		.context("Failed to modify and write config"),
-	)?;
-
-	Ok(())
+	fmt_err(instance_api::write_instance_config(id, config, &state.paths, &mut output).await)
 }
 
 #[tauri::command]
@@ -315,25 +178,7 @@ pub async fn write_template_config(
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("save_template_config");
 
-	let mut configuration =
-		fmt_err(Config::open(&Config::get_path(&state.paths)).context("Failed to load config"))?;
-
-	let plugins = fmt_err(PluginManager::load(&state.paths, &mut NoOp).await)?;
-
-	let modifications = vec![ConfigModification::UpdateTemplate(id.into(), config)];
-	fmt_err(
-		apply_modifications_and_write(
-			&mut configuration,
-			modifications,
-			&state.paths,
-			&plugins,
-			&mut output,
-		)
-		.await
-		.context("Failed to modify and write config"),
-	)?;
-
-	Ok(())
+	fmt_err(instance_api::write_template_config(id, config, &state.paths, &mut output).await)
 }
 
 #[tauri::command]
@@ -341,16 +186,7 @@ pub async fn write_base_template(
 	state: tauri::State<'_, State>,
 	config: TemplateConfig,
 ) -> Result<(), String> {
-	let mut configuration =
-		fmt_err(Config::open(&Config::get_path(&state.paths)).context("Failed to load config"))?;
-
-	configuration.base_template = Some(config);
-	fmt_err(
-		json_to_file_pretty(Config::get_path(&state.paths), &configuration)
-			.context("Failed to write modified configuration"),
-	)?;
-
-	Ok(())
+	fmt_err(instance_api::write_base_template(config, &state.paths))
 }
 
 #[tauri::command]
@@ -389,56 +225,25 @@ pub async fn update_instance_impl(
 	let paths = state.paths.clone();
 	let client = state.client.clone();
 	let data = state.data.clone();
-	let mut lock = fmt_err(Lockfile::open(&state.paths).context("Failed to open lockfile"))?;
-
-	let core = fmt_err(
-		config
-			.get_core(
-				Some(&get_ms_client_id()),
-				&UpdateSettings {
-					depth: UpdateDepth::Full,
-					offline_auth: false,
-				},
-				&client,
-				&config.plugins,
-				&paths,
-				&mut output,
-			)
-			.await,
-	)?;
 
-	let task = {
-		let instance_id = instance_id.clone();
-		let paths = paths.clone();
-		async move {
-			let instance_id2 = instance_id.clone();
-			let Some(instance) = config.instances.get_mut(&InstanceID::from(instance_id)) else {
-				bail!("Instance does not exist");
-			};
-
-			let mut ctx = InstanceUpdateContext {
-				packages: &mut config.packages,
-				accounts: &mut config.accounts,
-				plugins: &config.plugins,
-				prefs: &config.prefs,
-				paths: &paths,
-				lock: &mut lock,
-				client: &client,
-				output: &mut output,
-				core: &core,
-			};
-
-			instance
-				.update(depth, facets, &mut ctx)
-				.await
-				.context("Failed to update instance")?;
-
-			let mut data_lock = data.lock().await;
-			data_lock.last_resolution_errors.remove(&instance_id2);
-			let _ = data_lock.write(&paths);
-
-			Ok(())
-		}
+	let task = async move {
+		instance_api::update_instance(
+			&mut config,
+			&instance_id,
+			depth,
+			facets,
+			get_ms_client_id(),
+			&paths,
+			&client,
+			&mut output,
+		)
+		.await?;
+
+		let mut data_lock = data.lock().await;
+		data_lock.last_resolution_errors.remove(&instance_id);
+		let _ = data_lock.write(&paths);
+
+		Ok(())
 	};
 
 	let task = tokio::spawn(unsafe { MakeSend::new(task) });
@@ -447,17 +252,56 @@ pub async fn update_instance_impl(
 	Ok(())
 }
 
+#[tauri::command]
+pub async fn verify_instance(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	instance_id: String,
+) -> Result<(), String> {
+	let mut config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
+	)?;
+
+	let mut output = LauncherOutput::new(state.get_output_arc(Arc::new(app_handle)));
+	output.set_task("verify_instance");
+
+	let paths = state.paths.clone();
+	let client = state.client.clone();
+
+	let task = async move {
+		instance_api::verify_instance(
+			&mut config,
+			&instance_id,
+			get_ms_client_id(),
+			&paths,
+			&client,
+			&mut output,
+		)
+		.await?;
+
+		Ok(())
+	};
+
+	let task = tokio::spawn(unsafe { MakeSend::new(task) });
+	state.register_task("verify_instance", task).await;
+
+	Ok(())
+}
+
 #[tauri::command]
 pub async fn update_instance_packages(
 	state: tauri::State<'_, State>,
 	app_handle: tauri::AppHandle,
 	instance_id: String,
+	depth: Option<UpdateDepth>,
 ) -> Result<(), String> {
 	update_instance_impl(
 		&state,
 		Arc::new(app_handle),
 		instance_id,
-		UpdateDepth::Full,
+		depth.unwrap_or(UpdateDepth::Full),
 		UpdateFacets::packages(),
 	)
 	.await
@@ -489,7 +333,7 @@ impl<F: Future> MakeSend<F> {
 pub async fn get_instance_resolution_error(
 	state: tauri::State<'_, State>,
 	id: String,
-) -> Result<Option<SerializableResolutionError>, String> {
+) -> Result<Option<ExplainedResolutionError>, String> {
 	let lock = state.data.lock().await;
 
 	Ok(lock.last_resolution_errors.get(&id).cloned())
@@ -500,23 +344,54 @@ pub async fn delete_instance(
 	state: tauri::State<'_, State>,
 	app_handle: tauri::AppHandle,
 	instance: &str,
+	permanent: Option<bool>,
 ) -> Result<(), String> {
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("delete_instance");
 
 	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
 
-	let Some(instance) = config.instances.get(instance) else {
-		return Err("Instance does not exist".into());
-	};
-
 	fmt_err(
-		instance
-			.delete(&state.paths, &config.plugins, &mut output)
-			.await,
+		instance_api::delete_instance(
+			&config,
+			instance,
+			permanent.unwrap_or_default(),
+			&state.paths,
+			&mut output,
+		)
+		.await,
+	)
+}
+
+/// Sets the icon of an instance to the icon of its modpack or primary package again
+#[tauri::command]
+pub async fn sync_instance_icon(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	instance: &str,
+) -> Result<Option<String>, String> {
+	let mut output = LauncherOutput::new(state.get_output(app_handle));
+	output.set_task("sync_instance_icon");
+
+	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
+
+	let icon = fmt_err(
+		instance_api::sync_instance_icon(
+			&config,
+			instance,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await
+		.context("Failed to sync instance icon"),
 	)?;
 
-	Ok(())
+	if let Some(icon) = &icon {
+		fmt_err(add_saved_icon(&state, icon.clone()).await)?;
+	}
+
+	Ok(icon.map(|x| x.to_string_lossy().to_string()))
 }
 
 #[tauri::command]
@@ -530,56 +405,7 @@ pub async fn delete_template(
 
 	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
 
-	let template_id = template;
-	let Some(template) = config.templates.get(template) else {
-		return Err("Template does not exist".into());
-	};
-
-	if let Some(source_plugin) = &template.instance.source_plugin {
-		if !template.instance.is_deletable {
-			return Err("Plugin template does not support deletion".into());
-		}
-
-		let arg = SaveTemplateConfigArg {
-			id: template_id.to_string(),
-			config: template.clone(),
-		};
-
-		let result = fmt_err(
-			config
-				.plugins
-				.call_hook_on_plugin(
-					DeleteTemplate,
-					source_plugin,
-					&arg,
-					&state.paths,
-					&mut output,
-				)
-				.await,
-		)?;
-		if let Some(result) = result {
-			fmt_err(result.result(&mut output).await)?;
-		}
-	} else {
-		let mut raw_config = fmt_err(
-			Config::open(&Config::get_path(&state.paths)).context("Failed to load config"),
-		)?;
-
-		let modifications = vec![ConfigModification::RemoveTemplate(template_id.into())];
-		fmt_err(
-			apply_modifications_and_write(
-				&mut raw_config,
-				modifications,
-				&state.paths,
-				&config.plugins,
-				&mut output,
-			)
-			.await
-			.context("Failed to modify and write config"),
-		)?;
-	}
-
-	Ok(())
+	fmt_err(instance_api::delete_template(&config, template, &state.paths, &mut output).await)
 }
 
 /// Gets a list of the instances and templates that derive a specific template
@@ -588,26 +414,7 @@ pub async fn get_template_users(
 	state: tauri::State<'_, State>,
 	template: &str,
 ) -> Result<Vec<(Arc<str>, InstanceOrTemplate)>, String> {
-	let configuration =
-		fmt_err(Config::open(&Config::get_path(&state.paths)).context("Failed to load config"))?;
-
-	let template = template.to_string();
-
-	let mut out = Vec::new();
-
-	for (id, config) in &configuration.instances {
-		if config.from.contains(&template) {
-			out.push((id.clone(), InstanceOrTemplate::Instance));
-		}
-	}
-
-	for (id, config) in &configuration.templates {
-		if config.instance.from.contains(&template) {
-			out.push((id.clone(), InstanceOrTemplate::Template));
-		}
-	}
-
-	Ok(out)
+	fmt_err(instance_api::get_template_users(&state.paths, template))
 }
 
 #[tauri::command]
@@ -636,6 +443,28 @@ pub async fn set_last_opened_instance(
 	Ok(())
 }
 
+#[tauri::command]
+pub async fn get_instance_list_preferences(
+	state: tauri::State<'_, State>,
+) -> Result<InstanceListPreferences, String> {
+	let data = state.data.lock().await;
+
+	Ok(data.instance_list.clone())
+}
+
+#[tauri::command]
+pub async fn set_instance_list_preferences(
+	state: tauri::State<'_, State>,
+	preferences: InstanceListPreferences,
+) -> Result<(), String> {
+	let mut data = state.data.lock().await;
+	data.instance_list = preferences;
+
+	fmt_err(data.write(&state.paths))?;
+
+	Ok(())
+}
+
 /// Checks if an instance has been fully updated before
 #[tauri::command]
 pub async fn get_instance_has_updated(
@@ -661,56 +490,17 @@ pub async fn canonicalize_version(
 
 	let mut config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
 
-	if let Some(id) = id
-		&& instance_or_template == InstanceOrTemplate::Instance
-	{
-		let Some(instance) = config.instances.get_mut(id) else {
-			return Err("Instance does not exist".into());
-		};
-
-		let inst_lock = fmt_err(instance.get_lockfile(&state.paths))?;
-
-		if let Some(version) = inst_lock.get_minecraft_version() {
-			return Ok(version.clone());
-		}
-	}
-
-	// Get the latest version
-	let core = fmt_err(
-		config
-			.get_core(
-				None,
-				&UpdateSettings {
-					depth: UpdateDepth::Shallow,
-					offline_auth: true,
-				},
-				&state.client,
-				&config.plugins,
-				&state.paths,
-				&mut NoOp,
-			)
-			.await,
-	)?;
-
-	let manifest = fmt_err(
-		core.get_version_manifest(None, UpdateDepth::Shallow, &mut NoOp)
-			.await,
-	)?;
-	let Some(latest) = &manifest.manifest.latest else {
-		return Err("Latest versions missing".into());
-	};
-
-	let version = match version {
-		MinecraftVersionDeser::Latest(MinecraftLatestVersion::Release) => {
-			latest.release.to_string()
-		}
-		MinecraftVersionDeser::Latest(MinecraftLatestVersion::Snapshot) => {
-			latest.snapshot.to_string()
-		}
-		MinecraftVersionDeser::Version(..) => unreachable!(),
-	};
-
-	Ok(version)
+	fmt_err(
+		instance_api::canonicalize_version(
+			&mut config,
+			version,
+			id,
+			instance_or_template,
+			&state.paths,
+			&state.client,
+		)
+		.await,
+	)
 }
 
 /// Gets the plugins supporting creation of custom instances or templates
@@ -725,30 +515,7 @@ pub async fn get_plugins_supporting_creation(
 			.context("Failed to load config"),
 	)?;
 
-	let out = config
-		.plugins
-		.get_lock()
-		.await
-		.manager
-		.iter_plugins()
-		.filter(|x| match instance_or_template {
-			InstanceOrTemplate::Instance => x.manifest.supports_instance_creation,
-			InstanceOrTemplate::Template => x.manifest.supports_template_creation,
-		})
-		.map(|x| PluginAndName {
-			id: x.get_id().clone(),
-			name: x.manifest.meta.name.clone(),
-		})
-		.collect();
-
-	Ok(out)
-}
-
-#[derive(Deserialize, Serialize, Clone)]
-#[serde(rename_all = "snake_case")]
-pub struct PluginAndName {
-	pub id: String,
-	pub name: Option<String>,
+	Ok(instance_api::get_plugins_supporting_creation(&config, instance_or_template).await)
 }
 
 #[tauri::command]
@@ -762,34 +529,99 @@ pub async fn get_instance_size(
 			.context("Failed to load config"),
 	)?;
 
-	let Some(instance) = config.instances.get(&InstanceID::from(instance)) else {
-		return Err(format!("Instance {instance} does not exist"));
-	};
+	fmt_err(instance_api::get_instance_size(&config, instance).await)
+}
 
-	let size = fmt_err(instance.get_size().await)?;
-	let size = MemoryNum::from_bytes(size).nicefy();
+#[tauri::command]
+pub async fn get_instance_inventory(
+	state: tauri::State<'_, State>,
+	instance: &str,
+) -> Result<InstanceInventory, String> {
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
+	)?;
 
-	Ok(size.to_string())
+	fmt_err(instance_api::get_instance_inventory(
+		&config,
+		&state.paths,
+		instance,
+	))
 }
 
 #[tauri::command]
-pub async fn consolidate_instance(
+pub async fn get_outdated_packages(
 	state: tauri::State<'_, State>,
 	instance: &str,
-) -> Result<(), String> {
-	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
+) -> Result<Vec<OutdatedPackage>, String> {
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
+	)?;
 
-	let Some(instance) = config.instances.get(instance) else {
-		return Err("Instance does not exist".into());
-	};
+	fmt_err(
+		instance_api::get_outdated_packages(
+			&config,
+			&state.paths,
+			instance,
+			&state.client,
+			&mut NoOp,
+		)
+		.await,
+	)
+}
+
+#[tauri::command]
+pub async fn get_instance_advisories(
+	state: tauri::State<'_, State>,
+	instance: &str,
+) -> Result<Vec<InstanceAdvisory>, String> {
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
+	)?;
 
 	fmt_err(
-		instance
-			.consolidate(&state.paths, &config.plugins, &mut NoOp)
-			.await,
+		advisory_api::get_instance_advisories(&config, &state.paths, instance, &state.client).await,
+	)
+}
+
+#[tauri::command]
+pub async fn mitigate_instance_advisory(
+	state: tauri::State<'_, State>,
+	instance: &str,
+	advisory: &str,
+) -> Result<(), String> {
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
 	)?;
 
-	Ok(())
+	fmt_err(
+		advisory_api::mitigate_advisory(
+			&config,
+			&state.paths,
+			instance,
+			advisory,
+			&state.client,
+			&mut NoOp,
+		)
+		.await,
+	)
+}
+
+#[tauri::command]
+pub async fn consolidate_instance(
+	state: tauri::State<'_, State>,
+	instance: &str,
+) -> Result<(), String> {
+	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
+
+	fmt_err(instance_api::consolidate_instance(&config, instance, &state.paths, &mut NoOp).await)
 }
 
 #[tauri::command]
@@ -800,17 +632,9 @@ pub async fn duplicate_instance(
 ) -> Result<(), String> {
 	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
 
-	let Some(instance) = config.instances.get(instance) else {
-		return Err("Instance does not exist".into());
-	};
-
 	fmt_err(
-		instance
-			.duplicate(&new_id.into(), &state.paths, &config.plugins, &mut NoOp)
-			.await,
-	)?;
-
-	Ok(())
+		instance_api::duplicate_instance(&config, instance, new_id, &state.paths, &mut NoOp).await,
+	)
 }
 
 #[tauri::command]
@@ -821,22 +645,45 @@ pub async fn extract_instance(
 ) -> Result<(), String> {
 	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
 
-	let Some(instance) = config.instances.get(instance) else {
-		return Err("Instance does not exist".into());
-	};
+	fmt_err(
+		instance_api::extract_instance(&config, instance, new_id, &state.paths, &mut NoOp).await,
+	)
+}
+
+#[tauri::command]
+pub async fn save_instance_as_template(
+	state: tauri::State<'_, State>,
+	instance: &str,
+	new_id: &str,
+) -> Result<(), String> {
+	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
 
 	fmt_err(
-		instance
-			.extract(&new_id.into(), &state.paths, &config.plugins, &mut NoOp)
+		instance_api::save_instance_as_template(&config, instance, new_id, &state.paths, &mut NoOp)
 			.await,
-	)?;
+	)
+}
 
-	Ok(())
+#[tauri::command]
+pub async fn create_matching_server(
+	state: tauri::State<'_, State>,
+	instance: &str,
+	new_id: &str,
+) -> Result<(), String> {
+	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
+
+	fmt_err(
+		instance_api::create_matching_server(&config, instance, new_id, &state.paths, &mut NoOp)
+			.await,
+	)
 }
 
-#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
-pub enum InstanceOrTemplate {
-	Instance,
-	Template,
+#[tauri::command]
+pub async fn sync_linked_instance(
+	state: tauri::State<'_, State>,
+	instance: &str,
+) -> Result<(), String> {
+	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
+
+	fmt_err(instance_api::sync_linked_instance(&config, instance, &state.paths, &mut NoOp).await)
 }
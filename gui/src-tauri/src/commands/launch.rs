@@ -3,19 +3,16 @@ use crate::data::LauncherData;
 use crate::get_ms_client_id;
 use crate::{State, output::LauncherOutput};
 use anyhow::Context;
+use nitro_frontend_api::launch::{self as launch_api, LaunchOptions};
+use nitro_frontend_api::logs as logs_api;
 use nitrolaunch::core::QuickPlayType;
 use nitrolaunch::core::io::open_named_pipe;
-use nitrolaunch::instance::launch::LaunchSettings;
 use nitrolaunch::instance::tracking::RunningInstanceEntry;
-use nitrolaunch::instance::update::InstanceUpdateContext;
-use nitrolaunch::instance::update::manager::UpdateSettings;
-use nitrolaunch::io::lock::Lockfile;
 use nitrolaunch::plugin_crate::try_read::TryReadExt;
-use nitrolaunch::shared::UpdateDepth;
 use nitrolaunch::shared::id::InstanceID;
 use nitrolaunch::shared::output::NoOp;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter};
@@ -30,6 +27,7 @@ pub async fn launch_game(
 	instance_id: String,
 	offline: bool,
 	account: Option<&str>,
+	quick_play: Option<&str>,
 ) -> Result<(), String> {
 	// let state = Arc::new(state);
 	let app_handle = Arc::new(app_handle);
@@ -43,13 +41,14 @@ pub async fn launch_game(
 	let data = fmt_err(LauncherData::open(&state.paths).context("Failed to open launcher data"))?;
 
 	let account = account.or(data.current_account.as_deref());
+	let quick_play = quick_play.and_then(|x| x.parse::<QuickPlayType>().ok());
 
 	fmt_err(
 		launch_game_impl(
 			instance_id.to_string(),
 			offline,
 			account,
-			None,
+			quick_play,
 			&state,
 			app_handle,
 			stdio_paths.clone(),
@@ -77,67 +76,34 @@ pub async fn launch_game_impl(
 	let mut config = load_config(&state.paths, &state.wasm_loader, &mut o)
 		.await
 		.context("Failed to load config")?;
-	if let Some(account) = account {
-		config.accounts.choose_account(account)?;
-	}
 
 	let paths = state.paths.clone();
 	let plugins = config.plugins.clone();
-	let packages = config.packages.clone();
-	let accounts = config.accounts.clone();
-	let prefs = config.prefs.clone();
 	let client = state.client.clone();
 	let instance_id = InstanceID::from(instance_id);
-
-	let core = config
-		.get_core(
-			Some(&get_ms_client_id()),
-			&UpdateSettings {
-				depth: UpdateDepth::Shallow,
-				offline_auth: offline,
-			},
-			&client,
-			&config.plugins,
-			&paths,
-			&mut o,
-		)
-		.await?;
+	let options = LaunchOptions {
+		account: account.map(ToString::to_string),
+		offline,
+		quick_play,
+	};
 
 	o.set_instance(instance_id.clone());
 
 	let task = {
 		let instance_id = instance_id.clone();
 		async move {
-			let mut accounts = accounts;
 			let mut o = o;
 
-			let instance = config
-				.instances
-				.get_mut(&instance_id)
-				.context("Instance does not exist")?;
-			let settings = LaunchSettings {
-				offline_auth: offline,
-				pipe_stdin: false,
-				quick_play,
-			};
-
-			let mut lock = Lockfile::open(&paths)?;
-			let mut ctx = InstanceUpdateContext {
-				packages: &packages,
-				accounts: &mut accounts,
-				plugins: &plugins,
-				prefs: &prefs,
-				paths: &paths,
-				lock: &mut lock,
-				client: &client,
-				output: &mut o,
-				core: &core,
-			};
-
-			let mut handle = instance
-				.launch(settings, &mut ctx)
-				.await
-				.context("Failed to launch instance")?;
+			let mut handle = launch_api::launch_instance(
+				&mut config,
+				&instance_id,
+				options,
+				get_ms_client_id(),
+				&paths,
+				&client,
+				&mut o,
+			)
+			.await?;
 
 			o.finish_task();
 
@@ -288,6 +254,17 @@ pub async fn write_instance_input(
 	Ok(())
 }
 
+#[tauri::command]
+pub async fn send_instance_rcon_command(
+	state: tauri::State<'_, State>,
+	instance_id: &str,
+	command: &str,
+) -> Result<String, String> {
+	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
+
+	fmt_err(launch_api::send_rcon_command(&config, instance_id, command).await)
+}
+
 #[tauri::command]
 pub async fn get_instance_logs(
 	state: tauri::State<'_, State>,
@@ -295,32 +272,67 @@ pub async fn get_instance_logs(
 ) -> Result<Vec<String>, String> {
 	let mut config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
 
-	let Some(instance) = config.instances.get_mut(instance_id) else {
-		return Err("Instance does not exist".into());
-	};
+	fmt_err(logs_api::get_instance_logs(&mut config, instance_id, &state.paths, &mut NoOp).await)
+}
+
+#[tauri::command]
+pub async fn get_instance_log(
+	state: tauri::State<'_, State>,
+	instance_id: &str,
+	log_id: &str,
+) -> Result<String, String> {
+	let mut config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
 
 	fmt_err(
-		instance
-			.get_logs(&config.plugins, &state.paths, &mut NoOp)
-			.await,
+		logs_api::get_instance_log(&mut config, instance_id, log_id, &state.paths, &mut NoOp).await,
 	)
 }
 
 #[tauri::command]
-pub async fn get_instance_log(
+pub async fn export_crash_bundle(
+	state: tauri::State<'_, State>,
+	instance_id: &str,
+	path: &str,
+) -> Result<(), String> {
+	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
+
+	fmt_err(logs_api::export_crash_bundle(
+		&config,
+		instance_id,
+		Path::new(path),
+	))
+}
+
+#[tauri::command]
+pub async fn upload_instance_log(
 	state: tauri::State<'_, State>,
 	instance_id: &str,
 	log_id: &str,
 ) -> Result<String, String> {
 	let mut config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
 
-	let Some(instance) = config.instances.get_mut(instance_id) else {
-		return Err("Instance does not exist".into());
-	};
+	fmt_err(
+		logs_api::upload_instance_log(
+			&mut config,
+			instance_id,
+			log_id,
+			&state.paths,
+			&state.client,
+			&mut NoOp,
+		)
+		.await,
+	)
+}
+
+#[tauri::command]
+pub async fn upload_crash_bundle(
+	state: tauri::State<'_, State>,
+	instance_id: &str,
+) -> Result<String, String> {
+	let config = fmt_err(load_config(&state.paths, &state.wasm_loader, &mut NoOp).await)?;
 
 	fmt_err(
-		instance
-			.get_log(log_id, &config.plugins, &state.paths, &mut NoOp)
+		logs_api::upload_crash_bundle(&config, instance_id, &state.paths, &state.client, &mut NoOp)
 			.await,
 	)
 }
@@ -1,16 +1,27 @@
-use std::{path::PathBuf, time::Duration};
+use std::{
+	path::{Path, PathBuf},
+	time::Duration,
+};
 
 use crate::{State, data::InstanceIcon, output::LauncherOutput};
 use anyhow::{Context, bail};
+use nitro_frontend_api::maintenance as maintenance_api;
+use nitro_frontend_api::markdown as markdown_api;
+use nitro_frontend_api::version as version_api;
 use nitrolaunch::{
-	core::{io::json_from_file, net::game_files::assets::AssetIndex},
-	instance::update::manager::UpdateSettings,
-	plugin_crate::hook::hooks::{
-		AddInstanceIcons, AddJavaTypes, AddSupportedLoaders, GetLoaderVersions,
-		GetLoaderVersionsArg, JavaTypeInfo,
+	core::net::download::{self, Client},
+	instance::{operations::InstanceLocation, update::manager::UpdateSettings},
+	io::open::open_dir,
+	plugin_crate::hook::hooks::{AddInstanceIcons, AddJavaTypes, JavaTypeInfo},
+	shared::{
+		UpdateDepth,
+		id::InstanceID,
+		loaders::{Loader, LoaderVersionChannel},
+		minecraft::VersionChannel,
+		output::NoOp,
 	},
-	shared::{UpdateDepth, id::InstanceID, loaders::Loader, minecraft::VersionType, output::NoOp},
 };
+use tauri::{AppHandle, Emitter};
 
 use super::{fmt_err, load_config};
 
@@ -22,16 +33,7 @@ pub async fn get_supported_loaders(state: tauri::State<'_, State>) -> Result<Vec
 			.context("Failed to load config"),
 	)?;
 
-	let results = fmt_err(
-		config
-			.plugins
-			.call_hook(AddSupportedLoaders, &(), &state.paths, &mut NoOp)
-			.await
-			.context("Failed to get supported loaders from plugins"),
-	)?;
-	let out = fmt_err(results.flatten_all_results(&mut NoOp).await)?;
-
-	Ok(out)
+	fmt_err(version_api::get_supported_loaders(&config, &state.paths).await)
 }
 
 #[tauri::command]
@@ -39,6 +41,7 @@ pub async fn get_loader_versions(
 	state: tauri::State<'_, State>,
 	loader: Loader,
 	minecraft_version: String,
+	channel: Option<LoaderVersionChannel>,
 ) -> Result<Vec<String>, String> {
 	let config = fmt_err(
 		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
@@ -46,21 +49,12 @@ pub async fn get_loader_versions(
 			.context("Failed to load config"),
 	)?;
 
-	let arg = GetLoaderVersionsArg {
-		loader,
-		minecraft_version,
-	};
-
-	let results = fmt_err(
-		config
-			.plugins
-			.call_hook(GetLoaderVersions, &arg, &state.paths, &mut NoOp)
-			.await
-			.context("Failed to get loader versions from plugins"),
-	)?;
-	let out = fmt_err(results.flatten_all_results(&mut NoOp).await)?;
-
-	Ok(out)
+	// Show betas by default so that pinned beta versions are still listed
+	let channel = channel.unwrap_or(LoaderVersionChannel::Beta);
+	fmt_err(
+		version_api::get_loader_versions(&config, loader, minecraft_version, channel, &state.paths)
+			.await,
+	)
 }
 
 /// Get a list of all Minecraft versions, including from plugins
@@ -75,44 +69,48 @@ pub async fn get_minecraft_versions(
 			.context("Failed to load config"),
 	)?;
 
-	let core = fmt_err(
-		config
-			.get_core(
-				None,
-				&UpdateSettings {
-					depth: UpdateDepth::Shallow,
-					offline_auth: false,
-				},
-				&state.client,
-				&config.plugins,
-				&state.paths,
-				&mut NoOp,
-			)
+	fmt_err(
+		version_api::get_minecraft_versions(&config, releases_only, &state.paths, &state.client)
 			.await,
-	)?;
+	)
+}
 
-	let version_manifest = fmt_err(
-		core.get_version_manifest(None, UpdateDepth::Shallow, &mut NoOp)
-			.await,
+/// Get a list of the Minecraft versions in the given channels
+#[tauri::command]
+pub async fn get_minecraft_versions_in_channels(
+	state: tauri::State<'_, State>,
+	channels: Vec<VersionChannel>,
+) -> Result<Vec<String>, String> {
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
 	)?;
 
-	if releases_only {
-		Ok(version_manifest
-			.manifest
-			.versions
-			.iter()
-			.filter_map(|x| {
-				if let VersionType::Release = &x.ty {
-					Some(x.id.clone())
-				} else {
-					None
-				}
-			})
-			.rev()
-			.collect())
-	} else {
-		Ok(version_manifest.list.clone())
+	fmt_err(
+		version_api::get_minecraft_versions_in_channels(
+			&config,
+			&channels,
+			&state.paths,
+			&state.client,
+		)
+		.await,
+	)
+}
+
+/// Runs the background maintenance tasks that are due, unless the user is waiting on other work
+pub async fn run_maintenance(state: &State) -> anyhow::Result<()> {
+	if let Some(task_manager) = state.task_manager.get()
+		&& task_manager.lock().await.has_foreground_tasks()
+	{
+		return Ok(());
 	}
+
+	let config = load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+		.await
+		.context("Failed to load config")?;
+
+	maintenance_api::run_due_tasks(&config, &state.paths, &state.client, &mut NoOp).await
 }
 
 /// Updates the version manifest
@@ -130,12 +128,19 @@ pub async fn update_version_manifest(
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("update_versions");
 
+	// Use the cached manifest when offline
+	let depth = if download::is_offline() {
+		UpdateDepth::Shallow
+	} else {
+		UpdateDepth::Full
+	};
+
 	let core = fmt_err(
 		config
 			.get_core(
 				None,
 				&UpdateSettings {
-					depth: UpdateDepth::Full,
+					depth,
 					offline_auth: false,
 				},
 				&state.client,
@@ -146,14 +151,28 @@ pub async fn update_version_manifest(
 			.await,
 	)?;
 
-	fmt_err(
-		core.get_version_manifest(None, UpdateDepth::Full, &mut output)
-			.await,
-	)?;
+	fmt_err(core.get_version_manifest(None, depth, &mut output).await)?;
 
 	Ok(())
 }
 
+/// Gets whether the launcher is in offline mode
+#[tauri::command]
+pub async fn get_offline_status() -> Result<bool, String> {
+	Ok(download::is_offline())
+}
+
+/// Checks connectivity and updates whether we are in offline mode, notifying the frontend if it changed
+pub async fn update_offline_status(client: &Client, app_handle: &AppHandle) -> bool {
+	let offline = !download::check_connectivity(client).await;
+	if offline != download::is_offline() {
+		download::set_offline(offline);
+		let _ = app_handle.emit("nitro_offline_status", offline);
+	}
+
+	offline
+}
+
 /// Gets whether this is the first time the launcher was opened
 #[tauri::command]
 pub async fn get_is_first_launch(state: tauri::State<'_, State>) -> Result<bool, String> {
@@ -174,50 +193,7 @@ pub async fn get_version_banner_images(
 	state: tauri::State<'_, State>,
 	version: &str,
 ) -> Result<Option<(String, String)>, String> {
-	let index_path = state
-		.paths
-		.internal
-		.join(format!("assets/indexes/{version}.json"));
-
-	if !index_path.exists() {
-		return Ok(None);
-	}
-
-	let contents: AssetIndex =
-		fmt_err(json_from_file(index_path).context("Failed to open asset index"))?;
-
-	let pano1 = contents
-		.objects
-		.get("minecraft/textures/gui/title/background/panorama_0.png");
-	let pano2 = contents
-		.objects
-		.get("minecraft/textures/gui/title/background/panorama_1.png");
-
-	let Some(pano1) = pano1 else {
-		return Ok(None);
-	};
-
-	let Some(pano2) = pano2 else {
-		return Ok(None);
-	};
-
-	let path1 = state
-		.paths
-		.internal
-		.join(format!("assets/objects/{}", pano1.get_hash_path()));
-	let path2 = state
-		.paths
-		.internal
-		.join(format!("assets/objects/{}", pano2.get_hash_path()));
-
-	if !path1.exists() || !path2.exists() {
-		return Ok(None);
-	}
-
-	Ok(Some((
-		path1.to_string_lossy().to_string(),
-		path2.to_string_lossy().to_string(),
-	)))
+	fmt_err(version_api::get_banner_images(&state.paths, version))
 }
 
 /// Starts a long-running test task
@@ -236,17 +212,63 @@ pub async fn test_long_running_task(state: tauri::State<'_, State>) -> Result<()
 
 #[tauri::command]
 pub async fn open_data_dir(state: tauri::State<'_, State>) -> Result<(), String> {
-	let path = state.paths.data.clone();
-	tokio::task::spawn_blocking(move || {
-		showfile::show_path_in_file_manager(&path);
-	});
-	Ok(())
+	fmt_err(open_dir(&state.paths.data))
 }
 
 #[tauri::command]
 pub async fn open_instance_dir(
 	state: tauri::State<'_, State>,
 	instance: &str,
+) -> Result<(), String> {
+	open_instance_location(&state, instance, InstanceLocation::GameDir, None).await
+}
+
+/// Opens the worlds folder of an instance, optionally selecting a world
+#[tauri::command]
+pub async fn open_instance_worlds(
+	state: tauri::State<'_, State>,
+	instance: &str,
+	world: Option<String>,
+) -> Result<(), String> {
+	open_instance_location(&state, instance, InstanceLocation::Worlds, world).await
+}
+
+/// Opens the logs folder of an instance, selecting the given log or the latest one
+#[tauri::command]
+pub async fn open_instance_logs(
+	state: tauri::State<'_, State>,
+	instance: &str,
+	log: Option<String>,
+) -> Result<(), String> {
+	open_instance_location(&state, instance, InstanceLocation::Logs, log).await
+}
+
+/// Opens the crash reports folder of an instance, selecting the given report or the newest one
+#[tauri::command]
+pub async fn open_instance_crash_reports(
+	state: tauri::State<'_, State>,
+	instance: &str,
+	report: Option<String>,
+) -> Result<(), String> {
+	open_instance_location(&state, instance, InstanceLocation::CrashReports, report).await
+}
+
+/// Opens the screenshots folder of an instance, optionally selecting a screenshot
+#[tauri::command]
+pub async fn open_instance_screenshots(
+	state: tauri::State<'_, State>,
+	instance: &str,
+	screenshot: Option<String>,
+) -> Result<(), String> {
+	open_instance_location(&state, instance, InstanceLocation::Screenshots, screenshot).await
+}
+
+/// Opens a location of an instance in the file manager
+async fn open_instance_location(
+	state: &State,
+	instance: &str,
+	location: InstanceLocation,
+	file: Option<String>,
 ) -> Result<(), String> {
 	let mut config = fmt_err(
 		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
@@ -254,22 +276,16 @@ pub async fn open_instance_dir(
 			.context("Failed to load config"),
 	)?;
 
-	let Some(instance) = config.instances.get_mut(&InstanceID::from(instance)) else {
+	let Some(instance) = config.instances.remove(&InstanceID::from(instance)) else {
 		return Err(format!("Instance {instance} does not exist"));
 	};
 
-	let _ = instance.ensure_dir();
-
-	let path = instance.dir().map(|x| x.to_owned());
-	let Some(path) = path else {
-		return Err("Instance has no game dir".into());
-	};
-
-	tokio::task::spawn_blocking(move || {
-		showfile::show_path_in_file_manager(&path);
-	});
+	// Showing files in the file manager can block
+	let result =
+		tokio::task::spawn_blocking(move || instance.open_location(location, file.as_deref()))
+			.await;
 
-	Ok(())
+	fmt_err(fmt_err(result)?)
 }
 
 /// Gets the list of available instance icons
@@ -313,13 +329,18 @@ pub async fn get_available_icons(
 /// Adds a saved instance icon
 #[tauri::command]
 pub async fn save_icon(state: tauri::State<'_, State>, icon: String) -> Result<(), String> {
-	let mut data = state.data.lock().await;
-	data.saved_instance_icons
-		.push(InstanceIcon::File(icon.into()));
+	fmt_err(add_saved_icon(&state, icon.into()).await)
+}
 
-	fmt_err(data.write(&state.paths))?;
+/// Adds an icon to the saved instance icons if it isn't saved already
+pub async fn add_saved_icon(state: &State, icon: PathBuf) -> anyhow::Result<()> {
+	let mut data = state.data.lock().await;
+	let icon = InstanceIcon::File(icon);
+	if !data.saved_instance_icons.contains(&icon) {
+		data.saved_instance_icons.push(icon);
+	}
 
-	Ok(())
+	data.write(&state.paths)
 }
 
 #[tauri::command]
@@ -372,3 +393,40 @@ pub async fn linux_fixes_needed() -> Result<bool, String> {
 pub async fn get_nitro_version() -> String {
 	nitrolaunch::VERSION.to_string()
 }
+
+/// Renders a package or plugin description from Markdown to sanitized HTML, with its images cached locally
+#[tauri::command]
+pub async fn render_markdown(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	markdown: &str,
+) -> Result<String, String> {
+	let mut output = LauncherOutput::new(state.get_output(app_handle));
+	Ok(markdown_api::render_markdown_with_cached_images(
+		markdown,
+		&state.paths,
+		&state.client,
+		convert_file_src,
+		&mut output,
+	)
+	.await)
+}
+
+/// Converts a local file path to a URL that the webview can load, the same way that convertFileSrc does in the frontend
+fn convert_file_src(path: &Path) -> String {
+	let path = path.to_string_lossy();
+	let mut encoded = String::with_capacity(path.len());
+	for byte in path.bytes() {
+		if byte.is_ascii_alphanumeric() || b"-_.!~*'()".contains(&byte) {
+			encoded.push(byte as char);
+		} else {
+			encoded.push_str(&format!("%{byte:02X}"));
+		}
+	}
+
+	if cfg!(any(target_os = "windows", target_os = "android")) {
+		format!("http://asset.localhost/{encoded}")
+	} else {
+		format!("asset://localhost/{encoded}")
+	}
+}
@@ -1,10 +1,13 @@
 use anyhow::Context;
 use nitrolaunch::config::Config;
+use nitrolaunch::core::net::download;
 use nitrolaunch::io::logging::{get_log_file_path, get_log_files};
+use nitrolaunch::io::notifications::{Notification, NotificationStore};
 use nitrolaunch::io::paths::Paths;
-use nitrolaunch::plugin::PluginManager;
+use nitrolaunch::io::reports;
 use nitrolaunch::plugin_crate::hook::wasm::loader::WASMLoader;
 use nitrolaunch::shared::output::NitroOutput;
+use serde::Serialize;
 use std::fmt::Debug;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -15,8 +18,12 @@ pub mod account;
 pub mod instance;
 pub mod launch;
 pub mod misc;
+pub mod network;
+pub mod news;
 pub mod package;
 pub mod plugin;
+pub mod realms;
+pub mod search;
 pub mod settings;
 pub mod transfer;
 
@@ -25,27 +32,27 @@ async fn load_config(
 	wasm_loader: &Arc<Mutex<WASMLoader>>,
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<Config> {
-	let plugins = PluginManager::load(paths, o)
-		.await
-		.context("Failed to load plugin manager")?;
-
-	plugins.set_wasm_loader(wasm_loader.clone()).await;
-
-	Config::load(
-		&Config::get_path(paths),
-		plugins,
-		true,
+	nitro_frontend_api::config::load_config(
 		paths,
+		Some(wasm_loader),
 		crate::get_ms_client_id(),
+		true,
 		o,
 	)
 	.await
-	.context("Failed to load config")
 }
 
 /// Error formatting for results
 fn fmt_err<T, E: Debug>(r: Result<T, E>) -> Result<T, String> {
-	r.map_err(|x| format!("{x:?}"))
+	r.map_err(|x| {
+		let message = format!("{x:?}");
+		// The offline banner already explains these, so don't show the whole error chain
+		if message.contains(download::OFFLINE_ERROR) {
+			download::OFFLINE_ERROR.to_string()
+		} else {
+			message
+		}
+	})
 }
 
 /// Cancels a task
@@ -75,3 +82,76 @@ pub async fn get_log(state: tauri::State<'_, State>, log: &str) -> Result<String
 
 	Ok(contents)
 }
+
+/// Gets the saved reports of crashes and errors
+#[tauri::command]
+pub async fn get_error_reports(
+	state: tauri::State<'_, State>,
+) -> Result<Vec<ErrorReportInfo>, String> {
+	let reports = fmt_err(reports::list_reports(&state.paths))?;
+	let reports = reports.into_iter().map(|x| ErrorReportInfo {
+		title: x.report.title(),
+		body: x.report.to_issue_body(),
+		issue_url: x.report.issue_url(),
+		timestamp: x.report.timestamp,
+		id: x.id,
+	});
+
+	Ok(reports.collect())
+}
+
+/// Removes a saved error report
+#[tauri::command]
+pub async fn delete_error_report(state: tauri::State<'_, State>, id: &str) -> Result<(), String> {
+	fmt_err(reports::remove_report(id, &state.paths))
+}
+
+/// Removes all saved error reports
+#[tauri::command]
+pub async fn clear_error_reports(state: tauri::State<'_, State>) -> Result<(), String> {
+	fmt_err(reports::clear_reports(&state.paths))
+}
+
+/// Gets the stored notifications about background events, newest first
+#[tauri::command]
+pub async fn get_notifications(
+	state: tauri::State<'_, State>,
+) -> Result<Vec<Notification>, String> {
+	let store = fmt_err(NotificationStore::open(&state.paths))?;
+	Ok(store.iter().cloned().collect())
+}
+
+/// Marks a notification as read, or all of them if no ID is given
+#[tauri::command]
+pub async fn mark_notifications_read(
+	state: tauri::State<'_, State>,
+	id: Option<u64>,
+) -> Result<(), String> {
+	let _lock = fmt_err(NotificationStore::lock(&state.paths))?;
+	let mut store = fmt_err(NotificationStore::open(&state.paths))?;
+	if let Some(id) = id {
+		store.mark_read(id);
+	} else {
+		store.mark_all_read();
+	}
+	fmt_err(store.write(&state.paths))
+}
+
+/// Removes all stored notifications
+#[tauri::command]
+pub async fn clear_notifications(state: tauri::State<'_, State>) -> Result<(), String> {
+	let _lock = fmt_err(NotificationStore::lock(&state.paths))?;
+	let mut store = fmt_err(NotificationStore::open(&state.paths))?;
+	store.clear();
+	fmt_err(store.write(&state.paths))
+}
+
+/// An error report for the frontend
+#[derive(Serialize)]
+pub struct ErrorReportInfo {
+	pub id: String,
+	pub title: String,
+	pub body: String,
+	pub issue_url: String,
+	pub timestamp: u64,
+}
@@ -0,0 +1,44 @@
+use nitro_frontend_api::network as network_api;
+use nitrolaunch::core::net::activity::{self, DownloadActivitySummary};
+use nitrolaunch::core::net::stats::NetworkStats;
+use nitrolaunch::io::net_stats;
+use nitrolaunch::shared::output::NoOp;
+
+use crate::State;
+
+use super::fmt_err;
+
+/// Gets the downloads that are in progress along with overall download stats
+#[tauri::command]
+pub async fn get_download_activity() -> Result<DownloadActivitySummary, String> {
+	Ok(activity::get_summary())
+}
+
+/// Pauses or resumes all downloads
+#[tauri::command]
+pub async fn set_downloads_paused(paused: bool) -> Result<(), String> {
+	activity::set_paused(paused);
+
+	Ok(())
+}
+
+/// Sets and saves the global bandwidth limit in bytes per second
+#[tauri::command]
+pub async fn set_bandwidth_limit(
+	state: tauri::State<'_, State>,
+	limit: Option<u64>,
+) -> Result<(), String> {
+	fmt_err(network_api::set_bandwidth_limit(limit, &state.paths, &mut NoOp).await)
+}
+
+/// Gets the network statistics for every host
+#[tauri::command]
+pub async fn get_network_stats(state: tauri::State<'_, State>) -> Result<NetworkStats, String> {
+	fmt_err(net_stats::load(&state.paths))
+}
+
+/// Clears all recorded network statistics
+#[tauri::command]
+pub async fn reset_network_stats(state: tauri::State<'_, State>) -> Result<(), String> {
+	fmt_err(net_stats::reset(&state.paths))
+}
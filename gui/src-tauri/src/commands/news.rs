@@ -0,0 +1,14 @@
+use nitro_frontend_api::news::{self, NewsEntry};
+
+use crate::State;
+
+use super::fmt_err;
+
+/// Gets the latest Minecraft release and snapshot news, using a cache when possible
+#[tauri::command]
+pub async fn get_minecraft_news(
+	state: tauri::State<'_, State>,
+	refresh: bool,
+) -> Result<Vec<NewsEntry>, String> {
+	fmt_err(news::get_minecraft_news(&state.paths, &state.client, refresh).await)
+}
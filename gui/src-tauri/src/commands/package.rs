@@ -1,24 +1,32 @@
 use crate::commands::instance::InstanceOrTemplate;
 use crate::{State, output::LauncherOutput};
 use anyhow::Context;
+use nitro_frontend_api::package::{
+	self as package_api, MultiPackageMetaAndPropsResult, PACKAGES_PER_PAGE, RepoInfo,
+};
+use nitrolaunch::core::net::activity;
 use nitrolaunch::instance_crate::lock::LockfilePackage;
+use nitrolaunch::pkg_crate::compat::CompatibilityMatrix;
 use nitrolaunch::pkg_crate::declarative::DeclarativePackage;
+use nitrolaunch::pkg_crate::details::{AuthorProfile, PackageDetails};
 use nitrolaunch::pkg_crate::metadata::PackageMetadata;
 use nitrolaunch::pkg_crate::properties::PackageProperties;
-use nitrolaunch::pkg_crate::repo::RepoMetadata;
-use nitrolaunch::pkg_crate::{PackageSearchResults, PkgRequest, PkgRequestSource};
+use nitrolaunch::pkg_crate::{AggregatedSearchResults, PackageSearchResults};
 use nitrolaunch::shared::loaders::Loader;
-use nitrolaunch::shared::output::{MessageContents, NitroOutput, NoOp};
-use nitrolaunch::shared::pkg::{PackageCategory, PackageKind, PackageSearchParameters};
-use serde::{Deserialize, Serialize};
+use nitrolaunch::shared::output::NoOp;
+use nitrolaunch::shared::pkg::{
+	PackageCategory, PackageKind, PackageSearchParameters, PackageSortMethod,
+};
 use std::collections::HashMap;
-use std::ops::DerefMut;
 use std::sync::Arc;
-use tokio::task::JoinSet;
+use std::time::{Duration, Instant};
 
 use super::{fmt_err, load_config};
 
-const PACKAGES_PER_PAGE: u8 = 12;
+/// The longest that preloading will wait for foreground work to finish
+const PRELOAD_MAX_DELAY: Duration = Duration::from_secs(30);
+/// How often to check whether foreground work has finished before preloading
+const PRELOAD_POLL_INTERVAL: Duration = Duration::from_millis(250);
 
 #[tauri::command]
 pub async fn get_packages(
@@ -31,6 +39,9 @@ pub async fn get_packages(
 	minecraft_versions: Vec<String>,
 	loaders: Vec<Loader>,
 	categories: Vec<PackageCategory>,
+	sort: Option<PackageSortMethod>,
+	cursor: Option<String>,
+	allow_stale: Option<bool>,
 ) -> Result<PackageSearchResults, String> {
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("search_packages");
@@ -48,17 +59,63 @@ pub async fn get_packages(
 		minecraft_versions,
 		loaders,
 		categories,
+		sort: sort.unwrap_or_default(),
+		cursor,
+		allow_stale: allow_stale.unwrap_or_default(),
 	};
 
-	let results = fmt_err(
-		config
-			.packages
-			.search(params, Some(repo), &state.paths, &state.client, &mut output)
+	fmt_err(
+		package_api::search_packages(
+			&config,
+			repo,
+			params,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await,
+	)
+}
+
+#[tauri::command]
+pub async fn search_all_packages(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	page: usize,
+	search: Option<&str>,
+	package_kinds: Vec<PackageKind>,
+	minecraft_versions: Vec<String>,
+	loaders: Vec<Loader>,
+	categories: Vec<PackageCategory>,
+	sort: Option<PackageSortMethod>,
+	cursor: Option<String>,
+	allow_stale: Option<bool>,
+) -> Result<AggregatedSearchResults, String> {
+	let mut output = LauncherOutput::new(state.get_output(app_handle));
+	output.set_task("search_packages");
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
 			.await
-			.context("Failed to get list of available packages"),
+			.context("Failed to load config"),
 	)?;
 
-	Ok(results)
+	let params = PackageSearchParameters {
+		count: PACKAGES_PER_PAGE,
+		skip: page * PACKAGES_PER_PAGE as usize,
+		search: search.map(|x| x.to_string()),
+		types: package_kinds,
+		minecraft_versions,
+		loaders,
+		categories,
+		sort: sort.unwrap_or_default(),
+		cursor,
+		allow_stale: allow_stale.unwrap_or_default(),
+	};
+
+	fmt_err(
+		package_api::search_all_packages(&config, params, &state.paths, &state.client, &mut output)
+			.await,
+	)
 }
 
 #[tauri::command]
@@ -68,42 +125,45 @@ pub async fn preload_packages(
 	packages: Vec<String>,
 	repo: Option<&str>,
 ) -> Result<(), String> {
+	// Let updates and launches go first so that browsing packages doesn't slow them down
+	wait_for_foreground_work(&state).await;
+
 	let config = fmt_err(
 		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
 			.await
 			.context("Failed to load config"),
 	)?;
 
-	let packages = packages
-		.into_iter()
-		.map(|x| Arc::new(PkgRequest::parse(x, PkgRequestSource::UserRequire)))
-		.collect();
-
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("load_packages");
 
-	if let Some(repo) = repo {
-		let repo = config.packages.repos.iter().find(|x| x.get_id() == repo);
-		let Some(repo) = repo else {
-			return Err("Repository does not exist".into());
+	fmt_err(
+		package_api::preload_packages(
+			&config,
+			packages,
+			repo,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await,
+	)
+}
+
+/// Waits until no foreground tasks or downloads are running, for up to a limited amount of time
+async fn wait_for_foreground_work(state: &State) {
+	let start = Instant::now();
+	while start.elapsed() < PRELOAD_MAX_DELAY {
+		let has_tasks = match state.task_manager.get() {
+			Some(task_manager) => task_manager.lock().await.has_foreground_tasks(),
+			None => false,
 		};
+		if !has_tasks && activity::get_downloads().is_empty() {
+			return;
+		}
 
-		fmt_err(
-			repo.preload(packages, &state.paths, &config.plugins, &mut output)
-				.await
-				.context("Failed to preload packages from repository"),
-		)?;
-	} else {
-		fmt_err(
-			config
-				.packages
-				.preload_packages(packages.iter(), &state.paths, &state.client, &mut output)
-				.await
-				.context("Failed to preload packages from repositories"),
-		)?;
+		tokio::time::sleep(PRELOAD_POLL_INTERVAL).await;
 	}
-
-	Ok(())
 }
 
 #[tauri::command]
@@ -120,23 +180,10 @@ pub async fn get_package_meta(
 
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 
-	let req = Arc::new(PkgRequest::parse(package, PkgRequestSource::UserRequire));
-
-	let package = fmt_err(
-		config
-			.packages
-			.get(&req, &state.paths, &state.client, &mut output)
+	fmt_err(
+		package_api::get_package_meta(&config, package, &state.paths, &state.client, &mut output)
 			.await,
-	)?;
-
-	let meta = fmt_err(
-		package
-			.get_metadata(&state.paths, &state.client)
-			.await
-			.context("Failed to get metadata"),
-	)?;
-
-	Ok(meta)
+	)
 }
 
 #[tauri::command]
@@ -153,23 +200,10 @@ pub async fn get_package_props(
 
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 
-	let req = Arc::new(PkgRequest::parse(package, PkgRequestSource::UserRequire));
-
-	let package = fmt_err(
-		config
-			.packages
-			.get(&req, &state.paths, &state.client, &mut output)
+	fmt_err(
+		package_api::get_package_props(&config, package, &state.paths, &state.client, &mut output)
 			.await,
-	)?;
-
-	let props = fmt_err(
-		package
-			.get_properties(&state.paths, &state.client)
-			.await
-			.context("Failed to get properties"),
-	)?;
-
-	Ok(props)
+	)
 }
 
 #[tauri::command]
@@ -186,30 +220,63 @@ pub async fn get_package_meta_and_props(
 
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 
-	let req = Arc::new(PkgRequest::parse(package, PkgRequestSource::UserRequire));
-
-	let package = fmt_err(
-		config
-			.packages
-			.get(&req, &state.paths, &state.client, &mut output)
-			.await,
-	)?;
+	fmt_err(
+		package_api::get_package_meta_and_props(
+			&config,
+			package,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await,
+	)
+}
 
-	let meta = fmt_err(
-		package
-			.get_metadata(&state.paths, &state.client)
+#[tauri::command]
+pub async fn get_package_details(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	package: &str,
+) -> Result<PackageDetails, String> {
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
 			.await
-			.context("Failed to get metadata"),
+			.context("Failed to load config"),
 	)?;
 
-	let props = fmt_err(
-		package
-			.get_properties(&state.paths, &state.client)
+	let mut output = LauncherOutput::new(state.get_output(app_handle));
+
+	fmt_err(
+		package_api::get_package_details(
+			&config,
+			package,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await,
+	)
+}
+
+#[tauri::command]
+pub async fn get_package_author(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	repository: &str,
+	author: &str,
+) -> Result<Option<AuthorProfile>, String> {
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
 			.await
-			.context("Failed to get properties"),
+			.context("Failed to load config"),
 	)?;
 
-	Ok((meta, props))
+	let mut output = LauncherOutput::new(state.get_output(app_handle));
+
+	fmt_err(
+		package_api::get_package_author(&config, repository, author, &state.paths, &mut output)
+			.await,
+	)
 }
 
 #[tauri::command]
@@ -226,53 +293,16 @@ pub async fn get_multiple_package_meta_and_props(
 
 	let output = LauncherOutput::new(state.get_output(app_handle));
 
-	let paths = Arc::new(state.paths.clone());
-
-	let mut tasks = JoinSet::new();
-	for pkg in packages {
-		let req = Arc::new(PkgRequest::parse(pkg, PkgRequestSource::UserRequire));
-
-		let pkg = pkg.to_string();
-		let reg = config.packages.clone();
-		let paths = paths.clone();
-		let client = state.client.clone();
-		let mut output = output.get_greater_copy();
-		tasks.spawn(async move {
-			let package = match fmt_err(reg.get(&req, &paths, &client, &mut output).await) {
-				Ok(package) => package,
-				Err(e) => return (pkg, MultiPackageMetaAndPropsResult::Error(e)),
-			};
-			let meta = match fmt_err(package.get_metadata(&paths, &client).await) {
-				Ok(meta) => meta,
-				Err(e) => return (pkg, MultiPackageMetaAndPropsResult::Error(e)),
-			};
-			let props = match fmt_err(package.get_properties(&paths, &client).await) {
-				Ok(props) => props,
-				Err(e) => return (pkg, MultiPackageMetaAndPropsResult::Error(e)),
-			};
-
-			(
-				pkg,
-				MultiPackageMetaAndPropsResult::MetaAndProps(meta, props),
-			)
-		});
-	}
-
-	let mut out = HashMap::new();
-
-	while let Some(task) = tasks.join_next().await {
-		let (pkg, result) = fmt_err(task)?;
-		out.insert(pkg, result);
-	}
-
-	Ok(out)
-}
-
-#[derive(Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum MultiPackageMetaAndPropsResult {
-	Error(String),
-	MetaAndProps(Arc<PackageMetadata>, Arc<PackageProperties>),
+	fmt_err(
+		package_api::get_multiple_package_meta_and_props(
+			&config,
+			packages,
+			&state.paths,
+			&state.client,
+			&output,
+		)
+		.await,
+	)
 }
 
 #[tauri::command]
@@ -289,23 +319,42 @@ pub async fn get_declarative_package_contents(
 
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 
-	let req = Arc::new(PkgRequest::parse(package, PkgRequestSource::UserRequire));
-
-	let package = fmt_err(
-		config
-			.packages
-			.get(&req, &state.paths, &state.client, &mut output)
-			.await,
-	)?;
+	fmt_err(
+		package_api::get_declarative_package_contents(
+			&config,
+			package,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await,
+	)
+}
 
-	let contents = fmt_err(
-		package
-			.get_declarative_contents(&state.paths, &state.client)
+#[tauri::command]
+pub async fn get_package_compatibility(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	package: &str,
+) -> Result<Option<CompatibilityMatrix>, String> {
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
 			.await
-			.context("Failed to get declarative contents"),
+			.context("Failed to load config"),
 	)?;
 
-	Ok(contents.cloned())
+	let mut output = LauncherOutput::new(state.get_output(app_handle));
+
+	fmt_err(
+		package_api::get_package_compatibility(
+			&config,
+			package,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await,
+	)
 }
 
 #[tauri::command]
@@ -316,27 +365,7 @@ pub async fn get_package_repos(state: tauri::State<'_, State>) -> Result<Vec<Rep
 			.context("Failed to load config"),
 	)?;
 
-	let mut repos = Vec::new();
-	for repo in &config.packages.repos {
-		let id = repo.get_id().to_string();
-		let meta = fmt_err(
-			repo.get_metadata(&state.paths, &state.client, &mut NoOp)
-				.await
-				.context("Failed to get metadata for repository"),
-		)?;
-		repos.push(RepoInfo {
-			id,
-			meta: meta.into_owned(),
-		})
-	}
-
-	Ok(repos)
-}
-
-#[derive(Serialize, Deserialize)]
-pub struct RepoInfo {
-	pub id: String,
-	pub meta: RepoMetadata,
+	fmt_err(package_api::get_package_repos(&config, &state.paths, &state.client).await)
 }
 
 #[tauri::command]
@@ -350,13 +379,11 @@ pub async fn get_instance_packages(
 			.context("Failed to load config"),
 	)?;
 
-	let Some(instance) = config.instances.get_mut(instance) else {
-		return Ok(HashMap::new());
-	};
-
-	let lock = fmt_err(instance.get_lockfile(&state.paths))?;
-
-	Ok(lock.get_packages().clone())
+	fmt_err(package_api::get_instance_packages(
+		&mut config,
+		instance,
+		&state.paths,
+	))
 }
 
 #[tauri::command]
@@ -373,50 +400,7 @@ pub async fn sync_packages(
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("sync_packages");
 
-	for repo in config.packages.repos.iter() {
-		output.display(MessageContents::StartProcess(format!(
-			"Syncing repository {}",
-			repo.get_id()
-		)));
-		let mut process = output.get_process();
-		match repo
-			.sync(
-				&state.paths,
-				&config.plugins,
-				&state.client,
-				process.deref_mut(),
-			)
-			.await
-		{
-			Ok(..) => {
-				process.display(MessageContents::Success(format!(
-					"Synced repository {}",
-					repo.get_id()
-				)));
-			}
-			Err(e) => {
-				process.display(MessageContents::Error(format!(
-					"Failed to sync repository {}: {e}",
-					repo.get_id()
-				)));
-			}
-		};
-	}
-
-	let mut process = output.get_process();
-	process.display(MessageContents::StartProcess(
-		"Updating standard packages".into(),
-	));
-	fmt_err(
-		config
-			.packages
-			.update_cached_packages(&state.paths, &state.client, process.deref_mut())
-			.await
-			.context("Failed to update cached packages"),
-	)?;
-	process.display(MessageContents::Success("Packages updated".into()));
-
-	Ok(())
+	fmt_err(package_api::sync_packages(&config, &state.paths, &state.client, &mut output).await)
 }
 
 /// Gets the last viewed package repository on the browse page
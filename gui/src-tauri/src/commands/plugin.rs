@@ -1,50 +1,21 @@
 use crate::State;
 use crate::output::LauncherOutput;
 use anyhow::Context;
-use itertools::Itertools;
+use nitro_frontend_api::plugin::{self as plugin_api, PluginInfo, PluginPage};
 use nitrolaunch::config_crate::ConfigKind;
-use nitrolaunch::core::io::json_to_file_pretty;
 use nitrolaunch::plugin::PluginManager;
 use nitrolaunch::plugin_crate::control::Control;
 use nitrolaunch::plugin_crate::hook::hooks::{
-	AddDropdownButtons, AddInstanceConfigControls, AddInstanceConfigControlsArg, AddInstanceTiles,
-	AddPluginConfigControls, AddSidebarButtons, AddThemes, CustomAction, CustomActionArg,
-	DropdownButton, DropdownButtonLocation, GetPage, InjectPageScript, InjectPageScriptArg,
-	InstanceTile, SidebarButton, Theme,
+	DropdownButton, DropdownButtonLocation, InstanceTile, SidebarButton, Theme,
 };
-use nitrolaunch::plugin_crate::plugin::PluginMetadata;
-use nitrolaunch::{plugin::install::get_verified_plugins, shared::output::NoOp};
-use serde::{Deserialize, Serialize};
+use nitrolaunch::shared::output::NoOp;
 use std::collections::HashMap;
-use std::fmt::Debug;
 
 use super::{fmt_err, load_config};
 
 #[tauri::command]
 pub async fn get_local_plugins(state: tauri::State<'_, State>) -> Result<Vec<PluginInfo>, String> {
-	let config =
-		fmt_err(PluginManager::open_config(&state.paths).context("Failed to open plugin config"))?;
-
-	let plugins = fmt_err(
-		PluginManager::get_available_plugins(&state.paths)
-			.context("Failed to get available plugins"),
-	)?;
-
-	let plugins = plugins.into_iter().filter_map(|x| {
-		let id = x.0;
-		let manifest = PluginManager::read_plugin_manifest(&id, &state.paths).ok()?;
-
-		Some(PluginInfo {
-			enabled: config.plugins.contains(&id),
-			id,
-			version: manifest.version,
-			meta: manifest.meta,
-			installed: true,
-			is_official: false,
-		})
-	});
-
-	Ok(plugins.sorted_by_cached_key(|x| x.id.clone()).collect())
+	fmt_err(plugin_api::get_local_plugins(&state.paths))
 }
 
 #[tauri::command]
@@ -53,47 +24,14 @@ pub async fn get_remote_plugins(
 	app_handle: tauri::AppHandle,
 	offline: bool,
 ) -> Result<Vec<PluginInfo>, String> {
-	let verified_plugins = if offline {
-		fmt_err(
-			get_verified_plugins(&state.client, true)
-				.await
-				.context("Failed to get verified plugins"),
-		)?
+	if offline {
+		fmt_err(plugin_api::get_remote_plugins(&state.client, true).await)
 	} else {
 		let mut output = LauncherOutput::new(state.get_output(app_handle));
 		output.set_task("get_plugins");
 
-		fmt_err(
-			get_verified_plugins(&state.client, false)
-				.await
-				.context("Failed to get verified plugins"),
-		)?
-	};
-
-	let verified_plugins = verified_plugins.into_values().map(|x| PluginInfo {
-		id: x.id,
-		meta: x.meta,
-		version: x.version,
-		enabled: false,
-		installed: false,
-		is_official: x.github_owner == "Nitrolaunch",
-	});
-
-	Ok(verified_plugins
-		.sorted_by_cached_key(|x| x.id.clone())
-		.collect())
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct PluginInfo {
-	pub id: String,
-	pub version: Option<String>,
-	#[serde(flatten)]
-	pub meta: PluginMetadata,
-	pub enabled: bool,
-	pub installed: bool,
-	/// Whether this is an official Nitrolaunch plugin
-	pub is_official: bool,
+		fmt_err(plugin_api::get_remote_plugins(&state.client, false).await)
+	}
 }
 
 #[tauri::command]
@@ -102,15 +40,11 @@ pub async fn enable_disable_plugin(
 	plugin: &str,
 	enabled: bool,
 ) -> Result<(), String> {
-	if enabled {
-		fmt_err(
-			PluginManager::enable_plugin(plugin, &state.paths).context("Failed to enable plugin"),
-		)?;
-	} else {
-		fmt_err(
-			PluginManager::disable_plugin(plugin, &state.paths).context("Failed to disable plugin"),
-		)?;
-	}
+	fmt_err(plugin_api::set_plugin_enabled(
+		plugin,
+		enabled,
+		&state.paths,
+	))?;
 	state.remove_from_wasm_cache(plugin).await;
 
 	Ok(())
@@ -126,26 +60,9 @@ pub async fn install_plugin(
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("install_plugins");
 
-	let verified_list = fmt_err(
-		get_verified_plugins(&state.client, false)
-			.await
-			.context("Failed to get verified plugin list"),
-	)?;
-
-	let plugin_id = plugin;
-
-	let Some(plugin) = verified_list.get(plugin) else {
-		return Err(format!("Unknown plugin '{plugin}'"));
-	};
+	fmt_err(plugin_api::install_plugin(plugin, version, &state.paths, &state.client).await)?;
 
-	fmt_err(
-		plugin
-			.install(version, &state.paths, &state.client, &mut NoOp)
-			.await
-			.context("Failed to install plugin"),
-	)?;
-
-	state.remove_from_wasm_cache(plugin_id).await;
+	state.remove_from_wasm_cache(plugin).await;
 
 	Ok(())
 }
@@ -159,26 +76,7 @@ pub async fn get_plugin_versions(
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("get_plugin_versions");
 
-	let verified_list = fmt_err(
-		get_verified_plugins(&state.client, true)
-			.await
-			.context("Failed to get verified plugin list"),
-	)?;
-
-	let Some(plugin) = verified_list.get(plugin) else {
-		return Err(format!("Unknown plugin '{plugin}'"));
-	};
-
-	let assets = fmt_err(
-		plugin
-			.get_candidate_assets(None, &state.client)
-			.await
-			.context("Failed to install plugin"),
-	)?;
-
-	let versions = assets.into_iter().map(|x| x.version).unique().collect();
-
-	Ok(versions)
+	fmt_err(plugin_api::get_plugin_versions(plugin, &state.client).await)
 }
 
 #[tauri::command]
@@ -198,38 +96,7 @@ pub async fn install_default_plugins(
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 	output.set_task("install_plugins");
 
-	let default_plugins = [
-		"fabric_quilt",
-		"modrinth",
-		"smithed",
-		"stats",
-		"docs",
-		"multimc_transfer",
-		"xmcl_transfer",
-	];
-
-	let verified_list = fmt_err(
-		get_verified_plugins(&state.client, false)
-			.await
-			.context("Failed to get verified plugin list"),
-	)?;
-
-	for plugin in default_plugins {
-		let Some(plugin) = verified_list.get(plugin) else {
-			return Err(format!("Unknown plugin '{plugin}'"));
-		};
-
-		fmt_err(
-			plugin
-				.install(None, &state.paths, &state.client, &mut NoOp)
-				.await
-				.with_context(|| format!("Failed to install plugin {}", plugin.id)),
-		)?;
-
-		let _ = PluginManager::enable_plugin(&plugin.id, &state.paths);
-	}
-
-	Ok(())
+	fmt_err(plugin_api::install_default_plugins(&state.paths, &state.client).await)
 }
 
 #[tauri::command]
@@ -247,21 +114,11 @@ pub async fn get_page_inject_script(
 			.context("Failed to load config"),
 	)?;
 
-	let arg = InjectPageScriptArg { page, object };
-	let mut results = fmt_err(
-		config
-			.plugins
-			.call_hook(InjectPageScript, &arg, &state.paths, &mut output)
-			.await,
+	let script = fmt_err(
+		plugin_api::get_page_inject_script(&config, page, object, &state.paths, &mut output).await,
 	)?;
 
-	let mut out = String::new();
-	while let Some(result) = fmt_err(results.next_result(&mut output).await)? {
-		out.push_str(&result);
-		out.push('\n');
-	}
-
-	Ok(Some(out))
+	Ok(Some(script))
 }
 
 #[tauri::command]
@@ -277,15 +134,7 @@ pub async fn get_sidebar_buttons(
 			.context("Failed to load config"),
 	)?;
 
-	let results = fmt_err(
-		config
-			.plugins
-			.call_hook(AddSidebarButtons, &(), &state.paths, &mut output)
-			.await,
-	)?;
-	let out = fmt_err(results.flatten_all_results(&mut output).await)?;
-
-	Ok(out)
+	fmt_err(plugin_api::get_sidebar_buttons(&config, &state.paths, &mut output).await)
 }
 
 #[tauri::command]
@@ -293,7 +142,7 @@ pub async fn get_plugin_page(
 	state: tauri::State<'_, State>,
 	app_handle: tauri::AppHandle,
 	page: &str,
-) -> Result<Option<String>, String> {
+) -> Result<Option<PluginPage>, String> {
 	let mut output = LauncherOutput::new(state.get_output(app_handle));
 
 	let config = fmt_err(
@@ -302,20 +151,7 @@ pub async fn get_plugin_page(
 			.context("Failed to load config"),
 	)?;
 
-	let mut results = fmt_err(
-		config
-			.plugins
-			.call_hook(GetPage, &page.to_string(), &state.paths, &mut output)
-			.await,
-	)?;
-
-	while let Some(result) = fmt_err(results.next_result(&mut output).await)? {
-		if let Some(result) = result {
-			return Ok(Some(result));
-		}
-	}
-
-	Ok(None)
+	fmt_err(plugin_api::get_plugin_page(&config, page, &state.paths, &mut output).await)
 }
 
 #[tauri::command]
@@ -331,16 +167,9 @@ pub async fn get_themes(
 			.context("Failed to load config"),
 	)?;
 
-	let results = fmt_err(
-		config
-			.plugins
-			.call_hook(AddThemes, &(), &state.paths, &mut output)
-			.await,
-	)?;
-
-	let out = fmt_err(results.flatten_all_results(&mut output).await)?;
+	let custom_themes = state.data.lock().await.custom_themes.clone();
 
-	Ok(out)
+	fmt_err(plugin_api::get_themes(&config, custom_themes, &state.paths, &mut output).await)
 }
 
 #[tauri::command]
@@ -359,30 +188,41 @@ pub async fn run_custom_action(
 			.context("Failed to load config"),
 	)?;
 
-	let result = fmt_err(
-		config
-			.plugins
-			.call_hook_on_plugin(
-				CustomAction,
-				plugin,
-				&CustomActionArg {
-					id: action,
-					payload,
-				},
-				&state.paths,
-				&mut output,
-			)
-			.await
-			.context("Failed to call custom action"),
-	)?;
+	fmt_err(
+		plugin_api::call_custom_action(&config, plugin, action, payload, &state.paths, &mut output)
+			.await,
+	)
+}
 
-	let Some(result) = result else {
-		return Err("Plugin did not return a result".into());
-	};
+/// Runs a custom action from a sandboxed plugin page. Only actions that the plugin
+/// has declared in its manifest can be called
+#[tauri::command]
+pub async fn run_plugin_page_action(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	plugin: &str,
+	action: String,
+	payload: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+	let mut output = LauncherOutput::new(state.get_output(app_handle));
 
-	let result = fmt_err(result.result(&mut output).await)?;
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
+	)?;
 
-	Ok(result)
+	fmt_err(
+		plugin_api::call_plugin_page_action(
+			&config,
+			plugin,
+			action,
+			payload,
+			&state.paths,
+			&mut output,
+		)
+		.await,
+	)
 }
 
 #[tauri::command]
@@ -399,19 +239,7 @@ pub async fn get_dropdown_buttons(
 			.context("Failed to load config"),
 	)?;
 
-	let mut results = fmt_err(
-		config
-			.plugins
-			.call_hook(AddDropdownButtons, &(), &state.paths, &mut output)
-			.await,
-	)?;
-
-	let mut out = Vec::new();
-	while let Some(result) = fmt_err(results.next_result(&mut output).await)? {
-		out.extend(result.into_iter().filter(|x| x.location == location));
-	}
-
-	Ok(out)
+	fmt_err(plugin_api::get_dropdown_buttons(&config, location, &state.paths, &mut output).await)
 }
 
 #[tauri::command]
@@ -428,16 +256,7 @@ pub async fn get_instance_tiles(
 			.context("Failed to load config"),
 	)?;
 
-	let results = fmt_err(
-		config
-			.plugins
-			.call_hook(AddInstanceTiles, &instance_id, &state.paths, &mut output)
-			.await,
-	)?;
-
-	let out = fmt_err(results.flatten_all_results(&mut output).await)?;
-
-	Ok(out)
+	fmt_err(plugin_api::get_instance_tiles(&config, &instance_id, &state.paths, &mut output).await)
 }
 
 #[tauri::command]
@@ -453,20 +272,7 @@ pub async fn get_instance_config_controls(
 			.context("Failed to load config"),
 	)?;
 
-	let arg = AddInstanceConfigControlsArg { id, kind, plugin };
-	let mut results = fmt_err(
-		config
-			.plugins
-			.call_hook(AddInstanceConfigControls, &arg, &state.paths, &mut NoOp)
-			.await,
-	)?;
-
-	let mut out = Vec::new();
-	while let Some(result) = fmt_err(results.next_result(&mut NoOp).await)? {
-		out.extend(result.controls);
-	}
-
-	Ok(out)
+	fmt_err(plugin_api::get_instance_config_controls(&config, id, kind, plugin, &state.paths).await)
 }
 
 #[tauri::command]
@@ -479,32 +285,14 @@ pub async fn get_plugin_config_controls(
 			.context("Failed to load config"),
 	)?;
 
-	let mut results = fmt_err(
-		config
-			.plugins
-			.call_hook(AddPluginConfigControls, &(), &state.paths, &mut NoOp)
-			.await,
-	)?;
-
-	let mut out = HashMap::new();
-	while let Some(result) = results.next() {
-		let plugin_id = result.get_id().clone();
-		let result = fmt_err(result.result(&mut NoOp).await)?;
-
-		out.insert(plugin_id, result);
-	}
-
-	Ok(out)
+	fmt_err(plugin_api::get_plugin_config_controls(&config, &state.paths).await)
 }
 
 #[tauri::command]
 pub async fn get_plugin_config(
 	state: tauri::State<'_, State>,
 ) -> Result<HashMap<String, serde_json::Value>, String> {
-	let config =
-		fmt_err(PluginManager::open_config(&state.paths).context("Failed to open plugin config"))?;
-
-	Ok(config.config)
+	fmt_err(plugin_api::get_plugin_config(&state.paths))
 }
 
 #[tauri::command]
@@ -512,17 +300,5 @@ pub async fn write_plugin_config(
 	state: tauri::State<'_, State>,
 	config: HashMap<String, serde_json::Value>,
 ) -> Result<(), String> {
-	let config_path = PluginManager::get_config_path(&state.paths);
-
-	let mut base_config =
-		fmt_err(PluginManager::open_config(&state.paths).context("Failed to open plugin config"))?;
-
-	base_config.config = config;
-
-	fmt_err(
-		json_to_file_pretty(config_path, &base_config)
-			.context("Failed to write to plugin config file"),
-	)?;
-
-	Ok(())
+	fmt_err(plugin_api::write_plugin_config(config, &state.paths))
 }
@@ -0,0 +1,117 @@
+use crate::State;
+use crate::output::LauncherOutput;
+use anyhow::Context;
+use nitro_frontend_api::realms::{self as realms_api, RealmInfo};
+use nitrolaunch::core::net::minecraft::realms::RealmInvite;
+use nitrolaunch::shared::output::NoOp;
+
+use super::{fmt_err, load_config};
+
+/// Gets the account to use for Realms, falling back to the current account
+async fn get_realms_account(state: &State, account: Option<String>) -> Result<String, String> {
+	if let Some(account) = account {
+		return Ok(account);
+	}
+
+	let data = state.data.lock().await;
+	data.current_account
+		.clone()
+		.ok_or_else(|| "No account selected".to_string())
+}
+
+/// Gets the realms that an account owns or has joined
+#[tauri::command]
+pub async fn get_realms(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	account: Option<String>,
+) -> Result<Vec<RealmInfo>, String> {
+	let account = get_realms_account(&state, account).await?;
+
+	let mut config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
+	)?;
+
+	let mut output = LauncherOutput::new(state.get_output(app_handle));
+	output.set_task("get_realms");
+
+	fmt_err(
+		realms_api::get_realms(
+			&mut config,
+			&account,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await
+		.context("Failed to get realms"),
+	)
+}
+
+/// Gets the pending realm invites for an account
+#[tauri::command]
+pub async fn get_realm_invites(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	account: Option<String>,
+) -> Result<Vec<RealmInvite>, String> {
+	let account = get_realms_account(&state, account).await?;
+
+	let mut config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
+	)?;
+
+	let mut output = LauncherOutput::new(state.get_output(app_handle));
+	output.set_task("get_realm_invites");
+
+	fmt_err(
+		realms_api::get_realm_invites(
+			&mut config,
+			&account,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await
+		.context("Failed to get realm invites"),
+	)
+}
+
+/// Accepts or rejects a realm invite
+#[tauri::command]
+pub async fn answer_realm_invite(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	account: Option<String>,
+	invite: &str,
+	accept: bool,
+) -> Result<(), String> {
+	let account = get_realms_account(&state, account).await?;
+
+	let mut config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
+	)?;
+
+	let mut output = LauncherOutput::new(state.get_output(app_handle));
+	output.set_task("answer_realm_invite");
+
+	fmt_err(
+		realms_api::answer_realm_invite(
+			&mut config,
+			&account,
+			invite,
+			accept,
+			&state.paths,
+			&state.client,
+			&mut output,
+		)
+		.await
+		.context("Failed to answer realm invite"),
+	)
+}
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use crate::State;
+use anyhow::Context;
+use nitrolaunch::config::Config;
+use nitrolaunch::config::watch::ConfigWatcher;
+use nitrolaunch::plugin_crate::hook::hooks::AddDropdownButtons;
+use nitrolaunch::shared::output::NoOp;
+use nitrolaunch::shared::pkg::PackageSearchParameters;
+use serde::Serialize;
+
+use super::{fmt_err, load_config};
+
+/// Maximum number of results of each kind to return
+const MAX_RESULTS: usize = 8;
+
+/// Searches across instances, templates, packages, and plugin actions for the command palette
+#[tauri::command]
+pub async fn global_search(
+	state: tauri::State<'_, State>,
+	query: &str,
+) -> Result<Vec<GlobalSearchResult>, String> {
+	let config = fmt_err(get_search_config(&state).await)?;
+
+	let query = query.trim().to_lowercase();
+	let matches = |id: &str, name: Option<&str>| {
+		id.to_lowercase().contains(&query)
+			|| name.is_some_and(|x| x.to_lowercase().contains(&query))
+	};
+
+	let mut out = Vec::new();
+
+	let instances = config
+		.instances
+		.iter()
+		.filter(|(id, instance)| matches(id, instance.config().name.as_deref()))
+		.take(MAX_RESULTS)
+		.map(|(id, instance)| GlobalSearchResult {
+			kind: GlobalSearchResultKind::Instance,
+			id: id.to_string(),
+			name: instance.config().name.clone(),
+			icon: instance.config().icon.clone(),
+			plugin: None,
+		});
+	out.extend(instances);
+
+	let templates = config
+		.templates
+		.iter()
+		.filter(|(id, template)| matches(id, template.instance.name.as_deref()))
+		.take(MAX_RESULTS)
+		.map(|(id, template)| GlobalSearchResult {
+			kind: GlobalSearchResultKind::Template,
+			id: id.to_string(),
+			name: template.instance.name.clone(),
+			icon: template.instance.icon.clone(),
+			plugin: None,
+		});
+	out.extend(templates);
+
+	// Plugin actions come from the dropdown buttons that plugins add
+	let results = config
+		.plugins
+		.call_hook(AddDropdownButtons, &(), &state.paths, &mut NoOp)
+		.await;
+	if let Ok(results) = results
+		&& let Ok(buttons) = results.flatten_all_results(&mut NoOp).await
+	{
+		let actions = buttons
+			.into_iter()
+			.filter(|x| x.action.is_some() && matches(&x.text, None))
+			.take(MAX_RESULTS)
+			.map(|x| GlobalSearchResult {
+				kind: GlobalSearchResultKind::PluginAction,
+				id: x.action.unwrap_or_default(),
+				name: Some(x.text),
+				icon: None,
+				plugin: Some(x.plugin),
+			});
+		out.extend(actions);
+	}
+
+	// Package searches can be slow or fail when offline, so don't let them break the other results
+	if !query.is_empty() {
+		let params = PackageSearchParameters {
+			count: MAX_RESULTS as u8,
+			search: Some(query.clone()),
+			..Default::default()
+		};
+		let results = config
+			.packages
+			.search(params, None, &state.paths, &state.client, &mut NoOp)
+			.await;
+		if let Ok(results) = results {
+			let packages = results.results.into_iter().map(|id| {
+				let meta = results.previews.get(&id).map(|x| &x.0);
+				GlobalSearchResult {
+					kind: GlobalSearchResultKind::Package,
+					name: meta.and_then(|x| x.name.clone()),
+					icon: meta.and_then(|x| x.icon.clone()),
+					id,
+					plugin: None,
+				}
+			});
+			out.extend(packages);
+		}
+	}
+
+	Ok(out)
+}
+
+/// Gets the config for searching, which is only reloaded when the config files have changed
+/// since searches are run as the user types
+async fn get_search_config(state: &State) -> anyhow::Result<Arc<Config>> {
+	let mut cache = state.search_config.lock().await;
+	if let Some((watcher, config)) = cache.as_mut()
+		&& !watcher.check()
+	{
+		return Ok(config.clone());
+	}
+
+	// Don't keep an outdated config around if loading the new one fails
+	*cache = None;
+
+	// Start watching before loading so that changes made while loading cause another reload
+	let watcher = ConfigWatcher::new(&state.paths);
+	let config = load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+		.await
+		.context("Failed to load config")?;
+	let config = Arc::new(config);
+	*cache = Some((watcher, config.clone()));
+
+	Ok(config)
+}
+
+/// A single result from the global search
+#[derive(Serialize)]
+pub struct GlobalSearchResult {
+	pub kind: GlobalSearchResultKind,
+	pub id: String,
+	pub name: Option<String>,
+	pub icon: Option<String>,
+	/// The plugin that provides this result, for plugin actions
+	pub plugin: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GlobalSearchResultKind {
+	Instance,
+	Template,
+	Package,
+	PluginAction,
+}
@@ -1,7 +1,18 @@
+use std::path::PathBuf;
+
+use crate::data::LauncherData;
 use crate::{State, commands::fmt_err};
 
+use anyhow::Context;
+use nitro_frontend_api::cache as cache_api;
+use nitro_frontend_api::theme::validate_custom_theme;
+use nitrolaunch::core::io::{json_from_file, json_to_file_replace};
+use nitrolaunch::plugin_crate::hook::hooks::Theme;
+use nitrolaunch::{io::cache::CacheUsage, shared::output::NoOp};
 use serde::{Deserialize, Serialize};
 
+use super::load_config;
+
 #[tauri::command]
 pub async fn get_settings(state: tauri::State<'_, State>) -> Result<Settings, String> {
 	let data = state.data.lock().await;
@@ -9,6 +20,8 @@ pub async fn get_settings(state: tauri::State<'_, State>) -> Result<Settings, St
 	Ok(Settings {
 		base_theme: data.base_theme.clone(),
 		overlay_themes: data.overlay_themes.clone(),
+		ui_scale: data.ui_scale,
+		font_scale: data.font_scale,
 	})
 }
 
@@ -21,15 +34,112 @@ pub async fn write_settings(
 
 	data.base_theme = settings.base_theme;
 	data.overlay_themes = settings.overlay_themes;
+	data.ui_scale = settings.ui_scale;
+	data.font_scale = settings.font_scale;
+
+	fmt_err(data.write(&state.paths))?;
+
+	Ok(())
+}
+
+#[tauri::command]
+pub async fn get_custom_themes(state: tauri::State<'_, State>) -> Result<Vec<Theme>, String> {
+	let data = state.data.lock().await;
+
+	Ok(data.custom_themes.clone())
+}
+
+/// Adds a custom theme, replacing the existing one with the same ID
+#[tauri::command]
+pub async fn save_custom_theme(state: tauri::State<'_, State>, theme: Theme) -> Result<(), String> {
+	fmt_err(validate_custom_theme(&theme))?;
+
+	let mut data = state.data.lock().await;
+	add_custom_theme(&mut data, theme);
+	fmt_err(data.write(&state.paths))?;
+
+	Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_custom_theme(state: tauri::State<'_, State>, id: &str) -> Result<(), String> {
+	let mut data = state.data.lock().await;
+
+	data.custom_themes.retain(|x| x.id != id);
+	if data.base_theme.as_deref() == Some(id) {
+		data.base_theme = None;
+	}
+	data.overlay_themes.retain(|x| x != id);
+
+	fmt_err(data.write(&state.paths))?;
 
+	Ok(())
+}
+
+/// Imports a theme file as a custom theme, returning the imported theme
+#[tauri::command]
+pub async fn import_custom_theme(
+	state: tauri::State<'_, State>,
+	path: PathBuf,
+) -> Result<Theme, String> {
+	let theme: Theme = fmt_err(json_from_file(path).context("Failed to read theme file"))?;
+	fmt_err(validate_custom_theme(&theme))?;
+
+	let mut data = state.data.lock().await;
+	add_custom_theme(&mut data, theme.clone());
 	fmt_err(data.write(&state.paths))?;
 
+	Ok(theme)
+}
+
+#[tauri::command]
+pub async fn export_custom_theme(
+	state: tauri::State<'_, State>,
+	id: &str,
+	path: PathBuf,
+) -> Result<(), String> {
+	let data = state.data.lock().await;
+	let Some(theme) = data.custom_themes.iter().find(|x| x.id == id) else {
+		return Err(format!("Custom theme '{id}' does not exist"));
+	};
+
+	fmt_err(json_to_file_replace(path, theme).context("Failed to write theme file"))?;
+
 	Ok(())
 }
 
+fn add_custom_theme(data: &mut LauncherData, theme: Theme) {
+	if let Some(existing) = data.custom_themes.iter_mut().find(|x| x.id == theme.id) {
+		*existing = theme;
+	} else {
+		data.custom_themes.push(theme);
+	}
+}
+
+#[tauri::command]
+pub async fn get_cache_usage(state: tauri::State<'_, State>) -> Result<CacheUsage, String> {
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
+			.await
+			.context("Failed to load config"),
+	)?;
+
+	fmt_err(cache_api::get_cache_usage(&config, &state.paths))
+}
+
+#[tauri::command]
+pub async fn set_cache_limit(
+	state: tauri::State<'_, State>,
+	limit: Option<u64>,
+) -> Result<(), String> {
+	fmt_err(cache_api::set_cache_limit(limit, &state.paths, &mut NoOp).await)
+}
+
 /// Combination of config and launcher data to represent global settings
 #[derive(Serialize, Deserialize)]
 pub struct Settings {
 	pub base_theme: Option<String>,
 	pub overlay_themes: Vec<String>,
+	pub ui_scale: Option<f64>,
+	pub font_scale: Option<f64>,
 }
@@ -2,20 +2,13 @@ use std::path::PathBuf;
 
 use crate::State;
 use crate::commands::instance::write_instance_config;
+use crate::commands::misc::add_saved_icon;
 use crate::output::LauncherOutput;
 use anyhow::Context;
-use nitrolaunch::config::Config;
-use nitrolaunch::config::modifications::{ConfigModification, apply_modifications_and_write};
-use nitrolaunch::instance::update::manager::UpdateSettings;
-use nitrolaunch::instance::{Instance, transfer};
-use nitrolaunch::io::lock::Lockfile;
-use nitrolaunch::pkg_crate::{PkgRequest, PkgRequestSource};
-use nitrolaunch::plugin_crate::hook::hooks::{
-	AddInstanceTransferFormats, CheckMigration, CheckMigrationResult, InstanceTransferFormat,
-};
-use nitrolaunch::shared::id::InstanceID;
-use nitrolaunch::shared::output::NoOp;
-use nitrolaunch::shared::{Side, UpdateDepth};
+use nitro_frontend_api::transfer as transfer_api;
+use nitrolaunch::plugin_crate::hook::hooks::{CheckMigrationResult, InstanceTransferFormat};
+use nitrolaunch::shared::Side;
+use nitrolaunch::shared::output::{MessageContents, NitroOutput, NoOp};
 
 use super::{fmt_err, load_config};
 
@@ -32,16 +25,7 @@ pub async fn get_instance_transfer_formats(
 			.context("Failed to load config"),
 	)?;
 
-	let results = fmt_err(
-		config
-			.plugins
-			.call_hook(AddInstanceTransferFormats, &(), &state.paths, &mut output)
-			.await,
-	)?;
-
-	let out = fmt_err(results.flatten_all_results(&mut output).await)?;
-
-	Ok(out)
+	fmt_err(transfer_api::get_instance_transfer_formats(&config, &state.paths, &mut output).await)
 }
 
 #[tauri::command]
@@ -51,6 +35,7 @@ pub async fn import_instance(
 	format: String,
 	path: String,
 	id: String,
+	side: Option<Side>,
 ) -> Result<(), String> {
 	let mut output = LauncherOutput::new(state.get_output(app_handle.clone()));
 	output.set_task("import_instance");
@@ -61,25 +46,52 @@ pub async fn import_instance(
 			.context("Failed to load config"),
 	)?;
 
-	let formats = fmt_err(
-		transfer::load_formats(&config.plugins, &state.paths, &mut output)
+	let config = fmt_err(
+		transfer_api::import_instance(
+			&config,
+			&id,
+			&format,
+			&PathBuf::from(path),
+			side,
+			&state.paths,
+			&mut output,
+		)
+		.await,
+	)?;
+
+	write_instance_config(state, id, config, app_handle).await
+}
+
+#[tauri::command]
+pub async fn import_instance_from_url(
+	state: tauri::State<'_, State>,
+	app_handle: tauri::AppHandle,
+	format: Option<String>,
+	url: String,
+	id: String,
+	side: Option<Side>,
+) -> Result<(), String> {
+	let mut output = LauncherOutput::new(state.get_output(app_handle.clone()));
+	output.set_task("import_instance");
+
+	let config = fmt_err(
+		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
 			.await
-			.context("Failed to load transfer formats"),
+			.context("Failed to load config"),
 	)?;
 
 	let config = fmt_err(
-		Instance::import(
+		transfer_api::import_instance_from_url(
+			&config,
 			&id,
-			&format,
-			&PathBuf::from(path),
-			None,
-			&formats,
-			&config.plugins,
+			format,
+			&url,
+			side,
 			&state.paths,
+			&state.client,
 			&mut output,
 		)
-		.await
-		.context("Failed to import instance"),
+		.await,
 	)?;
 
 	write_instance_config(state, id, config, app_handle).await
@@ -102,34 +114,17 @@ pub async fn export_instance(
 			.context("Failed to load config"),
 	)?;
 
-	let formats = fmt_err(
-		transfer::load_formats(&config.plugins, &state.paths, &mut output)
-			.await
-			.context("Failed to load transfer formats"),
-	)?;
-
-	let Some(instance) = config.instances.get_mut(&InstanceID::from(id)) else {
-		return Err("Instance does not exist".into());
-	};
-
-	let lock = fmt_err(Lockfile::open(&state.paths).context("Failed to open lockfile"))?;
-
 	fmt_err(
-		instance
-			.export(
-				&format,
-				&PathBuf::from(path),
-				&formats,
-				&config.plugins,
-				&lock,
-				&state.paths,
-				&mut output,
-			)
-			.await
-			.context("Failed to export instance"),
-	)?;
-
-	Ok(())
+		transfer_api::export_instance(
+			&mut config,
+			&id,
+			&format,
+			&PathBuf::from(path),
+			&state.paths,
+			&mut output,
+		)
+		.await,
+	)
 }
 
 #[tauri::command]
@@ -146,20 +141,7 @@ pub async fn check_migration(
 			.context("Failed to load config"),
 	)?;
 
-	let result = fmt_err(
-		config
-			.plugins
-			.call_hook(
-				CheckMigration,
-				&format.to_string(),
-				&state.paths,
-				&mut output,
-			)
-			.await,
-	)?;
-	let result = fmt_err(result.first_some(&mut output).await)?;
-
-	Ok(result)
+	fmt_err(transfer_api::check_migration(&config, format, &state.paths, &mut output).await)
 }
 
 #[tauri::command]
@@ -179,49 +161,17 @@ pub async fn migrate_instances(
 			.context("Failed to load config"),
 	)?;
 
-	let formats = fmt_err(
-		transfer::load_formats(&config.plugins, &state.paths, &mut output)
-			.await
-			.context("Failed to load transfer formats"),
-	)?;
-
-	let instances = fmt_err(
-		nitrolaunch::instance::transfer::migrate_instances(
+	fmt_err(
+		transfer_api::migrate_instances(
+			&config,
 			format,
 			instances,
 			link,
-			&formats,
-			&config.plugins,
 			&state.paths,
 			&mut output,
 		)
-		.await
-		.context("Failed to migrate instances"),
-	)?;
-
-	let mut config2 =
-		fmt_err(Config::open(&Config::get_path(&state.paths)).context("Failed to load config"))?;
-
-	let count = instances.len();
-
-	let modifications: Vec<_> = instances
-		.into_iter()
-		.map(|(id, config)| ConfigModification::AddInstance(id.into(), config))
-		.collect();
-
-	fmt_err(
-		apply_modifications_and_write(
-			&mut config2,
-			modifications,
-			&state.paths,
-			&config.plugins,
-			&mut NoOp,
-		)
-		.await
-		.context("Failed to modify and write config"),
-	)?;
-
-	Ok(count)
+		.await,
+	)
 }
 
 #[tauri::command]
@@ -234,50 +184,31 @@ pub async fn install_modpack_package(
 	let mut output = LauncherOutput::new(state.get_output(app_handle.clone()));
 	output.set_task("install_modpack");
 
-	let req = PkgRequest::parse(modpack, PkgRequestSource::UserRequire).arc();
-
 	let config = fmt_err(
 		load_config(&state.paths, &state.wasm_loader, &mut NoOp)
 			.await
 			.context("Failed to load config"),
 	)?;
 
-	let core = fmt_err(
-		config
-			.get_core(
-				None,
-				&UpdateSettings {
-					depth: UpdateDepth::Shallow,
-					offline_auth: true,
-				},
-				&state.client,
-				&config.plugins,
-				&state.paths,
-				&mut NoOp,
-			)
-			.await,
-	)?;
-
-	let version_manifest = fmt_err(
-		core.get_version_manifest(None, UpdateDepth::Full, &mut NoOp)
-			.await,
-	)?;
-
-	let config = fmt_err(
-		Instance::create_from_modpack_package(
+	let (instance_config, icon) = fmt_err(
+		transfer_api::create_instance_from_modpack(
+			&config,
 			instance_id,
-			&req,
-			Side::Client,
-			version_manifest.list.clone(),
-			&config.packages,
-			&config.plugins,
-			&state.client,
+			modpack,
 			&state.paths,
+			&state.client,
 			&mut output,
 		)
-		.await
-		.context("Failed to import the new instance"),
+		.await,
 	)?;
 
-	write_instance_config(state, instance_id.to_string(), config, app_handle).await
+	if let Some(icon) = icon
+		&& let Err(e) = add_saved_icon(&state, icon).await
+	{
+		output.display(MessageContents::Warning(format!(
+			"Failed to save modpack icon: {e:?}"
+		)));
+	}
+
+	write_instance_config(state, instance_id.to_string(), instance_config, app_handle).await
 }
@@ -1,12 +1,13 @@
 use std::collections::HashSet;
 use std::{collections::HashMap, path::PathBuf};
 
-use nitrolaunch::core::io::{json_from_file, json_to_file};
+use nitrolaunch::core::io::{json_from_file, json_to_file_replace};
 use nitrolaunch::io::paths::Paths;
+use nitrolaunch::plugin_crate::hook::hooks::Theme;
 use serde::{Deserialize, Serialize};
 
 use crate::commands::instance::InstanceOrTemplate;
-use crate::output::SerializableResolutionError;
+use crate::output::ExplainedResolutionError;
 
 /// Stored launcher data
 #[derive(Serialize, Deserialize, Default)]
@@ -24,7 +25,7 @@ pub struct LauncherData {
 	/// The last selected package repository
 	pub last_repository: Option<String>,
 	/// The last package resolution error associated with instances
-	pub last_resolution_errors: HashMap<String, SerializableResolutionError>,
+	pub last_resolution_errors: HashMap<String, ExplainedResolutionError>,
 	/// The instance or template where a package was last added to
 	pub last_added_package: Option<(String, InstanceOrTemplate)>,
 	/// The instance or template that was last opened
@@ -34,6 +35,14 @@ pub struct LauncherData {
 	pub base_theme: Option<String>,
 	/// The currently selected overlay themes
 	pub overlay_themes: Vec<String>,
+	/// Themes that the user created in the launcher
+	pub custom_themes: Vec<Theme>,
+	/// Zoom factor for the whole UI. Defaults to 1.0
+	pub ui_scale: Option<f64>,
+	/// Multiplier for the font size. Defaults to 1.0
+	pub font_scale: Option<f64>,
+	/// How the instance list is sorted and displayed
+	pub instance_list: InstanceListPreferences,
 }
 
 impl LauncherData {
@@ -49,7 +58,7 @@ impl LauncherData {
 
 	/// Write the launcher data
 	pub fn write(&self, paths: &Paths) -> anyhow::Result<()> {
-		json_to_file(Self::path(paths), &self)
+		json_to_file_replace(Self::path(paths), &self)
 	}
 
 	/// Get the path to the launcher file
@@ -59,10 +68,65 @@ impl LauncherData {
 }
 
 /// Different icons for instances
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 pub enum InstanceIcon {
 	/// A custom user icon at a path
 	File(PathBuf),
 }
+
+/// Display preferences for the instance list
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct InstanceListPreferences {
+	/// How instances are sorted
+	pub sort: InstanceSort,
+	/// How instances are split into sections
+	pub group_by: InstanceGrouping,
+	/// How instances are laid out
+	pub layout: InstanceListLayout,
+}
+
+/// Method for sorting instances in the instance list
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceSort {
+	/// Alphabetically by name
+	#[default]
+	Name,
+	/// Most recently played first
+	LastPlayed,
+	/// Most playtime first
+	Playtime,
+	/// Newest Minecraft version first
+	Version,
+	/// By loader
+	Loader,
+}
+
+/// Method for grouping instances into sections in the instance list
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceGrouping {
+	/// Only the user's instance groups are shown as sections
+	#[default]
+	None,
+	/// One section per Minecraft version
+	Version,
+	/// One section per loader
+	Loader,
+	/// One section for clients and one for servers
+	Side,
+}
+
+/// Layout of the instance list
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceListLayout {
+	/// Large tiles in a grid
+	#[default]
+	Grid,
+	/// Compact rows in a list
+	Compact,
+}
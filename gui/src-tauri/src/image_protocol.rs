@@ -0,0 +1,79 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use nitro_frontend_api::image_cache::{self, ImageResolution, ImageSource};
+use tauri::http::{Request, Response, StatusCode, header};
+use tauri::{Manager, Runtime, UriSchemeContext, UriSchemeResponder, Url};
+
+use crate::State;
+
+/// The name of the protocol that cached images are loaded from
+pub const IMAGE_PROTOCOL: &str = "nitro-image";
+
+/// How long the webview can keep cached images without asking for them again, in seconds
+const MAX_AGE: u64 = 60 * 60 * 24 * 7;
+
+/// Handles a request for an image from the frontend. The request has the source of the image in the `src`
+/// query parameter, and the preferred resolution in the `resolution` parameter
+pub fn handle_request<R: Runtime>(
+	ctx: UriSchemeContext<'_, R>,
+	request: Request<Vec<u8>>,
+	responder: UriSchemeResponder,
+) {
+	let state = ctx.app_handle().state::<State>().inner().clone();
+	let uri = request.uri().to_string();
+	tauri::async_runtime::spawn(async move {
+		let response = match get_image(&uri, &state).await {
+			Ok((path, bytes)) => Response::builder()
+				.status(StatusCode::OK)
+				.header(header::CONTENT_TYPE, get_content_type(&path))
+				.header(header::CACHE_CONTROL, format!("max-age={MAX_AGE}"))
+				.body(bytes),
+			Err(e) => Response::builder()
+				.status(StatusCode::NOT_FOUND)
+				.body(format!("{e:?}").into_bytes()),
+		};
+
+		match response {
+			Ok(response) => responder.respond(response),
+			Err(e) => eprintln!("Failed to create image response: {e}"),
+		}
+	});
+}
+
+/// Gets the cached file and contents of the image that a request is for
+async fn get_image(uri: &str, state: &State) -> anyhow::Result<(PathBuf, Vec<u8>)> {
+	let url = Url::parse(uri).context("Invalid image URL")?;
+	let mut src = None;
+	let mut resolution = ImageResolution::Full;
+	for (key, value) in url.query_pairs() {
+		match key.as_ref() {
+			"src" => src = Some(ImageSource::parse(&value)),
+			"resolution" => {
+				resolution = ImageResolution::parse(&value).context("Invalid image resolution")?
+			}
+			_ => {}
+		}
+	}
+	let src = src.context("Image source is missing")?;
+
+	let path = image_cache::get_cached_image(&src, resolution, &state.paths, &state.client)
+		.await
+		.context("Failed to get image")?;
+	let bytes = tokio::fs::read(&path)
+		.await
+		.context("Failed to read cached image")?;
+
+	Ok((path, bytes))
+}
+
+/// Gets the MIME type of an image from its extension
+fn get_content_type(path: &Path) -> &'static str {
+	match path.extension().and_then(|x| x.to_str()) {
+		Some("png") => "image/png",
+		Some("jpg" | "jpeg") => "image/jpeg",
+		Some("gif") => "image/gif",
+		Some("webp") => "image/webp",
+		_ => "application/octet-stream",
+	}
+}
@@ -7,6 +7,8 @@ mod cli;
 mod commands;
 /// Storage and reading for GUI-specific data
 mod data;
+/// Local protocol for loading cached images
+mod image_protocol;
 /// Manager for running instances
 mod instance_manager;
 /// Nitrolaunch output for the launcher frontend
@@ -14,7 +16,6 @@ mod output;
 /// Management of long-running tasks
 mod task_manager;
 
-use std::collections::HashMap;
 use std::path::Path;
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
@@ -22,10 +23,14 @@ use std::time::Duration;
 use anyhow::Context;
 use clap::Parser;
 use data::LauncherData;
+use nitro_frontend_api::account::PasskeyCache;
+use nitrolaunch::config::Config;
+use nitrolaunch::config::watch::ConfigWatcher;
 use nitrolaunch::core::auth_crate::mc::ClientId;
 use nitrolaunch::core::{account::AccountManager, net::download::Client};
 use nitrolaunch::io::logging::Logger;
 use nitrolaunch::io::paths::Paths;
+use nitrolaunch::io::{net_stats, reports};
 use nitrolaunch::plugin_crate::hook::wasm::loader::WASMLoader;
 use nitrolaunch::shared::id::InstanceID;
 use nitrolaunch::shared::io::config::IO_CONFIG;
@@ -37,7 +42,7 @@ use tauri::process::restart;
 use tauri::{AppHandle, Emitter, Listener, Manager};
 
 use crate::cli::Cli;
-use crate::commands::misc::update_version_manifest;
+use crate::commands::misc::{run_maintenance, update_offline_status, update_version_manifest};
 use crate::instance_manager::RunningInstanceManager;
 use crate::output::{
 	LauncherOutput, MessageEvent, MessageType, ResolutionErrorEvent, YesNoPromptResponse,
@@ -53,6 +58,7 @@ fn main() {
 		.expect("Error when initializing application state");
 	let data = state.data.clone();
 	let paths = state.paths.clone();
+	reports::install_panic_hook(&paths);
 
 	if let Ok(mut exec_registry) = NitroExecutableRegistry::open(&paths.internal) {
 		let _ = exec_registry.add_this(NitroClientId::Gui);
@@ -84,7 +90,7 @@ fn main() {
 			});
 
 			// Setup task manager
-			let task_manager = TaskManager::new(app.app_handle().clone());
+			let task_manager = TaskManager::new(app.app_handle().clone(), paths.clone());
 
 			let _ = state2.task_manager.set(Arc::new(Mutex::new(task_manager)));
 
@@ -113,6 +119,9 @@ fn main() {
 				let app_handle = app.app_handle().clone();
 				let app_handle2 = app.app_handle().clone();
 				tauri::async_runtime::spawn(async move {
+					// Check connectivity first so that the manifest update can fall back to the cache
+					update_offline_status(&state.client, &app_handle).await;
+
 					let result = update_version_manifest(app_handle, &state).await;
 					if let Err(e) = result {
 						let _ = app_handle2.emit(
@@ -127,6 +136,68 @@ fn main() {
 				});
 			}
 
+			// Check connectivity periodically, switching to offline mode when it is lost
+			{
+				let client = state2.client.clone();
+				let app_handle = app.app_handle().clone();
+				tauri::async_runtime::spawn(async move {
+					loop {
+						tokio::time::sleep(CONNECTIVITY_CHECK_INTERVAL).await;
+						update_offline_status(&client, &app_handle).await;
+					}
+				});
+			}
+
+			// Lock accounts whose passkeys haven't been used within their auto-lock timeout
+			{
+				let passkeys = state2.passkeys.clone();
+				tauri::async_runtime::spawn(async move {
+					loop {
+						tokio::time::sleep(PASSKEY_LOCK_CHECK_INTERVAL).await;
+						passkeys.lock().await.remove_expired();
+					}
+				});
+			}
+
+			// Periodically save network statistics so that they are kept when the launcher closes
+			{
+				let paths = paths.clone();
+				tauri::async_runtime::spawn(async move {
+					loop {
+						tokio::time::sleep(NET_STATS_SAVE_INTERVAL).await;
+						let _ = net_stats::save(&paths);
+					}
+				});
+			}
+
+			// Run periodic maintenance like update checks, which saves its results as notifications
+			{
+				let state = state2.clone();
+				tauri::async_runtime::spawn(async move {
+					loop {
+						tokio::time::sleep(MAINTENANCE_CHECK_INTERVAL).await;
+						if let Err(e) = run_maintenance(&state).await {
+							eprintln!("Failed to run maintenance: {e:?}");
+						}
+					}
+				});
+			}
+
+			// Reload the frontend when the config is changed by another process, like the CLI
+			{
+				let paths = paths.clone();
+				let app_handle = app.app_handle().clone();
+				tauri::async_runtime::spawn(async move {
+					let mut watcher = ConfigWatcher::new(&paths);
+					loop {
+						tokio::time::sleep(CONFIG_CHECK_INTERVAL).await;
+						if watcher.check() {
+							let _ = app_handle.emit("nitro_config_changed", ());
+						}
+					}
+				});
+			}
+
 			// Save package resolution errors so that they can be displayed on the instance
 			app.listen_any("nitro_display_resolution_error", move |event| {
 				let paths = paths.clone();
@@ -193,6 +264,10 @@ fn main() {
 			Ok(())
 		})
 		.manage(state)
+		.register_asynchronous_uri_scheme_protocol(
+			image_protocol::IMAGE_PROTOCOL,
+			image_protocol::handle_request,
+		)
 		.invoke_handler(tauri::generate_handler![
 			commands::launch::launch_game,
 			commands::launch::answer_password_prompt,
@@ -201,8 +276,12 @@ fn main() {
 			commands::launch::kill_instance,
 			commands::launch::get_instance_output,
 			commands::launch::write_instance_input,
+			commands::launch::send_instance_rcon_command,
 			commands::launch::get_instance_logs,
 			commands::launch::get_instance_log,
+			commands::launch::export_crash_bundle,
+			commands::launch::upload_instance_log,
+			commands::launch::upload_crash_bundle,
 			commands::instance::get_instances,
 			commands::instance::get_templates,
 			commands::instance::get_instance_groups,
@@ -217,26 +296,41 @@ fn main() {
 			commands::instance::write_base_template,
 			commands::instance::update_instance,
 			commands::instance::update_instance_packages,
+			commands::instance::verify_instance,
 			commands::instance::get_instance_resolution_error,
 			commands::instance::delete_instance,
+			commands::instance::sync_instance_icon,
 			commands::instance::delete_template,
 			commands::instance::get_template_users,
 			commands::instance::get_last_opened_instance,
 			commands::instance::set_last_opened_instance,
+			commands::instance::get_instance_list_preferences,
+			commands::instance::set_instance_list_preferences,
 			commands::instance::get_instance_has_updated,
 			commands::instance::canonicalize_version,
 			commands::instance::get_plugins_supporting_creation,
 			commands::instance::get_instance_size,
+			commands::instance::get_instance_inventory,
+			commands::instance::get_outdated_packages,
+			commands::instance::get_instance_advisories,
+			commands::instance::mitigate_instance_advisory,
 			commands::instance::consolidate_instance,
 			commands::instance::duplicate_instance,
 			commands::instance::extract_instance,
+			commands::instance::save_instance_as_template,
+			commands::instance::create_matching_server,
+			commands::instance::sync_linked_instance,
 			commands::package::get_packages,
+			commands::package::search_all_packages,
 			commands::package::preload_packages,
 			commands::package::get_package_meta,
 			commands::package::get_package_props,
 			commands::package::get_package_meta_and_props,
+			commands::package::get_package_details,
+			commands::package::get_package_author,
 			commands::package::get_multiple_package_meta_and_props,
 			commands::package::get_declarative_package_contents,
+			commands::package::get_package_compatibility,
 			commands::package::get_package_repos,
 			commands::package::get_instance_packages,
 			commands::package::sync_packages,
@@ -255,7 +349,13 @@ fn main() {
 			commands::plugin::get_sidebar_buttons,
 			commands::plugin::get_plugin_page,
 			commands::plugin::get_themes,
+			commands::settings::get_custom_themes,
+			commands::settings::save_custom_theme,
+			commands::settings::delete_custom_theme,
+			commands::settings::import_custom_theme,
+			commands::settings::export_custom_theme,
 			commands::plugin::run_custom_action,
+			commands::plugin::run_plugin_page_action,
 			commands::plugin::get_dropdown_buttons,
 			commands::plugin::get_instance_tiles,
 			commands::plugin::get_instance_config_controls,
@@ -268,42 +368,83 @@ fn main() {
 			commands::account::logout_account,
 			commands::account::create_account,
 			commands::account::remove_account,
+			commands::account::lock_account,
+			commands::account::lock_all_accounts,
+			commands::account::get_unlocked_accounts,
+			commands::account::set_account_auto_lock,
 			commands::account::get_supported_account_types,
 			commands::account::get_cosmetics,
 			commands::account::upload_skin,
 			commands::account::activate_cape,
 			commands::account::get_skin_repositories,
 			commands::account::search_skins,
+			commands::realms::get_realms,
+			commands::realms::get_realm_invites,
+			commands::realms::answer_realm_invite,
 			commands::settings::get_settings,
 			commands::settings::write_settings,
+			commands::settings::get_cache_usage,
+			commands::settings::set_cache_limit,
 			commands::transfer::get_instance_transfer_formats,
 			commands::transfer::import_instance,
+			commands::transfer::import_instance_from_url,
 			commands::transfer::export_instance,
 			commands::transfer::check_migration,
 			commands::transfer::migrate_instances,
 			commands::transfer::install_modpack_package,
 			commands::misc::get_supported_loaders,
+			commands::search::global_search,
 			commands::misc::get_loader_versions,
 			commands::misc::get_minecraft_versions,
+			commands::misc::get_minecraft_versions_in_channels,
 			commands::misc::get_is_first_launch,
+			commands::misc::get_offline_status,
 			commands::misc::get_version_banner_images,
+			commands::news::get_minecraft_news,
 			commands::misc::test_long_running_task,
 			commands::misc::open_data_dir,
 			commands::misc::open_instance_dir,
+			commands::misc::open_instance_worlds,
+			commands::misc::open_instance_logs,
+			commands::misc::open_instance_crash_reports,
+			commands::misc::open_instance_screenshots,
 			commands::misc::get_available_icons,
 			commands::misc::save_icon,
 			commands::misc::get_supported_java_types,
 			commands::misc::answer_yes_no_prompt,
 			commands::misc::linux_fixes_needed,
 			commands::misc::get_nitro_version,
+			commands::misc::render_markdown,
+			commands::network::get_download_activity,
+			commands::network::set_downloads_paused,
+			commands::network::set_bandwidth_limit,
+			commands::network::get_network_stats,
+			commands::network::reset_network_stats,
 			commands::cancel_task,
 			commands::get_logs,
 			commands::get_log,
+			commands::get_error_reports,
+			commands::delete_error_report,
+			commands::clear_error_reports,
+			commands::get_notifications,
+			commands::mark_notifications_read,
+			commands::clear_notifications,
 		])
 		.run(tauri::generate_context!())
 		.expect("Error while running tauri application");
 }
 
+/// How often to check whether we are online
+const CONNECTIVITY_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// How often to check whether the config has changed on disk
+const CONFIG_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// How often to lock accounts whose passkeys have expired
+const PASSKEY_LOCK_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// How often to save network statistics
+const NET_STATS_SAVE_INTERVAL: Duration = Duration::from_secs(60);
+/// How often to check for maintenance tasks that are due
+const MAINTENANCE_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 15);
+
 /// State for the Tauri application
 #[derive(Clone)]
 pub struct State {
@@ -314,33 +455,44 @@ pub struct State {
 	pub paths: Paths,
 	pub client: Client,
 	pub account_manager: Arc<Mutex<AccountManager>>,
-	/// Map of accounts to their already entered passkeys
-	pub passkeys: Arc<Mutex<HashMap<String, String>>>,
+	/// Passkeys that have already been entered for accounts
+	pub passkeys: Arc<Mutex<PasskeyCache>>,
 	pub password_prompt: PromptResponse,
 	pub yes_no_prompt: YesNoPromptResponse,
 	pub output_inner: Arc<OnceLock<OutputInner>>,
 	pub logging_sender: Sender<Message>,
 	pub wasm_loader: Arc<Mutex<WASMLoader>>,
+	/// Config that is reused by the global search until the config files change
+	pub search_config: Arc<Mutex<Option<(ConfigWatcher, Arc<Config>)>>>,
 }
 
 impl State {
 	async fn new(logging_sender: Sender<Message>) -> anyhow::Result<Self> {
 		let paths = Paths::new().await?;
+		let data = LauncherData::open(&paths).context("Failed to open launcher data")?;
+		// The proxy is only read on startup, so the launcher has to be restarted for changes to it to apply
+		let client = match Config::create_client(&paths) {
+			Ok(client) => client,
+			Err(e) => {
+				eprintln!("Proxy will not be used: {e:?}");
+				Client::new()
+			}
+		};
+
 		Ok(Self {
-			data: Arc::new(Mutex::new(
-				LauncherData::open(&paths).context("Failed to open launcher data")?,
-			)),
+			data: Arc::new(Mutex::new(data)),
 			wasm_loader: Arc::new(Mutex::new(WASMLoader::new(&paths.data))),
 			running_instances: Arc::new(OnceLock::new()),
 			task_manager: Arc::new(OnceLock::new()),
 			paths,
-			client: Client::new(),
+			client,
 			account_manager: Arc::new(Mutex::new(AccountManager::new(get_ms_client_id()))),
-			passkeys: Arc::new(Mutex::new(HashMap::new())),
+			passkeys: Arc::new(Mutex::new(PasskeyCache::new())),
 			password_prompt: PromptResponse::new(Mutex::new(None)),
 			yes_no_prompt: YesNoPromptResponse::new(Mutex::new(None)),
 			output_inner: Arc::new(OnceLock::new()),
 			logging_sender,
+			search_config: Arc::new(Mutex::new(None)),
 		})
 	}
 
@@ -354,6 +506,7 @@ impl State {
 			password_prompt: self.password_prompt.clone(),
 			yes_no_prompt: self.yes_no_prompt.clone(),
 			passkeys: self.passkeys.clone(),
+			paths: self.paths.clone(),
 			logger: self.logging_sender.clone(),
 		})
 	}
@@ -1,10 +1,12 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{sync::Arc, time::Duration};
 
 use anyhow::Context;
+use nitro_frontend_api::account::{PasskeyCache, get_auto_lock_timeout};
+use nitrolaunch::io::paths::Paths;
 use nitrolaunch::shared::id::InstanceID;
 use nitrolaunch::shared::lang::translate::TranslationKey;
 use nitrolaunch::shared::output::{Message, MessageContents, MessageLevel, NitroOutput};
-use nitrolaunch::shared::pkg::{ArcPkgReq, PackageDiff, ResolutionError};
+use nitrolaunch::shared::pkg::{ArcPkgReq, PackageDiff, ResolutionError, ResolutionFix};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
 use tokio::sync::{Mutex, mpsc::Sender};
@@ -158,15 +160,17 @@ impl NitroOutput for LauncherOutput {
 		account_id: &str,
 	) -> anyhow::Result<String> {
 		{
-			let passkeys = self.inner.passkeys.lock().await;
+			let mut passkeys = self.inner.passkeys.lock().await;
 			if let Some(existing) = passkeys.get(account_id) {
-				return Ok(existing.clone());
+				return Ok(existing);
 			}
 		}
 
 		let result = self.prompt_password(message).await?;
+		let auto_lock = get_auto_lock_timeout(&self.inner.paths, account_id)
+			.context("Failed to get auto-lock timeout for account")?;
 		let mut passkeys = self.inner.passkeys.lock().await;
-		passkeys.insert(account_id.into(), result.clone());
+		passkeys.insert(account_id, result.clone(), auto_lock);
 		Ok(result)
 	}
 
@@ -232,7 +236,7 @@ impl NitroOutput for LauncherOutput {
 
 	fn display_special_resolution_error(&mut self, error: ResolutionError, instance_id: &str) {
 		eprintln!("Resolution error: {error}");
-		let error = SerializableResolutionError::from_err(error);
+		let error = ExplainedResolutionError::from_err(error);
 
 		let payload = ResolutionErrorEvent {
 			error,
@@ -276,6 +280,17 @@ impl NitroOutput for LauncherOutput {
 		let _ = self.inner.app.emit("nitro_output_end_section", &self.task);
 	}
 
+	fn emit_plugin_event(&mut self, plugin: &str, event: &str, data: serde_json::Value) {
+		let _ = self.inner.app.emit(
+			"nitro_plugin_event",
+			PluginEvent {
+				plugin: plugin.to_string(),
+				event: event.to_string(),
+				data,
+			},
+		);
+	}
+
 	fn get_lesser_copy(&self) -> Box<dyn NitroOutput + Sync> {
 		Box::new(Self::new(&self.inner))
 	}
@@ -306,7 +321,8 @@ pub struct OutputInner {
 	pub app: Arc<AppHandle>,
 	pub password_prompt: PromptResponse,
 	pub yes_no_prompt: YesNoPromptResponse,
-	pub passkeys: Arc<Mutex<HashMap<String, String>>>,
+	pub passkeys: Arc<Mutex<PasskeyCache>>,
+	pub paths: Paths,
 	pub logger: Sender<Message>,
 }
 
@@ -335,13 +351,40 @@ pub struct AuthDisplayEvent {
 	device_code: String,
 }
 
+/// Event sent from a plugin to its pages
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PluginEvent {
+	pub plugin: String,
+	pub event: String,
+	pub data: serde_json::Value,
+}
+
 /// Event for a package resolution error
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ResolutionErrorEvent {
-	pub error: SerializableResolutionError,
+	pub error: ExplainedResolutionError,
 	pub instance: String,
 }
 
+/// A resolution error along with suggestions for fixing it
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExplainedResolutionError {
+	#[serde(flatten)]
+	pub error: SerializableResolutionError,
+	#[serde(default)]
+	pub fixes: Vec<ResolutionFix>,
+}
+
+impl ExplainedResolutionError {
+	pub fn from_err(err: ResolutionError) -> Self {
+		let fixes = err.suggest_fixes();
+		Self {
+			error: SerializableResolutionError::from_err(err),
+			fixes,
+		}
+	}
+}
+
 /// A serializable ResolutionError
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -1,21 +1,31 @@
 use std::{sync::Arc, time::Duration};
 
+use nitrolaunch::io::{
+	notifications::{NotificationKind, notify},
+	paths::Paths,
+	reports,
+};
 use tauri::{AppHandle, Emitter};
 use tokio::{sync::Mutex, task::JoinHandle};
 
 use crate::output::{MessageEvent, MessageType};
 
+/// Prefixes of task IDs for work that the user is waiting on and that background work should make way for
+const FOREGROUND_TASKS: &[&str] = &["update_instance", "verify_instance", "launch_instance"];
+
 /// Manager for long-running tasks
 pub struct TaskManager {
 	tasks: Vec<RunningTask>,
 	app_handle: AppHandle,
+	paths: Paths,
 }
 
 impl TaskManager {
-	pub fn new(app_handle: AppHandle) -> Self {
+	pub fn new(app_handle: AppHandle, paths: Paths) -> Self {
 		Self {
 			tasks: Vec::new(),
 			app_handle,
+			paths,
 		}
 	}
 
@@ -47,6 +57,14 @@ impl TaskManager {
 					let result = join_handle.await;
 					if let Ok(Err(error)) = result {
 						eprintln!("Error: {error:?}");
+						reports::report_error(&task.id, &error, &self.paths);
+						let _ = notify(
+							&self.paths,
+							NotificationKind::TaskFailed,
+							"Task failed",
+							format!("{error}"),
+							Some(task.id.as_str()),
+						);
 						let _ = self.app_handle.emit(
 							"nitro_output_message",
 							MessageEvent {
@@ -65,6 +83,13 @@ impl TaskManager {
 		self.tasks.retain(|x| x.join_handle.is_some());
 	}
 
+	/// Checks whether any tasks that the user is waiting on, like updates, are running
+	pub fn has_foreground_tasks(&self) -> bool {
+		self.tasks
+			.iter()
+			.any(|task| FOREGROUND_TASKS.iter().any(|x| task.id.starts_with(x)))
+	}
+
 	/// Kills a task
 	pub fn kill(&mut self, task_id: &str) {
 		self.tasks.retain(|task| {
@@ -35,6 +35,8 @@ import PackageDiffsPrompt, {
 	PackageDiff,
 } from "./components/package/PackageDiffsPrompt";
 import Tips from "./components/dialog/Tips";
+import CommandPalette from "./components/dialog/CommandPalette";
+import OfflineBanner from "./components/navigation/OfflineBanner";
 
 export default function App() {
 	const [footerData, setFooterData] = createSignal<FooterData>({
@@ -166,6 +168,7 @@ function Layout(props: LayoutProps) {
 				onSidebarClose={() => setShowSidebar(false)}
 				location={props.location}
 			/>
+			<OfflineBanner />
 			<ErrorBoundary
 				fallback={
 					<div>An error occurred in the page. Please report this issue.</div>
@@ -206,6 +209,7 @@ function Layout(props: LayoutProps) {
 				diffs={packageDiffsPrompt()}
 				onClose={() => setPackageDiffsPrompt(undefined)}
 			/>
+			<CommandPalette />
 		</>
 	);
 }
@@ -34,6 +34,7 @@ export default function Global(props: GlobalProps) {
 				setBaseTheme(settings.base_theme);
 			}
 			setOverlayThemes(settings.overlay_themes);
+			applyScale(settings);
 
 			return availableThemes;
 		} catch (e) {
@@ -77,6 +78,16 @@ export default function Global(props: GlobalProps) {
 
 export interface GlobalProps {}
 
+// Applies the UI and font scale settings to the whole document
+function applyScale(settings: LauncherSettings) {
+	let root = document.documentElement;
+	root.style.zoom = `${settings.ui_scale == undefined ? 1 : settings.ui_scale}`;
+	// Most sizes are in rems, so this scales text along with the elements around it
+	let fontScale = settings.font_scale == undefined ? 1 : settings.font_scale;
+	root.style.fontSize = `${16 * fontScale}px`;
+	root.style.lineHeight = `${24 * fontScale}px`;
+}
+
 // Fixes the CSS of a theme by replacing local asset URLs with Tauri asset URLs
 function fixTheme(css: string): string {
 	let regex = /url\(\//g;
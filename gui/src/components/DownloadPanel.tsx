@@ -0,0 +1,199 @@
+import { invoke } from "@tauri-apps/api/core";
+import {
+	createResource,
+	createSignal,
+	For,
+	onCleanup,
+	onMount,
+	Show,
+} from "solid-js";
+import "./DownloadPanel.css";
+import { Download, Play, Stop } from "../icons";
+import IconButton from "./input/button/IconButton";
+import IconTextButton from "./input/button/IconTextButton";
+import ProgressBar from "./ProgressBar";
+import { errorToast } from "./dialog/Toasts";
+import { formatBytes } from "../utils";
+import { emptyUndefined } from "../utils/values";
+
+// Button in the footer that shows all current downloads in a popup
+export default function DownloadPanel() {
+	let [isOpen, setIsOpen] = createSignal(false);
+
+	let [activity, activityMethods] = createResource(async () => {
+		try {
+			return (await invoke("get_download_activity")) as DownloadActivitySummary;
+		} catch (e) {
+			console.error(e);
+			return undefined;
+		}
+	});
+
+	// Keep the activity up to date. It is polled more often while the panel is open.
+	let timeout: number | undefined;
+	let poll = () => {
+		activityMethods.refetch();
+		timeout = setTimeout(poll, isOpen() ? 500 : 2000);
+	};
+	onMount(poll);
+	onCleanup(() => clearTimeout(timeout));
+
+	let isActive = () =>
+		activity() != undefined && activity()!.downloads.length > 0;
+
+	let setPaused = async (paused: boolean) => {
+		try {
+			await invoke("set_downloads_paused", { paused: paused });
+			activityMethods.refetch();
+		} catch (e) {
+			errorToast("Failed to pause downloads: " + e);
+		}
+	};
+
+	// The bandwidth limit in the input, in KB/s
+	let [limit, setLimit] = createSignal<string | undefined>();
+
+	let saveLimit = async (value: string) => {
+		let kilobytes = +value;
+		if (isNaN(kilobytes) || kilobytes < 0) {
+			errorToast("Bandwidth limit must be a positive number");
+			return;
+		}
+
+		try {
+			await invoke("set_bandwidth_limit", {
+				limit: kilobytes == 0 ? undefined : Math.round(kilobytes * 1000),
+			});
+			setLimit(undefined);
+			activityMethods.refetch();
+		} catch (e) {
+			errorToast("Failed to set bandwidth limit: " + e);
+		}
+	};
+
+	let displayedLimit = () => {
+		if (limit() != undefined) {
+			return limit();
+		}
+		let current = activity()?.bandwidth_limit;
+		return current == undefined ? undefined : `${current / 1000}`;
+	};
+
+	return (
+		<div class="cont" id="download-panel">
+			<IconButton
+				icon={Download}
+				size="1.5rem"
+				color={isActive() ? "var(--templatebg)" : "var(--bg0)"}
+				iconColor={isActive() ? "var(--template)" : "var(--fg3)"}
+				border={isActive() ? "var(--template)" : undefined}
+				hoverBackground="var(--bg3)"
+				label="Downloads"
+				circle
+				onClick={() => {
+					setIsOpen(!isOpen());
+					activityMethods.refetch();
+				}}
+			/>
+			<Show when={isOpen() && activity() != undefined}>
+				<div class="cont col" id="download-panel-popup">
+					<div class="split fullwidth" id="download-panel-header">
+						<div class="cont start bold">
+							<Show when={activity()!.paused} fallback="Downloads">
+								Downloads (Paused)
+							</Show>
+						</div>
+						<div class="cont end">
+							<IconTextButton
+								icon={activity()!.paused ? Play : Stop}
+								size="1rem"
+								text={activity()!.paused ? "Resume" : "Pause"}
+								onClick={() => setPaused(!activity()!.paused)}
+							/>
+						</div>
+					</div>
+					<div class="split fullwidth download-panel-stats">
+						<div class="cont start">
+							{formatBytes(activity()!.total_speed)}/s
+						</div>
+						<div class="cont end">
+							{formatBytes(activity()!.total_downloaded)} total
+						</div>
+					</div>
+					<div class="cont col fullwidth" id="download-panel-list">
+						<For
+							each={activity()!.downloads}
+							fallback={
+								<div class="cont" style="color:var(--fg3)">
+									Nothing is downloading
+								</div>
+							}
+						>
+							{(download) => (
+								<div class="cont col fullwidth download-panel-entry">
+									<div class="split fullwidth">
+										<div class="download-panel-name" title={download.url}>
+											{getFileName(download.url)}
+										</div>
+										<div class="cont end download-panel-entry-stats">
+											<Show
+												when={download.total != undefined}
+												fallback={formatBytes(download.downloaded)}
+											>
+												{formatBytes(download.downloaded)} /{" "}
+												{formatBytes(download.total!)}
+											</Show>
+											{" - "}
+											{formatBytes(download.speed)}/s
+										</div>
+									</div>
+									<Show when={download.total != undefined && download.total > 0}>
+										<ProgressBar
+											value={download.downloaded / download.total!}
+											color="var(--template)"
+										/>
+									</Show>
+								</div>
+							)}
+						</For>
+					</div>
+					<div class="split fullwidth" id="download-panel-limit">
+						<label class="cont start" for="download-panel-limit-input">
+							Bandwidth limit (KB/s)
+						</label>
+						<input
+							id="download-panel-limit-input"
+							placeholder="Unlimited"
+							value={emptyUndefined(displayedLimit())}
+							oninput={(e) => setLimit(e.target.value)}
+							onchange={(e) => saveLimit(e.target.value)}
+						/>
+					</div>
+				</div>
+			</Show>
+		</div>
+	);
+}
+
+// Gets the file name at the end of a URL
+function getFileName(url: string) {
+	let path = url.split("?")[0];
+	let name = path.substring(path.lastIndexOf("/") + 1);
+	return name.length == 0 ? url : name;
+}
+
+export interface DownloadActivitySummary {
+	downloads: DownloadActivity[];
+	total_downloaded: number;
+	total_speed: number;
+	paused: boolean;
+	bandwidth_limit?: number;
+	download_bandwidth_limit?: number;
+}
+
+export interface DownloadActivity {
+	url: string;
+	downloaded: number;
+	total?: number;
+	speed: number;
+}
@@ -0,0 +1,99 @@
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-shell";
+import { createResource, For, Show } from "solid-js";
+import { errorToast } from "./dialog/Toasts";
+import IconTextButton from "./input/button/IconTextButton";
+import { Trash, Upload } from "../icons";
+import "./ErrorReports.css";
+
+// List of locally saved crash and error reports, which can be submitted as GitHub issues
+export default function ErrorReports() {
+	let [reports, reportMethods] = createResource(
+		async () => {
+			try {
+				return (await invoke("get_error_reports")) as ErrorReport[];
+			} catch (e) {
+				errorToast("Failed to fetch error reports: " + e);
+				return [];
+			}
+		},
+		{ initialValue: [] },
+	);
+
+	let deleteReport = async (id: string) => {
+		try {
+			await invoke("delete_error_report", { id: id });
+		} catch (e) {
+			errorToast("Failed to delete report: " + e);
+		}
+		reportMethods.refetch();
+	};
+
+	let clearReports = async () => {
+		try {
+			await invoke("clear_error_reports");
+		} catch (e) {
+			errorToast("Failed to clear reports: " + e);
+		}
+		reportMethods.refetch();
+	};
+
+	return (
+		<div class="cont col fullwidth" id="error-reports">
+			<div class="cont fullwidth" id="error-reports-header">
+				<div class="bold">ERROR REPORTS</div>
+				<Show when={reports().length > 0}>
+					<IconTextButton
+						icon={Trash}
+						size="1rem"
+						text="Clear all"
+						onClick={clearReports}
+					/>
+				</Show>
+			</div>
+			<Show
+				when={reports().length > 0}
+				fallback={
+					<div class="cont" id="error-reports-empty">
+						No reports saved. Set the error_reports preference to true to save
+						reports when something goes wrong. Reports are never sent anywhere
+						unless you submit them.
+					</div>
+				}
+			>
+				<For each={reports()}>
+					{(report) => (
+						<div class="cont fullwidth error-report">
+							<div class="cont col start error-report-info">
+								<div class="bold error-report-title">{report.title}</div>
+								<div class="error-report-date">
+									{new Date(report.timestamp * 1000).toLocaleString()}
+								</div>
+							</div>
+							<IconTextButton
+								icon={Upload}
+								size="1rem"
+								text="Submit as issue"
+								onClick={() => open(report.issue_url)}
+							/>
+							<IconTextButton
+								icon={Trash}
+								size="1rem"
+								text="Delete"
+								onClick={() => deleteReport(report.id)}
+							/>
+						</div>
+					)}
+				</For>
+			</Show>
+		</div>
+	);
+}
+
+export interface ErrorReport {
+	id: string;
+	title: string;
+	body: string;
+	issue_url: string;
+	timestamp: number;
+}
@@ -0,0 +1,115 @@
+import { invoke } from "@tauri-apps/api/core";
+import { createResource, createSignal, For, Show } from "solid-js";
+import "./NewsFeed.css";
+import { Refresh } from "../icons";
+import IconButton from "./input/button/IconButton";
+import Tip from "./dialog/Tip";
+import { getCachedImageSrc } from "../utils";
+
+// Feed of the latest Minecraft release and snapshot news
+export default function NewsFeed() {
+	let [refresh, setRefresh] = createSignal(false);
+
+	let [news, newsMethods] = createResource(refresh, async (refresh) => {
+		try {
+			return (await invoke("get_minecraft_news", {
+				refresh: refresh,
+			})) as NewsEntry[];
+		} catch (e) {
+			// The feed isn't essential, so just hide it instead of showing an error
+			console.error("Failed to get news: " + e);
+			return [];
+		}
+	});
+
+	return (
+		<Show when={news() != undefined && news()!.length > 0}>
+			<div class="cont col fullwidth" id="news-feed">
+				<div class="split fullwidth" id="news-feed-header">
+					<div class="cont start bold">MINECRAFT NEWS</div>
+					<div class="cont end">
+						<Tip tip="Refresh" side="top">
+							<IconButton
+								icon={Refresh}
+								size="1.5rem"
+								color="var(--bg2)"
+								border="var(--bg3)"
+								label="Refresh news"
+								onClick={() => {
+									if (refresh()) {
+										newsMethods.refetch();
+									} else {
+										setRefresh(true);
+									}
+								}}
+							/>
+						</Tip>
+					</div>
+				</div>
+				<div class="cont start fullwidth" id="news-feed-entries">
+					<For each={news()}>{(entry) => <NewsCard entry={entry} />}</For>
+				</div>
+			</div>
+		</Show>
+	);
+}
+
+function NewsCard(props: { entry: NewsEntry }) {
+	let date = () => {
+		let date = new Date(props.entry.date);
+		return isNaN(date.getTime()) ? "" : date.toLocaleDateString();
+	};
+
+	return (
+		<div class="cont col shadow news-card" title={props.entry.title}>
+			<div class="news-card-image">
+				{/* Use the banner from the installed game assets when we have it, as it works offline */}
+				<Show
+					when={props.entry.banner_images != undefined}
+					fallback={
+						<img
+							src={props.entry.image}
+							onerror={(e) => e.target.remove()}
+						/>
+					}
+				>
+					<div class="cont news-card-banner">
+						<img
+							src={getCachedImageSrc(props.entry.banner_images![0], "large")}
+							onerror={(e) => e.target.remove()}
+						/>
+						<img
+							src={getCachedImageSrc(props.entry.banner_images![1], "large")}
+							onerror={(e) => e.target.remove()}
+						/>
+					</div>
+				</Show>
+				<div
+					class={`news-card-type ${props.entry.version_type == "release" ? "release" : ""}`}
+				>
+					{props.entry.version_type.toLocaleUpperCase()}
+				</div>
+			</div>
+			<div class="cont col start news-card-body">
+				<div class="split fullwidth">
+					<div class="cont start bold news-card-version">
+						{props.entry.version}
+					</div>
+					<div class="cont end news-card-date">{date()}</div>
+				</div>
+				<div class="news-card-summary">{props.entry.summary}</div>
+			</div>
+		</div>
+	);
+}
+
+export interface NewsEntry {
+	id: string;
+	title: string;
+	version: string;
+	version_type: string;
+	date: string;
+	summary: string;
+	image: string;
+	banner_images?: [string, string];
+}
@@ -465,6 +465,8 @@ function getTaskDisplayName(task: string) {
 		return "Updating instance";
 	} else if (task == "update_instance_packages") {
 		return "Updating packages";
+	} else if (task == "verify_instance") {
+		return "Verifying files";
 	} else if (task.startsWith("launch_instance")) {
 		return "Launching";
 	} else if (task == "search_packages") {
@@ -509,6 +511,7 @@ function getTaskColor(task: string) {
 	} else if (
 		task.startsWith("launch_instance") ||
 		task == "update_instance" ||
+		task == "verify_instance" ||
 		task == "save_instance_config" ||
 		task == "delete_instance"
 	) {
@@ -540,6 +543,7 @@ function isTaskKillable(task: string) {
 	return (
 		task == "update_instance" ||
 		task == "update_instance_packages" ||
+		task == "verify_instance" ||
 		task.startsWith("launch_instance")
 	);
 }
@@ -12,6 +12,7 @@ import {
 	AngleDown,
 	AngleRight,
 	Check,
+	Lock,
 	Plus,
 	Properties,
 	User,
@@ -95,6 +96,11 @@ export default function AccountWidget() {
 					name: "Demo",
 					color: "#dddddd",
 				},
+				{
+					id: "offline",
+					name: "Offline",
+					color: "#f8e45c",
+				},
 			];
 			out = out.concat(pluginTypes);
 
@@ -183,6 +189,23 @@ export default function AccountWidget() {
 						</div>
 						<div class="cont account-tile-name">Add Account</div>
 					</div>
+					<div
+						class="bubble-hover account-tile"
+						onclick={async () => {
+							setIsOpen(false);
+							try {
+								await invoke("lock_all_accounts");
+								successToast("All accounts locked");
+							} catch (e) {
+								errorToast("Failed to lock accounts: " + e);
+							}
+						}}
+					>
+						<div class="cont">
+							<Icon icon={Lock} size="1.2rem" />
+						</div>
+						<div class="cont account-tile-name">Lock All Accounts</div>
+					</div>
 				</div>
 			</Show>
 			<Modal
@@ -268,7 +291,7 @@ type AccountMap = { [id: string]: AccountInfo | undefined };
 
 export interface AccountInfo {
 	id: string;
-	type: "microsoft" | "demo" | "other";
+	type: "microsoft" | "demo" | "offline" | "other";
 	username?: string;
 	uuid?: string;
 }
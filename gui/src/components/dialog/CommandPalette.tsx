@@ -0,0 +1,287 @@
+import { invoke } from "@tauri-apps/api/core";
+import { emit } from "@tauri-apps/api/event";
+import { useNavigate } from "@solidjs/router";
+import {
+	createEffect,
+	createSignal,
+	For,
+	JSX,
+	on,
+	onCleanup,
+	onMount,
+	Show,
+} from "solid-js";
+import ModalBase from "./ModalBase";
+import Icon from "../Icon";
+import {
+	Book,
+	Box,
+	Folder,
+	Gear,
+	Jigsaw,
+	Play,
+	Refresh,
+	Search,
+	Star,
+} from "../../icons";
+import { errorToast, successToast } from "./Toasts";
+import { setInstanceConfigModal } from "../../App";
+import { InstanceConfigMode } from "../../pages/instance/read_write";
+import "./CommandPalette.css";
+
+// Ctrl+K palette for searching and running commands from the keyboard
+export default function CommandPalette() {
+	let navigate = useNavigate();
+	let inputElem!: HTMLInputElement;
+
+	let [visible, setVisible] = createSignal(false);
+	let [query, setQuery] = createSignal("");
+	let [results, setResults] = createSignal<GlobalSearchResult[]>([]);
+	let [selected, setSelected] = createSignal(0);
+
+	let onKeyDown = (e: KeyboardEvent) => {
+		if ((e.ctrlKey || e.metaKey) && e.key.toLowerCase() == "k") {
+			e.preventDefault();
+			setVisible((x) => !x);
+		}
+	};
+
+	onMount(() => document.addEventListener("keydown", onKeyDown));
+	onCleanup(() => document.removeEventListener("keydown", onKeyDown));
+
+	createEffect(
+		on(visible, (visible) => {
+			if (visible) {
+				setQuery("");
+				setSelected(0);
+				setTimeout(() => inputElem?.focus(), 0);
+			}
+		}),
+	);
+
+	// Debounced search
+	let searchTimeout: number | undefined;
+	createEffect(
+		on(query, (query) => {
+			clearTimeout(searchTimeout);
+			searchTimeout = setTimeout(async () => {
+				try {
+					setResults(await invoke("global_search", { query: query }));
+				} catch (e) {
+					console.error("Search failed: " + e);
+					setResults([]);
+				}
+			}, 200);
+		}),
+	);
+
+	let commands = (): PaletteCommand[] => {
+		let out: PaletteCommand[] = [];
+		let query2 = query().toLowerCase();
+
+		for (let command of staticCommands(navigate)) {
+			if (command.name.toLowerCase().includes(query2)) {
+				out.push(command);
+			}
+		}
+
+		for (let result of results()) {
+			out = out.concat(resultCommands(result, navigate));
+		}
+
+		return out;
+	};
+
+	createEffect(on(commands, () => setSelected(0)));
+
+	let run = async (command: PaletteCommand) => {
+		setVisible(false);
+		try {
+			await command.run();
+		} catch (e) {
+			errorToast(`Failed to ${command.name.toLowerCase()}: ${e}`);
+		}
+	};
+
+	return (
+		<ModalBase visible={visible()} onClose={setVisible} width="40rem">
+			<div class="cont col fullwidth" id="command-palette">
+				<div class="cont fullwidth" id="command-palette-search">
+					<Icon icon={Search} size="1rem" />
+					<input
+						class="fullwidth"
+						ref={inputElem}
+						value={query()}
+						placeholder="Search instances, packages, settings, and actions..."
+						aria-label="Command palette search"
+						oninput={(e) => setQuery(e.target.value)}
+						onkeydown={(e) => {
+							let length = commands().length;
+							if (e.key == "ArrowDown") {
+								e.preventDefault();
+								setSelected((x) => Math.min(x + 1, length - 1));
+							} else if (e.key == "ArrowUp") {
+								e.preventDefault();
+								setSelected((x) => Math.max(x - 1, 0));
+							} else if (e.key == "Enter") {
+								e.preventDefault();
+								let command = commands()[selected()];
+								if (command != undefined) {
+									run(command);
+								}
+							} else if (e.key == "Escape") {
+								setVisible(false);
+							}
+						}}
+					/>
+				</div>
+				<div class="cont col fullwidth" id="command-palette-results" role="listbox">
+					<For each={commands()}>
+						{(command, i) => (
+							<div
+								class={`cont start fullwidth command-palette-item ${selected() == i() ? "selected" : ""}`}
+								role="option"
+								aria-selected={selected() == i()}
+								onclick={() => run(command)}
+								onmouseenter={() => setSelected(i())}
+							>
+								<div class="cont command-palette-icon">{command.icon}</div>
+								<div class="cont fullwidth start">{command.name}</div>
+								<Show when={command.category != undefined}>
+									<div class="cont command-palette-category">
+										{command.category}
+									</div>
+								</Show>
+							</div>
+						)}
+					</For>
+				</div>
+			</div>
+		</ModalBase>
+	);
+}
+
+// Commands that are always available
+function staticCommands(navigate: (path: string) => void): PaletteCommand[] {
+	return [
+		{
+			name: "Open Settings",
+			category: "Settings",
+			icon: <Icon icon={Gear} size="1rem" />,
+			run: () => emit("nitro_open_settings"),
+		},
+		{
+			name: "Browse Packages",
+			icon: <Icon icon={Box} size="1rem" />,
+			run: () => navigate("/packages/0"),
+		},
+		{
+			name: "Manage Plugins",
+			icon: <Icon icon={Jigsaw} size="1rem" />,
+			run: () => navigate("/plugins"),
+		},
+		{
+			name: "Open Documentation",
+			icon: <Icon icon={Book} size="1rem" />,
+			run: () => navigate("/docs"),
+		},
+		{
+			name: "Open Data Folder",
+			icon: <Icon icon={Folder} size="1rem" />,
+			run: () => invoke("open_data_dir"),
+		},
+	];
+}
+
+// Commands available for a search result
+function resultCommands(
+	result: GlobalSearchResult,
+	navigate: (path: string) => void,
+): PaletteCommand[] {
+	let name = result.name == undefined ? result.id : result.name;
+
+	if (result.kind == "instance") {
+		return [
+			{
+				name: `Launch ${name}`,
+				category: "Instance",
+				icon: <Icon icon={Play} size="1rem" />,
+				run: () => (window as any).__launchInstance(result.id, false),
+			},
+			{
+				name: `Go to ${name}`,
+				category: "Instance",
+				icon: <Icon icon={Box} size="1rem" />,
+				run: () => navigate(`/instance/${result.id}`),
+			},
+			{
+				name: `Update ${name}`,
+				category: "Instance",
+				icon: <Icon icon={Refresh} size="1rem" />,
+				run: () =>
+					invoke("update_instance", { instanceId: result.id, depth: "full" }),
+			},
+			{
+				name: `Open folder of ${name}`,
+				category: "Instance",
+				icon: <Icon icon={Folder} size="1rem" />,
+				run: () => invoke("open_instance_dir", { instance: result.id }),
+			},
+		];
+	} else if (result.kind == "template") {
+		return [
+			{
+				name: `Edit template ${name}`,
+				category: "Template",
+				icon: <Icon icon={Box} size="1rem" />,
+				run: () =>
+					setInstanceConfigModal(result.id, InstanceConfigMode.Template, false),
+			},
+		];
+	} else if (result.kind == "package") {
+		return [
+			{
+				name: `View ${name}`,
+				category: "Package",
+				icon:
+					result.icon == undefined ? (
+						<Icon icon={Box} size="1rem" />
+					) : (
+						<img src={result.icon} style="width:1rem" />
+					),
+				run: () => navigate(`/packages/package/${result.id}`),
+			},
+		];
+	} else {
+		return [
+			{
+				name: name,
+				category: "Plugin",
+				icon: <Icon icon={Star} size="1rem" />,
+				run: async () => {
+					await invoke("run_custom_action", {
+						plugin: result.plugin,
+						action: result.id,
+						payload: null,
+					});
+					successToast(`Ran ${name}`);
+				},
+			},
+		];
+	}
+}
+
+interface PaletteCommand {
+	name: string;
+	category?: string;
+	icon: JSX.Element;
+	run: () => any;
+}
+
+interface GlobalSearchResult {
+	kind: "instance" | "template" | "package" | "plugin_action";
+	id: string;
+	name?: string;
+	icon?: string;
+	plugin?: string;
+}
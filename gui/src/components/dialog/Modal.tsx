@@ -4,6 +4,7 @@ import "./Modal.css";
 import Icon, { HasWidthHeight } from "../Icon";
 import IconButton from "../input/button/IconButton";
 import { Delete } from "../../icons";
+import { activateOnKey } from "../../utils/keyboard";
 
 // Modal with a title box and buttons at the bottom
 export default function Modal(props: ModalProps) {
@@ -55,7 +56,10 @@ export default function Modal(props: ModalProps) {
 						onmouseenter={() => setIsHovered(true)}
 						onmouseleave={() => setIsHovered(false)}
 						style={`color:${textColor()};border-top-color:${borderColor()};background-color:${bgColor()}`}
+						role="button"
+						tabindex="0"
 						onclick={button.onClick}
+						onkeydown={activateOnKey(button.onClick)}
 					>
 						<Icon icon={button.icon} size="1.2rem" />
 						{button.text}
@@ -85,6 +89,7 @@ export default function Modal(props: ModalProps) {
 								color="var(--bg2)"
 								iconColor="var(--fg3)"
 								hoverBackground="var(--bg3)"
+								label="Close"
 								onClick={() => props.onClose!(false)}
 							/>
 						</div>
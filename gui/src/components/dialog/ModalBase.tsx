@@ -1,9 +1,26 @@
-import { JSX, Show } from "solid-js";
+import { createEffect, JSX, onCleanup, Show } from "solid-js";
 import PageBlock from "../PageBlock";
 import "./ModalBase.css";
 
 // Simple modal with no contents
 export default function ModalBase(props: ModalBaseProps) {
+	// Close the modal with the Escape key
+	let onKeyDown = (e: KeyboardEvent) => {
+		if (e.key == "Escape") {
+			props.onClose(false);
+		}
+	};
+
+	createEffect(() => {
+		if (props.visible) {
+			window.addEventListener("keydown", onKeyDown);
+		} else {
+			window.removeEventListener("keydown", onKeyDown);
+		}
+	});
+
+	onCleanup(() => window.removeEventListener("keydown", onKeyDown));
+
 	return (
 		<Show when={props.visible}>
 			<PageBlock onClick={() => props.onClose(false)} />
@@ -12,7 +29,15 @@ export default function ModalBase(props: ModalBaseProps) {
 					class="cont modal-behind"
 					onclick={() => props.onClose(false)}
 				></div>
-				<div class="cont modal fade-in-fast pop-in-fast" style={`width:${props.width}`}>
+				<div
+					class="cont modal fade-in-fast pop-in-fast"
+					style={`width:${props.width}`}
+					role="dialog"
+					aria-modal="true"
+					tabindex="-1"
+					// Move focus into the modal so that keyboard navigation starts inside of it
+					ref={(elem) => setTimeout(() => elem.focus())}
+				>
 					{props.children}
 				</div>
 			</div>
@@ -14,6 +14,7 @@ import "./Toasts.css";
 import Icon from "../Icon";
 import { Check, Copy, Delete, Notification } from "../../icons";
 import * as clipboard from "@tauri-apps/plugin-clipboard-manager";
+import { isOffline, OFFLINE_ERROR } from "../navigation/OfflineBanner";
 
 export default function Toasts() {
 	let [toasts, setToasts] = createSignal<ToastProps[]>([]);
@@ -244,6 +245,12 @@ export function warningToast(message: JSX.Element) {
 }
 
 export function errorToast(message: JSX.Element, isPersistent?: boolean) {
+	// The offline banner already explains these errors
+	if (isOffline() && typeof message == "string" && message.includes(OFFLINE_ERROR)) {
+		console.error("Error: " + message);
+		return;
+	}
+
 	(window as any).__createToast({
 		message: message,
 		type: "error",
@@ -1,4 +1,5 @@
 import "./SlideSwitch.css";
+import { activateOnKey } from "../../utils/keyboard";
 
 export default function SlideSwitch(props: SlideSwitchProps) {
 	let color = () => (props.enabled ? props.enabledColor : props.disabledColor);
@@ -13,7 +14,12 @@ export default function SlideSwitch(props: SlideSwitchProps) {
 		<div
 			class="cont bubble-hover slide-switch"
 			style={`color:${color()};border-color:${color()};${bgColor()}`}
+			role="switch"
+			aria-checked={props.enabled}
+			aria-label={props.label}
+			tabindex="0"
 			onclick={props.onToggle}
+			onkeydown={activateOnKey(props.onToggle)}
 		>
 			<div
 				class="cont slide-switch-handle"
@@ -29,4 +35,5 @@ export interface SlideSwitchProps {
 	disabledColor: string;
 	enabledColor: string;
 	enabledBg?: string;
+	label?: string;
 }
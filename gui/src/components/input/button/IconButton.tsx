@@ -1,6 +1,7 @@
 import { createSignal, JSXElement } from "solid-js";
 import "./IconButton.css";
 import Icon, { HasWidthHeight } from "../../Icon";
+import { activateOnKey } from "../../../utils/keyboard";
 
 export default function IconButton(props: IconButtonProps) {
 	let [isHovered, setIsHovered] = createSignal(false);
@@ -38,7 +39,11 @@ export default function IconButton(props: IconButtonProps) {
 				} bubble-hover`}
 			style={`${colorStyle()};width:${props.size};height:${props.size
 				};${iconColorStyle}`}
+			role="button"
+			tabindex="0"
+			aria-label={props.label}
 			onClick={props.onClick}
+			onkeydown={activateOnKey(props.onClick)}
 			onmouseenter={() => setIsHovered(true)}
 			onmouseleave={() => setIsHovered(false)}
 		>
@@ -59,5 +64,7 @@ export interface IconButtonProps {
 	circle?: boolean;
 	shadow?: boolean;
 	selected?: boolean;
+	// Label for screen readers, since the button has no text
+	label?: string;
 	onClick: (e: Event) => void;
 }
@@ -3,7 +3,7 @@ import "./IconSelector.css";
 import { invoke } from "@tauri-apps/api/core";
 import { getInstanceIconSrc } from "../../../utils";
 import Icon from "../../Icon";
-import { Folder } from "../../../icons";
+import { Folder, Refresh } from "../../../icons";
 import { open } from "@tauri-apps/plugin-dialog";
 import { errorToast } from "../../dialog/Toasts";
 
@@ -83,6 +83,21 @@ export default function IconSelector(props: IconSelectorProps) {
 		}
 	}
 
+	async function syncIcon() {
+		try {
+			let icon = await props.syncIcon!();
+			if (icon == undefined) {
+				errorToast("The modpack or first package does not have an icon");
+				return;
+			}
+
+			props.setIcon(icon);
+			iconMethods.refetch();
+		} catch (e) {
+			errorToast("Failed to sync icon: " + e);
+		}
+	}
+
 	return (
 		<div class="icon-selector">
 			<div class="cont" style="width: 10rem">
@@ -123,6 +138,15 @@ export default function IconSelector(props: IconSelectorProps) {
 						>
 							<Icon icon={Folder} size="2rem" />
 						</div>
+						<Show when={props.syncIcon != undefined}>
+							<div
+								class={`cont bubble-hover shadow icon-selector-icon`}
+								onclick={syncIcon}
+								title="Use the icon of the modpack or first package"
+							>
+								<Icon icon={Refresh} size="2rem" />
+							</div>
+						</Show>
 					</div>
 				</div>
 			</Show>
@@ -156,4 +180,6 @@ export interface IconSelectorProps {
 	icon: string | undefined;
 	setIcon: (value: string | undefined) => void;
 	derivedIcon: string | undefined;
+	// Sets the icon from the instance's packages, returning the new icon
+	syncIcon?: () => Promise<string | undefined>;
 }
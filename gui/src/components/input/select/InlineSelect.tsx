@@ -3,6 +3,7 @@ import "./InlineSelect.css";
 import Tip, { TipSide } from "../../dialog/Tip";
 import Icon from "../../Icon";
 import { Check } from "../../../icons";
+import { activateOnKey } from "../../../utils/keyboard";
 
 export default function InlineSelect(props: InlineSelectProps) {
 	let columns = () => (props.columns == undefined ? 3 : props.columns);
@@ -32,6 +33,7 @@ export default function InlineSelect(props: InlineSelectProps) {
 	return (
 		<div
 			class="inline-select"
+			role={Array.isArray(props.selected) ? "group" : "radiogroup"}
 			style={`display:${grid ? "grid" : "flex"
 				};grid-template-columns:repeat(${columns()}, minmax(0, 1fr))`}
 		>
@@ -125,12 +127,18 @@ function InlineSelectOption(props: OptionProps) {
 	let borderColor = () =>
 		isSelected() ? color : isHovered() ? "var(--bg4)" : "var(--bg3)";
 
+	let select = () => props.onSelect(props.option.value);
+
 	let contents = (
 		<div
 			class={`cont inline-select-option shadow bubble-hover ${props.class == undefined ? "" : props.class}
 			${isSelected() ? "selected" : ""}`}
 			style={`border-color:${borderColor()};color:${textColor()};background-color:${backgroundColor()}`}
-			onclick={() => props.onSelect(props.option.value)}
+			role={Array.isArray(props.selected) ? "checkbox" : "radio"}
+			aria-checked={isSelected()}
+			tabindex="0"
+			onclick={select}
+			onkeydown={activateOnKey(select)}
 			onmouseenter={() => setIsHovered(true)}
 			onmouseleave={() => setIsHovered(false)}
 		>
@@ -0,0 +1,123 @@
+import { invoke } from "@tauri-apps/api/core";
+import { createResource, createSignal, For, Show } from "solid-js";
+import "./AdvisoryBadge.css";
+import Icon from "../Icon";
+import { Check, Warning } from "../../icons";
+import IconTextButton from "../input/button/IconTextButton";
+import { errorToast, successToast } from "../dialog/Toasts";
+
+// Tag on the instance page that warns about known security problems with the instance's versions
+export default function AdvisoryBadge(props: AdvisoryBadgeProps) {
+	let [isOpen, setIsOpen] = createSignal(false);
+
+	let [advisories, advisoryMethods] = createResource(
+		() => props.instanceId,
+		async (instanceId) => {
+			try {
+				return (await invoke("get_instance_advisories", {
+					instance: instanceId,
+				})) as InstanceAdvisory[];
+			} catch (e) {
+				console.error(e);
+				return [];
+			}
+		},
+		{ initialValue: [] },
+	);
+
+	let unmitigated = () => advisories().filter((x) => !x.is_mitigated);
+
+	let mitigate = async (advisory: string) => {
+		try {
+			await invoke("mitigate_instance_advisory", {
+				instance: props.instanceId,
+				advisory: advisory,
+			});
+			successToast("Fix applied");
+		} catch (e) {
+			errorToast("Failed to apply fix: " + e);
+		}
+		advisoryMethods.refetch();
+	};
+
+	return (
+		<Show when={unmitigated().length > 0}>
+			<div class="cont" id="advisory-badge">
+				<div
+					class="cont tag"
+					id="advisory-badge-tag"
+					onclick={() => setIsOpen(!isOpen())}
+				>
+					<Icon icon={Warning} size="1rem" />
+					Vulnerable
+				</div>
+				<Show when={isOpen()}>
+					<div class="cont col" id="advisory-badge-popup">
+						<For each={unmitigated()}>
+							{(advisory) => (
+								<div class="cont col start fullwidth advisory-badge-entry">
+									<div class="split fullwidth">
+										<div class="cont start bold">
+											{advisory.advisory.title}
+										</div>
+										<div class="cont end advisory-badge-id">
+											{advisory.advisory.id}
+										</div>
+									</div>
+									<div>{advisory.advisory.description}</div>
+									<Show
+										when={advisory.mitigation != undefined}
+										fallback={
+											<div class="advisory-badge-note">
+												There is no known fix. Change the instance to a newer
+												version to stay safe.
+											</div>
+										}
+									>
+										<div class="split fullwidth">
+											<div class="cont start advisory-badge-note">
+												{advisory.mitigation!.description}
+											</div>
+											<div class="cont end">
+												<IconTextButton
+													icon={Check}
+													size="1rem"
+													text="Apply fix"
+													onClick={() => mitigate(advisory.advisory.id)}
+												/>
+											</div>
+										</div>
+									</Show>
+								</div>
+							)}
+						</For>
+					</div>
+				</Show>
+			</div>
+		</Show>
+	);
+}
+
+export interface AdvisoryBadgeProps {
+	instanceId: string;
+}
+
+export interface InstanceAdvisory {
+	advisory: Advisory;
+	mitigation?: Mitigation;
+	is_mitigated: boolean;
+}
+
+export interface Advisory {
+	id: string;
+	title: string;
+	description: string;
+	severity: "low" | "medium" | "high" | "critical";
+	url?: string;
+}
+
+export interface Mitigation {
+	description: string;
+	jvm_args: string[];
+	use_log4j_config: boolean;
+}
@@ -3,18 +3,21 @@ import Modal from "../dialog/Modal";
 import { errorToast, successToast } from "../dialog/Toasts";
 import { updateInstanceList } from "../../pages/instance/InstanceList";
 import { useNavigate } from "@solidjs/router";
-import { Copy, Delete, Download, Trash, Upload } from "../../icons";
+import { Box, Copy, Cycle, Delete, Download, Server, Trash, Upload } from "../../icons";
 import { createEffect, createSignal, Match, Switch } from "solid-js";
 import IdInput from "../input/text/IdInput";
 import Tip from "../dialog/Tip";
+import SlideSwitch from "../input/SlideSwitch";
 
 export default function InstanceOperationPrompt(props: InstanceOperationPromptProps) {
 	let navigate = useNavigate();
 
 	let [newId, setNewId] = createSignal("");
+	let [permanent, setPermanent] = createSignal(false);
 	createEffect(() => {
 		if (props.visible) {
 			setNewId("");
+			setPermanent(false);
 		}
 	})
 
@@ -27,6 +30,12 @@ export default function InstanceOperationPrompt(props: InstanceOperationPromptPr
 			return "Duplicate instance";
 		} else if (props.operation == "extract") {
 			return "Extract instance";
+		} else if (props.operation == "save_template") {
+			return "Save as template";
+		} else if (props.operation == "create_server") {
+			return "Create matching server";
+		} else if (props.operation == "sync") {
+			return "Sync linked instance";
 		} else {
 			return "";
 		}
@@ -41,6 +50,12 @@ export default function InstanceOperationPrompt(props: InstanceOperationPromptPr
 			return Copy;
 		} else if (props.operation == "extract") {
 			return Upload;
+		} else if (props.operation == "save_template") {
+			return Box;
+		} else if (props.operation == "create_server") {
+			return Server;
+		} else if (props.operation == "sync") {
+			return Cycle;
 		} else {
 			return Trash;
 		}
@@ -56,7 +71,10 @@ export default function InstanceOperationPrompt(props: InstanceOperationPromptPr
 
 	let deleteInstance = async () => {
 		try {
-			await invoke("delete_instance", { instance: props.instanceId });
+			await invoke("delete_instance", {
+				instance: props.instanceId,
+				permanent: permanent(),
+			});
 			successToast("Instance deleted");
 			props.onClose();
 			updateInstanceList();
@@ -98,6 +116,38 @@ export default function InstanceOperationPrompt(props: InstanceOperationPromptPr
 		}
 	};
 
+	let saveAsTemplate = async () => {
+		try {
+			await invoke("save_instance_as_template", { instance: props.instanceId, newId: newId() });
+			successToast("Template saved");
+			updateInstanceList();
+			props.onClose();
+		} catch (e) {
+			errorToast("Failed to save instance as template: " + e);
+		}
+	};
+
+	let createMatchingServer = async () => {
+		try {
+			await invoke("create_matching_server", { instance: props.instanceId, newId: newId() });
+			successToast("Server created");
+			updateInstanceList();
+			props.onClose();
+		} catch (e) {
+			errorToast("Failed to create server: " + e);
+		}
+	};
+
+	let syncLinkedInstance = async () => {
+		try {
+			await invoke("sync_linked_instance", { instance: props.instanceId });
+			successToast("Linked instance synced");
+			props.onClose();
+		} catch (e) {
+			errorToast("Failed to sync linked instance: " + e);
+		}
+	};
+
 	return <Modal
 		visible={props.visible}
 		onClose={props.onClose}
@@ -124,6 +174,12 @@ export default function InstanceOperationPrompt(props: InstanceOperationPromptPr
 						duplicateInstance();
 					} else if (props.operation == "extract") {
 						extractInstance();
+					} else if (props.operation == "save_template") {
+						saveAsTemplate();
+					} else if (props.operation == "create_server") {
+						createMatchingServer();
+					} else if (props.operation == "sync") {
+						syncLinkedInstance();
 					}
 				},
 			},
@@ -134,7 +190,19 @@ export default function InstanceOperationPrompt(props: InstanceOperationPromptPr
 				<Match when={props.operation == "delete"}>
 					<h3>Are you sure you want to delete this instance?</h3>
 					<div class="cont bold" style="font-size:0.9rem;color:var(--fg2)">
-						This will delete ALL of your worlds and data for the instance!
+						{permanent()
+							? "This will permanently delete ALL of your worlds and data for the instance!"
+							: "ALL of your worlds and data for the instance will be moved to the trash"}
+					</div>
+					<div class="cont">
+						<SlideSwitch
+							enabled={permanent()}
+							onToggle={() => setPermanent(!permanent())}
+							disabledColor="var(--fg3)"
+							enabledColor="var(--error)"
+							label="Delete permanently"
+						/>
+						<span>Delete permanently</span>
 					</div>
 				</Match>
 				<Match when={props.operation == "consolidate"}>
@@ -168,6 +236,38 @@ export default function InstanceOperationPrompt(props: InstanceOperationPromptPr
 						<IdInput value={newId()} onChange={setNewId} />
 					</Tip>
 				</Match>
+				<Match when={props.operation == "save_template"}>
+					<span>Save the version, loader, packages, and launch settings of this instance as a new template that you can create other instances from</span>
+					<span>This instance will be left as it is</span>
+					<label class="label">
+						ID
+					</label>
+					<Tip
+						tip="ID for the new template"
+						fullwidth
+						side="top"
+					>
+						<IdInput value={newId()} onChange={setNewId} />
+					</Tip>
+				</Match>
+				<Match when={props.operation == "create_server"}>
+					<span>Create a server instance with the same templates, version, and packages as this instance</span>
+					<span>The two instances will be linked so that you can keep them in sync</span>
+					<label class="label">
+						ID
+					</label>
+					<Tip
+						tip="ID for the new server instance"
+						fullwidth
+						side="top"
+					>
+						<IdInput value={newId()} onChange={setNewId} />
+					</Tip>
+				</Match>
+				<Match when={props.operation == "sync"}>
+					<span>Copy the templates, version, and packages of this instance to the instance it is linked to</span>
+					<span>Packages that are only configured for the other instance's side will be kept</span>
+				</Match>
 			</Switch>
 		</div>
 	</Modal>;
@@ -180,4 +280,11 @@ export interface InstanceOperationPromptProps {
 	onClose: () => void;
 }
 
-export type InstanceOperation = "delete" | "consolidate" | "duplicate" | "extract";
+export type InstanceOperation =
+	| "delete"
+	| "consolidate"
+	| "duplicate"
+	| "extract"
+	| "save_template"
+	| "create_server"
+	| "sync";
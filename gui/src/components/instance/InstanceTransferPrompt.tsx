@@ -2,7 +2,7 @@ import { createResource, createSignal, Match, Show, Switch } from "solid-js";
 import { invoke } from "@tauri-apps/api/core";
 import "./TemplateDeletePrompt.css";
 import InlineSelect from "../input/select/InlineSelect";
-import { Delete, Download, Popout } from "../../icons";
+import { Controller, Delete, Download, Folder, Link, Popout, Server } from "../../icons";
 import { errorToast, successToast } from "../dialog/Toasts";
 import { clearInputError, inputError } from "../../errors";
 import { open, save } from "@tauri-apps/plugin-dialog";
@@ -10,6 +10,8 @@ import Tip from "../dialog/Tip";
 import { updateInstanceList } from "../../pages/instance/InstanceList";
 import Modal from "../dialog/Modal";
 import IdInput from "../input/text/IdInput";
+import Icon from "../Icon";
+import { emptyUndefined } from "../../utils/values";
 
 export default function InstanceTransferPrompt(
 	props: InstanceTransferPromptProps
@@ -43,6 +45,45 @@ export default function InstanceTransferPrompt(
 
 	let [selectedFormat, setSelectedFormat] = createSignal<string | undefined>();
 	let [newInstanceId, setNewInstanceId] = createSignal("");
+	let [source, setSource] = createSignal<"file" | "url">("file");
+	let [url, setUrl] = createSignal("");
+	let [side, setSide] = createSignal<"client" | "server">("client");
+
+	// Whether the user needs to pick a side for the imported instance
+	let needsSide = () => {
+		if (!isImporting()) {
+			return false;
+		}
+		let format = formats().find((x) => x.id == selectedFormat());
+		// The format is auto-detected for URLs, so we don't know if it will need a side
+		if (format == undefined) {
+			return source() == "url";
+		}
+		return format.needs_import_side == true;
+	};
+
+	let importFromUrl = async () => {
+		if (url().length == 0) {
+			inputError("instance-transfer-url");
+			return;
+		} else {
+			clearInputError("instance-transfer-url");
+		}
+
+		try {
+			await invoke("import_instance_from_url", {
+				format: selectedFormat(),
+				id: newInstanceId(),
+				url: url(),
+				side: needsSide() ? side() : undefined,
+			});
+			successToast("Instance imported");
+			updateInstanceList();
+			props.onClose();
+		} catch (e) {
+			errorToast("Failed to import: " + e);
+		}
+	};
 
 	return (
 		<Modal
@@ -61,18 +102,24 @@ export default function InstanceTransferPrompt(
 						text: "Import",
 						icon: Download,
 						onClick: async () => {
-							if (selectedFormat() == undefined) {
-								inputError("instance-transfer-format");
+							if (newInstanceId().length == 0) {
+								inputError("instance-transfer-id");
 								return;
 							} else {
-								clearInputError("instance-transfer-format");
+								clearInputError("instance-transfer-id");
 							}
 
-							if (newInstanceId().length == 0) {
-								inputError("instance-transfer-id");
+							// Formats can be detected automatically from URLs
+							if (source() == "url") {
+								await importFromUrl();
+								return;
+							}
+
+							if (selectedFormat() == undefined) {
+								inputError("instance-transfer-format");
 								return;
 							} else {
-								clearInputError("instance-transfer-id");
+								clearInputError("instance-transfer-format");
 							}
 
 							try {
@@ -87,6 +134,7 @@ export default function InstanceTransferPrompt(
 										format: selectedFormat(),
 										id: newInstanceId(),
 										path: filePath,
+										side: needsSide() ? side() : undefined,
 									});
 									successToast("Instance imported");
 									updateInstanceList();
@@ -140,12 +188,64 @@ export default function InstanceTransferPrompt(
 			}
 		>
 			<div class="cont fields" style="width:100%">
+				<Show when={isImporting()}>
+					<div class="cont start label">
+						<label>SOURCE</label>
+					</div>
+					<Tip tip="Where to import the instance from" fullwidth>
+						<div class="fullwidth">
+							<InlineSelect
+								options={[
+									{
+										value: "file",
+										contents: (
+											<div class="cont">
+												<Icon icon={Folder} size="1rem" /> File
+											</div>
+										),
+									},
+									{
+										value: "url",
+										contents: (
+											<div class="cont">
+												<Icon icon={Link} size="1rem" /> URL
+											</div>
+										),
+									},
+								]}
+								selected={source()}
+								onChange={(x) => setSource(x as "file" | "url")}
+							/>
+						</div>
+					</Tip>
+					<Show when={source() == "url"}>
+						<div class="cont start label">
+							<label for="instance-transfer-url">URL</label>
+						</div>
+						<Tip
+							tip="A Modrinth modpack page, or a direct link to a .mrpack or .zip file"
+							fullwidth
+						>
+							<input
+								id="instance-transfer-url"
+								style="width:100%"
+								placeholder="https://modrinth.com/modpack/..."
+								value={emptyUndefined(url())}
+								onchange={(e) => setUrl(e.target.value.trim())}
+							/>
+						</Tip>
+					</Show>
+				</Show>
 				<div class="cont start label">
 					<label>FORMAT</label>
 				</div>
 				<Tip
 					fullwidth
-					tip="The format to use for the instance. Add new formats with plugins."
+					tip={
+						source() == "url" && isImporting()
+							? "The format of the instance. Select None to detect it automatically."
+							: "The format to use for the instance. Add new formats with plugins."
+					}
 				>
 					<div class="fullwidth" id="instance-transfer-format">
 						<Switch>
@@ -163,6 +263,7 @@ export default function InstanceTransferPrompt(
 									})}
 									selected={selectedFormat()}
 									onChange={setSelectedFormat}
+									allowEmpty={isImporting() && source() == "url"}
 									columns={1}
 								/>
 
@@ -178,6 +279,37 @@ export default function InstanceTransferPrompt(
 						<IdInput value={newInstanceId()} onChange={setNewInstanceId} />
 					</Tip>
 				</Show>
+				<Show when={needsSide()}>
+					<div class="cont start label">
+						<label>SIDE</label>
+					</div>
+					<Tip tip="Whether to import the instance as a client or a server" fullwidth>
+						<div class="fullwidth">
+							<InlineSelect
+								options={[
+									{
+										value: "client",
+										contents: (
+											<div class="cont">
+												<Icon icon={Controller} size="1.2rem" /> Client
+											</div>
+										),
+									},
+									{
+										value: "server",
+										contents: (
+											<div class="cont">
+												<Icon icon={Server} size="1rem" /> Server
+											</div>
+										),
+									},
+								]}
+								selected={side()}
+								onChange={(x) => setSide(x as "client" | "server")}
+							/>
+						</div>
+					</Tip>
+				</Show>
 			</div>
 		</Modal>
 	);
@@ -197,4 +329,5 @@ export interface InstanceTransferFormat {
 	import: any | undefined;
 	export: any | undefined;
 	migrate: any | undefined;
+	needs_import_side: boolean;
 }
@@ -0,0 +1,175 @@
+import { invoke } from "@tauri-apps/api/core";
+import { createResource, For, Match, Show, Switch } from "solid-js";
+import "./RealmsList.css";
+import { Check, Cross, Play, Refresh } from "../../icons";
+import IconTextButton from "../input/button/IconTextButton";
+import IconButton from "../input/button/IconButton";
+import LoadingSpinner from "../utility/LoadingSpinner";
+import Tip from "../dialog/Tip";
+import { errorToast, successToast } from "../dialog/Toasts";
+import { launchInstance } from "../navigation/Footer";
+
+// List of the current account's realms, which can be launched straight into with this instance
+export default function RealmsList(props: RealmsListProps) {
+	let [realms, realmsMethods] = createResource(async () => {
+		try {
+			let [realms, invites] = await Promise.all([
+				invoke("get_realms") as Promise<RealmInfo[]>,
+				invoke("get_realm_invites") as Promise<RealmInvite[]>,
+			]);
+			return { realms: realms, invites: invites, error: undefined };
+		} catch (e) {
+			return { realms: [], invites: [], error: "" + e };
+		}
+	});
+
+	let answerInvite = async (invite: RealmInvite, accept: boolean) => {
+		try {
+			await invoke("answer_realm_invite", {
+				invite: invite.invitation_id,
+				accept: accept,
+			});
+			if (accept) {
+				successToast(`Joined ${invite.world_name}`);
+			}
+			realmsMethods.refetch();
+		} catch (e) {
+			errorToast("Failed to answer invite: " + e);
+		}
+	};
+
+	return (
+		<div class="cont col fullwidth" id="realms-list">
+			<div class="split fullwidth">
+				<div class="cont start bold">REALMS</div>
+				<div class="cont end">
+					<Tip tip="Refresh" side="top">
+						<IconButton
+							icon={Refresh}
+							size="1.5rem"
+							color="var(--bg2)"
+							border="var(--bg3)"
+							label="Refresh realms"
+							onClick={() => realmsMethods.refetch()}
+						/>
+					</Tip>
+				</div>
+			</div>
+			<Switch>
+				<Match when={realms.loading}>
+					<LoadingSpinner size="2rem" />
+				</Match>
+				<Match when={realms()!.error != undefined}>
+					<div class="cont realms-list-message">{realms()!.error}</div>
+				</Match>
+				<Match when={true}>
+					<For each={realms()!.invites}>
+						{(invite) => (
+							<div class="split fullwidth shadow realm">
+								<div class="cont col start realm-details">
+									<div class="bold">{invite.world_name}</div>
+									<div class="realm-subtitle">
+										{`Invited by ${invite.world_owner_name}`}
+									</div>
+								</div>
+								<div class="cont end">
+									<IconTextButton
+										icon={Check}
+										size="1rem"
+										text="Accept"
+										onClick={() => answerInvite(invite, true)}
+									/>
+									<IconTextButton
+										icon={Cross}
+										size="1rem"
+										text="Decline"
+										onClick={() => answerInvite(invite, false)}
+									/>
+								</div>
+							</div>
+						)}
+					</For>
+					<For
+						each={realms()!.realms}
+						fallback={
+							<div class="cont realms-list-message">
+								This account isn't a member of any realms
+							</div>
+						}
+					>
+						{(realm) => {
+							let joinable = () => realm.state == "OPEN" && !realm.expired;
+
+							return (
+								<div class="split fullwidth shadow realm">
+									<div class="cont col start realm-details">
+										<div class="cont start">
+											<span class="bold">{realm.name}</span>
+											<Show when={realm.owned}>
+												<span class="realm-tag">OWNER</span>
+											</Show>
+											<Show when={realm.version != undefined}>
+												<span class="realm-tag">{realm.version}</span>
+											</Show>
+										</div>
+										<div class="realm-subtitle">
+											{realm.expired
+												? "Expired"
+												: realm.state != "OPEN"
+													? "Closed"
+													: realm.motd != undefined
+														? realm.motd
+														: `Owned by ${realm.owner}`}
+										</div>
+									</div>
+									<div class="cont end">
+										<Show when={joinable()}>
+											<IconTextButton
+												icon={Play}
+												size="1rem"
+												text="Join"
+												onClick={() =>
+													launchInstance(
+														props.instanceId,
+														false,
+														`realm:${realm.id}`,
+													)
+												}
+											/>
+										</Show>
+									</div>
+								</div>
+							);
+						}}
+					</For>
+					<div class="cont realms-list-message">
+						Joining a realm directly requires Minecraft 1.20 or newer
+					</div>
+				</Match>
+			</Switch>
+		</div>
+	);
+}
+
+export interface RealmsListProps {
+	instanceId: string;
+}
+
+export interface RealmInfo {
+	id: number;
+	name: string;
+	motd?: string;
+	owner?: string;
+	owned: boolean;
+	state: "OPEN" | "CLOSED" | "UNINITIALIZED" | "UNKNOWN";
+	expired: boolean;
+	version?: string;
+}
+
+export interface RealmInvite {
+	invitation_id: string;
+	world_name: string;
+	world_description?: string;
+	world_owner_name: string;
+	date?: number;
+}
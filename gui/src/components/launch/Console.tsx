@@ -1,4 +1,12 @@
-import { createMemo, createSignal, For, Match, Show, Switch } from "solid-js";
+import {
+	createMemo,
+	createSignal,
+	For,
+	JSX,
+	Match,
+	Show,
+	Switch,
+} from "solid-js";
 import "./Console.css";
 import InlineSelect from "../input/select/InlineSelect";
 import SearchBar from "../input/text/SearchBar";
@@ -9,11 +17,58 @@ import Dropdown, { Option } from "../input/select/Dropdown";
 // Console for logs
 export default function Console(props: ConsoleProps) {
 	let outputElem!: HTMLDivElement;
+	let inputElem!: HTMLInputElement;
 
 	let [filter, setFilter] = createSignal("all");
 	let [search, setSearch] = createSignal("");
 
 	let [input, setInput] = createSignal("");
+	// Previously sent commands, newest last
+	let [history, setHistory] = createSignal<string[]>([]);
+	// Position in the history when browsing with the arrow keys. Undefined when not browsing
+	let [historyIndex, setHistoryIndex] = createSignal<number | undefined>();
+
+	let send = (message: string) => {
+		if (message.length == 0) {
+			return;
+		}
+
+		props.sendMessage!(message + "\n");
+		setHistory((history) =>
+			history[history.length - 1] == message
+				? history
+				: history.concat(message).slice(-MAX_HISTORY),
+		);
+		setHistoryIndex(undefined);
+	};
+
+	let browseHistory = (offset: number) => {
+		let length = history().length;
+		if (length == 0) {
+			return;
+		}
+
+		let index = historyIndex() == undefined ? length : historyIndex()!;
+		index += offset;
+		if (index >= length) {
+			setHistoryIndex(undefined);
+			setInput("");
+			return;
+		}
+		index = Math.max(index, 0);
+
+		setHistoryIndex(index);
+		setInput(history()[index]);
+	};
+
+	let runQuickCommand = (command: QuickCommand) => {
+		if (command.needsArgument == true) {
+			setInput(command.command + " ");
+			inputElem.focus();
+		} else {
+			send(command.command);
+		}
+	};
 
 	let output = () => {
 		if (props.output == undefined) {
@@ -25,6 +80,10 @@ export default function Console(props: ConsoleProps) {
 		}
 	};
 
+	let hasCommandBar = () =>
+		(props.quickCommands != undefined && props.quickCommands.length > 0) ||
+		props.inputControls != undefined;
+
 	function scrollToBottom() {
 		if (outputElem != undefined) {
 			outputElem.scrollTop = outputElem.scrollHeight;
@@ -107,6 +166,7 @@ export default function Console(props: ConsoleProps) {
 						onChange={props.setSelectedLog}
 						zIndex="2"
 					/>
+					{props.headerControls}
 				</div>
 			</div>
 			<div class="cont col console-output">
@@ -115,11 +175,13 @@ export default function Console(props: ConsoleProps) {
 						<div class="cont col console-text" ref={outputElem}>
 							<For each={output()!}>
 								{(line) => {
-									let cls = line.includes("INFO")
+									let parsed = parseLogLine(line);
+									let level = parsed == undefined ? line : parsed.level;
+									let cls = level.includes("INFO")
 										? "info"
-										: line.includes("WARN")
+										: level.includes("WARN")
 											? "warning"
-											: line.includes("ERROR")
+											: level.includes("ERROR")
 												? "error"
 												: "";
 
@@ -153,7 +215,13 @@ export default function Console(props: ConsoleProps) {
 											class={`console-line ${cls}`}
 											style={`${isVisible() ? "" : "display:none"}`}
 										>
-											{line}
+											<Show when={parsed != undefined} fallback={line}>
+												<span class="console-time">[{parsed!.time}] </span>
+												<span class="console-thread">
+													[{parsed!.thread}/{parsed!.level}]:{" "}
+												</span>
+												{parsed!.message}
+											</Show>
 										</span>
 									);
 								}}
@@ -161,26 +229,52 @@ export default function Console(props: ConsoleProps) {
 						</div>
 						<div
 							class="cont shadow bubble-hover console-scroll"
-							style={`bottom:${props.sendMessage == undefined ? "1rem" : "3rem"}`}
+							style={`bottom:${props.sendMessage == undefined ? "1rem" : hasCommandBar() ? "5.5rem" : "3rem"}`}
 							onclick={() => {
 								scrollToBottom();
 							}}
 						>
 							<Icon icon={AngleDown} size="1.5rem" />
 						</div>
+						<Show when={props.sendMessage != undefined && hasCommandBar()}>
+							<div class="cont fullwidth console-commands">
+								<For each={props.quickCommands}>
+									{(command) => (
+										<button
+											class="console-command"
+											title={command.command}
+											onclick={() => runQuickCommand(command)}
+										>
+											{command.name}
+										</button>
+									)}
+								</For>
+								<div class="cont end fullwidth">{props.inputControls}</div>
+							</div>
+						</Show>
 						<Show when={props.sendMessage != undefined}>
 							<div class="fullwidth console-input">
 								<form
 									onsubmit={async (e) => {
 										e.preventDefault();
-										props.sendMessage!(input() + "\n");
+										send(input());
 										setInput("");
 									}}
 								>
 									<input
 										class="fullwidth"
 										value={input()}
-										onchange={(e) => setInput(e.target.value)}
+										ref={inputElem}
+										oninput={(e) => setInput(e.target.value)}
+										onkeydown={(e) => {
+											if (e.key == "ArrowUp") {
+												e.preventDefault();
+												browseHistory(-1);
+											} else if (e.key == "ArrowDown") {
+												e.preventDefault();
+												browseHistory(1);
+											}
+										}}
 									/>
 								</form>
 								<div class="cont console-input-prompt">
@@ -206,5 +300,45 @@ export interface ConsoleProps {
 	setSelectedLog: (log: string | undefined) => void;
 	availableLogs: string[];
 	sendMessage?: (message: string) => void;
+	// Buttons shown above the input for commonly used commands
+	quickCommands?: QuickCommand[];
+	// Extra controls shown next to the quick commands
+	inputControls?: JSX.Element;
+	// Extra controls shown next to the log selector
+	headerControls?: JSX.Element;
 	smallButtons?: boolean;
 }
+
+export interface QuickCommand {
+	name: string;
+	command: string;
+	// Whether the command is placed in the input to be completed instead of being sent immediately
+	needsArgument?: boolean;
+}
+
+// Maximum number of commands to keep in the history
+const MAX_HISTORY = 100;
+
+// A line in the standard game log format, like [12:34:56] [Server thread/INFO]: Message
+interface LogLine {
+	time: string;
+	thread: string;
+	level: string;
+	message: string;
+}
+
+const LOG_LINE_REGEX = /^\[([^\]]+)\] \[(.+)\/([A-Z]+)\]: (.*)$/;
+
+function parseLogLine(line: string): LogLine | undefined {
+	let match = line.match(LOG_LINE_REGEX);
+	if (match == null) {
+		return undefined;
+	}
+
+	return {
+		time: match[1],
+		thread: match[2],
+		level: match[3],
+		message: match[4],
+	};
+}
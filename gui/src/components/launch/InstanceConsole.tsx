@@ -1,13 +1,18 @@
 import { invoke } from "@tauri-apps/api/core";
 import { Event, listen } from "@tauri-apps/api/event";
+import { save } from "@tauri-apps/plugin-dialog";
+import * as clipboard from "@tauri-apps/plugin-clipboard-manager";
 import {
 	createEffect,
 	createResource,
 	createSignal,
 	onCleanup,
 } from "solid-js";
-import { errorToast } from "../dialog/Toasts";
-import Console from "./Console";
+import { errorToast, successToast } from "../dialog/Toasts";
+import Console, { QuickCommand } from "./Console";
+import InlineSelect from "../input/select/InlineSelect";
+import IconButton from "../input/button/IconButton";
+import { Folder, Popout, Upload } from "../../icons";
 
 export default function InstanceConsole(props: InstanceConsoleProps) {
 	// Undefined for the current instance output
@@ -80,31 +85,176 @@ export default function InstanceConsole(props: InstanceConsoleProps) {
 		{ initialValue: [] },
 	);
 
+	// How commands are sent to the server
+	let [inputMethod, setInputMethod] = createSignal<"stdin" | "rcon">("stdin");
+	// Responses to RCON commands, which do not show up in the normal output
+	let [rconOutput, setRconOutput] = createSignal<string[]>([]);
+
 	let sendMessage = async (message: string) => {
 		try {
-			await invoke("write_instance_input", {
+			if (inputMethod() == "rcon") {
+				let command = message.trim();
+				let response = (await invoke("send_instance_rcon_command", {
+					instanceId: props.instanceId,
+					command: command,
+				})) as string;
+				setRconOutput((output) =>
+					output.concat(`> ${command}`, response.trimEnd()),
+				);
+			} else {
+				await invoke("write_instance_input", {
+					instanceId: props.instanceId,
+					input: message,
+				});
+			}
+		} catch (e) {
+			errorToast("Failed to send: " + e);
+		}
+	};
+
+	// Shows the selected log file, or the latest one, in the file manager
+	let openLogFile = async () => {
+		try {
+			await invoke("open_instance_logs", {
+				instance: props.instanceId,
+				log: selectedLog(),
+			});
+		} catch (e) {
+			errorToast("Failed to open logs folder: " + e);
+		}
+	};
+
+	let exportCrashBundle = async () => {
+		try {
+			let filePath = await save({
+				defaultPath: `${props.instanceId}-crash.zip`,
+				filters: [{ name: "Crash Bundle", extensions: ["zip"] }],
+			});
+
+			if (filePath == null) {
+				return;
+			}
+
+			await invoke("export_crash_bundle", {
 				instanceId: props.instanceId,
-				input: message,
+				path: filePath,
 			});
+			successToast("Crash bundle exported");
 		} catch (e) {
-			errorToast("Failed to send: " + e);
+			errorToast("Failed to export crash bundle: " + e);
+		}
+	};
+
+	// Uploads the selected log, or the crash bundle when viewing the current output
+	let upload = async () => {
+		try {
+			let url: string;
+			if (selectedLog() == undefined) {
+				url = await invoke("upload_crash_bundle", {
+					instanceId: props.instanceId,
+				});
+			} else {
+				url = await invoke("upload_instance_log", {
+					instanceId: props.instanceId,
+					logId: selectedLog(),
+				});
+			}
+
+			await clipboard.writeText(url);
+			successToast(`Uploaded to ${url}. Link copied to clipboard`);
+		} catch (e) {
+			errorToast("Failed to upload: " + e);
 		}
 	};
 
+	let fullOutput = () => {
+		let text = output();
+		if (
+			text == undefined ||
+			selectedLog() != undefined ||
+			rconOutput().length == 0
+		) {
+			return text;
+		}
+
+		return text.trimEnd() + "\n" + rconOutput().join("\n");
+	};
+
 	return (
 		<Console
 			loadState={output.state}
-			output={output()}
+			output={fullOutput()}
 			fetchOutput={outputMethods.refetch}
 			sendMessage={props.isServer ? sendMessage : undefined}
+			quickCommands={SERVER_QUICK_COMMANDS}
+			inputControls={
+				<InlineSelect
+					options={[
+						{
+							value: "stdin",
+							contents: "STDIN",
+							tip: "Send commands through the server console",
+						},
+						{
+							value: "rcon",
+							contents: "RCON",
+							tip: "Send commands through RCON. Requires RCON to be enabled in the server properties",
+						},
+					]}
+					selected={inputMethod()}
+					onChange={(x) => setInputMethod(x as "stdin" | "rcon")}
+					columns={2}
+					solidSelect
+				/>
+			}
 			availableLogs={availableLogs()}
 			selectedLog={selectedLog()}
 			setSelectedLog={setSelectedLog}
+			headerControls={
+				<>
+					<IconButton
+						icon={Upload}
+						size="1.5rem"
+						color="var(--bg2)"
+						border="var(--bg3)"
+						hoverBackground="var(--bg3)"
+						label={
+							selectedLog() == undefined ? "Upload crash bundle" : "Upload log"
+						}
+						onClick={upload}
+					/>
+					<IconButton
+						icon={Popout}
+						size="1.5rem"
+						color="var(--bg2)"
+						border="var(--bg3)"
+						hoverBackground="var(--bg3)"
+						label="Export crash bundle"
+						onClick={exportCrashBundle}
+					/>
+					<IconButton
+						icon={Folder}
+						size="1.5rem"
+						color="var(--bg2)"
+						border="var(--bg3)"
+						hoverBackground="var(--bg3)"
+						label="Show log file"
+						onClick={openLogFile}
+					/>
+				</>
+			}
 			smallButtons
 		/>
 	);
 }
 
+const SERVER_QUICK_COMMANDS: QuickCommand[] = [
+	{ name: "Stop", command: "stop" },
+	{ name: "Save All", command: "save-all" },
+	{ name: "Whitelist Add", command: "whitelist add", needsArgument: true },
+	{ name: "List Players", command: "list" },
+];
+
 export interface InstanceConsoleProps {
 	instanceId: string;
 	isServer: boolean;
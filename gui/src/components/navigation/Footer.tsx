@@ -24,6 +24,7 @@ import IconButton from "../input/button/IconButton";
 import { AuthDisplayEvent } from "../../types";
 import MicrosoftAuthInfo from "../input/MicrosoftAuthInfo";
 import TaskIndicator from "../TaskIndicator";
+import DownloadPanel from "../DownloadPanel";
 import { errorToast } from "../dialog/Toasts";
 import Tip from "../dialog/Tip";
 import TemplateDeletePrompt from "../instance/TemplateDeletePrompt";
@@ -90,7 +91,11 @@ export default function Footer(props: FooterProps) {
 		}
 	});
 
-	async function launch(instance: string, offline: boolean) {
+	async function launch(
+		instance: string,
+		offline: boolean,
+		quickPlay?: string,
+	) {
 		// Prevent launching until the current authentication screens are finished
 		if (showPasswordPrompt() || authInfo() !== undefined) {
 			return;
@@ -101,6 +106,7 @@ export default function Footer(props: FooterProps) {
 		let launchPromise = invoke("launch_game", {
 			instanceId: instance,
 			offline: offline,
+			quickPlay: quickPlay,
 		});
 
 		try {
@@ -185,6 +191,7 @@ export default function Footer(props: FooterProps) {
 							<Tip tip="Update instance" side="top">
 								<IconButton
 									icon={Upload}
+									label="Update instance"
 									size="1.5rem"
 									color="var(--bg0)"
 									selectedColor="var(--accent)"
@@ -210,6 +217,7 @@ export default function Footer(props: FooterProps) {
 							<Tip tip="View instance" side="top">
 								<IconButton
 									icon={Properties}
+									label="View instance"
 									size="1.5rem"
 									color="var(--bg0)"
 									selectedColor="var(--accent)"
@@ -234,6 +242,7 @@ export default function Footer(props: FooterProps) {
 							<Tip tip="Delete template" side="top">
 								<IconButton
 									icon={Trash}
+									label="Delete template"
 									size="1.5rem"
 									color="var(--bg0)"
 									selectedColor="var(--accent)"
@@ -293,6 +302,7 @@ export default function Footer(props: FooterProps) {
 			</div>
 			<div id="footer-right" class="split fullwidth">
 				<div class="cont">
+					<DownloadPanel />
 					<TaskIndicator />
 				</div>
 				<div class="cont">
@@ -429,6 +439,11 @@ export enum FooterMode {
 }
 
 // Launches an instance
-export function launchInstance(instance: string, offline: boolean) {
-	(window as any).__launchInstance(instance, offline);
+// Launches an instance, optionally with a Quick Play target such as `realm:<id>`
+export function launchInstance(
+	instance: string,
+	offline: boolean,
+	quickPlay?: string,
+) {
+	(window as any).__launchInstance(instance, offline, quickPlay);
 }
@@ -13,6 +13,7 @@ import { Location } from "@solidjs/router";
 import Toasts from "../dialog/Toasts";
 import Icon, { HasWidthHeight } from "../Icon";
 import AccountWidget from "../account/AccountWidget";
+import NotificationPanel from "./NotificationPanel";
 
 export default function NavBar(props: NavBarProps) {
 	return (
@@ -25,6 +26,7 @@ export default function NavBar(props: NavBarProps) {
 						<div class="cont" id="sidebar-button">
 							<IconButton
 								icon={Menu}
+								label="Toggle sidebar"
 								size="1.8rem"
 								color="var(--bg)"
 								selectedColor="var(--accent)"
@@ -37,6 +39,7 @@ export default function NavBar(props: NavBarProps) {
 						<div class="cont">
 							<IconButton
 								icon={ArrowLeft}
+								label="Back"
 								size="1.8rem"
 								color="var(--bg)"
 								selectedColor="var(--accent)"
@@ -51,6 +54,7 @@ export default function NavBar(props: NavBarProps) {
 						<div class="cont">
 							<IconButton
 								icon={ArrowRight}
+								label="Forward"
 								size="1.8rem"
 								color="var(--bg)"
 								selectedColor="var(--accent)"
@@ -122,6 +126,7 @@ export default function NavBar(props: NavBarProps) {
 						/>
 					</div>
 					<div class="cont end navbar-item" id="navbar-right">
+						<NotificationPanel />
 						<AccountWidget />
 						<Toasts />
 					</div>
@@ -0,0 +1,161 @@
+import { invoke } from "@tauri-apps/api/core";
+import {
+	createResource,
+	createSignal,
+	For,
+	onCleanup,
+	onMount,
+	Show,
+} from "solid-js";
+import "./NotificationPanel.css";
+import { Check, Notification, Trash } from "../../icons";
+import IconButton from "../input/button/IconButton";
+import IconTextButton from "../input/button/IconTextButton";
+import { errorToast } from "../dialog/Toasts";
+
+// Button in the navbar that shows stored notifications about background events in a popup
+export default function NotificationPanel() {
+	let [isOpen, setIsOpen] = createSignal(false);
+
+	let [notifications, notificationMethods] = createResource(
+		async () => {
+			try {
+				return (await invoke("get_notifications")) as NotificationInfo[];
+			} catch (e) {
+				console.error(e);
+				return [];
+			}
+		},
+		{ initialValue: [] },
+	);
+
+	// Notifications can be added by background tasks and the CLI, so keep checking for new ones
+	let timeout: number | undefined;
+	let poll = () => {
+		notificationMethods.refetch();
+		timeout = setTimeout(poll, 10000);
+	};
+	onMount(poll);
+	onCleanup(() => clearTimeout(timeout));
+
+	let unreadCount = () => notifications().filter((x) => !x.read).length;
+
+	let markRead = async (id?: number) => {
+		try {
+			await invoke("mark_notifications_read", { id: id });
+		} catch (e) {
+			errorToast("Failed to mark notifications as read: " + e);
+		}
+		notificationMethods.refetch();
+	};
+
+	let clear = async () => {
+		try {
+			await invoke("clear_notifications");
+		} catch (e) {
+			errorToast("Failed to clear notifications: " + e);
+		}
+		notificationMethods.refetch();
+	};
+
+	return (
+		<div class="cont" id="notification-panel">
+			<IconButton
+				icon={Notification}
+				size="1.5rem"
+				color="var(--bg)"
+				iconColor={unreadCount() > 0 ? "var(--accent)" : "var(--fg3)"}
+				hoverBackground="var(--bg3)"
+				label="Notifications"
+				circle
+				onClick={() => {
+					setIsOpen(!isOpen());
+					notificationMethods.refetch();
+				}}
+			/>
+			<Show when={unreadCount() > 0}>
+				<div class="cont bold" id="notification-panel-count">
+					{unreadCount()}
+				</div>
+			</Show>
+			<Show when={isOpen()}>
+				<div class="cont col" id="notification-panel-popup">
+					<div class="split fullwidth" id="notification-panel-header">
+						<div class="cont start bold">Notifications</div>
+						<div class="cont end">
+							<Show when={unreadCount() > 0}>
+								<IconTextButton
+									icon={Check}
+									size="1rem"
+									text="Mark read"
+									onClick={() => markRead()}
+								/>
+							</Show>
+							<Show when={notifications().length > 0}>
+								<IconTextButton
+									icon={Trash}
+									size="1rem"
+									text="Clear"
+									onClick={clear}
+								/>
+							</Show>
+						</div>
+					</div>
+					<div class="cont col fullwidth" id="notification-panel-list">
+						<For
+							each={notifications()}
+							fallback={
+								<div class="cont" style="color:var(--fg3)">
+									No notifications
+								</div>
+							}
+						>
+							{(notification) => (
+								<div
+									class={`cont col start fullwidth notification-panel-entry ${
+										notification.read ? "" : "unread"
+									}`}
+									onclick={() => {
+										if (!notification.read) {
+											markRead(notification.id);
+										}
+									}}
+								>
+									<div class="split fullwidth">
+										<div class="cont start bold notification-panel-title">
+											{notification.title}
+										</div>
+										<div class="cont end notification-panel-date">
+											{new Date(notification.timestamp * 1000).toLocaleString()}
+										</div>
+									</div>
+									<div class="notification-panel-message">
+										{notification.message}
+									</div>
+								</div>
+							)}
+						</For>
+					</div>
+				</div>
+			</Show>
+		</div>
+	);
+}
+
+export interface NotificationInfo {
+	id: number;
+	kind: NotificationKind;
+	title: string;
+	message: string;
+	subject?: string;
+	timestamp: number;
+	read: boolean;
+}
+
+export type NotificationKind =
+	| "package_updates"
+	| "plugin_updates"
+	| "task_failed"
+	| "java_end_of_life"
+	| "broken_java"
+	| "other";
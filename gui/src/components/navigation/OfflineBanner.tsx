@@ -0,0 +1,51 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Event, listen } from "@tauri-apps/api/event";
+import { createResource, createSignal, onCleanup, Show } from "solid-js";
+import Icon from "../Icon";
+import { LinkBroken } from "../../icons";
+import "./OfflineBanner.css";
+
+// Error message from the backend when a download is attempted while offline
+export const OFFLINE_ERROR = "Cannot download while in offline mode";
+
+let [offline, setOffline] = createSignal(false);
+
+// Whether the launcher is in offline mode and only using cached data
+export function isOffline() {
+	return offline();
+}
+
+// Banner shown at the top of the window when there is no connection
+export default function OfflineBanner() {
+	let [unlisten, _] = createResource(async () => {
+		let unlisten = await listen(
+			"nitro_offline_status",
+			(event: Event<boolean>) => {
+				setOffline(event.payload);
+			},
+		);
+
+		try {
+			setOffline((await invoke("get_offline_status")) as boolean);
+		} catch (e) {
+			console.error(e);
+		}
+
+		return unlisten;
+	});
+
+	onCleanup(() => {
+		if (unlisten() != undefined) {
+			unlisten()!();
+		}
+	});
+
+	return (
+		<Show when={offline()}>
+			<div id="offline-banner" class="cont" role="status">
+				<Icon icon={LinkBroken} size="1rem" />
+				You are offline. Launching, logs, and cached packages still work.
+			</div>
+		</Show>
+	);
+}
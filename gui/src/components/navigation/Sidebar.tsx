@@ -90,14 +90,26 @@ export default function Sidebar(props: SidebarProps) {
 		return allInstances;
 	});
 
+	// Refresh when the config is changed outside of the launcher
+	let unlistenConfig = listen("nitro_config_changed", () => {
+		instanceButtonMethods.refetch();
+	});
+
+	let [settingsVisible, setSettingsVisible] = createSignal(false);
+
+	// Other parts of the launcher, like the command palette, can ask for the settings to be opened
+	let unlistenOpenSettings = listen("nitro_open_settings", () => {
+		setSettingsVisible(true);
+	});
+
 	onCleanup(() => {
 		if (unlisten() != undefined) {
 			unlisten()!();
 		}
+		unlistenConfig.then((unlisten) => unlisten());
+		unlistenOpenSettings.then((unlisten) => unlisten());
 	});
 
-	let [settingsVisible, setSettingsVisible] = createSignal(false);
-
 	let [nitroVersion, __] = createResource(async () => {
 		return (await invoke("get_nitro_version")) as string;
 	});
@@ -0,0 +1,99 @@
+import { createResource, For, Show } from "solid-js";
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-shell";
+import Modal from "../dialog/Modal";
+import { Delete, Popout, User } from "../../icons";
+import { AuthorProfile } from "../../types";
+import { parsePkgRequest, getCachedImageSrc } from "../../utils";
+import PackageChip from "./PackageChip";
+import LoadingSpinner from "../utility/LoadingSpinner";
+import "./AuthorModal.css";
+
+// Shows the profile of a package author and the other packages they work on
+export default function AuthorModal(props: AuthorModalProps) {
+	let [profile] = createResource(
+		() =>
+			props.author == undefined ? undefined : [props.repository, props.author],
+		async ([repository, author]) => {
+			try {
+				return (await invoke("get_package_author", {
+					repository: repository,
+					author: author,
+				})) as AuthorProfile | undefined;
+			} catch (e) {
+				console.error("Failed to load author: " + e);
+				return undefined;
+			}
+		},
+	);
+
+	let buttons = () => {
+		let out = [
+			{
+				text: "Close",
+				icon: Delete,
+				onClick: props.onClose,
+			},
+		];
+		let url = profile()?.url;
+		if (url != undefined) {
+			out.push({
+				text: "Open Profile",
+				icon: Popout,
+				onClick: () => open(url),
+			});
+		}
+		return out;
+	};
+
+	return (
+		<Modal
+			width="35rem"
+			height="30rem"
+			titleIcon={User}
+			title={profile() == undefined ? "Author" : profile()!.name}
+			visible={props.author != undefined}
+			onClose={props.onClose}
+			buttons={buttons()}
+		>
+			<Show
+				when={profile() != undefined}
+				fallback={
+					<div class="cont">
+						<Show when={profile.loading} fallback="Author not found">
+							<LoadingSpinner size="3rem" />
+						</Show>
+					</div>
+				}
+			>
+				<div class="cont col author-profile">
+					<div class="cont author-header">
+						<Show when={profile()!.avatar != undefined}>
+							<img
+								class="author-avatar"
+								src={getCachedImageSrc(profile()!.avatar!, "medium")}
+							/>
+						</Show>
+						<div class="author-bio">
+							{profile()!.bio == undefined ? "" : profile()!.bio}
+						</div>
+					</div>
+					<div class="cont start bold">PACKAGES</div>
+					<div class="cont start author-packages">
+						<For each={profile()!.packages}>
+							{(pkg) => <PackageChip req={parsePkgRequest(pkg)} />}
+						</For>
+					</div>
+				</div>
+			</Show>
+		</Modal>
+	);
+}
+
+export interface AuthorModalProps {
+	// The repository that the author is from
+	repository?: string;
+	// The ID of the author to show, or undefined to hide the modal
+	author?: string;
+	onClose: () => void;
+}
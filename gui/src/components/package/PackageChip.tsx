@@ -4,7 +4,7 @@ import { PackageMeta, PkgRequest } from "../../types";
 import { useNavigate } from "@solidjs/router";
 
 import "./PackageChip.css";
-import { pkgRequestToString } from "../../utils";
+import { pkgRequestToString, getCachedImageSrc } from "../../utils";
 
 export default function PackageChip(props: PackageChipProps) {
 	let navigate = useNavigate();
@@ -25,7 +25,7 @@ export default function PackageChip(props: PackageChipProps) {
 	}}>
 		<img
 			class="package-chip-icon"
-			src={meta().icon == undefined ? "icons/default_instance.png" : meta().icon!}
+			src={meta().icon == undefined ? "icons/default_instance.png" : getCachedImageSrc(meta().icon!, "small")}
 		/>
 		{meta().name == undefined ? pkg() : meta().name}
 	</div>
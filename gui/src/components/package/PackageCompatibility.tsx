@@ -0,0 +1,75 @@
+import { createResource, For, Show, Suspense } from "solid-js";
+import { invoke } from "@tauri-apps/api/core";
+import "./PackageCompatibility.css";
+import { CompatibilityMatrix } from "../../types";
+import { errorToast } from "../dialog/Toasts";
+import LoadingSpinner from "../utility/LoadingSpinner";
+import PackageLabels from "./PackageLabels";
+import { getAllLoaders } from "../../package";
+
+// Table of which Minecraft versions and loaders each version of a package supports
+export default function PackageCompatibility(props: PackageCompatibilityProps) {
+	let [matrix, _] = createResource(async () => {
+		try {
+			let matrix: CompatibilityMatrix | undefined = await invoke(
+				"get_package_compatibility",
+				{ package: props.packageId }
+			);
+			return matrix;
+		} catch (e) {
+			errorToast("Failed to load compatibility: " + e);
+			return undefined;
+		}
+	});
+
+	return (
+		<div class="cont col package-compatibility">
+			<Suspense fallback={<LoadingSpinner size="5rem" />}>
+				<Show
+					when={matrix() != undefined}
+					fallback={
+						<div style="padding:1rem;font-weight:bold">
+							Compatibility information is not available for this package
+						</div>
+					}
+				>
+					<div class="package-compatibility-row package-compatibility-header">
+						<div>Version</div>
+						<div>Minecraft Versions</div>
+						<div>Loaders</div>
+					</div>
+					<For each={matrix()!.entries}>
+						{(entry) => (
+							<div class="package-compatibility-row">
+								<div class="bold">
+									{entry.content_version == undefined
+										? "Unknown"
+										: entry.content_version}
+								</div>
+								<div class="package-compatibility-versions">
+									{entry.minecraft_versions == undefined
+										? "Any"
+										: entry.minecraft_versions.join(", ")}
+								</div>
+								<div class="cont start">
+									<Show when={entry.loaders != undefined} fallback="Any">
+										<PackageLabels
+											loaders={getAllLoaders(entry.loaders!)}
+											packageTypes={[]}
+											categories={[]}
+											small
+										/>
+									</Show>
+								</div>
+							</div>
+						)}
+					</For>
+				</Show>
+			</Suspense>
+		</div>
+	);
+}
+
+export interface PackageCompatibilityProps {
+	packageId: string;
+}
@@ -5,6 +5,7 @@ import "./PackageDiffsPrompt.css";
 import { createResource, For, Match, Switch } from "solid-js";
 import { PackageMeta } from "../../types";
 import Icon from "../Icon";
+import { getCachedImageSrc } from "../../utils";
 
 export default function PackageDiffsPrompt(props: PackageDiffsPromptProps) {
 	async function setAnswer(answer: boolean) {
@@ -77,7 +78,7 @@ export default function PackageDiffsPrompt(props: PackageDiffsPromptProps) {
 									src={
 										meta() == undefined || meta()!.icon == undefined
 											? "icons/default_instance.png"
-											: meta()!.icon!
+											: getCachedImageSrc(meta()!.icon!, "small")
 									}
 								/>
 								{meta() == undefined || meta()!.name == undefined
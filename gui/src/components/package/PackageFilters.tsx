@@ -21,6 +21,7 @@ import {
 	Loader,
 	PackageCategory,
 	packageCategoryDisplayName,
+	PackageSortMethod,
 	packageCategoryIcon,
 } from "../../package";
 import InlineSelect from "../input/select/InlineSelect";
@@ -472,6 +473,7 @@ export interface PackageFilterOptions {
 	loaders: string[];
 	stability?: "stable" | "latest";
 	categories: PackageCategory[];
+	sort?: PackageSortMethod;
 }
 
 export function defaultPackageFilters() {
@@ -3,10 +3,13 @@ import Icon from "../Icon";
 import { ArrowLeft, ArrowRight } from "../../icons";
 import ModalBase from "../dialog/ModalBase";
 import "./PackageGallery.css";
+import { GalleryImage } from "../../types";
 
 export default function PackageGallery(props: PackageGalleryProps) {
-	// The URL and index of the previewed gallery entry. Undefined if not shown.
-	let [preview, setPreview] = createSignal<[string, number] | undefined>();
+	// The previewed gallery entry and its index. Undefined if not shown.
+	let [preview, setPreview] = createSignal<
+		[GalleryImage, number] | undefined
+	>();
 
 	return (
 		<div class="package-gallery">
@@ -14,7 +17,8 @@ export default function PackageGallery(props: PackageGalleryProps) {
 				{(entry, i) => (
 					<img
 						class="package-gallery-entry shadow bubble-hover"
-						src={entry}
+						src={entry.url}
+						title={entry.title}
 						onclick={() => setPreview([entry, i()])}
 					/>
 				)}
@@ -26,9 +30,30 @@ export default function PackageGallery(props: PackageGalleryProps) {
 			>
 				<img
 					class="package-gallery-preview"
-					src={preview()![0]}
+					src={
+						preview()![0].raw_url == undefined
+							? preview()![0].url
+							: preview()![0].raw_url
+					}
 					onclick={() => setPreview(undefined)}
 				/>
+				<Show
+					when={
+						preview()![0].title != undefined ||
+						preview()![0].description != undefined
+					}
+				>
+					<div class="cont col package-gallery-caption">
+						<Show when={preview()![0].title != undefined}>
+							<div class="bold">{preview()![0].title}</div>
+						</Show>
+						<Show when={preview()![0].description != undefined}>
+							<div class="package-gallery-description">
+								{preview()![0].description}
+							</div>
+						</Show>
+					</div>
+				</Show>
 				<Show when={preview()![1] > 0}>
 					<div
 						class="cont bubble-hover pop-in-fast package-gallery-arrow"
@@ -59,5 +84,5 @@ export default function PackageGallery(props: PackageGalleryProps) {
 }
 
 export interface PackageGalleryProps {
-	gallery: string[];
+	gallery: GalleryImage[];
 }
@@ -12,11 +12,10 @@ import { errorToast } from "../dialog/Toasts";
 import { searchPackages } from "../../utils/package";
 import "./PackageQuickAdd.css";
 import SearchBar from "../input/text/SearchBar";
-import { parsePkgRequest } from "../../utils";
+import { parsePkgRequest, getCachedImageSrc } from "../../utils";
 import { invoke } from "@tauri-apps/api/core";
 import { PackageMeta, PackageProperties, PkgRequest } from "../../types";
 import LoadingSpinner from "../utility/LoadingSpinner";
-import { marked } from "marked";
 import InlineSelect from "../input/select/InlineSelect";
 import Icon from "../Icon";
 import { Plus } from "../../icons";
@@ -85,10 +84,12 @@ export default function PackageQuickAdd(props: PackageQuickAddProps) {
 
 				let longDescription =
 					meta.long_description == undefined ? "" : meta.long_description;
-				let longDescriptionHtml = `<div>${await marked.parse(
-					longDescription,
-				)}</div>`;
-				longDescriptionHtml = replaceExternalLinks(longDescriptionHtml);
+				let longDescriptionHtml = (await invoke("render_markdown", {
+					markdown: longDescription,
+				})) as string;
+				longDescriptionHtml = replaceExternalLinks(
+					`<div>${longDescriptionHtml}</div>`,
+				);
 				setLongDescription(longDescriptionHtml);
 
 				return [meta, props] as [PackageMeta, PackageProperties];
@@ -179,7 +180,7 @@ export default function PackageQuickAdd(props: PackageQuickAddProps) {
 							let icon =
 								pkg.meta.icon == undefined
 									? "/icons/default_instance.png"
-									: pkg.meta.icon;
+									: getCachedImageSrc(pkg.meta.icon, "small");
 
 							let isSelected = () => previewedPackage() == pkg.id;
 
@@ -19,6 +19,7 @@ import Icon, { HasWidthHeight } from "../Icon";
 import PackageLabels from "./PackageLabels";
 import { useNavigate } from "@solidjs/router";
 import Modal from "../dialog/Modal";
+import { formatBytes, getCachedImageSrc } from "../../utils";
 
 export default function PackageVersionInfo(props: PackageVersionInfoProps) {
 	let dependencies = () =>
@@ -156,6 +157,14 @@ export default function PackageVersionInfo(props: PackageVersionInfoProps) {
 							/>
 						</div>
 					</div>
+					<Show when={props.version.size != undefined}>
+						<div class="package-version-info-details-row">
+							<div class="cont start bold">Download Size</div>
+							<div class="cont start package-version-info-details-row-values">
+								{formatBytes(props.version.size!)}
+							</div>
+						</div>
+					</Show>
 				</div>
 				<div
 					class="cont col"
@@ -230,7 +239,7 @@ function RelationList(props: RelationListProps) {
 							let icon = () =>
 								meta() == undefined || meta()!.icon == undefined
 									? "/icons/default_instance.png"
-									: meta()!.icon;
+									: getCachedImageSrc(meta()!.icon!, "small");
 							let name = () =>
 								meta() == undefined || meta()!.name == undefined
 									? id
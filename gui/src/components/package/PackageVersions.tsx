@@ -105,6 +105,7 @@ export default function PackageVersions(props: PackageVersionsProps) {
 						operating_systems: version.operating_systems,
 						architectures: version.architectures,
 						languages: version.languages,
+						size: version.size,
 					};
 
 					// Add a new version or append an addon to one that already exists
@@ -114,7 +115,13 @@ export default function PackageVersions(props: PackageVersionsProps) {
 						if (versionsWithIds[contentVersion] == undefined) {
 							versionsWithIds[contentVersion] = newVersion;
 						} else {
-							versionsWithIds[contentVersion].addons.push(packageAddon);
+							let existing = versionsWithIds[contentVersion];
+							existing.addons.push(packageAddon);
+							if (existing.size != undefined && version.size != undefined) {
+								existing.size += version.size;
+							} else {
+								existing.size = undefined;
+							}
 						}
 					}
 				}
@@ -2,9 +2,12 @@ import { errorToast, warningToast } from "../dialog/Toasts";
 import { PackageCategory, PackageType, RepoInfo } from "../../package";
 import { invoke } from "@tauri-apps/api/core";
 import { createSignal, Show, createResource, createEffect } from "solid-js";
-import InlineSelect from "../input/select/InlineSelect";
+import InlineSelect, {
+	Option as SelectOption,
+} from "../input/select/InlineSelect";
 import Icon, { HTMLIcon } from "../Icon";
 import { Honeycomb } from "../../icons";
+import { ALL_REPOS } from "../../utils/package";
 
 export default function RepoSelector(props: RepoSelectorProps) {
 	let [repos, setRepos] = createSignal<RepoInfo[] | undefined>();
@@ -18,7 +21,10 @@ export default function RepoSelector(props: RepoSelectorProps) {
 		}
 		if (props.selectedRepo == undefined) {
 			if (lastSelectedRepo() != undefined) {
-				if (repos()!.some((x) => x.id == lastSelectedRepo())) {
+				if (
+					lastSelectedRepo() == ALL_REPOS ||
+					repos()!.some((x) => x.id == lastSelectedRepo())
+				) {
 					return lastSelectedRepo();
 				}
 			}
@@ -86,53 +92,64 @@ export default function RepoSelector(props: RepoSelectorProps) {
 		}
 	});
 
+	let allReposOption: SelectOption = {
+		value: ALL_REPOS,
+		contents: <div style="padding:0rem 0.3rem">ALL</div>,
+		color: "var(--package)",
+		selectedBgColor: "var(--packagebg)",
+		selectedTextColor: "var(--package)",
+		tip: "All Repositories",
+	};
+
 	return (
 		<Show when={!result.loading}>
 			<InlineSelect
-				options={repos()!.map((x) => {
-					if (x.id == "std") {
-						return {
-							value: "std",
-							contents: (
+				options={[allReposOption].concat(
+					repos()!.map((x) => {
+						if (x.id == "std") {
+							return {
+								value: "std",
+								contents: (
+									<div class="cont" style="width:1.5rem;aspect-ratio:1">
+										<Icon icon={Honeycomb} size="1rem" />
+									</div>
+								),
+								color: "var(--package)",
+								selectedBgColor: "var(--packagebg)",
+								selectedTextColor: "var(--package)",
+								tip: "Standard Repository",
+							};
+						}
+
+						let contents;
+						if (x.meta.icon == undefined) {
+							contents = (
+								<div style="padding:0rem 0.3rem">
+									{x.meta.name == undefined
+										? x.id.replace(/\_/g, " ").toLocaleUpperCase()
+										: x.meta.name.toLocaleUpperCase()}
+								</div>
+							);
+						} else {
+							contents = (
 								<div class="cont" style="width:1.5rem;aspect-ratio:1">
-									<Icon icon={Honeycomb} size="1rem" />
+									<Icon icon={HTMLIcon(x.meta.icon)} size="1rem" />
 								</div>
-							),
-							color: "var(--package)",
-							selectedBgColor: "var(--packagebg)",
-							selectedTextColor: "var(--package)",
-							tip: "Standard Repository",
-						};
-					}
-
-					let contents;
-					if (x.meta.icon == undefined) {
-						contents = (
-							<div style="padding:0rem 0.3rem">
-								{x.meta.name == undefined
-									? x.id.replace(/\_/g, " ").toLocaleUpperCase()
-									: x.meta.name.toLocaleUpperCase()}
-							</div>
-						);
-					} else {
-						contents = (
-							<div class="cont" style="width:1.5rem;aspect-ratio:1">
-								<Icon icon={HTMLIcon(x.meta.icon)} size="1rem" />
-							</div>
-						);
-					}
+							);
+						}
 
-					return {
-						value: x.id,
-						contents: contents,
-						color: x.meta.color,
-						selectedTextColor: x.meta.text_color,
-						tip: x.meta.name,
-					};
-				})}
+						return {
+							value: x.id,
+							contents: contents,
+							color: x.meta.color,
+							selectedTextColor: x.meta.text_color,
+							tip: x.meta.name,
+						};
+					}),
+				)}
 				grid={false}
 				selected={selectedRepo()}
-				columns={repos()!.length}
+				columns={repos()!.length + 1}
 				onChange={(x) => {
 					if (x != undefined) {
 						props.onSelect(x);
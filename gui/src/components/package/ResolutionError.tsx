@@ -1,22 +1,68 @@
-import { For, Match, Switch } from "solid-js";
+import { createSignal, For, Match, Show, Switch } from "solid-js";
 import { PkgRequest } from "../../types";
 import "./ResolutionError.css";
 import PackageChip from "./PackageChip";
+import Icon from "../Icon";
+import { AngleDown, AngleRight } from "../../icons";
+import { pkgRequestToString } from "../../utils";
 
-// Displays an error during package resolution
+// Displays an error during package resolution, along with suggested fixes
 export default function ResolutionError(props: ResolutionErrorProps) {
+	let fixes = () => (props.error.fixes == undefined ? [] : props.error.fixes);
+
+	return (
+		<div class="cont col package-resolution-error">
+			<ResolutionErrorNode error={props.error} />
+			<Show when={fixes().length > 0}>
+				<div class="cont col start resolution-error-fixes">
+					<div class="resolution-error-header">Suggested fixes</div>
+					<For each={fixes()}>
+						{(fix) => (
+							<div class="cont resolution-error-fix">
+								<Switch>
+									<Match when={fix.type == "remove_package"}>
+										Remove the package <PackageChip req={fix.package} />
+									</Match>
+									<Match when={fix.type == "add_package"}>
+										Add the package <PackageChip req={fix.package} />
+									</Match>
+									<Match when={fix.type == "pin_version"}>
+										Pin <PackageChip req={fix.package} /> to version
+										<code>{(fix as any).version}</code>
+									</Match>
+								</Switch>
+							</div>
+						)}
+					</For>
+				</div>
+			</Show>
+		</div>
+	);
+}
+
+// A single level of the error tree
+function ResolutionErrorNode(props: { error: ResolutionErrorData }) {
 	let data = () => props.error.data as any;
 
+	let [isOpen, setIsOpen] = createSignal(true);
+
 	return (
 		<div class="cont col package-resolution-error">
 			<Switch>
 				<Match when={props.error.type == "package_context"}>
-					<div class="cont resolution-error-context">
+					<div
+						class="cont start resolution-error-context"
+						onclick={() => setIsOpen((x) => !x)}
+					>
+						<Icon icon={isOpen() ? AngleDown : AngleRight} size="1rem" />
 						In <PackageChip req={data()[0]} />
+						<RequestChain req={data()[0]} />
 					</div>
-					<div class="cont" style="padding:0.5rem;box-sizing:border-box">
-						<ResolutionError error={data()[1]} />
-					</div>
+					<Show when={isOpen()}>
+						<div class="cont" style="padding:0.5rem;box-sizing:border-box">
+							<ResolutionErrorNode error={data()[1]} />
+						</div>
+					</Show>
 				</Match>
 				<Match when={props.error.type == "failed_to_preload"}>
 					<div class="cont resolution-error-header">
@@ -31,9 +77,17 @@ export default function ResolutionError(props: ResolutionErrorProps) {
 					<pre class="cont full-error">{data()[1]}</pre>
 				</Match>
 				<Match when={props.error.type == "no_valid_versions_found"}>
-					<div class="cont">
-						No valid versions found for <PackageChip req={data()[0]} />
-						Requested versions: {data()[1].toString()}
+					<div class="cont col start">
+						<div class="cont">
+							No valid versions found for <PackageChip req={data()[0]} />
+						</div>
+						<RequestChain req={data()[0]} />
+						<div class="cont start resolution-error-versions">
+							Considered versions:
+							<For each={data()[1] as string[]}>
+								{(version) => <code>{version}</code>}
+							</For>
+						</div>
 					</div>
 				</Match>
 				<Match when={props.error.type == "extension_not_fulfilled"}>
@@ -79,10 +133,44 @@ export default function ResolutionError(props: ResolutionErrorProps) {
 	);
 }
 
+// Shows where a package was requested from, like foo -> bar -> baz
+function RequestChain(props: { req: PkgRequest }) {
+	let chain = () => {
+		let out = [];
+		let req: PkgRequest | undefined = props.req;
+		while (req != undefined) {
+			out.unshift(pkgRequestToString(req));
+			let source: any = req.source;
+			req =
+				source == undefined || typeof source == "string"
+					? undefined
+					: (source.dependency ?? source.bundled ?? source.refused);
+		}
+
+		return out;
+	};
+
+	return (
+		<Show when={chain().length > 1}>
+			<div class="resolution-error-chain">{chain().join(" -> ")}</div>
+		</Show>
+	);
+}
+
 export interface ResolutionErrorProps {
-	error: ResolutionErrorData;
+	error: ExplainedResolutionError;
 }
 
+// A resolution error from the backend with suggestions for fixing it
+export type ExplainedResolutionError = ResolutionErrorData & {
+	fixes?: ResolutionFix[];
+};
+
+export type ResolutionFix =
+	| { type: "remove_package"; package: PkgRequest }
+	| { type: "add_package"; package: PkgRequest }
+	| { type: "pin_version"; package: PkgRequest; version: string };
+
 // Data for the actual error
 export type ResolutionErrorData =
 	| {
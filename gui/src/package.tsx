@@ -73,6 +73,8 @@ export interface PackageVersion {
 	operating_systems?: string | string[];
 	architectures?: string | string[];
 	languages?: string | string[];
+	// Combined size of the addon files in bytes
+	size?: number;
 }
 
 export interface PackageAddon {
@@ -102,6 +104,7 @@ export interface DeclarativeAddonVersion {
 	operating_systems?: string | string[];
 	architectures?: string | string[];
 	languages?: string | string[];
+	size?: number;
 }
 
 export interface DeclarativePackageRelations {
@@ -125,6 +128,29 @@ export type AddonKind =
 
 export type PackageType = AddonKind | "bundle" | "modpack";
 
+// Method for sorting package search results
+export type PackageSortMethod =
+	| "relevance"
+	| "downloads"
+	| "followers"
+	| "updated"
+	| "newest";
+
+export function packageSortMethodDisplayName(method: PackageSortMethod) {
+	switch (method) {
+		case "relevance":
+			return "Relevance";
+		case "downloads":
+			return "Downloads";
+		case "followers":
+			return "Followers";
+		case "updated":
+			return "Recently Updated";
+		case "newest":
+			return "Newest";
+	}
+}
+
 export enum PackageCategory {
 	Adventure = "adventure",
 	Atmosphere = "atmosphere",
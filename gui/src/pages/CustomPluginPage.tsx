@@ -1,32 +1,33 @@
 import { useParams } from "@solidjs/router";
 import { invoke } from "@tauri-apps/api/core";
-import { createEffect, createResource } from "solid-js";
+import { Event, listen, UnlistenFn } from "@tauri-apps/api/event";
+import { createEffect, createResource, onCleanup, Show } from "solid-js";
 import { setupPluginFunctions } from "../plugins";
 
 export default function ExecutablePluginPage() {
 	let params = useParams();
 
-	let [html, _] = createResource(
+	let [page, _] = createResource(
 		() => params.page,
 		async (page) => {
-			let html: string | undefined = await invoke("get_plugin_page", {
+			let result: PluginPage | undefined = await invoke("get_plugin_page", {
 				page: page,
 			});
-			return html;
+			return result;
 		}
 	);
 
 	// We need to run script tags
 	// https://stackoverflow.com/questions/2592092/executing-script-elements-inserted-with-innerhtml
 	createEffect(() => {
-		setupPluginFunctions();
-		let page = document.getElementById("custom-plugin-page")!;
-		if (html() == undefined) {
+		if (page() == undefined || page()!.sandboxed) {
 			return;
 		}
-		page.innerHTML = html()!;
+		setupPluginFunctions();
+		let element = document.getElementById("custom-plugin-page")!;
+		element.innerHTML = page()!.html;
 
-		let scripts = page.getElementsByTagName("script");
+		let scripts = element.getElementsByTagName("script");
 		if (scripts != undefined) {
 			for (let oldScriptEl of scripts) {
 				const newScriptEl = document.createElement("script");
@@ -43,5 +44,180 @@ export default function ExecutablePluginPage() {
 		}
 	});
 
-	return <div id="custom-plugin-page"></div>;
+	return (
+		<Show
+			when={page() != undefined && page()!.sandboxed}
+			fallback={<div id="custom-plugin-page"></div>}
+		>
+			<SandboxedPluginPage page={page()!} />
+		</Show>
+	);
+}
+
+// Renders a plugin page in a sandboxed iframe, which can only talk to the launcher through a message bridge
+function SandboxedPluginPage(props: { page: PluginPage }) {
+	let frame: HTMLIFrameElement | undefined;
+	let unlistens: Promise<UnlistenFn>[] = [];
+
+	let onMessage = async (e: MessageEvent) => {
+		if (frame == undefined || e.source !== frame.contentWindow) {
+			return;
+		}
+
+		let message = e.data as BridgeMessage;
+		let reply = (data: any) => frame!.contentWindow!.postMessage(data, "*");
+
+		if (message.type == "action") {
+			try {
+				let result = await invoke("run_plugin_page_action", {
+					plugin: props.page.plugin,
+					action: message.action,
+					payload: message.payload,
+				});
+				reply({ type: "result", id: message.id, result: result });
+			} catch (error) {
+				reply({ type: "result", id: message.id, error: `${error}` });
+			}
+		} else if (message.type == "theme") {
+			reply({ type: "result", id: message.id, result: getThemeTokens() });
+		} else if (message.type == "subscribe") {
+			let event = message.event;
+			unlistens.push(
+				listen("nitro_plugin_event", (e: Event<PluginEvent>) => {
+					if (
+						e.payload.plugin == props.page.plugin &&
+						e.payload.event == event
+					) {
+						reply({ type: "event", event: event, data: e.payload.data });
+					}
+				})
+			);
+		}
+	};
+
+	window.addEventListener("message", onMessage);
+	onCleanup(async () => {
+		window.removeEventListener("message", onMessage);
+		for (let unlisten of await Promise.all(unlistens)) {
+			unlisten();
+		}
+	});
+
+	let srcdoc = () => {
+		let tokens = Object.entries(getThemeTokens())
+			.map(([name, value]) => `${name}: ${value};`)
+			.join("");
+		return `<style>:root{${tokens}}</style>${BRIDGE_SCRIPT}${props.page.html}`;
+	};
+
+	return (
+		<iframe
+			ref={frame}
+			id="custom-plugin-page"
+			sandbox="allow-scripts"
+			srcdoc={srcdoc()}
+			style="width:100%;height:100%;border:none"
+		></iframe>
+	);
+}
+
+// Gets the current values of the theme CSS variables that are given to sandboxed pages
+function getThemeTokens() {
+	let style = getComputedStyle(document.documentElement);
+	let out: { [name: string]: string } = {};
+	for (let token of THEME_TOKENS) {
+		out[token] = style.getPropertyValue(token).trim();
+	}
+	return out;
+}
+
+const THEME_TOKENS = [
+	"--fg",
+	"--fg2",
+	"--fg3",
+	"--bg0",
+	"--bg",
+	"--bg2",
+	"--bg3",
+	"--bg4",
+	"--text",
+	"--instance",
+	"--template",
+	"--package",
+	"--plugin",
+	"--pluginfg",
+	"--pluginbg",
+	"--warning",
+	"--error",
+	"--errorbg",
+	"--round",
+	"--round2",
+	"--border",
+];
+
+// The API that is given to scripts in sandboxed pages as `window.nitro`
+const BRIDGE_SCRIPT = `<script>
+(function () {
+	let nextId = 0;
+	let pending = {};
+	let listeners = {};
+
+	window.addEventListener("message", (e) => {
+		if (e.source !== window.parent) {
+			return;
+		}
+		let message = e.data;
+		if (message.type == "result" && pending[message.id] != undefined) {
+			let request = pending[message.id];
+			delete pending[message.id];
+			if (message.error != undefined) {
+				request.reject(message.error);
+			} else {
+				request.resolve(message.result);
+			}
+		} else if (message.type == "event" && listeners[message.event] != undefined) {
+			for (let listener of listeners[message.event]) {
+				listener(message.data);
+			}
+		}
+	});
+
+	function request(message) {
+		return new Promise((resolve, reject) => {
+			let id = nextId++;
+			pending[id] = { resolve: resolve, reject: reject };
+			window.parent.postMessage({ ...message, id: id }, "*");
+		});
+	}
+
+	window.nitro = {
+		customAction: (action, payload) =>
+			request({ type: "action", action: action, payload: payload }),
+		getTheme: () => request({ type: "theme" }),
+		onEvent: (event, listener) => {
+			if (listeners[event] == undefined) {
+				listeners[event] = [];
+				window.parent.postMessage({ type: "subscribe", event: event }, "*");
+			}
+			listeners[event].push(listener);
+		},
+	};
+})();
+</script>`;
+
+interface PluginPage {
+	plugin: string;
+	html: string;
+	sandboxed: boolean;
+}
+
+interface PluginEvent {
+	plugin: string;
+	event: string;
+	data: any;
 }
+
+type BridgeMessage =
+	| { type: "action"; id: number; action: string; payload: any }
+	| { type: "theme"; id: number }
+	| { type: "subscribe"; event: string };
@@ -11,14 +11,28 @@ import Tip from "../components/dialog/Tip";
 import InlineSelect from "../components/input/select/InlineSelect";
 import { errorToast, successToast } from "../components/dialog/Toasts";
 import { emit } from "@tauri-apps/api/event";
+import { open, save } from "@tauri-apps/plugin-dialog";
 import IconTextButton from "../components/input/button/IconTextButton";
-import { Check, Delete, Folder, Gear, Jigsaw, Text } from "../icons";
+import {
+	Check,
+	Delete,
+	Download,
+	Edit,
+	Folder,
+	Gear,
+	Jigsaw,
+	Plus,
+	Text,
+	Upload,
+} from "../icons";
 import Modal, { ModalButton } from "../components/dialog/Modal";
 import FloatingTabs from "../components/input/select/FloatingTabs";
 import ApplicationLog from "../components/ApplicationLog";
+import ErrorReports from "../components/ErrorReports";
 import { ControlData } from "../components/input/Control";
 import { ControlledConfig } from "./instance/read_write";
 import ControlSections from "../components/input/ControlSections";
+import ThemeEditor from "./ThemeEditor";
 
 export default function Settings(props: SettingsProps) {
 	let [settings, settingsMethods] = createResource(
@@ -27,7 +41,7 @@ export default function Settings(props: SettingsProps) {
 
 	let [tab, setTab] = createSignal("general");
 
-	let [availableThemes, __] = createResource(async () => {
+	let [availableThemes, availableThemesMethods] = createResource(async () => {
 		let themes: Theme[] = [];
 		try {
 			themes = (await invoke("get_themes")) as Theme[];
@@ -56,6 +70,75 @@ export default function Settings(props: SettingsProps) {
 		return themes;
 	});
 
+	let [customThemes, customThemesMethods] = createResource(
+		async () => {
+			try {
+				return (await invoke("get_custom_themes")) as Theme[];
+			} catch (e) {
+				errorToast("Failed to load custom themes: " + e);
+				return [];
+			}
+		},
+		{ initialValue: [] },
+	);
+
+	let [editorVisible, setEditorVisible] = createSignal(false);
+	let [editedTheme, setEditedTheme] = createSignal<Theme | undefined>();
+
+	function openThemeEditor(theme?: Theme) {
+		setEditedTheme(theme);
+		setEditorVisible(true);
+	}
+
+	function onThemesChanged() {
+		availableThemesMethods.refetch();
+		customThemesMethods.refetch();
+		emit("update_theme");
+	}
+
+	async function importTheme() {
+		try {
+			let path = await open({
+				filters: [{ name: "Theme", extensions: ["json"] }],
+			});
+			if (path == null) {
+				return;
+			}
+
+			let theme = (await invoke("import_custom_theme", { path: path })) as Theme;
+			successToast(`Imported theme ${theme.name}`);
+			onThemesChanged();
+		} catch (e) {
+			errorToast("Failed to import theme: " + e);
+		}
+	}
+
+	async function exportTheme(theme: Theme) {
+		try {
+			let path = await save({
+				defaultPath: `${theme.id}.json`,
+				filters: [{ name: "Theme", extensions: ["json"] }],
+			});
+			if (path == null) {
+				return;
+			}
+
+			await invoke("export_custom_theme", { id: theme.id, path: path });
+			successToast("Theme exported");
+		} catch (e) {
+			errorToast("Failed to export theme: " + e);
+		}
+	}
+
+	async function deleteTheme(theme: Theme) {
+		try {
+			await invoke("delete_custom_theme", { id: theme.id });
+			onThemesChanged();
+		} catch (e) {
+			errorToast("Failed to delete theme: " + e);
+		}
+	}
+
 	let [isDirty, setIsDirty] = createSignal(false);
 
 	createEffect(() => {
@@ -67,6 +150,8 @@ export default function Settings(props: SettingsProps) {
 
 	let [baseTheme, setBaseTheme] = createSignal<string>("dark");
 	let [overlayThemes, setOverlayThemes] = createSignal<string[]>([]);
+	let [uiScale, setUiScale] = createSignal("1");
+	let [fontScale, setFontScale] = createSignal("1");
 
 	let pluginConfig: { [plugin: string]: ControlledConfig } = {};
 	createEffect(async () => {
@@ -109,6 +194,10 @@ export default function Settings(props: SettingsProps) {
 		let baseTheme = settings()!.base_theme;
 		setBaseTheme(baseTheme == undefined ? "dark" : baseTheme);
 		setOverlayThemes(settings()!.overlay_themes);
+		setUiScale(`${settings()!.ui_scale == undefined ? 1 : settings()!.ui_scale}`);
+		setFontScale(
+			`${settings()!.font_scale == undefined ? 1 : settings()!.font_scale}`,
+		);
 		pluginControlsMethods.refetch();
 	});
 
@@ -120,6 +209,8 @@ export default function Settings(props: SettingsProps) {
 		let newSettings: LauncherSettings = {
 			base_theme: baseTheme(),
 			overlay_themes: overlayThemes(),
+			ui_scale: +uiScale() == 1 ? undefined : +uiScale(),
+			font_scale: +fontScale() == 1 ? undefined : +fontScale(),
 		};
 
 		try {
@@ -170,149 +261,258 @@ export default function Settings(props: SettingsProps) {
 	};
 
 	return (
-		<Modal
-			width="60rem"
-			height="35rem"
-			titleIcon={Gear}
-			title="Settings"
-			visible={props.isVisible}
-			onClose={props.onClose}
-			buttons={buttons()}
-		>
-			<div class="cont fullwidth">
-				<FloatingTabs
-					tabs={[
-						{
-							id: "general",
-							title: "General",
-							icon: Gear,
-							color: "var(--instance)",
-							bgColor: "var(--instancebg)",
-						},
-						{
-							id: "logs",
-							title: "Logs",
-							icon: Text,
-							color: "var(--template)",
-							bgColor: "var(--templatebg)",
-						},
-						{
-							id: "plugins",
-							title: "Plugins",
-							icon: Jigsaw,
-							color: "var(--plugin)",
-							bgColor: "var(--pluginbg)",
-						},
-					]}
-					selectedTab={tab()}
-					setTab={setTab}
-				/>
-			</div>
-			<div class="cont fullwidth">
-				<Show when={tab() == "general"}>
-					<div class="cont fields">
-						<div class="cont start label">
-							<label for="theme">BASE THEME</label>
-						</div>
-						<Show when={availableThemes() != undefined}>
-							<InlineSelect
-								onChange={(x) => {
-									setBaseTheme(x as string);
-									setIsDirty(true);
-								}}
-								selected={baseTheme()}
-								options={availableThemes()!
-									.filter((x) => x.type == "base")
-									.map((theme) => {
-										return {
-											value: theme.id,
-											contents: <div>{theme.name}</div>,
-											tip: theme.description,
-											color: theme.color,
-											selectedTextColor: "var(--fg)",
-										};
-									})}
-								columns={3}
-								allowEmpty={false}
-							/>
-						</Show>
-						<div class="cont start label">
-							<label for="theme">OVERLAY THEMES</label>
-						</div>
-						<Show when={availableThemes() != undefined}>
-							<InlineSelect
-								onChangeMulti={(x) => {
-									setOverlayThemes(x as string[]);
-									setIsDirty(true);
-								}}
-								selected={overlayThemes()}
-								options={availableThemes()!
-									.filter((x) => x.type == "overlay")
-									.map((theme) => {
+		<>
+			<Modal
+				width="60rem"
+				height="35rem"
+				titleIcon={Gear}
+				title="Settings"
+				visible={props.isVisible}
+				onClose={props.onClose}
+				buttons={buttons()}
+			>
+				<div class="cont fullwidth">
+					<FloatingTabs
+						tabs={[
+							{
+								id: "general",
+								title: "General",
+								icon: Gear,
+								color: "var(--instance)",
+								bgColor: "var(--instancebg)",
+							},
+							{
+								id: "logs",
+								title: "Logs",
+								icon: Text,
+								color: "var(--template)",
+								bgColor: "var(--templatebg)",
+							},
+							{
+								id: "plugins",
+								title: "Plugins",
+								icon: Jigsaw,
+								color: "var(--plugin)",
+								bgColor: "var(--pluginbg)",
+							},
+						]}
+						selectedTab={tab()}
+						setTab={setTab}
+					/>
+				</div>
+				<div class="cont fullwidth">
+					<Show when={tab() == "general"}>
+						<div class="cont fields">
+							<div class="cont start label">
+								<label for="theme">BASE THEME</label>
+							</div>
+							<Show when={availableThemes() != undefined}>
+								<InlineSelect
+									onChange={(x) => {
+										setBaseTheme(x as string);
+										setIsDirty(true);
+									}}
+									selected={baseTheme()}
+									options={availableThemes()!
+										.filter((x) => x.type == "base")
+										.map((theme) => {
+											return {
+												value: theme.id,
+												contents: <div>{theme.name}</div>,
+												tip: theme.description,
+												color: theme.color,
+												selectedTextColor: "var(--fg)",
+											};
+										})}
+									columns={3}
+									allowEmpty={false}
+								/>
+							</Show>
+							<div class="cont start label">
+								<label for="theme">OVERLAY THEMES</label>
+							</div>
+							<Show when={availableThemes() != undefined}>
+								<InlineSelect
+									onChangeMulti={(x) => {
+										setOverlayThemes(x as string[]);
+										setIsDirty(true);
+									}}
+									selected={overlayThemes()}
+									options={availableThemes()!
+										.filter((x) => x.type == "overlay")
+										.map((theme) => {
+											return {
+												value: theme.id,
+												contents: <div>{theme.name}</div>,
+												tip: theme.description,
+												color: theme.color,
+												selectedTextColor: "var(--fg)",
+											};
+										})}
+									columns={3}
+									allowEmpty={false}
+								/>
+							</Show>
+							<div class="cont start label">
+								<label>CUSTOM THEMES</label>
+							</div>
+							<div class="cont col fullwidth">
+								<For each={customThemes()}>
+									{(theme) => (
+										<div class="cont fullwidth">
+											<div class="cont start" style="flex:1">
+												{theme.name}
+											</div>
+											<IconTextButton
+												icon={Edit}
+												size="1rem"
+												text="Edit"
+												onClick={() => openThemeEditor(theme)}
+											/>
+											<IconTextButton
+												icon={Upload}
+												size="1rem"
+												text="Export"
+												onClick={() => exportTheme(theme)}
+											/>
+											<IconTextButton
+												icon={Delete}
+												size="1rem"
+												text="Delete"
+												onClick={() => deleteTheme(theme)}
+											/>
+										</div>
+									)}
+								</For>
+								<div class="cont">
+									<IconTextButton
+										icon={Plus}
+										size="1rem"
+										text="New theme"
+										onClick={() => openThemeEditor()}
+									/>
+									<IconTextButton
+										icon={Download}
+										size="1rem"
+										text="Import theme"
+										onClick={importTheme}
+									/>
+								</div>
+							</div>
+							<div class="cont start label">
+								<label>UI SCALE</label>
+							</div>
+							<Tip tip="Makes everything in the launcher larger or smaller" fullwidth>
+								<InlineSelect
+									onChange={(x) => {
+										setUiScale(x!);
+										setIsDirty(true);
+									}}
+									selected={uiScale()}
+									options={UI_SCALES.map((scale) => {
 										return {
-											value: theme.id,
-											contents: <div>{theme.name}</div>,
-											tip: theme.description,
-											color: theme.color,
-											selectedTextColor: "var(--fg)",
+											value: `${scale}`,
+											contents: <div>{Math.round(scale * 100)}%</div>,
 										};
 									})}
-								columns={3}
-								allowEmpty={false}
-							/>
-						</Show>
-						<Tip
-							tip="Open the folder where Nitrolaunch stores its instances and data"
-							side="top"
-						>
-							<div class="cont">
-								<IconTextButton
-									icon={Folder}
-									size="1rem"
-									text="Open data folder"
-									onClick={() => invoke("open_data_dir")}
+									columns={UI_SCALES.length}
+									allowEmpty={false}
 								/>
+							</Tip>
+							<div class="cont start label">
+								<label>FONT SIZE</label>
 							</div>
-						</Tip>
-					</div>
-				</Show>
-				<Show when={tab() == "logs"}>
-					<div class="cont col fullwidth">
-						<ApplicationLog />
-					</div>
-				</Show>
-				<Show when={tab() == "plugins"}>
-					<div class="cont col fullwidth">
-						<For each={Object.keys(pluginControls())}>
-							{(plugin) => (
-								<ControlSections
-									controls={pluginControls()[plugin]}
-									getInitialValue={(id) => {
-										if (pluginConfig[plugin] == undefined) {
-											return undefined;
-										} else {
-											return pluginConfig[plugin].getControl(id);
-										}
-									}}
-									setValue={(id, value) => {
-										if (pluginConfig[plugin] == undefined) {
-											pluginConfig[plugin] = new ControlledConfig({});
-										}
-
-										pluginConfig[plugin].setControl(id, value);
+							<Tip tip="Changes the size of text and the spacing around it" fullwidth>
+								<InlineSelect
+									onChange={(x) => {
+										setFontScale(x!);
 										setIsDirty(true);
 									}}
-									parentConfigs={[]}
+									selected={fontScale()}
+									options={FONT_SCALES.map(([scale, name]) => {
+										return {
+											value: `${scale}`,
+											contents: <div>{name}</div>,
+										};
+									})}
+									columns={FONT_SCALES.length}
+									allowEmpty={false}
 								/>
-							)}
-						</For>
-					</div>
-				</Show>
-			</div>
-		</Modal>
+							</Tip>
+							<Tip
+								tip="Open the folder where Nitrolaunch stores its instances and data"
+								side="top"
+							>
+								<div class="cont">
+									<IconTextButton
+										icon={Folder}
+										size="1rem"
+										text="Open data folder"
+										onClick={() => invoke("open_data_dir")}
+									/>
+								</div>
+							</Tip>
+						</div>
+					</Show>
+					<Show when={tab() == "logs"}>
+						<div class="cont col fullwidth">
+							<ApplicationLog />
+							<ErrorReports />
+						</div>
+					</Show>
+					<Show when={tab() == "plugins"}>
+						<div class="cont col fullwidth">
+							<For each={Object.keys(pluginControls())}>
+								{(plugin) => (
+									<ControlSections
+										controls={pluginControls()[plugin]}
+										getInitialValue={(id) => {
+											if (pluginConfig[plugin] == undefined) {
+												return undefined;
+											} else {
+												return pluginConfig[plugin].getControl(id);
+											}
+										}}
+										setValue={(id, value) => {
+											if (pluginConfig[plugin] == undefined) {
+												pluginConfig[plugin] = new ControlledConfig({});
+											}
+
+											pluginConfig[plugin].setControl(id, value);
+											setIsDirty(true);
+										}}
+										parentConfigs={[]}
+									/>
+								)}
+							</For>
+						</div>
+					</Show>
+				</div>
+			</Modal>
+			<ThemeEditor
+				visible={editorVisible()}
+				theme={editedTheme()}
+				onClose={() => setEditorVisible(false)}
+				onSave={() => {
+					setEditorVisible(false);
+					onThemesChanged();
+				}}
+			/>
+		</>
 	);
 }
 
+// Available zoom levels for the UI
+const UI_SCALES = [0.8, 0.9, 1, 1.1, 1.25, 1.5];
+
+// Available font size multipliers and their names
+const FONT_SCALES: [number, string][] = [
+	[1, "Normal"],
+	[1.15, "Large"],
+	[1.3, "Larger"],
+	[1.5, "Largest"],
+];
+
 export interface SettingsProps {
 	isVisible: boolean;
 	onClose: () => void;
@@ -322,4 +522,6 @@ export interface SettingsProps {
 export interface LauncherSettings {
 	base_theme?: string;
 	overlay_themes: string[];
+	ui_scale?: number;
+	font_scale?: number;
 }
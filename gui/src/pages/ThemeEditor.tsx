@@ -0,0 +1,213 @@
+import { invoke } from "@tauri-apps/api/core";
+import { createEffect, createSignal, For, onCleanup, Show } from "solid-js";
+import { Check, Delete, Palette } from "../icons";
+import Modal from "../components/dialog/Modal";
+import InlineSelect from "../components/input/select/InlineSelect";
+import IdInput from "../components/input/text/IdInput";
+import { errorToast, successToast } from "../components/dialog/Toasts";
+import { Theme } from "../types";
+
+// Editor for creating and changing the user's own themes, with a live preview
+export default function ThemeEditor(props: ThemeEditorProps) {
+	let isNew = () => props.theme == undefined;
+
+	let [id, setId] = createSignal("");
+	let [name, setName] = createSignal("");
+	let [type, setType] = createSignal<"base" | "overlay">("base");
+	let [variables, setVariables] = createSignal<{ [name: string]: string }>({});
+	let [font, setFont] = createSignal("");
+
+	createEffect(() => {
+		if (!props.visible) {
+			return;
+		}
+
+		let theme = props.theme;
+		setId(theme == undefined ? "" : theme.id);
+		setName(theme == undefined ? "" : theme.name);
+		setType(theme == undefined ? "base" : theme.type);
+		setFont(theme == undefined || theme.font == undefined ? "" : theme.font);
+
+		// Start from the current look of the launcher so that new themes are tweaks of it
+		let style = getComputedStyle(document.documentElement);
+		let initial: { [name: string]: string } = {};
+		for (let token of THEME_TOKENS) {
+			let value =
+				theme != undefined && theme.variables != undefined
+					? theme.variables[token.id]
+					: undefined;
+			initial[token.id] =
+				value == undefined ? style.getPropertyValue(token.id).trim() : value;
+		}
+		setVariables(initial);
+	});
+
+	// Preview the theme by applying its variables directly to the document
+	createEffect(() => {
+		let root = document.documentElement;
+		if (!props.visible) {
+			clearPreview();
+			return;
+		}
+		for (let [name, value] of Object.entries(variables())) {
+			root.style.setProperty(name, value);
+		}
+		if (font() == "") {
+			document.body.style.removeProperty("font-family");
+		} else {
+			document.body.style.setProperty("font-family", font());
+		}
+	});
+
+	onCleanup(clearPreview);
+
+	function clearPreview() {
+		for (let token of THEME_TOKENS) {
+			document.documentElement.style.removeProperty(token.id);
+		}
+		document.body.style.removeProperty("font-family");
+	}
+
+	async function save() {
+		if (id() == "" || name() == "") {
+			errorToast("Theme must have an ID and name");
+			return;
+		}
+
+		let theme: Theme = {
+			id: id(),
+			name: name(),
+			description: props.theme == undefined ? undefined : props.theme.description,
+			type: type(),
+			css: "",
+			color: variables()["--bg3"],
+			variables: variables(),
+			font: font() == "" ? undefined : font(),
+		};
+
+		try {
+			await invoke("save_custom_theme", { theme: theme });
+			successToast("Theme saved");
+			props.onSave();
+		} catch (e) {
+			errorToast("Failed to save theme: " + e);
+		}
+	}
+
+	return (
+		<Modal
+			width="40rem"
+			height="35rem"
+			titleIcon={Palette}
+			title={isNew() ? "New Theme" : "Edit Theme"}
+			visible={props.visible}
+			onClose={props.onClose}
+			buttons={[
+				{
+					text: "Cancel",
+					icon: Delete,
+					onClick: props.onClose,
+				},
+				{
+					text: "Save",
+					icon: Check,
+					color: "var(--template)",
+					bgColor: "var(--templatebg)",
+					onClick: save,
+				},
+			]}
+		>
+			<div class="cont col fields">
+				<Show when={isNew()}>
+					<div class="cont start label">
+						<label for="id">ID</label>
+					</div>
+					<IdInput value={id()} onChange={setId} />
+				</Show>
+				<div class="cont start label">
+					<label for="theme-name">NAME</label>
+				</div>
+				<input
+					type="text"
+					id="theme-name"
+					value={name()}
+					onInput={(e) => setName(e.target.value)}
+				></input>
+				<div class="cont start label">
+					<label>TYPE</label>
+				</div>
+				<InlineSelect
+					onChange={(x) => setType(x as "base" | "overlay")}
+					selected={type()}
+					options={[
+						{ value: "base", contents: <div>Base</div> },
+						{ value: "overlay", contents: <div>Overlay</div> },
+					]}
+					columns={2}
+					allowEmpty={false}
+				/>
+				<For each={THEME_TOKENS}>
+					{(token) => (
+						<>
+							<div class="cont start label">
+								<label for={`theme${token.id}`}>{token.name}</label>
+							</div>
+							<input
+								type={token.kind == "color" ? "color" : "text"}
+								id={`theme${token.id}`}
+								value={variables()[token.id]}
+								onInput={(e) =>
+									setVariables({ ...variables(), [token.id]: e.target.value })
+								}
+							></input>
+						</>
+					)}
+				</For>
+				<div class="cont start label">
+					<label for="theme-font">FONT</label>
+				</div>
+				<input
+					type="text"
+					id="theme-font"
+					placeholder="Default"
+					value={font()}
+					onInput={(e) => setFont(e.target.value)}
+				></input>
+			</div>
+		</Modal>
+	);
+}
+
+export interface ThemeEditorProps {
+	visible: boolean;
+	// The theme to edit, or undefined to create a new one
+	theme?: Theme;
+	onClose: () => void;
+	onSave: () => void;
+}
+
+// The theme variables that can be edited
+const THEME_TOKENS: ThemeToken[] = [
+	{ id: "--fg", name: "TEXT", kind: "color" },
+	{ id: "--fg2", name: "SECONDARY TEXT", kind: "color" },
+	{ id: "--fg3", name: "MUTED TEXT", kind: "color" },
+	{ id: "--bg0", name: "DARKEST BACKGROUND", kind: "color" },
+	{ id: "--bg", name: "BACKGROUND", kind: "color" },
+	{ id: "--bg2", name: "PANEL BACKGROUND", kind: "color" },
+	{ id: "--bg3", name: "BORDER", kind: "color" },
+	{ id: "--bg4", name: "HIGHLIGHT", kind: "color" },
+	{ id: "--instance", name: "INSTANCE ACCENT", kind: "color" },
+	{ id: "--template", name: "TEMPLATE ACCENT", kind: "color" },
+	{ id: "--package", name: "PACKAGE ACCENT", kind: "color" },
+	{ id: "--plugin", name: "PLUGIN ACCENT", kind: "color" },
+	{ id: "--warning", name: "WARNING", kind: "color" },
+	{ id: "--error", name: "ERROR", kind: "color" },
+	{ id: "--round", name: "CORNER RADIUS", kind: "size" },
+	{ id: "--round2", name: "LARGE CORNER RADIUS", kind: "size" },
+];
+
+interface ThemeToken {
+	id: string;
+	name: string;
+	kind: "color" | "size";
+}
@@ -3,7 +3,7 @@ import { createEffect, createResource, createSignal, For, Match, Show, Switch }
 import { loadPagePlugins } from "../../plugins";
 import { errorToast, successToast } from "../../components/dialog/Toasts";
 import { beautifyString, getAccountIcon } from "../../utils";
-import { Check, Delete, Info, Login, Logout, Star, User } from "../../icons";
+import { Check, Delete, Info, Lock, Login, Logout, Star, User } from "../../icons";
 import "./AccountPage.css";
 import IconTextButton from "../../components/input/button/IconTextButton";
 import { convertFileSrc, invoke } from "@tauri-apps/api/core";
@@ -68,6 +68,15 @@ export default function AccountPage() {
 		}
 	}, { initialValue: [[], []] });
 
+	let [isUnlocked, unlockedMethods] = createResource(id, async () => {
+		try {
+			let unlocked = await invoke("get_unlocked_accounts") as string[];
+			return unlocked.includes(id());
+		} catch (e) {
+			return false;
+		}
+	}, { initialValue: false });
+
 	let [cosmeticType, setCosmeticType] = createSignal("skin");
 
 	let [skins, setSkins] = createSignal<Skin[]>([]);
@@ -181,6 +190,25 @@ export default function AccountPage() {
 										/>
 									</Match>
 								</Switch>
+								<Show when={isUnlocked()}>
+									<Tip tip="Forget the passkey for this account until it is entered again" side="top">
+										<IconTextButton
+											icon={Lock}
+											size="1.2rem"
+											text="Lock"
+											onClick={async () => {
+												try {
+													await invoke("lock_account", { account: id() });
+													successToast("Account locked");
+													unlockedMethods.refetch();
+												} catch (e) {
+													errorToast("Failed to lock account: " + e);
+												}
+											}}
+											shadow={false}
+										/>
+									</Tip>
+								</Show>
 								<IconTextButton
 									icon={Delete}
 									size="1.2rem"
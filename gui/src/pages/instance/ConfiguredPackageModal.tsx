@@ -7,7 +7,7 @@ import {
 } from "./PackagesConfig";
 import { RepoInfo } from "../../package";
 import { invoke } from "@tauri-apps/api/core";
-import { beautifyString } from "../../utils";
+import { beautifyString, getCachedImageSrc } from "../../utils";
 import "./ConfiguredPackageModal.css";
 import PackageVersion from "../../components/input/text/PackageVersion";
 import IconTextButton from "../../components/input/button/IconTextButton";
@@ -46,7 +46,7 @@ export default function ConfiguredPackageModal(
 			? "/icons/default_instance.png"
 			: props.props.meta == undefined || props.props.meta.icon == undefined
 				? "/icons/default_instance.png"
-				: props.props.meta.icon;
+				: getCachedImageSrc(props.props.meta.icon, "medium");
 
 	let shortDescription = () =>
 		props.props == undefined
@@ -47,7 +47,13 @@ import {
 	readEditableInstanceConfig,
 	saveInstanceConfig,
 } from "./read_write";
-import { InstanceConfig } from "./read_write";
+import {
+	getChannelVersion,
+	InstanceConfig,
+	isChannelVersion,
+	VERSION_CHANNELS,
+	VersionChannel,
+} from "./read_write";
 import DeriveIndicator from "./DeriveIndicator";
 import { InstanceInfo } from "../../types";
 import Dropdown from "../../components/input/select/Dropdown";
@@ -55,7 +61,6 @@ import LoadingSpinner from "../../components/utility/LoadingSpinner";
 import LaunchConfig from "./LaunchConfig";
 import IconSelector from "../../components/input/select/IconSelector";
 import { updateInstanceList } from "./InstanceList";
-import SlideSwitch from "../../components/input/SlideSwitch";
 import Icon from "../../components/Icon";
 import {
 	Box,
@@ -66,6 +71,7 @@ import {
 	Jigsaw,
 	Play,
 	Server,
+	Warning,
 } from "../../icons";
 import LoaderConfig from "./LoaderConfig";
 import FloatingTabs from "../../components/input/select/FloatingTabs";
@@ -154,7 +160,7 @@ export default function InstanceConfigModal(props: InstanceConfigProps) {
 			templateMethods.refetch();
 			pluginsSupportingCreationMethods.refetch();
 			controlsMethods.refetch();
-			setReleaseVersionsOnly(true);
+			setVersionChannels(["release"]);
 			setTab("general");
 
 			if (!isBaseTemplate()) {
@@ -168,28 +174,42 @@ export default function InstanceConfigModal(props: InstanceConfigProps) {
 		}
 	});
 
-	let [releaseVersionsOnly, setReleaseVersionsOnly] = createSignal(true);
+	let [versionChannels, setVersionChannels] = createSignal<VersionChannel[]>([
+		"release",
+	]);
 
 	let [supportedMinecraftVersions, supportedVersionsMethods] = createResource(
 		async () => {
-			let availableVersions = (await invoke("get_minecraft_versions", {
-				releasesOnly: releaseVersionsOnly(),
-			})) as string[];
+			let availableVersions = (await invoke(
+				"get_minecraft_versions_in_channels",
+				{
+					channels: versionChannels(),
+				},
+			)) as string[];
 
 			availableVersions.reverse();
-			if (releaseVersionsOnly()) {
-				return ["latest"].concat(availableVersions);
-			} else {
-				return ["latest", "latest_snapshot"].concat(availableVersions);
-			}
+			// Let the user follow any of the selected channels
+			let channelVersions = VERSION_CHANNELS.filter(
+				(x) => x == "release" || versionChannels().includes(x),
+			).map(getChannelVersion);
+			return channelVersions.concat(availableVersions);
 		},
 	);
 
 	createEffect(() => {
-		releaseVersionsOnly();
+		versionChannels();
 		supportedVersionsMethods.refetch();
 	});
 
+	// Whether the instance follows a version channel instead of a pinned version
+	let tracksChannel = () => {
+		let currentVersion =
+			version() == undefined
+				? getDerivedValue(parentConfigs(), (x) => x.version)
+				: version();
+		return currentVersion != undefined && isChannelVersion(currentVersion);
+	};
+
 	let [supportedLoaders, __] = createResource(async () => {
 		let loaders = await getSupportedLoaders();
 		return [undefined as string | undefined].concat(loaders);
@@ -659,6 +679,14 @@ export default function InstanceConfigModal(props: InstanceConfigProps) {
 									setIsIconDirty(true);
 								}}
 								derivedIcon={getDerivedValue(parentConfigs(), (x) => x.icon)}
+								syncIcon={
+									isInstance() && !isCreating()
+										? async () =>
+												(await invoke("sync_instance_icon", {
+													instance: id(),
+												})) as string | undefined
+										: undefined
+								}
 							/>
 						</Show>
 					</div>
@@ -919,9 +947,7 @@ export default function InstanceConfigModal(props: InstanceConfigProps) {
 												value: x,
 												contents: (
 													<div>
-														{x == "latest" || x == "latest_snapshot"
-															? beautifyString(x)
-															: x}
+														{isChannelVersion(x) ? beautifyString(x) : x}
 													</div>
 												),
 												color: "var(--instance)",
@@ -937,26 +963,44 @@ export default function InstanceConfigModal(props: InstanceConfigProps) {
 										zIndex="50"
 									/>
 								</div>
-								<div class="cont">
-									<SlideSwitch
-										enabled={!releaseVersionsOnly()}
-										onToggle={() =>
-											setReleaseVersionsOnly(!releaseVersionsOnly())
-										}
-										enabledColor="var(--instance)"
-										disabledColor="var(--fg3)"
-									/>
-									<span
-										class="bold"
-										style={`color:${
-											releaseVersionsOnly() ? "var(--fg3)" : "var(--instance)"
-										}`}
-									>
-										Include Snapshots
-									</span>
-								</div>
 							</div>
 						</Tip>
+						<Tip
+							tip="Which version channels to show versions from"
+							fullwidth
+							side="top"
+						>
+							<div class="fullwidth" id="version-channels">
+								<InlineSelect
+									options={VERSION_CHANNELS.map((x) => {
+										return {
+											value: x,
+											contents: <div>{beautifyString(x)}</div>,
+											color: "var(--instance)",
+										};
+									})}
+									selected={versionChannels()}
+									onChangeMulti={(x) => {
+										// Releases are always shown if nothing else is selected
+										setVersionChannels(
+											x == undefined || x.length == 0
+												? ["release"]
+												: (x as VersionChannel[]),
+										);
+									}}
+									columns={VERSION_CHANNELS.length}
+									checkboxes
+								/>
+							</div>
+						</Tip>
+					</Show>
+					<Show when={tracksChannel()}>
+						<div class="cont start version-channel-warning">
+							<Icon icon={Warning} size="1rem" />
+							This follows a version channel and will move to new versions
+							automatically. Worlds may not open in older versions after an
+							update.
+						</div>
 					</Show>
 					<LoaderConfig
 						minecraftVersion={
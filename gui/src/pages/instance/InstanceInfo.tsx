@@ -29,19 +29,28 @@ import {
 } from "./read_write";
 import { errorToast, successToast } from "../../components/dialog/Toasts";
 import LoadingSpinner from "../../components/utility/LoadingSpinner";
-import { getInstanceIconSrc, parseVersionedString } from "../../utils";
+import {
+	getCachedImageSrc,
+	getInstanceIconSrc,
+	parseVersionedString,
+} from "../../utils";
 import PackageLabels from "../../components/package/PackageLabels";
 import { Loader } from "../../package";
 import Icon from "../../components/Icon";
 import {
 	Box,
+	Check,
 	Copy,
+	Cycle,
 	Download,
 	Elipsis,
 	Folder,
 	Gear,
+	Globe,
+	Picture,
 	Play,
 	Popout,
+	Server,
 	Stop,
 	Tag,
 	Text,
@@ -62,11 +71,12 @@ import { FooterMode, launchInstance } from "../../components/navigation/Footer";
 import { canonicalizeListOrSingle } from "../../utils/values";
 import { Event, listen, UnlistenFn } from "@tauri-apps/api/event";
 import { RunningInstancesEvent } from "../../components/launch/RunningInstanceList";
-import { convertFileSrc } from "@tauri-apps/api/core";
 import Dropdown, { Option } from "../../components/input/select/Dropdown";
 import IconAndText from "../../components/utility/IconAndText";
 import InstanceTransferPrompt from "../../components/instance/InstanceTransferPrompt";
 import InstanceTiles from "../../components/instance/InstanceTiles";
+import RealmsList from "../../components/instance/RealmsList";
+import AdvisoryBadge from "../../components/instance/AdvisoryBadge";
 import Tip from "../../components/dialog/Tip";
 import InstanceOperationPrompt, { InstanceOperation } from "../../components/instance/InstanceOperationPrompt";
 
@@ -276,6 +286,8 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 	);
 
 	let [selectedTab, setSelectedTab] = createSignal("general");
+	// Realms can only be joined from client instances
+	let isClient = () => instance() != undefined && instance()!.type != "server";
 
 	let [operationPrompt, setOperationPrompt] = createSignal<InstanceOperation | undefined>();
 	let [showExportPrompt, setShowExportPrompt] = createSignal(false);
@@ -379,6 +391,16 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 				contents: <IconAndText icon={Folder} text="Open Folder" />,
 				tip: "Open this instance's files in your explorer",
 			},
+			{
+				value: "open_worlds",
+				contents: <IconAndText icon={Globe} text="Open Worlds" />,
+				tip: "Open this instance's worlds in your explorer",
+			},
+			{
+				value: "open_screenshots",
+				contents: <IconAndText icon={Picture} text="Open Screenshots" />,
+				tip: "Open this instance's screenshots in your explorer",
+			},
 		];
 
 		if (!isFromPlugin()) {
@@ -403,6 +425,31 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 				),
 				tip: "Create a shared template from this instance",
 			});
+			options.push({
+				value: "save_template",
+				contents: (
+					<IconAndText icon={Box} text="Save as Template" />
+				),
+				tip: "Save this instance's setup as a new template",
+			});
+
+			if (instance()!.linked != undefined) {
+				options.push({
+					value: "sync",
+					contents: (
+						<IconAndText icon={Cycle} text="Sync Linked" />
+					),
+					tip: "Copy this instance's config to the instance it is linked to",
+				});
+			} else if (isClient()) {
+				options.push({
+					value: "create_server",
+					contents: (
+						<IconAndText icon={Server} text="Create Server" />
+					),
+					tip: "Create a matching server for this instance",
+				});
+			}
 		}
 
 		if (isDeletable()) {
@@ -481,6 +528,7 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 												Imported
 											</div>
 										</Show>
+										<AdvisoryBadge instanceId={id()} />
 									</div>
 								</div>
 								<div class="cont end" style="margin-right:1rem">
@@ -529,6 +577,14 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 														} catch (e) {
 															errorToast("Failed to kill instance: " + e);
 														}
+													} else if (selection == "verify") {
+														try {
+															await invoke("verify_instance", {
+																instanceId: id(),
+															});
+														} catch (e) {
+															errorToast("Failed to verify instance: " + e);
+														}
 													} else {
 														runDropdownButtonClick(selection!);
 													}
@@ -565,6 +621,20 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 															),
 															tip: "Update the packages and files on this instance",
 														},
+														{
+															value: "quick_update",
+															contents: (
+																<IconAndText icon={Upload} text="Quick Update" />
+															),
+															tip: "Only install files that are missing from this instance, without checking for new versions of packages",
+														},
+														{
+															value: "verify",
+															contents: (
+																<IconAndText icon={Check} text="Verify Files" />
+															),
+															tip: "Check the game files and addons on this instance and redownload any that are corrupted. Can fix crashes caused by disk issues.",
+														},
 														{
 															value: "force_update",
 															contents: (
@@ -592,11 +662,16 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 												onChange={async (selection) => {
 													if (
 														selection == "update" ||
+														selection == "quick_update" ||
 														selection == "force_update"
 													) {
 														try {
 															let depth =
-																selection == "update" ? "full" : "force";
+																selection == "update"
+																	? "full"
+																	: selection == "quick_update"
+																		? "shallow"
+																		: "force";
 
 															await invoke("update_instance", {
 																instanceId: id(),
@@ -653,6 +728,14 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 														await invoke("open_instance_dir", {
 															instance: id(),
 														});
+													} else if (selection == "open_worlds") {
+														await invoke("open_instance_worlds", {
+															instance: id(),
+														});
+													} else if (selection == "open_screenshots") {
+														await invoke("open_instance_screenshots", {
+															instance: id(),
+														});
 													} else if (selection == "delete") {
 														setOperationPrompt("delete");
 													} else if (selection == "consolidate") {
@@ -661,6 +744,12 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 														setOperationPrompt("duplicate");
 													} else if (selection == "extract") {
 														setOperationPrompt("extract");
+													} else if (selection == "save_template") {
+														setOperationPrompt("save_template");
+													} else if (selection == "create_server") {
+														setOperationPrompt("create_server");
+													} else if (selection == "sync") {
+														setOperationPrompt("sync");
 													} else {
 														runDropdownButtonClick(selection!);
 													}
@@ -681,11 +770,11 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 						<div id="instance-banner-container">
 							<div id="instance-banner">
 								<img
-									src={convertFileSrc(bannerImages()![0])}
+									src={getCachedImageSrc(bannerImages()![0], "large")}
 									onerror={(e) => e.target.remove()}
 								/>
 								<img
-									src={convertFileSrc(bannerImages()![1])}
+									src={getCachedImageSrc(bannerImages()![1], "large")}
 									onerror={(e) => e.target.remove()}
 								/>
 							</div>
@@ -697,7 +786,7 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 							<div
 								class="instance-shadow"
 								id="instance-tabs"
-								style={`grid-template-columns:repeat(3,minmax(0,1fr))`}
+								style={`grid-template-columns:repeat(${isClient() ? 4 : 3},minmax(0,1fr))`}
 							>
 								<div
 									class={`cont instance-tab ${selectedTab() == "general" ? "selected" : ""
@@ -723,6 +812,16 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 									<Icon icon={Text} size="1rem" />
 									Console
 								</div>
+								<Show when={isClient()}>
+									<div
+										class={`cont instance-tab ${selectedTab() == "realms" ? "selected" : ""
+											}`}
+										onclick={() => setSelectedTab("realms")}
+									>
+										<Icon icon={Globe} size="1rem" />
+										Realms
+									</div>
+								</Show>
 							</div>
 							<div class="cont col instance-shadow" id="instance-tab-contents">
 								<Show when={selectedTab() == "general"}>
@@ -801,6 +900,9 @@ export default function InstanceInfo(props: InstanceInfoProps) {
 										/>
 									</div>
 								</Show>
+								<Show when={selectedTab() == "realms" && isClient()}>
+									<RealmsList instanceId={id()} />
+								</Show>
 							</div>
 						</div>
 					</div>
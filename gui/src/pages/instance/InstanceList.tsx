@@ -5,6 +5,7 @@ import {
 	createSignal,
 	For,
 	Match,
+	onCleanup,
 	onMount,
 	Show,
 	Switch,
@@ -26,6 +27,7 @@ import {
 } from "../../types";
 import { errorToast } from "../../components/dialog/Toasts";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import IconButton from "../../components/input/button/IconButton";
 import {
 	Box,
@@ -35,9 +37,11 @@ import {
 	Download,
 	Folder,
 	Globe,
+	Grid,
 	Honeycomb,
 	Info,
 	Jigsaw,
+	Menu,
 	Pin,
 	Plus,
 	Properties,
@@ -54,6 +58,7 @@ import Tip from "../../components/dialog/Tip";
 import FloatingTabs from "../../components/input/select/FloatingTabs";
 import { InstanceConfigMode } from "./read_write";
 import { getLoaderDisplayName, Loader } from "../../package";
+import NewsFeed from "../../components/NewsFeed";
 
 export default function InstanceList(props: InstanceListProps) {
 	onMount(() => loadPagePlugins("instances"));
@@ -72,6 +77,79 @@ export default function InstanceList(props: InstanceListProps) {
 		"instance" | "template"
 	>("instance");
 
+	const [listPrefs, setListPrefs] = createSignal<InstanceListPreferences>({
+		sort: "name",
+		group_by: "none",
+		layout: "grid",
+	});
+	// Stats for each instance from the stats plugin, if it is installed
+	const [stats, setStats] = createSignal<{ [id: string]: InstanceStats }>({});
+
+	onMount(async () => {
+		try {
+			setListPrefs(await invoke("get_instance_list_preferences"));
+		} catch (e) {
+			console.error("Failed to get instance list preferences: " + e);
+		}
+	});
+
+	createEffect(async () => {
+		let sort = listPrefs().sort;
+		if (sort != "last_played" && sort != "playtime") {
+			return;
+		}
+
+		try {
+			let result = await invoke("run_custom_action", {
+				plugin: "stats",
+				action: "get_stats",
+				payload: null,
+			});
+			setStats(result == null ? {} : (result as any));
+		} catch (e) {
+			console.error("Failed to get instance stats: " + e);
+		}
+	});
+
+	let updateListPrefs = (prefs: Partial<InstanceListPreferences>) => {
+		let newPrefs = { ...listPrefs(), ...prefs };
+		setListPrefs(newPrefs);
+		invoke("set_instance_list_preferences", { preferences: newPrefs }).catch(
+			(e) => errorToast("Failed to save instance list preferences: " + e),
+		);
+	};
+
+	let sortedInstances = () =>
+		sortInstances(instances(), listPrefs().sort, stats());
+
+	// Sections of instances created from the grouping preference
+	let groupedSections = (): GroupSectionData[] => {
+		let groupBy = listPrefs().group_by;
+		if (groupBy == "none") {
+			return [];
+		}
+
+		let out: GroupSectionData[] = [];
+		for (let instance of sortedInstances()) {
+			let key =
+				groupBy == "version"
+					? instance.version
+					: groupBy == "loader"
+						? getLoaderDisplayName(instance.loader as Loader)
+						: instance.side;
+			key = key == undefined ? "Unknown" : key;
+
+			let section = out.find((x) => x.id == key);
+			if (section == undefined) {
+				out.push({ id: key, instances: [instance] });
+			} else {
+				section.instances.push(instance);
+			}
+		}
+
+		return out;
+	};
+
 	let [importPromptVisible, setImportPromptVisible] = createSignal(false);
 	let [migratePromptVisible, setMigratePromptVisible] = createSignal(false);
 
@@ -134,6 +212,10 @@ export default function InstanceList(props: InstanceListProps) {
 
 	updateItems();
 
+	// Refresh when the config is changed outside of the launcher
+	let unlistenConfig = listen("nitro_config_changed", () => updateItems());
+	onCleanup(() => unlistenConfig.then((unlisten) => unlisten()));
+
 	function onSelect(item: SelectedItem, section: string) {
 		setSelectedItem(item);
 		setSelectedSection(section);
@@ -255,9 +337,65 @@ export default function InstanceList(props: InstanceListProps) {
 						</div>
 					</div>
 				</div>
+				<Show when={instancesOrTemplates() == "instance"}>
+					<div class="cont end fullwidth" id="instance-list-options">
+						<div style="width:10rem">
+							<Dropdown
+								options={[
+									{ value: "name", contents: "Sort: Name" },
+									{ value: "last_played", contents: "Sort: Last Played" },
+									{ value: "playtime", contents: "Sort: Playtime" },
+									{ value: "version", contents: "Sort: Version" },
+									{ value: "loader", contents: "Sort: Loader" },
+								]}
+								selected={listPrefs().sort}
+								onChange={(x) => updateListPrefs({ sort: x as InstanceSort })}
+								isSearchable={false}
+								zIndex="2"
+							/>
+						</div>
+						<div style="width:10rem">
+							<Dropdown
+								options={[
+									{ value: "none", contents: "Group: None" },
+									{ value: "version", contents: "Group: Version" },
+									{ value: "loader", contents: "Group: Loader" },
+									{ value: "side", contents: "Group: Side" },
+								]}
+								selected={listPrefs().group_by}
+								onChange={(x) =>
+									updateListPrefs({ group_by: x as InstanceGrouping })
+								}
+								isSearchable={false}
+								zIndex="2"
+							/>
+						</div>
+						<Tip
+							tip={
+								listPrefs().layout == "grid"
+									? "Switch to compact list"
+									: "Switch to grid"
+							}
+							side="top"
+						>
+							<IconButton
+								icon={listPrefs().layout == "grid" ? Menu : Grid}
+								size="1.8rem"
+								color="var(--bg2)"
+								border="var(--bg3)"
+								onClick={() =>
+									updateListPrefs({
+										layout: listPrefs().layout == "grid" ? "compact" : "grid",
+									})
+								}
+							/>
+						</Tip>
+					</div>
+				</Show>
 				<br />
 				<Switch>
 					<Match when={instancesOrTemplates() == "instance"}>
+						<NewsFeed />
 						<Show when={pinned().length > 0}>
 							<Section
 								id="pinned"
@@ -269,6 +407,7 @@ export default function InstanceList(props: InstanceListProps) {
 								onSelectItem={onSelect}
 								updateList={updateItems}
 								itemType="instance"
+								layout={listPrefs().layout}
 							/>
 						</Show>
 						<For each={groups()}>
@@ -277,12 +416,29 @@ export default function InstanceList(props: InstanceListProps) {
 									id={`group-${item.id}`}
 									kind="group"
 									header={item.id.toLocaleUpperCase()}
+									items={sortInstances(item.instances, listPrefs().sort, stats())}
+									selectedItem={selectedItem()}
+									selectedSection={selectedSection()}
+									onSelectItem={onSelect}
+									updateList={updateItems}
+									itemType="instance"
+									layout={listPrefs().layout}
+								/>
+							)}
+						</For>
+						<For each={groupedSections()}>
+							{(item) => (
+								<Section
+									id={`grouping-${item.id}`}
+									kind="group"
+									header={item.id.toLocaleUpperCase()}
 									items={item.instances}
 									selectedItem={selectedItem()}
 									selectedSection={selectedSection()}
 									onSelectItem={onSelect}
 									updateList={updateItems}
 									itemType="instance"
+									layout={listPrefs().layout}
 								/>
 							)}
 						</For>
@@ -290,12 +446,13 @@ export default function InstanceList(props: InstanceListProps) {
 							id="all"
 							kind="all"
 							header="ALL INSTANCES"
-							items={instances()}
+							items={listPrefs().group_by == "none" ? sortedInstances() : []}
 							selectedItem={selectedItem()}
 							selectedSection={selectedSection()}
 							onSelectItem={onSelect}
 							updateList={updateItems}
 							itemType="instance"
+							layout={listPrefs().layout}
 						/>
 					</Match>
 					<Match when={instancesOrTemplates() == "template"}>
@@ -361,7 +518,9 @@ function Section(props: SectionProps) {
 						<h2>{props.header}</h2>
 					</div>
 				</Show>
-				<div class="instance-list-section">
+				<div
+					class={`instance-list-section ${props.layout == "compact" ? "compact" : ""}`}
+				>
 					{/* Button for the global template */}
 					<Show when={props.kind == "templates"}>
 						<Tip
@@ -461,6 +620,7 @@ interface SectionProps {
 	selectedSection: string | null;
 	onSelectItem: (item: SelectedItem, section: string) => void;
 	updateList: () => void;
+	layout?: InstanceListLayout;
 }
 
 type SectionKind = "pinned" | "group" | "all" | "templates";
@@ -630,6 +790,52 @@ export interface InstanceListProps {
 	setFooterData: (data: FooterData) => void;
 }
 
+// Display preferences for the instance list, stored in the launcher data
+interface InstanceListPreferences {
+	sort: InstanceSort;
+	group_by: InstanceGrouping;
+	layout: InstanceListLayout;
+}
+
+type InstanceSort = "name" | "last_played" | "playtime" | "version" | "loader";
+type InstanceGrouping = "none" | "version" | "loader" | "side";
+type InstanceListLayout = "grid" | "compact";
+
+// Stats returned by the stats plugin
+interface InstanceStats {
+	playtime: number;
+	launches: number;
+	last_launch?: number;
+}
+
+function sortInstances(
+	instances: InstanceInfo[],
+	sort: InstanceSort,
+	stats: { [id: string]: InstanceStats },
+) {
+	let name = (x: InstanceInfo) => (x.name == null ? x.id : x.name);
+	let compare = (a: InstanceInfo, b: InstanceInfo) => {
+		if (sort == "last_played") {
+			return (
+				(stats[b.id]?.last_launch ?? 0) - (stats[a.id]?.last_launch ?? 0)
+			);
+		} else if (sort == "playtime") {
+			return (stats[b.id]?.playtime ?? 0) - (stats[a.id]?.playtime ?? 0);
+		} else if (sort == "version") {
+			return (b.version ?? "").localeCompare(a.version ?? "", undefined, {
+				numeric: true,
+			});
+		} else if (sort == "loader") {
+			return (a.loader ?? "").localeCompare(b.loader ?? "");
+		}
+		return 0;
+	};
+
+	return instances
+		.slice()
+		.sort((a, b) => compare(a, b) || name(a).localeCompare(name(b)));
+}
+
 export async function updateInstanceList() {
 	await (window as any).__updateInstanceList();
 }
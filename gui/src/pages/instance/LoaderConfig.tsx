@@ -264,18 +264,12 @@ export default function LoaderConfig(props: LoaderConfigProps) {
 						</div>
 						<Tip
 							tip={`The version for the${props.isTemplate ? " client" : ""
-								} loader. Leave empty to select the best version automatically.`}
+								} loader. Leave empty to use the latest stable version, or select Latest Beta to include unstable versions.`}
 							side="top"
 							fullwidth
 						>
 							<Dropdown
-								options={clientLoaderVersions()!.map((x) => {
-									return {
-										value: x,
-										contents: x,
-										color: "var(--package)",
-									};
-								})}
+								options={getLoaderVersionOptions(clientLoaderVersions()!)}
 								selected={props.clientLoaderVersion}
 								onChange={(x) => {
 									props.setClientLoaderVersion(x);
@@ -299,18 +293,12 @@ export default function LoaderConfig(props: LoaderConfigProps) {
 						</div>
 						<Tip
 							tip={`The version for the${props.isTemplate ? " server" : ""
-								} loader. Leave empty to select the best version automatically.`}
+								} loader. Leave empty to use the latest stable version, or select Latest Beta to include unstable versions.`}
 							side="top"
 							fullwidth
 						>
 							<Dropdown
-								options={serverLoaderVersions()!.map((x) => {
-									return {
-										value: x,
-										contents: x,
-										color: "var(--package)",
-									};
-								})}
+								options={getLoaderVersionOptions(serverLoaderVersions()!)}
 								selected={props.serverLoaderVersion}
 								onChange={(x) => {
 									props.setServerLoaderVersion(x);
@@ -327,6 +315,25 @@ export default function LoaderConfig(props: LoaderConfigProps) {
 	);
 }
 
+// Gets the dropdown options for a list of loader versions
+function getLoaderVersionOptions(versions: string[]) {
+	let options = versions.map((x) => {
+		return {
+			value: x,
+			contents: x,
+			color: "var(--package)",
+		};
+	});
+	// Special version that selects the newest version, even if it is unstable
+	options.unshift({
+		value: "beta",
+		contents: "Latest Beta",
+		color: "var(--fg2)",
+	});
+
+	return options;
+}
+
 export interface LoaderConfigProps {
 	minecraftVersion?: string;
 	side?: Side;
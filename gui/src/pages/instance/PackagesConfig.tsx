@@ -25,6 +25,7 @@ import {
 	pkgRequestsEqual,
 	pkgRequestToString,
 	stringCompare,
+	getCachedImageSrc,
 } from "../../utils";
 import IconButton from "../../components/input/button/IconButton";
 import {
@@ -44,7 +45,7 @@ import {
 import { errorToast } from "../../components/dialog/Toasts";
 import LoadingSpinner from "../../components/utility/LoadingSpinner";
 import ResolutionError, {
-	ResolutionErrorData,
+	ExplainedResolutionError,
 } from "../../components/package/ResolutionError";
 import { Loader } from "../../package";
 import IconTextButton from "../../components/input/button/IconTextButton";
@@ -266,7 +267,7 @@ export default function PackagesConfig(props: PackagesConfigProps) {
 		}
 
 		try {
-			let resolutionError: ResolutionErrorData = await invoke(
+			let resolutionError: ExplainedResolutionError = await invoke(
 				"get_instance_resolution_error",
 				{ id: props.id },
 			);
@@ -738,7 +739,7 @@ function ConfiguredPackage(props: ConfiguredPackageProps) {
 	let icon = () =>
 		props.meta == undefined || props.meta.icon == undefined
 			? "/icons/default_instance.png"
-			: props.meta.icon;
+			: getCachedImageSrc(props.meta.icon, "small");
 
 	let category = () => props.pkg.isClient ? "client" : props.pkg.isServer ? "server" : "global";
 
@@ -13,7 +13,7 @@ export interface InstanceConfig {
 	type?: "client" | "server";
 	name?: string;
 	icon?: string;
-	version?: string | "latest" | "latest_snapshot";
+	version?: string | ChannelVersion;
 	loader?: ConfiguredLoaders;
 	modpack?: string;
 	datapack_folder?: string;
@@ -24,9 +24,49 @@ export interface InstanceConfig {
 	is_editable?: boolean;
 	is_deletable?: boolean;
 	overrides?: PackageOverrides;
+	linked?: string;
 	[extraKey: string]: any;
 }
 
+// Channels that Minecraft versions are released in
+export type VersionChannel =
+	| "release"
+	| "snapshot"
+	| "beta"
+	| "alpha"
+	| "april_fools";
+
+export const VERSION_CHANNELS: VersionChannel[] = [
+	"release",
+	"snapshot",
+	"beta",
+	"alpha",
+	"april_fools",
+];
+
+// Special versions that follow the newest version in a channel
+export type ChannelVersion =
+	| "latest"
+	| "latest_snapshot"
+	| "latest_beta"
+	| "latest_alpha"
+	| "latest_april_fools";
+
+// Gets the version used to follow a channel
+export function getChannelVersion(channel: VersionChannel): ChannelVersion {
+	return channel == "release"
+		? "latest"
+		: (`latest_${channel}` as ChannelVersion);
+}
+
+// Checks if a configured version follows a channel instead of being pinned
+export function isChannelVersion(version: string): version is ChannelVersion {
+	return (
+		version == "latest" ||
+		VERSION_CHANNELS.some((x) => version == getChannelVersion(x))
+	);
+}
+
 export type ConfiguredLoaders =
 	| Loader
 	| {
@@ -14,27 +14,37 @@ import "@thisbeyond/solid-select/style.css";
 import PageButtons from "../../components/input/button/PageButtons";
 import { PackageMeta } from "../../types";
 import SearchBar from "../../components/input/text/SearchBar";
-import { formatNumber, parseQueryString } from "../../utils";
+import { formatNumber, parseQueryString, getCachedImageSrc } from "../../utils";
 import { FooterData } from "../../App";
 import { FooterMode } from "../../components/navigation/Footer";
 import { errorToast } from "../../components/dialog/Toasts";
 import PackageLabels from "../../components/package/PackageLabels";
-import { Loader, PackageCategory, PackageType } from "../../package";
+import {
+	Loader,
+	PackageCategory,
+	PackageSortMethod,
+	packageSortMethodDisplayName,
+	PackageType,
+} from "../../package";
 import PackageFilters, {
 	defaultPackageFilters,
 	PackageFilterOptions,
 } from "../../components/package/PackageFilters";
 import LoadingSpinner from "../../components/utility/LoadingSpinner";
 import RepoSelector from "../../components/package/RepoSelector";
-import { searchPackages } from "../../utils/package";
+import {
+	ExpandedPackageSearchResults,
+	searchPackages,
+} from "../../utils/package";
 import Tip from "../../components/dialog/Tip";
 import IconTextButton from "../../components/input/button/IconTextButton";
-import { Download, Grid, Menu, Refresh } from "../../icons";
+import { AngleDown, Download, Grid, Menu, Refresh } from "../../icons";
 import { invoke } from "@tauri-apps/api/core";
 import { loadPagePlugins } from "../../plugins";
 import Icon from "../../components/Icon";
 import ViewPackage from "./ViewPackage";
 import IconButton from "../../components/input/button/IconButton";
+import Dropdown from "../../components/input/select/Dropdown";
 
 const PACKAGES_PER_PAGE = 12;
 
@@ -98,6 +108,9 @@ export default function BrowsePackages(props: BrowsePackagesProps) {
 	let [filteredStability, setFilteredStability] = createSignal<
 		"stable" | "latest" | undefined
 	>();
+	let [sortMethod, setSortMethod] = createSignal<PackageSortMethod>(
+		filters().sort == undefined ? "relevance" : filters().sort!,
+	);
 
 	// Creates the PackageFilterOptions object to be put in URL parameters
 	let createPackageFiltersObject = () => {
@@ -105,6 +118,7 @@ export default function BrowsePackages(props: BrowsePackagesProps) {
 			minecraft_versions: filteredMinecraftVersions(),
 			loaders: filteredLoaders(),
 			categories: filteredCategories(),
+			sort: sortMethod(),
 		} as PackageFilterOptions;
 	};
 
@@ -137,6 +151,20 @@ export default function BrowsePackages(props: BrowsePackagesProps) {
 	};
 
 	let [packageCount, setPackageCount] = createSignal(0);
+	// Whether cached results are being shown while new ones are fetched
+	let [isRefreshing, setIsRefreshing] = createSignal(false);
+	// Incremented on every search so that old refreshes don't overwrite newer results
+	let searchId = 0;
+	// Cursor for loading the results after the ones that are shown
+	let [nextCursor, setNextCursor] = createSignal<string | undefined>();
+	let [isLoadingMore, setIsLoadingMore] = createSignal(false);
+	// Runs the current search with the given options
+	let currentSearch:
+		| ((
+				allowStale: boolean,
+				cursor?: string,
+			) => Promise<ExpandedPackageSearchResults | undefined>)
+		| undefined;
 
 	let [repoPackageTypes, setRepoPackageTypes] = createSignal<
 		PackageType[] | undefined
@@ -150,8 +178,11 @@ export default function BrowsePackages(props: BrowsePackagesProps) {
 			return undefined;
 		}
 
-		try {
-			let result = await searchPackages(
+		let id = ++searchId;
+		setIsRefreshing(false);
+		setNextCursor(undefined);
+		let runSearch = (allowStale: boolean, cursor?: string) =>
+			searchPackages(
 				selectedRepo(),
 				page(),
 				search(),
@@ -159,10 +190,21 @@ export default function BrowsePackages(props: BrowsePackagesProps) {
 				filteredMinecraftVersions(),
 				filteredLoaders() as Loader[],
 				filteredCategories(),
+				sortMethod(),
+				allowStale,
+				cursor,
 			);
+		currentSearch = runSearch;
+
+		try {
+			let result = await runSearch(true);
 
 			if (result != undefined) {
 				setPackageCount(result.totalCount);
+				setNextCursor(result.nextCursor);
+				if (result.stale && id == searchId) {
+					refreshPackages(id, runSearch(false));
+				}
 				return result.packages;
 			}
 		} catch (e) {
@@ -170,6 +212,51 @@ export default function BrowsePackages(props: BrowsePackagesProps) {
 		}
 	}
 
+	// Appends the next page of results for the current search to the ones that are shown
+	async function loadMore() {
+		let id = searchId;
+		let cursor = nextCursor();
+		if (cursor == undefined || currentSearch == undefined || isLoadingMore()) {
+			return;
+		}
+
+		setIsLoadingMore(true);
+		try {
+			let more = await currentSearch(false, cursor);
+			if (more != undefined && id == searchId) {
+				setNextCursor(more.nextCursor);
+				packageMethods.mutate([
+					...(packages() == undefined ? [] : packages()!),
+					...more.packages,
+				]);
+			}
+		} catch (e) {
+			errorToast(`${e}`);
+		}
+		setIsLoadingMore(false);
+	}
+
+	// Replaces stale results with fresh ones once they are fetched in the background
+	async function refreshPackages(
+		id: number,
+		fresh: Promise<ExpandedPackageSearchResults | undefined>,
+	) {
+		setIsRefreshing(true);
+		try {
+			let result = await fresh;
+			if (result != undefined && id == searchId) {
+				setPackageCount(result.totalCount);
+				setNextCursor(result.nextCursor);
+				packageMethods.mutate(result.packages);
+			}
+		} catch (e) {
+			console.error(e);
+		}
+		if (id == searchId) {
+			setIsRefreshing(false);
+		}
+	}
+
 	let [selectedPackage, setSelectedPackage] = createSignal<string | undefined>(
 		undefined,
 	);
@@ -206,6 +293,34 @@ export default function BrowsePackages(props: BrowsePackagesProps) {
 					<div></div>
 					<div class="cont" style="justify-content:flex-end">
 						<div class="cont">
+							<Tip tip="Sort By">
+								<Dropdown
+									options={(
+										[
+											"relevance",
+											"downloads",
+											"followers",
+											"updated",
+											"newest",
+										] as PackageSortMethod[]
+									).map((x) => {
+										return {
+											value: x,
+											contents: packageSortMethodDisplayName(x),
+										};
+									})}
+									selected={sortMethod()}
+									onChange={(x) => {
+										setSortMethod(
+											x == undefined ? "relevance" : (x as PackageSortMethod),
+										);
+										setPage(0);
+										updateFilters();
+									}}
+									isSearchable={false}
+									zIndex="5"
+								/>
+							</Tip>
 							<Tip tip="Gallery View">
 								<IconButton
 									icon={Grid}
@@ -280,7 +395,14 @@ export default function BrowsePackages(props: BrowsePackagesProps) {
 				</div>
 				<Show when={!isAlternate()}>
 					<div class="split3 fullwidth">
-						<div></div>
+						<div class="cont start">
+							<Show when={isRefreshing()}>
+								<div class="cont" id="browse-refreshing">
+									<LoadingSpinner size="1rem" />
+									Refreshing results
+								</div>
+							</Show>
+						</div>
 						<PageButtons
 							page={page()}
 							pageCount={Math.floor(packageCount() / PACKAGES_PER_PAGE)}
@@ -332,6 +454,7 @@ export default function BrowsePackages(props: BrowsePackagesProps) {
 											<Package
 												id={data.id}
 												meta={data.meta}
+												sources={data.sources}
 												selected={selectedPackage()}
 												onSelect={(pkg) => {
 													setSelectedPackage(pkg);
@@ -360,6 +483,20 @@ export default function BrowsePackages(props: BrowsePackagesProps) {
 								}}
 							</For>
 						</Show>
+						<Show when={packages() != undefined && nextCursor() != undefined}>
+							<div class="cont" id="browse-load-more">
+								<Show when={!isLoadingMore()} fallback={<LoadingSpinner size="2rem" />}>
+									<IconTextButton
+										icon={AngleDown}
+										text="Load More"
+										size="1.5rem"
+										color="var(--package)"
+										bgColor="var(--packagebg)"
+										onClick={loadMore}
+									/>
+								</Show>
+							</div>
+						</Show>
 					</div>
 					<Show when={isAlternate()}>
 						<div id="package-preview">
@@ -428,7 +565,7 @@ function Package(props: PackageProps) {
 		if (base == undefined) {
 			return "/icons/default_instance.png";
 		} else {
-			return base;
+			return getCachedImageSrc(base, props.alternate ? "medium" : "large");
 		}
 	};
 
@@ -477,6 +614,11 @@ function Package(props: PackageProps) {
 							</div>
 						</Show>
 					</div>
+					<Show when={props.sources != undefined && props.sources.length > 1}>
+						<div style="color:var(--fg3);font-size:0.8rem">
+							{`Found in ${props.sources!.join(", ")}`}
+						</div>
+					</Show>
 					<Show when={props.meta.categories != undefined}>
 						<div style="margin-top:-0.2rem">
 							<PackageLabels
@@ -502,6 +644,8 @@ function Package(props: PackageProps) {
 interface PackageProps {
 	id: string;
 	meta: PackageMeta;
+	// The repositories the package was found in
+	sources?: string[];
 	selected?: string;
 	onSelect: (pkg: string) => void;
 	getPackageFiltersObject: () => PackageFilterOptions;
@@ -3,8 +3,12 @@ import "./ViewPackage.css";
 import { invoke } from "@tauri-apps/api/core";
 import { createEffect, createSignal, For, JSX, Show } from "solid-js";
 import "@thisbeyond/solid-select/style.css";
-import { PackageMeta, PackageProperties } from "../../types";
-import { marked } from "marked";
+import {
+	ExternalLink,
+	PackageDetails,
+	PackageMeta,
+	PackageProperties,
+} from "../../types";
 import { errorToast } from "../../components/dialog/Toasts";
 import { FooterData, replaceExternalLinks } from "../../App";
 import { FooterMode } from "../../components/navigation/Footer";
@@ -15,6 +19,7 @@ import {
 	Download,
 	Folder,
 	Globe,
+	Grid,
 	Hashtag,
 	Heart,
 	Info,
@@ -32,8 +37,10 @@ import {
 	formatNumber,
 	parsePkgRequest,
 	parseQueryString,
+	getCachedImageSrc,
 } from "../../utils";
 import PackageVersions from "../../components/package/PackageVersions";
+import PackageCompatibility from "../../components/package/PackageCompatibility";
 import PackageInstallModal from "../../components/package/PackageInstallModal";
 import { canonicalizeListOrSingle } from "../../utils/values";
 import LoadingSpinner from "../../components/utility/LoadingSpinner";
@@ -42,6 +49,7 @@ import { open } from "@tauri-apps/plugin-shell";
 import IconButton from "../../components/input/button/IconButton";
 import { WebviewWindow } from "@tauri-apps/api/webviewWindow";
 import PackageGallery from "../../components/package/PackageGallery";
+import AuthorModal from "../../components/package/AuthorModal";
 
 export function ViewPackagePage(props: ViewPackagePageProps) {
 	let params = useParams();
@@ -91,6 +99,9 @@ export default function ViewPackage(props: ViewPackageProps) {
 	let [properties, setProperties] = createSignal<PackageProperties | undefined>(
 		undefined,
 	);
+	// Full project details, which are loaded after the metadata
+	let [details, setDetails] = createSignal<PackageDetails | undefined>();
+	let [selectedAuthor, setSelectedAuthor] = createSignal<string | undefined>();
 
 	let [repoInfo, setRepoInfo] = createSignal<RepoInfo | undefined>(undefined);
 	let [shortDescription, setShortDescription] = createSignal("");
@@ -118,6 +129,7 @@ export default function ViewPackage(props: ViewPackageProps) {
 		try {
 			setMeta(undefined);
 			setProperties(undefined);
+			setDetails(undefined);
 			let [[meta, properties], repos] = (await Promise.all([
 				invoke("get_package_meta_and_props", {
 					package: props.id,
@@ -135,16 +147,11 @@ export default function ViewPackage(props: ViewPackageProps) {
 
 			let description = meta.description == undefined ? "" : meta.description;
 			setShortDescription(description.slice(0, 200));
-			let longDescription =
-				meta.long_description == undefined ? "" : meta.long_description;
-			let longDescriptionHtml = `<div>${await marked.parse(
-				longDescription,
-			)}</div>`;
-			longDescriptionHtml = replaceExternalLinks(longDescriptionHtml);
-			setLongDescription(longDescriptionHtml);
+			setLongDescription(await renderDescription(meta.long_description));
 
 			setMeta(meta);
 			setProperties(properties);
+			updateDetails(props.id);
 		} catch (e) {
 			errorToast("Failed to load package: " + e);
 			setMeta(undefined);
@@ -152,6 +159,55 @@ export default function ViewPackage(props: ViewPackageProps) {
 		}
 	}
 
+	async function updateDetails(id: string) {
+		try {
+			let details = (await invoke("get_package_details", {
+				package: id,
+			})) as PackageDetails;
+			if (id != props.id) {
+				return;
+			}
+
+			if (details.body != undefined) {
+				setLongDescription(await renderDescription(details.body));
+			}
+			setDetails(details);
+		} catch (e) {
+			console.error("Failed to load package details: " + e);
+		}
+	}
+
+	async function renderDescription(description: string | undefined) {
+		let html = (await invoke("render_markdown", {
+			markdown: description == undefined ? "" : description,
+		})) as string;
+		return replaceExternalLinks(`<div>${html}</div>`);
+	}
+
+	let gallery = () => {
+		if (details()?.gallery != undefined) {
+			return details()!.gallery!;
+		}
+		return meta()!.gallery == undefined
+			? []
+			: meta()!.gallery!.map((url) => ({ url: url, featured: false }));
+	};
+
+	// Links from the details that aren't already shown from the metadata
+	let extraLinks = () => {
+		let known = [
+			meta()!.website,
+			meta()!.documentation,
+			meta()!.community,
+			meta()!.source,
+			meta()!.issues,
+			meta()!.support_link,
+		];
+		return (details()?.links == undefined ? [] : details()!.links!).filter(
+			(link) => !known.includes(link.url),
+		);
+	};
+
 	let info = () => {
 		return (
 			<>
@@ -185,13 +241,61 @@ export default function ViewPackage(props: ViewPackageProps) {
 						<OpenButton url={meta()!.issues} />
 					</Property>
 				</Show>
-				<For each={canonicalizeListOrSingle(meta()!.authors)}>
-					{(author) => (
-						<Property icon={User} label="Author">
-							{author}
+				<For each={extraLinks()}>
+					{(link) => (
+						<Property
+							icon={link.kind == "donation" ? Heart : Globe}
+							label={getLinkLabel(link)}
+							color={link.kind == "donation" ? "var(--error)" : undefined}
+						>
+							<OpenButton url={link.url} />
 						</Property>
 					)}
 				</For>
+				<Show
+					when={details()?.authors != undefined}
+					fallback={
+						<For each={canonicalizeListOrSingle(meta()!.authors)}>
+							{(author) => (
+								<Property icon={User} label="Author">
+									{author}
+								</Property>
+							)}
+						</For>
+					}
+				>
+					<For each={details()!.authors}>
+						{(author) => (
+							<Property
+								icon={User}
+								label={
+									author.role == undefined
+										? "Author"
+										: beautifyString(author.role)
+								}
+							>
+								<div
+									class={`cont package-author ${
+										author.id == undefined ? "" : "bubble-hover"
+									}`}
+									onclick={() => {
+										if (author.id != undefined) {
+											setSelectedAuthor(author.id);
+										}
+									}}
+								>
+									<Show when={author.avatar != undefined}>
+										<img
+											class="package-author-avatar"
+											src={getCachedImageSrc(author.avatar!, "small")}
+										/>
+									</Show>
+									{author.name}
+								</div>
+							</Property>
+						)}
+					</For>
+				</Show>
 				<Property icon={Key} label="License">
 					{meta()!.license == undefined ? (
 						"Unknown"
@@ -252,7 +356,7 @@ export default function ViewPackage(props: ViewPackageProps) {
 									src={
 										meta()?.icon == undefined
 											? "/icons/default_instance.png"
-											: meta()!.icon
+											: getCachedImageSrc(meta()!.icon!, "medium")
 									}
 									onerror={(e) =>
 										((e.target as any).src = "/icons/default_instance.png")
@@ -342,6 +446,15 @@ export default function ViewPackage(props: ViewPackageProps) {
 									<Icon icon={Folder} size="1rem" />
 									Versions
 								</div>
+								<div
+									class={`cont package-tab ${
+										selectedTab() == "compatibility" ? "selected" : ""
+									}`}
+									onclick={() => setSelectedTab("compatibility")}
+								>
+									<Icon icon={Grid} size="1rem" />
+									Compatibility
+								</div>
 								<div
 									class={`cont package-tab ${
 										selectedTab() == "gallery" ? "selected" : ""
@@ -387,13 +500,14 @@ export default function ViewPackage(props: ViewPackageProps) {
 										/>
 									</div>
 								</Show>
-								<Show
-									when={
-										selectedTab() == "gallery" && meta()!.gallery != undefined
-									}
-								>
+								<Show when={selectedTab() == "compatibility"}>
+									<div class="cont fullwidth">
+										<PackageCompatibility packageId={props.id} />
+									</div>
+								</Show>
+								<Show when={selectedTab() == "gallery"}>
 									<div class="cont">
-										<PackageGallery gallery={meta()!.gallery!} />
+										<PackageGallery gallery={gallery()} />
 									</div>
 								</Show>
 								<Show when={selectedTab() == "info"}>{info()}</Show>
@@ -427,6 +541,11 @@ export default function ViewPackage(props: ViewPackageProps) {
 				onClose={() => setShowInstallModal(false)}
 				onShowVersions={() => setSelectedTab("versions")}
 			/>
+			<AuthorModal
+				repository={packageReq().repository}
+				author={selectedAuthor()}
+				onClose={() => setSelectedAuthor(undefined)}
+			/>
 		</Show>
 	);
 }
@@ -473,6 +592,14 @@ function OpenButton({ url }: { url: string | undefined }) {
 	);
 }
 
+// Gets the label for an external link
+function getLinkLabel(link: ExternalLink) {
+	if (link.name != undefined) {
+		return beautifyString(link.name);
+	}
+	return beautifyString(link.kind);
+}
+
 interface PropertyProps {
 	icon: (props: HasWidthHeight) => JSX.Element;
 	label: string;
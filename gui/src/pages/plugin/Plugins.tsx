@@ -43,6 +43,7 @@ import { loadPagePlugins } from "../../plugins";
 import SlideSwitch from "../../components/input/SlideSwitch";
 import FloatingTabs from "../../components/input/select/FloatingTabs";
 import PackageVersion from "../../components/input/text/PackageVersion";
+import { replaceExternalLinks } from "../../App";
 import { useNavigate } from "@solidjs/router";
 import { open } from "@tauri-apps/plugin-shell";
 
@@ -240,6 +241,21 @@ function Plugin(props: PluginProps) {
 
 	let [inProgress, setInProgress] = createSignal(false);
 
+	let [description, _] = createResource(
+		() => props.info.description,
+		async (description) => {
+			try {
+				let html = (await invoke("render_markdown", {
+					markdown: description,
+				})) as string;
+				return replaceExternalLinks(html);
+			} catch (e) {
+				console.error("Failed to render plugin description: " + e);
+				return undefined;
+			}
+		},
+	);
+
 	return (
 		<div class={`cont col shadow plugin ${isDisabled() ? "disabled" : ""}`}>
 			<div class="plugin-top">
@@ -411,7 +427,14 @@ function Plugin(props: PluginProps) {
 				</div>
 			</div>
 			<div class="cont" style="justify-content:flex-start;width:100%">
-				<div class="plugin-description">{props.info.description}</div>
+				<Show
+					when={description() != undefined}
+					fallback={
+						<div class="plugin-description">{props.info.description}</div>
+					}
+				>
+					<div class="plugin-description" innerHTML={description()} />
+				</Show>
 			</div>
 		</div>
 	);
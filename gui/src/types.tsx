@@ -1,4 +1,4 @@
-import { ResolutionErrorData } from "./components/package/ResolutionError";
+import { ExplainedResolutionError } from "./components/package/ResolutionError";
 import { PackageCategory, PackageType } from "./package";
 
 export type Side = "client" | "server";
@@ -46,7 +46,7 @@ export interface AuthDisplayEvent {
 
 export interface ResolutionErrorEvent {
 	instance: string;
-	error: ResolutionErrorData;
+	error: ExplainedResolutionError;
 }
 
 export interface PackageMeta {
@@ -67,6 +67,8 @@ export interface PackageMeta {
 	license?: string;
 	authors?: string[];
 	downloads?: number;
+	followers?: number;
+	last_updated?: number;
 }
 
 export interface PackageProperties {
@@ -80,17 +82,101 @@ export interface PackageProperties {
 
 export type PackageMetaAndProps = [PackageMeta, PackageProperties];
 
+// Full details for a package's project page
+export interface PackageDetails {
+	body?: string;
+	gallery?: GalleryImage[];
+	links?: ExternalLink[];
+	authors?: PackageAuthor[];
+}
+
+export interface GalleryImage {
+	url: string;
+	raw_url?: string;
+	title?: string;
+	description?: string;
+	featured: boolean;
+}
+
+export interface ExternalLink {
+	kind:
+		| "website"
+		| "documentation"
+		| "source"
+		| "issues"
+		| "community"
+		| "donation"
+		| "other";
+	name?: string;
+	url: string;
+}
+
+export interface PackageAuthor {
+	id?: string;
+	name: string;
+	role?: string;
+	avatar?: string;
+}
+
+export interface AuthorProfile {
+	id: string;
+	name: string;
+	bio?: string;
+	avatar?: string;
+	url?: string;
+	packages?: string[];
+}
+
 export interface PkgRequest {
 	id: string;
 	repository?: string;
 	version?: string;
 	slug?: string;
+	// Where this request came from. Either a plain string or an object containing the parent request
+	source?:
+		| string
+		| { dependency: PkgRequest }
+		| { bundled: PkgRequest }
+		| { refused: PkgRequest };
 }
 
 export interface PackageSearchResults {
 	results: string[];
 	total_results: number;
 	previews: { [id: string]: [PackageMeta, PackageProperties] };
+	// Whether these are out of date cached results that should be refreshed
+	stale?: boolean;
+	// Cursor for getting the next page of results
+	next_cursor?: string;
+}
+
+export interface AggregatedSearchResults {
+	results: AggregatedSearchResult[];
+	total_results: number;
+	errors: { [repo: string]: string };
+	stale?: boolean;
+	next_cursor?: string;
+}
+
+export interface AggregatedSearchResult {
+	package: string;
+	sources: SearchResultSource[];
+	preview?: [PackageMeta, PackageProperties];
+}
+
+export interface SearchResultSource {
+	repository: string;
+	package: string;
+}
+
+export interface CompatibilityMatrix {
+	entries: CompatibilityEntry[];
+}
+
+export interface CompatibilityEntry {
+	content_version?: string;
+	minecraft_versions?: string[];
+	loaders?: string[];
 }
 
 export interface Theme {
@@ -100,6 +186,8 @@ export interface Theme {
 	type: "base" | "overlay";
 	css: string;
 	color: string;
+	variables?: { [name: string]: string };
+	font?: string;
 }
 
 export interface AccountTypeInfo {
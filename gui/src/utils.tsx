@@ -9,13 +9,27 @@ export function getInstanceIconSrc(
 		return "/icons/default_instance.png";
 	} else if (icon.startsWith("builtin:")) {
 		return icon.substring(8);
-	} else if (icon.startsWith("http")) {
-		return icon;
 	} else {
-		return convertFileSrc(icon);
+		return getCachedImageSrc(icon, "medium");
 	}
 }
 
+// The preferred resolution of a cached image
+export type ImageResolution = "small" | "medium" | "large" | "full";
+
+// Gets the src of an image from a URL or path through the image cache, so that it is only downloaded once
+export function getCachedImageSrc(
+	src: string,
+	resolution: ImageResolution = "full",
+): string {
+	if (src.startsWith("data:")) {
+		return src;
+	}
+	return `${convertFileSrc("", "nitro-image")}?src=${encodeURIComponent(
+		src,
+	)}&resolution=${resolution}`;
+}
+
 // Parses a URL query string (after the ?) into an easy to access map
 export function parseQueryString(string: string): QueryStringResult {
 	if (!string.startsWith("?")) {
@@ -142,6 +156,19 @@ export function formatNumber(num: number) {
 	}
 }
 
+// Formats a number of bytes
+export function formatBytes(bytes: number) {
+	if (bytes > 1000000000) {
+		return `${(bytes / 1000000000).toFixed(2)} GB`;
+	} else if (bytes > 1000000) {
+		return `${(bytes / 1000000).toFixed(1)} MB`;
+	} else if (bytes > 1000) {
+		return `${(bytes / 1000).toFixed(0)} KB`;
+	} else {
+		return `${bytes} B`;
+	}
+}
+
 // Creates a CSS translateY property to fix non-centered text based on the presence of descenders (p, g, q) and ascenders in the text
 export function fixCenter(text: string): string {
 	if (
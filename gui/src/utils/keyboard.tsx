@@ -0,0 +1,14 @@
+// Utilities for keyboard navigation
+
+/**
+ * Creates a keydown handler that runs an action when Enter or Space is pressed,
+ * so that custom controls can be used the same way as buttons
+ */
+export function activateOnKey(action: (e: Event) => void) {
+	return (e: KeyboardEvent) => {
+		if (e.key == "Enter" || e.key == " ") {
+			e.preventDefault();
+			action(e);
+		}
+	};
+}
@@ -1,6 +1,12 @@
 import { invoke } from "@tauri-apps/api/core";
-import { PackageCategory, PackageType } from "../package";
-import { PackageMeta, PackageProperties, PackageSearchResults } from "../types";
+import { PackageCategory, PackageSortMethod, PackageType } from "../package";
+import {
+	AggregatedSearchResults,
+	PackageMeta,
+	PackageProperties,
+	PackageSearchResults,
+} from "../types";
+import { warningToast } from "../components/dialog/Toasts";
 import { parsePkgRequest, parseVersionedString, pkgRequestToString } from "../utils";
 
 export async function searchPackages(
@@ -10,7 +16,12 @@ export async function searchPackages(
 	packageKinds: PackageType[],
 	minecraftVersions: string[],
 	loaders: string[],
-	categories: PackageCategory[]
+	categories: PackageCategory[],
+	sort?: PackageSortMethod,
+	// Whether out of date cached results can be returned right away
+	allowStale?: boolean,
+	// Cursor from a previous search to get the page after it
+	cursor?: string
 ): Promise<ExpandedPackageSearchResults | undefined> {
 	try {
 		let params = {
@@ -21,9 +32,40 @@ export async function searchPackages(
 			minecraftVersions: minecraftVersions,
 			loaders: loaders.map((x) => parseVersionedString(x)[0]),
 			categories: categories,
+			sort: sort,
+			allowStale: allowStale,
+			cursor: cursor,
 		};
 		console.log(params);
-		let results: PackageSearchResults = await invoke("get_packages", params);
+		let results: PackageSearchResults;
+		// Repositories that each package was found in when searching all of them
+		let sources: { [id: string]: string[] } = {};
+		if (repo == ALL_REPOS) {
+			let aggregated: AggregatedSearchResults = await invoke(
+				"search_all_packages",
+				params
+			);
+			for (let [repoId, error] of Object.entries(aggregated.errors)) {
+				warningToast(`Failed to search repository ${repoId}: ${error}`);
+			}
+
+			results = {
+				results: aggregated.results.map((x) => x.package),
+				total_results: aggregated.total_results,
+				previews: {},
+				stale: aggregated.stale,
+				next_cursor: aggregated.next_cursor,
+			};
+			for (let result of aggregated.results) {
+				if (result.preview != undefined) {
+					results.previews[result.package] = result.preview;
+				}
+				sources[result.package] = result.sources.map((x) => x.repository);
+			}
+			repo = undefined;
+		} else {
+			results = await invoke("get_packages", params);
+		}
 
 		let packages: (PackageData | "error")[] = [];
 
@@ -41,6 +83,7 @@ export async function searchPackages(
 					id: pkgRequestToString(pkg),
 					meta: preview[0],
 					props: preview[1],
+					sources: sources[results.results[i]],
 				});
 				results.results.splice(i, 1);
 				i--;
@@ -90,6 +133,7 @@ export async function searchPackages(
 							id: results.results[i],
 							meta: meta,
 							props: props,
+							sources: sources[results.results[i]],
 						} as PackageData;
 					}
 				});
@@ -103,6 +147,8 @@ export async function searchPackages(
 		return {
 			packages: packages,
 			totalCount: results.total_results,
+			stale: results.stale == true,
+			nextCursor: results.next_cursor,
 		};
 	} catch (e) {
 		throw "Failed to search packages: " + e;
@@ -112,10 +158,19 @@ export async function searchPackages(
 export interface ExpandedPackageSearchResults {
 	totalCount: number;
 	packages: (PackageData | "error")[];
+	// Whether the results are from an out of date cache and are being refreshed
+	stale: boolean;
+	// Cursor for getting the next page of results, if there are more
+	nextCursor?: string;
 }
 
 export interface PackageData {
 	id: string;
 	meta: PackageMeta;
 	props: PackageProperties;
+	// The repositories this package was found in, when searching all of them
+	sources?: string[];
 }
+
+// Repository selection for searching all repositories at once
+export const ALL_REPOS = "all";
@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter, Read};
+use std::io::{BufReader, BufWriter};
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, anyhow, bail, ensure};
 use base64::Engine;
 use base64::engine::GeneralPurposeConfig;
+use nitro_plugin::api::wasm::sys::{copy_file, delete_path};
 use nitro_shared::util::utc_timestamp;
 use serde::{Deserialize, Serialize};
 use zip::{ZipArchive, ZipWriter};
@@ -165,18 +166,13 @@ impl Index {
 		let backup_path =
 			self.get_backup_path(group_id, &backup_id, group_config.common.storage_type);
 
-		let mut readers = Vec::new();
+		let mut paths = Vec::new();
 		for path in &group_config.common.paths {
-			let paths = get_instance_file_paths(path, instance_dir)
+			let recursive_paths = get_instance_file_paths(path, instance_dir)
 				.context("Failed to get recursive file paths")?;
-			for path in paths {
-				let file = File::open(instance_dir.join(&path))
-					.with_context(|| format!("Failed to open backed up file with path {path}"))?;
-				let file = BufReader::new(file);
-				readers.push((path.clone(), file));
-			}
+			paths.extend(recursive_paths);
 		}
-		write_backup_files(&backup_path, &group_config, readers)?;
+		write_backup_files(&backup_path, &group_config, instance_dir, paths)?;
 
 		let now = utc_timestamp()?;
 		// Add the backup entry to the group
@@ -193,8 +189,13 @@ impl Index {
 		Ok(())
 	}
 
-	/// Remove a backup
-	pub fn remove_backup(&mut self, group_id: &str, backup_id: &str) -> anyhow::Result<()> {
+	/// Remove a backup. Unless the removal is permanent, the backup is moved to the trash
+	pub fn remove_backup(
+		&mut self,
+		group_id: &str,
+		backup_id: &str,
+		permanent: bool,
+	) -> anyhow::Result<()> {
 		let group_entry = self.contents.groups.entry(group_id.into()).or_default();
 		let index = group_entry
 			.backups
@@ -207,12 +208,7 @@ impl Index {
 		group_entry.backups.remove(index);
 
 		let backup_path = self.get_backup_path(group_id, backup_id, storage_type);
-		if backup_path.exists() {
-			match storage_type {
-				StorageType::Archive => fs::remove_file(backup_path)?,
-				StorageType::Folder => fs::remove_dir_all(backup_path)?,
-			}
-		}
+		delete_path(&backup_path, permanent).context("Failed to delete backup")?;
 
 		Ok(())
 	}
@@ -235,7 +231,8 @@ impl Index {
 				.map(|x| x.id.clone())
 				.collect();
 			for id in to_remove {
-				self.remove_backup(group_id, &id)
+				// Old backups are removed as part of normal operation, so they aren't kept in the trash
+				self.remove_backup(group_id, &id, true)
 					.with_context(|| format!("Failed to remove old backup '{id}'"))?;
 			}
 		}
@@ -395,12 +392,13 @@ fn get_instance_file_paths(path: &str, instance_dir: &Path) -> anyhow::Result<Ve
 	}
 }
 
-/// Writes backup files to the stored format. Takes the path to the backup file / directory.
-/// Readers are pairs of relative file paths and readers for files.
-fn write_backup_files<R: Read>(
+/// Writes backup files to the stored format. Takes the path to the backup file / directory
+/// and the paths of the files to back up, relative to the instance directory.
+fn write_backup_files(
 	backup_path: &Path,
 	group_config: &GroupConfig,
-	readers: Vec<(String, R)>,
+	instance_dir: &Path,
+	paths: Vec<String>,
 ) -> anyhow::Result<()> {
 	match &group_config.common.storage_type {
 		StorageType::Archive => {
@@ -415,7 +413,10 @@ fn write_backup_files<R: Read>(
 			let options = zip::write::FileOptions::<()>::default()
 				.compression_method(zip::CompressionMethod::Deflated);
 
-			for (path, mut reader) in readers {
+			for path in paths {
+				let reader = File::open(instance_dir.join(&path))
+					.with_context(|| format!("Failed to open backed up file with path {path}"))?;
+				let mut reader = BufReader::new(reader);
 				arc.start_file(path, options)?;
 				std::io::copy(&mut reader, &mut arc).context("Failed to copy to archive file")?;
 			}
@@ -423,16 +424,14 @@ fn write_backup_files<R: Read>(
 			arc.finish()?;
 		}
 		StorageType::Folder => {
-			for (path, mut reader) in readers {
-				let dest = backup_path.join(path);
+			// Folder snapshots are copied with reflinks when possible, so they don't take up extra space
+			for path in paths {
+				let dest = backup_path.join(&path);
 				if let Some(parent) = dest.parent() {
 					std::fs::create_dir_all(parent)?;
 				}
-				let file =
-					File::create(dest).context("Failed to create snapshot destination file")?;
-				let mut file = BufWriter::new(file);
-				std::io::copy(&mut reader, &mut file)
-					.context("Failed to copy to snapshot destination file")?;
+				copy_file(instance_dir.join(&path), dest)
+					.with_context(|| format!("Failed to copy backed up file with path {path}"))?;
 			}
 		}
 	};
@@ -472,10 +471,7 @@ fn copy_dir_contents(src: &Path, dest: &Path) -> anyhow::Result<()> {
 		let rel = src_path.strip_prefix(src)?;
 		let dest_path = dest.join(rel);
 
-		let mut src_file = std::io::BufReader::new(std::fs::File::open(src_path)?);
-		let mut dest_file = std::io::BufWriter::new(std::fs::File::create(dest_path)?);
-
-		std::io::copy(&mut src_file, &mut dest_file)?;
+		copy_file(src_path, dest_path)?;
 	}
 
 	Ok(())
@@ -44,7 +44,8 @@ fn main(plugin: &mut WASMPlugin) -> anyhow::Result<()> {
 				instance,
 				group,
 				backup,
-			} => remove(&instance, group.as_deref(), &backup, &mut o),
+				permanent,
+			} => remove(&instance, group.as_deref(), &backup, permanent, &mut o),
 			Subcommand::Restore {
 				instance,
 				group,
@@ -170,6 +171,9 @@ enum Subcommand {
 		group: Option<String>,
 		/// The backup to remove
 		backup: String,
+		/// Delete the backup permanently instead of moving it to the trash
+		#[arg(long)]
+		permanent: bool,
 	},
 	#[command(about = "Restore an existing backup")]
 	Restore {
@@ -242,13 +246,14 @@ fn remove(
 	instance: &str,
 	group: Option<&str>,
 	backup: &str,
+	permanent: bool,
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<()> {
 	let group = group.unwrap_or(DEFAULT_GROUP);
 
 	let mut index = get_index(instance)?;
 
-	index.remove_backup(group, backup)?;
+	index.remove_backup(group, backup, permanent)?;
 	index.finish()?;
 
 	o.display(MessageContents::Success("Backup removed".into()));
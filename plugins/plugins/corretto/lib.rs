@@ -0,0 +1,175 @@
+use std::{
+	fs::File,
+	io::{Read, Seek},
+	path::{Component, Path, PathBuf},
+};
+
+use anyhow::{Context, bail};
+use nitro_plugin::{
+	api::wasm::{
+		WASMPlugin,
+		net::download_bytes,
+		sys::{get_arch_string, get_data_dir, get_os_string},
+	},
+	hook::hooks::InstallCustomJavaResult,
+	nitro_wasm_plugin,
+};
+use tar::Archive;
+use zip::ZipArchive;
+
+nitro_wasm_plugin!(main, "corretto");
+
+fn main(plugin: &mut WASMPlugin) -> anyhow::Result<()> {
+	plugin.install_custom_java(|arg| {
+		if arg.kind != "corretto" {
+			return Ok(None);
+		}
+
+		let out_dir = get_data_dir().join("internal/java/corretto");
+		if !out_dir.exists() {
+			let _ = std::fs::create_dir_all(&out_dir);
+		}
+
+		let url = download_url(&arg.major_version)?;
+		let archive = download_bytes(&url).with_context(|| {
+			format!(
+				"Failed to download Corretto binaries. Corretto may not be available for Java {}",
+				arg.major_version
+			)
+		})?;
+
+		let dir_name = extract_archive(std::io::Cursor::new(archive), &out_dir)
+			.context("Failed to extract Corretto archive")?;
+		let mut extracted_dir = out_dir.join(dir_name);
+
+		// MacOS builds are bundles with the actual installation deeper inside
+		let bundle_home = extracted_dir.join("Contents/Home");
+		if bundle_home.exists() {
+			extracted_dir = bundle_home;
+		}
+
+		let version =
+			get_installation_version(&extracted_dir).unwrap_or_else(|| arg.major_version.clone());
+
+		Ok(Some(InstallCustomJavaResult {
+			path: extracted_dir.to_string_lossy().to_string(),
+			version,
+		}))
+	})?;
+
+	Ok(())
+}
+
+/// Gets the download URL for the newest Corretto build of a major Java version
+fn download_url(major_version: &str) -> anyhow::Result<String> {
+	Ok(format!(
+		"https://corretto.aws/downloads/latest/amazon-corretto-{major_version}-{}-{}-jdk.{}",
+		get_arch_arg()?,
+		get_os_arg()?,
+		get_preferred_archive()
+	))
+}
+
+/// Gets the OS name used in Corretto downloads
+fn get_os_arg() -> anyhow::Result<&'static str> {
+	match get_os_string().as_str() {
+		"linux" => Ok("linux"),
+		"windows" => Ok("windows"),
+		"macos" => Ok("macos"),
+		other => bail!("Corretto is not available for the operating system '{other}'"),
+	}
+}
+
+/// Gets the architecture name used in Corretto downloads
+fn get_arch_arg() -> anyhow::Result<&'static str> {
+	match get_arch_string().as_str() {
+		"x86_64" => Ok("x64"),
+		"x86" => Ok("x86"),
+		"aarch64" => Ok("aarch64"),
+		"arm" => Ok("arm"),
+		other => bail!("Corretto is not available for the architecture '{other}'"),
+	}
+}
+
+/// Gets the preferred archive
+fn get_preferred_archive() -> &'static str {
+	match get_os_string().as_str() {
+		"windows" => "zip",
+		_ => "tar.gz",
+	}
+}
+
+/// Reads the full Java version of an installation from its release file, since the archive names
+/// don't consistently include it
+fn get_installation_version(path: &Path) -> Option<String> {
+	let release = std::fs::read_to_string(path.join("release")).ok()?;
+	release.lines().find_map(|line| {
+		let version = line.strip_prefix("JAVA_VERSION=")?;
+		Some(version.trim().trim_matches('"').to_string())
+	})
+}
+
+/// Gets the top-level directory of a path inside an archive
+fn get_root_dir(path: &Path) -> anyhow::Result<String> {
+	let Some(Component::Normal(root)) = path.components().next() else {
+		bail!("Missing archive internal directory");
+	};
+
+	Ok(root.to_string_lossy().to_string())
+}
+
+/// Extracts the JDK archive (either a tar or a zip) and also returns the internal extraction directory name
+fn extract_archive<R: Read + Seek>(reader: R, out_dir: &Path) -> anyhow::Result<String> {
+	let dir_name = if get_os_string() == "windows" {
+		let mut archive = ZipArchive::new(reader).context("Failed to open zip archive")?;
+
+		let dir_name = get_root_dir(&PathBuf::from(
+			archive
+				.file_names()
+				.next()
+				.context("Missing archive internal directory")?,
+		))?;
+
+		archive
+			.extract(out_dir)
+			.context("Failed to extract zip file")?;
+
+		dir_name
+	} else {
+		let mut decoder =
+			libflate::gzip::Decoder::new(reader).context("Failed to decode tar.gz")?;
+		let mut arc = Archive::new(&mut decoder);
+
+		let mut dir_name = None;
+		// Manual extraction implementation since WASI-p2 doesn't support fs::canonicalize
+		for entry in arc.entries()? {
+			let mut entry = entry?;
+			let entry_path = entry.path()?.to_path_buf();
+			if dir_name.is_none() {
+				dir_name = Some(get_root_dir(&entry_path)?);
+			}
+
+			let dest_path = out_dir.join(&entry_path);
+			if entry.header().entry_type().is_dir() {
+				if !dest_path.exists() {
+					let _ = std::fs::create_dir_all(dest_path);
+				}
+				continue;
+			}
+
+			if let Some(parent) = dest_path.parent()
+				&& !parent.exists()
+			{
+				std::fs::create_dir_all(parent)?;
+			}
+
+			let mut out_file =
+				File::create(dest_path).context("Failed to open destination file")?;
+			std::io::copy(&mut entry, &mut out_file).context("Failed to copy file")?;
+		}
+
+		dir_name.context("Missing archive internal directory")?
+	};
+
+	Ok(dir_name)
+}
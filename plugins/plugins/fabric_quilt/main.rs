@@ -3,7 +3,10 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, bail};
 use nitro_mods::fabric_quilt;
 use nitro_plugin::{api::executable::ExecutablePlugin, hook::hooks::OnInstanceSetupResult};
-use nitro_shared::{UpdateDepth, loaders::Loader, versions::VersionPattern};
+use nitro_shared::{
+	UpdateDepth,
+	loaders::{Loader, list_loader_versions},
+};
 
 fn main() -> anyhow::Result<()> {
 	let mut plugin =
@@ -36,16 +39,10 @@ fn main() -> anyhow::Result<()> {
 		let client = nitro_net::download::Client::new();
 		let runtime = tokio::runtime::Runtime::new()?;
 
-		let desired_fq_version = if let VersionPattern::Single(pat) = arg.desired_loader_version {
-			Some(pat)
-		} else {
-			None
-		};
-
 		let meta = runtime
 			.block_on(fabric_quilt::get_meta(
 				&arg.version_info.version,
-				desired_fq_version.as_deref(),
+				&arg.desired_loader_version,
 				&mode,
 				&internal_dir,
 				arg.update_depth,
@@ -53,7 +50,10 @@ fn main() -> anyhow::Result<()> {
 			))
 			.context("Failed to get metadata")?;
 
-		let libraries_dir = internal_dir.join("libraries");
+		// Libraries can be relocated out of the internal directory
+		let libraries_dir = nitro_core::Paths::new_no_create()
+			.context("Failed to get paths")?
+			.libraries;
 
 		runtime
 			.block_on(fabric_quilt::download_files(
@@ -126,14 +126,9 @@ fn main() -> anyhow::Result<()> {
 			))
 			.context("Failed to get metadata")?;
 
-		let out = meta.into_iter().map(|version| {
-			version
-				.loader
-				.maven
-				.replace("net.fabricmc:fabric-loader:", "")
-		});
+		let versions = fabric_quilt::get_loader_versions(&meta);
 
-		Ok(out.collect())
+		Ok(list_loader_versions(&versions, arg.channel))
 	})?;
 
 	Ok(())
@@ -5,7 +5,7 @@ use nitro_mods::forge::{self, Mode};
 use nitro_net::neoforge;
 use nitro_plugin::{api::executable::ExecutablePlugin, hook::hooks::OnInstanceSetupResult};
 use nitro_shared::{
-	loaders::Loader,
+	loaders::{Loader, LoaderVersion, list_loader_versions, select_loader_version},
 	output::{MessageContents, NitroOutput},
 };
 
@@ -41,10 +41,16 @@ fn main() -> anyhow::Result<()> {
 		let result = match mode {
 			Mode::NeoForge => {
 				let versions = runtime.block_on(neoforge::get_versions(&client))?;
+				let versions = get_neoforge_versions(&versions, &arg.version_info.version);
 
-				let version =
-					neoforge::get_latest_neoforge_version(&versions, &arg.version_info.version)
-						.context("Could not find NeoForge version for this Minecraft version")?;
+				let version = select_loader_version(&arg.desired_loader_version, &versions)
+					.map(|x| &x.version)
+					.with_context(|| {
+						format!(
+							"Could not find a NeoForge version matching {} for this Minecraft version",
+							arg.desired_loader_version
+						)
+					})?;
 
 				loader_version = Some(version.clone());
 
@@ -77,5 +83,29 @@ fn main() -> anyhow::Result<()> {
 		})
 	})?;
 
+	plugin.get_loader_versions(|_, arg| {
+		if arg.loader != Loader::NeoForged {
+			return Ok(Vec::new());
+		}
+
+		let client = nitro_net::download::Client::new();
+		let runtime = tokio::runtime::Runtime::new()?;
+
+		let versions = runtime
+			.block_on(neoforge::get_versions(&client))
+			.context("Failed to get list of NeoForge versions")?;
+		let versions = get_neoforge_versions(&versions, &arg.minecraft_version);
+
+		Ok(list_loader_versions(&versions, arg.channel))
+	})?;
+
 	Ok(())
 }
+
+/// Gets the NeoForge versions for a Minecraft version, sorted from oldest to newest
+fn get_neoforge_versions(versions: &[String], minecraft_version: &str) -> Vec<LoaderVersion> {
+	neoforge::get_compatible_versions(versions, minecraft_version)
+		.into_iter()
+		.map(|x| LoaderVersion::from_name(x.clone()))
+		.collect()
+}
@@ -0,0 +1,380 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use anyhow::Context;
+use nitro_plugin::api::executable::ExecutablePlugin;
+use nitro_shared::Side;
+use nitro_shared::output::{MessageContents, NitroOutput};
+use serde::Serialize;
+
+use crate::metadata::{DependencyKind, ModInfo, read_jar};
+use crate::versions::VersionRange;
+
+mod metadata;
+mod versions;
+
+/// Mod IDs that are provided by the game or loaders instead of by mod jars
+static BUILTIN_MODS: &[&str] = &[
+	"java",
+	"fabricloader",
+	"fabric-loader",
+	"quilt_loader",
+	"forge",
+	"neoforge",
+	"fml",
+	"javafml",
+	"lowcodefml",
+	"mclanguage",
+];
+
+fn main() -> anyhow::Result<()> {
+	let mut plugin =
+		ExecutablePlugin::from_manifest_file("mod_scanner", include_str!("plugin.json"))?;
+
+	plugin.after_packages_installed(|mut ctx, arg| {
+		let Some(inst_dir) = arg.inst_dir else {
+			return Ok(());
+		};
+		let mods_dir = Path::new(&inst_dir).join("mods");
+		if !mods_dir.exists() {
+			return Ok(());
+		}
+
+		let mut mods = Vec::new();
+		let mut entries: Vec<_> = std::fs::read_dir(&mods_dir)
+			.context("Failed to read mods directory")?
+			.filter_map(|x| x.ok())
+			.map(|x| x.path())
+			.filter(|x| x.extension().is_some_and(|x| x == "jar"))
+			.collect();
+		entries.sort();
+
+		for path in entries {
+			let file = path.file_name().unwrap_or_default().to_string_lossy();
+			let result = File::open(&path)
+				.map_err(anyhow::Error::from)
+				.and_then(|x| read_jar(BufReader::new(x), &file));
+			match result {
+				Ok(jar_mods) => mods.extend(jar_mods),
+				Err(e) => ctx.get_output().debug(MessageContents::Warning(format!(
+					"Failed to read mod '{file}': {e:#}"
+				))),
+			}
+		}
+
+		let problems = find_problems(&mods, &arg.version_info.version, arg.side);
+
+		if !problems.is_empty() {
+			let output = ctx.get_output();
+			output.display(MessageContents::Warning(format!(
+				"Found potential problems with the mods of instance '{}':",
+				arg.id
+			)));
+			for problem in &problems {
+				output.display(MessageContents::ListItem(Box::new(
+					MessageContents::Simple(problem.to_string()),
+				)));
+			}
+		}
+
+		// Write the report so that other tools can inspect the problems
+		let report_path = ctx
+			.get_data_dir()?
+			.join("internal/mod_scanner")
+			.join(format!("{}.json", arg.id));
+		if let Some(parent) = report_path.parent() {
+			let _ = std::fs::create_dir_all(parent);
+		}
+		let report = Report {
+			mod_count: mods.iter().filter(|x| !x.nested).count(),
+			problems,
+		};
+		let data = serde_json::to_string_pretty(&report).context("Failed to serialize report")?;
+		std::fs::write(report_path, data).context("Failed to write report")?;
+
+		Ok(())
+	})?;
+
+	Ok(())
+}
+
+/// Report of the problems found in an instance
+#[derive(Serialize)]
+struct Report {
+	/// The number of mod jars that were found
+	mod_count: usize,
+	/// The problems that were found
+	problems: Vec<Problem>,
+}
+
+/// A problem with the mods of an instance
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Problem {
+	/// A mod is installed more than once
+	DuplicateMod {
+		/// The ID of the mod
+		id: String,
+		/// The files that contain the mod
+		files: Vec<String>,
+	},
+	/// A mod requires another mod that isn't installed
+	MissingDependency {
+		/// The ID of the mod
+		id: String,
+		/// The file that contains the mod
+		file: String,
+		/// The ID of the missing dependency
+		dependency: String,
+		/// The versions of the dependency that are accepted
+		range: String,
+	},
+	/// A mod depends on a version of another mod that isn't installed
+	WrongDependencyVersion {
+		/// The ID of the mod
+		id: String,
+		/// The file that contains the mod
+		file: String,
+		/// The ID of the dependency
+		dependency: String,
+		/// The versions of the dependency that are accepted
+		range: String,
+		/// The versions of the dependency that are installed
+		installed: Vec<String>,
+	},
+	/// A mod is installed alongside another mod that it is incompatible with
+	Incompatible {
+		/// The ID of the mod
+		id: String,
+		/// The file that contains the mod
+		file: String,
+		/// The ID of the incompatible mod
+		other: String,
+		/// The versions of the other mod that are incompatible
+		range: String,
+	},
+}
+
+impl Display for Problem {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::DuplicateMod { id, files } => {
+				write!(
+					f,
+					"Mod '{id}' is installed more than once, in {}",
+					files.join(", ")
+				)
+			}
+			Self::MissingDependency {
+				id,
+				file,
+				dependency,
+				range,
+			} => {
+				write!(f, "Mod '{id}' in {file} requires '{dependency}'")?;
+				if range != "*" {
+					write!(f, " {range}")?;
+				}
+				write!(f, ", but it is not installed")
+			}
+			Self::WrongDependencyVersion {
+				id,
+				file,
+				dependency,
+				range,
+				installed,
+			} => write!(
+				f,
+				"Mod '{id}' in {file} requires '{dependency}' {range}, but version {} is installed",
+				installed.join(", ")
+			),
+			Self::Incompatible {
+				id,
+				file,
+				other,
+				range,
+			} => {
+				write!(f, "Mod '{id}' in {file} is incompatible with '{other}'")?;
+				if range != "*" {
+					write!(f, " {range}")?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+/// Finds problems with a set of installed mods
+fn find_problems(mods: &[ModInfo], minecraft_version: &str, side: Option<Side>) -> Vec<Problem> {
+	let mods: Vec<_> = mods.iter().filter(|x| is_on_side(x.side, side)).collect();
+
+	// Map of mod IDs to the versions of them that are available
+	let mut available: HashMap<&str, Vec<Option<&str>>> = HashMap::new();
+	for info in &mods {
+		let version = info.version.as_deref();
+		available.entry(&info.id).or_default().push(version);
+		for id in &info.provides {
+			available.entry(id).or_default().push(version);
+		}
+	}
+	// Snapshot versions can't be compared to ranges properly
+	let is_release = !minecraft_version.is_empty()
+		&& minecraft_version
+			.chars()
+			.all(|x| x.is_ascii_digit() || x == '.');
+	available
+		.entry("minecraft")
+		.or_default()
+		.push(is_release.then_some(minecraft_version));
+	for id in BUILTIN_MODS {
+		available.entry(*id).or_default().push(None);
+	}
+
+	let mut problems = Vec::new();
+
+	// Nested mods are deduplicated by the loader, so only top-level ones are checked
+	let mut files_by_id: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+	for info in mods.iter().filter(|x| !x.nested) {
+		let files = files_by_id.entry(&info.id).or_default();
+		if !files.contains(&info.file) {
+			files.push(info.file.clone());
+		}
+	}
+	for (id, files) in files_by_id {
+		if files.len() > 1 {
+			problems.push(Problem::DuplicateMod {
+				id: id.to_string(),
+				files,
+			});
+		}
+	}
+
+	for info in &mods {
+		for dependency in &info.dependencies {
+			if dependency.id == "java" || !is_on_side(dependency.side, side) {
+				continue;
+			}
+
+			let versions = available.get(dependency.id.as_str());
+			let problem = match (dependency.kind, versions) {
+				(DependencyKind::Required, None) => Some(Problem::MissingDependency {
+					id: info.id.clone(),
+					file: info.file.clone(),
+					dependency: dependency.id.clone(),
+					range: dependency.range.to_string(),
+				}),
+				(DependencyKind::Required | DependencyKind::Optional, Some(versions)) => {
+					// Only report versions that definitely don't match
+					let is_wrong = versions
+						.iter()
+						.all(|x| x.and_then(|x| dependency.range.matches(x)) == Some(false));
+					is_wrong.then(|| Problem::WrongDependencyVersion {
+						id: info.id.clone(),
+						file: info.file.clone(),
+						dependency: dependency.id.clone(),
+						range: dependency.range.to_string(),
+						installed: versions.iter().flatten().map(|x| x.to_string()).collect(),
+					})
+				}
+				(DependencyKind::Incompatible, Some(versions)) => {
+					let is_incompatible = versions.iter().any(|x| match x {
+						Some(version) => dependency.range.matches(version) == Some(true),
+						None => dependency.range == VersionRange::Any,
+					});
+					is_incompatible.then(|| Problem::Incompatible {
+						id: info.id.clone(),
+						file: info.file.clone(),
+						other: dependency.id.clone(),
+						range: dependency.range.to_string(),
+					})
+				}
+				(DependencyKind::Optional | DependencyKind::Incompatible, None) => None,
+			};
+
+			if let Some(problem) = problem
+				&& !problems.contains(&problem)
+			{
+				problems.push(problem);
+			}
+		}
+	}
+
+	problems
+}
+
+/// Checks if something limited to a side applies to the side of an instance
+fn is_on_side(limit: Option<Side>, side: Option<Side>) -> bool {
+	match (limit, side) {
+		(Some(limit), Some(side)) => limit == side,
+		_ => true,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::metadata::Dependency;
+
+	fn create_mod(id: &str, version: &str, file: &str, dependencies: Vec<Dependency>) -> ModInfo {
+		ModInfo {
+			id: id.into(),
+			version: Some(version.into()),
+			file: file.into(),
+			nested: false,
+			provides: Vec::new(),
+			dependencies,
+			side: None,
+		}
+	}
+
+	fn create_dependency(id: &str, range: &str, kind: DependencyKind) -> Dependency {
+		Dependency {
+			id: id.into(),
+			range: VersionRange::Fabric(vec![range.into()]),
+			kind,
+			side: None,
+		}
+	}
+
+	#[test]
+	fn test_find_problems() {
+		let mods = vec![
+			create_mod(
+				"sodium",
+				"0.5.8",
+				"sodium.jar",
+				vec![
+					create_dependency("minecraft", "~1.20.1", DependencyKind::Required),
+					create_dependency("fabric-api", ">=0.90.0", DependencyKind::Required),
+					create_dependency("optifabric", "*", DependencyKind::Incompatible),
+				],
+			),
+			create_mod(
+				"iris",
+				"1.7.0",
+				"iris.jar",
+				vec![create_dependency(
+					"sodium",
+					">=0.5.8",
+					DependencyKind::Required,
+				)],
+			),
+			create_mod("lithium", "0.11.2", "lithium-old.jar", Vec::new()),
+			create_mod("lithium", "0.12.0", "lithium.jar", Vec::new()),
+		];
+
+		let problems = find_problems(&mods, "1.21", Some(Side::Client));
+		assert_eq!(problems.len(), 3);
+		assert!(matches!(&problems[0], Problem::DuplicateMod { id, .. } if id == "lithium"));
+		assert!(
+			matches!(&problems[1], Problem::WrongDependencyVersion { dependency, .. } if dependency == "minecraft")
+		);
+		assert!(
+			matches!(&problems[2], Problem::MissingDependency { dependency, .. } if dependency == "fabric-api")
+		);
+	}
+}
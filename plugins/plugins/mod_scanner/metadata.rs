@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Seek};
+
+use anyhow::Context;
+use nitro_shared::Side;
+use nitro_shared::util::DeserListOrSingle;
+use serde::Deserialize;
+use zip::ZipArchive;
+
+use crate::versions::VersionRange;
+
+/// How deep to look for mods inside of other mod jars
+const MAX_NESTING: u8 = 3;
+
+/// Metadata for a single mod found in a jar
+#[derive(Debug, Clone)]
+pub struct ModInfo {
+	/// The ID of the mod
+	pub id: String,
+	/// The version of the mod, if it is known
+	pub version: Option<String>,
+	/// The filename of the top-level jar that this mod was found in
+	pub file: String,
+	/// Whether this mod was nested inside of another mod jar
+	pub nested: bool,
+	/// Other mod IDs that this mod provides
+	pub provides: Vec<String>,
+	/// The dependencies of this mod
+	pub dependencies: Vec<Dependency>,
+	/// The only side that this mod runs on, if it is limited to one
+	pub side: Option<Side>,
+}
+
+/// A dependency of a mod on another mod
+#[derive(Debug, Clone)]
+pub struct Dependency {
+	/// The ID of the other mod
+	pub id: String,
+	/// The versions of the other mod that are accepted
+	pub range: VersionRange,
+	/// What kind of dependency this is
+	pub kind: DependencyKind,
+	/// The only side that this dependency applies to, if it is limited to one
+	pub side: Option<Side>,
+}
+
+/// Kind of a dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyKind {
+	/// The other mod must be installed
+	Required,
+	/// The other mod does not have to be installed, but has to match the version range if it is
+	Optional,
+	/// The other mod can't be installed alongside this one
+	Incompatible,
+}
+
+/// Reads the metadata of all the mods in a jar file, including the ones nested inside of it
+pub fn read_jar(reader: impl Read + Seek, file: &str) -> anyhow::Result<Vec<ModInfo>> {
+	let mut out = Vec::new();
+	read_jar_impl(reader, file, 0, &mut out)?;
+	Ok(out)
+}
+
+/// Recursive implementation for read_jar
+fn read_jar_impl(
+	reader: impl Read + Seek,
+	file: &str,
+	depth: u8,
+	out: &mut Vec<ModInfo>,
+) -> anyhow::Result<()> {
+	let mut archive = ZipArchive::new(reader).context("Failed to open jar")?;
+	let nested = depth > 0;
+
+	let mut nested_jars = Vec::new();
+	if let Some(contents) = read_entry(&mut archive, "fabric.mod.json") {
+		let metadata: FabricModJson =
+			serde_json::from_str(&contents).context("Failed to deserialize fabric.mod.json")?;
+		nested_jars.extend(metadata.jars.iter().map(|x| x.file.clone()));
+		out.push(metadata.into_mod_info(file, nested));
+	}
+
+	for path in ["META-INF/mods.toml", "META-INF/neoforge.mods.toml"] {
+		if let Some(contents) = read_entry(&mut archive, path) {
+			let manifest_version = read_entry(&mut archive, "META-INF/MANIFEST.MF")
+				.and_then(|x| get_manifest_version(&x));
+			out.extend(parse_mods_toml(
+				&contents,
+				file,
+				nested,
+				manifest_version.as_deref(),
+			));
+			nested_jars.extend(
+				archive
+					.file_names()
+					.filter(|x| x.starts_with("META-INF/jarjar/") && x.ends_with(".jar"))
+					.map(ToString::to_string),
+			);
+			break;
+		}
+	}
+
+	if depth >= MAX_NESTING {
+		return Ok(());
+	}
+
+	for path in nested_jars {
+		let Some(bytes) = read_entry_bytes(&mut archive, &path) else {
+			continue;
+		};
+		// A broken nested jar shouldn't stop the rest of the mod from being read
+		let _ = read_jar_impl(Cursor::new(bytes), file, depth + 1, out);
+	}
+
+	Ok(())
+}
+
+/// Reads a text file from an archive, if it exists
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, path: &str) -> Option<String> {
+	let bytes = read_entry_bytes(archive, path)?;
+	String::from_utf8(bytes).ok()
+}
+
+/// Reads a file from an archive, if it exists
+fn read_entry_bytes<R: Read + Seek>(archive: &mut ZipArchive<R>, path: &str) -> Option<Vec<u8>> {
+	let mut entry = archive.by_name(path).ok()?;
+	let mut out = Vec::new();
+	entry.read_to_end(&mut out).ok()?;
+	Some(out)
+}
+
+/// Gets the implementation version from the contents of a jar manifest
+fn get_manifest_version(manifest: &str) -> Option<String> {
+	manifest.lines().find_map(|line| {
+		let version = line.strip_prefix("Implementation-Version:")?;
+		Some(version.trim().to_string())
+	})
+}
+
+/// Format for a fabric.mod.json file
+#[derive(Deserialize)]
+struct FabricModJson {
+	/// The ID of the mod
+	id: String,
+	/// The version of the mod
+	#[serde(default)]
+	version: Option<String>,
+	/// Other mod IDs that this mod provides
+	#[serde(default)]
+	provides: Vec<String>,
+	/// The environment the mod runs in
+	#[serde(default)]
+	environment: Option<String>,
+	/// Required dependencies
+	#[serde(default)]
+	depends: HashMap<String, DeserListOrSingle<String>>,
+	/// Incompatible mods
+	#[serde(default)]
+	breaks: HashMap<String, DeserListOrSingle<String>>,
+	/// Nested jars
+	#[serde(default)]
+	jars: Vec<FabricNestedJar>,
+}
+
+/// A nested jar in a fabric.mod.json file
+#[derive(Deserialize)]
+struct FabricNestedJar {
+	/// Path to the jar inside of the parent jar
+	file: String,
+}
+
+impl FabricModJson {
+	/// Converts this metadata into mod info
+	fn into_mod_info(self, file: &str, nested: bool) -> ModInfo {
+		let side = match self.environment.as_deref() {
+			Some("client") => Some(Side::Client),
+			Some("server") => Some(Side::Server),
+			_ => None,
+		};
+
+		let depends = self
+			.depends
+			.into_iter()
+			.map(|x| (x, DependencyKind::Required));
+		let breaks = self
+			.breaks
+			.into_iter()
+			.map(|x| (x, DependencyKind::Incompatible));
+		let dependencies = depends
+			.chain(breaks)
+			.map(|((id, predicates), kind)| {
+				let predicates = predicates.get_vec();
+				let range = if predicates.is_empty() || predicates.iter().any(|x| x.trim() == "*") {
+					VersionRange::Any
+				} else {
+					VersionRange::Fabric(predicates)
+				};
+
+				Dependency {
+					id,
+					range,
+					kind,
+					side: None,
+				}
+			})
+			.collect();
+
+		ModInfo {
+			id: self.id,
+			version: self.version,
+			file: file.to_string(),
+			nested,
+			provides: self.provides,
+			dependencies,
+			side,
+		}
+	}
+}
+
+/// Section of a mods.toml file that is being parsed
+enum TomlSection {
+	/// A [[mods]] entry
+	Mod,
+	/// A [[dependencies.<id>]] entry
+	Dependency,
+	/// Any other section
+	Other,
+}
+
+/// Parses the mods from a Forge or NeoForge mods.toml file. This only handles the subset of TOML
+/// that these files use in practice
+fn parse_mods_toml(
+	contents: &str,
+	file: &str,
+	nested: bool,
+	manifest_version: Option<&str>,
+) -> Vec<ModInfo> {
+	let mut mods: Vec<ModInfo> = Vec::new();
+	let mut dependencies: Vec<(String, Dependency)> = Vec::new();
+	let mut section = TomlSection::Other;
+	let mut multiline_delimiter = None;
+
+	for line in contents.lines() {
+		let line = line.trim();
+
+		// Skip over multiline strings, which are usually descriptions
+		if let Some(delimiter) = multiline_delimiter {
+			if line.contains(delimiter) {
+				multiline_delimiter = None;
+			}
+			continue;
+		}
+
+		if let Some(header) = line.strip_prefix("[[").and_then(|x| x.split("]]").next()) {
+			let header = header.trim();
+			section = if header == "mods" {
+				mods.push(ModInfo {
+					id: String::new(),
+					version: None,
+					file: file.to_string(),
+					nested,
+					provides: Vec::new(),
+					dependencies: Vec::new(),
+					side: None,
+				});
+				TomlSection::Mod
+			} else if let Some(owner) = header.strip_prefix("dependencies.") {
+				dependencies.push((
+					owner.trim_matches('"').to_string(),
+					Dependency {
+						id: String::new(),
+						range: VersionRange::Any,
+						kind: DependencyKind::Required,
+						side: None,
+					},
+				));
+				TomlSection::Dependency
+			} else {
+				TomlSection::Other
+			};
+			continue;
+		}
+		if line.starts_with('[') {
+			section = TomlSection::Other;
+			continue;
+		}
+
+		let Some((key, value)) = line.split_once('=') else {
+			continue;
+		};
+		let key = key.trim();
+		let value = value.trim();
+		for delimiter in ["'''", "\"\"\""] {
+			if let Some(rest) = value.strip_prefix(delimiter)
+				&& !rest.contains(delimiter)
+			{
+				multiline_delimiter = Some(delimiter);
+			}
+		}
+		let value = parse_toml_value(value);
+
+		match &section {
+			TomlSection::Mod => {
+				let Some(info) = mods.last_mut() else {
+					continue;
+				};
+				match key {
+					"modId" => info.id = value,
+					"version" => {
+						info.version = if value.contains("${") {
+							manifest_version.map(ToString::to_string)
+						} else {
+							Some(value)
+						};
+					}
+					_ => {}
+				}
+			}
+			TomlSection::Dependency => {
+				let Some((_, dependency)) = dependencies.last_mut() else {
+					continue;
+				};
+				match key {
+					"modId" => dependency.id = value,
+					"versionRange" => {
+						dependency.range = if value.is_empty() || value == "*" {
+							VersionRange::Any
+						} else {
+							VersionRange::Maven(value)
+						};
+					}
+					"mandatory" => {
+						dependency.kind = if value == "true" {
+							DependencyKind::Required
+						} else {
+							DependencyKind::Optional
+						};
+					}
+					"type" => {
+						dependency.kind = match value.to_lowercase().as_str() {
+							"required" => DependencyKind::Required,
+							"incompatible" => DependencyKind::Incompatible,
+							_ => DependencyKind::Optional,
+						};
+					}
+					"side" => {
+						dependency.side = match value.to_uppercase().as_str() {
+							"CLIENT" => Some(Side::Client),
+							"SERVER" => Some(Side::Server),
+							_ => None,
+						};
+					}
+					_ => {}
+				}
+			}
+			TomlSection::Other => {}
+		}
+	}
+
+	mods.retain(|x| !x.id.is_empty());
+	for (owner, dependency) in dependencies {
+		if dependency.id.is_empty() {
+			continue;
+		}
+		if let Some(info) = mods.iter_mut().find(|x| x.id == owner) {
+			info.dependencies.push(dependency);
+		}
+	}
+
+	mods
+}
+
+/// Parses a simple TOML value into a string, removing quotes and comments
+fn parse_toml_value(value: &str) -> String {
+	for quote in ['"', '\''] {
+		if let Some(rest) = value.strip_prefix(quote) {
+			return rest.split(quote).next().unwrap_or_default().to_string();
+		}
+	}
+
+	value
+		.split('#')
+		.next()
+		.unwrap_or_default()
+		.trim()
+		.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_mods_toml_parsing() {
+		let contents = r#"
+modLoader="javafml"
+loaderVersion="[47,)"
+
+[[mods]]
+modId="examplemod"
+version="${file.jarVersion}"
+description='''
+An example mod
+[[mods]]
+'''
+
+[[dependencies.examplemod]]
+	modId="forge" # The loader
+	mandatory=true
+	versionRange="[47,)"
+	side="BOTH"
+
+[[dependencies.examplemod]]
+	modId="jei"
+	type="incompatible"
+	versionRange="[15,)"
+	side="CLIENT"
+"#;
+
+		let mods = parse_mods_toml(contents, "example.jar", false, Some("1.2.0"));
+		assert_eq!(mods.len(), 1);
+		let info = &mods[0];
+		assert_eq!(info.id, "examplemod");
+		assert_eq!(info.version.as_deref(), Some("1.2.0"));
+		assert_eq!(info.dependencies.len(), 2);
+
+		let forge = &info.dependencies[0];
+		assert_eq!(forge.id, "forge");
+		assert_eq!(forge.kind, DependencyKind::Required);
+		assert_eq!(forge.range, VersionRange::Maven("[47,)".into()));
+		assert_eq!(forge.side, None);
+
+		let jei = &info.dependencies[1];
+		assert_eq!(jei.kind, DependencyKind::Incompatible);
+		assert_eq!(jei.side, Some(Side::Client));
+	}
+}
@@ -0,0 +1,214 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+use version_compare::Version;
+
+/// A range of versions that a dependency accepts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionRange {
+	/// Any version
+	Any,
+	/// Version predicates from a fabric.mod.json. The range matches if any of them match
+	Fabric(Vec<String>),
+	/// A Maven version range from a mods.toml, like `[1.0,2.0)`
+	Maven(String),
+}
+
+impl VersionRange {
+	/// Checks if a version is in this range. Returns None if the versions can't be compared
+	pub fn matches(&self, version: &str) -> Option<bool> {
+		match self {
+			Self::Any => Some(true),
+			Self::Fabric(predicates) => {
+				let mut is_unknown = false;
+				for predicate in predicates {
+					match matches_fabric_predicate(predicate, version) {
+						Some(true) => return Some(true),
+						Some(false) => {}
+						None => is_unknown = true,
+					}
+				}
+
+				if is_unknown { None } else { Some(false) }
+			}
+			Self::Maven(range) => matches_maven_range(range, version),
+		}
+	}
+}
+
+impl Display for VersionRange {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Any => write!(f, "*"),
+			Self::Fabric(predicates) => write!(f, "{}", predicates.join(" or ")),
+			Self::Maven(range) => write!(f, "{range}"),
+		}
+	}
+}
+
+/// Compares two versions, ignoring build metadata like `+1.20.1`
+fn compare(left: &str, right: &str) -> Option<Ordering> {
+	let left = left.split('+').next().unwrap_or(left);
+	let right = right.split('+').next().unwrap_or(right);
+	Version::from(left)?.compare(Version::from(right)?).ord()
+}
+
+/// Checks a version against a Fabric predicate, like `>=1.2.0 <2.0.0`, `~1.20.1`, or `1.20.x`
+fn matches_fabric_predicate(predicate: &str, version: &str) -> Option<bool> {
+	// Space separated parts must all match
+	let mut out = true;
+	for part in predicate.split_whitespace() {
+		out &= matches_fabric_part(part, version)?;
+	}
+
+	Some(out)
+}
+
+/// Checks a version against a single part of a Fabric predicate
+fn matches_fabric_part(part: &str, version: &str) -> Option<bool> {
+	if part == "*" {
+		return Some(true);
+	}
+
+	let out = if let Some(other) = part.strip_prefix(">=") {
+		compare(version, other)? != Ordering::Less
+	} else if let Some(other) = part.strip_prefix("<=") {
+		compare(version, other)? != Ordering::Greater
+	} else if let Some(other) = part.strip_prefix('>') {
+		compare(version, other)? == Ordering::Greater
+	} else if let Some(other) = part.strip_prefix('<') {
+		compare(version, other)? == Ordering::Less
+	} else if let Some(other) = part.strip_prefix('^') {
+		compare(version, other)? != Ordering::Less
+			&& compare(version, &bump_version(other, 0))? == Ordering::Less
+	} else if let Some(other) = part.strip_prefix('~') {
+		compare(version, other)? != Ordering::Less
+			&& compare(version, &bump_version(other, 1))? == Ordering::Less
+	} else {
+		let other = part.strip_prefix('=').unwrap_or(part);
+		if let Some(prefix) = get_wildcard_prefix(other) {
+			version == prefix || version.starts_with(&format!("{prefix}."))
+		} else {
+			compare(version, other)? == Ordering::Equal
+		}
+	};
+
+	Some(out)
+}
+
+/// Gets the part of a wildcard version like `1.20.x` before the wildcard
+fn get_wildcard_prefix(version: &str) -> Option<&str> {
+	let index = version
+		.split('.')
+		.position(|x| matches!(x, "x" | "X" | "*"))?;
+	let prefix_length = version
+		.split('.')
+		.take(index)
+		.map(|x| x.len() + 1)
+		.sum::<usize>();
+
+	Some(&version[..prefix_length.saturating_sub(1)])
+}
+
+/// Increments one component of a version and removes the ones after it, so that `1.20.1`
+/// bumped at index 1 becomes `1.21`
+fn bump_version(version: &str, index: usize) -> String {
+	let version = version.split(['+', '-']).next().unwrap_or(version);
+	let mut components: Vec<u64> = version.split('.').map_while(|x| x.parse().ok()).collect();
+	components.resize(components.len().max(index + 1), 0);
+	components.truncate(index + 1);
+	components[index] += 1;
+
+	components
+		.iter()
+		.map(ToString::to_string)
+		.collect::<Vec<_>>()
+		.join(".")
+}
+
+/// Checks a version against a Maven version range, like `[1.0,2.0)` or `[1,2),[3,)`
+fn matches_maven_range(range: &str, version: &str) -> Option<bool> {
+	let range = range.trim();
+	// A bare version is only a recommendation, so it matches anything
+	if !range.starts_with(['[', '(']) {
+		return Some(true);
+	}
+
+	// Multiple ranges match if any of them do
+	let mut rest = range;
+	let mut out = false;
+	while !rest.is_empty() {
+		let end = rest.find([']', ')'])?;
+		out |= matches_maven_part(&rest[..=end], version)?;
+		rest = rest[end + 1..].trim_start_matches(',').trim();
+	}
+
+	Some(out)
+}
+
+/// Checks a version against a single Maven range
+fn matches_maven_part(part: &str, version: &str) -> Option<bool> {
+	if part.len() < 2 {
+		return None;
+	}
+	let includes_start = part.starts_with('[');
+	let includes_end = part.ends_with(']');
+	let inner = &part[1..part.len() - 1];
+
+	let Some((start, end)) = inner.split_once(',') else {
+		// An exact version
+		return Some(compare(version, inner.trim())? == Ordering::Equal);
+	};
+
+	let (start, end) = (start.trim(), end.trim());
+	if !start.is_empty() {
+		let ordering = compare(version, start)?;
+		if ordering == Ordering::Less || (ordering == Ordering::Equal && !includes_start) {
+			return Some(false);
+		}
+	}
+	if !end.is_empty() {
+		let ordering = compare(version, end)?;
+		if ordering == Ordering::Greater || (ordering == Ordering::Equal && !includes_end) {
+			return Some(false);
+		}
+	}
+
+	Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_fabric_ranges() {
+		let range = |x: &str| VersionRange::Fabric(vec![x.into()]);
+		assert_eq!(range(">=0.15.0").matches("0.16.2"), Some(true));
+		assert_eq!(range(">=0.15.0").matches("0.14.9"), Some(false));
+		assert_eq!(range("~1.20.1").matches("1.20.4"), Some(true));
+		assert_eq!(range("~1.20.1").matches("1.21"), Some(false));
+		assert_eq!(range("^1.2.0").matches("1.9.0+build.5"), Some(true));
+		assert_eq!(range("^1.2.0").matches("2.0.0"), Some(false));
+		assert_eq!(range("1.20.x").matches("1.20.6"), Some(true));
+		assert_eq!(range("1.20.x").matches("1.21"), Some(false));
+		assert_eq!(range(">=1.20 <1.21").matches("1.20.2"), Some(true));
+		assert_eq!(
+			VersionRange::Fabric(vec!["1.19.4".into(), "1.20.1".into()]).matches("1.20.1"),
+			Some(true)
+		);
+	}
+
+	#[test]
+	fn test_maven_ranges() {
+		let range = |x: &str| VersionRange::Maven(x.into());
+		assert_eq!(range("[47,)").matches("47.2.0"), Some(true));
+		assert_eq!(range("[1.20.1,1.21)").matches("1.21"), Some(false));
+		assert_eq!(range("[1.20.1,1.21)").matches("1.20.1"), Some(true));
+		assert_eq!(range("(,2.0]").matches("2.0"), Some(true));
+		assert_eq!(range("[1,2),[3,)").matches("2.5"), Some(false));
+		assert_eq!(range("[1,2),[3,)").matches("3.1"), Some(true));
+		assert_eq!(range("[1.0]").matches("1.0"), Some(true));
+		assert_eq!(range("1.0").matches("0.5"), Some(true));
+	}
+}
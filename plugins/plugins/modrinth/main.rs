@@ -3,22 +3,28 @@ use std::{
 	fs::File,
 	io::BufReader,
 	path::{Path, PathBuf},
-	sync::Arc,
-	time::SystemTime,
+	time::{Instant, SystemTime},
 };
 
 use anyhow::{Context, bail};
 use nitro_core::io::{files::create_leading_dirs, json_from_file, json_to_file};
 use nitro_instance::addon::modpack::{
 	Modpack,
-	mrpack::{ModrinthIndex, ModrinthPack},
+	mrpack::{ModrinthIndex, ModrinthPack, ModrinthPackFile},
 };
 use nitro_net::{
-	download::Client,
-	modrinth::{self, Member, Project, SearchResults, Version},
+	download::{self, Client},
+	modrinth::{self, GalleryEntry, Member, Project, SearchResults, Version},
+	throttle::AdaptiveBatcher,
+};
+use nitro_pkg::details::{
+	AuthorProfile, ExternalLink, GalleryImage, LinkKind, PackageAuthor, PackageDetails,
 };
 use nitro_pkg::{PackageSearchResults, PkgRequest, PkgRequestSource};
-use nitro_pkg_gen::{modrinth::get_preview, relation_substitution::RelationSubNone};
+use nitro_pkg_gen::{
+	modrinth::{cleanup_version_name, get_preview},
+	relation_substitution::RelationSubNone,
+};
 use nitro_plugin::{
 	api::{executable::ExecutablePlugin, utils::PackageSearchCache},
 	hook::hooks::{CustomRepoQueryResult, ImportInstanceResult, InstallModpackResult},
@@ -26,14 +32,24 @@ use nitro_plugin::{
 use nitro_shared::{
 	Side,
 	io::update_link,
+	loaders::Loader,
 	output::{MessageContents, NitroOutput},
 	versions::{MinecraftVersionDeser, VersionPattern},
 };
 use nitrolaunch::config_crate::instance::InstanceConfig;
+use nitrolaunch::config_crate::package::{FullPackageConfig, PackageConfigDeser};
+use nitrolaunch::instance::parse_loader_config;
+use nitrolaunch::instance::publish::{MrpackSettings, MrpackSource, build_mrpack};
+use nitrolaunch::instance::watch::CONTENT_DIRS;
 use serde::{Deserialize, Serialize};
-use tokio::sync::Mutex;
 
 const PROJECT_CACHE_TIME_SECS: u64 = 3600;
+/// Base URL used to look up the rate limit for the Modrinth API
+const MODRINTH_API_URL: &str = "https://api.modrinth.com";
+/// Smallest number of projects or versions to request at once
+const BATCH_MIN: usize = 25;
+/// Largest number of projects or versions to request at once, as more will make the URL too long
+const BATCH_MAX: usize = 215;
 
 fn main() -> anyhow::Result<()> {
 	let mut plugin = ExecutablePlugin::from_manifest_file("modrinth", include_str!("plugin.json"))?;
@@ -99,23 +115,28 @@ fn main() -> anyhow::Result<()> {
 
 		let data_dir = ctx.get_data_dir()?;
 
-		let (projects, previews, total_results) = runtime.block_on(async move {
+		let (projects, previews, total_results, stale) = runtime.block_on(async move {
 			let cache_path = data_dir.join("internal/modrinth/search_cache.json");
 			create_leading_dirs(&cache_path)?;
 			let mut search_cache =
 				PackageSearchCache::open(cache_path, 250).context("Failed to open search cache")?;
 
-			let results =
-				if let Some(results) = search_cache.check::<SearchResults>(&arg.parameters) {
-					results
-				} else {
-					let results = modrinth::search_projects(arg.parameters.clone(), &client)
-						.await
-						.context("Failed to search projects from the API")?;
+			let (results, stale) = if let Some(results) =
+				search_cache.check_allow_stale::<SearchResults>(&arg.parameters)
+			{
+				results
+			} else if download::is_offline()
+				&& let Some(results) = search_cache.check_stale::<SearchResults>(&arg.parameters)
+			{
+				(results, false)
+			} else {
+				let results = modrinth::search_projects(arg.parameters.clone(), &client)
+					.await
+					.context("Failed to search projects from the API")?;
 
-					let _ = search_cache.write(&arg.parameters, results.clone());
-					results
-				};
+				let _ = search_cache.write(&arg.parameters, results.clone());
+				(results, false)
+			};
 
 			let mut previews = HashMap::with_capacity(results.hits.len());
 			let mut projects = Vec::with_capacity(results.hits.len());
@@ -146,13 +167,15 @@ fn main() -> anyhow::Result<()> {
 				}
 			}
 
-			Ok::<_, anyhow::Error>((projects, previews, results.total_hits))
+			Ok::<_, anyhow::Error>((projects, previews, results.total_hits, stale))
 		})?;
 
 		Ok(PackageSearchResults {
 			results: projects,
 			total_results,
 			previews,
+			stale,
+			next_cursor: None,
 		})
 	})?;
 
@@ -175,6 +198,35 @@ fn main() -> anyhow::Result<()> {
 		Ok(())
 	})?;
 
+	plugin.get_custom_package_details(|ctx, arg| {
+		if arg.repository != "modrinth" {
+			return Ok(None);
+		}
+
+		let storage_dirs = StorageDirs::new(&ctx.get_data_dir()?);
+
+		let runtime = tokio::runtime::Runtime::new()?;
+		let client = Client::new();
+
+		let project_info =
+			runtime.block_on(get_cached_project(&arg.package, &storage_dirs, &client))?;
+
+		Ok(project_info.map(get_project_details))
+	})?;
+
+	plugin.get_custom_package_author(|_, arg| {
+		if arg.repository != "modrinth" {
+			return Ok(None);
+		}
+
+		let runtime = tokio::runtime::Runtime::new()?;
+		let client = Client::new();
+
+		runtime
+			.block_on(get_author_profile(&arg.author, &client))
+			.map(Some)
+	})?;
+
 	plugin.install_modpack(|mut ctx, arg| {
 		let mut old_pack = if let Some(old_path) = arg.old_path {
 			let file = BufReader::new(File::open(old_path).context("Failed to open old modpack")?);
@@ -235,6 +287,72 @@ fn main() -> anyhow::Result<()> {
 		})
 	})?;
 
+	plugin.export_instance(|_, arg| {
+		if arg.format != "mrpack" {
+			bail!("Invalid format");
+		}
+
+		let inst_dir = PathBuf::from(arg.inst_dir);
+		let side = arg.config.side.unwrap_or(Side::Client);
+
+		// Every addon file is included, since the pack needs to reproduce the whole instance
+		let mut files = Vec::new();
+		let mut content_dirs: Vec<_> = CONTENT_DIRS.iter().map(|x| inst_dir.join(x)).collect();
+		if side == Side::Server {
+			content_dirs.push(inst_dir.join("plugins"));
+		}
+		for dir in content_dirs {
+			if !dir.exists() {
+				continue;
+			}
+			for entry in dir.read_dir().context("Failed to read addon directory")? {
+				let path = entry?.path();
+				if path.is_file() {
+					files.push((path, None));
+				}
+			}
+		}
+
+		let loader = arg
+			.config
+			.loader
+			.as_deref()
+			.map(|x| parse_loader_config(x).0)
+			.unwrap_or(Loader::Vanilla);
+		let source = MrpackSource {
+			inst_dir: &inst_dir,
+			files,
+			minecraft_version: arg.minecraft_version,
+			loader,
+			loader_version: arg.loader_version,
+		};
+
+		let overrides = if inst_dir.join("config").exists() {
+			vec!["config".into()]
+		} else {
+			Vec::new()
+		};
+		let settings = MrpackSettings {
+			name: arg.config.name.clone().unwrap_or(arg.id),
+			version: "1.0.0".into(),
+			summary: None,
+			overrides,
+		};
+
+		let runtime = tokio::runtime::Runtime::new()?;
+		let client = Client::new();
+		runtime
+			.block_on(build_mrpack(
+				source,
+				&settings,
+				Path::new(&arg.result_path),
+				&client,
+			))
+			.context("Failed to build pack")?;
+
+		Ok(())
+	})?;
+
 	plugin.import_instance(|mut ctx, arg| {
 		if arg.format != "mrpack" {
 			bail!("Invalid format");
@@ -252,6 +370,14 @@ fn main() -> anyhow::Result<()> {
 		let file = File::open(source_path).context("Failed to open pack file")?;
 		let mut modpack = ModrinthPack::from_stream(file).context("Failed to open mrpack")?;
 
+		// Files from Modrinth become packages, so only the rest are installed directly
+		let files = std::mem::take(&mut modpack.index_mut().files);
+		let (package_files, other_files): (Vec<_>, Vec<_>) = files
+			.into_iter()
+			.filter(|x| x.supports_side(side))
+			.partition(|x| x.get_modrinth_info().0.is_some());
+		modpack.index_mut().files = other_files;
+
 		// Download files
 		let mut process = output.get_process();
 		process.display(MessageContents::StartProcess("Downloading mods".into()));
@@ -278,7 +404,23 @@ fn main() -> anyhow::Result<()> {
 		process.display(MessageContents::Success("Modpack installed".into()));
 		process.finish();
 
-		let config = mrpack_index_to_config(modpack.index(), side);
+		// Look up the versions of the packages so that the instance keeps the versions from the pack
+		let hashes: Vec<_> = package_files
+			.iter()
+			.map(|x| x.hashes.sha512.clone())
+			.collect();
+		let versions = match runtime.block_on(modrinth::get_versions_from_hashes(&hashes, &client))
+		{
+			Ok(versions) => versions,
+			Err(e) => {
+				output.display(MessageContents::Warning(format!(
+					"Failed to look up package versions, so they will not be pinned: {e:?}"
+				)));
+				HashMap::new()
+			}
+		};
+
+		let config = mrpack_index_to_config(modpack.index(), &package_files, &versions, side);
 
 		Ok(ImportInstanceResult {
 			format: arg.format,
@@ -340,8 +482,9 @@ async fn get_cached_project(
 		return Ok(None);
 	}
 
+	// Outdated projects are still used when offline
 	let project_info = if project_path.exists()
-		&& !project_needs_update(&project_path).unwrap_or(true)
+		&& (download::is_offline() || !project_needs_update(&project_path).unwrap_or(true))
 	{
 		// TODO: Add versions to partially loaded project infos
 		json_from_file(&project_path).context("Failed to read project info from file")?
@@ -398,6 +541,104 @@ async fn get_cached_project(
 	Ok(Some(project_info))
 }
 
+/// Gets the details for the page of a Modrinth project
+fn get_project_details(project_info: ProjectInfo) -> PackageDetails {
+	let project = project_info.project;
+
+	let gallery = project
+		.gallery
+		.unwrap_or_default()
+		.into_iter()
+		.map(|entry| match entry {
+			GalleryEntry::Simple(url) => GalleryImage {
+				url,
+				..Default::default()
+			},
+			GalleryEntry::Full(entry) => GalleryImage {
+				url: entry.url,
+				raw_url: Some(entry.raw_url),
+				title: entry.title,
+				description: entry.description,
+				featured: entry.featured,
+			},
+		})
+		.collect();
+
+	let mut links: Vec<_> = [
+		(LinkKind::Source, project.source_url),
+		(LinkKind::Issues, project.issues_url),
+		(LinkKind::Documentation, project.wiki_url),
+		(LinkKind::Community, project.discord_url),
+	]
+	.into_iter()
+	.filter_map(|(kind, url)| {
+		Some(ExternalLink {
+			kind,
+			name: None,
+			url: url?,
+		})
+	})
+	.collect();
+	links.extend(project.donation_urls.into_iter().map(|x| ExternalLink {
+		kind: LinkKind::Donation,
+		name: x.platform,
+		url: x.url,
+	}));
+
+	let mut members = project_info.members;
+	members.sort();
+	let authors = members
+		.into_iter()
+		.map(|x| PackageAuthor {
+			// Projects that were cached before user IDs were stored won't have them
+			id: Some(x.user.id).filter(|x| !x.is_empty()),
+			name: x.user.username,
+			role: x.role,
+			avatar: x.user.avatar_url,
+		})
+		.collect();
+
+	PackageDetails {
+		body: project.body,
+		gallery,
+		links,
+		authors,
+	}
+}
+
+/// Gets the profile of a Modrinth user along with their projects
+async fn get_author_profile(user_id: &str, client: &Client) -> anyhow::Result<AuthorProfile> {
+	let (user, projects) = tokio::join!(
+		modrinth::get_user(user_id, client),
+		modrinth::get_user_projects(user_id, client)
+	);
+	let user = user?;
+	let projects = projects.context("Failed to get projects for user")?;
+
+	let packages = projects
+		.into_iter()
+		.map(|project| {
+			PkgRequest {
+				source: PkgRequestSource::UserRequire,
+				id: project.id.into(),
+				content_version: VersionPattern::Any,
+				repository: Some("modrinth".into()),
+				slug: Some(project.slug),
+			}
+			.to_string()
+		})
+		.collect();
+
+	Ok(AuthorProfile {
+		url: Some(format!("https://modrinth.com/user/{}", user.username)),
+		id: user.id,
+		name: user.username,
+		bio: user.bio,
+		avatar: user.avatar_url,
+		packages,
+	})
+}
+
 /// Gets a cached Modrinth project and it's versions, but never downloads it
 fn get_known_cached_project(
 	project_id: &str,
@@ -441,10 +682,19 @@ async fn download_multiple_projects(
 		return Ok(Vec::new());
 	}
 
-	// Download the new projects
-	let projects = modrinth::get_multiple_projects(&project_ids, client)
-		.await
-		.context("Failed to download projects")?;
+	// Download the new projects in batches that adapt to how quickly Modrinth is responding
+	let mut batcher = AdaptiveBatcher::new(MODRINTH_API_URL, BATCH_MIN, BATCH_MAX);
+	let mut projects = Vec::with_capacity(project_ids.len());
+	let mut remaining = project_ids.as_slice();
+	while !remaining.is_empty() {
+		let batch = batcher.next_batch(&mut remaining).await;
+		let start = Instant::now();
+		let batch_projects = modrinth::get_multiple_projects(batch, client)
+			.await
+			.context("Failed to download projects")?;
+		batcher.record(start.elapsed());
+		projects.extend(batch_projects);
+	}
 
 	// List of existing and new projects to have new data applied to them
 	let mut project_needed_versions = Vec::new();
@@ -478,65 +728,49 @@ async fn download_multiple_projects(
 		})
 		.collect();
 
-	// Collect Modrinth project versions. We have to batch these into multiple requests because there becomes
-	// just too many parameters for the URL to handle
-	let batch_limit = 215;
-	let version_ids: Vec<_> = project_needed_versions;
-
-	let chunks = version_ids.chunks(batch_limit);
-
-	// Download each chunk
-	let all_versions = Arc::new(Mutex::new(Vec::new()));
-	let mut tasks = tokio::task::JoinSet::new();
-	for chunk in chunks {
-		let chunk = chunk.to_vec();
-		let client = client.clone();
-		let all_versions = all_versions.clone();
-		let task = async move {
-			let versions = modrinth::get_multiple_versions(&chunk, &client)
-				.await
-				.context("Failed to get Modrinth versions")?;
-
-			let mut lock = all_versions.lock().await;
-			lock.extend(versions);
-
-			Ok::<(), anyhow::Error>(())
-		};
-		tasks.spawn(task);
-	}
-
-	// Download teams at the same time
+	// Download teams while the versions are downloading
 	let mut team_ids = Vec::new();
 	for project in &projects {
 		team_ids.push(project.project.team.clone());
 	}
-	let all_teams = Arc::new(Mutex::new(Vec::new()));
-	{
+	let teams_task = {
 		let client = client.clone();
-		let all_teams = all_teams.clone();
-		let task = async move {
-			let teams = modrinth::get_multiple_teams(&team_ids, &client)
+		tokio::spawn(async move {
+			modrinth::get_multiple_teams(&team_ids, &client)
 				.await
-				.context("Failed to get Modrinth teams")?;
-			let mut lock = all_teams.lock().await;
-			*lock = teams;
+				.context("Failed to get Modrinth teams")
+		})
+	};
 
-			Ok::<(), anyhow::Error>(())
-		};
-		tasks.spawn(task);
+	// Collect Modrinth project versions. These are batched into multiple requests because there becomes
+	// just too many parameters for the URL to handle. The batches are downloaded at the same time
+	let mut tasks = tokio::task::JoinSet::new();
+	let mut remaining = project_needed_versions.as_slice();
+	while !remaining.is_empty() {
+		let batch = batcher.next_batch(&mut remaining).await.to_vec();
+		let client = client.clone();
+		tasks.spawn(async move {
+			let start = Instant::now();
+			let versions = modrinth::get_multiple_versions(&batch, &client)
+				.await
+				.context("Failed to get Modrinth versions")?;
+			Ok::<_, anyhow::Error>((versions, start.elapsed()))
+		});
 	}
 
-	// Run the tasks
+	let mut all_versions = Vec::with_capacity(project_needed_versions.len());
 	while let Some(result) = tasks.join_next().await {
-		result?.context("Task failed")?;
+		let (versions, elapsed) = result.context("Task failed")??;
+		batcher.record(elapsed);
+		all_versions.extend(versions);
 	}
-	let all_versions = all_versions.lock().await;
-	let all_teams = all_teams.lock().await;
+
+	let all_teams = teams_task.await.context("Task failed")??;
 
 	// Collect the versions into a HashMap so that we can look them up when ordering them correctly
 	let mut all_versions = all_versions
-		.iter()
-		.map(|x| (x.id.clone(), x.clone()))
+		.into_iter()
+		.map(|x| (x.id.clone(), x))
 		.collect::<HashMap<_, _>>();
 
 	// Create missing placeholder files for projects that weren't in the response
@@ -684,16 +918,42 @@ impl StorageDirs {
 	}
 }
 
-/// Creates InstanceConfig from an mrpack index
-fn mrpack_index_to_config(index: &ModrinthIndex, side: Side) -> InstanceConfig {
-	// Suppress mods that this pack provides
-	let mut suppress = Vec::new();
-	for file in &index.files {
+/// Creates InstanceConfig from an mrpack index and the files in it that are Modrinth packages
+fn mrpack_index_to_config(
+	index: &ModrinthIndex,
+	package_files: &[ModrinthPackFile],
+	versions: &HashMap<String, Version>,
+	side: Side,
+) -> InstanceConfig {
+	let mut packages = Vec::new();
+	let mut seen = HashSet::new();
+	for file in package_files {
 		let (Some(project_id), _) = file.get_modrinth_info() else {
 			continue;
 		};
+		let id = format!("modrinth:{project_id}");
+		if !seen.insert(id.clone()) {
+			continue;
+		}
+
+		let Some(version) = versions.get(&file.hashes.sha512) else {
+			packages.push(PackageConfigDeser::Basic(id.into()));
+			continue;
+		};
 
-		suppress.push(format!("modrinth:{project_id}"));
+		packages.push(PackageConfigDeser::Full(Box::new(FullPackageConfig {
+			id: id.into(),
+			features: Vec::new(),
+			use_default_features: true,
+			permissions: Default::default(),
+			stability: None,
+			worlds: Vec::new(),
+			content_version: Some(cleanup_version_name(&version.version_number)),
+			optional: false,
+			file: None,
+			on_conflict: None,
+			side: None,
+		})));
 	}
 
 	let loader = if let Some(version) = &index.dependencies.forge {
@@ -717,6 +977,7 @@ fn mrpack_index_to_config(index: &ModrinthIndex, side: Side) -> InstanceConfig {
 			index.dependencies.minecraft.clone().into(),
 		)),
 		loader,
+		packages,
 		..Default::default()
 	}
 }
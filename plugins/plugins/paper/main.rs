@@ -13,7 +13,7 @@ use nitro_net::download::Client;
 use nitro_plugin::{api::executable::ExecutablePlugin, hook::hooks::OnInstanceSetupResult};
 use nitro_shared::{
 	Side, UpdateDepth,
-	loaders::Loader,
+	loaders::{Loader, LoaderVersion, list_loader_versions, select_loader_version},
 	output::{MessageContents, NitroOutput, OutputProcess},
 };
 use tokio::runtime::Runtime;
@@ -77,28 +77,35 @@ fn main() -> anyhow::Result<()> {
 
 		// Get the build numbers (actual project versions)
 		let builds_path = get_stored_builds_path(&paths, mode, &arg.version_info.version);
-		let build_nums = if builds_path.exists() && arg.update_depth == UpdateDepth::Shallow {
-			json_from_file(&builds_path).context("Failed to read builds from file")?
-		} else {
-			process.display(MessageContents::StartProcess(
-				"Getting build list".to_string(),
-			));
-			runtime
-				.block_on(paper::get_builds(mode, &arg.version_info.version, &client))
-				.with_context(|| {
-					format!("Failed to get list of build numbers for {mode} project")
-				})?
-		};
+		let builds: Vec<LoaderVersion> =
+			if builds_path.exists() && arg.update_depth == UpdateDepth::Shallow {
+				json_from_file(&builds_path).context("Failed to read builds from file")?
+			} else {
+				process.display(MessageContents::StartProcess(
+					"Getting build list".to_string(),
+				));
+				runtime
+					.block_on(paper::get_build_versions(
+						mode,
+						&arg.version_info.version,
+						&client,
+					))
+					.with_context(|| {
+						format!("Failed to get list of build numbers for {mode} project")
+					})?
+			};
 		let _ = create_leading_dirs(&builds_path);
-		json_to_file(builds_path, &build_nums).context("Failed to write builds to file")?;
+		json_to_file(builds_path, &builds).context("Failed to write builds to file")?;
 
-		let build_nums_strings: Vec<_> = build_nums.iter().map(|x| x.to_string()).collect();
-
-		let desired_version = arg
-			.desired_loader_version
-			.get_match(&build_nums_strings)
-			.with_context(|| format!("Failed to find the given {mode} version"))?;
+		let desired_version = select_loader_version(&arg.desired_loader_version, &builds)
+			.with_context(|| {
+				format!(
+					"Failed to find a {mode} build matching {}",
+					arg.desired_loader_version
+				)
+			})?;
 		let desired_build_num: u16 = desired_version
+			.version
 			.parse()
 			.context("The desired version must be a an unsigned integer")?;
 
@@ -172,6 +179,27 @@ fn main() -> anyhow::Result<()> {
 		})
 	})?;
 
+	plugin.get_loader_versions(|_, arg| {
+		let mode = match arg.loader {
+			Loader::Paper => paper::Mode::Paper,
+			Loader::Folia => paper::Mode::Folia,
+			_ => return Ok(Vec::new()),
+		};
+
+		let client = nitro_net::download::Client::new();
+		let runtime = tokio::runtime::Runtime::new()?;
+
+		let builds = runtime
+			.block_on(paper::get_build_versions(
+				mode,
+				&arg.minecraft_version,
+				&client,
+			))
+			.with_context(|| format!("Failed to get list of {mode} builds"))?;
+
+		Ok(list_loader_versions(&builds, arg.channel))
+	})?;
+
 	Ok(())
 }
 
@@ -182,9 +210,10 @@ fn get_stored_versions_path(paths: &Paths, mode: paper::Mode) -> PathBuf {
 }
 
 fn get_stored_builds_path(paths: &Paths, mode: paper::Mode, version: &str) -> PathBuf {
-	paths
-		.internal
-		.join(format!("paper/{}/{version}_builds.json", mode.to_str()))
+	paths.internal.join(format!(
+		"paper/{}/{version}_build_channels.json",
+		mode.to_str()
+	))
 }
 
 fn get_stored_build_info_path(
@@ -0,0 +1,413 @@
+use std::{
+	collections::HashMap,
+	fs::File,
+	io::BufReader,
+	path::{Path, PathBuf},
+};
+
+use anyhow::{Context, bail};
+use nitro_config::{
+	instance::{InstanceConfig, LaunchConfig},
+	package::{FullPackageConfig, PackageConfigDeser},
+};
+use nitro_plugin::{
+	api::wasm::{WASMPlugin, sys::copy_file},
+	hook::hooks::ImportInstanceResult,
+	nitro_wasm_plugin,
+};
+use nitro_shared::{
+	Side, java_args::MemoryArg, versions::MinecraftVersionDeser, versions::parse_versioned_string,
+};
+use serde::{Deserialize, Serialize};
+
+nitro_wasm_plugin!(main, "server_bundle");
+
+/// Filename of the bundle manifest
+const MANIFEST_FILE: &str = "server_bundle.json";
+/// Filename of the server JAR in both instances and bundles
+const SERVER_JAR: &str = "server.jar";
+/// Files that are generated for the bundle and shouldn't be imported back into an instance
+const BUNDLE_FILES: [&str; 4] = [MANIFEST_FILE, SERVER_JAR, "start.sh", "start.bat"];
+/// Directories in the instance that don't belong in a bundle
+const EXCLUDED_DIRS: [&str; 2] = ["logs", "crash-reports"];
+/// The current version of the manifest format
+const FORMAT_VERSION: u32 = 1;
+
+fn main(plugin: &mut WASMPlugin) -> anyhow::Result<()> {
+	plugin.export_instance(|arg| {
+		if arg.config.side != Some(Side::Server) {
+			bail!("Only server instances can be exported as server bundles");
+		}
+
+		let inst_dir = PathBuf::from(arg.inst_dir);
+		let target_dir = PathBuf::from(arg.result_path);
+
+		if !inst_dir.join(SERVER_JAR).exists() {
+			bail!("The server JAR is missing. Launch the instance once before exporting it.");
+		}
+
+		if target_dir.exists() {
+			if !target_dir.is_dir() {
+				bail!("Server bundles must be exported to a folder");
+			}
+			if target_dir.read_dir()?.next().is_some() {
+				bail!("The folder to export the server bundle to must be empty");
+			}
+		}
+		std::fs::create_dir_all(&target_dir).context("Failed to create bundle folder")?;
+
+		// Copy the server files. The server JAR is usually a hardlink into shared storage,
+		// so copying it gives the bundle its own version
+		copy_dir(&inst_dir, &target_dir, &|path| {
+			should_export_file(path.strip_prefix(&inst_dir).unwrap_or(path))
+		})
+		.context("Failed to copy server files")?;
+
+		// Nitrolaunch normally accepts the EULA at launch, so make sure the bundle has it too
+		let eula_path = target_dir.join("eula.txt");
+		if !eula_path.exists() {
+			std::fs::write(
+				eula_path,
+				"# By changing the setting below to TRUE you are indicating your agreement to the EULA (https://aka.ms/MinecraftEULA).\neula=true\n",
+			)
+			.context("Failed to write EULA")?;
+		}
+
+		// Start scripts
+		let args = get_launch_args(&arg.config.launch);
+		std::fs::write(target_dir.join("start.sh"), create_unix_script(&args))
+			.context("Failed to write start script")?;
+		std::fs::write(target_dir.join("start.bat"), create_windows_script(&args))
+			.context("Failed to write start script")?;
+
+		// Manifest
+		let lock = read_instance_lock(&inst_dir).context("Failed to read instance lockfile")?;
+		let loader = arg
+			.config
+			.loader
+			.as_deref()
+			.map(|x| parse_versioned_string(x).0.to_string());
+		let manifest = Manifest {
+			format_version: FORMAT_VERSION,
+			id: arg.id,
+			name: arg.config.name,
+			minecraft_version: arg.minecraft_version,
+			loader,
+			loader_version: arg.loader_version,
+			packages: get_pinned_packages(lock, &inst_dir),
+			launch: arg.config.launch,
+		};
+
+		let manifest_file =
+			File::create(target_dir.join(MANIFEST_FILE)).context("Failed to create manifest")?;
+		serde_json::to_writer_pretty(manifest_file, &manifest)
+			.context("Failed to write manifest")?;
+
+		Ok(())
+	})?;
+
+	plugin.import_instance(|arg| {
+		let source_dir = PathBuf::from(arg.source_path);
+		let target_dir = PathBuf::from(arg.result_path);
+
+		if !source_dir.is_dir() {
+			bail!("Server bundles must be imported from a folder");
+		}
+
+		let manifest_file = File::open(source_dir.join(MANIFEST_FILE))
+			.context("Manifest is missing in server bundle")?;
+		let manifest: Manifest = serde_json::from_reader(BufReader::new(manifest_file))
+			.context("Failed to deserialize server bundle manifest")?;
+
+		if manifest.format_version > FORMAT_VERSION {
+			bail!("This server bundle was created by a newer version of the plugin");
+		}
+
+		// Package files are installed again by Nitrolaunch, so they don't need to be copied
+		let package_files: Vec<_> = manifest
+			.packages
+			.iter()
+			.flat_map(|x| x.files.iter().map(|x| source_dir.join(x)))
+			.collect();
+
+		copy_dir(&source_dir, &target_dir, &|path| {
+			let relative = path.strip_prefix(&source_dir).unwrap_or(path);
+			!BUNDLE_FILES.iter().any(|x| relative == Path::new(x))
+				&& !package_files.iter().any(|x| x == path)
+		})
+		.context("Failed to copy server files")?;
+
+		let loader = manifest.loader.map(|loader| match manifest.loader_version {
+			Some(version) => format!("{loader}@{version}"),
+			None => loader,
+		});
+
+		let packages = manifest
+			.packages
+			.into_iter()
+			.map(|x| {
+				PackageConfigDeser::Full(Box::new(FullPackageConfig {
+					id: x.id.into(),
+					features: Vec::new(),
+					use_default_features: true,
+					permissions: Default::default(),
+					stability: None,
+					worlds: Vec::new(),
+					content_version: x.content_version,
+					optional: false,
+					file: None,
+					on_conflict: None,
+					side: None,
+				}))
+			})
+			.collect();
+
+		let config = InstanceConfig {
+			side: Some(Side::Server),
+			name: manifest.name,
+			version: Some(MinecraftVersionDeser::Version(
+				manifest.minecraft_version.into(),
+			)),
+			loader,
+			launch: manifest.launch,
+			packages,
+			..Default::default()
+		};
+
+		Ok(ImportInstanceResult {
+			format: arg.format,
+			config,
+		})
+	})?;
+
+	Ok(())
+}
+
+/// Recursively copies a directory, only including files that pass the filter
+fn copy_dir(src: &Path, dest: &Path, filter: &impl Fn(&Path) -> bool) -> anyhow::Result<()> {
+	std::fs::create_dir_all(dest)?;
+
+	for item in src.read_dir().context("Failed to read directory")? {
+		let item = item?;
+		let path = item.path();
+		if !filter(&path) {
+			continue;
+		}
+
+		let dest = dest.join(item.file_name());
+		if item.file_type()?.is_dir() {
+			copy_dir(&path, &dest, filter)?;
+		} else {
+			copy_file(&path, &dest)
+				.with_context(|| format!("Failed to copy file {}", path.display()))?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Checks if a file, relative to the instance directory, should be included in the bundle
+fn should_export_file(path: &Path) -> bool {
+	if EXCLUDED_DIRS.iter().any(|x| path == Path::new(x)) {
+		return false;
+	}
+
+	if let Some(file_name) = path.file_name() {
+		let file_name = file_name.to_string_lossy();
+		if file_name.starts_with("nitro_") {
+			return false;
+		}
+	}
+
+	true
+}
+
+/// Gets the arguments for running the server, except for the Java command
+fn get_launch_args(launch: &LaunchConfig) -> Vec<String> {
+	let mut out = Vec::new();
+
+	let (min, max) = launch.memory.clone().to_min_max();
+	if let Some(min) = min {
+		out.push(MemoryArg::Min.to_string(&min));
+	}
+	if let Some(max) = max {
+		out.push(MemoryArg::Max.to_string(&max));
+	}
+
+	out.extend(
+		launch
+			.args
+			.jvm
+			.parse()
+			.into_iter()
+			.filter(|x| !x.is_empty()),
+	);
+	out.push("-jar".into());
+	out.push(SERVER_JAR.into());
+	out.push("nogui".into());
+	out.extend(
+		launch
+			.args
+			.game
+			.parse()
+			.into_iter()
+			.filter(|x| !x.is_empty()),
+	);
+
+	out
+}
+
+/// Creates the start script for Linux and macOS
+fn create_unix_script(args: &[String]) -> String {
+	let args: Vec<_> = args.iter().map(|x| quote_unix_arg(x)).collect();
+	format!(
+		"#!/bin/sh\n# Starts the server. Set JAVA to use a specific Java installation\ncd \"$(dirname \"$0\")\"\nexec \"${{JAVA:-java}}\" {} \"$@\"\n",
+		args.join(" ")
+	)
+}
+
+/// Creates the start script for Windows
+fn create_windows_script(args: &[String]) -> String {
+	let args: Vec<_> = args.iter().map(|x| quote_windows_arg(x)).collect();
+	format!(
+		"@echo off\r\nrem Starts the server. Set JAVA to use a specific Java installation\r\ncd /d \"%~dp0\"\r\nif not defined JAVA set JAVA=java\r\n\"%JAVA%\" {} %*\r\npause\r\n",
+		args.join(" ")
+	)
+}
+
+/// Quotes an argument for a POSIX shell if it contains special characters
+fn quote_unix_arg(arg: &str) -> String {
+	if !arg.is_empty()
+		&& arg
+			.chars()
+			.all(|x| x.is_ascii_alphanumeric() || "-_=.,:/@+%".contains(x))
+	{
+		arg.to_string()
+	} else {
+		format!("'{}'", arg.replace('\'', "'\\''"))
+	}
+}
+
+/// Quotes an argument for a Windows batch file if it contains special characters
+fn quote_windows_arg(arg: &str) -> String {
+	let arg = arg.replace('%', "%%");
+	if !arg.is_empty()
+		&& arg
+			.chars()
+			.all(|x| x.is_ascii_alphanumeric() || "-_=.,:/\\@+%".contains(x))
+	{
+		arg
+	} else {
+		format!("\"{}\"", arg.replace('"', "\"\""))
+	}
+}
+
+/// Reads the parts of the instance lockfile that are needed for the manifest
+fn read_instance_lock(inst_dir: &Path) -> anyhow::Result<InstanceLock> {
+	let path = inst_dir.join("nitro_lock.json");
+	if !path.exists() {
+		return Ok(InstanceLock::default());
+	}
+
+	let file = BufReader::new(File::open(path)?);
+	Ok(serde_json::from_reader(file)?)
+}
+
+/// Gets the installed packages with their exact content versions and files
+fn get_pinned_packages(lock: InstanceLock, inst_dir: &Path) -> Vec<BundlePackage> {
+	let mut out: Vec<_> = lock
+		.packages
+		.into_iter()
+		.map(|(id, package)| {
+			let files = lock
+				.addons
+				.iter()
+				.filter(|x| x.package.as_ref() == Some(&id))
+				.flat_map(|x| &x.files)
+				.filter_map(|x| {
+					let path = Path::new(x);
+					let path = path.strip_prefix(inst_dir).unwrap_or(path);
+					// Files outside of the instance aren't part of the bundle
+					if path.is_absolute() {
+						None
+					} else {
+						Some(path.to_string_lossy().replace('\\', "/"))
+					}
+				})
+				.collect();
+
+			BundlePackage {
+				id,
+				content_version: package.content_version,
+				files,
+			}
+		})
+		.collect();
+	out.sort_by(|a, b| a.id.cmp(&b.id));
+
+	out
+}
+
+/// Manifest describing what a server bundle contains
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+	/// Version of the manifest format
+	format_version: u32,
+	/// The ID of the instance the bundle was exported from
+	id: String,
+	/// The display name of the instance
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	name: Option<String>,
+	/// The exact Minecraft version of the server
+	minecraft_version: String,
+	/// The loader of the server
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	loader: Option<String>,
+	/// The exact loader version of the server
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	loader_version: Option<String>,
+	/// Packages installed on the server, pinned to their installed versions
+	#[serde(default)]
+	packages: Vec<BundlePackage>,
+	/// Launch settings of the instance
+	#[serde(default)]
+	launch: LaunchConfig,
+}
+
+/// A package in a server bundle
+#[derive(Serialize, Deserialize)]
+struct BundlePackage {
+	/// The ID of the package
+	id: String,
+	/// The installed content version of the package
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	content_version: Option<String>,
+	/// Files in the bundle that were installed by this package
+	#[serde(default)]
+	files: Vec<String>,
+}
+
+/// Parts of the instance lockfile used by the bundle
+#[derive(Deserialize, Default)]
+struct InstanceLock {
+	#[serde(default)]
+	packages: HashMap<String, LockPackage>,
+	#[serde(default)]
+	addons: Vec<LockAddon>,
+}
+
+#[derive(Deserialize)]
+struct LockPackage {
+	content_version: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LockAddon {
+	#[serde(default)]
+	package: Option<String>,
+	files: Vec<String>,
+}
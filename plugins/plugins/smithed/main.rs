@@ -1,27 +1,36 @@
 use std::{
 	collections::{HashMap, HashSet},
 	path::{Path, PathBuf},
-	time::SystemTime,
+	time::{Instant, SystemTime},
 };
 
 use anyhow::{Context, bail};
 use nitro_core::io::{files::create_leading_dirs, json_from_file, json_to_file};
+use nitro_instance::lock::InstanceLockfile;
 use nitro_net::{
 	download::{self, Client},
-	smithed::{self, Pack, PackMeta, PackSearchResult},
+	smithed::{self, Pack, PackMeta, PackSearchResult, PackVersion},
+	throttle::AdaptiveBatcher,
 };
 use nitro_pkg::{PackageSearchResults, PkgRequest, PkgRequestSource};
 use nitro_pkg_gen::relation_substitution::{
 	PackageAndVersion, RelationSubFunction, RelationSubNone,
 };
 use nitro_plugin::{
-	api::executable::ExecutablePlugin, api::utils::PackageSearchCache,
-	hook::hooks::CustomRepoQueryResult,
+	api::executable::ExecutablePlugin,
+	api::utils::PackageSearchCache,
+	hook::hooks::{CustomRepoQueryResult, InstanceTile, InstanceTileSize},
 };
 use nitro_shared::versions::VersionPattern;
 use serde::{Deserialize, Serialize};
 
 const PROJECT_CACHE_TIME_SECS: u64 = 3600;
+/// Base URL used to look up the rate limit for the Smithed API
+const SMITHED_API_URL: &str = "https://api.smithed.dev";
+/// Smallest number of packages to query at once
+const BATCH_MIN: usize = 4;
+/// Largest number of packages to query at once
+const BATCH_MAX: usize = 32;
 static SUPPORTED_VERSIONS_FILENAME: &str = "supported_versions.json";
 
 fn main() -> anyhow::Result<()> {
@@ -51,16 +60,26 @@ fn main() -> anyhow::Result<()> {
 		let data_dir = ctx.get_data_dir()?;
 
 		runtime.block_on(async move {
-			let mut tasks = tokio::task::JoinSet::new();
-			for package in arg.packages {
-				let client = client.clone();
-				let data_dir = data_dir.clone();
+			// Query packages concurrently in batches that adapt to how quickly Smithed is responding
+			let mut batcher = AdaptiveBatcher::new(SMITHED_API_URL, BATCH_MIN, BATCH_MAX);
+			let mut remaining = arg.packages.as_slice();
+			while !remaining.is_empty() {
+				let batch = batcher.next_batch(&mut remaining).await;
+				let start = Instant::now();
+
+				let mut tasks = tokio::task::JoinSet::new();
+				for package in batch {
+					let package = package.clone();
+					let client = client.clone();
+					let data_dir = data_dir.clone();
 
-				tasks.spawn(async move { query_package(&package, &client, &data_dir).await });
-			}
+					tasks.spawn(async move { query_package(&package, &client, &data_dir).await });
+				}
 
-			while let Some(task) = tasks.join_next().await {
-				let _ = task??;
+				while let Some(task) = tasks.join_next().await {
+					let _ = task??;
+				}
+				batcher.record(start.elapsed());
 			}
 
 			Ok::<(), anyhow::Error>(())
@@ -82,14 +101,14 @@ fn main() -> anyhow::Result<()> {
 		let client = Client::new();
 		let runtime = tokio::runtime::Runtime::new()?;
 
-		let (packs, previews, total_results) = runtime.block_on(async move {
+		let (packs, previews, total_results, stale) = runtime.block_on(async move {
 			let cache_path = smithed_dir.join("search_cache.json");
 			create_leading_dirs(&cache_path)?;
 			let mut search_cache =
 				PackageSearchCache::open(cache_path, 300).context("Failed to open search cache")?;
 
-			let (results, total_results) = if let Some(entry) =
-				search_cache.check::<(Vec<PackSearchResult>, usize)>(&arg.parameters)
+			let ((results, total_results), stale) = if let Some(entry) =
+				search_cache.check_allow_stale::<(Vec<PackSearchResult>, usize)>(&arg.parameters)
 			{
 				entry
 			} else {
@@ -115,7 +134,7 @@ fn main() -> anyhow::Result<()> {
 
 				let result = tokio::try_join!(search_task, count_task)?;
 				let _ = search_cache.write(&arg.parameters, result.clone());
-				result
+				(result, false)
 			};
 
 			let mut previews = HashMap::with_capacity(results.len());
@@ -148,13 +167,15 @@ fn main() -> anyhow::Result<()> {
 				}
 			}
 
-			Ok::<_, anyhow::Error>((packs, previews, total_results))
+			Ok::<_, anyhow::Error>((packs, previews, total_results, stale))
 		})?;
 
 		Ok(PackageSearchResults {
 			results: packs,
 			total_results,
 			previews,
+			stale,
+			next_cursor: None,
 		})
 	})?;
 
@@ -182,9 +203,128 @@ fn main() -> anyhow::Result<()> {
 		Ok(())
 	})?;
 
+	plugin.add_instance_tiles(|ctx, arg| {
+		let data_dir = ctx.get_data_dir()?;
+		let lock_path = data_dir
+			.join("instances")
+			.join(&arg)
+			.join("nitro_lock.json");
+		if !lock_path.exists() {
+			return Ok(Vec::new());
+		}
+		let lock =
+			InstanceLockfile::open(&lock_path).context("Failed to open instance lockfile")?;
+
+		let runtime = tokio::runtime::Runtime::new()?;
+		let client = Client::new();
+		let storage_dir = data_dir.join("internal/smithed/packs");
+
+		let updates = runtime.block_on(get_pack_updates(&lock, &storage_dir, &client));
+		if updates.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		Ok(vec![InstanceTile {
+			id: "smithed_updates".into(),
+			contents: format_update_card(&updates),
+			size: InstanceTileSize::Small,
+		}])
+	})?;
+
 	Ok(())
 }
 
+/// A Smithed pack on an instance that has a newer version available
+struct PackUpdate {
+	name: String,
+	installed_version: String,
+	latest_version: String,
+}
+
+/// Checks the Smithed packs installed on an instance for newer versions that support its Minecraft version.
+/// Packs that can't be looked up are skipped
+async fn get_pack_updates(
+	lock: &InstanceLockfile,
+	storage_dir: &Path,
+	client: &Client,
+) -> Vec<PackUpdate> {
+	let Some(minecraft_version) = lock.get_minecraft_version() else {
+		return Vec::new();
+	};
+
+	let mut out = Vec::new();
+	for (id, package) in lock.get_packages() {
+		let Some(pack_id) = id.strip_prefix("smithed:") else {
+			continue;
+		};
+		let Some(installed_version) = &package.content_version else {
+			continue;
+		};
+		let Ok(Some(pack_info)) = get_cached_pack(pack_id, false, storage_dir, client).await else {
+			continue;
+		};
+
+		if let Some(latest_version) = find_newer_version(
+			&pack_info.pack.versions,
+			installed_version,
+			minecraft_version,
+		) {
+			out.push(PackUpdate {
+				name: pack_info.pack.display.name,
+				installed_version: installed_version.clone(),
+				latest_version,
+			});
+		}
+	}
+	out.sort_by(|a, b| a.name.cmp(&b.name));
+
+	out
+}
+
+/// Finds the newest version of a pack that supports a Minecraft version, if it is newer than the installed one.
+/// Smithed lists versions from oldest to newest
+fn find_newer_version(
+	versions: &[PackVersion],
+	installed_version: &str,
+	minecraft_version: &str,
+) -> Option<String> {
+	let mut versions = versions
+		.iter()
+		.rev()
+		.filter(|x| x.supports.iter().any(|x| x == minecraft_version));
+	let latest = versions.next()?;
+	if latest.name == installed_version {
+		return None;
+	}
+
+	versions
+		.any(|x| x.name == installed_version)
+		.then(|| latest.name.clone())
+}
+
+fn format_update_card(updates: &[PackUpdate]) -> String {
+	let updates: String = updates
+		.iter()
+		.map(|x| {
+			format!(
+				"<span class=\"cont\">{}: {} &rarr; {}</span>",
+				html_escape(&x.name),
+				html_escape(&x.installed_version),
+				html_escape(&x.latest_version)
+			)
+		})
+		.collect();
+
+	include_str!("update_card.html").replace("{{updates}}", &updates)
+}
+
+fn html_escape(text: &str) -> String {
+	text.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+}
+
 /// Queries for a Smithed package
 async fn query_package(
 	id: &str,
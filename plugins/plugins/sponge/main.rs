@@ -2,7 +2,10 @@ use anyhow::{Context, bail};
 use nitro_core::Paths;
 use nitro_mods::sponge;
 use nitro_plugin::{api::executable::ExecutablePlugin, hook::hooks::OnInstanceSetupResult};
-use nitro_shared::{Side, loaders::Loader};
+use nitro_shared::{
+	Side,
+	loaders::{Loader, list_loader_versions, select_loader_version},
+};
 
 fn main() -> anyhow::Result<()> {
 	let mut plugin = ExecutablePlugin::from_manifest_file("sponge", include_str!("plugin.json"))?;
@@ -40,10 +43,14 @@ fn main() -> anyhow::Result<()> {
 			))
 			.context("Failed to get list of Sponge versions")?;
 
-		let artifact = arg
-			.desired_loader_version
-			.get_match(&artifacts)
-			.context("Sponge version does not exist")?;
+		let artifact = select_loader_version(&arg.desired_loader_version, &artifacts)
+			.map(|x| x.version.clone())
+			.with_context(|| {
+				format!(
+					"Could not find a Sponge version matching {}",
+					arg.desired_loader_version
+				)
+			})?;
 
 		let artifact_info = runtime
 			.block_on(sponge::get_artifact_info(mode, &artifact, &client))
@@ -66,6 +73,7 @@ fn main() -> anyhow::Result<()> {
 		Ok(OnInstanceSetupResult {
 			main_class_override: Some(main_class.into()),
 			jar_path_override: Some(jar_path.to_string_lossy().to_string()),
+			loader_version: Some(artifact),
 			..Default::default()
 		})
 	})?;
@@ -84,7 +92,7 @@ fn main() -> anyhow::Result<()> {
 			.block_on(sponge::get_artifacts(mode, &arg.minecraft_version, &client))
 			.context("Failed to get list of Sponge versions")?;
 
-		Ok(artifacts)
+		Ok(list_loader_versions(&artifacts, arg.channel))
 	})?;
 
 	Ok(())
@@ -93,6 +93,28 @@ fn main(plugin: &mut WASMPlugin) -> anyhow::Result<()> {
 		}])
 	})?;
 
+	plugin.custom_action(|arg| {
+		if arg.id != "get_stats" {
+			return Ok(serde_json::Value::Null);
+		}
+
+		let stats = Stats::open().context("Failed to open stats")?;
+		let out: HashMap<_, _> = stats
+			.instances
+			.into_iter()
+			.map(|(id, stats)| {
+				let summary = StatsSummary {
+					playtime: stats.calculate_playtime(),
+					launches: stats.launches,
+					last_launch: stats.last_launch,
+				};
+				(id, summary)
+			})
+			.collect();
+
+		serde_json::to_value(out).context("Failed to serialize stats")
+	})?;
+
 	Ok(())
 }
 
@@ -234,6 +256,15 @@ impl InstanceStats {
 	}
 }
 
+/// Stats for an instance that are given to the frontend
+#[derive(Serialize)]
+struct StatsSummary {
+	/// The calculated playtime in minutes
+	playtime: u64,
+	launches: u32,
+	last_launch: Option<u64>,
+}
+
 /// Config for the plugin
 #[derive(Serialize, Deserialize, Clone, Default)]
 #[serde(default)]
@@ -0,0 +1,196 @@
+use std::{
+	fs::File,
+	io::{Read, Seek},
+	path::{Component, Path, PathBuf},
+};
+
+use anyhow::{Context, bail};
+use nitro_plugin::{
+	api::wasm::{
+		WASMPlugin,
+		net::download_bytes,
+		sys::{get_arch_string, get_data_dir, get_os_string},
+	},
+	hook::hooks::InstallCustomJavaResult,
+	nitro_wasm_plugin,
+};
+use serde::Deserialize;
+use tar::Archive;
+use zip::ZipArchive;
+
+nitro_wasm_plugin!(main, "temurin");
+
+fn main(plugin: &mut WASMPlugin) -> anyhow::Result<()> {
+	plugin.install_custom_java(|arg| {
+		if arg.kind != "temurin" {
+			return Ok(None);
+		}
+
+		let out_dir = get_data_dir().join("internal/java/temurin");
+		if !out_dir.exists() {
+			let _ = std::fs::create_dir_all(&out_dir);
+		}
+
+		let package =
+			get_latest(&arg.major_version).context("Failed to get the latest Temurin version")?;
+
+		let archive = download_bytes(&package.binary.package.link)
+			.context("Failed to download JRE binaries")?;
+
+		let dir_name = extract_archive(std::io::Cursor::new(archive), &out_dir)
+			.context("Failed to extract Temurin archive")?;
+		let mut extracted_dir = out_dir.join(dir_name);
+
+		// MacOS builds are bundles with the actual installation deeper inside
+		let bundle_home = extracted_dir.join("Contents/Home");
+		if bundle_home.exists() {
+			extracted_dir = bundle_home;
+		}
+
+		let version = package
+			.release_name
+			.strip_prefix("jdk-")
+			.unwrap_or(&package.release_name)
+			.to_string();
+
+		Ok(Some(InstallCustomJavaResult {
+			path: extracted_dir.to_string_lossy().to_string(),
+			version,
+		}))
+	})?;
+
+	Ok(())
+}
+
+/// Gets the newest Temurin package for a major Java version
+fn get_latest(major_version: &str) -> anyhow::Result<PackageFormat> {
+	let url = json_url(major_version)?;
+	let bytes = download_bytes(&url).context("Failed to download manifest of Temurin versions")?;
+	let mut manifest: Vec<PackageFormat> = serde_json::from_slice(&bytes)
+		.context("Failed to deserialize manifest of Temurin versions")?;
+	if manifest.is_empty() {
+		bail!("A valid installation was not found");
+	}
+
+	Ok(manifest.swap_remove(0))
+}
+
+/// Gets the URL to the Adoptium API for a major Java version
+fn json_url(major_version: &str) -> anyhow::Result<String> {
+	Ok(format!(
+		"https://api.adoptium.net/v3/assets/latest/{major_version}/hotspot?image_type=jre&vendor=eclipse&os={}&architecture={}",
+		get_os_arg()?,
+		get_arch_arg()?,
+	))
+}
+
+/// Gets the OS argument for the Adoptium API
+fn get_os_arg() -> anyhow::Result<&'static str> {
+	match get_os_string().as_str() {
+		"linux" => Ok("linux"),
+		"windows" => Ok("windows"),
+		"macos" => Ok("mac"),
+		other => bail!("Temurin is not available for the operating system '{other}'"),
+	}
+}
+
+/// Gets the architecture argument for the Adoptium API
+fn get_arch_arg() -> anyhow::Result<&'static str> {
+	match get_arch_string().as_str() {
+		"x86_64" => Ok("x64"),
+		"x86" => Ok("x86"),
+		"aarch64" => Ok("aarch64"),
+		"arm" => Ok("arm"),
+		"powerpc64" => Ok("ppc64le"),
+		"riscv64" => Ok("riscv64"),
+		other => bail!("Temurin is not available for the architecture '{other}'"),
+	}
+}
+
+/// A single package from the Adoptium API
+#[derive(Deserialize)]
+struct PackageFormat {
+	/// Information about the binary
+	binary: Binary,
+	/// Name of the Java release
+	release_name: String,
+}
+
+/// Binary for an Adoptium package
+#[derive(Deserialize)]
+struct Binary {
+	/// Package field that contains the download link
+	package: BinaryPackage,
+}
+
+/// Package field inside the binary struct
+#[derive(Deserialize)]
+struct BinaryPackage {
+	/// Link to the JRE download
+	link: String,
+}
+
+/// Gets the top-level directory of a path inside an archive
+fn get_root_dir(path: &Path) -> anyhow::Result<String> {
+	let Some(Component::Normal(root)) = path.components().next() else {
+		bail!("Missing archive internal directory");
+	};
+
+	Ok(root.to_string_lossy().to_string())
+}
+
+/// Extracts the JRE archive (either a tar or a zip) and also returns the internal extraction directory name
+fn extract_archive<R: Read + Seek>(reader: R, out_dir: &Path) -> anyhow::Result<String> {
+	let dir_name = if get_os_string() == "windows" {
+		let mut archive = ZipArchive::new(reader).context("Failed to open zip archive")?;
+
+		let dir_name = get_root_dir(&PathBuf::from(
+			archive
+				.file_names()
+				.next()
+				.context("Missing archive internal directory")?,
+		))?;
+
+		archive
+			.extract(out_dir)
+			.context("Failed to extract zip file")?;
+
+		dir_name
+	} else {
+		let mut decoder =
+			libflate::gzip::Decoder::new(reader).context("Failed to decode tar.gz")?;
+		let mut arc = Archive::new(&mut decoder);
+
+		let mut dir_name = None;
+		// Manual extraction implementation since WASI-p2 doesn't support fs::canonicalize
+		for entry in arc.entries()? {
+			let mut entry = entry?;
+			let entry_path = entry.path()?.to_path_buf();
+			if dir_name.is_none() {
+				dir_name = Some(get_root_dir(&entry_path)?);
+			}
+
+			let dest_path = out_dir.join(&entry_path);
+			if entry.header().entry_type().is_dir() {
+				if !dest_path.exists() {
+					let _ = std::fs::create_dir_all(dest_path);
+				}
+				continue;
+			}
+
+			if let Some(parent) = dest_path.parent()
+				&& !parent.exists()
+			{
+				std::fs::create_dir_all(parent)?;
+			}
+
+			let mut out_file =
+				File::create(dest_path).context("Failed to open destination file")?;
+			std::io::copy(&mut entry, &mut out_file).context("Failed to copy file")?;
+		}
+
+		dir_name.context("Missing archive internal directory")?
+	};
+
+	Ok(dir_name)
+}
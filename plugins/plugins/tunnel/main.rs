@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+
+use anyhow::Context;
+use nitro_plugin::api::executable::ExecutablePlugin;
+use nitro_plugin::hook::hooks::{InstanceTile, InstanceTileSize};
+use nitro_plugin::input_output::InputAction;
+use nitro_shared::Side;
+use nitro_shared::output::{MessageContents, NitroOutput};
+use nitrolaunch::instance::network::ServerBinding;
+use nitrolaunch::io::lock::FileLock;
+use nitrolaunch::io::notifications::{NotificationKind, notify};
+use nitrolaunch::io::paths::Paths;
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+
+fn main() -> anyhow::Result<()> {
+	let mut plugin = ExecutablePlugin::from_manifest_file("tunnel", include_str!("plugin.json"))?;
+
+	plugin.while_instance_launch(|mut ctx, arg| {
+		if !arg.side.is_some_and(|x| x == Side::Server) {
+			return Ok(());
+		}
+		let (Some(config), Some(inst_dir)) = (arg.config.plugin_config.get("tunnel"), arg.inst_dir)
+		else {
+			return Ok(());
+		};
+		let config: Config =
+			serde_json::from_value(config.clone()).context("Failed to deserialize config")?;
+
+		let paths = Paths::new_no_create()?;
+		let binding = ServerBinding::from_dir(Path::new(&inst_dir))
+			.context("Failed to get the port of the server")?;
+
+		let mut child = start_tunnel(&config, &binding).with_context(|| {
+			format!(
+				"Failed to start {} tunnel. Make sure that it is installed",
+				config.provider
+			)
+		})?;
+
+		Tunnels::update(&paths, |tunnels| {
+			tunnels.tunnels.insert(
+				arg.id.clone(),
+				Tunnel {
+					pid: child.id(),
+					provider: config.provider,
+					address: config.address.clone(),
+				},
+			);
+		})?;
+
+		if let Some(address) = &config.address {
+			announce_address(&paths, &arg.id, address)?;
+			ctx.get_output().display(MessageContents::Success(format!(
+				"Tunnel open at {address}"
+			)));
+		} else if let Some(stdout) = child.stdout.take() {
+			// Read the public address from the output of the tunnel in the background, since the main
+			// thread has to wait for the instance to stop
+			let provider = config.provider;
+			let id = arg.id.clone();
+			std::thread::spawn(move || watch_output(stdout, provider, &paths, &id));
+		}
+
+		// Keep the tunnel open until the instance stops
+		loop {
+			match ctx.poll() {
+				Ok(Some(InputAction::Terminate)) | Ok(None) | Err(..) => break,
+				Ok(Some(..)) => continue,
+			}
+		}
+
+		let _ = child.kill();
+		let _ = child.wait();
+		Tunnels::update(&Paths::new_no_create()?, |tunnels| {
+			tunnels.tunnels.remove(&arg.id);
+		})?;
+
+		Ok(())
+	})?;
+
+	plugin.on_instance_stop(|mut ctx, arg| {
+		let paths = Paths::new_no_create()?;
+		let mut tunnel = None;
+		Tunnels::update(&paths, |tunnels| {
+			tunnel = tunnels.tunnels.remove(&arg.id);
+		})?;
+
+		// The tunnel will usually be closed when the launch hook is terminated, but make sure it
+		// doesn't outlive the instance if that hook was killed instead
+		if let Some(tunnel) = tunnel
+			&& tunnel.kill()
+		{
+			ctx.get_output().debug(MessageContents::Success(format!(
+				"Closed tunnel for instance '{}'",
+				arg.id
+			)));
+		}
+
+		Ok(())
+	})?;
+
+	plugin.add_instance_tiles(|_, arg| {
+		let tunnels = Tunnels::open(&Paths::new_no_create()?)?;
+		let Some(tunnel) = tunnels.tunnels.get(&arg).filter(|x| x.is_alive()) else {
+			return Ok(Vec::new());
+		};
+
+		let address = tunnel
+			.address
+			.as_deref()
+			.unwrap_or("Waiting for address...");
+		let contents = include_str!("tile.html")
+			.replace("{{provider}}", &tunnel.provider.to_string())
+			.replace("{{address}}", &html_escape(address));
+
+		Ok(vec![InstanceTile {
+			id: "tunnel".into(),
+			contents,
+			size: InstanceTileSize::Small,
+		}])
+	})?;
+
+	Ok(())
+}
+
+/// Config for the tunnel of an instance
+#[derive(Deserialize)]
+struct Config {
+	/// The tunneling service to use
+	provider: Provider,
+	/// Path to the executable of the tunnel agent. Defaults to finding it on the PATH
+	#[serde(default)]
+	path: Option<PathBuf>,
+	/// Extra arguments for the tunnel agent
+	#[serde(default)]
+	args: Vec<String>,
+	/// The public address of the tunnel, if it is already known
+	#[serde(default)]
+	address: Option<String>,
+}
+
+/// A tunneling service
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+enum Provider {
+	/// playit.gg
+	Playit,
+	/// ngrok
+	Ngrok,
+}
+
+impl Provider {
+	/// Gets the default name of the executable for this provider
+	fn executable(&self) -> &'static str {
+		match self {
+			Self::Playit => "playit",
+			Self::Ngrok => "ngrok",
+		}
+	}
+
+	/// Finds the public address of a tunnel in a line of output from its agent
+	fn find_address(&self, line: &str) -> Option<String> {
+		match self {
+			Self::Ngrok => {
+				let line: serde_json::Value = serde_json::from_str(line).ok()?;
+				let url = line.get("url")?.as_str()?;
+				url.strip_prefix("tcp://").map(ToString::to_string)
+			}
+			Self::Playit => line
+				.split_whitespace()
+				.map(|x| x.trim_matches(|c: char| !c.is_alphanumeric()))
+				.find(|x| x.contains(".joinmc.link") || x.contains(".ply.gg"))
+				.map(ToString::to_string),
+		}
+	}
+}
+
+impl std::fmt::Display for Provider {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Playit => write!(f, "playit.gg"),
+			Self::Ngrok => write!(f, "ngrok"),
+		}
+	}
+}
+
+/// Starts the agent process for a tunnel to a server
+fn start_tunnel(config: &Config, binding: &ServerBinding) -> anyhow::Result<Child> {
+	let executable = config
+		.path
+		.clone()
+		.unwrap_or_else(|| PathBuf::from(config.provider.executable()));
+	let mut command = Command::new(executable);
+
+	match config.provider {
+		Provider::Ngrok => {
+			let target = if binding.address.is_some() {
+				binding.to_string()
+			} else {
+				binding.port.to_string()
+			};
+			command.args(["tcp", &target, "--log", "stdout", "--log-format", "json"]);
+		}
+		// The playit.gg agent forwards whatever tunnels are set up on the website
+		Provider::Playit => {}
+	}
+	command.args(&config.args);
+
+	command.stdin(Stdio::null());
+	command.stderr(Stdio::null());
+	if config.address.is_some() {
+		command.stdout(Stdio::null());
+	} else {
+		command.stdout(Stdio::piped());
+	}
+
+	let child = command.spawn()?;
+
+	Ok(child)
+}
+
+/// Reads the output of a tunnel agent until it closes, recording its public address once found
+fn watch_output(stdout: impl std::io::Read, provider: Provider, paths: &Paths, id: &str) {
+	let mut found = false;
+	// The output has to keep being read, otherwise the agent will block once the pipe fills up
+	for line in BufReader::new(stdout).lines() {
+		let Ok(line) = line else {
+			break;
+		};
+		if found {
+			continue;
+		}
+
+		if let Some(address) = provider.find_address(&line) {
+			found = true;
+			let _ = Tunnels::update(paths, |tunnels| {
+				if let Some(tunnel) = tunnels.tunnels.get_mut(id) {
+					tunnel.address = Some(address.clone());
+				}
+			});
+			let _ = announce_address(paths, id, &address);
+		}
+	}
+}
+
+/// Sends a notification with the public address of a tunnel
+fn announce_address(paths: &Paths, id: &str, address: &str) -> anyhow::Result<()> {
+	notify(
+		paths,
+		NotificationKind::Other,
+		"Tunnel open",
+		format!("Server instance '{id}' can be joined at {address}"),
+		Some(id),
+	)
+	.context("Failed to send notification")
+}
+
+/// Escapes text so that it can be put in the HTML of a tile
+fn html_escape(text: &str) -> String {
+	text.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+		.replace('\'', "&#39;")
+}
+
+/// Stored data for all open tunnels
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct Tunnels {
+	/// Map of instance IDs to their tunnels
+	tunnels: HashMap<String, Tunnel>,
+}
+
+/// An open tunnel for an instance
+#[derive(Serialize, Deserialize)]
+struct Tunnel {
+	/// The PID of the tunnel agent process
+	pid: u32,
+	/// The tunneling service
+	provider: Provider,
+	/// The public address of the tunnel, once it is known
+	address: Option<String>,
+}
+
+impl Tunnel {
+	/// Gets the agent process of this tunnel, if it is still running
+	fn with_process<T>(&self, f: impl FnOnce(&sysinfo::Process) -> T) -> Option<T> {
+		let mut system = System::new();
+		let pid = Pid::from_u32(self.pid);
+		system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+		let process = system.process(pid)?;
+		// Make sure the PID wasn't reused by another program
+		if !process
+			.name()
+			.to_string_lossy()
+			.contains(self.provider.executable())
+		{
+			return None;
+		}
+
+		Some(f(process))
+	}
+
+	/// Checks if the agent of this tunnel is still running
+	fn is_alive(&self) -> bool {
+		self.with_process(|_| ()).is_some()
+	}
+
+	/// Kills the agent of this tunnel, returning whether it was running
+	fn kill(&self) -> bool {
+		self.with_process(|x| x.kill()).unwrap_or(false)
+	}
+}
+
+impl Tunnels {
+	/// Gets the path to the tunnels file
+	fn get_path(paths: &Paths) -> PathBuf {
+		paths.internal.join("tunnels.json")
+	}
+
+	/// Opens the tunnels file
+	fn open(paths: &Paths) -> anyhow::Result<Self> {
+		let path = Self::get_path(paths);
+		if !path.exists() {
+			return Ok(Self::default());
+		}
+
+		let data = std::fs::read_to_string(path).context("Failed to read tunnels file")?;
+		serde_json::from_str(&data).context("Failed to deserialize tunnels")
+	}
+
+	/// Opens the tunnels file, modifies it, and writes it back. The file is locked while this happens,
+	/// since the output watcher thread and other hooks update it at the same time
+	fn update(paths: &Paths, f: impl FnOnce(&mut Self)) -> anyhow::Result<()> {
+		let _lock = FileLock::acquire(&paths.internal.join("tunnels.lock"))?;
+		let mut tunnels = Self::open(paths)?;
+		f(&mut tunnels);
+
+		let path = Self::get_path(paths);
+		if let Some(parent) = path.parent() {
+			let _ = std::fs::create_dir_all(parent);
+		}
+		let data = serde_json::to_string(&tunnels).context("Failed to serialize tunnels")?;
+		std::fs::write(path, data).context("Failed to write tunnels file")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_address_parsing() {
+		let line = r#"{"addr":"localhost:25565","lvl":"info","msg":"started tunnel","name":"command_line","url":"tcp://0.tcp.ngrok.io:12345"}"#;
+		assert_eq!(
+			Provider::Ngrok.find_address(line).as_deref(),
+			Some("0.tcp.ngrok.io:12345")
+		);
+		assert_eq!(Provider::Ngrok.find_address("not json"), None);
+
+		assert_eq!(
+			Provider::Playit
+				.find_address("tunnel running: my-server.gl.joinmc.link => 127.0.0.1:25565")
+				.as_deref(),
+			Some("my-server.gl.joinmc.link")
+		);
+		assert_eq!(Provider::Playit.find_address("agent starting"), None);
+	}
+
+	#[test]
+	fn test_html_escape() {
+		assert_eq!(
+			html_escape("<img src=x onerror=\"alert('hi')\">"),
+			"&lt;img src=x onerror=&quot;alert(&#39;hi&#39;)&quot;&gt;"
+		);
+	}
+}
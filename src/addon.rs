@@ -11,7 +11,7 @@ use reqwest::Client;
 use crate::io::paths::Paths;
 use crate::util::hash::{get_best_hash, hash_file_with_best_hash};
 use nitro_core::io::files::create_leading_dirs;
-use nitro_core::net::download;
+use nitro_core::net::{download, stats};
 
 use std::future::Future;
 use std::path::{Path, PathBuf};
@@ -121,6 +121,19 @@ impl AddonRequest {
 		self.addon.get_unique_id(instance_id)
 	}
 
+	/// Checks whether acquiring this addon will download it
+	pub fn needs_download(&self, paths: &Paths, instance_id: &str, force: bool) -> bool {
+		matches!(self.location, AddonLocation::Remote(..))
+			&& (force || self.addon.should_update(paths, instance_id))
+	}
+
+	/// Records that this addon did not need to be downloaded because it is already stored
+	pub fn record_cache_hit(&self) {
+		if let AddonLocation::Remote(url) = &self.location {
+			stats::record_cache_hit(url);
+		}
+	}
+
 	/// Get the addon and store it
 	pub async fn acquire(
 		&self,
@@ -244,6 +257,7 @@ mod tests {
 			version: None,
 			modpack_format: None,
 			hashes: AddonOptionalHashes::default(),
+			size: None,
 		};
 		assert_eq!(addon.split_filename(), ("FooBar", ".baz.jar"));
 	}
@@ -14,15 +14,27 @@ use crate::{io::paths::Paths, plugin::PluginManager};
 
 /// Creates an account from an account config
 pub fn read_account_config(config: &AccountConfig, id: &str) -> Account {
-	match config {
-		AccountConfig::Simple(variant) | AccountConfig::Advanced { variant } => {
-			let kind = match variant {
-				AccountVariant::Microsoft => AccountKind::Microsoft { xbox_uid: None },
-				AccountVariant::Demo => AccountKind::Demo,
-				AccountVariant::Unknown(id) => AccountKind::Unknown(id.clone()),
-			};
-			Account::new(kind, id.into())
-		}
+	let kind = match config.variant() {
+		AccountVariant::Microsoft => AccountKind::Microsoft { xbox_uid: None },
+		AccountVariant::Demo => AccountKind::Demo,
+		AccountVariant::Offline => AccountKind::Offline {
+			username: config.username().unwrap_or(id).to_string(),
+		},
+		AccountVariant::AuthlibInjector => AccountKind::AuthlibInjector {
+			server: normalize_server_url(config.server().unwrap_or_default()),
+			login: config.username().unwrap_or(id).to_string(),
+		},
+		AccountVariant::Unknown(id) => AccountKind::Unknown(id.clone()),
+	};
+	Account::new(kind, id.into())
+}
+
+/// Adds the scheme to an authentication server URL if it was left out
+fn normalize_server_url(server: &str) -> String {
+	if server.is_empty() || server.starts_with("https://") || server.starts_with("http://") {
+		server.to_string()
+	} else {
+		format!("https://{server}")
 	}
 }
 
@@ -48,9 +48,11 @@ pub fn check_configured_packages(
 ) {
 	for inst in instances.values() {
 		if inst.packages.iter().any(|x| {
-			PkgRequest::parse(x.get_pkg_id(), nitro_pkg::PkgRequestSource::UserRequire)
-				.repository
-				.is_none()
+			// Pinned packages don't come from a repository
+			x.get_file().is_none()
+				&& PkgRequest::parse(x.get_pkg_id(), nitro_pkg::PkgRequestSource::UserRequire)
+					.repository
+					.is_none()
 		}) {
 			o.display(MessageContents::Warning(
 				"An instance uses deprecated generic packages".into(),
@@ -61,9 +63,10 @@ pub fn check_configured_packages(
 
 	for temp in templates.values() {
 		if temp.instance.packages.iter().any(|x| {
-			PkgRequest::parse(x.get_pkg_id(), nitro_pkg::PkgRequestSource::UserRequire)
-				.repository
-				.is_none()
+			x.get_file().is_none()
+				&& PkgRequest::parse(x.get_pkg_id(), nitro_pkg::PkgRequestSource::UserRequire)
+					.repository
+					.is_none()
 		}) {
 			o.display(MessageContents::Warning(
 				"A template uses deprecated generic packages".into(),
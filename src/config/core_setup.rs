@@ -14,21 +14,28 @@ use nitro_shared::{
 };
 use reqwest::Client;
 
-use crate::{instance::update::manager::UpdateSettings, io::paths::Paths, plugin::PluginManager};
+use crate::{
+	config::preferences::ConfigPreferences, instance::update::manager::UpdateSettings,
+	io::paths::Paths, plugin::PluginManager,
+};
 
-/// Sets up and configures a NitroCore according to Nitrolaunch's features
+/// Sets up and configures a NitroCore according to Nitrolaunch's features and the user's preferences
 pub async fn setup_core(
 	client_id: Option<&ClientId>,
 	settings: &UpdateSettings,
+	prefs: &ConfigPreferences,
 	client: &Client,
 	plugins: &PluginManager,
 	paths: &Paths,
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<NitroCore> {
-	let mut core_config = nitro_core::ConfigBuilder::new().branding(BrandingProperties::new(
-		"Nitrolaunch".into(),
-		crate::VERSION.into(),
-	));
+	let mut core_config = nitro_core::ConfigBuilder::new()
+		.branding(BrandingProperties::new(
+			"Nitrolaunch".into(),
+			crate::VERSION.into(),
+		))
+		.download_mirrors(prefs.mirrors.clone())
+		.download_concurrency(prefs.download_concurrency);
 	if let Some(client_id) = client_id {
 		core_config = core_config.ms_client_id(client_id.clone());
 	}
@@ -12,6 +12,10 @@ pub mod package;
 pub mod plugin;
 /// Configuring global preferences
 pub mod preferences;
+/// Encrypting sensitive config values with the launcher passphrase
+pub mod secrets;
+/// Detecting changes to configuration files
+pub mod watch;
 
 use crate::config::account::{AuthFunction, read_account_config};
 use crate::config::checks::{check_configured_packages, check_nitro_version};
@@ -26,7 +30,7 @@ use nitro_config::template::consolidate_template_configs;
 use nitro_core::NitroCore;
 use nitro_core::account::AccountManager;
 use nitro_core::auth_crate::mc::ClientId;
-use nitro_core::io::{json_from_file, json_to_file_pretty};
+use nitro_core::io::{json_from_file, json_to_file_atomic};
 use nitro_plugin::hook::hooks::{AddInstances, AddInstancesArg, AddSupportedLoaders, AddTemplates};
 use nitro_shared::id::{InstanceID, TemplateID};
 use nitro_shared::output::{MessageContents, NitroOutput};
@@ -82,16 +86,30 @@ impl Config {
 			Ok(json_from_file(path).context("Failed to open config")?)
 		} else {
 			let config = default_config();
-			json_to_file_pretty(path, &config).context("Failed to write default configuration")?;
+			json_to_file_atomic(path, &config).context("Failed to write default configuration")?;
 			Ok(serde_json::from_value(config).context("Failed to parse default configuration")?)
 		}
 	}
 
+	/// Creates a client for network requests that uses the proxy from the config file, and applies
+	/// the bandwidth limits from it. This only reads the preferences, so it can be used before the
+	/// full config is loaded
+	pub fn create_client(paths: &Paths) -> anyhow::Result<Client> {
+		let path = Self::get_path(paths);
+		if !path.exists() {
+			return Ok(Client::new());
+		}
+
+		let config = Self::open(&path)?;
+		preferences::apply_bandwidth_limit(&config.preferences);
+		preferences::create_client(&config.preferences)
+	}
+
 	/// Create the default config at the specified path if it does not exist
 	pub fn create_default(path: &Path) -> anyhow::Result<()> {
 		if !path.exists() {
 			let doc = default_config();
-			json_to_file_pretty(path, &doc).context("Failed to write default configuration")?;
+			json_to_file_atomic(path, &doc).context("Failed to write default configuration")?;
 		}
 		Ok(())
 	}
@@ -114,6 +132,7 @@ impl Config {
 			plugins: plugins.clone(),
 			paths: paths.clone(),
 		}));
+		accounts.set_strict_ownership_check(config.preferences.strict_ownership_check);
 		let mut instances = HashMap::with_capacity(config.instances.len());
 		// Preferences
 		let (prefs, repositories) =
@@ -277,6 +296,19 @@ impl Config {
 				)));
 			}
 
+			// Packages pinned to files don't come from a repository, so we add them to the registry now
+			for package in instance.packages() {
+				let Some(contents) = package.get_pinned_contents() else {
+					continue;
+				};
+				if let Err(e) = packages.insert_pinned(package.get_request(), &contents) {
+					o.display(MessageContents::Error(format!(
+						"Failed to add pinned package '{}': {e:?}",
+						package.id
+					)));
+				}
+			}
+
 			instances.insert(instance_id, instance);
 		}
 
@@ -344,7 +376,7 @@ impl Config {
 		paths: &Paths,
 		o: &mut impl NitroOutput,
 	) -> anyhow::Result<NitroCore> {
-		setup_core(client_id, settings, client, plugins, paths, o).await
+		setup_core(client_id, settings, &self.prefs, client, plugins, paths, o).await
 	}
 }
 
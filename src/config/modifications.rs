@@ -3,7 +3,7 @@ use nitro_config::ConfigDeser;
 use nitro_config::instance::InstanceConfig;
 use nitro_config::template::TemplateConfig;
 use nitro_config::{account::AccountConfig, package::PackageConfigDeser};
-use nitro_core::io::json_to_file_pretty;
+use nitro_core::io::json_to_file_atomic;
 use nitro_plugin::hook::hooks::{
 	SaveInstanceConfig, SaveInstanceConfigArg, SaveTemplateConfig, SaveTemplateConfigArg,
 };
@@ -86,6 +86,14 @@ pub enum ConfigModification {
 	RemoveInstance(InstanceID),
 	/// Removes a template
 	RemoveTemplate(InstanceID),
+	/// Sets the limit on the size of cached downloads, in megabytes
+	SetCacheLimit(Option<u64>),
+	/// Sets the limit on the total download speed, in bytes per second
+	SetBandwidthLimit(Option<u64>),
+	/// Sets the auto-lock timeout of an account, in minutes
+	SetAccountAutoLock(String, Option<u64>),
+	/// Sets the icon of an instance
+	SetInstanceIcon(InstanceID, Option<String>),
 }
 
 /// Applies modifications to the config
@@ -178,12 +186,44 @@ pub async fn apply_modifications(
 			ConfigModification::RemoveAccount(account) => {
 				config.accounts.remove(&account);
 			}
+			ConfigModification::SetAccountAutoLock(id, auto_lock) => {
+				let account = config
+					.accounts
+					.get_mut(&id)
+					.with_context(|| format!("Account {id} does not exist"))?;
+				let variant = account.variant().clone();
+				let username = account.username().map(ToString::to_string);
+				let server = account.server().map(ToString::to_string);
+				*account = if auto_lock.is_some() || username.is_some() || server.is_some() {
+					AccountConfig::Advanced {
+						variant,
+						auto_lock,
+						username,
+						server,
+					}
+				} else {
+					AccountConfig::Simple(variant)
+				};
+			}
 			ConfigModification::RemoveInstance(instance) => {
 				config.instances.remove(&instance);
 			}
 			ConfigModification::RemoveTemplate(template) => {
 				config.templates.remove(&template);
 			}
+			ConfigModification::SetCacheLimit(limit) => {
+				config.preferences.cache_limit = limit;
+			}
+			ConfigModification::SetBandwidthLimit(limit) => {
+				config.preferences.bandwidth_limit.total = limit;
+			}
+			ConfigModification::SetInstanceIcon(instance_id, icon) => {
+				let instance = config
+					.instances
+					.get_mut(&instance_id)
+					.ok_or(anyhow!("Unknown instance '{instance_id}'"))?;
+				instance.icon = icon;
+			}
 		};
 	}
 	Ok(())
@@ -199,10 +239,7 @@ pub async fn apply_modifications_and_write(
 ) -> anyhow::Result<()> {
 	apply_modifications(config, modifications, paths, plugins, o).await?;
 	let path = Config::get_path(paths);
-	// Backup the contents first
-	std::fs::copy(&path, paths.config.join("nitro_write_backup.json"))
-		.context("Failed to backup config")?;
-	json_to_file_pretty(path, config).context("Failed to write modified configuration")?;
+	json_to_file_atomic(path, config).context("Failed to write modified configuration")?;
 
 	Ok(())
 }
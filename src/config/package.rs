@@ -1,8 +1,13 @@
 use std::sync::Arc;
 
 use anyhow::ensure;
-use nitro_config::package::{EvalPermissions, PackageConfigDeser};
+use nitro_config::package::{
+	AddonConflictResolution, EvalPermissions, PackageConfigDeser, PinnedPackageFile,
+};
+use nitro_pkg::declarative::{DeclarativeAddon, DeclarativeAddonVersion, DeclarativePackage};
 use nitro_pkg::properties::PackageProperties;
+use nitro_shared::Side;
+use nitro_shared::id::InstanceID;
 use nitro_shared::pkg::{ArcPkgReq, PackageID, PackageStability};
 
 use nitro_pkg::{PkgRequest, PkgRequestSource};
@@ -26,8 +31,19 @@ pub struct PackageConfig {
 	pub content_version: Option<String>,
 	/// Whether this package is optional
 	pub optional: bool,
+	/// A file that this package is pinned to instead of coming from a repository
+	pub file: Option<PinnedPackageFile>,
+	/// How to resolve conflicts between this package's addons and another package's
+	pub on_conflict: Option<AddonConflictResolution>,
+	/// The only side to install this package on, overriding the sides the package supports
+	pub side: Option<Side>,
+	/// The instance that this package is configured for. Pinned packages are scoped to it
+	pub instance: Option<InstanceID>,
 }
 
+/// Prefix for the repository of a pinned package request, which is followed by the ID of its instance
+pub const PINNED_REPOSITORY_PREFIX: &str = "pinned-";
+
 /// Where a package was configured from
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PackageConfigSource {
@@ -49,6 +65,10 @@ impl PackageConfig {
 			worlds: Vec::new(),
 			content_version: None,
 			optional: false,
+			file: None,
+			on_conflict: None,
+			side: None,
+			instance: None,
 		}
 	}
 
@@ -78,10 +98,53 @@ impl PackageConfig {
 
 	/// Get the request of the config
 	pub fn get_request(&self) -> ArcPkgReq {
-		Arc::new(PkgRequest::parse(
-			self.id.clone(),
-			PkgRequestSource::UserRequire,
-		))
+		let mut req = PkgRequest::parse(self.id.clone(), PkgRequestSource::UserRequire);
+		// Pinned packages get their own repository for their instance so that they don't replace
+		// the package for other instances or for packages that depend on it
+		if self.file.is_some()
+			&& let Some(instance) = &self.instance
+		{
+			req.repository = Some(format!("{PINNED_REPOSITORY_PREFIX}{instance}"));
+		}
+
+		Arc::new(req)
+	}
+
+	/// Creates the contents of the package if it is pinned to a file
+	pub fn get_pinned_contents(&self) -> Option<DeclarativePackage> {
+		let file = self.file.as_ref()?;
+		let req = self.get_request();
+
+		let version = DeclarativeAddonVersion {
+			url: file.url.clone(),
+			path: file.path.clone(),
+			filename: file.filename.clone(),
+			// Use the hash as the version so that changing the file will replace it
+			version: file.version.clone().or_else(|| {
+				let hash = file
+					.hashes
+					.sha256
+					.as_ref()
+					.or(file.hashes.sha512.as_ref())?;
+				Some(hash.chars().take(16).collect())
+			}),
+			hashes: file.hashes.clone(),
+			..Default::default()
+		};
+		let addon = DeclarativeAddon {
+			kind: file.kind.into(),
+			modpack_format: None,
+			versions: vec![version],
+			conditions: Vec::new(),
+			optional: false,
+		};
+
+		let mut out = DeclarativePackage::default();
+		out.meta.name = Some(req.id.to_string());
+		out.properties.kinds = vec![file.kind.into()];
+		out.addons.insert("file".into(), addon);
+
+		Some(out)
 	}
 }
 
@@ -89,17 +152,29 @@ impl PackageConfig {
 pub fn read_package_config(
 	config: PackageConfigDeser,
 	default_stability: PackageStability,
+	instance: &InstanceID,
 ) -> PackageConfig {
 	let id = config.get_pkg_id();
 
+	// The user chose the local file themselves, so the generated package is allowed to use it
+	let permissions = if config.get_file().is_some_and(|x| x.path.is_some()) {
+		EvalPermissions::Elevated
+	} else {
+		config.get_permissions()
+	};
+
 	PackageConfig {
 		id,
 		features: config.get_features(),
 		use_default_features: config.get_use_default_features(),
-		permissions: config.get_permissions(),
+		permissions,
 		stability: config.get_stability(default_stability),
 		worlds: config.get_worlds().into_owned(),
 		content_version: None,
 		optional: config.get_optional(),
+		file: config.get_file().cloned(),
+		on_conflict: config.get_on_conflict(),
+		side: config.get_side(),
+		instance: Some(instance.clone()),
 	}
 }
@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::{
-	io::paths::Paths,
+	io::{paths::Paths, reports},
 	pkg::repo::{
 		PackageRepository,
 		basic::{BasicPackageRepository, RepoLocation},
@@ -10,10 +11,15 @@ use crate::{
 	},
 	plugin::PluginManager,
 };
-use nitro_config::preferences::{PrefDeser, RepoDeser};
-use nitro_core::net::download::validate_url;
+use nitro_config::preferences::{
+	MaintenanceDeser, MirrorDeser, PrefDeser, RepoDeser, UpdateChannel,
+};
+use nitro_core::net::activity;
+use nitro_core::net::download::{self, Client, ProxySettings, RetryPolicy, validate_url};
+use nitro_core::net::game_files::mirrors::DownloadMirror;
 
 use anyhow::{Context, bail};
+use itertools::Itertools;
 use nitro_plugin::hook::hooks::AddCustomPackageRepositories;
 use nitro_shared::{
 	lang::Language,
@@ -25,6 +31,24 @@ use nitro_shared::{
 pub struct ConfigPreferences {
 	/// The global language
 	pub language: Language,
+	/// The paste backend to upload logs to
+	pub paste_backend: Option<String>,
+	/// The number of days between automatically pruning unused game files
+	pub prune_interval: Option<u32>,
+	/// The maximum total size of cached downloads in megabytes
+	pub cache_limit: Option<u64>,
+	/// Whether to save reports of crashes and errors locally
+	pub error_reports: bool,
+	/// The release channel to update the CLI from
+	pub update_channel: UpdateChannel,
+	/// The proxy to send network requests through
+	pub proxy: Option<ProxySettings>,
+	/// Mirrors to download game files from
+	pub mirrors: Vec<DownloadMirror>,
+	/// Intervals for background maintenance tasks
+	pub maintenance: MaintenanceDeser,
+	/// The maximum number of files to download at the same time
+	pub download_concurrency: Option<usize>,
 }
 
 impl ConfigPreferences {
@@ -72,10 +96,8 @@ impl ConfigPreferences {
 			}
 		}
 
-		for repo in prefs.repositories.preferred.iter() {
-			if !repo.disable
-				&& let Err(e) = add_repo(&mut repositories, repo)
-			{
+		for repo in get_enabled_repos(&prefs.repositories.preferred) {
+			if let Err(e) = add_repo(&mut repositories, repo) {
 				o.display(MessageContents::Error(format!(
 					"Failed to add repository {}: {e:?}",
 					repo.id
@@ -85,10 +107,8 @@ impl ConfigPreferences {
 		repositories.extend(preferred_plugin_repositories);
 		repositories.extend(PackageRepository::default_repos());
 		repositories.extend(backup_plugin_repositories);
-		for repo in prefs.repositories.backup.iter() {
-			if !repo.disable
-				&& let Err(e) = add_repo(&mut repositories, repo)
-			{
+		for repo in get_enabled_repos(&prefs.repositories.backup) {
+			if let Err(e) = add_repo(&mut repositories, repo) {
 				o.display(MessageContents::Error(format!(
 					"Failed to add repository {}: {e:?}",
 					repo.id
@@ -108,15 +128,133 @@ impl ConfigPreferences {
 			existing.insert(repo.get_id());
 		}
 
+		// Reports can come from anywhere in the program, so the setting is global
+		reports::set_enabled(prefs.error_reports);
+		// The same goes for network requests
+		download::set_retry_policy(read_retry_policy(prefs));
+		apply_bandwidth_limit(prefs);
+		apply_connectivity_check_urls(prefs);
+
 		(
 			Self {
 				language: prefs.language,
+				paste_backend: prefs.paste_backend.clone(),
+				prune_interval: prefs.prune_interval,
+				cache_limit: prefs.cache_limit,
+				error_reports: prefs.error_reports,
+				update_channel: prefs.update_channel,
+				proxy: read_proxy(prefs),
+				mirrors: read_mirrors(prefs, o),
+				maintenance: prefs.maintenance.clone(),
+				download_concurrency: prefs.download_concurrency.filter(|x| *x > 0),
 			},
 			repositories,
 		)
 	}
 }
 
+/// Gets the proxy settings from deserialized preferences
+pub fn read_proxy(prefs: &PrefDeser) -> Option<ProxySettings> {
+	prefs.proxy.as_ref().map(|proxy| ProxySettings {
+		url: proxy.url.clone(),
+		bypass: proxy.bypass.clone(),
+	})
+}
+
+/// Gets the retry policy for network requests from deserialized preferences
+pub fn read_retry_policy(prefs: &PrefDeser) -> RetryPolicy {
+	let default = RetryPolicy::default();
+	let retry = &prefs.retry;
+
+	RetryPolicy {
+		// There is always at least the first attempt
+		attempts: retry.attempts.unwrap_or(default.attempts).max(1),
+		base_delay: retry
+			.base_delay
+			.map(Duration::from_millis)
+			.unwrap_or(default.base_delay),
+		max_delay: retry
+			.max_delay
+			.map(Duration::from_millis)
+			.unwrap_or(default.max_delay),
+		retryable_statuses: retry.statuses.clone().unwrap_or(default.retryable_statuses),
+	}
+}
+
+/// Gets the game file download mirrors from deserialized preferences, skipping invalid ones
+fn read_mirrors(prefs: &PrefDeser, o: &mut impl NitroOutput) -> Vec<DownloadMirror> {
+	let mut out = Vec::new();
+	for mirror in &prefs.mirrors {
+		match read_mirror(mirror) {
+			Ok(mirror) => out.push(mirror),
+			Err(e) => o.display(MessageContents::Error(format!(
+				"Invalid download mirror: {e:?}"
+			))),
+		}
+	}
+
+	out
+}
+
+/// Reads a single download mirror
+fn read_mirror(mirror: &MirrorDeser) -> anyhow::Result<DownloadMirror> {
+	match mirror {
+		MirrorDeser::Preset(name) => {
+			DownloadMirror::preset(name).with_context(|| format!("Unknown mirror '{name}'"))
+		}
+		MirrorDeser::Custom { name, rewrites } => {
+			for (official, mirror) in rewrites {
+				validate_url(official)
+					.with_context(|| format!("Invalid URL '{official}' in mirror '{name}'"))?;
+				validate_url(mirror)
+					.with_context(|| format!("Invalid URL '{mirror}' in mirror '{name}'"))?;
+			}
+
+			Ok(DownloadMirror {
+				name: name.clone(),
+				rewrites: rewrites
+					.iter()
+					.map(|(official, mirror)| (official.clone(), mirror.clone()))
+					.collect(),
+			})
+		}
+	}
+}
+
+/// Applies the bandwidth limits from deserialized preferences to all downloads. The preferences
+/// are the only place that the limits are stored, so frontends change them by modifying the config
+pub fn apply_bandwidth_limit(prefs: &PrefDeser) {
+	let bandwidth_limit = &prefs.bandwidth_limit;
+	activity::set_bandwidth_limit(bandwidth_limit.total.filter(|x| *x > 0));
+	activity::set_download_bandwidth_limit(bandwidth_limit.per_download.filter(|x| *x > 0));
+}
+
+/// Makes the connectivity check also try the download mirrors from deserialized preferences, so
+/// that users who can only reach the mirrors aren't put into offline mode
+pub fn apply_connectivity_check_urls(prefs: &PrefDeser) {
+	let urls = prefs
+		.mirrors
+		.iter()
+		.filter_map(|x| read_mirror(x).ok())
+		.flat_map(|x| x.rewrites.into_iter().map(|(_, mirror)| mirror))
+		.unique()
+		.collect();
+	download::set_connectivity_check_urls(urls);
+}
+
+/// Creates a client for network requests that uses the proxy from deserialized preferences.
+/// This can be used before the full config is loaded. The proxy is also stored so that it is
+/// passed to plugins
+pub fn create_client(prefs: &PrefDeser) -> anyhow::Result<Client> {
+	apply_connectivity_check_urls(prefs);
+	let proxy = read_proxy(prefs);
+	let client =
+		download::create_client(proxy.as_ref()).context("Failed to create client with proxy")?;
+	download::set_proxy(proxy);
+
+	Ok(client)
+}
+
 /// Add a repo to the list
 fn add_repo(repos: &mut Vec<PackageRepository>, repo: &RepoDeser) -> anyhow::Result<()> {
 	let location = if let Some(url) = &repo.url {
@@ -130,9 +268,82 @@ fn add_repo(repos: &mut Vec<PackageRepository>, repo: &RepoDeser) -> anyhow::Res
 		bail!("Niether path nor URL was set for repository {}", repo.id);
 	};
 
-	repos.push(PackageRepository::Basic(BasicPackageRepository::new(
-		&repo.id, location,
-	)));
+	if !repo.mirrors.is_empty() && !matches!(location, RepoLocation::Remote(..)) {
+		bail!(
+			"Mirrors can only be used with remote repository {}",
+			repo.id
+		);
+	}
+	for mirror in &repo.mirrors {
+		validate_url(mirror).with_context(|| {
+			format!(
+				"Invalid mirror url '{}' in package repository '{}'",
+				mirror, repo.id
+			)
+		})?;
+	}
+
+	let repository = BasicPackageRepository::new(&repo.id, location)
+		.with_mirrors(repo.mirrors.clone())
+		.with_cache_ttl(repo.cache_ttl.map(Duration::from_secs));
+	repos.push(PackageRepository::Basic(repository));
 
 	Ok(())
 }
+
+/// Gets the repositories in a list that aren't disabled, with the highest priority first.
+/// Repositories with the same priority keep the order they were configured in
+fn get_enabled_repos(repos: &[RepoDeser]) -> Vec<&RepoDeser> {
+	let mut out: Vec<_> = repos.iter().filter(|x| !x.disable).collect();
+	out.sort_by_key(|x| std::cmp::Reverse(x.priority));
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn repo(id: &str, priority: i32, disable: bool) -> RepoDeser {
+		RepoDeser {
+			id: id.into(),
+			url: Some(format!("https://example.com/{id}")),
+			path: None,
+			disable,
+			mirrors: Vec::new(),
+			priority,
+			cache_ttl: None,
+		}
+	}
+
+	#[test]
+	fn test_repo_priority() {
+		let repos = vec![
+			repo("a", 0, false),
+			repo("b", 5, false),
+			repo("c", 0, false),
+			repo("d", 10, true),
+		];
+		let ids: Vec<_> = get_enabled_repos(&repos)
+			.into_iter()
+			.map(|x| x.id.as_str())
+			.collect();
+		assert_eq!(ids, vec!["b", "a", "c"]);
+	}
+
+	#[test]
+	fn test_read_mirror() {
+		let preset = read_mirror(&MirrorDeser::Preset("bmclapi".into())).unwrap();
+		assert_eq!(preset, DownloadMirror::bmclapi());
+		assert!(read_mirror(&MirrorDeser::Preset("foo".into())).is_err());
+
+		let custom = MirrorDeser::Custom {
+			name: "custom".into(),
+			rewrites: [(
+				"https://libraries.minecraft.net/".to_string(),
+				"not a url".to_string(),
+			)]
+			.into(),
+		};
+		assert!(read_mirror(&custom).is_err());
+	}
+}
@@ -0,0 +1,221 @@
+use std::num::NonZeroU32;
+use std::sync::Mutex;
+
+use anyhow::{Context, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use nitro_shared::output::{MessageContents, NitroOutput};
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde_json::Value;
+
+/// Prefix for config values that are encrypted with the launcher passphrase
+pub const ENCRYPTED_PREFIX: &str = "nitro_encrypted:";
+/// Environment variable that can be used to provide the launcher passphrase without a prompt
+pub const PASSPHRASE_ENV_VAR: &str = "NITRO_CONFIG_PASSPHRASE";
+
+/// Length of the salt used to derive the key for each value
+const SALT_LEN: usize = 16;
+/// Number of PBKDF2 iterations used to derive the key from the passphrase
+const KEY_ITERATIONS: u32 = 100_000;
+
+/// The passphrase that was last entered, so that it only has to be entered once per session
+static PASSPHRASE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Checks if a config value is encrypted
+pub fn is_encrypted(value: &str) -> bool {
+	value.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Encrypts a config value with the launcher passphrase
+pub fn encrypt_value(plaintext: &str, passphrase: &str) -> anyhow::Result<String> {
+	let rng = SystemRandom::new();
+	let mut salt = [0u8; SALT_LEN];
+	let mut nonce = [0u8; NONCE_LEN];
+	rng.fill(&mut salt)
+		.map_err(|_| anyhow!("Failed to generate salt"))?;
+	rng.fill(&mut nonce)
+		.map_err(|_| anyhow!("Failed to generate nonce"))?;
+
+	let key = derive_key(passphrase, &salt)?;
+	let mut data = plaintext.as_bytes().to_vec();
+	key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+		.map_err(|_| anyhow!("Failed to encrypt value"))?;
+
+	let mut out = Vec::with_capacity(SALT_LEN + NONCE_LEN + data.len());
+	out.extend(salt);
+	out.extend(nonce);
+	out.extend(data);
+
+	Ok(format!("{ENCRYPTED_PREFIX}{}", STANDARD.encode(out)))
+}
+
+/// Decrypts a config value that was encrypted with the launcher passphrase
+pub fn decrypt_value(value: &str, passphrase: &str) -> anyhow::Result<String> {
+	let Some(data) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+		bail!("Value is not encrypted");
+	};
+	let data = STANDARD
+		.decode(data)
+		.context("Encrypted value is not valid base64")?;
+	if data.len() < SALT_LEN + NONCE_LEN {
+		bail!("Encrypted value is too short");
+	}
+
+	let (salt, rest) = data.split_at(SALT_LEN);
+	let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+	let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| anyhow!("Invalid nonce"))?;
+
+	let key = derive_key(passphrase, salt)?;
+	let mut ciphertext = ciphertext.to_vec();
+	let plaintext = key
+		.open_in_place(nonce, Aad::empty(), &mut ciphertext)
+		.map_err(|_| anyhow!("Incorrect passphrase or corrupted value"))?;
+
+	String::from_utf8(plaintext.to_vec()).context("Decrypted value is not valid UTF-8")
+}
+
+/// Checks if a JSON value contains any encrypted strings
+pub fn contains_encrypted(value: &Value) -> bool {
+	match value {
+		Value::String(string) => is_encrypted(string),
+		Value::Array(array) => array.iter().any(contains_encrypted),
+		Value::Object(object) => object.values().any(contains_encrypted),
+		_ => false,
+	}
+}
+
+/// Finds the first encrypted string in a JSON value
+fn find_encrypted(value: &Value) -> Option<&str> {
+	match value {
+		Value::String(string) if is_encrypted(string) => Some(string),
+		Value::Array(array) => array.iter().find_map(find_encrypted),
+		Value::Object(object) => object.values().find_map(find_encrypted),
+		_ => None,
+	}
+}
+
+/// Decrypts all of the encrypted strings in a JSON value in place
+pub fn decrypt_json(value: &mut Value, passphrase: &str) -> anyhow::Result<()> {
+	match value {
+		Value::String(string) if is_encrypted(string) => {
+			*string = decrypt_value(string, passphrase)?;
+		}
+		Value::Array(array) => {
+			for value in array {
+				decrypt_json(value, passphrase)?;
+			}
+		}
+		Value::Object(object) => {
+			for value in object.values_mut() {
+				decrypt_json(value, passphrase)?;
+			}
+		}
+		_ => {}
+	}
+
+	Ok(())
+}
+
+/// Encrypts the string at a dot-separated path in a JSON object, like `launch.env.TOKEN`
+pub fn encrypt_json_field(value: &mut Value, path: &str, passphrase: &str) -> anyhow::Result<()> {
+	let mut current = value;
+	for key in path.split('.') {
+		current = current
+			.get_mut(key)
+			.with_context(|| format!("Field '{path}' does not exist"))?;
+	}
+
+	let Value::String(string) = current else {
+		bail!("Field '{path}' is not a string. Only strings can be encrypted");
+	};
+	if !is_encrypted(string) {
+		*string = encrypt_value(string, passphrase)?;
+	}
+
+	Ok(())
+}
+
+/// Gets the launcher passphrase, prompting for it if it hasn't been entered yet this session.
+/// If an encrypted value is given, the passphrase is checked against it
+pub async fn get_passphrase(
+	check_against: Option<&Value>,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<String> {
+	let check = check_against.and_then(find_encrypted);
+	let cached = PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner()).clone();
+	let passphrase = if let Some(passphrase) = cached {
+		passphrase
+	} else if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR) {
+		passphrase
+	} else if check.is_some() {
+		o.prompt_password(MessageContents::Simple(
+			"Enter the launcher passphrase to decrypt config".into(),
+		))
+		.await
+		.context("Failed to get launcher passphrase")?
+	} else {
+		o.prompt_new_password(MessageContents::Simple(
+			"Enter the launcher passphrase to encrypt config with".into(),
+		))
+		.await
+		.context("Failed to get launcher passphrase")?
+	};
+
+	if let Some(check) = check {
+		decrypt_value(check, &passphrase).context("Failed to decrypt config")?;
+	}
+
+	*PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner()) = Some(passphrase.clone());
+	Ok(passphrase)
+}
+
+/// Forgets the launcher passphrase so that it has to be entered again
+pub fn lock_passphrase() {
+	*PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> anyhow::Result<LessSafeKey> {
+	let mut key = [0u8; 32];
+	pbkdf2::derive(
+		pbkdf2::PBKDF2_HMAC_SHA256,
+		NonZeroU32::new(KEY_ITERATIONS).expect("Iterations should not be zero"),
+		salt,
+		passphrase.as_bytes(),
+		&mut key,
+	);
+	let key = UnboundKey::new(&CHACHA20_POLY1305, &key).map_err(|_| anyhow!("Invalid key"))?;
+
+	Ok(LessSafeKey::new(key))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_encrypt_json() {
+		let mut config = serde_json::json!({
+			"launch": {
+				"env": {
+					"TOKEN": "secret"
+				}
+			},
+			"version": "1.21.1"
+		});
+
+		encrypt_json_field(&mut config, "launch.env.TOKEN", "hunter2").unwrap();
+		let encrypted = config["launch"]["env"]["TOKEN"].as_str().unwrap();
+		assert!(is_encrypted(encrypted));
+		assert!(!encrypted.contains("secret"));
+		assert!(contains_encrypted(&config));
+
+		assert!(decrypt_value(encrypted, "wrong").is_err());
+		assert!(encrypt_json_field(&mut config, "version.major", "hunter2").is_err());
+
+		decrypt_json(&mut config, "hunter2").unwrap();
+		assert_eq!(config["launch"]["env"]["TOKEN"], "secret");
+		assert!(!contains_encrypted(&config));
+	}
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::io::paths::Paths;
+
+/// Directories in the config directory that don't contain configuration
+const IGNORED_DIRS: &[&str] = &["temp"];
+
+/// Detects changes to the configuration files on disk, including ones made by other processes
+/// like the CLI or plugins that store configuration in their own files
+pub struct ConfigWatcher {
+	dir: PathBuf,
+	snapshot: HashMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl ConfigWatcher {
+	/// Create a new ConfigWatcher for the config directory, starting from its current state
+	pub fn new(paths: &Paths) -> Self {
+		Self::from_dir(paths.config.clone())
+	}
+
+	/// Create a new ConfigWatcher for a directory, starting from its current state
+	pub fn from_dir(dir: PathBuf) -> Self {
+		let snapshot = take_snapshot(&dir);
+		Self { dir, snapshot }
+	}
+
+	/// Checks whether the configuration has changed since the last check
+	pub fn check(&mut self) -> bool {
+		let snapshot = take_snapshot(&self.dir);
+		let changed = snapshot != self.snapshot;
+		self.snapshot = snapshot;
+
+		changed
+	}
+}
+
+/// Gets the modification times and sizes of all of the config files in a directory
+fn take_snapshot(dir: &Path) -> HashMap<PathBuf, (SystemTime, u64)> {
+	let mut out = HashMap::new();
+	add_dir_to_snapshot(dir, true, &mut out);
+	out
+}
+
+fn add_dir_to_snapshot(dir: &Path, is_root: bool, out: &mut HashMap<PathBuf, (SystemTime, u64)>) {
+	let Ok(entries) = dir.read_dir() else {
+		return;
+	};
+
+	for entry in entries.flatten() {
+		let path = entry.path();
+		let file_name = entry.file_name().to_string_lossy().to_string();
+		let Ok(meta) = entry.metadata() else {
+			continue;
+		};
+
+		if meta.is_dir() {
+			if !(is_root && IGNORED_DIRS.contains(&file_name.as_str())) {
+				add_dir_to_snapshot(&path, false, out);
+			}
+			continue;
+		}
+
+		// Temporary files and backups from atomic writes aren't configuration
+		if !file_name.ends_with(".json") || file_name.starts_with('.') {
+			continue;
+		}
+
+		let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+		out.insert(path, (modified, meta.len()));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_config_watcher() {
+		let dir = std::env::temp_dir().join("nitro_test_config_watcher");
+		let _ = std::fs::remove_dir_all(&dir);
+		std::fs::create_dir_all(dir.join("instances")).unwrap();
+		std::fs::write(dir.join("nitro.json"), "{}").unwrap();
+
+		let mut watcher = ConfigWatcher::from_dir(dir.clone());
+		assert!(!watcher.check());
+
+		std::fs::write(dir.join("instances/foo.json"), "{}").unwrap();
+		assert!(watcher.check());
+		assert!(!watcher.check());
+
+		std::fs::write(dir.join("nitro.json.bak.1"), "{}").unwrap();
+		std::fs::create_dir_all(dir.join("temp")).unwrap();
+		std::fs::write(dir.join("temp/edit.json"), "{}").unwrap();
+		assert!(!watcher.check());
+
+		std::fs::write(dir.join("nitro.json"), "{ \"instances\": {} }").unwrap();
+		assert!(watcher.check());
+
+		let _ = std::fs::remove_dir_all(dir);
+	}
+}
@@ -0,0 +1,224 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use nitro_config::package::{FullPackageConfig, PackageConfigDeser, PinnedPackageFile};
+use nitro_net::{curseforge, modrinth};
+use nitro_shared::Side;
+use nitro_shared::minecraft::AddonKind;
+use nitro_shared::output::{MessageContents, NitroOutput};
+use nitro_shared::pkg::AddonOptionalHashes;
+use reqwest::Client;
+use sha2::{Digest, Sha512};
+
+use crate::io::paths::Paths;
+
+use super::Instance;
+
+/// Environment variable for the CurseForge API key. CurseForge lookups are skipped if it isn't set
+pub const CURSEFORGE_API_KEY_VAR: &str = "NITRO_CURSEFORGE_API_KEY";
+
+/// A loose addon file that was identified and can be replaced with a package
+#[derive(Debug, Clone)]
+pub struct AdoptableFile {
+	/// Path to the loose file
+	pub path: PathBuf,
+	/// Configuration for the package that replaces the file
+	pub package: PackageConfigDeser,
+}
+
+/// Results from identifying the loose addon files in an instance
+#[derive(Debug, Default)]
+pub struct LooseFiles {
+	/// Files that were identified as packages
+	pub identified: Vec<AdoptableFile>,
+	/// Files that could not be identified
+	pub unidentified: Vec<PathBuf>,
+}
+
+impl LooseFiles {
+	/// Removes the files that were identified, so that their packages can install them again
+	pub fn remove_identified(&self) -> anyhow::Result<()> {
+		for file in &self.identified {
+			std::fs::remove_file(&file.path)
+				.with_context(|| format!("Failed to remove file {}", file.path.display()))?;
+		}
+
+		Ok(())
+	}
+}
+
+/// A loose file with its fingerprints
+struct LooseFile {
+	path: PathBuf,
+	kind: AddonKind,
+	sha512: String,
+	fingerprint: u32,
+}
+
+impl Instance {
+	/// Gets the mod and plugin files in this instance that weren't installed by a package
+	pub fn get_loose_addon_files(
+		&self,
+		paths: &Paths,
+	) -> anyhow::Result<Vec<(PathBuf, AddonKind)>> {
+		let Some(inst_dir) = self.dir() else {
+			return Ok(Vec::new());
+		};
+
+		let inst_lock = self.get_lockfile(paths)?;
+		let managed: HashSet<PathBuf> = inst_lock
+			.get_addons()
+			.iter()
+			.flat_map(|x| x.files.iter().map(PathBuf::from))
+			.collect();
+
+		let mut dirs = vec![(inst_dir.join("mods"), AddonKind::Mod)];
+		if self.side() == Side::Server {
+			dirs.push((inst_dir.join("plugins"), AddonKind::Plugin));
+		}
+
+		let mut out = Vec::new();
+		for (dir, kind) in dirs {
+			if !dir.exists() {
+				continue;
+			}
+
+			for entry in dir.read_dir().context("Failed to read addon directory")? {
+				let path = entry?.path();
+				if !path.is_file() || managed.contains(&path) || !is_jar(&path) {
+					continue;
+				}
+
+				out.push((path, kind));
+			}
+		}
+
+		out.sort_by(|a, b| a.0.cmp(&b.0));
+
+		Ok(out)
+	}
+
+	/// Identifies the loose mod and plugin files in this instance using their hashes, so that they
+	/// can be replaced with packages
+	pub async fn identify_loose_files(
+		&self,
+		paths: &Paths,
+		client: &Client,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<LooseFiles> {
+		let mut files = Vec::new();
+		for (path, kind) in self.get_loose_addon_files(paths)? {
+			let data = std::fs::read(&path)
+				.with_context(|| format!("Failed to read file {}", path.display()))?;
+			files.push(LooseFile {
+				path,
+				kind,
+				sha512: hex::encode(Sha512::digest(&data)),
+				fingerprint: curseforge::compute_fingerprint(&data),
+			});
+		}
+
+		let mut out = LooseFiles::default();
+
+		// Modrinth is checked first since its projects can be used as packages directly
+		let hashes: Vec<_> = files.iter().map(|x| x.sha512.clone()).collect();
+		let versions = modrinth::get_versions_from_hashes(&hashes, client)
+			.await
+			.context("Failed to look up file hashes on Modrinth")?;
+
+		let mut remaining = Vec::new();
+		for file in files {
+			if let Some(version) = versions.get(&file.sha512) {
+				// Pin the package to the version that was installed so that adopting doesn't update it.
+				// Content versions of Modrinth packages have the same cleanup as when they are generated
+				let package = FullPackageConfig {
+					id: format!("modrinth:{}", version.project_id).into(),
+					features: Vec::new(),
+					use_default_features: true,
+					permissions: Default::default(),
+					stability: None,
+					worlds: Vec::new(),
+					content_version: Some(version.version_number.replace('+', "-")),
+					optional: false,
+					file: None,
+					on_conflict: None,
+					side: None,
+				};
+				out.identified.push(AdoptableFile {
+					path: file.path,
+					package: PackageConfigDeser::Full(Box::new(package)),
+				});
+			} else {
+				remaining.push(file);
+			}
+		}
+
+		// Files from CurseForge become packages that are pinned to the CurseForge download
+		let curseforge_matches = match std::env::var(CURSEFORGE_API_KEY_VAR) {
+			Ok(api_key) if !remaining.is_empty() => {
+				let fingerprints: Vec<_> = remaining.iter().map(|x| x.fingerprint).collect();
+				match curseforge::get_fingerprint_matches(&fingerprints, &api_key, client).await {
+					Ok(matches) => matches
+						.into_iter()
+						.map(|x| (x.file.file_fingerprint, x.file))
+						.collect(),
+					Err(e) => {
+						o.display(MessageContents::Warning(format!(
+							"Failed to look up files on CurseForge: {e:?}"
+						)));
+						HashMap::new()
+					}
+				}
+			}
+			_ => HashMap::new(),
+		};
+
+		for file in remaining {
+			let Some(curse_file) = curseforge_matches.get(&file.fingerprint) else {
+				out.unidentified.push(file.path);
+				continue;
+			};
+			// Authors can disable downloads from outside of CurseForge
+			let Some(url) = &curse_file.download_url else {
+				out.unidentified.push(file.path);
+				continue;
+			};
+
+			let package = FullPackageConfig {
+				id: format!("curseforge-{}", curse_file.mod_id).into(),
+				features: Vec::new(),
+				use_default_features: true,
+				permissions: Default::default(),
+				stability: None,
+				worlds: Vec::new(),
+				content_version: None,
+				optional: false,
+				file: Some(PinnedPackageFile {
+					url: Some(url.clone()),
+					path: None,
+					kind: file.kind,
+					hashes: AddonOptionalHashes {
+						sha256: None,
+						sha512: Some(file.sha512),
+					},
+					filename: Some(curse_file.file_name.clone()),
+					version: Some(curse_file.id.to_string()),
+				}),
+				on_conflict: None,
+				side: None,
+			};
+			out.identified.push(AdoptableFile {
+				path: file.path,
+				package: PackageConfigDeser::Full(Box::new(package)),
+			});
+		}
+
+		Ok(out)
+	}
+}
+
+/// Checks if a file is a JAR file
+fn is_jar(path: &Path) -> bool {
+	path.extension().is_some_and(|x| x == "jar")
+}
@@ -0,0 +1,107 @@
+use nitro_config::instance::{Args, InstanceConfig};
+use nitro_net::advisories::{Advisory, Mitigation, get_advisories};
+use nitro_shared::versions::VersionInfo;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::io::paths::Paths;
+
+use super::Instance;
+
+/// ID of the Log4Shell advisory, which is mitigated automatically at launch unless that is turned off
+const LOG4SHELL_ADVISORY_ID: &str = "CVE-2021-44228";
+
+/// A security advisory that affects an instance
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstanceAdvisory {
+	/// The advisory
+	pub advisory: Advisory,
+	/// The mitigation that can be applied to the instance, if any
+	pub mitigation: Option<Mitigation>,
+	/// Whether the mitigation is already applied to the instance
+	pub is_mitigated: bool,
+}
+
+impl Instance {
+	/// Gets the known security advisories that affect this instance with the given Minecraft
+	/// and loader versions
+	pub async fn get_advisories(
+		&self,
+		version_info: &VersionInfo,
+		loader_version: Option<&str>,
+		paths: &Paths,
+		client: &Client,
+	) -> anyhow::Result<Vec<InstanceAdvisory>> {
+		let advisories = get_advisories(&paths.internal.join("advisories.json"), client).await?;
+
+		let out = advisories
+			.into_iter()
+			.filter(|x| x.affects(version_info, &self.loader, loader_version))
+			.map(|advisory| {
+				let mitigation = advisory.get_mitigation(version_info, self.side()).cloned();
+				let is_mitigated = (advisory.id == LOG4SHELL_ADVISORY_ID
+					&& self.launch.log4shell_mitigation)
+					|| mitigation.as_ref().is_some_and(|x| self.has_mitigation(x));
+
+				InstanceAdvisory {
+					advisory,
+					mitigation,
+					is_mitigated,
+				}
+			})
+			.collect();
+
+		Ok(out)
+	}
+
+	/// Checks whether a mitigation is already applied to this instance's launch options
+	fn has_mitigation(&self, mitigation: &Mitigation) -> bool {
+		let has_args = mitigation
+			.jvm_args
+			.iter()
+			.all(|x| self.launch.jvm_args.contains(x));
+
+		has_args && (!mitigation.use_log4j_config || self.launch.use_log4j_config)
+	}
+}
+
+/// Applies a mitigation to an instance configuration, without adding arguments that are already there
+pub fn apply_mitigation(config: &mut InstanceConfig, mitigation: &Mitigation) {
+	let launch = &mut config.launch;
+	let existing_args = launch.args.jvm.parse();
+	let new_args: Vec<_> = mitigation
+		.jvm_args
+		.iter()
+		.filter(|x| !existing_args.contains(x))
+		.cloned()
+		.collect();
+	launch.args.jvm.merge(Args::List(new_args));
+
+	if mitigation.use_log4j_config {
+		launch.use_log4j_config = true;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_apply_mitigation() {
+		let mitigation = Mitigation {
+			minecraft_versions: Default::default(),
+			side: None,
+			description: String::new(),
+			jvm_args: vec!["-Dfoo=true".into()],
+			use_log4j_config: true,
+		};
+
+		let mut config = InstanceConfig::default();
+		config.launch.args.jvm = Args::String("-Xss1M".into());
+		apply_mitigation(&mut config, &mitigation);
+		apply_mitigation(&mut config, &mitigation);
+
+		assert_eq!(config.launch.args.jvm.parse(), vec!["-Xss1M", "-Dfoo=true"]);
+		assert!(config.launch.use_log4j_config);
+	}
+}
@@ -0,0 +1,273 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, ensure};
+use nitro_instance::lock::InstanceLockfile;
+use nitro_shared::output::NitroOutput;
+use nitro_shared::pkg::{PkgRequest, PkgRequestSource};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::io::paths::Paths;
+use crate::pkg::reg::PkgRegistry;
+
+/// The changes to an instance between two states of its lockfile, used to write release notes for modpacks
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Changelog {
+	/// The change to the Minecraft version
+	pub minecraft_version: Option<VersionChange>,
+	/// The change to the loader, including its version
+	pub loader: Option<VersionChange>,
+	/// Packages that were added
+	pub added: Vec<ChangelogPackage>,
+	/// Packages that were removed
+	pub removed: Vec<ChangelogPackage>,
+	/// Packages that changed versions
+	pub updated: Vec<ChangelogPackage>,
+}
+
+/// A change from one version to another
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VersionChange {
+	/// The old version
+	pub old: Option<String>,
+	/// The new version
+	pub new: Option<String>,
+}
+
+/// A package in a changelog
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChangelogPackage {
+	/// The package request, without a version
+	pub id: String,
+	/// The display name of the package, if it was looked up
+	pub name: Option<String>,
+	/// A link to the package, if it was looked up
+	pub link: Option<String>,
+	/// The content version before the change
+	pub old_version: Option<String>,
+	/// The content version after the change
+	pub new_version: Option<String>,
+}
+
+impl Changelog {
+	/// Compares two states of an instance lockfile
+	pub fn generate(old: &InstanceLockfile, new: &InstanceLockfile) -> Self {
+		let mut out = Self {
+			minecraft_version: compare(old.get_minecraft_version(), new.get_minecraft_version()),
+			..Default::default()
+		};
+
+		let old_loader = format_loader(old);
+		let new_loader = format_loader(new);
+		out.loader = compare(Some(&old_loader), Some(&new_loader));
+
+		let old_packages = old.get_packages();
+		let new_packages = new.get_packages();
+		let ids: BTreeSet<_> = old_packages.keys().chain(new_packages.keys()).collect();
+		for id in ids {
+			let old_version = old_packages.get(id).map(|x| x.content_version.clone());
+			let new_version = new_packages.get(id).map(|x| x.content_version.clone());
+			let package =
+				|old_version: Option<String>, new_version: Option<String>| ChangelogPackage {
+					id: id.clone(),
+					name: None,
+					link: None,
+					old_version,
+					new_version,
+				};
+
+			match (old_version, new_version) {
+				(None, Some(new_version)) => out.added.push(package(None, new_version)),
+				(Some(old_version), None) => out.removed.push(package(old_version, None)),
+				(Some(old_version), Some(new_version)) if old_version != new_version => {
+					out.updated.push(package(old_version, new_version))
+				}
+				_ => {}
+			}
+		}
+
+		out
+	}
+
+	/// Compares two lockfile snapshots on disk
+	pub fn from_files(old: &Path, new: &Path) -> anyhow::Result<Self> {
+		ensure!(old.exists(), "Lockfile {} does not exist", old.display());
+		ensure!(new.exists(), "Lockfile {} does not exist", new.display());
+		let old = InstanceLockfile::open(old).context("Failed to open old lockfile")?;
+		let new = InstanceLockfile::open(new).context("Failed to open new lockfile")?;
+
+		Ok(Self::generate(&old, &new))
+	}
+
+	/// Checks if nothing changed
+	pub fn is_empty(&self) -> bool {
+		self.minecraft_version.is_none()
+			&& self.loader.is_none()
+			&& self.added.is_empty()
+			&& self.removed.is_empty()
+			&& self.updated.is_empty()
+	}
+
+	/// Looks up the names and links of the packages in the changelog. Packages that
+	/// can't be found are left as they are
+	pub async fn fill_package_info(
+		&mut self,
+		reg: &PkgRegistry,
+		paths: &Paths,
+		client: &Client,
+		o: &mut impl NitroOutput,
+	) {
+		let packages = self
+			.added
+			.iter_mut()
+			.chain(self.removed.iter_mut())
+			.chain(self.updated.iter_mut());
+		for package in packages {
+			let req = Arc::new(PkgRequest::parse(
+				&package.id,
+				PkgRequestSource::UserRequire,
+			));
+			let Ok(pkg) = reg.get(&req, paths, client, o).await else {
+				continue;
+			};
+			let Ok(meta) = pkg.get_metadata(paths, client).await else {
+				continue;
+			};
+
+			package.name = meta.name.clone();
+			package.link = meta.website.clone().or(meta.source.clone());
+		}
+	}
+
+	/// Formats the changelog as Markdown
+	pub fn to_markdown(&self) -> String {
+		let mut out = String::new();
+		if let Some(change) = &self.minecraft_version {
+			let _ = writeln!(out, "- Minecraft version: {}", format_change(change));
+		}
+		if let Some(change) = &self.loader {
+			let _ = writeln!(out, "- Loader: {}", format_change(change));
+		}
+
+		let sections = [
+			("Added", &self.added),
+			("Removed", &self.removed),
+			("Updated", &self.updated),
+		];
+		for (title, packages) in sections {
+			if packages.is_empty() {
+				continue;
+			}
+
+			if !out.is_empty() {
+				out.push('\n');
+			}
+			let _ = writeln!(out, "## {title}\n");
+			for package in packages {
+				let name = package.name.as_ref().unwrap_or(&package.id);
+				let name = if let Some(link) = &package.link {
+					format!("[{name}]({link})")
+				} else {
+					name.clone()
+				};
+				let change = VersionChange {
+					old: package.old_version.clone(),
+					new: package.new_version.clone(),
+				};
+				let _ = writeln!(out, "- {name}: {}", format_change(&change));
+			}
+		}
+
+		if out.is_empty() {
+			out.push_str("No changes\n");
+		}
+
+		out
+	}
+}
+
+fn compare(old: Option<&String>, new: Option<&String>) -> Option<VersionChange> {
+	if old == new {
+		None
+	} else {
+		Some(VersionChange {
+			old: old.cloned(),
+			new: new.cloned(),
+		})
+	}
+}
+
+fn format_loader(lock: &InstanceLockfile) -> String {
+	let loader = lock.get_loader().to_string();
+	if let Some(version) = lock.get_loader_version() {
+		format!("{loader} {version}")
+	} else {
+		loader
+	}
+}
+
+fn format_change(change: &VersionChange) -> String {
+	match (&change.old, &change.new) {
+		(Some(old), Some(new)) => format!("{old} -> {new}"),
+		(None, Some(new)) => new.clone(),
+		(Some(old), None) => old.clone(),
+		(None, None) => "any version".into(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_changelog() {
+		let dir = std::env::temp_dir().join("nitro_test_changelog");
+		let _ = std::fs::remove_dir_all(&dir);
+		std::fs::create_dir_all(&dir).unwrap();
+		std::fs::write(
+			dir.join("old.json"),
+			r#"{"minecraft_version":"1.21.1","loader":"fabric","loader_version":"0.16.0","packages":{
+				"sodium":{"content_version":"0.5"},
+				"lithium":{"content_version":"0.12"},
+				"modrinth:iris":{"content_version":"1.7"}
+			}}"#,
+		)
+		.unwrap();
+		std::fs::write(
+			dir.join("new.json"),
+			r#"{"minecraft_version":"1.21.1","loader":"fabric","loader_version":"0.16.5","packages":{
+				"sodium":{"content_version":"0.6"},
+				"lithium":{"content_version":"0.12"},
+				"ferritecore":{"content_version":"7.0"}
+			}}"#,
+		)
+		.unwrap();
+
+		let old = InstanceLockfile::open(&dir.join("old.json")).unwrap();
+		let new = InstanceLockfile::open(&dir.join("new.json")).unwrap();
+		let changelog = Changelog::generate(&old, &new);
+
+		assert!(changelog.minecraft_version.is_none());
+		assert_eq!(
+			changelog.loader,
+			Some(VersionChange {
+				old: Some("Fabric 0.16.0".into()),
+				new: Some("Fabric 0.16.5".into()),
+			})
+		);
+		assert_eq!(changelog.added.len(), 1);
+		assert_eq!(changelog.added[0].id, "ferritecore");
+		assert_eq!(changelog.removed[0].id, "modrinth:iris");
+		assert_eq!(changelog.updated.len(), 1);
+		assert_eq!(changelog.updated[0].new_version.as_deref(), Some("0.6"));
+
+		let markdown = changelog.to_markdown();
+		assert!(markdown.contains("- sodium: 0.5 -> 0.6"));
+		assert!(!markdown.contains("lithium"));
+
+		let _ = std::fs::remove_dir_all(&dir);
+	}
+}
@@ -0,0 +1,276 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, bail};
+use nitro_config::instance::InstanceConfig;
+use nitro_core::account::AccountManager;
+use nitro_core::io::logs::{censor_log, censor_secrets};
+use nitro_net::download::Client;
+use nitro_shared::output::NitroOutput;
+use nitro_shared::util::{ARCH_STRING, OS_STRING, utc_timestamp};
+use serde::Serialize;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+use crate::io::paste::upload_paste;
+use crate::io::paths::Paths;
+use crate::plugin::PluginManager;
+
+use super::Instance;
+
+/// Metadata about an instance that is included in a crash bundle
+#[derive(Serialize)]
+struct CrashBundleMetadata<'a> {
+	instance_id: &'a str,
+	nitrolaunch_version: &'a str,
+	os: &'a str,
+	arch: &'a str,
+	exit_code: Option<i32>,
+	config: &'a InstanceConfig,
+}
+
+impl Instance {
+	/// Exports a crash bundle for this instance to the given path. The access tokens of the
+	/// accounts are censored from it
+	pub fn export_crash_bundle(
+		&self,
+		accounts: &AccountManager,
+		path: &Path,
+	) -> anyhow::Result<()> {
+		let Some(inst_dir) = &self.dir else {
+			bail!("Instance does not have a directory to collect crash information from");
+		};
+
+		let secrets = accounts.get_secrets();
+		create_crash_bundle(&self.id, inst_dir, &self.config, None, &secrets, path)
+	}
+
+	/// Uploads the contents of a crash bundle for this instance to a paste backend, returning the URL
+	pub async fn upload_crash_bundle(
+		&self,
+		backend: Option<&str>,
+		accounts: &AccountManager,
+		plugins: &PluginManager,
+		client: &Client,
+		paths: &Paths,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<String> {
+		let Some(inst_dir) = &self.dir else {
+			bail!("Instance does not have a directory to collect crash information from");
+		};
+
+		let secrets = accounts.get_secrets();
+		let text = get_crash_bundle_text(&self.id, inst_dir, &self.config, None, &secrets)?;
+		let name = format!("{}-crash", self.id);
+		upload_paste(&name, &text, backend, plugins, client, paths, o).await
+	}
+}
+
+/// Creates a zip file containing the information needed to diagnose a crashed instance:
+/// JVM fatal error logs, the latest game log and crash report, and metadata about the instance.
+/// The secrets, along with anything else that looks like account credentials, are censored from every file
+pub fn create_crash_bundle(
+	instance_id: &str,
+	inst_dir: &Path,
+	config: &InstanceConfig,
+	exit_code: Option<i32>,
+	secrets: &[String],
+	path: &Path,
+) -> anyhow::Result<()> {
+	let files = get_crash_files(inst_dir)?;
+
+	let file = File::create(path).context("Failed to create crash bundle file")?;
+	let mut zip = ZipWriter::new(file);
+
+	for (name, path) in files {
+		let contents = read_crash_file(&name, &path, secrets)?;
+		zip.start_file(name, SimpleFileOptions::default())?;
+		zip.write_all(contents.as_bytes())?;
+	}
+
+	let metadata = get_crash_bundle_metadata(instance_id, config, exit_code, secrets)?;
+	zip.start_file("instance.json", SimpleFileOptions::default())?;
+	zip.write_all(metadata.as_bytes())?;
+
+	zip.finish().context("Failed to finish crash bundle")?;
+
+	Ok(())
+}
+
+/// Gets the contents of a crash bundle as a single text document that can be uploaded to a paste service
+pub fn get_crash_bundle_text(
+	instance_id: &str,
+	inst_dir: &Path,
+	config: &InstanceConfig,
+	exit_code: Option<i32>,
+	secrets: &[String],
+) -> anyhow::Result<String> {
+	let mut out = String::new();
+	out.push_str("==== instance.json ====\n");
+	out.push_str(&get_crash_bundle_metadata(
+		instance_id,
+		config,
+		exit_code,
+		secrets,
+	)?);
+
+	for (name, path) in get_crash_files(inst_dir)? {
+		out.push_str(&format!("\n\n==== {name} ====\n"));
+		out.push_str(&read_crash_file(&name, &path, secrets)?);
+	}
+
+	Ok(out)
+}
+
+/// Checks if the game in an instance directory crashed since a time, which is when it left a
+/// JVM fatal error log or a crash report
+pub fn has_crashed_since(inst_dir: &Path, time: SystemTime) -> bool {
+	let Ok(files) = get_crash_files(inst_dir) else {
+		return false;
+	};
+
+	files.iter().any(|(name, path)| {
+		name != "latest.log"
+			&& path
+				.metadata()
+				.and_then(|x| x.modified())
+				.is_ok_and(|x| x >= time)
+	})
+}
+
+/// Gets the files to include in a crash bundle, along with their names in the bundle
+fn get_crash_files(inst_dir: &Path) -> anyhow::Result<Vec<(String, PathBuf)>> {
+	let mut files = Vec::new();
+
+	// The JVM writes fatal error logs to the working directory of the process
+	let entries = inst_dir
+		.read_dir()
+		.context("Failed to read instance directory")?;
+	for entry in entries {
+		let entry = entry?;
+		let name = entry.file_name().to_string_lossy().to_string();
+		if name.starts_with("hs_err_pid") && name.ends_with(".log") {
+			files.push((name, entry.path()));
+		}
+	}
+
+	let latest_log = inst_dir.join("logs").join("latest.log");
+	if latest_log.exists() {
+		files.push(("latest.log".into(), latest_log));
+	}
+
+	if let Some(crash_report) = get_newest_file(&inst_dir.join("crash-reports")) {
+		let name = crash_report
+			.file_name()
+			.unwrap_or_default()
+			.to_string_lossy();
+		files.push((format!("crash-reports/{name}"), crash_report.clone()));
+	}
+
+	Ok(files)
+}
+
+/// Reads a file for a crash bundle with credentials censored
+fn read_crash_file(name: &str, path: &Path, secrets: &[String]) -> anyhow::Result<String> {
+	let contents = std::fs::read(path).with_context(|| format!("Failed to read file {name}"))?;
+	Ok(censor(&String::from_utf8_lossy(&contents), secrets))
+}
+
+/// Censors the known secrets and anything else that looks like account credentials from text
+fn censor(text: &str, secrets: &[String]) -> String {
+	censor_log(&censor_secrets(text, secrets))
+}
+
+/// Gets the serialized metadata for a crash bundle
+fn get_crash_bundle_metadata(
+	instance_id: &str,
+	config: &InstanceConfig,
+	exit_code: Option<i32>,
+	secrets: &[String],
+) -> anyhow::Result<String> {
+	let metadata = CrashBundleMetadata {
+		instance_id,
+		nitrolaunch_version: crate::VERSION,
+		os: OS_STRING,
+		arch: ARCH_STRING,
+		exit_code,
+		config,
+	};
+	let metadata =
+		serde_json::to_string_pretty(&metadata).context("Failed to serialize instance metadata")?;
+
+	Ok(censor(&metadata, secrets))
+}
+
+/// Gets the path to store an automatically created crash bundle for an instance at
+pub fn get_crash_bundle_path(instance_id: &str, paths: &Paths) -> PathBuf {
+	let timestamp = utc_timestamp().unwrap_or_default();
+	paths
+		.crash_bundles
+		.join(format!("{instance_id}-{timestamp}.zip"))
+}
+
+/// Gets the most recently modified file in a directory
+pub(crate) fn get_newest_file(dir: &Path) -> Option<PathBuf> {
+	let entries = dir.read_dir().ok()?;
+	entries
+		.filter_map(|x| {
+			let x = x.ok()?;
+			let modified = x.metadata().ok()?.modified().ok()?;
+			x.file_type().ok()?.is_file().then(|| (x.path(), modified))
+		})
+		.max_by_key(|x| x.1)
+		.map(|x| x.0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_crash_bundle() {
+		let dir = std::env::temp_dir().join("nitro_crash_bundle_test");
+		let _ = std::fs::remove_dir_all(&dir);
+		std::fs::create_dir_all(dir.join("logs")).unwrap();
+		std::fs::write(dir.join("hs_err_pid1234.log"), "SIGSEGV").unwrap();
+		std::fs::write(
+			dir.join("logs").join("latest.log"),
+			"--accessToken eyJhbGc.eyJzdWIi.c2lnbmF0dXJl --xuid opaque-token",
+		)
+		.unwrap();
+		let secrets = vec!["opaque-token".to_string()];
+
+		let path = dir.join("bundle.zip");
+		create_crash_bundle(
+			"test",
+			&dir,
+			&InstanceConfig::default(),
+			Some(1),
+			&secrets,
+			&path,
+		)
+		.unwrap();
+
+		let mut zip = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+		assert!(zip.by_name("hs_err_pid1234.log").is_ok());
+		assert!(zip.by_name("instance.json").is_ok());
+		let log = std::io::read_to_string(zip.by_name("latest.log").unwrap()).unwrap();
+		assert_eq!(log, "--accessToken *** --xuid ***");
+
+		let text = get_crash_bundle_text("test", &dir, &InstanceConfig::default(), None, &secrets)
+			.unwrap();
+		assert!(text.contains("==== hs_err_pid1234.log ====\nSIGSEGV"));
+		assert!(!text.contains("eyJ"));
+		assert!(!text.contains("opaque-token"));
+
+		// The fatal error log was just written, so the game counts as having crashed
+		let launch_time = SystemTime::now() - std::time::Duration::from_secs(60);
+		assert!(has_crashed_since(&dir, launch_time));
+		let later = SystemTime::now() + std::time::Duration::from_secs(60);
+		assert!(!has_crashed_since(&dir, later));
+
+		let _ = std::fs::remove_dir_all(&dir);
+	}
+}
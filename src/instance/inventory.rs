@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use anyhow::Context;
+use nitro_instance::lock::{InstanceLockfile, LockfileAddon, LockfileJava};
+use nitro_shared::Side;
+use nitro_shared::loaders::Loader;
+use nitro_shared::minecraft::AddonKind;
+use nitro_shared::pkg::{AddonHashes, AddonOptionalHashes};
+use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Sha512};
+
+use crate::io::paths::Paths;
+use crate::util::hash::digest_reader;
+
+use super::Instance;
+
+/// A complete, machine-readable list of everything that is installed on an instance,
+/// for support requests and audits
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstanceInventory {
+	/// The ID of the instance
+	pub id: String,
+	/// The side of the instance
+	pub side: Side,
+	/// The version of Nitrolaunch that created the inventory
+	pub nitrolaunch_version: String,
+	/// The installed Minecraft version
+	pub minecraft_version: Option<String>,
+	/// The installed loader
+	pub loader: Loader,
+	/// The installed loader version
+	pub loader_version: Option<String>,
+	/// The configured kind of Java installation
+	pub java_kind: String,
+	/// The Java installation the instance was last set up with
+	pub java: Option<LockfileJava>,
+	/// The installed modpack, if any
+	pub modpack: Option<String>,
+	/// The installed packages, sorted by ID
+	pub packages: Vec<InventoryPackage>,
+	/// Addons that were installed from the modpack
+	pub modpack_addons: Vec<InventoryAddon>,
+	/// Addon files that were added by hand and aren't managed by any package
+	pub unmanaged_files: Vec<InventoryFile>,
+	/// Addon files that are recorded as installed but were deleted
+	pub missing_files: Vec<String>,
+}
+
+/// A package in an inventory
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InventoryPackage {
+	/// The package request, without a version
+	pub id: String,
+	/// The exact content version that is installed
+	pub content_version: Option<String>,
+	/// The addons that the package installed
+	pub addons: Vec<InventoryAddon>,
+}
+
+/// An addon in an inventory
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InventoryAddon {
+	/// The ID of the addon
+	pub id: Option<String>,
+	/// The kind of the addon
+	pub kind: AddonKind,
+	/// The file name of the addon
+	pub file_name: String,
+	/// The paths the addon is installed to
+	pub files: Vec<String>,
+	/// The hashes of the addon
+	#[serde(default)]
+	#[serde(skip_serializing_if = "AddonOptionalHashes::is_empty")]
+	pub hashes: AddonOptionalHashes,
+}
+
+/// A file that isn't managed by a package in an inventory
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InventoryFile {
+	/// The path to the file
+	pub path: String,
+	/// The size of the file in bytes. Directories have no size
+	pub size: Option<u64>,
+	/// The hashes of the file. Directories have no hashes
+	#[serde(default)]
+	#[serde(skip_serializing_if = "AddonOptionalHashes::is_empty")]
+	pub hashes: AddonOptionalHashes,
+}
+
+impl Instance {
+	/// Creates an inventory of everything that is installed on this instance
+	pub fn get_inventory(&self, paths: &Paths) -> anyhow::Result<InstanceInventory> {
+		let lock = self.get_lockfile(paths)?;
+		let content = self
+			.check_content(paths)
+			.context("Failed to check instance files")?;
+
+		let mut inventory = inventory_from_lockfile(&lock);
+		inventory.id = self.id.to_string();
+		inventory.side = self.side();
+		inventory.java_kind = self
+			.config
+			.launch
+			.java
+			.clone()
+			.unwrap_or_else(|| "auto".into());
+
+		for path in content.unmanaged {
+			inventory.unmanaged_files.push(
+				get_file_info(&path)
+					.with_context(|| format!("Failed to inspect file {}", path.display()))?,
+			);
+		}
+		inventory.missing_files = content
+			.missing
+			.into_iter()
+			.map(|x| x.path.to_string_lossy().to_string())
+			.collect();
+
+		Ok(inventory)
+	}
+}
+
+/// Fills out the parts of an inventory that come from the lockfile of an instance
+fn inventory_from_lockfile(lock: &InstanceLockfile) -> InstanceInventory {
+	let mut packages = BTreeMap::new();
+	for (id, package) in lock.get_packages() {
+		packages.insert(
+			id.clone(),
+			InventoryPackage {
+				id: id.clone(),
+				content_version: package.content_version.clone(),
+				addons: Vec::new(),
+			},
+		);
+	}
+
+	let mut modpack_addons = Vec::new();
+	for addon in lock.get_addons() {
+		if let Some(package) = addon.package.as_ref().and_then(|x| packages.get_mut(x)) {
+			package.addons.push(addon.into());
+		} else if addon.from_modpack {
+			modpack_addons.push(addon.into());
+		}
+	}
+
+	InstanceInventory {
+		id: String::new(),
+		side: Side::Client,
+		nitrolaunch_version: crate::VERSION.to_string(),
+		minecraft_version: lock.get_minecraft_version().cloned(),
+		loader: lock.get_loader().clone(),
+		loader_version: lock.get_loader_version().cloned(),
+		java_kind: String::new(),
+		java: lock.get_java().cloned(),
+		modpack: lock.get_modpack().map(|x| x.name.clone()),
+		packages: packages.into_values().collect(),
+		modpack_addons,
+		unmanaged_files: Vec::new(),
+		missing_files: Vec::new(),
+	}
+}
+
+impl From<&LockfileAddon> for InventoryAddon {
+	fn from(addon: &LockfileAddon) -> Self {
+		Self {
+			id: addon.id.clone(),
+			kind: addon.kind,
+			file_name: addon.file_name.clone(),
+			files: addon.files.clone(),
+			hashes: addon.hashes.clone(),
+		}
+	}
+}
+
+/// Gets the size and hashes of a file
+fn get_file_info(path: &Path) -> anyhow::Result<InventoryFile> {
+	let path_str = path.to_string_lossy().to_string();
+	if path.is_dir() {
+		return Ok(InventoryFile {
+			path: path_str,
+			size: None,
+			hashes: AddonHashes::default(),
+		});
+	}
+
+	let size = path.metadata()?.len();
+	let sha256 = digest_reader::<Sha256, _>(BufReader::new(File::open(path)?))?;
+	let sha512 = digest_reader::<Sha512, _>(BufReader::new(File::open(path)?))?;
+
+	Ok(InventoryFile {
+		path: path_str,
+		size: Some(size),
+		hashes: AddonHashes {
+			sha256: Some(hex::encode(sha256)),
+			sha512: Some(hex::encode(sha512)),
+		},
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_file_info() {
+		let dir = std::env::temp_dir().join("nitro_test_inventory_file_info");
+		let _ = std::fs::remove_dir_all(&dir);
+		std::fs::create_dir_all(dir.join("pack")).unwrap();
+		std::fs::write(dir.join("mod.jar"), "abc").unwrap();
+
+		let file = get_file_info(&dir.join("mod.jar")).unwrap();
+		assert_eq!(file.size, Some(3));
+		assert_eq!(
+			file.hashes.sha256.as_deref(),
+			Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+		);
+
+		let pack = get_file_info(&dir.join("pack")).unwrap();
+		assert!(pack.size.is_none());
+		assert!(pack.hashes.is_empty());
+
+		let _ = std::fs::remove_dir_all(&dir);
+	}
+}
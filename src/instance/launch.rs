@@ -1,30 +1,39 @@
 use nitro_core::io::files::open_file_append;
-use nitro_core::launch::get_stdio_file_path;
+use nitro_core::io::minecraft::game_jar::extract_version_json_optional;
+use nitro_core::launch::{PreparedLaunch, SERVER_READY_MESSAGE, get_stdio_file_path};
 use nitro_core::{NitroCore, QuickPlayType};
+use nitro_net::download;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::ExitStatus;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 use sysinfo::{Pid, System};
 
 use anyhow::{Context, bail};
-use nitro_config::instance::{QuickPlay, WrapperCommand};
+use nitro_config::instance::{
+	InstanceConfig, JavaAgentConfig, LaunchBrandingConfig, QuickPlay, WrapperCommand,
+};
 use nitro_core::account::{AccountID, AccountManager};
 use nitro_core::io::java::install::JavaInstallationKind;
 use nitro_plugin::hook::call::HookHandles;
 use nitro_plugin::hook::hooks::{
 	InstanceLaunchArg, OnInstanceLaunch, OnInstanceStop, ReplaceInstanceLaunch, WhileInstanceLaunch,
 };
+use nitro_shared::error::{LaunchError, NitroError, NitroResult};
 use nitro_shared::id::InstanceID;
 use nitro_shared::java_args::MemoryNum;
+use nitro_shared::minecraft::{GpuPreference, JvmPreset, NativesArch};
 use nitro_shared::output::{MessageContents, NitroOutput};
 use nitro_shared::{Side, UpdateDepth, translate};
 use tokio::io::{AsyncWriteExt, Stdout};
 
 use super::tracking::RunningInstanceRegistry;
 use super::update::manager::UpdateManager;
+use crate::config::secrets::{contains_encrypted, decrypt_json, get_passphrase};
+use crate::instance::crash::{create_crash_bundle, get_crash_bundle_path, has_crashed_since};
+use crate::instance::network::ServerBinding;
 use crate::instance::tracking::{RunningInstanceEntry, is_process_alive};
 use crate::instance::update::manager::UpdateSettings;
 use crate::instance::update::{InstanceUpdateContext, UpdateFacets};
@@ -40,7 +49,11 @@ impl Instance {
 		&mut self,
 		settings: LaunchSettings,
 		ctx: &mut InstanceUpdateContext<'a, O>,
-	) -> anyhow::Result<InstanceHandle> {
+	) -> NitroResult<InstanceHandle> {
+		self.decrypt_config(ctx.plugins, ctx.output)
+			.await
+			.context("Failed to decrypt instance config")?;
+
 		let manager = UpdateManager::from_settings(UpdateSettings {
 			depth: UpdateDepth::Shallow,
 			offline_auth: settings.offline_auth,
@@ -71,6 +84,28 @@ impl Instance {
 		// Make sure that any fluff from the update gets ended
 		ctx.output.end_process();
 
+		self.resolve_network_conflicts(ctx.paths, ctx.output)
+			.context("Failed to check for network conflicts")?;
+
+		// The server can't be reached in offline mode, so there's no use checking it
+		if !download::is_offline()
+			&& let Some((server, port)) = self.get_quick_play_server(&settings)
+		{
+			// The protocol is read from the game jar, which may not have it for old versions
+			let protocol = extract_version_json_optional(&version_info, &ctx.core.get_paths().jars)
+				.ok()
+				.flatten()
+				.and_then(|x| x.protocol_version);
+			self.check_quick_play_server(
+				&server,
+				port,
+				&version_info.version,
+				protocol,
+				ctx.output,
+			)
+			.await;
+		}
+
 		ctx.output.display(MessageContents::Simple(translate!(
 			ctx.output,
 			PreparingLaunch
@@ -95,14 +130,121 @@ impl Instance {
 				ctx.output,
 			)
 			.await
+			.map_err(NitroError::from)
 		} else {
 			let account = ctx
 				.accounts
 				.get_chosen_account()
 				.map(|x| x.get_id().clone());
-			self.launch_custom(hook_arg, account, ctx.paths, ctx.plugins, ctx.output)
-				.await
+			let secrets = ctx.accounts.get_secrets();
+			self.launch_custom(
+				hook_arg,
+				account,
+				secrets,
+				ctx.paths,
+				ctx.plugins,
+				ctx.output,
+			)
+			.await
+			.map_err(NitroError::from)
+		}
+	}
+
+	/// Gets the server that this instance will Quick Play into when launched, if any
+	fn get_quick_play_server(&self, settings: &LaunchSettings) -> Option<(String, Option<u16>)> {
+		if self.side() != Side::Client {
+			return None;
+		}
+
+		match &settings.quick_play {
+			Some(QuickPlayType::Server { server, port }) => Some((server.clone(), *port)),
+			Some(..) => None,
+			None => match &self.launch.quick_play {
+				QuickPlay::Server { server, port } => Some((server.clone(), *port)),
+				_ => None,
+			},
+		}
+	}
+
+	/// Decrypts any encrypted values in the config of this instance and in the configs of plugins
+	/// so that they can be used for launching. The decrypted instance config is only used for the
+	/// launch options, so that the config given to hooks and crash bundles stays encrypted
+	async fn decrypt_config(
+		&mut self,
+		plugins: &PluginManager,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<()> {
+		let mut config =
+			serde_json::to_value(&self.config).context("Failed to serialize instance config")?;
+		let is_instance_encrypted = contains_encrypted(&config);
+		let plugin_config = plugins.get_encrypted_config().await;
+		if !is_instance_encrypted && plugin_config.is_none() {
+			return Ok(());
+		}
+
+		let check_against = if is_instance_encrypted {
+			Some(&config)
+		} else {
+			plugin_config.as_ref()
+		};
+		let passphrase = get_passphrase(check_against, o).await?;
+
+		if is_instance_encrypted {
+			decrypt_json(&mut config, &passphrase)?;
+			let config: InstanceConfig = serde_json::from_value(config)
+				.context("Failed to read decrypted instance config")?;
+			self.launch = super::launch_config_to_options(config.launch)?;
 		}
+
+		plugins
+			.decrypt_configs(&passphrase)
+			.await
+			.context("Failed to decrypt plugin configs")?;
+
+		Ok(())
+	}
+
+	/// Update the instance and resolve the command that would launch it, without spawning the process
+	/// or running any launch hooks. Only works for instances that are launched normally
+	pub async fn prepare_launch<'a, O: NitroOutput>(
+		&mut self,
+		settings: LaunchSettings,
+		ctx: &mut InstanceUpdateContext<'a, O>,
+	) -> NitroResult<PreparedLaunch> {
+		if self.dir.is_none() || self.config.custom_launch {
+			return Err(anyhow::anyhow!(
+				"This instance is launched by a plugin, so its launch command can't be prepared"
+			)
+			.into());
+		}
+
+		self.decrypt_config(ctx.plugins, ctx.output)
+			.await
+			.context("Failed to decrypt instance config")?;
+
+		self.update(UpdateDepth::Shallow, UpdateFacets::all(), ctx)
+			.await
+			.context("Failed to update instance")?;
+		ctx.output.end_process();
+
+		let core_version = ctx
+			.core
+			.get_version(&self.version, UpdateDepth::Shallow, ctx.output)
+			.await?;
+
+		let mut instance = self
+			.create_core_instance(&core_version, ctx.paths, ctx.output)
+			.await
+			.context("Failed to create core instance")?;
+
+		instance
+			.prepare_launch(
+				ctx.accounts,
+				settings.offline_auth,
+				settings.quick_play,
+				ctx.output,
+			)
+			.await
 	}
 
 	/// Standard Java launch
@@ -130,11 +272,12 @@ impl Instance {
 
 		instance.pipe_stdin(settings.pipe_stdin);
 
+		let launch_time = SystemTime::now();
 		// Launch the instance using core
 		let handle = instance
 			.launch_with_handle(accounts, settings.offline_auth, settings.quick_play, o)
 			.await
-			.context("Failed to launch core instance")?;
+			.map_err(|e| e.context("Failed to launch core instance"))?;
 
 		hook_arg.pid = Some(handle.get_pid());
 		hook_arg.classpath = Some(handle.classpath().get_str());
@@ -163,6 +306,8 @@ impl Instance {
 			stdout: tokio::io::stdout(),
 			is_silent: false,
 			account: selected_account.clone(),
+			secrets: accounts.get_secrets(),
+			launch_time,
 			inner: InstanceHandleInner::Standard {
 				inner: handle,
 				world_files,
@@ -179,10 +324,13 @@ impl Instance {
 		&mut self,
 		mut hook_arg: InstanceLaunchArg,
 		selected_account: Option<AccountID>,
+		secrets: Vec<String>,
 		paths: &Paths,
 		plugins: &PluginManager,
 		o: &mut impl NitroOutput,
 	) -> anyhow::Result<InstanceHandle> {
+		let launch_time = SystemTime::now();
+
 		// Set up stdio
 		let stdout_path = get_stdio_file_path(&paths.core, false);
 		let stdin_path = get_stdio_file_path(&paths.core, true);
@@ -195,7 +343,7 @@ impl Instance {
 			.context("Failed to call custom launch hook")?;
 
 		let Some(result) = result.first_some(o).await? else {
-			bail!("No plugins handled custom launch for this instance");
+			bail!(LaunchError::CustomLaunchNotHandled);
 		};
 
 		hook_arg.pid = Some(result.pid);
@@ -222,6 +370,8 @@ impl Instance {
 			stdout: tokio::io::stdout(),
 			is_silent: false,
 			account: selected_account.clone(),
+			secrets,
+			launch_time,
 			inner: InstanceHandleInner::Plugin {
 				pid: result.pid,
 				stdout_file,
@@ -268,6 +418,22 @@ pub struct LaunchOptions {
 	pub quick_play: QuickPlay,
 	/// Whether or not to use the Log4J configuration
 	pub use_log4j_config: bool,
+	/// Whether to apply compatibility fixes for old versions
+	pub legacy_fixes: bool,
+	/// Whether to automatically protect versions that are affected by Log4Shell
+	pub log4shell_mitigation: bool,
+	/// Whether to launch the game in demo mode
+	pub demo: bool,
+	/// Architecture to select native libraries for
+	pub natives_arch: Option<NativesArch>,
+	/// Java agents to attach to the game
+	pub java_agents: Vec<JavaAgentConfig>,
+	/// The GPU to prefer for the client
+	pub gpu_preference: Option<GpuPreference>,
+	/// Preset of tuned JVM arguments
+	pub jvm_preset: Option<JvmPreset>,
+	/// Overrides for the launcher branding
+	pub branding: LaunchBrandingConfig,
 }
 
 /// A handle for an instance
@@ -284,6 +450,10 @@ pub struct InstanceHandle {
 	is_silent: bool,
 	/// The account that launched this instance
 	account: Option<String>,
+	/// Secrets like account access tokens to censor from crash bundles
+	secrets: Vec<String>,
+	/// When the instance was launched
+	launch_time: SystemTime,
 	/// Inner implementation
 	inner: InstanceHandleInner,
 }
@@ -331,11 +501,21 @@ impl InstanceHandle {
 					.to_string(),
 			),
 			account: self.account.clone(),
+			server_binding: self.get_server_binding(),
 		};
 		registry.add_instance(entry);
 		registry.write()
 	}
 
+	/// Gets the address and port that the launched server is listening on
+	fn get_server_binding(&self) -> Option<ServerBinding> {
+		if self.hook_arg.side != Some(Side::Server) {
+			return None;
+		}
+		let dir = self.hook_arg.inst_dir.as_ref()?;
+		ServerBinding::from_dir(Path::new(dir)).ok()
+	}
+
 	/// Waits for the process to complete
 	pub async fn wait(
 		mut self,
@@ -392,6 +572,14 @@ impl InstanceHandle {
 			tokio::time::sleep(Duration::from_millis(5)).await;
 		};
 
+		// Only actual crashes get a bundle, and not every time the game exits with an error
+		if !status.success()
+			&& let Some(inst_dir) = &self.hook_arg.inst_dir
+			&& has_crashed_since(Path::new(inst_dir), self.launch_time)
+		{
+			self.create_crash_bundle(status, paths, o);
+		}
+
 		// Terminate any sibling processes now that the main one is complete
 		self.hook_handles.terminate().await;
 
@@ -409,6 +597,68 @@ impl InstanceHandle {
 		Ok(status)
 	}
 
+	/// Creates a crash bundle after the instance crashes
+	fn create_crash_bundle(&self, status: ExitStatus, paths: &Paths, o: &mut impl NitroOutput) {
+		let Some(inst_dir) = &self.hook_arg.inst_dir else {
+			return;
+		};
+
+		let path = get_crash_bundle_path(&self.instance_id, paths);
+		let result = create_crash_bundle(
+			&self.instance_id,
+			Path::new(inst_dir),
+			&self.hook_arg.config,
+			status.code(),
+			&self.secrets,
+			&path,
+		);
+		match result {
+			Ok(()) => {
+				let message = translate!(o, CrashBundleCreated, "path" = &path.to_string_lossy());
+				o.display(MessageContents::Warning(message));
+			}
+			Err(e) => o.display(MessageContents::Error(format!(
+				"Failed to create crash bundle: {e:?}"
+			))),
+		}
+	}
+
+	/// Waits until a server has finished starting by watching its output for the ready message
+	pub async fn wait_until_ready(&mut self, timeout: Duration) -> anyhow::Result<()> {
+		let mut stdout = File::open(self.stdout()).context("Failed to open instance output")?;
+		let mut output = Vec::new();
+		let start = Instant::now();
+		loop {
+			stdout
+				.read_to_end(&mut output)
+				.context("Failed to read instance output")?;
+			if String::from_utf8_lossy(&output).contains(SERVER_READY_MESSAGE) {
+				return Ok(());
+			}
+
+			if !self.is_running() {
+				bail!("Server exited before it finished starting");
+			}
+			if start.elapsed() > timeout {
+				bail!("Timed out waiting for server to finish starting");
+			}
+
+			tokio::time::sleep(Duration::from_millis(250)).await;
+		}
+	}
+
+	/// Checks whether the instance process is still running
+	pub fn is_running(&mut self) -> bool {
+		match &mut self.inner {
+			InstanceHandleInner::Standard { inner, .. } => matches!(inner.try_wait(), Ok(None)),
+			InstanceHandleInner::Plugin { pid, .. } => {
+				let mut system = System::new();
+				system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+				is_process_alive(*pid, &system, false)
+			}
+		}
+	}
+
 	/// Kills the process early
 	pub async fn kill(
 		self,
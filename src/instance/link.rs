@@ -0,0 +1,180 @@
+use anyhow::{bail, ensure};
+use nitro_config::instance::InstanceConfig;
+use nitro_shared::Side;
+use nitro_shared::id::InstanceID;
+use nitro_shared::output::NitroOutput;
+
+use crate::config::Config;
+use crate::config::modifications::{ConfigModification, apply_modifications_and_write};
+use crate::io::paths::Paths;
+use crate::plugin::PluginManager;
+
+use super::{Instance, parse_loader_config};
+
+impl Instance {
+	/// Gets the ID of the instance that this instance is linked to
+	pub fn linked_instance(&self) -> Option<InstanceID> {
+		self.original_config.linked.as_deref().map(InstanceID::from)
+	}
+
+	/// Creates a server instance that matches this client instance and links the two together
+	pub async fn create_matching_server(
+		&self,
+		new_id: &InstanceID,
+		paths: &Paths,
+		plugins: &PluginManager,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<()> {
+		if self.side() != Side::Client {
+			bail!("Matching servers can only be created for client instances");
+		}
+		if let Some(linked) = &self.original_config.linked {
+			bail!("Instance is already linked to instance '{linked}'");
+		}
+
+		let server_config = create_linked_config(&self.original_config, &self.id, Side::Server);
+		let mut client_config = self.original_config.clone();
+		client_config.linked = Some(new_id.to_string());
+
+		let modifications = vec![
+			ConfigModification::AddInstance(new_id.clone(), server_config),
+			ConfigModification::UpdateInstance(self.id.clone(), client_config),
+		];
+		let mut config = Config::open(&Config::get_path(paths))?;
+
+		apply_modifications_and_write(&mut config, modifications, paths, plugins, o).await
+	}
+
+	/// Copies the shared configuration of this instance to the instance it is linked to
+	pub async fn sync_to_linked(
+		&self,
+		linked: &Instance,
+		paths: &Paths,
+		plugins: &PluginManager,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<()> {
+		ensure!(
+			self.linked_instance().as_ref() == Some(&linked.id),
+			"Instance is not linked to instance '{}'",
+			linked.id
+		);
+		ensure!(
+			self.side() != linked.side(),
+			"Linked instances must be of different types"
+		);
+
+		let mut linked_config = linked.original_config.clone();
+		sync_linked_config(&self.original_config, &mut linked_config, linked.side());
+
+		let modifications = vec![ConfigModification::UpdateInstance(
+			linked.id.clone(),
+			linked_config,
+		)];
+		let mut config = Config::open(&Config::get_path(paths))?;
+
+		apply_modifications_and_write(&mut config, modifications, paths, plugins, o).await
+	}
+}
+
+/// Creates the config for a new instance on the given side that is linked to another instance
+fn create_linked_config(source: &InstanceConfig, source_id: &str, side: Side) -> InstanceConfig {
+	let mut out = InstanceConfig {
+		side: Some(side),
+		name: source.name.as_ref().map(|x| format!("{x} ({side})")),
+		icon: source.icon.clone(),
+		linked: Some(source_id.to_string()),
+		..Default::default()
+	};
+	sync_linked_config(source, &mut out, side);
+
+	out
+}
+
+/// Copies the config that is shared between linked instances from one to the other.
+/// The target keeps its own loader if the source's loader doesn't work on its side,
+/// and keeps packages that are configured only for its side.
+fn sync_linked_config(source: &InstanceConfig, target: &mut InstanceConfig, side: Side) {
+	target.from = source.from.clone();
+	target.version = source.version.clone();
+	target.package_stability = source.package_stability;
+	target.overrides = source.overrides.clone();
+	target.datapack_folder = source.datapack_folder.clone();
+
+	let loader_supported = source.loader.as_ref().is_none_or(|x| {
+		let loader = parse_loader_config(x).0;
+		match side {
+			Side::Client => loader.is_client(),
+			Side::Server => loader.is_server(),
+		}
+	});
+	if loader_supported {
+		target.loader = source.loader.clone();
+	}
+
+	// Packages that the source only has for its own side are left out
+	let mut packages: Vec<_> = source
+		.packages
+		.iter()
+		.filter(|x| x.get_side().is_none_or(|x| x == side))
+		.cloned()
+		.collect();
+	for package in &target.packages {
+		if package.get_side() == Some(side)
+			&& !packages
+				.iter()
+				.any(|x| x.get_pkg_id() == package.get_pkg_id())
+		{
+			packages.push(package.clone());
+		}
+	}
+	target.packages = packages;
+}
+
+#[cfg(test)]
+mod tests {
+	use nitro_config::package::PackageConfigDeser;
+
+	use super::*;
+
+	#[test]
+	fn test_linked_config_sync() {
+		let client = InstanceConfig {
+			side: Some(Side::Client),
+			name: Some("Survival".into()),
+			loader: Some("quilt".into()),
+			packages: vec![
+				PackageConfigDeser::Basic("foo".into()),
+				create_side_package("shader", Side::Client),
+				create_side_package("backup", Side::Server),
+			],
+			..Default::default()
+		};
+
+		let mut server = create_linked_config(&client, "client", Side::Server);
+		assert_eq!(server.name.as_deref(), Some("Survival (server)"));
+		assert_eq!(server.linked.as_deref(), Some("client"));
+		assert_eq!(server.loader.as_deref(), Some("quilt"));
+		assert_eq!(get_ids(&server), vec!["foo", "backup"]);
+
+		server.loader = Some("paper".into());
+		server
+			.packages
+			.push(create_side_package("luckperms", Side::Server));
+		let mut client2 = client.clone();
+		sync_linked_config(&server, &mut client2, Side::Client);
+		assert_eq!(client2.loader.as_deref(), Some("quilt"));
+		assert_eq!(get_ids(&client2), vec!["foo", "shader"]);
+	}
+
+	fn create_side_package(id: &str, side: Side) -> PackageConfigDeser {
+		serde_json::from_str(&format!(r#"{{ "id": "{id}", "side": "{side}" }}"#)).unwrap()
+	}
+
+	fn get_ids(config: &InstanceConfig) -> Vec<String> {
+		config
+			.packages
+			.iter()
+			.map(|x| x.get_pkg_id().to_string())
+			.collect()
+	}
+}
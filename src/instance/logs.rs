@@ -1,9 +1,12 @@
+use anyhow::Context;
 use nitro_core::io::logs::{list_logs, read_log};
+use nitro_net::download::Client;
 use nitro_plugin::hook::hooks::{
 	GetInstanceLog, GetInstanceLogArg, GetInstanceLogs, GetInstanceLogsArg,
 };
 use nitro_shared::output::NitroOutput;
 
+use crate::io::paste::upload_paste;
 use crate::{instance::Instance, io::paths::Paths, plugin::PluginManager};
 
 impl Instance {
@@ -66,4 +69,22 @@ impl Instance {
 			Ok(String::new())
 		}
 	}
+
+	/// Uploads a log file to a paste backend, returning the URL
+	pub async fn upload_log(
+		&mut self,
+		log_id: &str,
+		backend: Option<&str>,
+		plugins: &PluginManager,
+		client: &Client,
+		paths: &Paths,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<String> {
+		let log = self
+			.get_log(log_id, plugins, paths, o)
+			.await
+			.context("Failed to read log")?;
+
+		upload_paste(log_id, &log, backend, plugins, client, paths, o).await
+	}
 }
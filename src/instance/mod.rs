@@ -1,19 +1,45 @@
 /// Addon-related functions for instances
 mod addons;
+/// Converting loose addon files into packages
+pub mod adopt;
+/// Checking instances for known security problems
+pub mod advisories;
+/// Generating changelogs between states of an instance
+pub mod changelog;
+/// Collecting diagnostic information when an instance crashes
+pub mod crash;
+/// Listing everything that is installed on an instance
+pub mod inventory;
 /// Launching an instance
 pub mod launch;
+/// Linking client and server instances together
+pub mod link;
 /// Accessing log files
 pub mod logs;
+/// Network options for server instances
+pub mod network;
 /// Operations on the instance, like deleting, modifying, or querying files
 pub mod operations;
+/// Launching groups of instances together in dependency order
+pub mod orchestrate;
+/// Checking for packages on an instance that have newer versions
+pub mod outdated;
 /// Managing and installing packages on an instance
 pub mod packages;
+/// Building instances into modpacks and publishing them
+pub mod publish;
+/// Sending commands to servers over RCON
+pub mod rcon;
 /// Keeping track of running instance processes
 pub mod tracking;
 /// Import and export of instances to other formats
 pub mod transfer;
 /// Updating an instance
 pub mod update;
+/// Verifying and repairing the files of an instance
+pub mod verify;
+/// Detecting changes to the addon files of an instance made outside of Nitrolaunch
+pub mod watch;
 /// Updating shared world files
 pub mod world_files;
 
@@ -103,11 +129,13 @@ impl Instance {
 				.context("Instance is missing a Minecraft version")?,
 		);
 
+		// Packages that are configured for the other side are left out
 		let read_packages = config
 			.packages
 			.clone()
 			.into_iter()
-			.map(|x| read_package_config(x, config.package_stability.unwrap_or_default()))
+			.filter(|x| x.get_side().is_none_or(|side| side == kind.to_side()))
+			.map(|x| read_package_config(x, config.package_stability.unwrap_or_default(), &id))
 			.collect();
 
 		let base_dir = paths.data.join("instances").join(&*id);
@@ -266,5 +294,13 @@ fn launch_config_to_options(config: LaunchConfig) -> anyhow::Result<LaunchOption
 		wrapper: config.wrapper,
 		quick_play: config.quick_play,
 		use_log4j_config: config.use_log4j_config,
+		legacy_fixes: config.legacy_fixes,
+		log4shell_mitigation: config.log4shell_mitigation,
+		demo: config.demo,
+		natives_arch: config.natives_arch,
+		java_agents: config.java_agents,
+		gpu_preference: config.gpu,
+		jvm_preset: config.jvm_preset,
+		branding: config.branding,
 	})
 }
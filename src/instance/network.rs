@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, TcpListener};
+use std::path::Path;
+
+use anyhow::{Context, bail};
+use nitro_config::instance::ServerNetworkConfig;
+use nitro_net::server::{VersionCompatibility, diagnose};
+use nitro_options::server::{read_server_properties, write_server_properties};
+use nitro_shared::Side;
+use nitro_shared::output::{MessageContents, NitroOutput};
+use serde::{Deserialize, Serialize};
+
+use crate::io::paths::Paths;
+
+use super::Instance;
+use super::tracking::RunningInstanceRegistry;
+
+/// The port that servers listen on when server.properties doesn't set one
+const DEFAULT_PORT: u16 = 25565;
+/// How many ports after the configured one to try when looking for a free port
+const MAX_PORT_SEARCH: u16 = 100;
+
+/// The address and port that a server listens on
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ServerBinding {
+	/// The address that the server is bound to, or None if it listens on all interfaces
+	pub address: Option<IpAddr>,
+	/// The port that the server is bound to
+	pub port: u16,
+}
+
+impl ServerBinding {
+	/// Reads the binding of a server from its server.properties file
+	pub fn from_properties(properties: &HashMap<String, String>) -> Self {
+		let address = properties
+			.get("server-ip")
+			.and_then(|x| x.parse::<IpAddr>().ok())
+			.filter(|x| !x.is_unspecified());
+		let port = properties
+			.get("server-port")
+			.and_then(|x| x.parse().ok())
+			.unwrap_or(DEFAULT_PORT);
+
+		Self { address, port }
+	}
+
+	/// Reads the binding of the server in a directory
+	pub fn from_dir(dir: &Path) -> anyhow::Result<Self> {
+		let properties = read_server_properties(&dir.join("server.properties"))?;
+		Ok(Self::from_properties(&properties))
+	}
+
+	/// Checks if two servers can't both be listening at the same time
+	pub fn conflicts_with(&self, other: &Self) -> bool {
+		if self.port != other.port {
+			return false;
+		}
+
+		// A server on all interfaces conflicts with every other address
+		match (self.address, other.address) {
+			(Some(left), Some(right)) => left == right,
+			_ => true,
+		}
+	}
+
+	/// Checks if nothing on this system is listening on this address and port, by trying to listen on it
+	pub fn is_available(&self) -> bool {
+		let address = self.address.unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+		TcpListener::bind((address, self.port)).is_ok()
+	}
+}
+
+impl std::fmt::Display for ServerBinding {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self.address {
+			Some(IpAddr::V6(address)) => write!(f, "[{address}]:{}", self.port),
+			Some(IpAddr::V4(address)) => write!(f, "{address}:{}", self.port),
+			None => write!(f, "*:{}", self.port),
+		}
+	}
+}
+
+/// Describes why a server can't listen on an address and port, if it can't
+fn get_conflict(binding: &ServerBinding, running: &[(&str, ServerBinding)]) -> Option<String> {
+	if let Some((id, other)) = running
+		.iter()
+		.find(|(_, other)| binding.conflicts_with(other))
+	{
+		return Some(format!(
+			"Server instance '{id}' is already running on {other}, which overlaps with {binding}"
+		));
+	}
+
+	if !binding.is_available() {
+		return Some(format!(
+			"Address {binding} is already in use by another program"
+		));
+	}
+
+	None
+}
+
+/// Gets the server.properties keys for the network config of a server instance
+pub(crate) fn get_network_properties(
+	config: &ServerNetworkConfig,
+) -> anyhow::Result<HashMap<String, String>> {
+	let mut out = HashMap::new();
+	if let Some(address) = &config.address {
+		address
+			.parse::<IpAddr>()
+			.with_context(|| format!("Server address '{address}' is not a valid IP address"))?;
+		out.insert("server-ip".into(), address.clone());
+	}
+	if let Some(port) = config.port {
+		if port == 0 {
+			bail!("Server port cannot be zero");
+		}
+		out.insert("server-port".into(), port.to_string());
+	}
+
+	Ok(out)
+}
+
+impl Instance {
+	/// Gets the address and port that this instance will listen on, if it is a server
+	pub fn get_server_binding(&self) -> anyhow::Result<Option<ServerBinding>> {
+		let Some(dir) = self.dir.as_ref().filter(|_| self.side() == Side::Server) else {
+			return Ok(None);
+		};
+
+		ServerBinding::from_dir(dir).map(Some)
+	}
+
+	/// Makes sure that the address and port of this server aren't already in use, either by another
+	/// running server from this launcher or by some other program. If the `auto_port` option is on,
+	/// the server is moved to the next free port instead of failing
+	pub fn resolve_network_conflicts(
+		&self,
+		paths: &Paths,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<()> {
+		let (Some(binding), Some(dir)) = (self.get_server_binding()?, &self.dir) else {
+			return Ok(());
+		};
+
+		let registry = RunningInstanceRegistry::open(paths)
+			.context("Failed to open registry of running instances")?;
+		let running: Vec<_> = registry
+			.iter_entries()
+			.filter_map(|x| Some((x.instance_id.as_str(), x.server_binding?)))
+			.collect();
+
+		let Some(conflict) = get_conflict(&binding, &running) else {
+			return Ok(());
+		};
+
+		if !self.config.network.auto_port {
+			bail!(
+				"{conflict}. Change the `network.port` config of this instance or turn on `network.auto_port`"
+			);
+		}
+
+		let Some(port) = (binding.port.saturating_add(1)
+			..=binding.port.saturating_add(MAX_PORT_SEARCH))
+			.find(|port| {
+				let candidate = ServerBinding {
+					port: *port,
+					..binding
+				};
+				get_conflict(&candidate, &running).is_none()
+			})
+		else {
+			bail!("{conflict}, and no free port was found after it");
+		};
+
+		let properties = HashMap::from([("server-port".to_string(), port.to_string())]);
+		write_server_properties(properties, &dir.join("server.properties"))
+			.context("Failed to write server.properties")?;
+
+		o.display(MessageContents::Warning(format!(
+			"{conflict}, so server instance '{}' will use port {port} instead",
+			self.id
+		)));
+
+		Ok(())
+	}
+
+	/// Checks that a server this client will Quick Play into can be reached and runs the same version,
+	/// showing warnings for any problems. These never stop the launch, since the server may
+	/// still be joinable through things like proxies and protocol translation plugins
+	pub async fn check_quick_play_server(
+		&self,
+		server: &str,
+		port: Option<u16>,
+		version: &str,
+		protocol: Option<i32>,
+		o: &mut impl NitroOutput,
+	) {
+		let diagnostics = match diagnose(server, port, version, protocol).await {
+			Ok(diagnostics) => diagnostics,
+			Err(e) => {
+				o.display(MessageContents::Warning(format!(
+					"Could not reach server '{server}', it may be offline: {e:?}"
+				)));
+				return;
+			}
+		};
+
+		o.debug(MessageContents::Simple(format!(
+			"Server '{server}' resolved to {}:{} and responded in {}ms",
+			diagnostics.resolved.target, diagnostics.resolved.port, diagnostics.status.latency_ms
+		)));
+
+		if let VersionCompatibility::Incompatible { server_version } = diagnostics.compatibility {
+			o.display(MessageContents::Warning(format!(
+				"Server '{server}' requires version {server_version}, but instance '{}' is {version}",
+				self.id
+			)));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_binding_conflicts() {
+		let properties = HashMap::from([
+			("server-ip".to_string(), String::new()),
+			("server-port".to_string(), "25570".to_string()),
+		]);
+		let wildcard = ServerBinding::from_properties(&properties);
+		assert_eq!(
+			wildcard,
+			ServerBinding {
+				address: None,
+				port: 25570
+			}
+		);
+
+		let local = ServerBinding {
+			address: Some("127.0.0.1".parse().unwrap()),
+			port: 25570,
+		};
+		let lan = ServerBinding {
+			address: Some("192.168.1.5".parse().unwrap()),
+			port: 25570,
+		};
+		assert!(wildcard.conflicts_with(&local));
+		assert!(local.conflicts_with(&local));
+		assert!(!local.conflicts_with(&lan));
+		assert!(get_conflict(&local, &[("other", wildcard)]).is_some());
+		assert!(!local.conflicts_with(&ServerBinding {
+			port: 25565,
+			..local
+		}));
+	}
+}
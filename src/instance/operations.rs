@@ -1,5 +1,8 @@
+use std::path::PathBuf;
+
 use anyhow::{Context, bail};
-use nitro_config::{instance::InstanceConfig, template::TemplateConfig};
+use nitro_config::instance::InstanceConfig;
+use nitro_config::template::{TemplateConfig, TemplatePackageConfiguration};
 use nitro_plugin::hook::hooks::{DeleteInstance, SaveInstanceConfigArg};
 use nitro_shared::{
 	Side,
@@ -8,14 +11,17 @@ use nitro_shared::{
 	output::NitroOutput,
 	util::DeserListOrSingle,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::{
 	config::{
 		Config,
 		modifications::{ConfigModification, apply_modifications_and_write},
+		secrets::{encrypt_json_field, get_passphrase},
 	},
 	instance::Instance,
-	io::paths::Paths,
+	instance::crash::get_newest_file,
+	io::{delete::delete_path, open::open_dir_or_file, paths::Paths},
 	plugin::PluginManager,
 };
 
@@ -81,14 +87,53 @@ impl Instance {
 		apply_modifications_and_write(&mut config, modifications, paths, plugins, o).await
 	}
 
-	/// Deletes this instance and all of its files. Use with caution!
+	/// Saves the setup of this instance as a new template, leaving the instance as it is
+	pub async fn save_as_template(
+		&self,
+		new_id: &TemplateID,
+		paths: &Paths,
+		plugins: &PluginManager,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<()> {
+		let template = template_from_instance(self.original_config.clone(), self.side());
+
+		let modifications = vec![ConfigModification::AddTemplate(new_id.clone(), template)];
+		let mut config = Config::open(&Config::get_path(paths))?;
+
+		apply_modifications_and_write(&mut config, modifications, paths, plugins, o).await
+	}
+
+	/// Encrypts a field in the config of this instance with the launcher passphrase. The field is
+	/// a dot-separated path to a string, like `launch.env.TOKEN`
+	pub async fn encrypt_config_field(
+		&self,
+		field: &str,
+		paths: &Paths,
+		plugins: &PluginManager,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<()> {
+		let mut config = serde_json::to_value(&self.original_config)
+			.context("Failed to serialize instance config")?;
+		let passphrase = get_passphrase(Some(&config), o).await?;
+		encrypt_json_field(&mut config, field, &passphrase)?;
+		let config = serde_json::from_value(config).context("Failed to read encrypted config")?;
+
+		let modifications = vec![ConfigModification::UpdateInstance(self.id.clone(), config)];
+		let mut config = Config::open(&Config::get_path(paths))?;
+
+		apply_modifications_and_write(&mut config, modifications, paths, plugins, o).await
+	}
+
+	/// Deletes this instance and all of its files. Unless the deletion is permanent,
+	/// the files are moved to the system trash. Use with caution!
 	pub async fn delete(
 		&self,
 		paths: &Paths,
 		plugins: &PluginManager,
+		permanent: bool,
 		o: &mut impl NitroOutput,
 	) -> anyhow::Result<()> {
-		self.delete_files()
+		self.delete_files(permanent)
 			.await
 			.context("Failed to delete files")?;
 
@@ -119,8 +164,9 @@ impl Instance {
 		Ok(())
 	}
 
-	/// Removes all game files for an instance, including saves. Does not remove the instance from config. Use with caution!
-	pub async fn delete_files(&self) -> anyhow::Result<()> {
+	/// Removes all game files for an instance, including saves. Unless the deletion is permanent,
+	/// the files are moved to the system trash. Does not remove the instance from config. Use with caution!
+	pub async fn delete_files(&self, permanent: bool) -> anyhow::Result<()> {
 		if let Some(dir) = &self.dir {
 			// Remove the parent directory above .minecraft for clients
 			let path = if self.config.dir.is_none() && self.side() == Side::Client {
@@ -139,7 +185,7 @@ impl Instance {
 				dir
 			};
 
-			tokio::fs::remove_dir_all(path).await?;
+			delete_path(path, permanent)?;
 		}
 
 		Ok(())
@@ -157,4 +203,136 @@ impl Instance {
 
 		dir_size(dir)
 	}
+	/// Gets the directory of a location in this instance
+	pub fn get_location_dir(&self, location: InstanceLocation) -> Option<PathBuf> {
+		let dir = self.dir.as_ref()?;
+		let out = match location {
+			InstanceLocation::GameDir => dir.clone(),
+			// Servers keep their worlds in the root of the game directory
+			InstanceLocation::Worlds => match self.side() {
+				Side::Client => dir.join("saves"),
+				Side::Server => dir.clone(),
+			},
+			InstanceLocation::Logs => dir.join("logs"),
+			InstanceLocation::CrashReports => dir.join("crash-reports"),
+			InstanceLocation::Screenshots => dir.join("screenshots"),
+		};
+
+		Some(out)
+	}
+
+	/// Opens a location of this instance in the file manager. If the name of a file in the location is given,
+	/// it is selected where the platform supports it. Otherwise, the most relevant file is selected if there is one,
+	/// like the latest log or the newest crash report. This can block, so it should be run on a blocking thread in async code
+	pub fn open_location(
+		&self,
+		location: InstanceLocation,
+		file: Option<&str>,
+	) -> anyhow::Result<()> {
+		self.ensure_dir()?;
+		let Some(dir) = self.get_location_dir(location) else {
+			bail!("Instance has no game directory");
+		};
+
+		let file = if let Some(file) = file {
+			if file.is_empty() || file.contains(['/', '\\']) || file == ".." {
+				bail!("Invalid file name '{file}'");
+			}
+			Some(dir.join(file))
+		} else {
+			match location {
+				InstanceLocation::Logs => Some(dir.join("latest.log")),
+				InstanceLocation::CrashReports => get_newest_file(&dir),
+				_ => None,
+			}
+		};
+
+		open_dir_or_file(&dir, file.as_deref())
+	}
+}
+
+/// Creates a template from the config of an instance. Settings that only make sense for that one
+/// instance, like its name and directory, are left out, and its packages are only applied to its side
+pub fn template_from_instance(mut config: InstanceConfig, side: Side) -> TemplateConfig {
+	config.remove_plugin_only_fields();
+	config.side = None;
+	config.name = None;
+	config.icon = None;
+	config.dir = None;
+	config.linked = None;
+	config.imported = false;
+
+	let packages = std::mem::take(&mut config.packages);
+	let packages = match side {
+		Side::Client => TemplatePackageConfiguration::Full {
+			global: Vec::new(),
+			client: packages,
+			server: Vec::new(),
+		},
+		Side::Server => TemplatePackageConfiguration::Full {
+			global: Vec::new(),
+			client: Vec::new(),
+			server: packages,
+		},
+	};
+
+	TemplateConfig {
+		instance: config,
+		packages,
+		..Default::default()
+	}
+}
+
+/// A location in the files of an instance that can be opened
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceLocation {
+	/// The game directory
+	GameDir,
+	/// The directory containing worlds
+	Worlds,
+	/// The game log files
+	Logs,
+	/// The game crash reports
+	CrashReports,
+	/// Screenshots taken in game
+	Screenshots,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_template_from_instance() {
+		let config: InstanceConfig = serde_json::from_value(serde_json::json!({
+			"from": "base",
+			"type": "server",
+			"name": "My Server",
+			"version": "1.21.1",
+			"loader": "fabric",
+			"dir": "/srv/minecraft",
+			"linked": "client",
+			"launch": { "memory": "4G" },
+			"packages": ["lithium"]
+		}))
+		.unwrap();
+
+		let template = template_from_instance(config, Side::Server);
+		assert!(template.instance.side.is_none());
+		assert!(template.instance.name.is_none());
+		assert!(template.instance.dir.is_none());
+		assert!(template.instance.linked.is_none());
+		assert!(template.instance.packages.is_empty());
+		assert_eq!(
+			template.instance.from.iter().collect::<Vec<_>>(),
+			vec!["base"]
+		);
+		assert_eq!(template.instance.loader.as_deref(), Some("fabric"));
+		let TemplatePackageConfiguration::Full { client, server, .. } = template.packages else {
+			panic!("Packages should be split by side");
+		};
+		assert!(client.is_empty());
+		assert_eq!(server.len(), 1);
+	}
 }
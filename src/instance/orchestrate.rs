@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Context;
+use nitro_shared::Side;
+use nitro_shared::id::InstanceID;
+use nitro_shared::output::{MessageContents, NitroOutput};
+
+use crate::io::paths::Paths;
+use crate::plugin::PluginManager;
+
+use super::Instance;
+use super::launch::{InstanceHandle, LaunchSettings};
+use super::update::InstanceUpdateContext;
+
+/// How long to wait for a server in a group to finish starting before giving up
+pub const SERVER_READY_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Handles for a group of instances that were launched together
+pub struct GroupHandle {
+	/// Handles for the servers in the group, in the order they were launched
+	pub servers: Vec<(InstanceID, InstanceHandle)>,
+	/// Handles for the clients in the group, in the order they were launched
+	pub clients: Vec<(InstanceID, InstanceHandle)>,
+}
+
+impl GroupHandle {
+	/// Waits for every instance in the group to exit. Clients are waited on first, then servers
+	pub async fn wait(
+		self,
+		plugins: &PluginManager,
+		paths: &Paths,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<()> {
+		for (id, handle) in self.clients.into_iter().chain(self.servers) {
+			handle
+				.wait(plugins, paths, o)
+				.await
+				.with_context(|| format!("Failed to wait for instance {id}"))?;
+		}
+
+		Ok(())
+	}
+
+	/// Kills every instance in the group
+	pub async fn kill(
+		self,
+		plugins: &PluginManager,
+		paths: &Paths,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<()> {
+		for (id, handle) in self.clients.into_iter().chain(self.servers) {
+			handle
+				.kill(plugins, paths, o)
+				.await
+				.with_context(|| format!("Failed to kill instance {id}"))?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Launches the instances in a group as a unit. Servers are launched first, one at a time,
+/// and each one must finish starting before the next instance is launched. Clients are launched
+/// once all of the servers are ready. If any instance fails to launch, the ones that were already
+/// launched are stopped
+pub async fn launch_group<O: NitroOutput>(
+	members: &[InstanceID],
+	instances: &mut HashMap<InstanceID, Instance>,
+	offline_auth: bool,
+	ctx: &mut InstanceUpdateContext<'_, O>,
+) -> anyhow::Result<GroupHandle> {
+	let mut order = Vec::with_capacity(members.len());
+	for id in members {
+		let instance = instances
+			.get(id)
+			.with_context(|| format!("Instance {id} in group does not exist"))?;
+		order.push((id.clone(), instance.side()));
+	}
+	let order = get_group_launch_order(order);
+
+	let mut out = GroupHandle {
+		servers: Vec::new(),
+		clients: Vec::new(),
+	};
+	for (id, side) in order {
+		let result = launch_member(&id, side, instances, offline_auth, ctx).await;
+		let handle = match result {
+			Ok(handle) => handle,
+			Err(e) => {
+				let _ = out.kill(ctx.plugins, ctx.paths, ctx.output).await;
+				return Err(e);
+			}
+		};
+
+		match side {
+			Side::Server => out.servers.push((id, handle)),
+			Side::Client => out.clients.push((id, handle)),
+		}
+	}
+
+	Ok(out)
+}
+
+/// Launches a single instance in a group, waiting for it to be ready if it is a server
+async fn launch_member<O: NitroOutput>(
+	id: &InstanceID,
+	side: Side,
+	instances: &mut HashMap<InstanceID, Instance>,
+	offline_auth: bool,
+	ctx: &mut InstanceUpdateContext<'_, O>,
+) -> anyhow::Result<InstanceHandle> {
+	let instance = instances
+		.get_mut(id)
+		.with_context(|| format!("Instance {id} in group does not exist"))?;
+
+	let settings = LaunchSettings {
+		offline_auth,
+		pipe_stdin: false,
+		quick_play: None,
+	};
+	let mut handle = instance
+		.launch(settings, ctx)
+		.await
+		.map_err(|e| e.context(format!("Instance {id} failed to launch")))?;
+
+	if side == Side::Server {
+		// Server output is left in its log so that it doesn't mix with the client's
+		handle.silence_output(true);
+
+		ctx.output.display(MessageContents::StartProcess(format!(
+			"Waiting for server {id} to start"
+		)));
+		if let Err(e) = handle.wait_until_ready(SERVER_READY_TIMEOUT).await {
+			let _ = handle.kill(ctx.plugins, ctx.paths, ctx.output).await;
+			return Err(e.context(format!("Server {id} failed to start")));
+		}
+		ctx.output
+			.display(MessageContents::Success(format!("Server {id} is ready")));
+	}
+
+	Ok(handle)
+}
+
+/// Gets the order to launch the instances in a group in. Servers come first, followed by clients,
+/// and instances of the same side keep the order they have in the group
+pub fn get_group_launch_order(members: Vec<(InstanceID, Side)>) -> Vec<(InstanceID, Side)> {
+	let (mut servers, clients): (Vec<_>, Vec<_>) = members
+		.into_iter()
+		.partition(|(_, side)| *side == Side::Server);
+	servers.extend(clients);
+	servers
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_group_launch_order() {
+		let order = get_group_launch_order(vec![
+			("client".into(), Side::Client),
+			("proxy".into(), Side::Server),
+			("client2".into(), Side::Client),
+			("lobby".into(), Side::Server),
+		]);
+		let ids: Vec<_> = order.iter().map(|x| x.0.to_string()).collect();
+		assert_eq!(ids, vec!["proxy", "lobby", "client", "client2"]);
+	}
+}
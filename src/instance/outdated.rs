@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use nitro_pkg::compat::CompatibilityMatrix;
+use nitro_shared::loaders::Loader;
+use nitro_shared::output::{MessageContents, NitroOutput};
+use nitro_shared::pkg::{PkgRequest, PkgRequestSource};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::io::paths::Paths;
+use crate::pkg::reg::PkgRegistry;
+
+use super::Instance;
+
+/// A package on an instance that has a newer content version available
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutdatedPackage {
+	/// The package request, without a version
+	pub id: String,
+	/// The content version that is installed
+	pub installed_version: String,
+	/// The newest content version that supports the instance
+	pub latest_version: String,
+}
+
+impl Instance {
+	/// Checks the packages installed on this instance for newer content versions that support its
+	/// Minecraft version and loader. Packages that are pinned to a content version or file are skipped,
+	/// as are packages that can't be looked up
+	pub async fn get_outdated_packages(
+		&self,
+		version_list: &[String],
+		reg: &PkgRegistry,
+		paths: &Paths,
+		client: &Client,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<Vec<OutdatedPackage>> {
+		let lock = self.get_lockfile(paths)?;
+		let Some(minecraft_version) = lock.get_minecraft_version() else {
+			return Ok(Vec::new());
+		};
+		let loader = lock.get_loader();
+
+		let mut packages: Vec<_> = lock.get_packages().iter().collect();
+		packages.sort_by_key(|x| x.0);
+
+		let mut out = Vec::new();
+		for (id, package) in packages {
+			let Some(installed_version) = &package.content_version else {
+				continue;
+			};
+			let req = Arc::new(PkgRequest::parse(id, PkgRequestSource::UserRequire));
+			let is_pinned = self.packages.iter().any(|x| {
+				(x.content_version.is_some() || x.file.is_some())
+					&& PkgRequest::parse(&x.id, PkgRequestSource::UserRequire)
+						.to_string_no_version()
+						== req.to_string_no_version()
+			});
+			if is_pinned {
+				continue;
+			}
+
+			let matrix = match reg.get(&req, paths, client, o).await {
+				Ok(pkg) => pkg.get_compatibility_matrix(paths, client).await,
+				Err(e) => Err(e),
+			};
+			let matrix = match matrix {
+				Ok(Some(matrix)) => matrix,
+				Ok(None) => continue,
+				Err(e) => {
+					o.debug(MessageContents::Error(format!(
+						"Failed to check package {id} for updates: {e:?}"
+					)));
+					continue;
+				}
+			};
+
+			if let Some(latest_version) = find_newer_version(
+				&matrix,
+				installed_version,
+				minecraft_version,
+				loader,
+				version_list,
+			) {
+				out.push(OutdatedPackage {
+					id: id.clone(),
+					installed_version: installed_version.clone(),
+					latest_version,
+				});
+			}
+		}
+
+		Ok(out)
+	}
+}
+
+/// Finds the newest content version in a compatibility matrix that supports a Minecraft version and loader,
+/// if it is newer than the installed one. The entries of the matrix are ordered from newest to oldest
+fn find_newer_version(
+	matrix: &CompatibilityMatrix,
+	installed_version: &str,
+	minecraft_version: &str,
+	loader: &Loader,
+	version_list: &[String],
+) -> Option<String> {
+	let mut versions = matrix
+		.get_supporting_entries(minecraft_version, loader, version_list)
+		.filter_map(|x| x.content_version.as_deref());
+	let latest = versions.next()?;
+	if latest == installed_version {
+		return None;
+	}
+
+	// Versions that aren't in the matrix can't be compared
+	versions
+		.any(|x| x == installed_version)
+		.then(|| latest.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use nitro_pkg::compat::CompatibilityEntry;
+	use nitro_shared::versions::VersionPattern;
+
+	use super::*;
+
+	#[test]
+	fn test_find_newer_version() {
+		let entry = |version: &str, minecraft_version: &str| CompatibilityEntry {
+			content_version: Some(version.into()),
+			minecraft_versions: Some(vec![VersionPattern::Single(minecraft_version.into())]),
+			loaders: None,
+		};
+		let matrix = CompatibilityMatrix {
+			entries: vec![
+				entry("3.0", "1.21.4"),
+				entry("2.1", "1.21.1"),
+				entry("2.0", "1.21.1"),
+			],
+		};
+
+		let newer = |installed: &str| {
+			find_newer_version(&matrix, installed, "1.21.1", &Loader::Vanilla, &[])
+		};
+		assert_eq!(newer("2.0"), Some("2.1".into()));
+		assert_eq!(newer("2.1"), None);
+		assert_eq!(newer("unknown"), None);
+	}
+}
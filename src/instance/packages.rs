@@ -68,6 +68,8 @@ impl Instance {
 					.get_acquire_task(paths, &self.id, client)
 					.context("Failed to get task for acquiring addon")?;
 				tasks.insert(addon.get_unique_id(&self.id), task);
+			} else {
+				addon.record_cache_hit();
 			}
 		}
 
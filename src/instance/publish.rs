@@ -0,0 +1,324 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+use nitro_instance::addon::modpack::mrpack::{
+	ModrinthHashes, ModrinthIndex, ModrinthPackDependencies, ModrinthPackFile,
+};
+use nitro_net::modrinth::{self, NewVersion, ReleaseChannel, Version};
+use nitro_shared::loaders::Loader;
+use reqwest::Client;
+use sha2::Sha512;
+
+use crate::io::paths::Paths;
+use crate::util::hash::digest_reader;
+
+use super::Instance;
+
+/// Settings for building a Modrinth pack from an instance
+#[derive(Debug, Clone)]
+pub struct MrpackSettings {
+	/// The name of the pack
+	pub name: String,
+	/// The version of the pack
+	pub version: String,
+	/// A short description of the pack
+	pub summary: Option<String>,
+	/// Files and directories in the instance, relative to it, to include as overrides along with the addons
+	pub overrides: Vec<String>,
+}
+
+/// A Modrinth pack that was built from an instance
+#[derive(Debug, Clone)]
+pub struct BuiltMrpack {
+	/// The path to the pack file
+	pub path: PathBuf,
+	/// The Minecraft version of the pack
+	pub minecraft_version: String,
+	/// The loader of the pack, as a Modrinth loader
+	pub loader: String,
+	/// The number of files that will be downloaded from Modrinth
+	pub downloaded_files: usize,
+	/// The number of files that are included in the pack itself
+	pub override_files: usize,
+}
+
+/// Settings for publishing a pack as a new version of a Modrinth project
+#[derive(Clone)]
+pub struct PublishSettings {
+	/// The ID or slug of the project
+	pub project_id: String,
+	/// The changelog of the version, in Markdown
+	pub changelog: Option<String>,
+	/// The release channel of the version
+	pub version_type: ReleaseChannel,
+	/// Whether the version is featured
+	pub featured: bool,
+	/// The API token to upload with
+	pub token: String,
+}
+
+impl Instance {
+	/// Builds a Modrinth pack (.mrpack) of this instance. Addons that are available on Modrinth are
+	/// referenced by their download, while the rest are included in the pack as overrides
+	pub async fn build_mrpack(
+		&self,
+		settings: &MrpackSettings,
+		path: &Path,
+		paths: &Paths,
+		client: &Client,
+	) -> anyhow::Result<BuiltMrpack> {
+		let Some(inst_dir) = self.dir() else {
+			bail!("Only local instances can be built into packs");
+		};
+		let lock = self.get_lockfile(paths)?;
+		let Some(minecraft_version) = lock.get_minecraft_version().cloned() else {
+			bail!("Instance has not been updated yet. Update it first");
+		};
+
+		// Get the addon files in the instance along with their hashes
+		let mut files = Vec::new();
+		for addon in lock.get_addons() {
+			for file in &addon.files {
+				let file = PathBuf::from(file);
+				if file.exists() {
+					files.push((file, addon.hashes.sha512.clone()));
+				}
+			}
+		}
+
+		let content = self
+			.check_content(paths)
+			.context("Failed to check instance files")?;
+		for file in content.unmanaged {
+			if file.is_file() {
+				files.push((file, None));
+			}
+		}
+
+		let source = MrpackSource {
+			inst_dir,
+			files,
+			minecraft_version,
+			loader: lock.get_loader().clone(),
+			loader_version: lock.get_loader_version().cloned(),
+		};
+		build_mrpack(source, settings, path, client).await
+	}
+}
+
+/// The files and versions of an instance that a Modrinth pack is built from
+pub struct MrpackSource<'a> {
+	/// The game directory of the instance
+	pub inst_dir: &'a Path,
+	/// The addon files in the instance, along with their SHA-512 hashes if they are already known
+	pub files: Vec<(PathBuf, Option<String>)>,
+	/// The Minecraft version of the instance
+	pub minecraft_version: String,
+	/// The loader of the instance
+	pub loader: Loader,
+	/// The version of the loader
+	pub loader_version: Option<String>,
+}
+
+/// Builds a Modrinth pack (.mrpack) from instance files. Files that are available on Modrinth are
+/// referenced by their download, while the rest are included in the pack as overrides
+pub async fn build_mrpack(
+	source: MrpackSource<'_>,
+	settings: &MrpackSettings,
+	path: &Path,
+	client: &Client,
+) -> anyhow::Result<BuiltMrpack> {
+	let inst_dir = source.inst_dir;
+	let mut dependencies = ModrinthPackDependencies {
+		minecraft: source.minecraft_version.clone(),
+		forge: None,
+		neoforge: None,
+		fabric_loader: None,
+		quilt_loader: None,
+	};
+	let dependency = match &source.loader {
+		Loader::Vanilla => None,
+		Loader::Forge => Some(&mut dependencies.forge),
+		Loader::NeoForged => Some(&mut dependencies.neoforge),
+		Loader::Fabric => Some(&mut dependencies.fabric_loader),
+		Loader::Quilt => Some(&mut dependencies.quilt_loader),
+		other => bail!("Modrinth packs do not support the {other} loader"),
+	};
+	if let Some(dependency) = dependency {
+		let Some(loader_version) = source.loader_version else {
+			bail!("Loader version of the instance is unknown. Update it first");
+		};
+		*dependency = Some(loader_version);
+	}
+
+	let mut files = Vec::with_capacity(source.files.len());
+	for (file, hash) in source.files {
+		let hash = if let Some(hash) = hash {
+			hash
+		} else {
+			hash_file(&file)?
+		};
+		files.push((file, hash));
+	}
+
+	let hashes: Vec<_> = files.iter().map(|x| x.1.clone()).collect();
+	let versions = modrinth::get_versions_from_hashes(&hashes, client)
+		.await
+		.context("Failed to look up files on Modrinth")?;
+
+	let mut index_files = Vec::new();
+	let mut overrides = Vec::new();
+	for (file, hash) in files {
+		let rel_path = get_relative_path(&file, inst_dir)?;
+		let download = versions
+			.get(&hash)
+			.and_then(|x| x.files.iter().find(|x| x.hashes.sha512 == hash));
+		if let Some(download) = download {
+			index_files.push(ModrinthPackFile {
+				path: rel_path,
+				hashes: ModrinthHashes {
+					sha512: hash,
+					sha1: download.hashes.sha1.clone(),
+				},
+				env: None,
+				downloads: vec![download.url.clone()],
+				file_size: download.size,
+			});
+		} else {
+			overrides.push((rel_path, file));
+		}
+	}
+
+	for rel_path in &settings.overrides {
+		let override_path = inst_dir.join(rel_path);
+		if !override_path.exists() {
+			bail!("Override {rel_path} does not exist in the instance");
+		}
+		collect_overrides(&override_path, inst_dir, &mut overrides)?;
+	}
+
+	let index = ModrinthIndex {
+		format_version: 1,
+		game: "minecraft".into(),
+		name: settings.name.clone(),
+		version_id: settings.version.clone(),
+		summary: settings.summary.clone(),
+		files: index_files,
+		dependencies,
+	};
+	index
+		.write_pack(&overrides, path)
+		.context("Failed to write pack")?;
+
+	Ok(BuiltMrpack {
+		path: path.to_owned(),
+		minecraft_version: source.minecraft_version,
+		loader: get_modrinth_loader(&source.loader).to_string(),
+		downloaded_files: index.files.len(),
+		override_files: overrides.len(),
+	})
+}
+
+/// Uploads a built pack as a new version of a Modrinth project
+pub async fn publish_mrpack(
+	pack: &BuiltMrpack,
+	settings: &MrpackSettings,
+	publish: &PublishSettings,
+	client: &Client,
+) -> anyhow::Result<Version> {
+	let version = NewVersion {
+		project_id: publish.project_id.clone(),
+		name: settings.name.clone(),
+		version_number: settings.version.clone(),
+		changelog: publish.changelog.clone(),
+		version_type: publish.version_type,
+		game_versions: vec![pack.minecraft_version.clone()],
+		loaders: vec![pack.loader.clone()],
+		featured: publish.featured,
+	};
+
+	let data = std::fs::read(&pack.path).context("Failed to read pack")?;
+	let file_name = format!("{}-{}.mrpack", settings.name, settings.version);
+	modrinth::create_version(&version, &file_name, data, &publish.token, client)
+		.await
+		.context("Failed to upload version to Modrinth")
+}
+
+/// Gets the name of a loader on Modrinth
+fn get_modrinth_loader(loader: &Loader) -> &'static str {
+	match loader {
+		Loader::Forge => "forge",
+		Loader::NeoForged => "neoforge",
+		Loader::Fabric => "fabric",
+		Loader::Quilt => "quilt",
+		_ => "minecraft",
+	}
+}
+
+/// Recursively adds the files in an override to the list of overrides
+fn collect_overrides(
+	path: &Path,
+	inst_dir: &Path,
+	out: &mut Vec<(String, PathBuf)>,
+) -> anyhow::Result<()> {
+	if path.is_dir() {
+		for entry in path
+			.read_dir()
+			.context("Failed to read override directory")?
+		{
+			collect_overrides(&entry?.path(), inst_dir, out)?;
+		}
+	} else {
+		let rel_path = get_relative_path(path, inst_dir)?;
+		if !out.iter().any(|x| x.0 == rel_path) {
+			out.push((rel_path, path.to_owned()));
+		}
+	}
+
+	Ok(())
+}
+
+/// Gets the path of a file relative to the instance, in the format used by packs
+fn get_relative_path(path: &Path, inst_dir: &Path) -> anyhow::Result<String> {
+	let rel_path = path
+		.strip_prefix(inst_dir)
+		.with_context(|| format!("File {} is not in the instance", path.display()))?;
+	let components: Vec<_> = rel_path
+		.components()
+		.map(|x| x.as_os_str().to_string_lossy().to_string())
+		.collect();
+
+	Ok(components.join("/"))
+}
+
+fn hash_file(path: &Path) -> anyhow::Result<String> {
+	let file =
+		File::open(path).with_context(|| format!("Failed to open file {}", path.display()))?;
+	let hash = digest_reader::<Sha512, _>(BufReader::new(file))?;
+	Ok(hex::encode(hash))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_collect_overrides() {
+		let dir = std::env::temp_dir().join("nitro_test_collect_overrides");
+		let _ = std::fs::remove_dir_all(&dir);
+		std::fs::create_dir_all(dir.join("config/sodium")).unwrap();
+		std::fs::write(dir.join("config/sodium/options.json"), "{}").unwrap();
+		std::fs::write(dir.join("options.txt"), "").unwrap();
+
+		let mut overrides = Vec::new();
+		collect_overrides(&dir.join("config"), &dir, &mut overrides).unwrap();
+		collect_overrides(&dir.join("options.txt"), &dir, &mut overrides).unwrap();
+		collect_overrides(&dir.join("options.txt"), &dir, &mut overrides).unwrap();
+		let rel_paths: Vec<_> = overrides.iter().map(|x| x.0.as_str()).collect();
+		assert_eq!(rel_paths, vec!["config/sodium/options.json", "options.txt"]);
+
+		let _ = std::fs::remove_dir_all(&dir);
+	}
+}
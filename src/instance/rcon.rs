@@ -0,0 +1,181 @@
+use std::time::Duration;
+
+use anyhow::{Context, bail, ensure};
+use nitro_options::server::read_server_properties;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::instance::Instance;
+
+/// Packet type for logging in
+const LOGIN_TYPE: i32 = 3;
+/// Packet type for running a command
+const COMMAND_TYPE: i32 = 2;
+/// The default RCON port used by the server
+const DEFAULT_PORT: u16 = 25575;
+/// Maximum size of a packet we will accept from the server
+const MAX_PACKET_SIZE: i32 = 4096 + 10;
+/// Timeout for connecting to the server and waiting for each response
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Simple client for the Minecraft server RCON protocol
+pub struct RconClient {
+	stream: TcpStream,
+	next_id: i32,
+}
+
+impl RconClient {
+	/// Connects and logs in to an RCON server
+	pub async fn connect(address: &str, password: &str) -> anyhow::Result<Self> {
+		let stream = tokio::time::timeout(TIMEOUT, TcpStream::connect(address))
+			.await
+			.context("Timed out while connecting to RCON server")?
+			.context("Failed to connect to RCON server")?;
+		let mut out = Self { stream, next_id: 1 };
+
+		let id = out
+			.send(LOGIN_TYPE, password)
+			.await
+			.context("Failed to send login packet")?;
+		let (response_id, _) = out.read().await.context("Failed to read login response")?;
+		if response_id == -1 {
+			bail!("RCON password was incorrect");
+		}
+		ensure!(
+			response_id == id,
+			"RCON login response had an unexpected ID"
+		);
+
+		Ok(out)
+	}
+
+	/// Runs a command on the server and returns its response
+	pub async fn command(&mut self, command: &str) -> anyhow::Result<String> {
+		self.send(COMMAND_TYPE, command)
+			.await
+			.context("Failed to send command packet")?;
+		let (_, body) = self
+			.read()
+			.await
+			.context("Failed to read command response")?;
+
+		Ok(body)
+	}
+
+	/// Sends a packet and returns its request ID
+	async fn send(&mut self, packet_type: i32, body: &str) -> anyhow::Result<i32> {
+		let id = self.next_id;
+		self.next_id += 1;
+
+		let packet = encode_packet(id, packet_type, body);
+		self.stream.write_all(&packet).await?;
+
+		Ok(id)
+	}
+
+	/// Reads a packet, returning the request ID and body
+	async fn read(&mut self) -> anyhow::Result<(i32, String)> {
+		tokio::time::timeout(TIMEOUT, self.read_impl())
+			.await
+			.context("Timed out waiting for the RCON server to respond")?
+	}
+
+	/// Implementation of reading a packet without a timeout
+	async fn read_impl(&mut self) -> anyhow::Result<(i32, String)> {
+		let len = self.stream.read_i32_le().await?;
+		ensure!(
+			(10..=MAX_PACKET_SIZE).contains(&len),
+			"Invalid RCON packet length {len}"
+		);
+		let mut buf = vec![0u8; len as usize];
+		self.stream.read_exact(&mut buf).await?;
+
+		decode_packet(&buf)
+	}
+}
+
+impl Instance {
+	/// Connects to the RCON interface of this server instance, using the settings from its server.properties
+	pub async fn connect_rcon(&self) -> anyhow::Result<RconClient> {
+		let Some(dir) = &self.dir else {
+			bail!("Instance does not have a directory");
+		};
+		let properties = read_server_properties(&dir.join("server.properties"))
+			.context("Failed to read server.properties")?;
+
+		if properties.get("enable-rcon").map(String::as_str) != Some("true") {
+			bail!("RCON is not enabled for this server");
+		}
+		let Some(password) = properties.get("rcon.password").filter(|x| !x.is_empty()) else {
+			bail!("RCON password is not set for this server");
+		};
+		let port = properties
+			.get("rcon.port")
+			.and_then(|x| x.parse().ok())
+			.unwrap_or(DEFAULT_PORT);
+
+		let host = get_rcon_host(properties.get("server-ip").map(String::as_str));
+
+		RconClient::connect(&format!("{host}:{port}"), password).await
+	}
+}
+
+/// Gets the host to connect to for RCON from the server-ip property of a server.
+/// Servers bound to all interfaces or without an IP set are reached through loopback
+fn get_rcon_host(server_ip: Option<&str>) -> String {
+	let server_ip = server_ip.map(str::trim).unwrap_or_default();
+	match server_ip {
+		"" | "0.0.0.0" => "127.0.0.1".into(),
+		"::" | "[::]" => "[::1]".into(),
+		ip if ip.contains(':') && !ip.starts_with('[') => format!("[{ip}]"),
+		ip => ip.into(),
+	}
+}
+
+/// Encodes an RCON packet
+fn encode_packet(id: i32, packet_type: i32, body: &str) -> Vec<u8> {
+	let len = body.len() as i32 + 10;
+	let mut out = Vec::with_capacity(len as usize + 4);
+	out.extend(len.to_le_bytes());
+	out.extend(id.to_le_bytes());
+	out.extend(packet_type.to_le_bytes());
+	out.extend(body.as_bytes());
+	out.extend([0, 0]);
+
+	out
+}
+
+/// Decodes the contents of an RCON packet after the length
+fn decode_packet(buf: &[u8]) -> anyhow::Result<(i32, String)> {
+	ensure!(buf.len() >= 10, "RCON packet is too short");
+	let id = i32::from_le_bytes(buf[0..4].try_into()?);
+	let body = &buf[8..buf.len() - 2];
+
+	Ok((id, String::from_utf8_lossy(body).to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_packet_roundtrip() {
+		let packet = encode_packet(5, COMMAND_TYPE, "say hello");
+		assert_eq!(
+			i32::from_le_bytes(packet[0..4].try_into().unwrap()) as usize,
+			packet.len() - 4
+		);
+		let (id, body) = decode_packet(&packet[4..]).unwrap();
+		assert_eq!(id, 5);
+		assert_eq!(body, "say hello");
+	}
+
+	#[test]
+	fn test_rcon_host() {
+		assert_eq!(get_rcon_host(None), "127.0.0.1");
+		assert_eq!(get_rcon_host(Some("0.0.0.0")), "127.0.0.1");
+		assert_eq!(get_rcon_host(Some("192.168.1.5")), "192.168.1.5");
+		assert_eq!(get_rcon_host(Some("::")), "[::1]");
+		assert_eq!(get_rcon_host(Some("fe80::1")), "[fe80::1]");
+	}
+}
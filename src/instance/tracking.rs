@@ -4,10 +4,11 @@ use std::{
 };
 
 use anyhow::Context;
-use nitro_core::io::{json_from_file, json_to_file_pretty};
+use nitro_core::io::{json_from_file, json_to_file_replace};
 use serde::{Deserialize, Serialize};
 use sysinfo::{Pid, System};
 
+use crate::instance::network::ServerBinding;
 use crate::io::paths::Paths;
 
 /// A registry of running instances
@@ -64,7 +65,7 @@ impl RunningInstanceRegistry {
 			return Ok(());
 		}
 
-		json_to_file_pretty(&self.path, &self.data).context("Failed to write to registry file")?;
+		json_to_file_replace(&self.path, &self.data).context("Failed to write to registry file")?;
 
 		self.is_dirty = false;
 
@@ -222,6 +223,9 @@ pub struct RunningInstanceEntry {
 	#[serde(default)]
 	#[serde(alias = "user")]
 	pub account: Option<String>,
+	/// The address and port that this instance is listening on, if it is a server
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub server_binding: Option<ServerBinding>,
 }
 
 fn default_is_java() -> bool {
@@ -1,14 +1,19 @@
+use std::io::Read;
+use std::path::PathBuf;
 use std::{collections::HashMap, path::Path};
 
 use anyhow::{Context, bail};
+use itertools::Itertools;
 use nitro_config::instance::InstanceConfig;
+use nitro_net::download::{self, Client, ProgressiveDownload};
+use nitro_net::modrinth;
 use nitro_plugin::hook::hooks::{
 	AddInstanceTransferFormats, ExportInstance, ExportInstanceArg, ImportInstance,
 	ImportInstanceArg, InstanceTransferFeatureSupport, InstanceTransferFormat,
 	InstanceTransferFormatDirection, MigrateInstances, MigrateInstancesArg,
 };
 use nitro_shared::lang::translate::TranslationKey;
-use nitro_shared::output::{MessageContents, NitroOutput};
+use nitro_shared::output::{MessageContents, NitroOutput, OutputProcess};
 use nitro_shared::{Side, translate};
 
 use crate::io::lock::Lockfile;
@@ -72,7 +77,9 @@ impl Instance {
 			minecraft_version: minecraft_version.clone(),
 			loader_version: inst_lock.get_loader_version().cloned(),
 			inst_dir: inst_dir.to_string_lossy().to_string(),
-			result_path: result_path.canonicalize()?.to_string_lossy().to_string(),
+			result_path: canonicalize_result_path(result_path)?
+				.to_string_lossy()
+				.to_string(),
 		};
 		let result = plugins
 			.call_hook_on_plugin(ExportInstance, &format.plugin, &arg, paths, o)
@@ -237,6 +244,137 @@ pub async fn migrate_instances(
 	Ok(result.instances)
 }
 
+/// Downloads an instance file from a URL so that it can be imported, returning the path to the downloaded file.
+/// Modrinth modpack pages are resolved to the file of their latest version, or the version in the URL if there is one
+pub async fn download_import_source(
+	url: &str,
+	paths: &Paths,
+	client: &Client,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<PathBuf> {
+	download::validate_url(url)?;
+
+	let (url, filename) = if let Some((project, version)) = parse_modrinth_url(url) {
+		let versions = modrinth::get_project_versions(project, client)
+			.await
+			.context("Failed to get modpack versions from Modrinth")?;
+		let version = if let Some(version) = version {
+			versions
+				.iter()
+				.find(|x| x.id == version || x.version_number == version)
+				.context("Modpack version does not exist")?
+		} else {
+			versions.first().context("Modpack has no versions")?
+		};
+		let file = version.get_primary_download()?;
+
+		(file.url.clone(), file.filename.clone())
+	} else {
+		let filename = url
+			.split(['?', '#'])
+			.next()
+			.and_then(|x| x.rsplit('/').next())
+			.filter(|x| !x.is_empty())
+			.unwrap_or("instance.zip");
+
+		(url.to_string(), filename.to_string())
+	};
+
+	let dir = paths.internal.join("imports");
+	std::fs::create_dir_all(&dir).context("Failed to create imports directory")?;
+	let path = dir.join(sanitize_filename(&filename));
+
+	let mut process = OutputProcess::new(o);
+	let message = translate!(process, StartDownloadingImport, "url" = &url);
+	process.display(MessageContents::StartProcess(message.clone()));
+
+	let result = async {
+		let mut download = ProgressiveDownload::file(&url, &path, client)
+			.await
+			.context("Failed to start download")?;
+		while !download.is_finished() {
+			download.poll_download().await?;
+			process.display(MessageContents::Associated(
+				Box::new(download.get_progress()),
+				Box::new(MessageContents::Simple(message.clone())),
+			));
+		}
+
+		Ok::<_, anyhow::Error>(())
+	}
+	.await;
+	// Don't leave a partial download behind
+	if let Err(e) = result {
+		let _ = std::fs::remove_file(&path);
+		return Err(e);
+	}
+
+	let message = process
+		.translate(TranslationKey::FinishDownloadingImport)
+		.into();
+	process.display(MessageContents::Success(message));
+
+	Ok(path)
+}
+
+/// Parses a Modrinth modpack URL into the project and optional version
+fn parse_modrinth_url(url: &str) -> Option<(&str, Option<&str>)> {
+	let path = url
+		.strip_prefix("https://modrinth.com/")
+		.or_else(|| url.strip_prefix("https://www.modrinth.com/"))?;
+	let path = path.split(['?', '#']).next()?;
+	let mut segments = path.split('/').filter(|x| !x.is_empty());
+	if segments.next()? != "modpack" {
+		return None;
+	}
+	let project = segments.next()?;
+	let version = if segments.next() == Some("version") {
+		segments.next()
+	} else {
+		None
+	};
+
+	Some((project, version))
+}
+
+/// Removes characters from a downloaded filename that could escape the download directory.
+/// Falls back to a default name if nothing is left
+fn sanitize_filename(filename: &str) -> String {
+	let out = filename
+		.chars()
+		.map(|x| {
+			if x == '/' || x == '\\' || x == ':' {
+				'_'
+			} else {
+				x
+			}
+		})
+		.collect::<String>()
+		.trim_start_matches('.')
+		.to_string();
+
+	if out.is_empty() {
+		"import.zip".into()
+	} else {
+		out
+	}
+}
+
+/// Gets the absolute path for an export result, which may not exist yet
+fn canonicalize_result_path(path: &Path) -> anyhow::Result<PathBuf> {
+	if path.exists() {
+		return Ok(path.canonicalize()?);
+	}
+
+	let parent = match path.parent() {
+		Some(parent) if !parent.as_os_str().is_empty() => parent.canonicalize()?,
+		_ => std::env::current_dir()?,
+	};
+	let file_name = path.file_name().context("Export path has no file name")?;
+
+	Ok(parent.join(file_name))
+}
+
 /// Load transfer formats from plugins
 pub async fn load_formats(
 	plugins: &PluginManager,
@@ -276,6 +414,65 @@ impl Formats {
 	pub fn iter_format_names(&self) -> impl Iterator<Item = &String> {
 		self.formats.keys()
 	}
+
+	/// Tries to detect which importable format a file or directory is in
+	pub fn detect(&self, path: &Path) -> anyhow::Result<Option<&str>> {
+		let extension = path.extension().map(|x| x.to_string_lossy().to_lowercase());
+		let root_files = list_root_files(path).context("Failed to read files to detect format")?;
+
+		// Extensions are more specific, so check them first
+		for pass in [true, false] {
+			for (id, format) in self.formats.iter().sorted_by_key(|x| x.0) {
+				if format.info.import.is_none() {
+					continue;
+				}
+				let Some(detect) = &format.info.detect else {
+					continue;
+				};
+
+				let matches = if pass {
+					extension
+						.as_ref()
+						.is_some_and(|x| detect.extensions.iter().any(|y| y == x))
+				} else {
+					!detect.files.is_empty() && detect.files.iter().all(|x| root_files.contains(x))
+				};
+				if matches {
+					return Ok(Some(id));
+				}
+			}
+		}
+
+		Ok(None)
+	}
+}
+
+/// Lists the files at the root of a directory or zip archive
+fn list_root_files(path: &Path) -> anyhow::Result<Vec<String>> {
+	if path.is_dir() {
+		let out = path
+			.read_dir()?
+			.filter_map(|x| x.ok())
+			.map(|x| x.file_name().to_string_lossy().to_string())
+			.collect();
+
+		return Ok(out);
+	}
+
+	let mut header = [0u8; 4];
+	let mut file = std::fs::File::open(path)?;
+	if file.read_exact(&mut header).is_err() || header != *b"PK\x03\x04" {
+		return Ok(Vec::new());
+	}
+
+	let archive = zip::ZipArchive::new(std::fs::File::open(path)?)?;
+	let out = archive
+		.file_names()
+		.filter(|x| !x.trim_end_matches('/').contains('/'))
+		.map(|x| x.trim_end_matches('/').to_string())
+		.collect();
+
+	Ok(out)
 }
 
 /// A single loaded transfer format
@@ -316,3 +513,30 @@ fn output_support_warnings(info: &InstanceTransferFormatDirection, o: &mut impl
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_modrinth_url() {
+		assert_eq!(
+			parse_modrinth_url("https://modrinth.com/modpack/fabulously-optimized"),
+			Some(("fabulously-optimized", None))
+		);
+		assert_eq!(
+			parse_modrinth_url("https://modrinth.com/modpack/cobblemon/version/1.5?foo=bar"),
+			Some(("cobblemon", Some("1.5")))
+		);
+		assert_eq!(parse_modrinth_url("https://modrinth.com/mod/sodium"), None);
+		assert_eq!(parse_modrinth_url("https://example.com/pack.mrpack"), None);
+	}
+
+	#[test]
+	fn test_sanitize_filename() {
+		assert_eq!(sanitize_filename("pack.mrpack"), "pack.mrpack");
+		assert_eq!(sanitize_filename("../pack.zip"), "_pack.zip");
+		assert_eq!(sanitize_filename(".."), "import.zip");
+		assert_eq!(sanitize_filename(""), "import.zip");
+	}
+}
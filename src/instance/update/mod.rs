@@ -16,6 +16,7 @@ use nitro_core::NitroCore;
 use nitro_core::account::AccountManager;
 use nitro_pkg::{PkgRequest, PkgRequestSource};
 use nitro_plugin::hook::hooks::{AfterPackagesInstalled, AfterPackagesInstalledArg};
+use nitro_shared::versions::{VersionInfo, VersionPattern};
 use nitro_shared::{UpdateDepth, translate};
 #[cfg(not(feature = "disable_instance_update_packages"))]
 use packages::print_package_support_messages;
@@ -93,6 +94,8 @@ impl Instance {
 
 		std::mem::drop(version);
 
+		self.warn_advisories(&version_info, ctx).await;
+
 		if facets.instance {
 			self.setup(
 				&mut manager,
@@ -190,6 +193,48 @@ impl Instance {
 
 		Ok(())
 	}
+
+	/// Warns about known security advisories that affect this instance and haven't been mitigated
+	async fn warn_advisories<O: NitroOutput>(
+		&self,
+		version_info: &VersionInfo,
+		ctx: &mut InstanceUpdateContext<'_, O>,
+	) {
+		let loader_version = match &self.loader_version {
+			VersionPattern::Single(version) => Some(version.as_str()),
+			_ => None,
+		};
+
+		let advisories = match self
+			.get_advisories(version_info, loader_version, ctx.paths, ctx.client)
+			.await
+		{
+			Ok(advisories) => advisories,
+			Err(e) => {
+				ctx.output.debug(MessageContents::Error(format!(
+					"Failed to check security advisories: {e:?}"
+				)));
+				return;
+			}
+		};
+
+		for advisory in advisories.into_iter().filter(|x| !x.is_mitigated) {
+			let mut message = format!(
+				"Instance {} is affected by {} ({}): {}",
+				self.id,
+				advisory.advisory.title,
+				advisory.advisory.id,
+				advisory.advisory.description
+			);
+			if advisory.mitigation.is_some() {
+				message.push_str(&format!(
+					" A fix can be applied with `nitro instance advisories {} --mitigate` or from the instance page in the GUI",
+					self.id
+				));
+			}
+			ctx.output.display(MessageContents::Warning(message));
+		}
+	}
 }
 
 /// Parts of an instance to update
@@ -10,6 +10,7 @@ use nitro_instance::lock::{LockfileAddon, LockfileModpack};
 use nitro_plugin::hook::hooks::{AddModpackFormats, InstallModpack, InstallModpackArg};
 use nitro_shared::{
 	Side, UpdateDepth,
+	io::mark_file_used,
 	lang::Language,
 	loaders::Loader,
 	minecraft::AddonKind,
@@ -343,7 +344,9 @@ pub async fn download_modpack_package(
 
 	// Download the modpack
 	let modpack_path = addon.addon.get_path(paths, instance_id);
-	if !modpack_path.exists() {
+	if modpack_path.exists() {
+		mark_file_used(&modpack_path);
+	} else {
 		addon
 			.acquire(paths, instance_id, client)
 			.await
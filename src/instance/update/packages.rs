@@ -12,11 +12,13 @@ use nitro_shared::minecraft::AddonKind;
 use nitro_shared::output::{MessageContents, NitroOutput};
 use nitro_shared::pkg::{ArcPkgReq, PackageDiff, merge_package_lists};
 use nitro_shared::translate;
+use nitro_shared::util::format_bytes;
 use nitro_shared::versions::VersionInfo;
 use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
 
 use crate::instance::Instance;
+use crate::pkg::eval::conflicts::{AddonConflict, AddonConflictKind, AppliedConflictResolution};
 use crate::pkg::eval::{EvalConstants, EvalParameters, ResolutionAndEvalResult, resolve};
 use crate::util::select_random_n_items_from_list;
 
@@ -68,6 +70,21 @@ pub async fn update_instance_packages<O: NitroOutput>(
 		}
 	}
 
+	// Addon conflicts need to be resolved before anything gets overwritten
+	display_addon_conflicts(&resolution.addon_conflicts, ctx.output)?;
+
+	// Show how much will be downloaded before confirming
+	let estimate = resolution.estimate_download_size(ctx.paths, &instance.id, force);
+	if estimate.bytes > 0 {
+		let size = format_bytes(estimate.bytes);
+		let message = if estimate.unknown_files > 0 {
+			translate!(ctx.output, EstimatedDownloadSizeIncomplete, "size" = &size)
+		} else {
+			translate!(ctx.output, EstimatedDownloadSize, "size" = &size)
+		};
+		ctx.output.display(MessageContents::Simple(message));
+	}
+
 	if !diffs.is_empty() && !ctx.output.prompt_special_package_diffs(diffs).await? {
 		bail!("Package update aborted");
 	}
@@ -113,7 +130,11 @@ pub async fn update_instance_packages<O: NitroOutput>(
 	}
 
 	// Run the acquire tasks
-	run_addon_tasks(tasks, ctx.output)
+	let transfer_limit = ctx
+		.prefs
+		.download_concurrency
+		.unwrap_or_else(get_transfer_limit);
+	run_addon_tasks(tasks, transfer_limit, ctx.output)
 		.await
 		.context("Failed to acquire addons")?;
 
@@ -174,12 +195,13 @@ pub async fn update_instance_packages<O: NitroOutput>(
 /// Evaluates addon acquire tasks efficiently with a progress display to the user
 async fn run_addon_tasks(
 	tasks: HashMap<String, impl Future<Output = anyhow::Result<()>> + Send + 'static>,
+	transfer_limit: usize,
 	o: &mut impl NitroOutput,
 ) -> anyhow::Result<()> {
 	let total_count = tasks.len();
 	let mut task_set = JoinSet::new();
 
-	let sem = Arc::new(Semaphore::new(get_transfer_limit()));
+	let sem = Arc::new(Semaphore::new(transfer_limit));
 	for task in tasks.into_values() {
 		let permit = sem.clone().acquire_owned().await;
 		let task = async move {
@@ -244,6 +266,79 @@ async fn resolve_instance<O: NitroOutput>(
 	Ok(resolution)
 }
 
+/// Displays conflicts between package addons, failing if any are unresolved
+fn display_addon_conflicts(
+	conflicts: &[AddonConflict],
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let mut unresolved_count = 0;
+	for conflict in conflicts {
+		let pkg1 = conflict.first.pkg.to_string();
+		let pkg2 = conflict.second.pkg.to_string();
+		let message = match &conflict.kind {
+			AddonConflictKind::SamePath(file) => translate!(
+				o,
+				AddonConflictSamePath,
+				"pkg1" = &pkg1,
+				"pkg2" = &pkg2,
+				"file" = file
+			),
+			AddonConflictKind::SameContent => {
+				translate!(o, AddonConflictSameContent, "pkg1" = &pkg1, "pkg2" = &pkg2)
+			}
+		};
+
+		match &conflict.resolution {
+			Some(resolution) => {
+				o.display(MessageContents::Warning(message));
+				let resolution = match resolution {
+					AppliedConflictResolution::Skipped(addon) => translate!(
+						o,
+						AddonConflictSkipped,
+						"addon" = &addon.addon,
+						"pkg" = &addon.pkg.to_string()
+					),
+					AppliedConflictResolution::Renamed(addon, file) => translate!(
+						o,
+						AddonConflictRenamed,
+						"addon" = &addon.addon,
+						"pkg" = &addon.pkg.to_string(),
+						"file" = file
+					),
+				};
+				o.display(MessageContents::ListItem(Box::new(
+					MessageContents::Simple(resolution),
+				)));
+			}
+			None => {
+				unresolved_count += 1;
+				o.display(MessageContents::Error(message));
+				let options = conflict.get_options().iter().join(", ");
+				o.display(MessageContents::ListItem(Box::new(
+					MessageContents::Simple(translate!(
+						o,
+						AddonConflictOptions,
+						"options" = &options
+					)),
+				)));
+			}
+		}
+	}
+
+	if unresolved_count > 0 {
+		bail!(
+			"{}",
+			translate!(
+				o,
+				AddonConflictsUnresolved,
+				"count" = &unresolved_count.to_string()
+			)
+		);
+	}
+
+	Ok(())
+}
+
 /// Removes existing addons on an instance just in case there are lockfile issues
 fn remove_existing_addons(
 	instance: &mut Instance,
@@ -11,7 +11,8 @@ use nitro_core::io::json_to_file;
 use nitro_core::launch::LaunchConfiguration;
 use nitro_core::version::InstalledVersion;
 use nitro_core::{NitroCore, QuickPlayType};
-use nitro_instance::lock::InstanceLockfile;
+use nitro_instance::lock::{InstanceLockfile, LockfileJava};
+use nitro_options::server::write_server_properties;
 use nitro_plugin::hook::hooks::{
 	AfterInstanceSetup, OnInstanceSetup, OnInstanceSetupArg, OnInstanceSetupResult, RemoveLoader,
 };
@@ -19,9 +20,11 @@ use nitro_shared::Side;
 use nitro_shared::output::OutputProcess;
 use nitro_shared::output::{MessageContents, NitroOutput};
 use nitro_shared::translate;
+use nitro_shared::util::DefaultExt;
 use nitro_shared::uuid::hyphenate_uuid;
-use nitro_shared::versions::VersionInfo;
+use nitro_shared::versions::{VersionInfo, VersionPattern};
 
+use crate::instance::network::get_network_properties;
 use crate::io::paths::Paths;
 use crate::plugin::PluginManager;
 
@@ -106,6 +109,20 @@ impl Instance {
 					"version2" = &version_info.version
 				));
 				process.display(message);
+
+				if let Some(channel) = self
+					.config
+					.version
+					.as_ref()
+					.and_then(|x| x.get_tracked_channel())
+				{
+					let message = MessageContents::Warning(translate!(
+						process,
+						InstanceAdvancedChannel,
+						"channel" = &channel.to_string()
+					));
+					process.display(message);
+				}
 			}
 
 			// Teardown
@@ -132,12 +149,33 @@ impl Instance {
 			process.display(message);
 		}
 
-		let mut results = plugins
-			.call_hook(OnInstanceSetup, &arg, paths, o)
-			.await
-			.context("Failed to call instance setup hook")?;
+		let results = match call_setup_hook(&arg, plugins, paths, o).await {
+			Ok(results) => results,
+			Err(e) => {
+				// Roll back to the loader version that was last set up successfully, if there is one
+				let Some(previous_version) = arg.current_loader_version.clone() else {
+					return Err(e);
+				};
+				if arg.desired_loader_version == VersionPattern::Single(previous_version.clone()) {
+					return Err(e);
+				}
 
-		while let Some(result) = results.next_result(o).await? {
+				o.display(MessageContents::Warning(translate!(
+					o,
+					LoaderVersionRolledBack,
+					"loader" = &self.loader.to_string(),
+					"version" = &previous_version,
+					"error" = &format!("{e:?}")
+				)));
+
+				arg.desired_loader_version = VersionPattern::Single(previous_version);
+				call_setup_hook(&arg, plugins, paths, o)
+					.await
+					.context("Failed to set up the previous loader version")?
+			}
+		};
+
+		for result in results {
 			self.modify_from_setup_result(result, &mut inst_lock)?;
 		}
 
@@ -164,11 +202,47 @@ impl Instance {
 			while let Some(result) = results.next_result(o).await? {
 				self.modify_from_setup_result(result, &mut inst_lock)?;
 			}
+
+			// Remember the classpath so that its libraries aren't pruned
+			let mut classpath = instance.get_classpath().get_paths();
+			classpath.extend(self.modification_data.classpath_extension.get_paths());
+			inst_lock.update_classpath(
+				classpath
+					.into_iter()
+					.map(|x| x.to_string_lossy().to_string())
+					.collect(),
+			);
+			let java = instance.get_java();
+			inst_lock.update_java(LockfileJava {
+				major_version: java.get_major_version().0,
+				path: java.get_path().to_string_lossy().to_string(),
+			});
+			inst_lock
+				.write()
+				.context("Failed to finish using lockfile")?;
+
+			self.apply_server_properties()
+				.context("Failed to apply server properties")?;
 		}
 
 		Ok(())
 	}
 
+	/// Writes the network config from the instance config to its server.properties file.
+	/// This runs after the setup hooks so that the config always has the final say
+	fn apply_server_properties(&self) -> anyhow::Result<()> {
+		let (Side::Server, Some(dir)) = (self.side(), &self.dir) else {
+			return Ok(());
+		};
+		if self.config.network.is_default() {
+			return Ok(());
+		}
+
+		let properties =
+			get_network_properties(&self.config.network).context("Invalid network config")?;
+		write_server_properties(properties, &dir.join("server.properties"))
+	}
+
 	fn modify_from_setup_result(
 		&mut self,
 		result: OnInstanceSetupResult,
@@ -226,6 +300,7 @@ impl Instance {
 					resolution: window
 						.resolution
 						.map(|x| WindowResolution::new(x.width, x.height)),
+					title_suffix: window.title_suffix.clone(),
 				},
 			},
 			InstKind::Server { .. } => nitro_core::InstanceKind::Server {
@@ -262,6 +337,22 @@ impl Instance {
 		let mut game_args = self.launch.game_args.clone();
 		game_args.extend(self.modification_data.game_args.clone());
 
+		let inst_dir = self
+			.dir
+			.clone()
+			.unwrap_or(paths.data.join("instances").join(&*self.id));
+
+		// Relative agent paths are relative to the instance
+		let java_agents = self
+			.launch
+			.java_agents
+			.iter()
+			.map(|x| nitro_core::launch::JavaAgent {
+				path: inst_dir.join(&x.path),
+				options: x.options.clone(),
+			})
+			.collect();
+
 		let launch_config = LaunchConfiguration {
 			java: self.launch.java.clone(),
 			jvm_args,
@@ -272,11 +363,16 @@ impl Instance {
 			wrappers,
 			quick_play,
 			use_log4j_config: self.launch.use_log4j_config,
+			log4shell_mitigation: self.launch.log4shell_mitigation,
+			legacy_fixes: self.launch.legacy_fixes,
+			demo: self.launch.demo,
+			natives_arch: self.launch.natives_arch,
+			java_agents,
+			gpu_preference: self.launch.gpu_preference,
+			jvm_preset: self.launch.jvm_preset,
+			launcher_name: self.launch.branding.launcher_name.clone(),
+			launcher_version: self.launch.branding.launcher_version.clone(),
 		};
-		let inst_dir = self
-			.dir
-			.clone()
-			.unwrap_or(paths.data.join("instances").join(&*self.id));
 		let config = nitro_core::InstanceConfiguration {
 			side,
 			path: inst_dir,
@@ -339,6 +435,26 @@ impl Instance {
 	}
 }
 
+/// Calls the instance setup hook and collects all of its results, so that none of them are applied if any plugin fails
+async fn call_setup_hook(
+	arg: &OnInstanceSetupArg,
+	plugins: &PluginManager,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<OnInstanceSetupResult>> {
+	let mut results = plugins
+		.call_hook(OnInstanceSetup, arg, paths, o)
+		.await
+		.context("Failed to call instance setup hook")?;
+
+	let mut out = Vec::new();
+	while let Some(result) = results.next_result(o).await? {
+		out.push(result);
+	}
+
+	Ok(out)
+}
+
 /// Things that modifications for an instance change when creating it
 #[derive(Debug)]
 pub struct ModificationData {
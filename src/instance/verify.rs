@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, bail};
+use nitro_shared::output::{MessageContents, NitroOutput};
+use nitro_shared::{UpdateDepth, translate};
+
+use crate::io::paths::Paths;
+use crate::util::hash::{get_best_hash, hash_file_with_best_hash};
+
+use super::Instance;
+use super::update::{InstanceUpdateContext, UpdateFacets};
+
+/// Files that were found to be corrupted when verifying an instance
+#[derive(Debug, Default)]
+pub struct VerifyResult {
+	/// Game files like the game JAR, libraries, and assets
+	pub game_files: Vec<PathBuf>,
+	/// Files of installed addons
+	pub addons: Vec<PathBuf>,
+}
+
+impl VerifyResult {
+	/// Gets the total number of corrupted files
+	pub fn count(&self) -> usize {
+		self.game_files.len() + self.addons.len()
+	}
+}
+
+impl Instance {
+	/// Checks the hashes of the game files and installed addons of this instance and redownloads any that are corrupted.
+	/// The core in the context must have a file verifier set
+	pub async fn verify<O: NitroOutput>(
+		&mut self,
+		ctx: &mut InstanceUpdateContext<'_, O>,
+	) -> anyhow::Result<VerifyResult> {
+		let verifier = ctx
+			.core
+			.get_file_verifier()
+			.context("Core does not have a file verifier")?
+			.clone();
+
+		if !ctx.lock.has_instance_done_first_update(&self.id) {
+			bail!("Instance has not been updated yet, so there is nothing to verify");
+		}
+
+		let message = translate!(ctx.output, StartVerifyingInstance, "inst" = &self.id);
+		ctx.output.display(MessageContents::Header(message));
+
+		// The core checks game files against their hashes and redownloads them as the instance is set up
+		let facets = UpdateFacets {
+			instance: true,
+			packages: false,
+			modpack: false,
+		};
+		self.update(UpdateDepth::Shallow, facets, ctx)
+			.await
+			.context("Failed to verify game files")?;
+
+		let mut out = VerifyResult {
+			game_files: verifier.get_corrupted_files(),
+			addons: self
+				.remove_corrupted_addons(ctx.paths)
+				.context("Failed to verify addons")?,
+		};
+		out.game_files.sort();
+		out.game_files.dedup();
+
+		for file in out.game_files.iter().chain(out.addons.iter()) {
+			let message = translate!(
+				ctx.output,
+				CorruptedFileFound,
+				"file" = &file.to_string_lossy()
+			);
+			ctx.output.display(MessageContents::Warning(message));
+		}
+
+		// Where addons are stored isn't known from the lockfile, so force all of them to be acquired again
+		if !out.addons.is_empty() {
+			let facets = UpdateFacets {
+				instance: false,
+				packages: true,
+				modpack: true,
+			};
+			self.update(UpdateDepth::Force, facets, ctx)
+				.await
+				.context("Failed to reinstall corrupted addons")?;
+		}
+
+		let message = if out.count() == 0 {
+			translate!(ctx.output, NoCorruptedFiles)
+		} else {
+			translate!(
+				ctx.output,
+				FinishVerifyingInstance,
+				"count" = &out.count().to_string()
+			)
+		};
+		ctx.output.display(MessageContents::Success(message));
+
+		Ok(out)
+	}
+
+	/// Checks the hashes of installed addons, removing the files of any that don't match. Returns the removed files
+	fn remove_corrupted_addons(&self, paths: &Paths) -> anyhow::Result<Vec<PathBuf>> {
+		let inst_lock = self.get_lockfile(paths)?;
+
+		let mut out = Vec::new();
+		for addon in inst_lock.get_addons() {
+			for file in &addon.files {
+				let path = PathBuf::from(file);
+				if !path.exists() {
+					continue;
+				}
+				let Some(hash) = get_best_hash(&addon.hashes) else {
+					continue;
+				};
+
+				if !hash_file_with_best_hash(&path, hash)? {
+					std::fs::remove_file(&path).context("Failed to remove corrupted addon")?;
+					out.push(path);
+				}
+			}
+		}
+
+		Ok(out)
+	}
+}
@@ -0,0 +1,231 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::Context;
+use nitro_shared::Side;
+use nitro_shared::output::{MessageContents, NitroOutput};
+
+use crate::io::paths::Paths;
+
+use super::Instance;
+
+/// Directories in an instance that contain addon files
+pub const CONTENT_DIRS: &[&str] = &["mods", "resourcepacks", "shaderpacks"];
+
+/// A file that was installed by a package but has been removed from the instance
+#[derive(Debug, Clone)]
+pub struct MissingFile {
+	/// Path to the missing file
+	pub path: PathBuf,
+	/// The package that installed the file, if any
+	pub package: Option<String>,
+}
+
+/// Differences between the addon files in an instance and the ones it has recorded as installed
+#[derive(Debug, Default)]
+pub struct ContentReport {
+	/// Files that are in the instance but weren't installed by a package
+	pub unmanaged: Vec<PathBuf>,
+	/// Files that were installed by a package but don't exist anymore
+	pub missing: Vec<MissingFile>,
+}
+
+impl ContentReport {
+	/// Checks whether the instance content matches what was installed
+	pub fn is_empty(&self) -> bool {
+		self.unmanaged.is_empty() && self.missing.is_empty()
+	}
+
+	/// Displays warnings about the files in this report
+	pub fn display_warnings(&self, o: &mut impl NitroOutput) {
+		for file in &self.missing {
+			let message = if let Some(package) = &file.package {
+				format!(
+					"File {} was deleted but is still installed by package '{package}'. It will be reinstalled on the next update unless the package is removed",
+					file.path.display()
+				)
+			} else {
+				format!("Installed file {} was deleted", file.path.display())
+			};
+			o.display(MessageContents::Warning(message));
+		}
+
+		for file in &self.unmanaged {
+			o.display(MessageContents::Warning(format!(
+				"File {} is not managed by a package. Use `instance adopt` to replace it with one",
+				file.display()
+			)));
+		}
+	}
+}
+
+impl Instance {
+	/// Gets the directories of this instance that contain addon files
+	pub fn get_content_dirs(&self) -> Vec<PathBuf> {
+		let Some(inst_dir) = self.dir() else {
+			return Vec::new();
+		};
+
+		let mut out: Vec<_> = CONTENT_DIRS.iter().map(|x| inst_dir.join(x)).collect();
+		if self.side() == Side::Server {
+			out.push(inst_dir.join("plugins"));
+		}
+
+		out
+	}
+
+	/// Compares the addon files in this instance to the ones recorded in its lockfile
+	pub fn check_content(&self, paths: &Paths) -> anyhow::Result<ContentReport> {
+		let inst_lock = self.get_lockfile(paths)?;
+		let mut managed = HashSet::new();
+		let mut out = ContentReport::default();
+		for addon in inst_lock.get_addons() {
+			for file in &addon.files {
+				let path = PathBuf::from(file);
+				if !path.exists() {
+					out.missing.push(MissingFile {
+						path: path.clone(),
+						package: addon.package.clone(),
+					});
+				}
+				managed.insert(path);
+			}
+		}
+
+		for dir in self.get_content_dirs() {
+			if !dir.exists() {
+				continue;
+			}
+
+			for entry in dir.read_dir().context("Failed to read addon directory")? {
+				let path = entry?.path();
+				// Hidden files are usually created by the system or other programs
+				let is_hidden = path
+					.file_name()
+					.is_some_and(|x| x.to_string_lossy().starts_with('.'));
+				if is_hidden || managed.contains(&path) {
+					continue;
+				}
+
+				out.unmanaged.push(path);
+			}
+		}
+
+		out.unmanaged.sort();
+		out.missing.sort_by(|a, b| a.path.cmp(&b.path));
+
+		Ok(out)
+	}
+
+	/// Checks the addon files in this instance and removes files that were deleted from its lockfile,
+	/// so that they aren't treated as installed anymore. Displays warnings about the changes
+	pub fn reconcile_content(
+		&self,
+		paths: &Paths,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<ContentReport> {
+		let report = self.check_content(paths)?;
+
+		if !report.missing.is_empty() {
+			let mut inst_lock = self.get_lockfile(paths)?;
+			let missing: Vec<_> = report
+				.missing
+				.iter()
+				.map(|x| x.path.to_string_lossy().to_string())
+				.collect();
+			inst_lock.remove_addon_files(&missing);
+			inst_lock
+				.write()
+				.context("Failed to write instance lockfile")?;
+		}
+
+		report.display_warnings(o);
+
+		Ok(report)
+	}
+}
+
+/// Detects changes to the addon directories of an instance that were made outside of Nitrolaunch,
+/// like adding or deleting mods by hand
+pub struct InstanceContentWatcher {
+	dirs: Vec<PathBuf>,
+	snapshot: HashMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl InstanceContentWatcher {
+	/// Create a new InstanceContentWatcher for an instance, starting from its current state
+	pub fn new(instance: &Instance) -> Self {
+		Self::from_dirs(instance.get_content_dirs())
+	}
+
+	/// Create a new InstanceContentWatcher for a list of directories, starting from their current state
+	pub fn from_dirs(dirs: Vec<PathBuf>) -> Self {
+		let snapshot = take_snapshot(&dirs);
+		Self { dirs, snapshot }
+	}
+
+	/// Checks whether the addon files have changed since the last check
+	pub fn check(&mut self) -> bool {
+		let snapshot = take_snapshot(&self.dirs);
+		let changed = snapshot != self.snapshot;
+		self.snapshot = snapshot;
+
+		changed
+	}
+}
+
+/// Gets the modification times and sizes of all of the files in a list of directories
+fn take_snapshot(dirs: &[PathBuf]) -> HashMap<PathBuf, (SystemTime, u64)> {
+	let mut out = HashMap::new();
+	for dir in dirs {
+		add_dir_to_snapshot(dir, &mut out);
+	}
+	out
+}
+
+fn add_dir_to_snapshot(dir: &Path, out: &mut HashMap<PathBuf, (SystemTime, u64)>) {
+	let Ok(entries) = dir.read_dir() else {
+		return;
+	};
+
+	for entry in entries.flatten() {
+		let Ok(meta) = entry.metadata() else {
+			continue;
+		};
+
+		let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+		out.insert(entry.path(), (modified, meta.len()));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_content_watcher() {
+		let dir = std::env::temp_dir().join("nitro_test_content_watcher");
+		let _ = std::fs::remove_dir_all(&dir);
+		let mods = dir.join("mods");
+		std::fs::create_dir_all(&mods).unwrap();
+		std::fs::write(mods.join("a.jar"), "a").unwrap();
+
+		let mut watcher =
+			InstanceContentWatcher::from_dirs(vec![mods.clone(), dir.join("plugins")]);
+		assert!(!watcher.check());
+
+		std::fs::write(mods.join("b.jar"), "b").unwrap();
+		assert!(watcher.check());
+		assert!(!watcher.check());
+
+		std::fs::remove_file(mods.join("a.jar")).unwrap();
+		assert!(watcher.check());
+
+		std::fs::create_dir_all(dir.join("plugins")).unwrap();
+		std::fs::write(dir.join("plugins/c.jar"), "c").unwrap();
+		assert!(watcher.check());
+
+		let _ = std::fs::remove_dir_all(dir);
+	}
+}
@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use nitro_shared::output::{MessageContents, NitroOutput};
+use nitro_shared::translate;
+use nitro_shared::util::format_bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+use super::paths::Paths;
+
+/// The number of bytes in a megabyte, which the cache limit is configured in
+const MEGABYTE: u64 = 1024 * 1024;
+
+/// A kind of file that is cached by Nitrolaunch and can be removed and downloaded again later
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheKind {
+	/// Addon files that were downloaded for packages, including modpack archives
+	Addons,
+	/// Loader installers
+	Installers,
+	/// Archives of instances that were downloaded to be imported
+	Imports,
+	/// Icons and other images that are shown in frontends
+	Images,
+}
+
+impl CacheKind {
+	/// All of the cache kinds
+	pub const ALL: [Self; 4] = [Self::Addons, Self::Installers, Self::Imports, Self::Images];
+
+	/// Gets the directory that files of this kind are cached in
+	pub fn get_dir(&self, paths: &Paths) -> PathBuf {
+		match self {
+			Self::Addons => paths.addons.clone(),
+			Self::Installers => paths.internal.join("forge"),
+			Self::Imports => paths.internal.join("imports"),
+			Self::Images => paths.internal.join("images"),
+		}
+	}
+
+	/// Checks if a cached file is of this kind. Not every file in the cache directories is a cached download
+	fn includes(&self, path: &Path) -> bool {
+		match self {
+			Self::Installers => path
+				.file_name()
+				.is_some_and(|x| x.to_string_lossy().ends_with("-installer.jar")),
+			_ => true,
+		}
+	}
+}
+
+/// A file in the cache
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CacheEntry {
+	/// The path to the file
+	pub path: PathBuf,
+	/// What kind of file this is
+	pub kind: CacheKind,
+	/// The size of the file in bytes
+	pub size: u64,
+	/// When the file was last used, as a UTC timestamp
+	pub last_used: u64,
+}
+
+/// How much space the cache is using
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CacheUsage {
+	/// The total size of the cache in bytes
+	pub total_size: u64,
+	/// The configured limit on the size of the cache in bytes
+	pub limit: Option<u64>,
+	/// The usage of each kind of cached file
+	pub kinds: Vec<CacheKindUsage>,
+}
+
+/// How much space one kind of cached file is using
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CacheKindUsage {
+	/// The kind of file
+	pub kind: CacheKind,
+	/// The total size of the files in bytes
+	pub size: u64,
+	/// The number of files
+	pub file_count: usize,
+}
+
+/// Gets all of the files in the cache
+pub fn get_cache_entries(paths: &Paths) -> anyhow::Result<Vec<CacheEntry>> {
+	let mut out = Vec::new();
+	for kind in CacheKind::ALL {
+		collect_entries(&kind.get_dir(paths), kind, &mut out)
+			.with_context(|| format!("Failed to read {kind:?} cache"))?;
+	}
+
+	Ok(out)
+}
+
+/// Gets how much space the cache is using
+pub fn get_cache_usage(config: &Config, paths: &Paths) -> anyhow::Result<CacheUsage> {
+	let entries = get_cache_entries(paths)?;
+	let kinds = CacheKind::ALL
+		.into_iter()
+		.map(|kind| {
+			let files = entries.iter().filter(|x| x.kind == kind);
+			CacheKindUsage {
+				kind,
+				size: files.clone().map(|x| x.size).sum(),
+				file_count: files.count(),
+			}
+		})
+		.collect();
+
+	Ok(CacheUsage {
+		total_size: entries.iter().map(|x| x.size).sum(),
+		limit: get_cache_limit(config),
+		kinds,
+	})
+}
+
+/// Removes the least recently used cached files until the cache is under the configured limit.
+/// Returns the files that were removed
+pub fn enforce_cache_limit(
+	config: &Config,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<Vec<CacheEntry>> {
+	let Some(limit) = get_cache_limit(config) else {
+		return Ok(Vec::new());
+	};
+
+	let entries = get_cache_entries(paths)?;
+	let protected = get_protected_files(config, paths)?;
+	let evicted = select_evicted(entries, limit, &protected);
+	if evicted.is_empty() {
+		return Ok(evicted);
+	}
+
+	for entry in &evicted {
+		if let Err(e) = std::fs::remove_file(&entry.path)
+			&& e.kind() != std::io::ErrorKind::NotFound
+		{
+			return Err(e)
+				.with_context(|| format!("Failed to remove cached file {:?}", entry.path));
+		}
+	}
+
+	let count = evicted.len().to_string();
+	let size = format_bytes(evicted.iter().map(|x| x.size).sum());
+	o.display(MessageContents::Success(translate!(
+		o,
+		CacheLimitEnforced,
+		"count" = &count,
+		"size" = &size
+	)));
+
+	Ok(evicted)
+}
+
+/// Gets the configured cache limit in bytes
+fn get_cache_limit(config: &Config) -> Option<u64> {
+	config.prefs.cache_limit.map(|x| x * MEGABYTE)
+}
+
+/// Picks the least recently used files to remove so that the total size is under the limit
+fn select_evicted(
+	mut entries: Vec<CacheEntry>,
+	limit: u64,
+	protected: &HashSet<PathBuf>,
+) -> Vec<CacheEntry> {
+	let mut total: u64 = entries.iter().map(|x| x.size).sum();
+	entries.sort_by_key(|x| x.last_used);
+
+	let mut out = Vec::new();
+	for entry in entries {
+		if total <= limit {
+			break;
+		}
+		if protected.contains(&entry.path) {
+			continue;
+		}
+
+		total = total.saturating_sub(entry.size);
+		out.push(entry);
+	}
+
+	out
+}
+
+/// Gets cached files that instances link to, which would break if they were removed
+fn get_protected_files(config: &Config, paths: &Paths) -> anyhow::Result<HashSet<PathBuf>> {
+	let mut out = HashSet::new();
+	for instance in config.instances.values() {
+		let lock = instance
+			.get_lockfile(paths)
+			.with_context(|| format!("Failed to open lockfile for instance {}", instance.id()))?;
+		for addon in lock.get_addons() {
+			for file in &addon.files {
+				if let Ok(target) = std::fs::read_link(file) {
+					out.insert(target);
+				}
+			}
+		}
+	}
+
+	Ok(out)
+}
+
+/// Recursively collects the cached files in a directory
+fn collect_entries(dir: &Path, kind: CacheKind, out: &mut Vec<CacheEntry>) -> anyhow::Result<()> {
+	if !dir.exists() {
+		return Ok(());
+	}
+
+	for entry in dir.read_dir()? {
+		let entry = entry?;
+		let path = entry.path();
+		let meta = entry.metadata()?;
+		if meta.is_dir() {
+			collect_entries(&path, kind, out)?;
+			continue;
+		}
+		if !meta.is_file() || !kind.includes(&path) {
+			continue;
+		}
+
+		let last_used = meta
+			.modified()
+			.ok()
+			.and_then(|x| x.duration_since(UNIX_EPOCH).ok())
+			.map(|x| x.as_secs())
+			.unwrap_or_default();
+		out.push(CacheEntry {
+			path,
+			kind,
+			size: meta.len(),
+			last_used,
+		});
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_select_evicted() {
+		let entry = |path: &str, size: u64, last_used: u64| CacheEntry {
+			path: PathBuf::from(path),
+			kind: CacheKind::Addons,
+			size,
+			last_used,
+		};
+		let entries = vec![
+			entry("new", 40, 300),
+			entry("old", 30, 100),
+			entry("protected", 20, 50),
+			entry("middle", 30, 200),
+		];
+		let protected = HashSet::from([PathBuf::from("protected")]);
+
+		let evicted = select_evicted(entries.clone(), 60, &protected);
+		let evicted: Vec<_> = evicted.iter().map(|x| x.path.to_str().unwrap()).collect();
+		assert_eq!(evicted, vec!["old", "middle"]);
+
+		assert!(select_evicted(entries, 120, &protected).is_empty());
+	}
+}
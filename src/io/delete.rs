@@ -0,0 +1,43 @@
+use std::path::Path;
+
+use anyhow::Context;
+
+/// Deletes a file or directory. Unless the deletion is permanent, it is moved to the trash or recycle bin
+/// of the system instead, so that it can be restored if it was deleted by mistake
+pub fn delete_path(path: &Path, permanent: bool) -> anyhow::Result<()> {
+	if !path.exists() && !path.is_symlink() {
+		return Ok(());
+	}
+
+	if !permanent {
+		return trash::delete(path).context(
+			"Failed to move to the trash. If your system doesn't have one, delete permanently instead",
+		);
+	}
+
+	if path.is_dir() && !path.is_symlink() {
+		std::fs::remove_dir_all(path).context("Failed to remove directory")
+	} else {
+		std::fs::remove_file(path).context("Failed to remove file")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_permanent_delete() {
+		let dir = std::env::temp_dir().join("nitro_test_permanent_delete");
+		let _ = std::fs::remove_dir_all(&dir);
+		std::fs::create_dir_all(dir.join("saves")).unwrap();
+		std::fs::write(dir.join("saves/level.dat"), "level").unwrap();
+
+		delete_path(&dir.join("saves/level.dat"), true).unwrap();
+		assert!(!dir.join("saves/level.dat").exists());
+		delete_path(&dir, true).unwrap();
+		assert!(!dir.exists());
+		// Paths that are already gone are fine
+		delete_path(&dir, false).unwrap();
+	}
+}
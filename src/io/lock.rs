@@ -1,8 +1,10 @@
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::fs::File;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
-use nitro_core::io::{json_from_file, json_to_file_pretty};
+use nitro_core::io::{json_from_file, json_to_file_replace};
+use nitro_shared::util::utc_timestamp;
 use serde::{Deserialize, Serialize};
 
 use super::paths::Paths;
@@ -18,6 +20,8 @@ pub struct Lockfile {
 struct LockfileContents {
 	/// Instances that have done their first update
 	created_instances: HashSet<String>,
+	/// When unused game files were last pruned, as a UTC timestamp
+	last_prune: Option<u64>,
 }
 
 impl Lockfile {
@@ -39,7 +43,7 @@ impl Lockfile {
 
 	/// Finish using the lockfile and write to the disk
 	pub fn finish(&mut self, paths: &Paths) -> anyhow::Result<()> {
-		json_to_file_pretty(Self::get_path(paths), &self.contents)
+		json_to_file_replace(Self::get_path(paths), &self.contents)
 			.context("Failed to write to lockfile")?;
 
 		Ok(())
@@ -54,4 +58,42 @@ impl Lockfile {
 	pub fn update_instance_has_done_first_update(&mut self, instance: &str) {
 		self.contents.created_instances.insert(instance.to_string());
 	}
+
+	/// Checks whether unused game files should be pruned, given the number of days between prunes
+	pub fn should_prune(&self, interval: u32) -> bool {
+		let Some(last_prune) = self.contents.last_prune else {
+			return true;
+		};
+		let now = utc_timestamp().unwrap_or_default();
+		now.saturating_sub(last_prune) >= interval as u64 * 24 * 60 * 60
+	}
+
+	/// Updates when unused game files were last pruned to now
+	pub fn update_last_prune(&mut self) {
+		self.contents.last_prune = utc_timestamp().ok();
+	}
+}
+
+/// An exclusive lock on a file, which stops other threads and processes from doing a
+/// read-modify-write of the same data at once. The lock is released when this is dropped
+pub struct FileLock {
+	_file: File,
+}
+
+impl FileLock {
+	/// Waits until the lock at the given path can be acquired. The lock file is created if it doesn't exist
+	pub fn acquire(path: &Path) -> anyhow::Result<Self> {
+		if let Some(parent) = path.parent() {
+			let _ = std::fs::create_dir_all(parent);
+		}
+		let file = File::options()
+			.create(true)
+			.truncate(false)
+			.write(true)
+			.open(path)
+			.context("Failed to open lock file")?;
+		file.lock().context("Failed to acquire file lock")?;
+
+		Ok(Self { _file: file })
+	}
 }
@@ -1,6 +1,24 @@
-/// Use of the lockfile for persistent data
+/// Size limits on cached downloads
+pub mod cache;
+/// Deleting files through the system trash
+pub mod delete;
+/// Use of the lockfile for persistent data, and locks on shared files
 pub mod lock;
 /// NitroOutput message logging
 pub mod logging;
+/// Persistent network statistics
+pub mod net_stats;
+/// Persistent notifications about background events
+pub mod notifications;
+/// Opening files and directories in the system file manager
+pub mod open;
+/// Uploading logs to paste services
+pub mod paste;
 /// Standard paths for Nitrolaunch
 pub mod paths;
+/// Pruning of unused game files
+pub mod prune;
+/// Local reports of crashes and errors that can be submitted as issues
+pub mod reports;
+/// Updating the CLI binary from GitHub releases
+pub mod self_update;
@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use nitro_core::net::stats::{self, NetworkStats};
+
+use crate::io::paths::Paths;
+
+/// Gets the path to the stored network statistics
+fn get_path(paths: &Paths) -> PathBuf {
+	paths.internal.join("net_stats.json")
+}
+
+/// Adds the network statistics collected by this process to the stored ones
+pub fn save(paths: &Paths) -> anyhow::Result<()> {
+	stats::save(&get_path(paths)).context("Failed to save network stats")
+}
+
+/// Gets all network statistics, including ones from this process that have not been saved yet
+pub fn load(paths: &Paths) -> anyhow::Result<NetworkStats> {
+	stats::load(&get_path(paths)).context("Failed to load network stats")
+}
+
+/// Clears all stored network statistics
+pub fn reset(paths: &Paths) -> anyhow::Result<()> {
+	stats::reset(&get_path(paths)).context("Failed to reset network stats")
+}
@@ -0,0 +1,235 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use nitro_core::io::{json_from_file, json_to_file_replace};
+use nitro_shared::util::utc_timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::io::lock::FileLock;
+use crate::io::paths::Paths;
+
+/// The most notifications that are kept. The oldest ones are removed past this
+const MAX_NOTIFICATIONS: usize = 100;
+
+/// A notification about something that happened in the background, which is kept until the
+/// user has seen it so that it isn't lost when the window or terminal that caused it closes
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Notification {
+	/// The unique ID of the notification
+	pub id: u64,
+	/// What the notification is about
+	pub kind: NotificationKind,
+	/// A short title
+	pub title: String,
+	/// The full message
+	pub message: String,
+	/// The instance, plugin, or task that the notification is about, if any. An unread notification
+	/// with the same kind and subject is replaced instead of adding another one
+	#[serde(default)]
+	pub subject: Option<String>,
+	/// When the notification was created, as a UTC timestamp
+	pub timestamp: u64,
+	/// Whether the user has seen the notification
+	#[serde(default)]
+	pub read: bool,
+}
+
+/// What a notification is about
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+	/// Packages on an instance have newer versions available
+	PackageUpdates,
+	/// Installed plugins have newer versions available
+	PluginUpdates,
+	/// A task that was running in the background failed
+	TaskFailed,
+	/// A Java installation is no longer supported
+	JavaEndOfLife,
+	/// A Java installation doesn't work
+	BrokenJava,
+	/// Anything else, such as notifications from plugins
+	#[serde(other)]
+	Other,
+}
+
+/// Persistent list of notifications
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct NotificationStore {
+	/// All stored notifications, oldest first
+	notifications: Vec<Notification>,
+	/// The ID to give to the next notification
+	next_id: u64,
+}
+
+impl NotificationStore {
+	/// Opens the stored notifications
+	pub fn open(paths: &Paths) -> anyhow::Result<Self> {
+		let path = Self::get_path(paths);
+		if path.exists() {
+			json_from_file(path).context("Failed to open notifications")
+		} else {
+			Ok(Self::default())
+		}
+	}
+
+	/// Writes the notifications
+	pub fn write(&self, paths: &Paths) -> anyhow::Result<()> {
+		json_to_file_replace(Self::get_path(paths), self).context("Failed to write notifications")
+	}
+
+	/// Gets the path to the notifications file
+	fn get_path(paths: &Paths) -> PathBuf {
+		paths.internal.join("notifications.json")
+	}
+
+	/// Acquires the lock on the notifications file. This should be held while opening, changing,
+	/// and writing the notifications so that changes from other processes aren't lost
+	pub fn lock(paths: &Paths) -> anyhow::Result<FileLock> {
+		FileLock::acquire(&paths.internal.join("notifications.lock"))
+			.context("Failed to lock notifications")
+	}
+
+	/// Adds a new notification, returning its ID
+	pub fn add(
+		&mut self,
+		kind: NotificationKind,
+		title: String,
+		message: String,
+		subject: Option<String>,
+	) -> u64 {
+		if subject.is_some() {
+			self.notifications
+				.retain(|x| x.read || x.kind != kind || x.subject != subject);
+		}
+
+		let id = self.next_id;
+		self.next_id += 1;
+		self.notifications.push(Notification {
+			id,
+			kind,
+			title,
+			message,
+			subject,
+			timestamp: utc_timestamp().unwrap_or_default(),
+			read: false,
+		});
+
+		if self.notifications.len() > MAX_NOTIFICATIONS {
+			let excess = self.notifications.len() - MAX_NOTIFICATIONS;
+			self.notifications.drain(0..excess);
+		}
+
+		id
+	}
+
+	/// Iterates over the notifications, newest first
+	pub fn iter(&self) -> impl Iterator<Item = &Notification> {
+		self.notifications.iter().rev()
+	}
+
+	/// Gets the number of notifications that haven't been read
+	pub fn unread_count(&self) -> usize {
+		self.notifications.iter().filter(|x| !x.read).count()
+	}
+
+	/// Marks a notification as read. Returns false if it does not exist
+	pub fn mark_read(&mut self, id: u64) -> bool {
+		let Some(notification) = self.notifications.iter_mut().find(|x| x.id == id) else {
+			return false;
+		};
+		notification.read = true;
+		true
+	}
+
+	/// Marks all notifications as read
+	pub fn mark_all_read(&mut self) {
+		for notification in &mut self.notifications {
+			notification.read = true;
+		}
+	}
+
+	/// Removes a notification. Returns false if it does not exist
+	pub fn remove(&mut self, id: u64) -> bool {
+		let len = self.notifications.len();
+		self.notifications.retain(|x| x.id != id);
+		self.notifications.len() != len
+	}
+
+	/// Removes all notifications
+	pub fn clear(&mut self) {
+		self.notifications.clear();
+	}
+}
+
+/// Adds a notification to the stored ones
+pub fn notify(
+	paths: &Paths,
+	kind: NotificationKind,
+	title: impl Into<String>,
+	message: impl Into<String>,
+	subject: Option<&str>,
+) -> anyhow::Result<()> {
+	let _lock = NotificationStore::lock(paths)?;
+	let mut store = NotificationStore::open(paths)?;
+	store.add(
+		kind,
+		title.into(),
+		message.into(),
+		subject.map(ToString::to_string),
+	);
+	store.write(paths)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_notification_replacement() {
+		let mut store = NotificationStore::default();
+		let first = store.add(
+			NotificationKind::PackageUpdates,
+			"Updates".into(),
+			"1 update".into(),
+			Some("foo".into()),
+		);
+		store.add(
+			NotificationKind::PackageUpdates,
+			"Updates".into(),
+			"2 updates".into(),
+			Some("foo".into()),
+		);
+		store.add(
+			NotificationKind::PackageUpdates,
+			"Updates".into(),
+			"1 update".into(),
+			Some("bar".into()),
+		);
+		assert_eq!(store.unread_count(), 2);
+		assert!(!store.mark_read(first));
+
+		// Read notifications are kept
+		store.mark_all_read();
+		store.add(
+			NotificationKind::PackageUpdates,
+			"Updates".into(),
+			"3 updates".into(),
+			Some("foo".into()),
+		);
+		assert_eq!(store.iter().count(), 3);
+		assert_eq!(store.unread_count(), 1);
+		assert_eq!(store.iter().next().unwrap().message, "3 updates");
+	}
+
+	#[test]
+	fn test_notification_limit() {
+		let mut store = NotificationStore::default();
+		for _ in 0..MAX_NOTIFICATIONS + 5 {
+			store.add(NotificationKind::Other, "".into(), "".into(), None);
+		}
+		assert_eq!(store.iter().count(), MAX_NOTIFICATIONS);
+		assert_eq!(store.iter().last().unwrap().id, 5);
+	}
+}
@@ -0,0 +1,45 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::Context;
+
+/// Command used to open directories in the file manager
+#[cfg(target_os = "windows")]
+const OPEN_DIR_CMD: &str = "explorer";
+/// Command used to open directories in the file manager
+#[cfg(target_os = "macos")]
+const OPEN_DIR_CMD: &str = "open";
+/// Command used to open directories in the file manager
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const OPEN_DIR_CMD: &str = "xdg-open";
+
+/// Opens a directory in the system file manager, showing its contents. The directory is
+/// created if it doesn't exist yet
+pub fn open_dir(path: &Path) -> anyhow::Result<()> {
+	std::fs::create_dir_all(path).context("Failed to create directory")?;
+
+	Command::new(OPEN_DIR_CMD)
+		.arg(path)
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.spawn()
+		.context("Failed to open file manager")?;
+
+	Ok(())
+}
+
+/// Shows a file in the system file manager, selecting it on platforms that support it.
+/// This can block, so it should be run on a blocking thread in async code
+pub fn reveal_file(path: &Path) {
+	showfile::show_path_in_file_manager(path);
+}
+
+/// Opens a directory in the file manager, or selects a file in it if one is given and exists
+pub fn open_dir_or_file(dir: &Path, file: Option<&Path>) -> anyhow::Result<()> {
+	if let Some(file) = file.filter(|x| x.exists()) {
+		reveal_file(file);
+		Ok(())
+	} else {
+		open_dir(dir)
+	}
+}
@@ -0,0 +1,67 @@
+use anyhow::Context;
+use nitro_core::io::logs::censor_log;
+use nitro_net::download::Client;
+use nitro_net::paste::{MCLOGS_BACKEND_ID, upload_to_mclogs};
+use nitro_plugin::hook::hooks::{AddPasteBackends, UploadPaste, UploadPasteArg};
+use nitro_shared::output::{MessageContents, NitroOutput};
+use nitro_shared::translate;
+
+use crate::{io::paths::Paths, plugin::PluginManager};
+
+/// Uploads text to a paste backend and returns the URL to view it. Account credentials are censored before uploading.
+/// Uses the built-in mclo.gs backend when no backend is given
+pub async fn upload_paste(
+	name: &str,
+	contents: &str,
+	backend: Option<&str>,
+	plugins: &PluginManager,
+	client: &Client,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<String> {
+	let backend = backend.unwrap_or(MCLOGS_BACKEND_ID);
+	let contents = censor_log(contents);
+
+	o.display(MessageContents::StartProcess(translate!(
+		o,
+		StartUploadingPaste,
+		"name" = name,
+		"backend" = backend
+	)));
+
+	let url = if backend == MCLOGS_BACKEND_ID {
+		upload_to_mclogs(&contents, client).await?
+	} else {
+		let backends = plugins
+			.call_hook(AddPasteBackends, &(), paths, o)
+			.await
+			.context("Failed to get paste backends from plugins")?
+			.flatten_all_results_with_ids(o)
+			.await?;
+		let (plugin, _) = backends
+			.into_iter()
+			.find(|x| x.1.id == backend)
+			.with_context(|| format!("Paste backend '{backend}' does not exist"))?;
+
+		let arg = UploadPasteArg {
+			backend: backend.to_string(),
+			name: name.to_string(),
+			contents,
+		};
+		let result = plugins
+			.call_hook_on_plugin(UploadPaste, &plugin, &arg, paths, o)
+			.await
+			.context("Failed to upload using plugin")?
+			.context("Paste plugin did not return a result")?;
+
+		result.result(o).await?
+	};
+
+	o.display(MessageContents::Success(translate!(
+		o,
+		FinishUploadingPaste,
+		"url" = &url
+	)));
+
+	Ok(url)
+}
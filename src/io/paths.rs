@@ -23,6 +23,10 @@ pub struct Paths {
 	pub logs: PathBuf,
 	/// Holds launch log files
 	pub launch_logs: PathBuf,
+	/// Holds crash bundles that are created when an instance crashes
+	pub crash_bundles: PathBuf,
+	/// Holds reports of crashes and errors in Nitrolaunch itself
+	pub reports: PathBuf,
 	/// Used for runtime info like PIDs
 	pub run: PathBuf,
 	/// Storing instance snapshots
@@ -53,6 +57,7 @@ impl Paths {
 			tokio::fs::create_dir_all(&self.pkg_index_cache),
 			tokio::fs::create_dir_all(&self.logs),
 			tokio::fs::create_dir_all(&self.launch_logs),
+			tokio::fs::create_dir_all(&self.crash_bundles),
 			tokio::fs::create_dir_all(&self.run),
 			tokio::fs::create_dir_all(&self.snapshots),
 			tokio::fs::create_dir_all(&self.proxy),
@@ -60,6 +65,9 @@ impl Paths {
 		);
 
 		self.core.create_dirs()?;
+		self.core
+			.relocate_dirs()
+			.context("Failed to relocate game file directories")?;
 
 		Ok(())
 	}
@@ -75,6 +83,8 @@ impl Paths {
 		let pkg_index_cache = pkg_cache.join("index");
 		let logs = data.join("logs");
 		let launch_logs = logs.join("launch");
+		let crash_bundles = logs.join("crash");
+		let reports = logs.join("reports");
 		let snapshots = internal.join("snapshots");
 		let proxy = data.join("proxy");
 		let plugins = data.join("plugins");
@@ -90,6 +100,8 @@ impl Paths {
 			pkg_index_cache,
 			logs,
 			launch_logs,
+			crash_bundles,
+			reports,
 			snapshots,
 			proxy,
 			plugins,
@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use nitro_core::io::prune::{PruneOptions, PruneReport, ReferencedFiles, prune};
+use nitro_shared::output::{MessageContents, NitroOutput};
+use nitro_shared::translate;
+use nitro_shared::util::format_bytes;
+
+use crate::config::Config;
+
+use super::lock::Lockfile;
+use super::paths::Paths;
+
+/// Removes game assets, libraries, and jars that aren't used by any of the instances in the config
+pub fn prune_game_files(
+	config: &Config,
+	options: &PruneOptions,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<PruneReport> {
+	o.display(MessageContents::StartProcess(translate!(
+		o,
+		StartPruningFiles
+	)));
+
+	let referenced =
+		get_referenced_files(config, paths).context("Failed to get files used by instances")?;
+	let report = prune(&referenced, options, &paths.core).context("Failed to prune files")?;
+
+	let count = report.file_count().to_string();
+	let size = format_bytes(report.total_size());
+	let message = if options.dry_run {
+		translate!(o, PruneDryRun, "count" = &count, "size" = &size)
+	} else {
+		translate!(o, FinishPruningFiles, "count" = &count, "size" = &size)
+	};
+	o.display(MessageContents::Success(message));
+
+	Ok(report)
+}
+
+/// Prunes unused game files if the configured interval has passed since the last time they were pruned
+pub fn prune_if_scheduled(
+	config: &Config,
+	lock: &mut Lockfile,
+	paths: &Paths,
+	o: &mut impl NitroOutput,
+) -> anyhow::Result<()> {
+	let Some(interval) = config.prefs.prune_interval else {
+		return Ok(());
+	};
+	if !lock.should_prune(interval) {
+		return Ok(());
+	}
+
+	prune_game_files(config, &PruneOptions::default(), paths, o)?;
+	lock.update_last_prune();
+
+	Ok(())
+}
+
+/// Gets the versions and files that are used by the instances in the config
+fn get_referenced_files(config: &Config, paths: &Paths) -> anyhow::Result<ReferencedFiles> {
+	let mut out = ReferencedFiles::default();
+	for instance in config.instances.values() {
+		let lock = instance
+			.get_lockfile(paths)
+			.with_context(|| format!("Failed to open lockfile for instance {}", instance.id()))?;
+		if let Some(version) = lock.get_minecraft_version() {
+			out.versions.insert(version.clone());
+		}
+		out.files
+			.extend(lock.get_classpath().iter().map(PathBuf::from));
+	}
+
+	Ok(out)
+}
@@ -0,0 +1,335 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use anyhow::Context;
+use nitro_core::io::json_to_file_replace;
+use nitro_core::io::logs::censor_log;
+use nitro_shared::util::{ARCH_STRING, OS_STRING, utc_timestamp};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::io::paths::Paths;
+
+/// URL for creating a new issue on the Nitrolaunch repository
+pub const ISSUE_URL: &str = "https://github.com/Nitrolaunch/nitrolaunch/issues/new";
+
+/// The maximum length of the report text put in an issue URL, since browsers and GitHub
+/// reject URLs that are too long
+const MAX_ISSUE_BODY_LENGTH: usize = 6000;
+
+/// Whether error reports are saved. Reports are opt-in, so this is off until the preference is read
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// Directory that reports from panics are saved to
+static PANIC_REPORTS_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// Counter to keep the IDs of reports unique within a process
+static REPORT_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// Sets whether error reports are saved
+pub fn set_enabled(enabled: bool) {
+	ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Checks whether error reports are saved
+pub fn is_enabled() -> bool {
+	ENABLED.load(Ordering::Relaxed)
+}
+
+/// A report of a panic or a failed operation that is stored locally. Reports are never
+/// sent anywhere automatically; the user can choose to submit them as an issue
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ErrorReport {
+	/// What caused the report
+	pub kind: ReportKind,
+	/// The operation that failed, if known
+	pub operation: Option<String>,
+	/// The error message, with account credentials censored
+	pub message: String,
+	/// The source location of a panic
+	pub location: Option<String>,
+	/// The version of Nitrolaunch that created the report
+	pub nitrolaunch_version: String,
+	/// The operating system
+	pub os: String,
+	/// The system architecture
+	pub arch: String,
+	/// When the report was created, as a UTC timestamp
+	pub timestamp: u64,
+}
+
+/// What caused an error report
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+	/// The program panicked
+	Panic,
+	/// An operation returned an error
+	Error,
+}
+
+impl ErrorReport {
+	/// Create a new report. Credentials are censored from the operation and message
+	pub fn new(
+		kind: ReportKind,
+		operation: Option<&str>,
+		message: &str,
+		location: Option<String>,
+	) -> Self {
+		Self {
+			kind,
+			operation: operation.map(censor_log),
+			message: censor_log(message),
+			location,
+			nitrolaunch_version: env!("CARGO_PKG_VERSION").to_string(),
+			os: OS_STRING.to_string(),
+			arch: ARCH_STRING.to_string(),
+			timestamp: utc_timestamp().unwrap_or_default(),
+		}
+	}
+
+	/// Create a new report for an operation that failed
+	pub fn from_error(operation: &str, error: &anyhow::Error) -> Self {
+		Self::new(
+			ReportKind::Error,
+			Some(operation),
+			&format!("{error:?}"),
+			None,
+		)
+	}
+
+	/// Gets a short title for the report
+	pub fn title(&self) -> String {
+		let message = strip_home_dir(&self.message);
+		let first_line = message.lines().next().unwrap_or_default();
+		match (self.kind, &self.operation) {
+			(ReportKind::Panic, _) => format!("Panic: {first_line}"),
+			(ReportKind::Error, Some(operation)) => format!("{operation}: {first_line}"),
+			(ReportKind::Error, None) => first_line.to_string(),
+		}
+	}
+
+	/// Formats the report as the body of an issue
+	pub fn to_issue_body(&self) -> String {
+		let mut message = strip_home_dir(&self.message);
+		if message.len() > MAX_ISSUE_BODY_LENGTH {
+			let mut end = MAX_ISSUE_BODY_LENGTH;
+			while !message.is_char_boundary(end) {
+				end -= 1;
+			}
+			message.truncate(end);
+			message.push_str("\n...");
+		}
+
+		let mut out = String::from("<!-- Describe what you were doing when this happened -->\n\n");
+		out.push_str("### Report\n\n");
+		out.push_str(&format!("- Kind: {:?}\n", self.kind));
+		if let Some(operation) = &self.operation {
+			out.push_str(&format!("- Operation: `{operation}`\n"));
+		}
+		if let Some(location) = &self.location {
+			out.push_str(&format!("- Location: `{location}`\n"));
+		}
+		out.push_str(&format!(
+			"- Nitrolaunch version: {}\n- OS: {} ({})\n\n",
+			self.nitrolaunch_version, self.os, self.arch
+		));
+		out.push_str(&format!("```\n{message}\n```\n"));
+
+		out
+	}
+
+	/// Gets the URL to create a GitHub issue with the contents of this report filled in
+	pub fn issue_url(&self) -> String {
+		let mut url = Url::parse(ISSUE_URL).expect("Issue URL should be valid");
+		url.query_pairs_mut()
+			.append_pair("title", &self.title())
+			.append_pair("body", &self.to_issue_body());
+
+		url.to_string()
+	}
+
+	/// Saves this report to the reports directory, returning its ID
+	pub fn save(&self, paths: &Paths) -> anyhow::Result<String> {
+		save_to_dir(self, &paths.reports)
+	}
+}
+
+/// A report that was saved, with its ID
+#[derive(Serialize, Debug, Clone)]
+pub struct SavedReport {
+	/// The ID of the report
+	pub id: String,
+	/// The report itself
+	#[serde(flatten)]
+	pub report: ErrorReport,
+}
+
+/// Saves a report for an operation that failed, if reports are enabled
+pub fn report_error(operation: &str, error: &anyhow::Error, paths: &Paths) {
+	if is_enabled() {
+		let _ = ErrorReport::from_error(operation, error).save(paths);
+	}
+}
+
+/// Installs a panic hook that saves a report when the program panics, if reports are enabled.
+/// The previous hook is still called afterwards
+pub fn install_panic_hook(paths: &Paths) {
+	if PANIC_REPORTS_DIR.set(paths.reports.clone()).is_err() {
+		return;
+	}
+
+	let previous = std::panic::take_hook();
+	std::panic::set_hook(Box::new(move |info| {
+		if is_enabled()
+			&& let Some(dir) = PANIC_REPORTS_DIR.get()
+		{
+			let payload = info.payload();
+			let message = if let Some(message) = payload.downcast_ref::<&str>() {
+				message.to_string()
+			} else if let Some(message) = payload.downcast_ref::<String>() {
+				message.clone()
+			} else {
+				"Unknown panic".to_string()
+			};
+			let location = info.location().map(|x| x.to_string());
+
+			let report = ErrorReport::new(ReportKind::Panic, None, &message, location);
+			let _ = save_to_dir(&report, dir);
+		}
+
+		previous(info);
+	}));
+}
+
+/// Gets all of the saved reports, with the newest first
+pub fn list_reports(paths: &Paths) -> anyhow::Result<Vec<SavedReport>> {
+	if !paths.reports.exists() {
+		return Ok(Vec::new());
+	}
+
+	let mut out = Vec::new();
+	for entry in paths
+		.reports
+		.read_dir()
+		.context("Failed to read reports directory")?
+	{
+		let path = entry?.path();
+		if path.extension().is_none_or(|x| x != "json") {
+			continue;
+		}
+		let Some(id) = path.file_stem() else {
+			continue;
+		};
+		let Ok(contents) = std::fs::read(&path) else {
+			continue;
+		};
+		let Ok(report) = serde_json::from_slice(&contents) else {
+			continue;
+		};
+
+		out.push(SavedReport {
+			id: id.to_string_lossy().to_string(),
+			report,
+		});
+	}
+
+	out.sort_by(|a, b| {
+		b.report
+			.timestamp
+			.cmp(&a.report.timestamp)
+			.then_with(|| b.id.cmp(&a.id))
+	});
+
+	Ok(out)
+}
+
+/// Gets a saved report
+pub fn get_report(id: &str, paths: &Paths) -> anyhow::Result<ErrorReport> {
+	let contents =
+		std::fs::read(get_report_path(id, paths)?).context("Failed to read report file")?;
+	serde_json::from_slice(&contents).context("Failed to deserialize report")
+}
+
+/// Removes a saved report
+pub fn remove_report(id: &str, paths: &Paths) -> anyhow::Result<()> {
+	std::fs::remove_file(get_report_path(id, paths)?).context("Failed to remove report file")
+}
+
+/// Removes all saved reports
+pub fn clear_reports(paths: &Paths) -> anyhow::Result<()> {
+	for report in list_reports(paths)? {
+		remove_report(&report.id, paths)?;
+	}
+
+	Ok(())
+}
+
+/// Gets the path to the file of a report
+fn get_report_path(id: &str, paths: &Paths) -> anyhow::Result<PathBuf> {
+	if id.is_empty() || id.contains(['/', '\\', '.']) {
+		anyhow::bail!("Invalid report ID '{id}'");
+	}
+
+	Ok(paths.reports.join(format!("{id}.json")))
+}
+
+/// Replaces the user's home directory in text with ~ so that their username isn't shared
+fn strip_home_dir(text: &str) -> String {
+	let Some(dirs) = directories::BaseDirs::new() else {
+		return text.to_string();
+	};
+	let home = dirs.home_dir().to_string_lossy();
+	if home.len() <= 1 {
+		return text.to_string();
+	}
+
+	text.replace(home.trim_end_matches(['/', '\\']), "~")
+}
+
+fn save_to_dir(report: &ErrorReport, dir: &Path) -> anyhow::Result<String> {
+	std::fs::create_dir_all(dir).context("Failed to create reports directory")?;
+	let id = format!(
+		"{}-{}-{}",
+		report.timestamp,
+		std::process::id(),
+		REPORT_COUNTER.fetch_add(1, Ordering::Relaxed)
+	);
+	json_to_file_replace(dir.join(format!("{id}.json")), report)
+		.context("Failed to write report")?;
+
+	Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_report_censoring_and_issue_url() {
+		let error = anyhow::anyhow!("--accessToken eyJhbGc.eyJzdWIi.c2lnbmF0dXJl was rejected")
+			.context("Failed to launch");
+		let report = ErrorReport::from_error("instance launch", &error);
+		assert!(!report.message.contains("eyJhbGc"));
+		assert_eq!(report.title(), "instance launch: Failed to launch");
+
+		let url = Url::parse(&report.issue_url()).unwrap();
+		let body = url
+			.query_pairs()
+			.find(|x| x.0 == "body")
+			.map(|x| x.1.to_string())
+			.unwrap();
+		assert!(body.contains("Failed to launch"));
+		assert!(!body.contains("eyJhbGc"));
+	}
+
+	#[test]
+	fn test_report_home_dir_stripped() {
+		let dirs = directories::BaseDirs::new().unwrap();
+		let home = dirs.home_dir();
+		let error = anyhow::anyhow!("Failed to read {}", home.join("test.json").display());
+		let report = ErrorReport::from_error("nitro instance", &error);
+		let body = report.to_issue_body();
+		assert!(!body.contains(&*home.to_string_lossy()));
+		assert!(body.contains("~"));
+	}
+}
@@ -0,0 +1,303 @@
+use std::env::consts::{ARCH, OS};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+use base64::Engine;
+use minisign_verify::{PublicKey, Signature};
+use nitro_config::preferences::UpdateChannel;
+use nitro_core::net::download;
+use nitro_net::github::{GithubRelease, get_github_releases};
+use reqwest::Client;
+use sha2::{Digest, Sha256};
+use version_compare::Version;
+
+/// Public key that releases are signed with. This is the same key that the GUI updater uses
+pub const UPDATE_PUBLIC_KEY: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IDc0QjRCMjY2RkI2QzM4NkIKUldSck9HejdackswZEJvRTFuM0hvSkFzcnRYcmNETXRiNmRnZ1Q5Y05rYUZKczhHOUNFMzFmWkYK";
+
+/// Extension of the signature asset that is published alongside each binary
+const SIGNATURE_EXTENSION: &str = ".sig";
+/// Extension of the checksum asset that is published alongside each binary
+const CHECKSUM_EXTENSION: &str = ".sha256";
+
+/// A newer version of the CLI that can be installed
+#[derive(Debug, Clone)]
+pub struct AvailableUpdate {
+	/// The version of the update
+	pub version: String,
+	/// The release notes
+	pub notes: Option<String>,
+	/// Whether the update is a prerelease
+	pub prerelease: bool,
+	/// URL to the binary
+	pub url: String,
+	/// URL to the signature of the binary
+	pub signature_url: String,
+	/// URL to the SHA-256 checksum of the binary, if one was published
+	pub checksum_url: Option<String>,
+}
+
+/// How an update was installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallResult {
+	/// The executable was replaced and the new version will be used from now on
+	Replaced,
+	/// The running executable can't be replaced, so the update was staged and
+	/// will be applied the next time the CLI runs
+	Staged,
+}
+
+/// Checks the releases on GitHub for a newer version of the CLI in a channel
+pub async fn check_for_update(
+	channel: UpdateChannel,
+	client: &Client,
+) -> anyhow::Result<Option<AvailableUpdate>> {
+	let releases = get_github_releases("Nitrolaunch", "nitrolaunch", client)
+		.await
+		.context("Failed to get releases")?;
+
+	Ok(find_update(&releases, channel, crate::VERSION))
+}
+
+/// Finds the newest release in a channel that is newer than the current version and has a binary for this system
+pub fn find_update(
+	releases: &[GithubRelease],
+	channel: UpdateChannel,
+	current_version: &str,
+) -> Option<AvailableUpdate> {
+	let current_version = Version::from(current_version)?;
+
+	let mut best: Option<(Version, AvailableUpdate)> = None;
+	for release in releases {
+		// Plugins and the GUI are released from the same repository
+		if release.tag_name.contains("plugin") || release.tag_name.contains("gui") {
+			continue;
+		}
+		if release.prerelease && channel == UpdateChannel::Stable {
+			continue;
+		}
+
+		let version_str = release.tag_name.trim_start_matches('v');
+		let Some(version) = Version::from(version_str) else {
+			continue;
+		};
+		if !version.compare_to(&current_version, version_compare::Cmp::Gt) {
+			continue;
+		}
+		if let Some((best_version, _)) = &best
+			&& !version.compare_to(best_version, version_compare::Cmp::Gt)
+		{
+			continue;
+		}
+
+		let binary_name = get_binary_asset_name();
+		let Some(binary) = release.assets.iter().find(|x| x.name == binary_name) else {
+			continue;
+		};
+		// Unsigned binaries are never installed
+		let Some(signature) = release
+			.assets
+			.iter()
+			.find(|x| x.name == format!("{binary_name}{SIGNATURE_EXTENSION}"))
+		else {
+			continue;
+		};
+		let checksum = release
+			.assets
+			.iter()
+			.find(|x| x.name == format!("{binary_name}{CHECKSUM_EXTENSION}"));
+
+		let update = AvailableUpdate {
+			version: version_str.to_string(),
+			notes: release.body.clone(),
+			prerelease: release.prerelease,
+			url: binary.browser_download_url.clone(),
+			signature_url: signature.browser_download_url.clone(),
+			checksum_url: checksum.map(|x| x.browser_download_url.clone()),
+		};
+		best = Some((version, update));
+	}
+
+	best.map(|x| x.1)
+}
+
+/// Downloads the binary of an update and verifies it
+pub async fn download_update(update: &AvailableUpdate, client: &Client) -> anyhow::Result<Vec<u8>> {
+	let binary = download::bytes(&update.url, client)
+		.await
+		.context("Failed to download update")?;
+	let signature = download::text(&update.signature_url, client)
+		.await
+		.context("Failed to download update signature")?;
+	let checksum = if let Some(url) = &update.checksum_url {
+		Some(
+			download::text(url, client)
+				.await
+				.context("Failed to download update checksum")?,
+		)
+	} else {
+		None
+	};
+
+	verify_update(&binary, &signature, checksum.as_deref())?;
+
+	Ok(binary.to_vec())
+}
+
+/// Verifies a downloaded binary against its base64-encoded minisign signature and an optional SHA-256 checksum
+pub fn verify_update(binary: &[u8], signature: &str, checksum: Option<&str>) -> anyhow::Result<()> {
+	if let Some(checksum) = checksum {
+		// Checksum files may also contain the file name after the hash
+		let expected = checksum.split_whitespace().next().unwrap_or_default();
+		let actual = hex::encode(Sha256::digest(binary));
+		if !expected.eq_ignore_ascii_case(&actual) {
+			bail!("Checksum of the downloaded update does not match");
+		}
+	}
+
+	let public_key =
+		decode_base64_text(UPDATE_PUBLIC_KEY).context("Failed to decode public key")?;
+	let public_key = PublicKey::decode(&public_key).context("Failed to parse public key")?;
+	let signature = decode_base64_text(signature.trim()).context("Failed to decode signature")?;
+	let signature = Signature::decode(&signature).context("Failed to parse signature")?;
+	public_key
+		.verify(binary, &signature, false)
+		.context("Signature of the downloaded update is invalid")?;
+
+	Ok(())
+}
+
+/// Installs a verified binary in place of the running executable
+pub fn install_update(binary: &[u8]) -> anyhow::Result<InstallResult> {
+	let exe = get_current_exe()?;
+
+	// Windows doesn't allow replacing the executable while it is running
+	if cfg!(windows) {
+		std::fs::write(get_staged_path(&exe), binary).context("Failed to stage update")?;
+		return Ok(InstallResult::Staged);
+	}
+
+	let temp_path = exe.with_file_name(".nitro_update");
+	std::fs::write(&temp_path, binary).context("Failed to write update")?;
+	let permissions = std::fs::metadata(&exe)
+		.context("Failed to get executable permissions")?
+		.permissions();
+	std::fs::set_permissions(&temp_path, permissions)
+		.context("Failed to set update permissions")?;
+	std::fs::rename(&temp_path, &exe).context("Failed to replace executable")?;
+
+	Ok(InstallResult::Replaced)
+}
+
+/// Applies an update that was staged by a previous run and cleans up after old ones.
+/// Returns true if an update was applied. The running executable can be renamed even where it
+/// can't be replaced, so it is moved aside and the new version is used from the next run
+pub fn apply_staged_update() -> anyhow::Result<bool> {
+	let exe = get_current_exe()?;
+	let old_path = get_old_path(&exe);
+	if old_path.exists() {
+		let _ = std::fs::remove_file(&old_path);
+	}
+
+	let staged_path = get_staged_path(&exe);
+	if !staged_path.exists() {
+		return Ok(false);
+	}
+
+	std::fs::rename(&exe, &old_path).context("Failed to move old executable")?;
+	if let Err(e) = std::fs::rename(&staged_path, &exe) {
+		// Put the old executable back so that the CLI still works
+		let _ = std::fs::rename(&old_path, &exe);
+		return Err(e).context("Failed to move staged update");
+	}
+
+	Ok(true)
+}
+
+/// Gets the name of the release asset for the binary on this system
+fn get_binary_asset_name() -> String {
+	let extension = if cfg!(windows) { ".exe" } else { "" };
+	format!("nitro-{OS}-{ARCH}{extension}")
+}
+
+fn get_current_exe() -> anyhow::Result<PathBuf> {
+	let exe = std::env::current_exe().context("Failed to get path to the executable")?;
+	Ok(exe.canonicalize().unwrap_or(exe))
+}
+
+fn get_staged_path(exe: &Path) -> PathBuf {
+	append_extension(exe, "new")
+}
+
+fn get_old_path(exe: &Path) -> PathBuf {
+	append_extension(exe, "old")
+}
+
+fn append_extension(path: &Path, extension: &str) -> PathBuf {
+	let mut path = path.as_os_str().to_owned();
+	path.push(".");
+	path.push(extension);
+	PathBuf::from(path)
+}
+
+/// Decodes base64 that contains the text of a minisign file
+fn decode_base64_text(data: &str) -> anyhow::Result<String> {
+	let data = base64::engine::general_purpose::STANDARD.decode(data)?;
+	Ok(String::from_utf8(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use nitro_net::github::GithubAsset;
+
+	use super::*;
+
+	fn release(tag: &str, prerelease: bool) -> GithubRelease {
+		let binary_name = get_binary_asset_name();
+		let asset = |name: String| GithubAsset {
+			browser_download_url: format!("https://example.com/{tag}/{name}"),
+			name,
+			content_type: "application/octet-stream".into(),
+		};
+		GithubRelease {
+			id: 0,
+			tag_name: tag.into(),
+			name: tag.into(),
+			body: None,
+			prerelease,
+			assets: vec![
+				asset(binary_name.clone()),
+				asset(format!("{binary_name}{SIGNATURE_EXTENSION}")),
+			],
+		}
+	}
+
+	#[test]
+	fn test_find_update() {
+		let releases = vec![
+			release("plugin-foo-2.0.0", false),
+			release("0.32.0-beta.1", true),
+			release("0.31.0", false),
+			release("0.30.0", false),
+		];
+
+		let update = find_update(&releases, UpdateChannel::Stable, "0.30.0").unwrap();
+		assert_eq!(update.version, "0.31.0");
+		let update = find_update(&releases, UpdateChannel::Beta, "0.30.0").unwrap();
+		assert_eq!(update.version, "0.32.0-beta.1");
+		assert!(find_update(&releases, UpdateChannel::Stable, "0.31.0").is_none());
+
+		let mut unsigned = release("0.33.0", false);
+		unsigned.assets.pop();
+		assert!(find_update(&[unsigned], UpdateChannel::Stable, "0.30.0").is_none());
+	}
+
+	#[test]
+	fn test_verify_update_rejects_bad_binaries() {
+		let binary = b"nitro";
+		let bad_checksum = hex::encode(Sha256::digest(b"other"));
+		assert!(verify_update(binary, "", Some(&bad_checksum)).is_err());
+
+		let checksum = format!("{}  nitro", hex::encode(Sha256::digest(binary)));
+		assert!(verify_update(binary, "not a signature", Some(&checksum)).is_err());
+	}
+}
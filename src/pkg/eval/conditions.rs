@@ -1,6 +1,9 @@
+use anyhow::bail;
+use nitro_config::package::EvalPermissions;
 use nitro_shared::versions::VersionPattern;
 
 use super::EvalData;
+use nitro_parse::builtins::compare_semver;
 use nitro_parse::conditions::{ArchCondition, ConditionKind, OSCondition};
 use nitro_parse::vars::VariableStore;
 
@@ -29,10 +32,19 @@ pub fn eval_condition(condition: &ConditionKind, eval: &EvalData) -> anyhow::Res
 			.params
 			.features
 			.contains(&feature.get(&eval.vars)?)),
-		ConditionKind::OS(os) => Ok(check_os_condition(os.get())),
-		ConditionKind::Arch(arch) => Ok(check_arch_condition(arch.get())),
+		ConditionKind::OS(os) => {
+			check_host_condition_allowed("os", eval)?;
+			Ok(check_os_condition(os.get()))
+		}
+		ConditionKind::Arch(arch) => {
+			check_host_condition_allowed("arch", eval)?;
+			Ok(check_arch_condition(arch.get()))
+		}
 		ConditionKind::Stability(stability) => Ok(eval.input.params.stability == *stability.get()),
-		ConditionKind::Language(lang) => Ok(eval.input.constants.language == *lang.get()),
+		ConditionKind::Language(lang) => {
+			check_host_condition_allowed("language", eval)?;
+			Ok(eval.input.constants.language == *lang.get())
+		}
 		ConditionKind::ContentVersion(version) => {
 			let version = version.get(&eval.vars)?;
 
@@ -48,10 +60,29 @@ pub fn eval_condition(condition: &ConditionKind, eval: &EvalData) -> anyhow::Res
 		ConditionKind::Value(left, right) => Ok(left.get(&eval.vars)? == right.get(&eval.vars)?),
 		ConditionKind::Defined(var) => Ok(eval.vars.var_exists(var.get())),
 		ConditionKind::Const(val) => Ok(val.get_clone()),
-		ConditionKind::Plugin(plugin) => Ok(eval.plugins.has_plugin(&plugin.get(&eval.vars)?)),
+		ConditionKind::Plugin(plugin) => {
+			check_host_condition_allowed("plugin", eval)?;
+			Ok(eval.plugins.has_plugin(&plugin.get(&eval.vars)?))
+		}
+		ConditionKind::Semver(left, op, right) => {
+			let ordering = compare_semver(&left.get(&eval.vars)?, &right.get(&eval.vars)?)?;
+			Ok(op.get().matches(ordering))
+		}
 	}
 }
 
+/// Checks that a condition which reveals information about the user's system can be used.
+/// Restricted packages can't use these, so that their results only depend on the instance
+fn check_host_condition_allowed(condition: &str, eval: &EvalData) -> anyhow::Result<()> {
+	if eval.input.params.perms == EvalPermissions::Restricted {
+		bail!(
+			"The '{condition}' condition is not allowed for packages with restricted permissions"
+		);
+	}
+
+	Ok(())
+}
+
 /// Checks an OS condition to see if it matches the current operating system
 pub fn check_os_condition(condition: &OSCondition) -> bool {
 	match condition {
@@ -0,0 +1,328 @@
+use nitro_config::package::AddonConflictResolution;
+use nitro_pkg::addon::PackageAddon;
+use nitro_shared::minecraft::AddonKind;
+use nitro_shared::pkg::ArcPkgReq;
+
+use crate::addon::{AddonRequest, get_addon_instance_filename};
+use crate::config::package::PackageConfig;
+
+use super::ResolvedPackage;
+
+/// A conflict between the addons of two different packages
+#[derive(Debug, Clone)]
+pub struct AddonConflict {
+	/// What the addons conflict on
+	pub kind: AddonConflictKind,
+	/// The addon from the first package
+	pub first: ConflictingAddon,
+	/// The addon from the second package
+	pub second: ConflictingAddon,
+	/// How the conflict was resolved. Will be None if no package configured a resolution for it
+	pub resolution: Option<AppliedConflictResolution>,
+}
+
+impl AddonConflict {
+	/// Gets the resolutions that could be configured to resolve this conflict
+	pub fn get_options(&self) -> Vec<AddonConflictResolution> {
+		match self.kind {
+			AddonConflictKind::SamePath(..) => vec![
+				AddonConflictResolution::Priority,
+				AddonConflictResolution::Rename,
+				AddonConflictResolution::Skip,
+			],
+			// Renaming would just install the same content twice
+			AddonConflictKind::SameContent => vec![
+				AddonConflictResolution::Priority,
+				AddonConflictResolution::Skip,
+			],
+		}
+	}
+}
+
+/// One of the addons in a conflict
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictingAddon {
+	/// The package that installs the addon
+	pub pkg: ArcPkgReq,
+	/// The ID of the addon in the package
+	pub addon: String,
+}
+
+/// What two addons conflict on
+#[derive(Debug, Clone, PartialEq)]
+pub enum AddonConflictKind {
+	/// Both addons would be installed to the same file
+	SamePath(String),
+	/// Both addons are the same mod or plugin
+	SameContent,
+}
+
+/// A resolution that was applied to a conflict
+#[derive(Debug, Clone)]
+pub enum AppliedConflictResolution {
+	/// The addon was not installed
+	Skipped(ConflictingAddon),
+	/// The addon was installed with a new filename
+	Renamed(ConflictingAddon, String),
+}
+
+/// Finds conflicts between the addons of resolved packages and applies the resolutions
+/// that are configured for them. Returns all of the conflicts, including unresolved ones.
+pub fn resolve_addon_conflicts(
+	packages: &mut [ResolvedPackage],
+	configs: &[PackageConfig],
+) -> Vec<AddonConflict> {
+	let mut out: Vec<AddonConflict> = Vec::new();
+	for mut conflict in find_addon_conflicts(packages) {
+		// An earlier resolution may have already removed this conflict
+		let is_handled = out.iter().any(|x| match &x.resolution {
+			Some(AppliedConflictResolution::Skipped(addon)) => {
+				addon == &conflict.first || addon == &conflict.second
+			}
+			Some(AppliedConflictResolution::Renamed(addon, ..)) => {
+				matches!(conflict.kind, AddonConflictKind::SamePath(..))
+					&& (addon == &conflict.first || addon == &conflict.second)
+			}
+			None => false,
+		});
+		if is_handled {
+			continue;
+		}
+
+		let get_resolution = |pkg: &ArcPkgReq| {
+			configs
+				.iter()
+				.find(|x| x.get_request() == *pkg)
+				.and_then(|x| x.on_conflict)
+		};
+		let first = get_resolution(&conflict.first.pkg);
+		let second = get_resolution(&conflict.second.pkg);
+		let can_rename = matches!(conflict.kind, AddonConflictKind::SamePath(..));
+
+		use AddonConflictResolution::*;
+		let (addon, rename) = match (first, second) {
+			(Some(Priority), Some(Priority)) => {
+				out.push(conflict);
+				continue;
+			}
+			(Some(Priority), _) => (conflict.second.clone(), false),
+			(_, Some(Priority)) => (conflict.first.clone(), false),
+			(Some(Skip), _) => (conflict.first.clone(), false),
+			(_, Some(Skip)) => (conflict.second.clone(), false),
+			(Some(Rename), _) if can_rename => (conflict.first.clone(), true),
+			(_, Some(Rename)) if can_rename => (conflict.second.clone(), true),
+			_ => {
+				out.push(conflict);
+				continue;
+			}
+		};
+
+		let package = packages
+			.iter_mut()
+			.find(|x| x.req == addon.pkg)
+			.expect("Package should exist");
+		if rename {
+			let file_name = rename_addon(&mut package.eval.addon_reqs, &addon);
+			conflict.resolution = Some(AppliedConflictResolution::Renamed(addon, file_name));
+		} else {
+			package
+				.eval
+				.addon_reqs
+				.retain(|x| x.addon.id != addon.addon);
+			conflict.resolution = Some(AppliedConflictResolution::Skipped(addon));
+		}
+
+		out.push(conflict);
+	}
+
+	out
+}
+
+/// Finds conflicts between the addons of resolved packages. Addons are the same content if
+/// their hashes match, or if they are the only mod or plugin of packages with the same
+/// Modrinth or CurseForge project
+fn find_addon_conflicts(packages: &[ResolvedPackage]) -> Vec<AddonConflict> {
+	let mut out = Vec::new();
+	for (i, first_pkg) in packages.iter().enumerate() {
+		for second_pkg in &packages[i + 1..] {
+			let same_project = is_same_project(first_pkg, second_pkg);
+
+			for first in &first_pkg.eval.addon_reqs {
+				for second in &second_pkg.eval.addon_reqs {
+					let (first, second) = (&first.addon, &second.addon);
+					if first.kind == AddonKind::Modpack || second.kind == AddonKind::Modpack {
+						continue;
+					}
+
+					let kind = if first.kind == second.kind && first.file_name == second.file_name {
+						AddonConflictKind::SamePath(first.file_name.clone())
+					} else if is_code_addon(first.kind)
+						&& is_code_addon(second.kind)
+						&& (same_project || have_same_hash(first, second))
+					{
+						AddonConflictKind::SameContent
+					} else {
+						continue;
+					};
+
+					out.push(AddonConflict {
+						kind,
+						first: ConflictingAddon {
+							pkg: first_pkg.req.clone(),
+							addon: first.id.clone(),
+						},
+						second: ConflictingAddon {
+							pkg: second_pkg.req.clone(),
+							addon: second.id.clone(),
+						},
+						resolution: None,
+					});
+				}
+			}
+		}
+	}
+
+	out
+}
+
+/// Checks if two packages are for the same project and both install a single mod or plugin
+fn is_same_project(first: &ResolvedPackage, second: &ResolvedPackage) -> bool {
+	let code_addon_count = |pkg: &ResolvedPackage| {
+		pkg.eval
+			.addon_reqs
+			.iter()
+			.filter(|x| is_code_addon(x.addon.kind))
+			.count()
+	};
+	if code_addon_count(first) != 1 || code_addon_count(second) != 1 {
+		return false;
+	}
+
+	let (first, second) = (&first.eval.properties, &second.eval.properties);
+	let modrinth = first.modrinth_id.is_some() && first.modrinth_id == second.modrinth_id;
+	let curseforge = first.curseforge_id.is_some() && first.curseforge_id == second.curseforge_id;
+
+	modrinth || curseforge
+}
+
+/// Checks if two addons have a matching hash
+fn have_same_hash(first: &PackageAddon, second: &PackageAddon) -> bool {
+	let (first, second) = (&first.hashes, &second.hashes);
+	(first.sha512.is_some() && first.sha512 == second.sha512)
+		|| (first.sha256.is_some() && first.sha256 == second.sha256)
+}
+
+/// Checks if an addon kind is loaded as code, and can't be installed twice
+fn is_code_addon(kind: AddonKind) -> bool {
+	matches!(kind, AddonKind::Mod | AddonKind::Plugin)
+}
+
+/// Renames an addon to the unique filename for its package, returning the new filename
+fn rename_addon(addon_reqs: &mut [AddonRequest], addon: &ConflictingAddon) -> String {
+	let req = addon_reqs
+		.iter_mut()
+		.find(|x| x.addon.id == addon.addon)
+		.expect("Addon should exist");
+	req.addon.file_name = get_addon_instance_filename(
+		&addon.pkg.to_string_no_version().replace(":", "_"),
+		&addon.addon,
+		&req.addon.kind,
+	);
+
+	req.addon.file_name.clone()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use nitro_pkg::PkgRequest;
+	use nitro_pkg::properties::PackageProperties;
+	use nitro_pkg::script_eval::AddonInstructionData;
+	use nitro_shared::Side;
+	use nitro_shared::lang::Language;
+	use nitro_shared::loaders::Loader;
+	use nitro_shared::pkg::{AddonOptionalHashes, PackageStability};
+
+	use crate::io::paths::Paths;
+	use crate::pkg::eval::{
+		EvalConstants, EvalData, EvalInput, EvalParameters, Routine, create_valid_addon_request,
+	};
+	use crate::plugin::PluginManager;
+
+	use super::*;
+
+	#[test]
+	fn test_addon_conflict_resolution() {
+		let mut packages = vec![
+			create_package("foo", &[("mod", "shared.jar")]),
+			create_package("bar", &[("mod", "shared.jar"), ("other", "other.jar")]),
+		];
+
+		let conflicts = resolve_addon_conflicts(&mut packages, &[]);
+		assert_eq!(conflicts.len(), 1);
+		assert_eq!(
+			conflicts[0].kind,
+			AddonConflictKind::SamePath("shared.jar".into())
+		);
+		assert!(conflicts[0].resolution.is_none());
+
+		let mut config = PackageConfig::from_id("bar".into());
+		config.on_conflict = Some(AddonConflictResolution::Rename);
+		let conflicts = resolve_addon_conflicts(&mut packages, &[config]);
+		assert!(matches!(
+			conflicts[0].resolution,
+			Some(AppliedConflictResolution::Renamed(..))
+		));
+		assert_ne!(packages[1].eval.addon_reqs[0].addon.file_name, "shared.jar");
+		assert!(resolve_addon_conflicts(&mut packages, &[]).is_empty());
+
+		let mut config = PackageConfig::from_id("bar".into());
+		config.on_conflict = Some(AddonConflictResolution::Priority);
+		packages[1].eval.addon_reqs[0].addon.file_name = "shared.jar".into();
+		resolve_addon_conflicts(&mut packages, &[config]);
+		assert!(packages[0].eval.addon_reqs.is_empty());
+		assert_eq!(packages[1].eval.addon_reqs.len(), 2);
+	}
+
+	fn create_package(id: &str, addons: &[(&str, &str)]) -> ResolvedPackage {
+		let req = PkgRequest::parse(id, nitro_pkg::PkgRequestSource::UserRequire).arc();
+		let input = EvalInput {
+			constants: Arc::new(EvalConstants {
+				version: Some("1.19.2".into()),
+				version_list: vec!["1.19.2".to_string()],
+				loader: Loader::Fabric,
+				language: Language::AmericanEnglish,
+				default_stability: PackageStability::Latest,
+				suppress: Vec::new(),
+			}),
+			params: EvalParameters::new(Side::Client),
+		};
+		let plugins = PluginManager::new(&Paths::new_no_create().unwrap());
+		let mut eval = EvalData::new(
+			input,
+			req.clone(),
+			Arc::new(PackageProperties::default()),
+			&Routine::Install,
+			plugins,
+		);
+
+		for (addon_id, file_name) in addons {
+			let data = AddonInstructionData {
+				id: addon_id.to_string(),
+				file_name: Some(file_name.to_string()),
+				kind: AddonKind::Mod,
+				url: Some("example.com".into()),
+				path: None,
+				version: Some("1".into()),
+				modpack_format: None,
+				hashes: AddonOptionalHashes::default(),
+				size: None,
+			};
+			let addon_req = create_valid_addon_request(data, req.clone(), &eval.input).unwrap();
+			eval.addon_reqs.push(addon_req);
+		}
+
+		ResolvedPackage { req, eval }
+	}
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use anyhow::bail;
 use itertools::Itertools;
 use nitro_pkg::RequiredPackage;
+use nitro_pkg::compat::CompatibilityMatrix;
 use nitro_pkg::declarative::{
 	DeclarativeAddon, DeclarativeAddonVersion, DeclarativeConditionSet, DeclarativePackage,
 };
@@ -89,6 +90,7 @@ fn eval_declarative_package_impl(
 					version: version.version.clone(),
 					modpack_format: addon.modpack_format.clone(),
 					hashes: version.hashes.clone(),
+					size: version.size,
 				};
 
 				let addon_req = create_valid_addon_request(data, req.clone(), &eval_data.input)?;
@@ -115,7 +117,7 @@ fn eval_declarative_package_impl(
 				eval_data.selected_content_version = Some(content_version.clone());
 			}
 		} else {
-			handle_no_matched_versions(addon)?;
+			handle_no_matched_versions(addon, &eval_data.input)?;
 		}
 	}
 
@@ -340,12 +342,40 @@ fn check_condition_set(
 }
 
 /// Handle the case where no versions were matched for an addon
-fn handle_no_matched_versions(addon: &DeclarativeAddon) -> anyhow::Result<()> {
+fn handle_no_matched_versions(addon: &DeclarativeAddon, input: &EvalInput) -> anyhow::Result<()> {
 	// If the addon is optional then this is ok
 	if addon.optional {
 		return Ok(());
 	}
 
+	// Use the compatibility matrix to give a better explanation
+	if let Some(version) = &input.constants.version {
+		let version_list = &input.constants.version_list;
+		let matrix = CompatibilityMatrix::from_addon(addon);
+		if !matrix.supports_minecraft_version(version, version_list) {
+			let supported = matrix.get_all_minecraft_versions(version_list);
+			if supported.is_empty() {
+				bail!("No version of this package supports Minecraft {version}");
+			}
+			// Only show the newest few versions so that the message doesn't get too long
+			let newest = supported.iter().rev().take(5).rev().join(", ");
+			bail!(
+				"No version of this package supports Minecraft {version}. Newest supported versions: {newest}"
+			);
+		}
+
+		let loader = &input.constants.loader;
+		if matrix
+			.get_supporting_entries(version, loader, version_list)
+			.next()
+			.is_none()
+		{
+			bail!(
+				"No version of this package supports Minecraft {version} with the {loader} loader"
+			);
+		}
+	}
+
 	bail!("No valid addon version found")
 }
 
@@ -1,5 +1,7 @@
 /// Evaluating script package conditions
 pub mod conditions;
+/// Detecting and resolving conflicts between the addons of packages
+pub mod conflicts;
 /// Evaluating declarative packages
 pub mod declarative;
 /// Evaluating script packages
@@ -9,6 +11,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use anyhow::Context;
+use anyhow::anyhow;
 use anyhow::bail;
 use async_trait::async_trait;
 use nitro_config::package::EvalPermissions;
@@ -42,6 +45,7 @@ use nitro_shared::pkg::ArcPkgReq;
 use nitro_shared::pkg::PackageDiff;
 use nitro_shared::pkg::PackageID;
 use nitro_shared::pkg::PackageOverrides;
+use nitro_shared::pkg::ResolutionError;
 use nitro_shared::util::io::replace_tilde;
 use nitro_shared::util::is_valid_identifier;
 use nitro_shared::versions::VersionPattern;
@@ -49,6 +53,7 @@ use reqwest::Client;
 
 use self::conditions::check_arch_condition;
 use self::conditions::check_os_condition;
+use self::conflicts::{AddonConflict, resolve_addon_conflicts};
 use self::declarative::eval_declarative_package;
 use self::script::eval_script_package;
 
@@ -159,6 +164,8 @@ pub struct EvalParameters {
 	pub preferred_content_versions: Vec<String>,
 	/// Whether to force installation of the requested content version
 	pub force: bool,
+	/// Whether to install the package even if it doesn't support the side
+	pub ignore_supported_sides: bool,
 }
 
 impl EvalParameters {
@@ -173,6 +180,7 @@ impl EvalParameters {
 			required_content_versions: Vec::new(),
 			preferred_content_versions: Vec::new(),
 			force: false,
+			ignore_supported_sides: false,
 		}
 	}
 
@@ -190,6 +198,8 @@ impl EvalParameters {
 		self.features = features;
 		self.perms = config.permissions;
 		self.stability = config.stability;
+		// Configuring a side overrides the sides declared by the package
+		self.ignore_supported_sides = config.side.is_some();
 
 		Ok(())
 	}
@@ -350,6 +360,7 @@ pub fn eval_check_properties(
 	if let Some(supported_sides) = &properties.supported_sides
 		&& !supported_sides.is_empty()
 		&& !supported_sides.contains(&input.params.side)
+		&& !input.params.ignore_supported_sides
 	{
 		return Ok(true);
 	}
@@ -434,6 +445,7 @@ pub fn create_valid_addon_request(
 		version,
 		modpack_format: data.modpack_format,
 		hashes: data.hashes,
+		size: data.size,
 	};
 
 	if let Some(url) = data.url {
@@ -609,6 +621,8 @@ pub struct ResolutionAndEvalResult {
 	pub packages: Vec<ResolvedPackage>,
 	/// Package recommendations that were not satisfied
 	pub unfulfilled_recommendations: Vec<nitro_pkg::resolve::RecommendedPackage>,
+	/// Conflicts between the addons of packages
+	pub addon_conflicts: Vec<AddonConflict>,
 }
 
 impl ResolutionAndEvalResult {
@@ -696,6 +710,41 @@ impl ResolutionAndEvalResult {
 	}
 }
 
+impl ResolutionAndEvalResult {
+	/// Estimates how much will be downloaded to install the addons for all of the packages,
+	/// skipping addons that are already stored
+	pub fn estimate_download_size(
+		&self,
+		paths: &Paths,
+		instance_id: &str,
+		force: bool,
+	) -> DownloadSizeEstimate {
+		let mut out = DownloadSizeEstimate::default();
+		for addon in self.packages.iter().flat_map(|x| &x.eval.addon_reqs) {
+			if !addon.needs_download(paths, instance_id, force) {
+				continue;
+			}
+
+			if let Some(size) = addon.addon.size {
+				out.bytes += size;
+			} else {
+				out.unknown_files += 1;
+			}
+		}
+
+		out
+	}
+}
+
+/// An estimate of how much will be downloaded
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DownloadSizeEstimate {
+	/// The combined size in bytes of the files with known sizes
+	pub bytes: u64,
+	/// The number of files that will be downloaded but don't have a known size
+	pub unknown_files: usize,
+}
+
 /// Data from a package after resolution
 pub struct ResolvedPackage {
 	/// The package
@@ -730,6 +779,7 @@ pub async fn resolve(
 
 	let common_input = EvaluatorCommonInput { client, paths };
 
+	let configs = packages;
 	let packages = packages
 		.iter()
 		.map(|x| EvalPackageConfig((*x).clone(), x.get_request()))
@@ -741,8 +791,10 @@ pub async fn resolve(
 		{
 			Ok(result) => result,
 			Err(e) => {
+				// The full error is consumed by the output, so keep its message for the returned error
+				let summary = ResolutionError::Misc(anyhow!("{e}"));
 				o.display_special_resolution_error(e, instance_id);
-				bail!("Package resolution failed");
+				return Err(anyhow::Error::new(summary).context("Package resolution failed"));
 			}
 		};
 
@@ -765,9 +817,12 @@ pub async fn resolve(
 		print_recommendation_warning(package, o);
 	}
 
+	let addon_conflicts = resolve_addon_conflicts(&mut packages, configs);
+
 	Ok(ResolutionAndEvalResult {
 		packages,
 		unfulfilled_recommendations: result.unfulfilled_recommendations,
+		addon_conflicts,
 	})
 }
 
@@ -7,7 +7,8 @@ use nitro_parse::vars::{HashMapVariableStore, ReservedConstantVariables, Variabl
 use nitro_pkg::RecommendedPackage;
 use nitro_pkg::properties::PackageProperties;
 use nitro_pkg::script_eval::{
-	AddonInstructionData, ScriptEvalConfig, ScriptEvaluator as ScriptEvaluatorTrait,
+	AddonInstructionData, ScriptEvalConfig, ScriptEvalLimits,
+	ScriptEvaluator as ScriptEvaluatorTrait,
 };
 use nitro_plugin::hook::hooks::{CustomPackageInstruction, CustomPackageInstructionArg};
 use nitro_shared::output::NoOp;
@@ -50,7 +51,10 @@ pub async fn eval_script_package(
 		parsed,
 		&mut ScriptEvaluator,
 		&mut data,
-		&ScriptEvalConfig { reason },
+		&ScriptEvalConfig {
+			reason,
+			limits: ScriptEvalLimits::default(),
+		},
 	)
 	.await?;
 
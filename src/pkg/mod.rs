@@ -8,8 +8,9 @@ pub mod reg;
 pub mod repo;
 
 use crate::io::paths::Paths;
-use nitro_core::net::download;
+use nitro_core::net::{download, stats};
 use nitro_pkg::PackageContentType;
+use nitro_pkg::compat::CompatibilityMatrix;
 use nitro_pkg::declarative::{
 	DeclarativeAddonVersion, DeclarativeConditionSet, DeclarativePackage,
 	deserialize_declarative_package,
@@ -221,6 +222,8 @@ impl Package {
 					.clone();
 				let client = client.clone();
 				return Some(async move { try_3!({ download::file(&url, &path, &client).await }) });
+			} else if let Some(url) = url {
+				stats::record_cache_hit(url);
 			}
 		}
 
@@ -346,6 +349,17 @@ impl Package {
 		}
 	}
 
+	/// Gets the matrix of content versions and the Minecraft versions and loaders that they support.
+	/// Returns None for script packages
+	pub async fn get_compatibility_matrix(
+		&self,
+		paths: &Paths,
+		client: &Client,
+	) -> anyhow::Result<Option<CompatibilityMatrix>> {
+		let contents = self.get_declarative_contents(paths, client).await?;
+		Ok(contents.map(CompatibilityMatrix::from_declarative))
+	}
+
 	/// Gets the content versions of this addon
 	pub async fn get_content_versions(
 		&self,
@@ -6,21 +6,30 @@ use nitro_pkg::PackageContentType;
 use nitro_pkg::PackageSearchResults;
 use nitro_pkg::PkgRequest;
 use nitro_pkg::PkgRequestSource;
+use nitro_pkg::declarative::DeclarativePackage;
+use nitro_pkg::details::{AuthorProfile, PackageDetails};
+use nitro_pkg::metadata::PackageMetadata;
+use nitro_pkg::properties::PackageProperties;
+use nitro_pkg::{
+	AggregatedSearchResult, AggregatedSearchResults, SearchCursor, SearchResultSource,
+};
 use nitro_shared::output::MessageContents;
 use nitro_shared::output::NitroOutput;
 use nitro_shared::pkg::ArcPkgReq;
 use nitro_shared::pkg::PackageSearchParameters;
+use nitro_shared::pkg::PackageSortMethod;
 use nitro_shared::versions::VersionPattern;
 use reqwest::Client;
 use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
 
-use super::Package;
+use super::repo::custom::CustomPackageRepository;
 use super::repo::{PackageRepository, query_all};
+use super::{Package, PkgLocation};
 use crate::io::paths::Paths;
 use crate::plugin::PluginManager;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// An object used to store and cache all of the packages that we are working with.
@@ -30,6 +39,9 @@ pub struct PkgRegistry {
 	/// The package repositories that the user has configured
 	pub repos: Vec<PackageRepository>,
 	packages: DashMap<ArcPkgReq, Arc<Package>>,
+	/// Packages pinned to files, which are kept when the registry is cleared.
+	/// Their requests are scoped to the instance they are configured for
+	pinned: DashMap<ArcPkgReq, Arc<Package>>,
 	plugins: PluginManager,
 }
 
@@ -39,6 +51,7 @@ impl PkgRegistry {
 		Self {
 			repos,
 			packages: DashMap::new(),
+			pinned: DashMap::new(),
 			plugins: plugins.clone(),
 		}
 	}
@@ -46,6 +59,10 @@ impl PkgRegistry {
 	/// Clear the registry
 	pub fn clear(&self) {
 		self.packages.clear();
+		for entry in self.pinned.iter() {
+			self.packages
+				.insert(entry.key().clone(), entry.value().clone());
+		}
 	}
 
 	/// Insert a package into the registry and return a mutable reference to the
@@ -58,6 +75,28 @@ impl PkgRegistry {
 			.clone()
 	}
 
+	/// Adds a package that is pinned to a file instead of coming from a repository.
+	/// The request should be scoped to its instance, like the ones from `PackageConfig::get_request`
+	pub fn insert_pinned(
+		&self,
+		req: ArcPkgReq,
+		contents: &DeclarativePackage,
+	) -> anyhow::Result<Arc<Package>> {
+		let contents =
+			serde_json::to_string(contents).context("Failed to serialize package contents")?;
+		let package = Package::new(
+			req.clone(),
+			PkgLocation::Inline(Arc::from(contents)),
+			PackageContentType::Declarative,
+			HashSet::new(),
+		);
+
+		let package = Arc::new(package);
+		self.pinned.insert(req.clone(), package.clone());
+
+		Ok(self.insert(req, package))
+	}
+
 	/// Checks if a package is in the registry already
 	pub fn has_now(&self, req: &PkgRequest) -> bool {
 		self.packages.contains_key(req)
@@ -291,88 +330,50 @@ impl PkgRegistry {
 		Ok(())
 	}
 
-	/// Searches the registry and repositories for packages
+	/// Searches the registry and repositories for packages. Results are taken from each repository in order
+	/// until the page is full, and the returned cursor continues from where this page left off
 	pub async fn search(
 		&self,
-		mut params: PackageSearchParameters,
+		params: PackageSearchParameters,
 		repo: Option<&str>,
 		paths: &Paths,
 		client: &Client,
 		o: &mut impl NitroOutput,
 	) -> anyhow::Result<PackageSearchResults> {
-		let original_count = params.count;
+		let original_count = params.count as usize;
 		if original_count == 0 {
 			return Ok(PackageSearchResults::default());
 		}
 
-		let mut total_results = 0;
-
-		// TODO: Get all the package contents at the beginning
+		let mut cursor = match &params.cursor {
+			Some(cursor) => SearchCursor::parse(cursor)?,
+			None => SearchCursor::default(),
+		};
 
-		let mut out = Vec::with_capacity(params.count as usize);
+		let mut out = Vec::with_capacity(original_count);
 		let mut previews = HashMap::new();
+		let mut stale = false;
 
 		// Search through all of the basic packages
 		if repo.is_none() || repo.is_some_and(|x| x == "core" || x == "std") {
-			let all_basic_packages = self
-				.get_all_available_packages(paths, client, o)
-				.await
-				.context("Failed to get available packages from basic repositories")?;
-
-			let mut num_skipped = 0;
-
-			for req in all_basic_packages.into_iter().sorted() {
-				if !params.categories.is_empty() || params.search.is_some() {
-					let pkg = self.query_insert(&req, true, paths, client, o).await?;
-					let meta = pkg.get_metadata(paths, client).await?;
-
-					// Check all of the parameters
-					if !params.categories.is_empty() {
-						let default = Vec::new();
-						if !params
-							.categories
-							.iter()
-							.any(|x| meta.categories.as_ref().unwrap_or(&default).contains(x))
-						{
-							continue;
-						}
-					}
-
-					if let Some(search) = &params.search {
-						let default = String::new();
-						if !req.id.to_lowercase().contains(search)
-							&& !meta
-								.name
-								.as_ref()
-								.unwrap_or(&default)
-								.to_lowercase()
-								.contains(search) && !meta
-							.description
-							.as_ref()
-							.unwrap_or(&default)
-							.to_lowercase()
-							.contains(search)
-						{
-							continue;
-						}
-					}
-				}
-
-				total_results += 1;
-				if num_skipped < params.skip {
-					num_skipped += 1;
-					continue;
-				}
-
-				if out.len() < original_count as usize {
-					out.push(req.to_string());
-				}
+			let position = cursor.get_position("core", params.skip);
+			if !position.finished {
+				let matching = self
+					.get_basic_search_matches(&params, paths, client, o)
+					.await?;
+
+				let requested = original_count - out.len();
+				let page: Vec<_> = matching
+					.iter()
+					.skip(position.skip)
+					.take(requested)
+					.map(|x| x.to_string())
+					.collect();
+				position.advance(requested, page.len(), matching.len(), None);
+				out.extend(page);
 			}
 		}
 
-		// Narrow the search limit
-		params.count -= out.len() as u8;
-
 		// Now search plugin repositories
 		let searched_repo = repo;
 		for repo in &self.repos {
@@ -381,8 +382,20 @@ impl PkgRegistry {
 					continue;
 				}
 
+				let position = cursor.get_position(repo.get_id(), params.skip);
+				if position.finished {
+					continue;
+				}
+
+				// Repositories are still searched once the page is full so that we know their total number of results
+				let requested = original_count - out.len();
+				let mut repo_params = params.clone();
+				repo_params.count = requested as u8;
+				repo_params.skip = position.skip;
+				repo_params.cursor = position.cursor.clone();
+
 				let result = repo
-					.search(params.clone(), &self.plugins, paths, o)
+					.search(repo_params, &self.plugins, paths, o)
 					.await
 					.with_context(|| {
 						format!(
@@ -390,28 +403,231 @@ impl PkgRegistry {
 							repo.get_id()
 						)
 					})?;
-				// Narrow the search limit
-				if result.results.len() <= params.count as usize {
-					params.count -= result.results.len() as u8;
-				}
-				let results = result.results.into_iter();
 
-				total_results += result.total_results;
-				if out.len() < original_count as usize {
-					out.extend(results);
-					out.truncate(original_count as usize);
-					previews.extend(result.previews);
-				}
+				let count = result.results.len().min(requested);
+				position.advance(requested, count, result.total_results, result.next_cursor);
+				stale |= result.stale;
+				out.extend(result.results.into_iter().take(count));
+				previews.extend(result.previews);
 			}
 		}
 
 		Ok(PackageSearchResults {
 			results: out,
-			total_results,
+			total_results: cursor.get_total(),
 			previews,
+			stale,
+			next_cursor: cursor.get_next(),
+		})
+	}
+
+	/// Gets all of the basic packages that match a search, in order
+	async fn get_basic_search_matches(
+		&self,
+		params: &PackageSearchParameters,
+		paths: &Paths,
+		client: &Client,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<Vec<ArcPkgReq>> {
+		let all_basic_packages = self
+			.get_all_available_packages(paths, client, o)
+			.await
+			.context("Failed to get available packages from basic repositories")?;
+
+		let needs_meta = !params.categories.is_empty()
+			|| params.search.is_some()
+			|| params.sort != PackageSortMethod::Relevance;
+
+		let mut matching = Vec::new();
+		for req in all_basic_packages.into_iter().sorted() {
+			let mut package_meta = None;
+			if needs_meta {
+				let pkg = self.query_insert(&req, true, paths, client, o).await?;
+				let meta = pkg.get_metadata(paths, client).await?;
+
+				// Check all of the parameters
+				if !params.categories.is_empty() {
+					let default = Vec::new();
+					if !params
+						.categories
+						.iter()
+						.any(|x| meta.categories.as_ref().unwrap_or(&default).contains(x))
+					{
+						continue;
+					}
+				}
+
+				if let Some(search) = &params.search {
+					let default = String::new();
+					if !req.id.to_lowercase().contains(search)
+						&& !meta
+							.name
+							.as_ref()
+							.unwrap_or(&default)
+							.to_lowercase()
+							.contains(search) && !meta
+						.description
+						.as_ref()
+						.unwrap_or(&default)
+						.to_lowercase()
+						.contains(search)
+					{
+						continue;
+					}
+				}
+
+				package_meta = Some(meta);
+			}
+
+			matching.push((req, package_meta));
+		}
+
+		sort_by_metadata(&mut matching, params.sort);
+
+		Ok(matching.into_iter().map(|(req, _)| req).collect())
+	}
+
+	/// Searches all of the repositories at the same time, merging results for the same project
+	/// that are found in more than one of them. Each repository returns up to the requested number of results,
+	/// so there may be more merged results than were asked for.
+	pub async fn search_all(
+		&self,
+		params: PackageSearchParameters,
+		paths: &Paths,
+		client: &Client,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<AggregatedSearchResults> {
+		if params.count == 0 {
+			return Ok(AggregatedSearchResults::default());
+		}
+
+		let mut cursor = match &params.cursor {
+			Some(cursor) => SearchCursor::parse(cursor)?,
+			None => SearchCursor::default(),
+		};
+
+		// Start the plugin repositories first since they are usually slower
+		let mut tasks = JoinSet::new();
+		for (i, repo) in self.repos.iter().enumerate() {
+			let PackageRepository::Custom(repo) = repo else {
+				continue;
+			};
+
+			let position = cursor.get_position(repo.get_id(), params.skip);
+			if position.finished {
+				continue;
+			}
+			let mut params = params.clone();
+			params.skip = position.skip;
+			params.cursor = position.cursor.clone();
+
+			let repo = repo.clone();
+			let plugins = self.plugins.clone();
+			let paths = paths.clone();
+			let mut o = o.get_lesser_copy();
+			tasks.spawn(async move {
+				let result = repo.search(params, &plugins, &paths, &mut o).await;
+				(i, repo.get_id().to_string(), result)
+			});
+		}
+
+		let mut repo_results = Vec::new();
+		let mut errors = HashMap::new();
+
+		// Search the basic packages, filling out their previews so that they can be merged
+		let core_index = self
+			.repos
+			.iter()
+			.position(|x| !matches!(x, PackageRepository::Custom(..)))
+			.unwrap_or_default();
+		let position = cursor.get_position("core", params.skip);
+		if !position.finished {
+			let mut core_params = params.clone();
+			core_params.skip = position.skip;
+			core_params.cursor = None;
+			match self
+				.get_basic_search_results(core_params, paths, client, o)
+				.await
+			{
+				Ok(results) => repo_results.push((core_index, "core".to_string(), results)),
+				Err(e) => {
+					errors.insert("core".to_string(), format!("{e:?}"));
+				}
+			}
+		}
+
+		while let Some(task) = tasks.join_next().await {
+			let (i, repo, result) = task?;
+			match result {
+				Ok(results) => repo_results.push((i, repo, results)),
+				Err(e) => {
+					errors.insert(repo, format!("{e:?}"));
+				}
+			}
+		}
+
+		// Move each repository to its next page. Repositories that failed are not searched again
+		for (_, repo, results) in &mut repo_results {
+			// The basic packages have their own cursor for their single repository, which we don't need
+			let next_cursor = if repo == "core" {
+				None
+			} else {
+				results.next_cursor.take()
+			};
+			cursor.get_position(repo, params.skip).advance(
+				params.count as usize,
+				results.results.len(),
+				results.total_results,
+				next_cursor,
+			);
+		}
+		for repo in errors.keys() {
+			cursor.get_position(repo, params.skip).finished = true;
+		}
+
+		// Keep the priority order of the repositories
+		repo_results.sort_by_key(|(i, ..)| *i);
+		let stale = repo_results.iter().any(|(.., x)| x.stale);
+		let results = merge_search_results(
+			repo_results
+				.into_iter()
+				.map(|(_, repo, results)| (repo, results))
+				.collect(),
+			params.sort,
+		);
+
+		Ok(AggregatedSearchResults {
+			results,
+			total_results: cursor.get_total(),
+			errors,
+			stale,
+			next_cursor: cursor.get_next(),
 		})
 	}
 
+	/// Searches the basic packages and gets previews for all of the results
+	async fn get_basic_search_results(
+		&self,
+		params: PackageSearchParameters,
+		paths: &Paths,
+		client: &Client,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<PackageSearchResults> {
+		let mut results = self.search(params, Some("core"), paths, client, o).await?;
+		for package in &results.results {
+			let req = Arc::new(PkgRequest::parse(package, PkgRequestSource::Repository));
+			let pkg = self.get(&req, paths, client, o).await?;
+			let meta = pkg.get_metadata(paths, client).await?;
+			let props = pkg.get_properties(paths, client).await?;
+			results.previews.insert(
+				package.clone(),
+				(meta.as_ref().clone(), props.as_ref().clone()),
+			);
+		}
+
+		Ok(results)
+	}
+
 	/// Preloads packages, if it can, from the repos in this registry
 	pub async fn preload_packages(
 		&self,
@@ -464,6 +680,61 @@ impl PkgRegistry {
 		Ok(())
 	}
 
+	/// Gets the full project details for a package. Custom repositories are asked for them first,
+	/// and otherwise they are filled out from the package's metadata
+	pub async fn get_details(
+		&self,
+		req: &ArcPkgReq,
+		paths: &Paths,
+		client: &Client,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<PackageDetails> {
+		if let Some(repo) = req
+			.repository
+			.as_deref()
+			.and_then(|x| self.get_custom_repo(x))
+		{
+			let details = repo
+				.get_details(&req.id, &self.plugins, paths, o)
+				.await
+				.context("Failed to get details from repository")?;
+			if let Some(details) = details {
+				return Ok(details);
+			}
+		}
+
+		let pkg = self.get(req, paths, client, o).await?;
+		let meta = pkg
+			.get_metadata(paths, client)
+			.await
+			.context("Failed to get package metadata")?;
+
+		Ok(PackageDetails::from_metadata(&meta))
+	}
+
+	/// Gets the profile of an author from a repository, if it has author profiles
+	pub async fn get_author(
+		&self,
+		repository: &str,
+		author: &str,
+		paths: &Paths,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<Option<AuthorProfile>> {
+		let Some(repo) = self.get_custom_repo(repository) else {
+			return Ok(None);
+		};
+
+		repo.get_author(author, &self.plugins, paths, o).await
+	}
+
+	/// Gets a custom repository in this registry by its ID
+	fn get_custom_repo(&self, id: &str) -> Option<&CustomPackageRepository> {
+		self.repos.iter().find_map(|x| match x {
+			PackageRepository::Custom(repo) if repo.get_id() == id => Some(repo),
+			_ => None,
+		})
+	}
+
 	/// Gets the repositories stored in this registry in their correct order
 	pub fn get_repos(&self) -> &[PackageRepository] {
 		&self.repos
@@ -474,3 +745,188 @@ impl PkgRegistry {
 		&self.plugins
 	}
 }
+
+/// Sorts packages using their metadata. Packages without the metadata needed for sorting are placed last
+fn sort_by_metadata(
+	packages: &mut [(ArcPkgReq, Option<Arc<PackageMetadata>>)],
+	sort: PackageSortMethod,
+) {
+	// The sort is stable, so packages with equal keys stay in alphabetical order
+	packages.sort_by_key(|(_, meta)| {
+		std::cmp::Reverse(meta.as_deref().and_then(|x| get_sort_key(x, sort)))
+	});
+}
+
+/// Gets the value to sort package metadata by, in descending order
+fn get_sort_key(meta: &PackageMetadata, sort: PackageSortMethod) -> Option<u64> {
+	match sort {
+		PackageSortMethod::Downloads => meta.downloads.map(u64::from),
+		PackageSortMethod::Followers => meta.followers.map(u64::from),
+		PackageSortMethod::Updated => meta.last_updated,
+		// Metadata doesn't have creation dates, so keep the existing order
+		PackageSortMethod::Relevance | PackageSortMethod::Newest => None,
+	}
+}
+
+/// Merges the search results from multiple repositories, in order of priority.
+/// Results are interleaved so that the top results from each repository come first
+fn merge_search_results(
+	results: Vec<(String, PackageSearchResults)>,
+	sort: PackageSortMethod,
+) -> Vec<AggregatedSearchResult> {
+	let max_len = results
+		.iter()
+		.map(|(_, x)| x.results.len())
+		.max()
+		.unwrap_or_default();
+	let mut repos: Vec<_> = results
+		.into_iter()
+		.map(|(repo, x)| (repo, x.results.into_iter(), x.previews))
+		.collect();
+
+	let mut out: Vec<AggregatedSearchResult> = Vec::new();
+	// Map of deduplication keys to indices in the output
+	let mut seen = HashMap::new();
+	for _ in 0..max_len {
+		for (repo, results, previews) in &mut repos {
+			let Some(package) = results.next() else {
+				continue;
+			};
+
+			let preview = previews.remove(&package);
+			let source = SearchResultSource {
+				repository: repo.clone(),
+				package: package.clone(),
+			};
+			let keys = preview
+				.as_ref()
+				.map(|(meta, props)| get_dedup_keys(meta, props))
+				.unwrap_or_default();
+
+			let index = if let Some(index) = keys.iter().find_map(|x| seen.get(x).copied()) {
+				let existing: &mut AggregatedSearchResult = &mut out[index];
+				existing.sources.push(source);
+				if existing.preview.is_none() {
+					existing.preview = preview;
+				}
+				index
+			} else {
+				out.push(AggregatedSearchResult {
+					package,
+					sources: vec![source],
+					preview,
+				});
+				out.len() - 1
+			};
+
+			for key in keys {
+				seen.entry(key).or_insert(index);
+			}
+		}
+	}
+
+	out.sort_by_key(|x| {
+		std::cmp::Reverse(
+			x.preview
+				.as_ref()
+				.and_then(|(meta, _)| get_sort_key(meta, sort)),
+		)
+	});
+
+	out
+}
+
+/// Gets keys that identify the project a package is for, so that the same project from different repositories can be merged
+fn get_dedup_keys(meta: &PackageMetadata, props: &PackageProperties) -> Vec<String> {
+	let mut out = Vec::new();
+	if let Some(id) = &props.modrinth_id {
+		out.push(format!("modrinth:{id}"));
+	}
+	if let Some(id) = &props.curseforge_id {
+		out.push(format!("curseforge:{id}"));
+	}
+	if let Some(id) = &props.smithed_id {
+		out.push(format!("smithed:{id}"));
+	}
+	// Slugs alone are too likely to collide between unrelated projects
+	if let (Some(slug), Some(name)) = (&meta.slug, &meta.name) {
+		out.push(format!(
+			"slug:{}:{}",
+			slug.to_lowercase(),
+			name.to_lowercase()
+		));
+	}
+
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn preview(slug: &str, modrinth_id: Option<&str>) -> (PackageMetadata, PackageProperties) {
+		let meta = PackageMetadata {
+			slug: Some(slug.into()),
+			name: Some(slug.into()),
+			..Default::default()
+		};
+		let props = PackageProperties {
+			modrinth_id: modrinth_id.map(|x| x.into()),
+			..Default::default()
+		};
+		(meta, props)
+	}
+
+	fn results(packages: &[(&str, (PackageMetadata, PackageProperties))]) -> PackageSearchResults {
+		PackageSearchResults {
+			results: packages.iter().map(|x| x.0.to_string()).collect(),
+			total_results: packages.len(),
+			previews: packages
+				.iter()
+				.map(|x| (x.0.to_string(), x.1.clone()))
+				.collect(),
+			stale: false,
+			next_cursor: None,
+		}
+	}
+
+	#[test]
+	fn test_merge_search_results() {
+		let modrinth = results(&[
+			("modrinth:sodium", preview("sodium", Some("AANobbMI"))),
+			("modrinth:lithium", preview("lithium", Some("gvQqBUqZ"))),
+		]);
+		let curseforge = results(&[
+			("curseforge:sodium", preview("sodium", None)),
+			("curseforge:jei", preview("jei", None)),
+		]);
+		let std = results(&[("std:lithium", preview("lithium", Some("gvQqBUqZ")))]);
+
+		let merged = merge_search_results(
+			vec![
+				("std".into(), std),
+				("modrinth".into(), modrinth),
+				("curseforge".into(), curseforge),
+			],
+			PackageSortMethod::Relevance,
+		);
+
+		let packages: Vec<_> = merged.iter().map(|x| x.package.as_str()).collect();
+		assert_eq!(
+			packages,
+			vec!["std:lithium", "modrinth:sodium", "curseforge:jei"]
+		);
+		let sources: Vec<_> = merged[0]
+			.sources
+			.iter()
+			.map(|x| x.repository.as_str())
+			.collect();
+		assert_eq!(sources, vec!["std", "modrinth"]);
+		let sources: Vec<_> = merged[1]
+			.sources
+			.iter()
+			.map(|x| x.repository.as_str())
+			.collect();
+		assert_eq!(sources, vec!["modrinth", "curseforge"]);
+	}
+}
@@ -1,4 +1,9 @@
-use std::{fmt::Display, path::PathBuf, sync::OnceLock};
+use std::{
+	fmt::Display,
+	path::{Path, PathBuf},
+	sync::OnceLock,
+	time::{Duration, SystemTime},
+};
 
 use anyhow::{Context, bail};
 use nitro_core::io::json_from_file;
@@ -20,11 +25,17 @@ pub struct BasicPackageRepository {
 	/// The identifier for the repository
 	pub id: String,
 	location: RepoLocation,
+	/// Mirror URLs to fall back to when the remote location can't be reached
+	mirrors: Vec<String>,
+	/// How long the cached index is used for before it is updated
+	cache_ttl: Option<Duration>,
+	/// The URL that the index was last downloaded from, which may be a mirror
+	active_url: OnceLock<String>,
 	index: OnceLock<RepoIndex>,
 }
 
 /// Location for a BasicPackageRepository
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum RepoLocation {
 	/// A repository on a remote device
 	Remote(String),
@@ -47,10 +58,25 @@ impl BasicPackageRepository {
 		Self {
 			id: id.to_owned(),
 			location,
+			mirrors: Vec::new(),
+			cache_ttl: None,
+			active_url: OnceLock::new(),
 			index: OnceLock::new(),
 		}
 	}
 
+	/// Sets the mirror URLs of this repository, which are tried in order when the main URL can't be reached
+	pub fn with_mirrors(mut self, mirrors: Vec<String>) -> Self {
+		self.mirrors = mirrors;
+		self
+	}
+
+	/// Sets how long the cached index of this repository is used for before it is updated
+	pub fn with_cache_ttl(mut self, cache_ttl: Option<Duration>) -> Self {
+		self.cache_ttl = cache_ttl;
+		self
+	}
+
 	/// The cached path of the index
 	pub fn get_path(&self, paths: &Paths) -> PathBuf {
 		paths.pkg_index_cache.join(format!("{}.json", &self.id))
@@ -61,6 +87,15 @@ impl BasicPackageRepository {
 		&self.location
 	}
 
+	/// Gets the location that packages are downloaded from, which is the mirror that the index was
+	/// downloaded from if the main URL couldn't be reached
+	pub fn get_active_location(&self) -> RepoLocation {
+		match (&self.location, self.active_url.get()) {
+			(RepoLocation::Remote(..), Some(url)) => RepoLocation::Remote(url.clone()),
+			_ => self.location.clone(),
+		}
+	}
+
 	/// Update the currently cached index file and return the contents
 	pub async fn sync(&self, paths: &Paths, client: &Client) -> anyhow::Result<()> {
 		let bytes = match &self.location {
@@ -71,14 +106,12 @@ impl BasicPackageRepository {
 				bytes
 			}
 			RepoLocation::Remote(url) => {
-				let bytes = download::bytes(get_index_url(url), client)
-					.await
-					.context("Failed to download index")?;
+				let bytes = self.download_index(url, client).await?;
 				tokio::fs::write(self.get_path(paths), &bytes)
 					.await
 					.context("Failed to write index to cached file")?;
 
-				bytes.to_vec()
+				bytes
 			}
 		};
 
@@ -87,6 +120,23 @@ impl BasicPackageRepository {
 		Ok(())
 	}
 
+	/// Downloads the index from the main URL, falling back to the mirrors in order
+	async fn download_index(&self, url: &str, client: &Client) -> anyhow::Result<Vec<u8>> {
+		let mut result = Err(anyhow::anyhow!("No URLs to download from"));
+		for url in std::iter::once(url).chain(self.mirrors.iter().map(String::as_str)) {
+			result = download::bytes(get_index_url(url), client)
+				.await
+				.map(|x| x.to_vec())
+				.with_context(|| format!("Failed to download index from {url}"));
+			if result.is_ok() {
+				let _ = self.active_url.set(url.to_string());
+				break;
+			}
+		}
+
+		result
+	}
+
 	/// Make sure that the repository index is downloaded and returns it
 	pub async fn ensure_index<'this>(
 		&self,
@@ -96,7 +146,8 @@ impl BasicPackageRepository {
 	) -> anyhow::Result<()> {
 		if self.index.get().is_none() {
 			let path = self.get_path(paths);
-			if path.exists() {
+			let is_expired = self.cache_ttl.is_some_and(|ttl| is_older_than(&path, ttl));
+			if path.exists() && !is_expired {
 				let result = json_from_file(&path);
 				match result {
 					Ok(index) => {
@@ -108,6 +159,16 @@ impl BasicPackageRepository {
 							.context("Failed to sync index")?;
 					}
 				};
+			} else if path.exists() {
+				// Keep using the expired index if the repository can't be reached
+				if let Err(e) = self.sync(paths, client).await {
+					o.display(MessageContents::Warning(format!(
+						"Failed to update the index of repository '{}', so the cached one will be used: {e}",
+						self.id
+					)));
+					let index = json_from_file(&path).context("Failed to read cached index")?;
+					let _ = self.index.set(index);
+				}
 			} else {
 				self.sync(paths, client)
 					.await
@@ -149,7 +210,7 @@ impl BasicPackageRepository {
 		self.ensure_index(paths, client, o).await?;
 		let index = self.index.get().unwrap();
 		if let Some(entry) = index.packages.get(id) {
-			let location = get_package_location(entry, &self.location, &self.id)
+			let location = get_package_location(entry, &self.get_active_location(), &self.id)
 				.context("Failed to get location of package")?;
 			return Ok(Some(RepoQueryResult {
 				location,
@@ -202,6 +263,17 @@ impl BasicPackageRepository {
 	}
 }
 
+/// Checks whether a file was last modified longer ago than a duration
+fn is_older_than(path: &Path, duration: Duration) -> bool {
+	let Ok(modified) = path.metadata().and_then(|x| x.modified()) else {
+		return false;
+	};
+
+	SystemTime::now()
+		.duration_since(modified)
+		.is_ok_and(|x| x > duration)
+}
+
 /// Gets the location of a package from it's repository entry in line with url and path rules
 pub fn get_package_location(
 	entry: &RepoPkgEntry,
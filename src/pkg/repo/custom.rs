@@ -1,14 +1,16 @@
 use std::{collections::HashSet, sync::Arc};
 
 use anyhow::Context;
+use nitro_pkg::details::{AuthorProfile, PackageDetails};
 use nitro_pkg::{PackageSearchResults, repo::RepoMetadata};
 use nitro_plugin::{
 	hook::call::HookHandle,
 	hook::hooks::{
-		PreloadPackages, PreloadPackagesArg, QueryCustomPackageRepository,
-		QueryCustomPackageRepositoryArg, SearchCustomPackageRepository,
-		SearchCustomPackageRepositoryArg, SyncCustomPackageRepository,
-		SyncCustomPackageRepositoryArg,
+		GetCustomPackageAuthor, GetCustomPackageAuthorArg, GetCustomPackageDetails,
+		GetCustomPackageDetailsArg, PreloadPackages, PreloadPackagesArg,
+		QueryCustomPackageRepository, QueryCustomPackageRepositoryArg,
+		SearchCustomPackageRepository, SearchCustomPackageRepositoryArg,
+		SyncCustomPackageRepository, SyncCustomPackageRepositoryArg,
 	},
 };
 use nitro_shared::{
@@ -21,6 +23,7 @@ use crate::{io::paths::Paths, pkg::PkgLocation, plugin::PluginManager};
 use super::RepoQueryResult;
 
 /// A custom package repository from a plugin
+#[derive(Clone)]
 pub struct CustomPackageRepository {
 	/// The ID of this repository
 	id: String,
@@ -169,6 +172,54 @@ impl CustomPackageRepository {
 		result.result(o).await
 	}
 
+	/// Gets the full project details for a package from this repository
+	pub async fn get_details(
+		&self,
+		package: &str,
+		plugins: &PluginManager,
+		paths: &Paths,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<Option<PackageDetails>> {
+		let arg = GetCustomPackageDetailsArg {
+			repository: self.id.clone(),
+			package: package.to_string(),
+		};
+		let result = plugins
+			.call_hook_on_plugin(GetCustomPackageDetails, &self.plugin, &arg, paths, o)
+			.await
+			.context("Failed to call details hook")?;
+
+		let Some(result) = result else {
+			return Ok(None);
+		};
+
+		result.result(o).await
+	}
+
+	/// Gets the profile of an author from this repository
+	pub async fn get_author(
+		&self,
+		author: &str,
+		plugins: &PluginManager,
+		paths: &Paths,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<Option<AuthorProfile>> {
+		let arg = GetCustomPackageAuthorArg {
+			repository: self.id.clone(),
+			author: author.to_string(),
+		};
+		let result = plugins
+			.call_hook_on_plugin(GetCustomPackageAuthor, &self.plugin, &arg, paths, o)
+			.await
+			.context("Failed to call author hook")?;
+
+		let Some(result) = result else {
+			return Ok(None);
+		};
+
+		result.result(o).await
+	}
+
 	/// Gets the ID for this repository
 	pub fn get_id(&self) -> &str {
 		&self.id
@@ -2,9 +2,11 @@ use std::{
 	collections::HashMap,
 	env::consts::{ARCH, OS},
 	io::Cursor,
+	path::PathBuf,
 };
 
 use anyhow::{Context, bail};
+use nitro_core::io::{json_from_file, json_to_file_replace};
 use nitro_core::net::download;
 use nitro_net::github::{GithubAsset, get_github_releases};
 use nitro_plugin::plugin::PluginMetadata;
@@ -54,6 +56,67 @@ pub async fn get_verified_plugins(
 	Ok(list)
 }
 
+/// Where an installed plugin was downloaded from. Plugins installed from a file don't have one
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PluginSource {
+	/// The organization / user that owns the repo the plugin was downloaded from
+	pub github_owner: String,
+	/// The name of the GitHub repo the plugin was downloaded from
+	pub github_repo: String,
+}
+
+impl PluginSource {
+	/// Checks if this source is an official Nitrolaunch repo
+	pub fn is_official(&self) -> bool {
+		self.github_owner == "Nitrolaunch"
+	}
+}
+
+/// Gets the path to the file storing the sources of installed plugins. This is kept
+/// outside of the plugins directory so that plugins can't write their own source
+fn get_plugin_sources_path(paths: &Paths) -> PathBuf {
+	paths.internal.join("plugin_sources.json")
+}
+
+/// Reads the sources of all installed plugins, by plugin ID
+fn read_plugin_sources(paths: &Paths) -> anyhow::Result<HashMap<String, PluginSource>> {
+	let path = get_plugin_sources_path(paths);
+	if path.exists() {
+		json_from_file(path).context("Failed to read plugin sources")
+	} else {
+		Ok(HashMap::new())
+	}
+}
+
+/// Gets the source that an installed plugin was downloaded from, if it was installed from the verified list
+pub fn get_plugin_source(plugin: &str, paths: &Paths) -> Option<PluginSource> {
+	read_plugin_sources(paths).ok()?.remove(plugin)
+}
+
+/// Sets or clears the recorded source of an installed plugin
+pub(crate) fn set_plugin_source(
+	plugin: &str,
+	source: Option<PluginSource>,
+	paths: &Paths,
+) -> anyhow::Result<()> {
+	let mut sources = read_plugin_sources(paths)?;
+	let changed = match source {
+		Some(source) => sources.insert(plugin.to_string(), source.clone()) != Some(source),
+		None => sources.remove(plugin).is_some(),
+	};
+	if changed {
+		json_to_file_replace(get_plugin_sources_path(paths), &sources)
+			.context("Failed to write plugin sources")?;
+	}
+
+	Ok(())
+}
+
+/// Checks if an installed plugin was downloaded from an official Nitrolaunch repo
+pub fn is_official_install(plugin: &str, paths: &Paths) -> bool {
+	get_plugin_source(plugin, paths).is_some_and(|x| x.is_official())
+}
+
 impl VerifiedPlugin {
 	/// Gets the list of candidate GitHub assets for this plugin, ordered from newest to oldest
 	pub async fn get_candidate_assets(
@@ -146,6 +209,13 @@ impl VerifiedPlugin {
 			.await
 			.context("Failed to install downloaded plugin")?;
 
+		let source = PluginSource {
+			github_owner: self.github_owner.clone(),
+			github_repo: self.github_repo.clone(),
+		};
+		set_plugin_source(&self.id, Some(source), paths)
+			.context("Failed to record where the plugin was installed from")?;
+
 		Ok(())
 	}
 }
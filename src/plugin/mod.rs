@@ -10,9 +10,12 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::config::plugin::{PluginConfig, PluginsConfig};
+use crate::config::secrets::{
+	contains_encrypted, decrypt_json, encrypt_json_field, get_passphrase,
+};
 use crate::io::paths::Paths;
 use anyhow::{Context, bail};
-use nitro_core::io::{json_from_file, json_to_file_pretty};
+use nitro_core::io::{json_from_file, json_to_file_atomic};
 use nitro_plugin::PluginPaths;
 use nitro_plugin::hook::call::{HookHandle, HookHandles};
 use nitro_plugin::hook::wasm::loader::WASMLoader;
@@ -49,7 +52,7 @@ impl PluginManager {
 			json_from_file(path).context("Failed to load plugin config from file")
 		} else {
 			let out = PluginsConfig::default();
-			json_to_file_pretty(path, &out)
+			json_to_file_atomic(path, &out)
 				.context("Failed to write default plugin config to file")?;
 
 			Ok(out)
@@ -89,7 +92,7 @@ impl PluginManager {
 		let path = Self::get_config_path(paths);
 		if !path.exists() {
 			let out = PluginsConfig::default();
-			json_to_file_pretty(path, &out)
+			json_to_file_atomic(path, &out)
 				.context("Failed to write default plugin config to file")?;
 		}
 
@@ -250,6 +253,9 @@ impl PluginManager {
 			std::fs::remove_dir_all(dir_path).context("Failed to remove plugin directory")?;
 		}
 
+		// The plugin's files are gone, so where they came from no longer applies
+		install::set_plugin_source(plugin, None, paths).context("Failed to clear plugin source")?;
+
 		Ok(())
 	}
 
@@ -323,7 +329,7 @@ impl PluginManager {
 		let config_path = Self::get_config_path(paths);
 		let mut config = Self::open_config(paths).context("Failed to open plugin configuration")?;
 		config.plugins.insert(plugin.to_string());
-		json_to_file_pretty(config_path, &config).context("Failed to write to config file")
+		json_to_file_atomic(config_path, &config).context("Failed to write to config file")
 	}
 
 	/// Disables a plugin
@@ -331,7 +337,72 @@ impl PluginManager {
 		let config_path = Self::get_config_path(paths);
 		let mut config = Self::open_config(paths).context("Failed to open plugin configuration")?;
 		config.plugins.remove(plugin);
-		json_to_file_pretty(config_path, &config).context("Failed to write to config file")
+		json_to_file_atomic(config_path, &config).context("Failed to write to config file")
+	}
+
+	/// Encrypts a field in the custom config of a plugin in plugins.json with the launcher passphrase.
+	/// The field is a dot-separated path to a string, like `token`
+	pub async fn encrypt_config_field(
+		plugin: &str,
+		field: &str,
+		paths: &Paths,
+		o: &mut impl NitroOutput,
+	) -> anyhow::Result<()> {
+		let config_path = Self::get_config_path(paths);
+		let mut config = Self::open_config(paths).context("Failed to open plugin configuration")?;
+
+		// Check against values that are already encrypted for any plugin, so that they all share a passphrase
+		let all_configs =
+			serde_json::to_value(&config.config).context("Failed to serialize plugin configs")?;
+		let passphrase = get_passphrase(Some(&all_configs), o).await?;
+
+		let custom_config = config
+			.config
+			.get_mut(plugin)
+			.with_context(|| format!("Plugin '{plugin}' does not have any configuration"))?;
+		encrypt_json_field(custom_config, field, &passphrase)?;
+
+		json_to_file_atomic(config_path, &config).context("Failed to write to config file")
+	}
+
+	/// Gets the first custom plugin config that contains encrypted values, if there is one
+	pub async fn get_encrypted_config(&self) -> Option<serde_json::Value> {
+		let inner = self.inner.lock().await;
+		inner
+			.configs
+			.iter()
+			.filter_map(|x| x.custom_config.as_ref())
+			.find(|x| contains_encrypted(x))
+			.cloned()
+	}
+
+	/// Decrypts the encrypted values in the custom configs of plugins so that they can be used by
+	/// the plugins. The values are only decrypted in memory and are only given to the plugin they belong to
+	pub async fn decrypt_configs(&self, passphrase: &str) -> anyhow::Result<()> {
+		let mut inner = self.inner.lock().await;
+		let inner = &mut *inner;
+		for config in &inner.configs {
+			let Some(custom_config) = &config.custom_config else {
+				continue;
+			};
+			if !contains_encrypted(custom_config) {
+				continue;
+			}
+
+			let mut custom_config = custom_config.clone();
+			decrypt_json(&mut custom_config, passphrase)
+				.with_context(|| format!("Failed to decrypt config for plugin '{}'", config.id))?;
+
+			if let Some(plugin) = inner
+				.manager
+				.iter_plugins_mut()
+				.find(|x| x.get_id() == &config.id)
+			{
+				plugin.set_custom_config(custom_config)?;
+			}
+		}
+
+		Ok(())
 	}
 
 	/// Call a plugin hook on the manager and collects the results into a Vec